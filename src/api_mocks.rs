@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Mock servers for the API specs of the project.
+//!
+//! OpenAPI/Swagger documents (YAML or JSON with a top-level `openapi` or
+//! `swagger` key) are served by Prism, which answers every operation with the
+//! examples of the spec (or data generated from its schemas) and validates the
+//! requests. Protobuf files declaring a `service` are served together by
+//! GripMock, whose responses are stubbed through its admin API. The specs are
+//! mounted read-only from the project, so the mocks follow them on restart.
+//! GraphQL schemas are found by the same scan and go to the GraphQL sandbox
+//! (see [`crate::graphql`]) instead of a mock.
+use std::fs;
+use std::path::Path;
+
+use crate::dev_services::DockerService;
+
+const PRISM_IMAGE: &str = "stoplight/prism:5";
+const GRIPMOCK_IMAGE: &str = "tkpd/gripmock:latest";
+/// First host port of the HTTP mocks; each further spec takes the next one.
+const PRISM_PORT: u16 = 4010;
+/// gRPC server and stub admin API of GripMock.
+const GRIPMOCK_PORTS: [u16; 2] = [4770, 4771];
+/// Specs larger than this are generated bundles, not hand-written specs.
+const MAX_SPEC_BYTES: u64 = 5 * 1024 * 1024;
+
+const SKIP_DIRS: &[&str] = &["node_modules", "target", "build", "dist", "vendor"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecKind {
+    OpenApi,
+    Proto,
+    GraphQl,
+}
+
+/// An API spec, with its path relative to the project (forward slashes).
+#[derive(Clone, Debug)]
+pub struct Spec {
+    pub kind: SpecKind,
+    pub file: String,
+}
+
+fn is_openapi(ext: &str, content: &str) -> bool {
+    match ext {
+        "yaml" | "yml" => content
+            .lines()
+            .any(|l| l.starts_with("openapi:") || l.starts_with("swagger:")),
+        "json" => {
+            let head: String = content.chars().take(4096).collect();
+            head.trim_start().starts_with('{')
+                && (head.contains("\"openapi\"") || head.contains("\"swagger\""))
+        }
+        _ => false,
+    }
+}
+
+/// Schema (SDL) rather than operations (`query`, `mutation`, `fragment`).
+fn is_graphql_schema(content: &str) -> bool {
+    content.lines().any(|l| {
+        let l = l.trim_start();
+        l.starts_with("type ") || l.starts_with("extend type ") || l.starts_with("schema ")
+    })
+}
+
+fn is_grpc_proto(content: &str) -> bool {
+    content
+        .lines()
+        .any(|l| l.trim_start().starts_with("service "))
+}
+
+/// OpenAPI, gRPC and GraphQL specs under `project_dir`, sorted by path.
+pub fn scan(project_dir: &Path) -> Vec<Spec> {
+    let walker = ignore::WalkBuilder::new(project_dir)
+        .filter_entry(|e| {
+            !(e.file_type().is_some_and(|t| t.is_dir())
+                && e.file_name()
+                    .to_str()
+                    .is_some_and(|n| SKIP_DIRS.contains(&n)))
+        })
+        .build();
+    let mut specs = Vec::new();
+    for entry in walker.flatten() {
+        let path = entry.path();
+        let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+            continue;
+        };
+        let ext = ext.to_ascii_lowercase();
+        if !matches!(
+            ext.as_str(),
+            "yaml" | "yml" | "json" | "proto" | "graphql" | "graphqls" | "gql"
+        ) || entry.metadata().map_or(true, |m| m.len() > MAX_SPEC_BYTES)
+        {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        let kind = match ext.as_str() {
+            "proto" => is_grpc_proto(&content).then_some(SpecKind::Proto),
+            "graphql" | "graphqls" | "gql" => {
+                is_graphql_schema(&content).then_some(SpecKind::GraphQl)
+            }
+            _ => is_openapi(&ext, &content).then_some(SpecKind::OpenApi),
+        };
+        if let Some(kind) = kind {
+            let file = path.strip_prefix(project_dir).unwrap_or(path);
+            let file = file.display().to_string().replace('\\', "/");
+            tracing::debug!(file, ?kind, "especificação de API detectada");
+            specs.push(Spec { kind, file });
+        }
+    }
+    specs.sort_by(|a, b| a.file.cmp(&b.file));
+    specs
+}
+
+/// Service name fragment for a spec file (`api/orders-v1.yaml` -> `orders-v1`).
+fn slug(file: &str) -> String {
+    let stem = Path::new(file)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("api");
+    let slug: String = stem
+        .to_ascii_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    slug.trim_matches('-').to_string()
+}
+
+fn mock(image: &str, ports: Vec<u16>, volumes: Vec<String>, command: String) -> DockerService {
+    DockerService {
+        image: image.to_string(),
+        env: Default::default(),
+        ports,
+        volumes,
+        command: Some(command),
+        profiles: vec!["mocks".to_string()],
+        ..Default::default()
+    }
+}
+
+/// Mock services for `specs`: `api-mock` (one per OpenAPI spec, suffixed by the
+/// file name when there are several) and `grpc-mock` for all the protos.
+pub fn services(specs: &[Spec]) -> Vec<(String, DockerService)> {
+    let openapi: Vec<&Spec> = specs
+        .iter()
+        .filter(|s| s.kind == SpecKind::OpenApi)
+        .collect();
+    let protos: Vec<&Spec> = specs.iter().filter(|s| s.kind == SpecKind::Proto).collect();
+
+    let mut services = Vec::new();
+    for (i, spec) in openapi.iter().enumerate() {
+        let mut name = "api-mock".to_string();
+        if openapi.len() > 1 {
+            name = format!("{name}-{}", slug(&spec.file));
+            if services.iter().any(|(n, _)| *n == name) {
+                name = format!("{name}-{}", i + 1);
+            }
+        }
+        let port = PRISM_PORT + i as u16;
+        let target = format!("/specs/{}", spec.file);
+        services.push((
+            name,
+            mock(
+                PRISM_IMAGE,
+                vec![port],
+                vec![format!("../{}:{target}:ro", spec.file)],
+                format!("mock -h 0.0.0.0 -p {port} {target}"),
+            ),
+        ));
+    }
+    if !protos.is_empty() {
+        let targets: Vec<String> = protos
+            .iter()
+            .map(|s| format!("/proto/{}", s.file))
+            .collect();
+        services.push((
+            "grpc-mock".to_string(),
+            mock(
+                GRIPMOCK_IMAGE,
+                GRIPMOCK_PORTS.to_vec(),
+                protos
+                    .iter()
+                    .zip(&targets)
+                    .map(|(spec, target)| format!("../{}:{target}:ro", spec.file))
+                    .collect(),
+                format!("--imports=/proto {}", targets.join(" ")),
+            ),
+        ));
+    }
+    services
+}
+
+/// Report line of a mock service.
+pub fn info(name: &str, svc: &DockerService) -> Option<String> {
+    let port = svc.ports.first()?;
+    if name == "grpc-mock" {
+        return Some(format!(
+            "gRPC: localhost:{port}, stubs: http://localhost:{}/add",
+            svc.ports.get(1).unwrap_or(&GRIPMOCK_PORTS[1])
+        ));
+    }
+    if !name.starts_with("api-mock") {
+        return None;
+    }
+    let spec = svc
+        .command
+        .as_deref()?
+        .rsplit(' ')
+        .next()?
+        .trim_start_matches("/specs/");
+    Some(format!("mock de {spec}: http://localhost:{port}"))
+}
@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! CPU architecture of the machine and images without a native build for it.
+//!
+//! Most images are multi-arch, but a few common ones are published for
+//! linux/amd64 only and fail on Apple Silicon and other arm64 hosts with "no
+//! matching manifest". On arm64, `dev-services run` swaps such an image for a
+//! multi-arch build of the same software when one exists (same tag, other
+//! repository) and otherwise adds `platform: linux/amd64` to the service, so
+//! Docker runs it under emulation; `dx doctor` and the analyzer report warn
+//! about the emulated ones. The changes go to `.dx/docker-compose.arch.yml`,
+//! passed to Compose with the manifest, so the generated manifest is the same
+//! on every machine. `DX_ARCH` (`amd64` or `arm64`) overrides the detected
+//! architecture, e.g. when the engine runs on another machine.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{DxResult, IoContext};
+
+/// Overrides the architecture dx detects for this machine.
+pub const ARCH_ENV: &str = "DX_ARCH";
+
+/// Platform amd64-only images are emulated with.
+pub const AMD64_PLATFORM: &str = "linux/amd64";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Arch {
+    Amd64,
+    Arm64,
+    Other,
+}
+
+impl Arch {
+    /// Architecture of this machine (or `DX_ARCH`).
+    pub fn host() -> Arch {
+        let name = std::env::var(ARCH_ENV)
+            .ok()
+            .filter(|a| !a.is_empty())
+            .unwrap_or_else(|| std::env::consts::ARCH.to_string());
+        match name.as_str() {
+            "x86_64" | "amd64" => Arch::Amd64,
+            "aarch64" | "arm64" => Arch::Arm64,
+            _ => Arch::Other,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Arch::Amd64 => "amd64",
+            Arch::Arm64 => "arm64",
+            Arch::Other => std::env::consts::ARCH,
+        }
+    }
+}
+
+/// An image published for linux/amd64 only.
+struct Amd64Only {
+    repository: &'static str,
+    /// Tags it applies to, by prefix (`""`: every tag)
+    tags: &'static str,
+    /// Repository of a multi-arch build with the same tags
+    multi_arch: Option<&'static str>,
+}
+
+const AMD64_ONLY: &[Amd64Only] = &[
+    Amd64Only {
+        repository: "postgis/postgis",
+        tags: "",
+        multi_arch: Some("imresamu/postgis"),
+    },
+    Amd64Only {
+        repository: "mysql",
+        tags: "5",
+        multi_arch: None,
+    },
+    Amd64Only {
+        repository: "mcr.microsoft.com/mssql/server",
+        tags: "",
+        multi_arch: None,
+    },
+    Amd64Only {
+        repository: "ibmcom/db2",
+        tags: "",
+        multi_arch: None,
+    },
+    Amd64Only {
+        repository: "jboss/keycloak",
+        tags: "",
+        multi_arch: None,
+    },
+    Amd64Only {
+        repository: "wurstmeister/kafka",
+        tags: "",
+        multi_arch: None,
+    },
+    Amd64Only {
+        repository: "wurstmeister/zookeeper",
+        tags: "",
+        multi_arch: None,
+    },
+];
+
+/// `image` as (repository, tag), without Docker Hub's implicit prefixes.
+fn split(image: &str) -> (&str, &str) {
+    let image = image.split('@').next().unwrap_or(image);
+    let (repository, tag) = match image.rsplit_once(':') {
+        Some((repository, tag)) if !tag.contains('/') => (repository, tag),
+        _ => (image, "latest"),
+    };
+    let repository = repository.strip_prefix("docker.io/").unwrap_or(repository);
+    let repository = repository.strip_prefix("library/").unwrap_or(repository);
+    (repository, tag)
+}
+
+fn lookup(image: &str) -> Option<&'static Amd64Only> {
+    let (repository, tag) = split(image);
+    AMD64_ONLY
+        .iter()
+        .find(|i| i.repository == repository && tag.starts_with(i.tags))
+}
+
+/// Whether `image` has no build for `arch` and runs emulated there.
+pub fn emulated(image: &str, arch: Arch) -> bool {
+    arch == Arch::Arm64 && lookup(image).is_some_and(|i| i.multi_arch.is_none())
+}
+
+/// The multi-arch build of `image` to use on `arch`, when `image` has none
+/// for it.
+pub fn native(image: &str, arch: Arch) -> Option<String> {
+    if arch != Arch::Arm64 {
+        return None;
+    }
+    let multi_arch = lookup(image)?.multi_arch?;
+    Some(format!("{multi_arch}:{}", split(image).1))
+}
+
+/// Write the compose file fitting the images of the manifest at
+/// `compose_path` to `arch`: multi-arch builds where there are, `platform:
+/// linux/amd64` for the rest of the amd64-only ones. `None`, and no file, when
+/// every image has a build for `arch`.
+pub fn write_override(
+    project_dir: &Path,
+    compose_path: &Path,
+    arch: Arch,
+) -> DxResult<Option<PathBuf>> {
+    let yaml = fs::read_to_string(compose_path)
+        .context(format!("Erro ao ler {}", compose_path.display()))?;
+    let mut services = String::new();
+    for service in crate::dev_services::manifest_services(&yaml) {
+        if let Some(image) = native(&service.image, arch) {
+            services.push_str(&format!("  {}:\n    image: {image}\n", service.name));
+        } else if emulated(&service.image, arch) {
+            services.push_str(&format!(
+                "  {}:\n    platform: {AMD64_PLATFORM}\n",
+                service.name
+            ));
+        }
+    }
+    let path = project_dir.join(".dx").join(crate::compose::ARCH_FILE);
+    if services.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path).context(format!("Erro ao remover {}", path.display()))?;
+        }
+        return Ok(None);
+    }
+    let content = format!(
+        "# Gerado pelo dx (dev-services run em {}); não edite.\nservices:\n{services}",
+        arch.name()
+    );
+    fs::write(&path, content).context(format!("Erro ao salvar {}", path.display()))?;
+    Ok(Some(path))
+}
@@ -0,0 +1,378 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Idle auto-stop of the Dev Services (`dx dev-services run --auto-stop 2h`).
+//!
+//! `run` starts the containers with the `dev.dx.auto-stop` and `dev.dx.project`
+//! labels (from `.dx/auto-stop/compose.yml`, passed as an extra compose file)
+//! and spawns a background watchdog (`dx dev-services watchdog`). The watchdog
+//! counts as activity any established TCP connection to a published port of the
+//! manifest; after the configured time without any, it stops the labeled
+//! containers and exits. A later `run` replaces the watchdog (resetting the
+//! timer) or, without auto-stop, retires it.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DxError, DxResult, IoContext};
+
+/// Label holding the idle time, on every container started with auto-stop.
+pub const LABEL: &str = "dev.dx.auto-stop";
+/// Label holding the project directory, so each watchdog stops only its own containers.
+pub const PROJECT_LABEL: &str = "dev.dx.project";
+
+/// Longest wait between two checks.
+const MAX_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct State {
+    /// Process id of the current watchdog
+    pid: u32,
+    idle_secs: u64,
+    /// Host ports watched for connections
+    ports: Vec<u16>,
+    /// Seconds since the Unix epoch
+    started_at: u64,
+}
+
+fn auto_stop_dir(project_dir: &Path) -> PathBuf {
+    project_dir.join(".dx").join("auto-stop")
+}
+
+fn state_path(project_dir: &Path) -> PathBuf {
+    auto_stop_dir(project_dir).join("state.json")
+}
+
+/// Extra compose file with the auto-stop labels.
+pub fn labels_path(project_dir: &Path) -> PathBuf {
+    auto_stop_dir(project_dir).join("compose.yml")
+}
+
+/// `2h`, `30m`, `1h30m`, `45s`, `1d`; `off` (or `0`) disables auto-stop.
+pub fn parse_duration(text: &str) -> DxResult<Option<Duration>> {
+    let text = text.trim().to_ascii_lowercase();
+    if matches!(text.as_str(), "off" | "0" | "false" | "") {
+        return Ok(None);
+    }
+    let invalid = || {
+        DxError::InvalidInput(format!(
+            "Duração inválida para auto-stop: '{text}' (ex.: 2h, 30m, 1h30m, 1d ou off)."
+        ))
+    };
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            _ => return Err(invalid()),
+        };
+        let value: u64 = number.parse().map_err(|_| invalid())?;
+        total += value * unit;
+        number.clear();
+    }
+    if !number.is_empty() || total == 0 {
+        return Err(invalid());
+    }
+    Ok(Some(Duration::from_secs(total)))
+}
+
+/// "2h", "1h30m", "45s".
+pub fn format_duration(duration: Duration) -> String {
+    let mut secs = duration.as_secs();
+    let mut text = String::new();
+    for (unit, size) in [("d", 86400), ("h", 3600), ("m", 60), ("s", 1)] {
+        if secs >= size {
+            text.push_str(&format!("{}{unit}", secs / size));
+            secs %= size;
+        }
+    }
+    if text.is_empty() {
+        text.push_str("0s");
+    }
+    text
+}
+
+/// Idle time for `dx dev-services run`: `--auto-stop` or `[dev_services] auto_stop`.
+pub fn resolve(project_dir: &Path, flag: Option<String>) -> DxResult<Option<Duration>> {
+    match flag {
+        Some(value) => parse_duration(&value),
+        None => match crate::settings::load(project_dir)?.dev_services.auto_stop {
+            Some(value) => parse_duration(&value),
+            None => Ok(None),
+        },
+    }
+}
+
+/// Write the compose file adding the auto-stop labels to every service of the manifest.
+pub fn write_labels(project_dir: &Path, compose_path: &Path, idle: Duration) -> DxResult<PathBuf> {
+    let yaml = fs::read_to_string(compose_path)
+        .context(format!("Erro ao ler {}", compose_path.display()))?;
+    let project = project_label(project_dir);
+    let mut content =
+        String::from("# Gerado pelo dx (dev-services run --auto-stop); não edite.\nservices:\n");
+    for name in crate::dev_services::manifest_service_names(&yaml) {
+        content.push_str(&format!(
+            "  {name}:\n    labels:\n      {LABEL}: \"{}\"\n      {PROJECT_LABEL}: \"{}\"\n",
+            format_duration(idle),
+            project.replace('\\', "\\\\").replace('"', "\\\"")
+        ));
+    }
+    let path = labels_path(project_dir);
+    let dir = auto_stop_dir(project_dir);
+    fs::create_dir_all(&dir).context(format!("Erro ao criar {}", dir.display()))?;
+    fs::write(&path, content).context(format!("Erro ao salvar {}", path.display()))?;
+    Ok(path)
+}
+
+fn project_label(project_dir: &Path) -> String {
+    project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf())
+        .display()
+        .to_string()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn read_state(project_dir: &Path) -> Option<State> {
+    let content = fs::read_to_string(state_path(project_dir)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Start the watchdog for the containers just started, replacing any previous one.
+pub fn start(project_dir: &Path, compose_path: &Path, idle: Duration) -> DxResult<()> {
+    let yaml = fs::read_to_string(compose_path).unwrap_or_default();
+    let mut ports: Vec<u16> = crate::dev_services::manifest_services(&yaml)
+        .iter()
+        .flat_map(|s| s.ports.iter().map(|(host, _)| *host))
+        .collect();
+    ports.sort_unstable();
+    ports.dedup();
+
+    let exe = std::env::current_exe().map_err(|e| {
+        DxError::Tool(format!(
+            "Não foi possível localizar o executável do dx: {e}"
+        ))
+    })?;
+    let dir = auto_stop_dir(project_dir);
+    fs::create_dir_all(&dir).context(format!("Erro ao criar {}", dir.display()))?;
+    let log_path = dir.join("watchdog.log");
+    let log =
+        fs::File::create(&log_path).context(format!("Erro ao criar {}", log_path.display()))?;
+    let log_err = log
+        .try_clone()
+        .context(format!("Erro ao abrir {}", log_path.display()))?;
+
+    let mut command = Command::new(exe);
+    command
+        .args(["dev-services", "watchdog"])
+        .arg(project_dir)
+        .stdin(Stdio::null())
+        .stdout(log)
+        .stderr(log_err);
+    detach(&mut command);
+    let child = command
+        .spawn()
+        .map_err(|e| DxError::Tool(format!("Erro ao iniciar o watchdog de auto-stop: {e}")))?;
+
+    // The watchdog exits as soon as the state names another process, so
+    // writing it also retires the previous one
+    let state = State {
+        pid: child.id(),
+        idle_secs: idle.as_secs(),
+        ports,
+        started_at: now(),
+    };
+    let path = state_path(project_dir);
+    let json = serde_json::to_string_pretty(&state).unwrap_or_default();
+    fs::write(&path, format!("{json}\n")).context(format!("Erro ao salvar {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn detach(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    // Own process group: Ctrl-C in the terminal doesn't reach the watchdog
+    command.process_group(0);
+}
+
+#[cfg(windows)]
+fn detach(command: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    const DETACHED_PROCESS: u32 = 0x0000_0008;
+    const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+    command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+}
+
+/// Retire the watchdog and labels of a previous `run --auto-stop`.
+pub fn disable(project_dir: &Path) {
+    for path in [state_path(project_dir), labels_path(project_dir)] {
+        if path.exists()
+            && let Err(e) = fs::remove_file(&path)
+        {
+            tracing::debug!(path = %path.display(), "não foi possível remover: {e}");
+        }
+    }
+}
+
+/// Host ports with an established TCP connection (either end), from
+/// `/proc/net/tcp*` on Linux or `netstat -an` elsewhere.
+fn connected_ports() -> Vec<u16> {
+    let mut ports = Vec::new();
+    let mut from_proc = false;
+    for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        let Ok(content) = fs::read_to_string(table) else {
+            continue;
+        };
+        from_proc = true;
+        for line in content.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // sl local_address rem_address st ...; 01 = ESTABLISHED
+            if fields.len() < 4 || fields[3] != "01" {
+                continue;
+            }
+            for address in &fields[1..3] {
+                if let Some(port) = address
+                    .rsplit(':')
+                    .next()
+                    .and_then(|p| u16::from_str_radix(p, 16).ok())
+                {
+                    ports.push(port);
+                }
+            }
+        }
+    }
+    if from_proc {
+        return ports;
+    }
+    let Ok(output) = Command::new("netstat")
+        .arg("-an")
+        .stdin(Stdio::null())
+        .output()
+    else {
+        return ports;
+    };
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if !line.contains("ESTABLISHED") {
+            continue;
+        }
+        // `127.0.0.1:5432`, `[::1]:5432` or `127.0.0.1.5432` (BSD)
+        for address in line.split_whitespace() {
+            if let Some(port) = address
+                .rsplit([':', '.'])
+                .next()
+                .and_then(|p| p.parse::<u16>().ok())
+                && address.len() > port.to_string().len()
+            {
+                ports.push(port);
+            }
+        }
+    }
+    ports
+}
+
+/// Running containers started by `run --auto-stop` for `project_dir`.
+fn labeled_containers(project_dir: &Path) -> Option<Vec<String>> {
+    let output = Command::new("docker")
+        .args(["ps", "-q", "--filter"])
+        .arg(format!("label={LABEL}"))
+        .arg("--filter")
+        .arg(format!(
+            "label={PROJECT_LABEL}={}",
+            project_label(project_dir)
+        ))
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+            .collect(),
+    )
+}
+
+/// `dx dev-services watchdog` (started by `run --auto-stop`): stops the
+/// project's containers once no published port has seen a connection for the
+/// configured time.
+pub fn watchdog(dir: Option<PathBuf>) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    let own_pid = std::process::id();
+    // The state is written right after spawning; give `run` a moment
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let state = loop {
+        match read_state(&project_dir) {
+            Some(state) if state.pid == own_pid => break state,
+            _ if Instant::now() >= deadline => return Ok(()),
+            _ => std::thread::sleep(Duration::from_millis(100)),
+        }
+    };
+    let idle = Duration::from_secs(state.idle_secs);
+    let interval = (idle / 4).clamp(Duration::from_secs(1), MAX_INTERVAL);
+    eprintln!(
+        "watchdog {own_pid}: auto-stop após {} sem conexões nas portas {:?}",
+        format_duration(idle),
+        state.ports
+    );
+
+    let mut last_activity = Instant::now();
+    loop {
+        std::thread::sleep(interval);
+        if read_state(&project_dir).is_none_or(|s| s.pid != own_pid) {
+            eprintln!("watchdog {own_pid}: substituído ou desativado; encerrando");
+            return Ok(());
+        }
+        let connected = connected_ports();
+        if state.ports.iter().any(|p| connected.contains(p)) {
+            last_activity = Instant::now();
+            continue;
+        }
+        if last_activity.elapsed() < idle {
+            continue;
+        }
+        let Some(containers) = labeled_containers(&project_dir) else {
+            eprintln!("watchdog {own_pid}: Docker indisponível; nova tentativa em {interval:?}");
+            continue;
+        };
+        if !containers.is_empty() {
+            eprintln!(
+                "watchdog {own_pid}: {} sem atividade; parando {} container(s)",
+                format_duration(idle),
+                containers.len()
+            );
+            let status = Command::new("docker")
+                .arg("stop")
+                .args(&containers)
+                .stdin(Stdio::null())
+                .status();
+            if !status.is_ok_and(|s| s.success()) {
+                eprintln!(
+                    "watchdog {own_pid}: 'docker stop' falhou; nova tentativa em {interval:?}"
+                );
+                continue;
+            }
+        }
+        let _ = fs::remove_file(state_path(&project_dir));
+        return Ok(());
+    }
+}
@@ -0,0 +1,260 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::clean::{dir_size, format_size};
+use crate::error::{DxError, DxResult};
+
+const DAY: u64 = 24 * 60 * 60;
+
+/// Known cache categories: (name, description, TTL in seconds).
+pub const CATEGORIES: &[(&str, &str, u64)] = &[
+    ("governance", "bundles de policies compartilhados", 30 * DAY),
+    ("registry", "últimas versões dos registries de pacotes", DAY),
+    ("dashboards", "dashboards baixados", 7 * DAY),
+    ("indexes", "índices de detecção", 7 * DAY),
+];
+
+/// TTL applied to subdirectories that are not a known category.
+const DEFAULT_TTL: u64 = 7 * DAY;
+
+/// Marker file used to run the automatic pruning at most once a day.
+const PRUNE_STAMP: &str = ".last-prune";
+
+/// Root of the user-level cache shared by every project on the machine.
+pub fn root() -> PathBuf {
+    if let Some(dir) = std::env::var_os("XDG_CACHE_HOME").filter(|d| !d.is_empty()) {
+        return PathBuf::from(dir).join("dx");
+    }
+    if let Some(dir) = std::env::var_os("LOCALAPPDATA").filter(|d| !d.is_empty()) {
+        return PathBuf::from(dir).join("dx").join("cache");
+    }
+    if let Some(home) = std::env::var_os("HOME").filter(|d| !d.is_empty()) {
+        return PathBuf::from(home).join(".cache").join("dx");
+    }
+    std::env::temp_dir().join("dx-cache")
+}
+
+/// Directory of a cache category (e.g. `~/.cache/dx/registry`).
+pub fn category_dir(category: &str) -> PathBuf {
+    root().join(category)
+}
+
+/// TTL of a cache category, in seconds.
+pub fn ttl_for(category: &str) -> u64 {
+    CATEGORIES
+        .iter()
+        .find(|(name, _, _)| *name == category)
+        .map(|(_, _, ttl)| *ttl)
+        .unwrap_or(DEFAULT_TTL)
+}
+
+fn format_ttl(secs: u64) -> String {
+    if secs.is_multiple_of(DAY) {
+        format!("{}d", secs / DAY)
+    } else {
+        format!("{}h", secs / 3600)
+    }
+}
+
+/// Known categories plus any other subdirectory found in the cache root.
+fn categories(cache: &Path) -> Vec<String> {
+    let mut names: Vec<String> = CATEGORIES.iter().map(|(n, _, _)| n.to_string()).collect();
+    if let Ok(entries) = fs::read_dir(cache) {
+        let mut extra: Vec<String> = entries
+            .flatten()
+            .filter(|e| e.file_type().is_ok_and(|ft| ft.is_dir()))
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .filter(|n| !n.starts_with('.') && !names.contains(n))
+            .collect();
+        extra.sort();
+        names.extend(extra);
+    }
+    names
+}
+
+fn validate_category(cache: &Path, category: &str) -> DxResult {
+    if categories(cache).iter().any(|c| c == category) {
+        return Ok(());
+    }
+    let known: Vec<&str> = CATEGORIES.iter().map(|(n, _, _)| *n).collect();
+    Err(DxError::InvalidInput(format!(
+        "Categoria de cache desconhecida: {} (conhecidas: {})",
+        category,
+        known.join(", ")
+    )))
+}
+
+fn entry_size(path: &Path) -> u64 {
+    match fs::symlink_metadata(path) {
+        Ok(m) if m.is_dir() => dir_size(path),
+        Ok(m) => m.len(),
+        Err(_) => 0,
+    }
+}
+
+fn remove_entry(path: &Path) -> std::io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// Print entries, size and TTL of each category.
+pub fn stats(category: Option<String>) -> DxResult {
+    let cache = root();
+    if let Some(c) = &category {
+        validate_category(&cache, c)?;
+    }
+    outln!("Cache do dx em: {}", cache.display());
+
+    let mut total = 0u64;
+    let mut report = Vec::new();
+    for name in categories(&cache) {
+        if category.as_ref().is_some_and(|c| *c != name) {
+            continue;
+        }
+        let dir = cache.join(&name);
+        let entries = fs::read_dir(&dir).map(|r| r.flatten().count()).unwrap_or(0);
+        let size = dir_size(&dir);
+        total += size;
+        let description = CATEGORIES
+            .iter()
+            .find(|(n, _, _)| *n == name)
+            .map(|(_, d, _)| format!(" - {}", d))
+            .unwrap_or_default();
+        report.push(serde_json::json!({
+            "name": name,
+            "entries": entries,
+            "size_bytes": size,
+            "ttl_secs": ttl_for(&name),
+        }));
+        outln!(
+            "- {}: {} entrada(s), {} (TTL {}){}",
+            name,
+            entries,
+            format_size(size),
+            format_ttl(ttl_for(&name)),
+            description
+        );
+    }
+    outln!("Total: {}", format_size(total));
+    crate::output::set_data(serde_json::json!({
+        "root": cache.display().to_string(),
+        "categories": report,
+        "total_bytes": total,
+    }));
+    Ok(())
+}
+
+/// Remove the whole cache or only one category.
+pub fn clear(category: Option<String>) -> DxResult {
+    let cache = root();
+    let targets: Vec<String> = match category {
+        Some(c) => {
+            validate_category(&cache, &c)?;
+            vec![c]
+        }
+        None => categories(&cache),
+    };
+
+    let mut reclaimed = 0u64;
+    let mut failure = None;
+    let mut removed = Vec::new();
+    for name in targets {
+        let dir = cache.join(&name);
+        if !dir.exists() {
+            continue;
+        }
+        let size = dir_size(&dir);
+        match fs::remove_dir_all(&dir) {
+            Ok(_) => {
+                reclaimed += size;
+                outln!("Removido: {} ({})", name, format_size(size));
+                removed.push(serde_json::json!({ "category": name, "size_bytes": size }));
+            }
+            Err(e) => {
+                let e = DxError::Io {
+                    context: format!("Falha ao remover {}", dir.display()),
+                    source: e,
+                };
+                eprintln!("{e}");
+                failure = Some(e);
+            }
+        }
+    }
+    outln!("Espaço liberado: {}", format_size(reclaimed));
+    crate::output::set_data(serde_json::json!({
+        "removed": removed,
+        "reclaimed_bytes": reclaimed,
+    }));
+    failure.map_or(Ok(()), Err)
+}
+
+/// Remove entries older than their category TTL. Returns (removed, bytes).
+fn prune_expired(cache: &Path) -> (usize, u64) {
+    let now = SystemTime::now();
+    let mut removed = 0usize;
+    let mut reclaimed = 0u64;
+    for name in categories(cache) {
+        let ttl = Duration::from_secs(ttl_for(&name));
+        let Ok(entries) = fs::read_dir(cache.join(&name)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let expired = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age > ttl);
+            if !expired {
+                continue;
+            }
+            let path = entry.path();
+            let size = entry_size(&path);
+            if remove_entry(&path).is_ok() {
+                removed += 1;
+                reclaimed += size;
+            }
+        }
+    }
+    (removed, reclaimed)
+}
+
+/// Explicit pruning of expired entries (`dx cache prune`).
+pub fn prune() -> DxResult {
+    let (removed, reclaimed) = prune_expired(&root());
+    outln!(
+        "Entradas expiradas removidas: {} | espaço liberado: {}",
+        removed,
+        format_size(reclaimed)
+    );
+    crate::output::set_data(serde_json::json!({
+        "removed": removed,
+        "reclaimed_bytes": reclaimed,
+    }));
+    Ok(())
+}
+
+/// Prune expired entries at most once a day; silent and best-effort.
+pub fn auto_prune() {
+    let cache = root();
+    if !cache.is_dir() {
+        return;
+    }
+    let stamp = cache.join(PRUNE_STAMP);
+    let recent = fs::metadata(&stamp)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| SystemTime::now().duration_since(t).ok())
+        .is_some_and(|age| age < Duration::from_secs(DAY));
+    if recent {
+        return;
+    }
+    prune_expired(&cache);
+    let _ = fs::write(&stamp, b"");
+}
@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! The project's own Dev Services (`.dx/services.d/*.yaml`).
+//!
+//! Each file describes a service dx doesn't know, or adjusts a built-in one,
+//! in the terms of a [`Detector`]:
+//!
+//! ```yaml
+//! # .dx/services.d/elasticmq.yaml
+//! keywords: [elasticmq, ELASTICMQ_URL]
+//! image: softwaremill/elasticmq-native:1.6
+//! ports: [9324, 9325]
+//! env:
+//!   JAVA_OPTS: -Xmx256m
+//! volumes: [elasticmq-data:/data]
+//! profile: messaging
+//! ui: 9325
+//! info: "SQS: http://localhost:9324"
+//! ```
+//!
+//! The file name is the service name (unless `name:` says otherwise).
+//! Keywords match like the built-ins' (see [`Keyword`]): whole words,
+//! case-insensitive unless they have an uppercase letter; a trailing `*`
+//! matches a word start (`elasticmq*`). A file named after a built-in
+//! (`postgres.yaml`) overrides it: its keywords are added to the built-in's
+//! and the fields it sets replace those of the main service; the rest
+//! (companion services, IaC mapping, badge, report info) is kept. New services
+//! come after the built-ins, in file name order. `${VAR}` placeholders are
+//! resolved like in the compose override (see [`crate::interpolate`]); an
+//! invalid file is logged and ignored.
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::detectors::{Detector, Keyword};
+use crate::dev_services::{DockerComposeConfig, DockerService};
+use crate::iac::LocalService;
+
+/// Profile of new services that don't set one.
+pub const DEFAULT_PROFILE: &str = "custom";
+
+/// Directory of the catalog files, relative to the project.
+pub fn dir(project_dir: &Path) -> PathBuf {
+    project_dir.join(".dx").join("services.d")
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Entry {
+    name: Option<String>,
+    profile: Option<String>,
+    keywords: Vec<String>,
+    image: Option<String>,
+    ports: Option<Vec<u16>>,
+    /// Values may be numbers or booleans, as in Compose
+    env: BTreeMap<String, serde_yaml::Value>,
+    volumes: Option<Vec<String>>,
+    command: Option<String>,
+    ui: Option<u16>,
+    info: Option<String>,
+    badge: Option<String>,
+}
+
+/// A service of the catalog, on its own or over the built-in `base`.
+struct Custom {
+    name: String,
+    profile: String,
+    keywords: Vec<Keyword>,
+    entry: Entry,
+    base: Option<Box<dyn Detector>>,
+}
+
+/// Keyword written in a catalog file.
+fn keyword(text: &str) -> Keyword {
+    let (text, prefix) = match text.strip_suffix('*') {
+        Some(text) => (text, true),
+        None => (text, false),
+    };
+    let case_sensitive = text.chars().any(char::is_uppercase);
+    Keyword {
+        text: Cow::Owned(text.to_string()),
+        case_sensitive,
+        prefix,
+        package: false,
+    }
+}
+
+fn scalar(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Compose accepts lowercase letters, digits, `-`, `_` and `.` in service
+/// names.
+fn valid_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "-_.".contains(c))
+}
+
+/// Name of the service described by `entry`, read from `path`.
+fn entry_name(path: &Path, entry: &Entry) -> String {
+    entry.name.clone().unwrap_or_else(|| {
+        path.file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    })
+}
+
+/// Check `entry` describes a usable service; `builtin` when it overrides one.
+fn validate(name: &str, entry: &Entry, builtin: bool) -> Result<(), String> {
+    if !valid_name(name) {
+        return Err(format!(
+            "nome de serviço inválido: '{name}' (use letras minúsculas, dígitos, '-', '_' e '.')"
+        ));
+    }
+    if let Some((key, _)) = entry.env.iter().find(|(_, v)| scalar(v).is_none()) {
+        return Err(format!("`env.{key}` deve ser texto, número ou booleano"));
+    }
+    if !builtin && entry.image.is_none() {
+        return Err("falta `image`".into());
+    }
+    if !builtin && entry.keywords.is_empty() {
+        return Err("falta `keywords` (sem elas o serviço nunca é detectado)".into());
+    }
+    Ok(())
+}
+
+impl Custom {
+    fn new(name: String, entry: Entry, base: Option<Box<dyn Detector>>) -> Self {
+        let mut keywords: Vec<Keyword> = base
+            .as_ref()
+            .map_or_else(Vec::new, |b| b.keywords().to_vec());
+        keywords.extend(entry.keywords.iter().map(|k| keyword(k)));
+        let profile = entry
+            .profile
+            .clone()
+            .or_else(|| base.as_ref().map(|b| b.profile().to_string()))
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+        Custom {
+            name,
+            profile,
+            keywords,
+            entry,
+            base,
+        }
+    }
+
+    /// Apply the fields set in the file to `svc`.
+    fn apply(&self, svc: &mut DockerService) {
+        let entry = &self.entry;
+        if let Some(image) = &entry.image {
+            svc.image = image.clone();
+        }
+        if let Some(ports) = &entry.ports {
+            svc.ports = ports.clone();
+        }
+        for (key, value) in &entry.env {
+            svc.env
+                .insert(key.clone(), scalar(value).unwrap_or_default());
+        }
+        if let Some(volumes) = &entry.volumes {
+            svc.volumes = volumes.clone();
+        }
+        if let Some(command) = &entry.command {
+            svc.command = Some(command.clone());
+        }
+    }
+}
+
+impl Detector for Custom {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    fn keywords(&self) -> &[Keyword] {
+        &self.keywords
+    }
+
+    fn iac(&self) -> Option<LocalService> {
+        self.base.as_ref().and_then(|b| b.iac())
+    }
+
+    fn services(&self) -> Vec<(String, DockerService)> {
+        let mut services = self.base.as_ref().map_or_else(Vec::new, |b| b.services());
+        match services.iter_mut().find(|(name, _)| *name == self.name) {
+            Some((_, svc)) => self.apply(svc),
+            None => {
+                let mut svc = DockerService::default();
+                self.apply(&mut svc);
+                services.insert(0, (self.name.clone(), svc));
+            }
+        }
+        services
+    }
+
+    fn badge(&self) -> Option<&str> {
+        self.entry
+            .badge
+            .as_deref()
+            .or_else(|| self.base.as_ref().and_then(|b| b.badge()))
+    }
+
+    fn ui(&self, service: &str) -> Option<u16> {
+        match self.entry.ui {
+            Some(port) if service == self.name => Some(port),
+            _ => self.base.as_ref().and_then(|b| b.ui(service)),
+        }
+    }
+
+    fn info(&self, service: &str, svc: &DockerService) -> Option<String> {
+        match &self.entry.info {
+            Some(info) if service == self.name => Some(info.clone()),
+            _ => self.base.as_ref().and_then(|b| b.info(service, svc)),
+        }
+    }
+
+    fn files(&self) -> Vec<(String, String)> {
+        self.base.as_ref().map_or_else(Vec::new, |b| b.files())
+    }
+
+    fn provides(&self, service: &str) -> bool {
+        service == self.name || self.base.as_ref().is_some_and(|b| b.provides(service))
+    }
+}
+
+/// Catalog files of `project_dir`, sorted by name.
+fn files(project_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir(project_dir)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| p.extension().is_some_and(|x| x == "yaml" || x == "yml"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// The catalog files of `project_dir` that parse, with their service names.
+fn entries(project_dir: &Path) -> Vec<(PathBuf, String, Entry)> {
+    let mut entries = Vec::new();
+    for path in files(project_dir) {
+        let parsed = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|yaml| serde_yaml::from_str::<Entry>(&yaml).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(entry) => entries.push((path.clone(), entry_name(&path, &entry), entry)),
+            Err(e) => tracing::warn!("{} inválido, ignorado: {e}", path.display()),
+        }
+    }
+    entries
+}
+
+/// `builtins` with the services of the project's catalog: overridden in
+/// place and new ones appended.
+pub fn merge(project_dir: &Path, builtins: Vec<Box<dyn Detector>>) -> Vec<Box<dyn Detector>> {
+    let mut detectors: Vec<Box<dyn Detector>> = builtins;
+    for (path, name, entry) in entries(project_dir) {
+        let slot = detectors.iter().position(|d| d.name() == name);
+        if let Err(e) = validate(&name, &entry, slot.is_some()) {
+            tracing::warn!("{} inválido, ignorado: {e}", path.display());
+            continue;
+        }
+        match slot {
+            Some(i) => {
+                let base = detectors.remove(i);
+                detectors.insert(i, Box::new(Custom::new(name, entry, Some(base))));
+            }
+            None => detectors.push(Box::new(Custom::new(name, entry, None))),
+        }
+    }
+    detectors
+}
+
+/// Resolve the `${VAR}` placeholders of the catalog's services in `config`.
+/// Done after the (cached) scan, so a variable change shows up without
+/// rescanning the project.
+pub fn resolve(project_dir: &Path, config: &mut DockerComposeConfig) {
+    use crate::interpolate::{Target, project};
+    let resolve = |text: &str| project(project_dir, text, Target::Compose);
+    for (_, name, _) in entries(project_dir) {
+        let Some(svc) = config.services.get_mut(&name) else {
+            continue;
+        };
+        svc.image = resolve(&svc.image);
+        for value in svc.env.values_mut() {
+            *value = resolve(value);
+        }
+        for volume in &mut svc.volumes {
+            *volume = resolve(volume);
+        }
+        if let Some(command) = &mut svc.command {
+            *command = resolve(command);
+        }
+    }
+}
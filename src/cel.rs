@@ -0,0 +1,744 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+
+//! Minimal evaluator for a subset of CEL (Common Expression Language).
+//!
+//! Supported: literals (int, double, string, bool, null), lists and maps,
+//! field access and indexing, `! - * / % + - < <= > >= == != in && || ?:`,
+//! `size()`, `has()`, `int()`, `string()`, string methods (`startsWith`,
+//! `endsWith`, `contains`, `lowerAscii`, `upperAscii`) and the list macros
+//! `all`, `exists`, `exists_one`, `filter` and `map`.
+//!
+//! Values are represented as `serde_json::Value`, so expressions evaluate
+//! directly against the analyzer JSON model.
+
+use serde_json::{Map, Number, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Int(i64),
+    Double(f64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Lit(Value),
+    Ident(String),
+    List(Vec<Expr>),
+    Map(Vec<(Expr, Expr)>),
+    Member(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Call {
+        target: Option<Box<Expr>>,
+        name: String,
+        args: Vec<Expr>,
+    },
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Cond(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+const OPERATORS: [&str; 24] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "+", "-", "*", "/", "%", "?", ":", ".",
+    ",", "(", ")", "[", "]", "{", "}",
+];
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                // Stop at a member access such as `1.size()` — digits must follow the dot
+                if chars[i] == '.' && !chars.get(i + 1).is_some_and(|n| n.is_ascii_digit()) {
+                    break;
+                }
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            if text.contains('.') {
+                let v = text
+                    .parse::<f64>()
+                    .map_err(|_| format!("número inválido: {text}"))?;
+                tokens.push(Token::Double(v));
+            } else {
+                let v = text
+                    .parse::<i64>()
+                    .map_err(|_| format!("número inválido: {text}"))?;
+                tokens.push(Token::Int(v));
+            }
+            continue;
+        }
+        if c == '"' || c == '\'' {
+            let quote = c;
+            i += 1;
+            let mut s = String::new();
+            loop {
+                let Some(&ch) = chars.get(i) else {
+                    return Err("string não terminada".to_string());
+                };
+                i += 1;
+                if ch == quote {
+                    break;
+                }
+                if ch == '\\' {
+                    let Some(&esc) = chars.get(i) else {
+                        return Err("string não terminada".to_string());
+                    };
+                    i += 1;
+                    s.push(match esc {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        other => other,
+                    });
+                } else {
+                    s.push(ch);
+                }
+            }
+            tokens.push(Token::Str(s));
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            continue;
+        }
+        let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+        if let Some(op) = OPERATORS.iter().find(|op| op.len() == 2 && **op == two) {
+            tokens.push(Token::Op(op));
+            i += 2;
+            continue;
+        }
+        if let Some(op) = OPERATORS
+            .iter()
+            .find(|op| op.len() == 1 && op.starts_with(c))
+        {
+            tokens.push(Token::Op(op));
+            i += 1;
+            continue;
+        }
+        return Err(format!("caractere inesperado: '{c}'"));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_op(&self, op: &str) -> bool {
+        matches!(self.peek(), Some(Token::Op(o)) if *o == op)
+    }
+
+    fn expect_op(&mut self, op: &str) -> Result<(), String> {
+        if self.peek_op(op) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("esperado '{op}'"))
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, String> {
+        let cond = self.parse_binary(0)?;
+        if self.peek_op("?") {
+            self.pos += 1;
+            let then = self.parse_expr()?;
+            self.expect_op(":")?;
+            let otherwise = self.parse_expr()?;
+            return Ok(Expr::Cond(
+                Box::new(cond),
+                Box::new(then),
+                Box::new(otherwise),
+            ));
+        }
+        Ok(cond)
+    }
+
+    fn parse_binary(&mut self, level: usize) -> Result<Expr, String> {
+        const LEVELS: [&[&str]; 6] = [
+            &["||"],
+            &["&&"],
+            &["==", "!=", "<", "<=", ">", ">=", "in"],
+            &["+", "-"],
+            &["*", "/", "%"],
+            &[],
+        ];
+        if LEVELS[level].is_empty() {
+            return self.parse_unary();
+        }
+        let mut lhs = self.parse_binary(level + 1)?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Op(o)) if LEVELS[level].contains(o) => *o,
+                Some(Token::Ident(i)) if i == "in" && LEVELS[level].contains(&"in") => "in",
+                _ => break,
+            };
+            self.pos += 1;
+            let rhs = self.parse_binary(level + 1)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.peek_op("!") {
+            self.pos += 1;
+            return Ok(Expr::Unary("!", Box::new(self.parse_unary()?)));
+        }
+        if self.peek_op("-") {
+            self.pos += 1;
+            return Ok(Expr::Unary("-", Box::new(self.parse_unary()?)));
+        }
+        self.parse_postfix()
+    }
+
+    fn parse_args(&mut self, close: &str) -> Result<Vec<Expr>, String> {
+        let mut args = Vec::new();
+        if self.peek_op(close) {
+            self.pos += 1;
+            return Ok(args);
+        }
+        loop {
+            args.push(self.parse_expr()?);
+            if self.peek_op(",") {
+                self.pos += 1;
+                continue;
+            }
+            self.expect_op(close)?;
+            return Ok(args);
+        }
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            if self.peek_op(".") {
+                self.pos += 1;
+                let Some(Token::Ident(name)) = self.peek().cloned() else {
+                    return Err("esperado identificador após '.'".to_string());
+                };
+                self.pos += 1;
+                if self.peek_op("(") {
+                    self.pos += 1;
+                    let args = self.parse_args(")")?;
+                    expr = Expr::Call {
+                        target: Some(Box::new(expr)),
+                        name,
+                        args,
+                    };
+                } else {
+                    expr = Expr::Member(Box::new(expr), name);
+                }
+            } else if self.peek_op("[") {
+                self.pos += 1;
+                let index = self.parse_expr()?;
+                self.expect_op("]")?;
+                expr = Expr::Index(Box::new(expr), Box::new(index));
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        let Some(token) = self.peek().cloned() else {
+            return Err("expressão incompleta".to_string());
+        };
+        self.pos += 1;
+        match token {
+            Token::Int(v) => Ok(Expr::Lit(Value::from(v))),
+            Token::Double(v) => Ok(Expr::Lit(Value::from(v))),
+            Token::Str(s) => Ok(Expr::Lit(Value::String(s))),
+            Token::Ident(name) => match name.as_str() {
+                "true" => Ok(Expr::Lit(Value::Bool(true))),
+                "false" => Ok(Expr::Lit(Value::Bool(false))),
+                "null" => Ok(Expr::Lit(Value::Null)),
+                _ if self.peek_op("(") => {
+                    self.pos += 1;
+                    let args = self.parse_args(")")?;
+                    Ok(Expr::Call {
+                        target: None,
+                        name,
+                        args,
+                    })
+                }
+                _ => Ok(Expr::Ident(name)),
+            },
+            Token::Op("(") => {
+                let e = self.parse_expr()?;
+                self.expect_op(")")?;
+                Ok(e)
+            }
+            Token::Op("[") => Ok(Expr::List(self.parse_args("]")?)),
+            Token::Op("{") => {
+                let mut entries = Vec::new();
+                if self.peek_op("}") {
+                    self.pos += 1;
+                    return Ok(Expr::Map(entries));
+                }
+                loop {
+                    let key = self.parse_expr()?;
+                    self.expect_op(":")?;
+                    let val = self.parse_expr()?;
+                    entries.push((key, val));
+                    if self.peek_op(",") {
+                        self.pos += 1;
+                        continue;
+                    }
+                    self.expect_op("}")?;
+                    return Ok(Expr::Map(entries));
+                }
+            }
+            Token::Op(op) => Err(format!("operador inesperado: '{op}'")),
+        }
+    }
+}
+
+struct Scope<'a> {
+    root: &'a Value,
+    vars: Vec<(String, Value)>,
+}
+
+impl Scope<'_> {
+    fn lookup(&self, name: &str) -> Result<Value, String> {
+        if let Some((_, v)) = self.vars.iter().rev().find(|(n, _)| n == name) {
+            return Ok(v.clone());
+        }
+        self.root
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("identificador desconhecido: {name}"))
+    }
+}
+
+fn as_f64(v: &Value) -> Option<f64> {
+    v.as_f64()
+}
+
+fn type_name(v: &Value) -> &'static str {
+    match v {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(n) if n.is_f64() => "double",
+        Value::Number(_) => "int",
+        Value::String(_) => "string",
+        Value::Array(_) => "list",
+        Value::Object(_) => "map",
+    }
+}
+
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x.as_f64() == y.as_f64(),
+        _ => a == b,
+    }
+}
+
+fn truthy(v: &Value, ctx: &str) -> Result<bool, String> {
+    v.as_bool()
+        .ok_or_else(|| format!("{ctx}: esperado bool, obtido {}", type_name(v)))
+}
+
+fn arith(op: &str, a: &Value, b: &Value) -> Result<Value, String> {
+    if let (Some(x), Some(y)) = (a.as_i64(), b.as_i64()) {
+        let r = match op {
+            "+" => x.checked_add(y),
+            "-" => x.checked_sub(y),
+            "*" => x.checked_mul(y),
+            "/" => x.checked_div(y),
+            _ => x.checked_rem(y),
+        };
+        return r
+            .map(Value::from)
+            .ok_or_else(|| format!("overflow ou divisão por zero em '{op}'"));
+    }
+    let (Some(x), Some(y)) = (as_f64(a), as_f64(b)) else {
+        return Err(format!(
+            "operador '{op}' não suportado para {} e {}",
+            type_name(a),
+            type_name(b)
+        ));
+    };
+    let r = match op {
+        "+" => x + y,
+        "-" => x - y,
+        "*" => x * y,
+        "/" => x / y,
+        _ => x % y,
+    };
+    Number::from_f64(r)
+        .map(Value::Number)
+        .ok_or_else(|| format!("resultado inválido em '{op}'"))
+}
+
+fn compare(op: &str, a: &Value, b: &Value) -> Result<Value, String> {
+    let ord = match (a, b) {
+        (Value::String(x), Value::String(y)) => x.cmp(y),
+        _ => {
+            let (Some(x), Some(y)) = (as_f64(a), as_f64(b)) else {
+                return Err(format!(
+                    "comparação '{op}' não suportada para {} e {}",
+                    type_name(a),
+                    type_name(b)
+                ));
+            };
+            x.partial_cmp(&y).ok_or("comparação inválida")?
+        }
+    };
+    let r = match op {
+        "<" => ord.is_lt(),
+        "<=" => ord.is_le(),
+        ">" => ord.is_gt(),
+        _ => ord.is_ge(),
+    };
+    Ok(Value::Bool(r))
+}
+
+fn eval(expr: &Expr, scope: &mut Scope) -> Result<Value, String> {
+    match expr {
+        Expr::Lit(v) => Ok(v.clone()),
+        Expr::Ident(name) => scope.lookup(name),
+        Expr::List(items) => Ok(Value::Array(
+            items
+                .iter()
+                .map(|e| eval(e, scope))
+                .collect::<Result<_, _>>()?,
+        )),
+        Expr::Map(entries) => {
+            let mut map = Map::new();
+            for (k, v) in entries {
+                let key = match eval(k, scope)? {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                map.insert(key, eval(v, scope)?);
+            }
+            Ok(Value::Object(map))
+        }
+        Expr::Member(base, field) => {
+            let b = eval(base, scope)?;
+            b.get(field)
+                .cloned()
+                .ok_or_else(|| format!("campo inexistente: {field}"))
+        }
+        Expr::Index(base, index) => {
+            let b = eval(base, scope)?;
+            let i = eval(index, scope)?;
+            match (&b, &i) {
+                (Value::Array(items), Value::Number(n)) => n
+                    .as_u64()
+                    .and_then(|n| items.get(n as usize))
+                    .cloned()
+                    .ok_or_else(|| format!("índice fora dos limites: {n}")),
+                (Value::Object(map), Value::String(k)) => map
+                    .get(k)
+                    .cloned()
+                    .ok_or_else(|| format!("chave inexistente: {k}")),
+                _ => Err(format!(
+                    "indexação não suportada para {}[{}]",
+                    type_name(&b),
+                    type_name(&i)
+                )),
+            }
+        }
+        Expr::Unary(op, inner) => {
+            let v = eval(inner, scope)?;
+            if *op == "!" {
+                Ok(Value::Bool(!truthy(&v, "!")?))
+            } else {
+                arith("-", &Value::from(0), &v)
+            }
+        }
+        Expr::Binary(op, lhs, rhs) => match *op {
+            "&&" => {
+                if !truthy(&eval(lhs, scope)?, "&&")? {
+                    return Ok(Value::Bool(false));
+                }
+                Ok(Value::Bool(truthy(&eval(rhs, scope)?, "&&")?))
+            }
+            "||" => {
+                if truthy(&eval(lhs, scope)?, "||")? {
+                    return Ok(Value::Bool(true));
+                }
+                Ok(Value::Bool(truthy(&eval(rhs, scope)?, "||")?))
+            }
+            _ => {
+                let a = eval(lhs, scope)?;
+                let b = eval(rhs, scope)?;
+                match *op {
+                    "==" => Ok(Value::Bool(values_equal(&a, &b))),
+                    "!=" => Ok(Value::Bool(!values_equal(&a, &b))),
+                    "<" | "<=" | ">" | ">=" => compare(op, &a, &b),
+                    "in" => match &b {
+                        Value::Array(items) => {
+                            Ok(Value::Bool(items.iter().any(|x| values_equal(x, &a))))
+                        }
+                        Value::Object(map) => Ok(Value::Bool(
+                            a.as_str().is_some_and(|k| map.contains_key(k)),
+                        )),
+                        _ => Err(format!("'in' não suportado para {}", type_name(&b))),
+                    },
+                    "+" => match (&a, &b) {
+                        (Value::String(x), Value::String(y)) => Ok(Value::String(format!("{x}{y}"))),
+                        (Value::Array(x), Value::Array(y)) => {
+                            Ok(Value::Array(x.iter().chain(y).cloned().collect()))
+                        }
+                        _ => arith("+", &a, &b),
+                    },
+                    _ => arith(op, &a, &b),
+                }
+            }
+        },
+        Expr::Cond(cond, then, otherwise) => {
+            if truthy(&eval(cond, scope)?, "?:")? {
+                eval(then, scope)
+            } else {
+                eval(otherwise, scope)
+            }
+        }
+        Expr::Call { target, name, args } => eval_call(target.as_deref(), name, args, scope),
+    }
+}
+
+fn eval_call(
+    target: Option<&Expr>,
+    name: &str,
+    args: &[Expr],
+    scope: &mut Scope,
+) -> Result<Value, String> {
+    // has(a.b): field presence test, must not fail on missing fields
+    if target.is_none() && name == "has" {
+        let [Expr::Member(base, field)] = args else {
+            return Err("has() espera um acesso a campo, ex.: has(a.b)".to_string());
+        };
+        let b = eval(base, scope)?;
+        return Ok(Value::Bool(b.get(field).is_some()));
+    }
+
+    // List macros: target.all(x, pred), exists, exists_one, filter, map
+    if let Some(t) = target
+        && matches!(name, "all" | "exists" | "exists_one" | "filter" | "map")
+    {
+        let [Expr::Ident(var), body] = args else {
+            return Err(format!("{name}() espera (variável, expressão)"));
+        };
+        let items = match eval(t, scope)? {
+            Value::Array(items) => items,
+            Value::Object(map) => map.keys().cloned().map(Value::String).collect(),
+            other => return Err(format!("{name}() não suportado para {}", type_name(&other))),
+        };
+        let mut results = Vec::with_capacity(items.len());
+        for item in items {
+            scope.vars.push((var.clone(), item.clone()));
+            let r = eval(body, scope);
+            scope.vars.pop();
+            results.push((item, r?));
+        }
+        let bools = || -> Result<Vec<bool>, String> {
+            results.iter().map(|(_, r)| truthy(r, name)).collect()
+        };
+        return match name {
+            "all" => Ok(Value::Bool(bools()?.iter().all(|b| *b))),
+            "exists" => Ok(Value::Bool(bools()?.iter().any(|b| *b))),
+            "exists_one" => Ok(Value::Bool(bools()?.iter().filter(|b| **b).count() == 1)),
+            "filter" => {
+                let keep = bools()?;
+                Ok(Value::Array(
+                    results
+                        .into_iter()
+                        .zip(keep)
+                        .filter(|(_, k)| *k)
+                        .map(|((item, _), _)| item)
+                        .collect(),
+                ))
+            }
+            _ => Ok(Value::Array(results.into_iter().map(|(_, r)| r).collect())),
+        };
+    }
+
+    let mut values = Vec::new();
+    if let Some(t) = target {
+        values.push(eval(t, scope)?);
+    }
+    for a in args {
+        values.push(eval(a, scope)?);
+    }
+
+    match (name, values.as_slice()) {
+        ("size", [Value::String(s)]) => Ok(Value::from(s.chars().count())),
+        ("size", [Value::Array(a)]) => Ok(Value::from(a.len())),
+        ("size", [Value::Object(m)]) => Ok(Value::from(m.len())),
+        ("startsWith", [Value::String(s), Value::String(p)]) => {
+            Ok(Value::Bool(s.starts_with(p.as_str())))
+        }
+        ("endsWith", [Value::String(s), Value::String(p)]) => {
+            Ok(Value::Bool(s.ends_with(p.as_str())))
+        }
+        ("contains", [Value::String(s), Value::String(p)]) => {
+            Ok(Value::Bool(s.contains(p.as_str())))
+        }
+        ("lowerAscii", [Value::String(s)]) => Ok(Value::String(s.to_ascii_lowercase())),
+        ("upperAscii", [Value::String(s)]) => Ok(Value::String(s.to_ascii_uppercase())),
+        ("string", [v]) => Ok(Value::String(match v {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })),
+        ("int", [Value::String(s)]) => s
+            .trim()
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("int(): valor inválido '{s}'")),
+        ("int", [Value::Number(n)]) => Ok(Value::from(n.as_f64().unwrap_or(0.0) as i64)),
+        _ => Err(format!(
+            "função não suportada: {name}({})",
+            values.iter().map(type_name).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// Parse and evaluate `source` against `input`, whose top-level fields are
+/// exposed as variables.
+pub fn evaluate(source: &str, input: &Value) -> Result<Value, String> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_expr()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(format!("token inesperado: {:?}", parser.tokens[parser.pos]));
+    }
+    let mut scope = Scope {
+        root: input,
+        vars: Vec::new(),
+    };
+    eval(&expr, &mut scope)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input() -> Value {
+        json!({
+            "project": { "language": "Rust", "markers": ["Cargo.toml"] },
+            "services": [
+                { "name": "postgres", "ports": ["5432:5432"] },
+                { "name": "redis", "ports": [] },
+            ],
+        })
+    }
+
+    fn eval_ok(source: &str) -> Value {
+        evaluate(source, &input()).unwrap_or_else(|e| panic!("{source}: {e}"))
+    }
+
+    #[test]
+    fn operators_follow_cel_precedence() {
+        assert_eq!(eval_ok("1 + 2 * 3"), json!(7));
+        assert_eq!(eval_ok("(1 + 2) * 3"), json!(9));
+        assert_eq!(eval_ok("10 - 4 - 3"), json!(3));
+        assert_eq!(eval_ok("7 % 4 * 2"), json!(6));
+        assert_eq!(eval_ok("-2 * 3"), json!(-6));
+        assert_eq!(eval_ok("1 + 1 == 2 && 3 < 4"), json!(true));
+        // && binds tighter than ||
+        assert_eq!(eval_ok("true || false && false"), json!(true));
+        assert_eq!(eval_ok("!true || true"), json!(true));
+        assert_eq!(eval_ok("1 < 2 ? 'a' : 'b'"), json!("a"));
+        assert_eq!(eval_ok("false ? 1 : true ? 2 : 3"), json!(2));
+    }
+
+    #[test]
+    fn in_and_has_look_into_lists_and_maps() {
+        assert_eq!(eval_ok("'Cargo.toml' in project.markers"), json!(true));
+        assert_eq!(eval_ok("'go.mod' in project.markers"), json!(false));
+        assert_eq!(eval_ok("'language' in project"), json!(true));
+        assert_eq!(eval_ok("2 in [1.0, 2.0]"), json!(true));
+        assert_eq!(eval_ok("has(project.language)"), json!(true));
+        // A missing field is false for has(), an error anywhere else
+        assert_eq!(eval_ok("has(project.framework)"), json!(false));
+        assert!(evaluate("project.framework == null", &input()).is_err());
+        assert!(evaluate("has(project)", &input()).is_err());
+        assert_eq!(
+            eval_ok("services.exists(s, s.name == 'redis' && size(s.ports) == 0)"),
+            json!(true)
+        );
+        assert_eq!(
+            eval_ok("services.filter(s, size(s.ports) > 0).map(s, s.name)"),
+            json!(["postgres"])
+        );
+    }
+
+    #[test]
+    fn strings_unescape_quotes_and_control_characters() {
+        assert_eq!(eval_ok(r#""tab\there""#), json!("tab\there"));
+        assert_eq!(eval_ok(r#"'line\nbreak'"#), json!("line\nbreak"));
+        assert_eq!(eval_ok(r#"'it\'s' + "\"q\"""#), json!("it's\"q\""));
+        assert_eq!(eval_ok(r#"size('a\\b')"#), json!(3));
+        assert_eq!(eval_ok("'ação'.upperAscii()"), json!("AçãO"));
+        assert!(evaluate("'open", &input()).is_err());
+        assert!(evaluate(r"'trailing\", &input()).is_err());
+    }
+
+    #[test]
+    fn mismatched_types_are_errors() {
+        for source in [
+            "1 + 'a'",
+            "'a' < 1",
+            "!1",
+            "1 && true",
+            "false || 'a'",
+            "1 ? 2 : 3",
+            "'a' in 'abc'",
+            "project[0]",
+            "size(1)",
+            "1 / 0",
+        ] {
+            let err = evaluate(source, &input()).expect_err(source);
+            assert!(!err.is_empty(), "{source}");
+        }
+        assert_eq!(
+            evaluate("1 + 'a'", &input()).unwrap_err(),
+            "operador '+' não suportado para int e string"
+        );
+    }
+
+    #[test]
+    fn logical_operators_short_circuit() {
+        // The right side would fail: unknown identifier
+        assert_eq!(eval_ok("false && missing"), json!(false));
+        assert_eq!(eval_ok("true || missing"), json!(true));
+        assert_eq!(
+            eval_ok("has(project.framework) && project.framework == 'x'"),
+            json!(false)
+        );
+        assert!(evaluate("true && missing", &input()).is_err());
+        assert!(evaluate("false || missing", &input()).is_err());
+    }
+
+    #[test]
+    fn malformed_expressions_are_rejected() {
+        for source in ["", "1 +", "(1", "[1, 2", "a.", "1 2", "#"] {
+            assert!(evaluate(source, &input()).is_err(), "{source}");
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Non-interactive CI mode.
+//!
+//! Enabled by `--ci` or automatically when a CI provider is detected from the
+//! environment. In CI mode dx never prompts, never colors its logs, never
+//! mutates files as a side effect (e.g. `.gitignore` or the global cache) and
+//! runs one-shot variants of long-running commands. `DX_CI=1`/`DX_CI=0`
+//! forces the mode on/off regardless of detection.
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Explicit override of the detection (`1`/`true` or `0`/`false`).
+pub const CI_ENV: &str = "DX_CI";
+
+/// Variables set by common CI providers. `CI` covers most of them; the others
+/// catch providers or runners that don't export it.
+const PROVIDER_VARS: &[&str] = &[
+    "CI",
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "CIRCLECI",
+    "BUILDKITE",
+    "TF_BUILD",
+    "JENKINS_URL",
+    "TEAMCITY_VERSION",
+    "BITBUCKET_BUILD_NUMBER",
+    "DRONE",
+    "TRAVIS",
+];
+
+static CI: AtomicBool = AtomicBool::new(false);
+
+fn is_truthy(value: &str) -> bool {
+    !matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "" | "0" | "false" | "no" | "off"
+    )
+}
+
+/// Name of the environment variable that identifies a CI run, if any.
+pub fn detect() -> Option<&'static str> {
+    if let Ok(value) = std::env::var(CI_ENV) {
+        return is_truthy(&value).then_some(CI_ENV);
+    }
+    PROVIDER_VARS
+        .iter()
+        .copied()
+        .find(|var| std::env::var(var).is_ok_and(|v| is_truthy(&v)))
+}
+
+/// Enable CI mode when `flag` is set or a CI environment is detected.
+pub fn init(flag: bool) {
+    CI.store(flag || detect().is_some(), Ordering::Relaxed);
+}
+
+pub fn is_ci() -> bool {
+    CI.load(Ordering::Relaxed)
+}
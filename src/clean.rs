@@ -0,0 +1,387 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, mpsc};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::{WalkBuilder, WalkState};
+
+use crate::error::{DxError, DxResult};
+
+pub struct CleanOptions {
+    /// Only list what would be removed
+    pub dry_run: bool,
+    /// Skip the interactive confirmation
+    pub yes: bool,
+    /// Maximum directory depth to traverse (0 = only the root)
+    pub max_depth: Option<usize>,
+    /// Glob patterns of directories to skip (matched against the name and the relative path)
+    pub exclude: Vec<String>,
+    /// Skip directories ignored by git (node_modules, target, ...)
+    pub respect_gitignore: bool,
+    /// Move the folders into a timestamped backup instead of deleting them
+    pub backup: bool,
+    /// Where backups are stored (default: $DX_BACKUP_DIR or <temp>/dx-backups)
+    pub backup_dir: Option<PathBuf>,
+}
+
+/// Backups older than this are removed whenever a new backup is made.
+const BACKUP_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const BACKUP_PREFIX: &str = "dx-clean-";
+
+fn backup_root(opts: &CleanOptions) -> PathBuf {
+    if let Some(dir) = &opts.backup_dir {
+        return dir.clone();
+    }
+    if let Some(dir) = std::env::var_os("DX_BACKUP_DIR").filter(|d| !d.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    std::env::temp_dir().join("dx-backups")
+}
+
+/// Remove expired backups from `root`; returns how many were removed.
+fn prune_backups(root: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(root) else {
+        return 0;
+    };
+    let now = SystemTime::now();
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        if !entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(BACKUP_PREFIX)
+        {
+            continue;
+        }
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| now.duration_since(t).ok())
+            .is_some_and(|age| age > BACKUP_TTL);
+        if expired && fs::remove_dir_all(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+fn copy_dir_all(src: &Path, dst: &Path) -> io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let ft = entry.file_type()?;
+        let target = dst.join(entry.file_name());
+        if ft.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else if ft.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &target)?;
+            #[cfg(not(unix))]
+            fs::copy(entry.path(), &target).map(|_| ())?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Move a directory, falling back to copy + delete across filesystems.
+fn move_dir(src: &Path, dst: &Path) -> io::Result<()> {
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+    copy_dir_all(src, dst)?;
+    fs::remove_dir_all(src)
+}
+
+fn build_exclude_set(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern.trim_end_matches('/'))
+            .map_err(|e| format!("Padrão --exclude inválido '{}': {}", pattern, e))?;
+        builder.add(glob);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Find every ".dx" directory under `root` (without descending into them).
+/// The walk runs in parallel; returns the directories found and the errors hit while listing.
+pub fn find_dx_dirs(root: &Path, opts: &CleanOptions) -> (Vec<PathBuf>, Vec<String>) {
+    let excludes = match build_exclude_set(&opts.exclude) {
+        Ok(set) => Arc::new(set),
+        Err(e) => return (Vec::new(), vec![e]),
+    };
+
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .standard_filters(false)
+        .hidden(false)
+        .follow_links(false)
+        .max_depth(opts.max_depth)
+        .git_ignore(opts.respect_gitignore)
+        .git_exclude(opts.respect_gitignore)
+        .git_global(opts.respect_gitignore)
+        .parents(opts.respect_gitignore);
+
+    let filter_root = root.to_path_buf();
+    builder.filter_entry(move |entry| {
+        // never descend into .dx itself; it is checked from its parent
+        if entry.file_name() == ".dx" {
+            return false;
+        }
+        if excludes.is_empty() || entry.depth() == 0 {
+            return true;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(&filter_root)
+            .unwrap_or(entry.path());
+        !(excludes.is_match(entry.file_name()) || excludes.is_match(rel))
+    });
+
+    let (tx, rx) = mpsc::channel::<Result<PathBuf, String>>();
+    builder.build_parallel().run(|| {
+        let tx = tx.clone();
+        Box::new(move |result| {
+            match result {
+                Ok(entry) => {
+                    let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+                    if is_dir {
+                        let candidate = entry.path().join(".dx");
+                        // avoid following symlinks
+                        if fs::symlink_metadata(&candidate).is_ok_and(|m| m.is_dir()) {
+                            let _ = tx.send(Ok(candidate));
+                        }
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(format!("Falha ao listar: {}", e)));
+                }
+            }
+            WalkState::Continue
+        })
+    });
+    drop(tx);
+
+    let mut found = Vec::new();
+    let mut errors = Vec::new();
+    for item in rx {
+        match item {
+            Ok(path) => found.push(path),
+            Err(e) => errors.push(e),
+        }
+    }
+    found.sort();
+    (found, errors)
+}
+
+/// Total size in bytes of the files under `path` (symlinks are not followed).
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(ft) if ft.is_dir() => dir_size(&entry.path()),
+            Ok(ft) if ft.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Human-readable size, e.g. "12.3 MB".
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Ask a yes/no question on stdin. Defaults to "no".
+fn confirm(question: &str) -> bool {
+    out!("{question} [s/N] ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(
+        answer.trim().to_lowercase().as_str(),
+        "s" | "sim" | "y" | "yes"
+    )
+}
+
+/// Clean ".dx" folders recursively from `dir` (or the current directory).
+pub fn run(dir: Option<PathBuf>, mut opts: CleanOptions) -> DxResult {
+    let root =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+
+    if !root.exists() || !root.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido para limpeza: {}",
+            root.display()
+        )));
+    }
+
+    // Defaults from dx.toml add to the flags
+    let settings = crate::settings::load(&root)?;
+    opts.exclude.extend(settings.clean.exclude);
+    opts.respect_gitignore |= settings.clean.respect_gitignore;
+
+    build_exclude_set(&opts.exclude).map_err(DxError::InvalidInput)?;
+
+    outln!("Procurando pastas .dx a partir de: {}", root.display());
+    let (found, mut errors) =
+        crate::progress::phase("Procurando pastas .dx", || find_dx_dirs(&root, &opts));
+    let mut folders = Vec::new();
+    let mut removed = 0usize;
+    let mut reclaimed = 0u64;
+    let mut backup_path = None;
+    let mut aborted = None;
+
+    if found.is_empty() {
+        outln!("Nenhuma pasta .dx encontrada sob {}", root.display());
+    } else {
+        let sized: Vec<(PathBuf, u64)> = crate::progress::phase("Calculando tamanhos", || {
+            found
+                .into_iter()
+                .map(|p| {
+                    let size = dir_size(&p);
+                    (p, size)
+                })
+                .collect()
+        });
+        let total: u64 = sized.iter().map(|(_, s)| s).sum();
+
+        for (path, size) in &sized {
+            outln!("- {} ({})", path.display(), format_size(*size));
+            folders.push(serde_json::json!({
+                "path": path.display().to_string(),
+                "size_bytes": size,
+            }));
+        }
+        outln!("Total: {} pasta(s), {}", sized.len(), format_size(total));
+
+        if opts.dry_run {
+            outln!("\nModo --dry-run: nada foi removido.");
+        } else if !opts.yes
+            && (crate::output::is_json() || crate::ci::is_ci() || !io::stdin().is_terminal())
+        {
+            aborted = Some(DxError::Aborted(
+                "Confirmação necessária: execute com --yes para remover sem interação.".to_string(),
+            ));
+        } else if !opts.yes && !confirm(&format!("\nRemover {} pasta(s) .dx?", sized.len())) {
+            aborted = Some(DxError::Aborted(
+                "Operação cancelada. Nada foi removido.".to_string(),
+            ));
+        } else {
+            let archive = opts.backup.then(|| {
+                let secs = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                backup_root(&opts).join(format!("{}{}-{}", BACKUP_PREFIX, secs, std::process::id()))
+            });
+
+            let mut manifest = Vec::new();
+            for (path, size) in &sized {
+                let result = match &archive {
+                    Some(archive) => {
+                        let rel = path.strip_prefix(&root).unwrap_or(path);
+                        let dest = archive.join(rel);
+                        move_dir(path, &dest).map(|_| {
+                            manifest.push(serde_json::json!({
+                                "original": path.display().to_string(),
+                                "backup": dest.display().to_string(),
+                            }));
+                            outln!("Movido: {} -> {}", path.display(), dest.display());
+                        })
+                    }
+                    None => {
+                        fs::remove_dir_all(path).map(|_| outln!("Removido: {}", path.display()))
+                    }
+                };
+                match result {
+                    Ok(_) => {
+                        removed += 1;
+                        reclaimed += size;
+                    }
+                    Err(e) => {
+                        let msg = format!("Falha ao remover {}: {}", path.display(), e);
+                        eprintln!("{}", msg);
+                        errors.push(msg);
+                    }
+                }
+            }
+            outln!(
+                "Pasta(s) .dx removidas: {} | espaço liberado: {}",
+                removed,
+                format_size(reclaimed)
+            );
+
+            if let Some(archive) = &archive
+                && !manifest.is_empty()
+            {
+                let manifest_path = archive.join("manifest.json");
+                let json = serde_json::to_string_pretty(&manifest).unwrap();
+                if let Err(e) = fs::write(&manifest_path, json) {
+                    errors.push(format!(
+                        "Falha ao gravar {}: {}",
+                        manifest_path.display(),
+                        e
+                    ));
+                }
+                outln!(
+                    "Backup salvo em: {} (expira em {} dias; para restaurar, mova as pastas de volta conforme manifest.json)",
+                    archive.display(),
+                    BACKUP_TTL.as_secs() / (24 * 60 * 60)
+                );
+                if let Some(parent) = archive.parent() {
+                    let expired = prune_backups(parent);
+                    if expired > 0 {
+                        outln!("Backups expirados removidos: {}", expired);
+                    }
+                }
+                backup_path = Some(archive.display().to_string());
+            }
+        }
+    }
+
+    crate::output::set_data(serde_json::json!({
+        "root": root.display().to_string(),
+        "dry_run": opts.dry_run,
+        "folders": folders,
+        "removed": removed,
+        "reclaimed_bytes": reclaimed,
+        "backup": backup_path,
+        "errors": errors,
+    }));
+
+    if let Some(e) = aborted {
+        return Err(e);
+    }
+    if !errors.is_empty() {
+        return Err(DxError::Io {
+            context: format!("Ocorreram {} erro(s) durante a limpeza", errors.len()),
+            source: io::Error::other(errors.join("\n- ")),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Shell completions and man pages.
+//!
+//! Completions use clap_complete's dynamic engine: the script printed by
+//! `dx completions <shell>` calls back into `dx` (with `COMPLETE=<shell>`) on
+//! every TAB, so values read from the project — service names from
+//! `.dx/docker-compose.yml` and keys from `.dx/config.json` — are always current.
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use clap::CommandFactory;
+use clap_complete::CompletionCandidate;
+use clap_complete::env::{Bash, EnvCompleter, Fish, Powershell, Zsh};
+
+use crate::error::{DxResult, IoContext};
+
+/// Environment variable that switches `dx` into completion mode.
+const COMPLETE_ENV: &str = "COMPLETE";
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+impl Shell {
+    fn completer(self) -> &'static dyn EnvCompleter {
+        match self {
+            Shell::Bash => &Bash,
+            Shell::Zsh => &Zsh,
+            Shell::Fish => &Fish,
+            Shell::Powershell => &Powershell,
+        }
+    }
+}
+
+/// Print the registration script for `shell` to stdout.
+pub fn print(shell: Shell) -> DxResult {
+    let mut stdout = io::stdout().lock();
+    shell
+        .completer()
+        .write_registration(COMPLETE_ENV, "dx", "dx", "dx", &mut stdout)
+        .and_then(|_| stdout.flush())
+        .context("Erro ao gerar o script de autocompletar")
+}
+
+/// Render `dx.1` plus one page per subcommand (`dx-dev-services-run.1`, ...) into `dir`.
+pub fn manpages(dir: &Path) -> DxResult {
+    fs::create_dir_all(dir).context(format!("Erro ao criar {}", dir.display()))?;
+    let mut cmd = crate::Cli::command();
+    cmd.build();
+
+    let mut pages = Vec::new();
+    render_page(&cmd, "dx", dir, &mut pages)?;
+    for path in &pages {
+        outln!("Página gerada: {}", path.display());
+    }
+    crate::output::set_data(serde_json::json!({
+        "dir": dir.display().to_string(),
+        "pages": pages.iter().map(|p| p.display().to_string()).collect::<Vec<_>>(),
+    }));
+    Ok(())
+}
+
+fn render_page(cmd: &clap::Command, name: &str, dir: &Path, pages: &mut Vec<PathBuf>) -> DxResult {
+    let page = cmd.clone().name(name.to_string());
+    let mut buf = Vec::new();
+    clap_mangen::Man::new(page)
+        .render(&mut buf)
+        .context(format!("Erro ao gerar a página de manual de {name}"))?;
+    let path = dir.join(format!("{name}.1"));
+    fs::write(&path, buf).context(format!("Erro ao salvar {}", path.display()))?;
+    pages.push(path);
+
+    for sub in cmd.get_subcommands() {
+        if sub.is_hide_set() || sub.get_name() == "help" {
+            continue;
+        }
+        render_page(sub, &format!("{name}-{}", sub.get_name()), dir, pages)?;
+    }
+    Ok(())
+}
+
+fn current_dir() -> PathBuf {
+    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+}
+
+/// Services declared in the current project's `.dx/docker-compose.yml`.
+pub fn service_names() -> Vec<CompletionCandidate> {
+    let compose = current_dir().join(".dx").join("docker-compose.yml");
+    fs::read_to_string(compose)
+        .map(|yaml| crate::dev_services::manifest_service_names(&yaml))
+        .unwrap_or_default()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Keys stored by `dx dev-config` in the current project.
+pub fn config_keys() -> Vec<CompletionCandidate> {
+    crate::dev_config::keys(&current_dir())
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
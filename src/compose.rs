@@ -0,0 +1,487 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Docker Compose file model.
+//!
+//! `.dx/docker-compose.yml` is written from and read into these types with
+//! serde_yaml, so quoting, multi-line values and the alternative syntaxes of
+//! Compose (`environment` as a list, long `ports`/`volumes`, `depends_on` with
+//! conditions) are left to the YAML library. Keys dx doesn't model are kept in
+//! `extra` and written back unchanged; services keep their order in the file.
+//!
+//! The generated manifest is owned by dx and rewritten on every detection.
+//! Local changes go to `.dx/docker-compose.override.yml`, which dx never
+//! writes and deep-merges into the generated file ([`ComposeFile::merge`]):
+//! maps (services, `environment`, `depends_on`, unknown keys) are merged key
+//! by key, `volumes` entries replace the generated ones with the same target,
+//! and every other value (`image`, `command`, `ports`, `profiles`...) is
+//! replaced. dx passes the manifest to Compose with `-f`, which disables
+//! Compose's own loading of the override file, so it is applied once.
+//!
+//! [`Runner`] runs Compose on the manifest, with the project's [`Runtime`].
+mod runner;
+
+pub use runner::{Runner, Runtime};
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_yaml::{Mapping, Value};
+
+/// Override files of the `.dx` directory, in lookup order.
+pub const OVERRIDE_FILES: [&str; 2] = [
+    "docker-compose.override.yml",
+    "docker-compose.override.yaml",
+];
+
+/// Manifest `dev-services run --containerized-cli` writes, with the bind
+/// mounts translated to paths of the host.
+pub const CONTAINERIZED_FILE: &str = "docker-compose.containerized.yml";
+
+/// Manifest `dev-services run --context/--host` sends to the remote engine.
+pub const REMOTE_FILE: &str = "docker-compose.remote.yml";
+
+/// Compose file `dev-services run` passes with the manifest to fit its images
+/// to the architecture of the machine (see [`crate::arch`]).
+pub const ARCH_FILE: &str = "docker-compose.arch.yml";
+
+/// Manifests of the `.dx` directory that `dev-services run` derives from the
+/// generated one for the engine it talks to; not part of the generation.
+pub const RUN_FILES: [&str; 3] = [CONTAINERIZED_FILE, REMOTE_FILE, ARCH_FILE];
+
+/// Condition `depends_on` waits for when the list syntax is used.
+pub const STARTED: &str = "service_started";
+
+/// Condition of a `depends_on` that waits for the dependency's healthcheck.
+pub const HEALTHY: &str = "service_healthy";
+
+/// A compose file.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComposeFile {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "opt_scalar"
+    )]
+    pub version: Option<String>,
+    #[serde(default, deserialize_with = "or_default")]
+    pub services: IndexMap<String, Service>,
+    /// Named volumes (`name: {}`, or with `driver`, `external`...)
+    #[serde(
+        default,
+        skip_serializing_if = "IndexMap::is_empty",
+        deserialize_with = "or_default"
+    )]
+    pub volumes: IndexMap<String, Option<Mapping>>,
+    /// Named networks, declared like the volumes
+    #[serde(
+        default,
+        skip_serializing_if = "IndexMap::is_empty",
+        deserialize_with = "or_default"
+    )]
+    pub networks: IndexMap<String, Option<Mapping>>,
+    #[serde(flatten)]
+    pub extra: IndexMap<String, Value>,
+}
+
+/// A service of a compose file.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Service {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub image: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<Command>,
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "or_default"
+    )]
+    pub profiles: Vec<String>,
+    /// Variables, from the map or the `KEY=value` list syntax
+    #[serde(
+        default,
+        skip_serializing_if = "BTreeMap::is_empty",
+        deserialize_with = "environment"
+    )]
+    pub environment: BTreeMap<String, String>,
+    /// Short syntax (`[ip:][host:]container[/protocol]`)
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "ports"
+    )]
+    pub ports: Vec<String>,
+    /// Short syntax (`source:target[:mode]`)
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "volumes"
+    )]
+    pub volumes: Vec<String>,
+    #[serde(default, skip_serializing_if = "DependsOn::is_empty")]
+    pub depends_on: DependsOn,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub healthcheck: Option<Healthcheck>,
+    /// Networks the service joins (aliases of the map syntax are not kept)
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "networks"
+    )]
+    pub networks: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart: Option<String>,
+    #[serde(flatten)]
+    pub extra: IndexMap<String, Value>,
+}
+
+/// `command` or healthcheck `test`: a shell string or an exec list.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Command {
+    Shell(String),
+    Exec(Vec<String>),
+}
+
+/// Healthcheck of a service; durations as Compose writes them (`10s`, `1m30s`).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Healthcheck {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test: Option<Command>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_period: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disable: bool,
+}
+
+/// `depends_on`: service -> condition (`service_started`, `service_healthy`,
+/// `service_completed_successfully`). Written as a list when every condition
+/// is [`STARTED`], as Compose's map syntax otherwise.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DependsOn(pub IndexMap<String, String>);
+
+impl DependsOn {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Serialize for DependsOn {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.values().all(|c| c == STARTED) {
+            return serializer.collect_seq(self.0.keys());
+        }
+        serializer.collect_map(
+            self.0
+                .iter()
+                .map(|(name, condition)| (name, BTreeMap::from([("condition", condition)]))),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for DependsOn {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut depends_on = IndexMap::new();
+        match Value::deserialize(deserializer)? {
+            Value::Null => {}
+            Value::Sequence(names) => {
+                for name in names {
+                    depends_on.insert(key(name)?, STARTED.to_string());
+                }
+            }
+            Value::Mapping(map) => {
+                for (name, options) in map {
+                    let condition = options
+                        .get("condition")
+                        .and_then(Value::as_str)
+                        .unwrap_or(STARTED);
+                    depends_on.insert(key(name)?, condition.to_string());
+                }
+            }
+            _ => return Err(D::Error::custom("depends_on deve ser uma lista ou um mapa")),
+        }
+        Ok(DependsOn(depends_on))
+    }
+}
+
+impl ComposeFile {
+    pub fn parse(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        // An empty document has no services rather than being an error
+        if yaml.trim().is_empty() {
+            return Ok(ComposeFile::default());
+        }
+        serde_yaml::from_str(yaml)
+    }
+
+    pub fn to_yaml(&self) -> String {
+        serde_yaml::to_string(self).expect("compose model serializes to YAML")
+    }
+
+    /// The override file in `dx_dir` and its contents, with the `${VAR}`
+    /// placeholders dx can resolve (see [`crate::interpolate`]), if there is
+    /// one. An invalid file is logged and ignored.
+    pub fn load_override(dx_dir: &Path) -> Option<(PathBuf, ComposeFile)> {
+        let path = OVERRIDE_FILES
+            .iter()
+            .map(|name| dx_dir.join(name))
+            .find(|path| path.is_file())?;
+        let yaml = std::fs::read_to_string(&path).ok()?;
+        let project_dir = dx_dir.parent().unwrap_or(dx_dir);
+        let yaml =
+            crate::interpolate::project(project_dir, &yaml, crate::interpolate::Target::Compose);
+        match ComposeFile::parse(&yaml) {
+            Ok(file) => Some((path, file)),
+            Err(e) => {
+                tracing::warn!("{} inválido, ignorado: {e}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Deep-merge `overrides` into this file (see the module docs).
+    pub fn merge(&mut self, overrides: &ComposeFile) {
+        if overrides.version.is_some() {
+            self.version = overrides.version.clone();
+        }
+        for (name, service) in &overrides.services {
+            match self.services.get_mut(name) {
+                Some(base) => base.merge(service),
+                None => {
+                    self.services.insert(name.clone(), service.clone());
+                }
+            }
+        }
+        self.volumes.extend(
+            overrides
+                .volumes
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+        self.networks.extend(
+            overrides
+                .networks
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+        merge_extra(&mut self.extra, &overrides.extra);
+    }
+}
+
+impl Service {
+    fn merge(&mut self, overrides: &Service) {
+        if !overrides.image.is_empty() {
+            self.image = overrides.image.clone();
+        }
+        if overrides.command.is_some() {
+            self.command = overrides.command.clone();
+        }
+        if !overrides.profiles.is_empty() {
+            self.profiles = overrides.profiles.clone();
+        }
+        self.environment.extend(overrides.environment.clone());
+        if !overrides.ports.is_empty() {
+            self.ports = overrides.ports.clone();
+        }
+        let target = |volume: &str| {
+            let mut parts = volume.split(':');
+            let first = parts.next().unwrap_or_default().to_string();
+            parts.next().map_or(first, str::to_string)
+        };
+        for volume in &overrides.volumes {
+            self.volumes.retain(|v| target(v) != target(volume));
+            self.volumes.push(volume.clone());
+        }
+        self.depends_on.0.extend(overrides.depends_on.0.clone());
+        if overrides.healthcheck.is_some() {
+            self.healthcheck = overrides.healthcheck.clone();
+        }
+        if !overrides.networks.is_empty() {
+            self.networks = overrides.networks.clone();
+        }
+        if overrides.restart.is_some() {
+            self.restart = overrides.restart.clone();
+        }
+        merge_extra(&mut self.extra, &overrides.extra);
+    }
+
+    /// Keys `overrides` sets on this service, for reports.
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = [
+            ("image", !self.image.is_empty()),
+            ("command", self.command.is_some()),
+            ("profiles", !self.profiles.is_empty()),
+            ("environment", !self.environment.is_empty()),
+            ("ports", !self.ports.is_empty()),
+            ("volumes", !self.volumes.is_empty()),
+            ("depends_on", !self.depends_on.is_empty()),
+            ("healthcheck", self.healthcheck.is_some()),
+            ("networks", !self.networks.is_empty()),
+            ("restart", self.restart.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, set)| *set)
+        .map(|(key, _)| key.to_string())
+        .collect();
+        keys.extend(self.extra.keys().cloned());
+        keys
+    }
+}
+
+fn merge_extra(base: &mut IndexMap<String, Value>, overrides: &IndexMap<String, Value>) {
+    for (key, value) in overrides {
+        match base.get_mut(key) {
+            Some(existing) => merge_value(existing, value),
+            None => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Maps merged recursively; anything else replaced.
+fn merge_value(base: &mut Value, overrides: &Value) {
+    match (base, overrides) {
+        (Value::Mapping(base), Value::Mapping(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(key) {
+                    Some(existing) => merge_value(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides.clone(),
+    }
+}
+
+/// A scalar as a string (`8080`, `true` and `3.8` are numbers/bools in YAML).
+fn scalar(value: Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Null => Some(String::new()),
+        Value::Tagged(tagged) => scalar(tagged.value),
+        Value::Sequence(_) | Value::Mapping(_) => None,
+    }
+}
+
+fn key<E: serde::de::Error>(value: Value) -> Result<String, E> {
+    scalar(value).ok_or_else(|| E::custom("esperado um valor simples"))
+}
+
+fn or_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+fn opt_scalar<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Null => Ok(None),
+        value => key(value).map(Some),
+    }
+}
+
+fn environment<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, String>, D::Error> {
+    let mut env = BTreeMap::new();
+    match Value::deserialize(deserializer)? {
+        Value::Null => {}
+        Value::Mapping(map) => {
+            for (name, value) in map {
+                env.insert(key(name)?, key(value)?);
+            }
+        }
+        // `KEY=value`; a bare `KEY` takes the value from the shell
+        Value::Sequence(entries) => {
+            for entry in entries {
+                let entry: String = key(entry)?;
+                let (name, value) = entry.split_once('=').unwrap_or((&entry, ""));
+                env.insert(name.to_string(), value.to_string());
+            }
+        }
+        _ => {
+            return Err(D::Error::custom(
+                "environment deve ser uma lista ou um mapa",
+            ));
+        }
+    }
+    Ok(env)
+}
+
+/// Entries of a list of scalars or long-syntax maps, the maps converted by `long`.
+fn entries<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    long: fn(&Mapping) -> Option<String>,
+) -> Result<Vec<String>, D::Error> {
+    let Some(list) = Option::<Vec<Value>>::deserialize(deserializer)? else {
+        return Ok(Vec::new());
+    };
+    list.into_iter()
+        .map(|entry| match entry {
+            Value::Mapping(map) => {
+                long(&map).ok_or_else(|| D::Error::custom("entrada longa sem target"))
+            }
+            value => key(value),
+        })
+        .collect()
+}
+
+fn field(map: &Mapping, name: &str) -> Option<String> {
+    map.get(name)
+        .cloned()
+        .and_then(scalar)
+        .filter(|s| !s.is_empty())
+}
+
+fn ports<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    entries(deserializer, |map| {
+        let mut port = field(map, "target")?;
+        if let Some(published) = field(map, "published") {
+            port = format!("{published}:{port}");
+            if let Some(ip) = field(map, "host_ip") {
+                port = format!("{ip}:{port}");
+            }
+        }
+        if let Some(protocol) = field(map, "protocol") {
+            port = format!("{port}/{protocol}");
+        }
+        Some(port)
+    })
+}
+
+fn volumes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    entries(deserializer, |map| {
+        let target = field(map, "target")?;
+        let mut volume = match field(map, "source") {
+            Some(source) => format!("{source}:{target}"),
+            None => target,
+        };
+        if map.get("read_only").and_then(Value::as_bool) == Some(true) {
+            volume.push_str(":ro");
+        }
+        Some(volume)
+    })
+}
+
+fn networks<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Null => Ok(Vec::new()),
+        Value::Sequence(names) => names.into_iter().map(key).collect(),
+        Value::Mapping(map) => map.into_iter().map(|(name, _)| key(name)).collect(),
+        _ => Err(D::Error::custom("networks deve ser uma lista ou um mapa")),
+    }
+}
@@ -0,0 +1,603 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Running Compose on the Dev Services manifest.
+//!
+//! dx drives Docker (`docker compose`, falling back to the legacy
+//! `docker-compose`), Podman (`podman compose`, falling back to
+//! `podman-compose`) or nerdctl (`nerdctl compose`). The runtime comes from
+//! `--runtime`, else from `container.runtime` in `.dx/config.json` (`dx
+//! dev-config add container.runtime podman`), else it is the first of
+//! `docker`, `podman` and `nerdctl` found in the PATH. A remote engine (see
+//! [`crate::remote`]) is always reached through Docker.
+//!
+//! [`Runner`] holds the files and profiles of one project and runs the
+//! Compose commands on them with the fallbacks of its runtime; when none
+//! works, the [`Error`] says why the last one didn't.
+use std::cell::OnceCell;
+use std::ffi::OsStr;
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Output, Stdio};
+use std::time::Duration;
+
+use crate::error::{DxError, DxResult};
+use crate::logs::Prefixer;
+
+/// Key of `.dx/config.json` with the project's runtime.
+pub const RUNTIME_KEY: &str = "container.runtime";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Runtime {
+    Docker,
+    Podman,
+    Nerdctl,
+}
+
+/// One way of running Compose: a program and the arguments that come before
+/// the Compose ones.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Invocation {
+    program: &'static str,
+    prefix: &'static [&'static str],
+}
+
+const DOCKER: &[Invocation] = &[
+    Invocation {
+        program: "docker",
+        prefix: &["compose"],
+    },
+    Invocation {
+        program: "docker-compose",
+        prefix: &[],
+    },
+];
+
+const PODMAN: &[Invocation] = &[
+    Invocation {
+        program: "podman",
+        prefix: &["compose"],
+    },
+    Invocation {
+        program: "podman-compose",
+        prefix: &[],
+    },
+];
+
+const NERDCTL: &[Invocation] = &[Invocation {
+    program: "nerdctl",
+    prefix: &["compose"],
+}];
+
+impl Runtime {
+    pub fn name(self) -> &'static str {
+        match self {
+            Runtime::Docker => "Docker",
+            Runtime::Podman => "Podman",
+            Runtime::Nerdctl => "nerdctl",
+        }
+    }
+
+    fn program(self) -> &'static str {
+        match self {
+            Runtime::Docker => "docker",
+            Runtime::Podman => "podman",
+            Runtime::Nerdctl => "nerdctl",
+        }
+    }
+
+    /// The runtime's own CLI, not spawned. Docker talks to the engine of the
+    /// last `run` (see [`crate::remote`]).
+    fn command(self, project_dir: &Path) -> Command {
+        match self {
+            Runtime::Docker => crate::remote::command(project_dir, self.program()),
+            _ => Command::new(self.program()),
+        }
+    }
+
+    /// The ways of running Compose with this runtime, in order of preference.
+    pub fn invocations(self) -> &'static [Invocation] {
+        match self {
+            Runtime::Docker => DOCKER,
+            Runtime::Podman => PODMAN,
+            Runtime::Nerdctl => NERDCTL,
+        }
+    }
+
+    /// The runtime named `name` (`docker`, `podman` or `nerdctl`).
+    fn parse(name: &str) -> Option<Runtime> {
+        [Runtime::Docker, Runtime::Podman, Runtime::Nerdctl]
+            .into_iter()
+            .find(|r| r.program() == name.trim().to_lowercase())
+    }
+
+    /// The runtime of `project_dir`: `flag`, else `.dx/config.json`, else
+    /// the first one installed.
+    pub fn resolve(project_dir: &Path, flag: Option<Runtime>) -> DxResult<Runtime> {
+        if let Some(runtime) = flag {
+            return Ok(runtime);
+        }
+        if let Some(value) = crate::dev_config::entries(project_dir).get(RUNTIME_KEY) {
+            return Runtime::parse(value).ok_or_else(|| {
+                DxError::InvalidInput(format!(
+                    "{RUNTIME_KEY} inválido em .dx/config.json: '{value}' (use docker, podman ou nerdctl)."
+                ))
+            });
+        }
+        Ok([Runtime::Docker, Runtime::Podman, Runtime::Nerdctl]
+            .into_iter()
+            .find(|r| crate::toolchain::on_path(r.program()))
+            .unwrap_or(Runtime::Docker))
+    }
+
+    /// What has to be running for Compose to work.
+    fn engine(self) -> &'static str {
+        match self {
+            Runtime::Docker => "Docker Desktop",
+            Runtime::Podman => "Podman (podman machine, no macOS e Windows)",
+            Runtime::Nerdctl => "containerd (Rancher Desktop, Lima ou colima)",
+        }
+    }
+
+    /// How to install Compose for this runtime.
+    fn hints(self) -> &'static str {
+        match self {
+            Runtime::Docker => {
+                " - Instale o Docker Desktop para Windows\n - Reabra o terminal após a instalação para atualizar o PATH\n - Teste no terminal: 'docker --version' e 'docker compose version'"
+            }
+            Runtime::Podman => {
+                " - Instale o Podman e um provedor do Compose (docker-compose ou podman-compose)\n - Teste no terminal: 'podman --version' e 'podman compose version'"
+            }
+            Runtime::Nerdctl => {
+                " - Instale o nerdctl completo (com o plugin do Compose) ou o Rancher Desktop\n - Teste no terminal: 'nerdctl --version' e 'nerdctl compose version'"
+            }
+        }
+    }
+}
+
+impl Invocation {
+    /// `docker compose`, `podman-compose`...
+    pub fn label(&self) -> String {
+        std::iter::once(self.program)
+            .chain(self.prefix.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The command, without the Compose arguments. Docker talks to the engine
+    /// of the last `run` (see [`crate::remote`]).
+    fn command(&self, project_dir: &Path) -> Command {
+        let mut command = if self.program.starts_with("docker") {
+            crate::remote::command(project_dir, self.program)
+        } else {
+            Command::new(self.program)
+        };
+        command.args(self.prefix);
+        command
+    }
+
+    /// `<label> -f <file>... <args>` as typed in the platform's shell.
+    fn command_line(&self, files: &[&Path], args: &[&str]) -> String {
+        let mut parts: Vec<&OsStr> = std::iter::once(self.program)
+            .chain(self.prefix.iter().copied())
+            .map(OsStr::new)
+            .collect();
+        for file in files {
+            parts.extend([OsStr::new("-f"), file.as_os_str()]);
+        }
+        parts.extend(args.iter().map(OsStr::new));
+        crate::platform::command_line(parts)
+    }
+}
+
+/// Why Compose couldn't run a command.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The last way of running Compose ran and failed.
+    #[error(
+        "Falha ao executar '{invocation}'. Verifique se o {} está instalado e em execução.",
+        runtime.engine()
+    )]
+    Failed {
+        runtime: Runtime,
+        invocation: String,
+    },
+    /// No way of running Compose could be started.
+    #[error("Erro ao tentar executar '{invocation}': {source}\nDicas:\n{}", runtime.hints())]
+    Unavailable {
+        runtime: Runtime,
+        invocation: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl From<Error> for DxError {
+    fn from(e: Error) -> DxError {
+        DxError::Tool(e.to_string())
+    }
+}
+
+/// Run `attempt` with each way of running Compose of `runtime` until one
+/// `succeeded`, warning before each fallback when `warn`.
+fn first<T>(
+    runtime: Runtime,
+    warn: bool,
+    mut attempt: impl FnMut(&Invocation) -> io::Result<T>,
+    succeeded: impl Fn(&T) -> bool,
+) -> Result<(&'static Invocation, T), Error> {
+    let invocations = runtime.invocations();
+    let mut error = None;
+    for (i, invocation) in invocations.iter().enumerate() {
+        let fallback = invocations
+            .get(i + 1)
+            .map(|next| format!(" Tentando '{}'...", next.label()))
+            .unwrap_or_default();
+        match attempt(invocation) {
+            Ok(result) if succeeded(&result) => return Ok((invocation, result)),
+            Ok(_) => {
+                if warn {
+                    tracing::warn!("Falha ao executar '{}'.{fallback}", invocation.label());
+                }
+                error = Some(Error::Failed {
+                    runtime,
+                    invocation: invocation.label(),
+                });
+            }
+            Err(source) => {
+                if warn {
+                    tracing::warn!(
+                        "Não foi possível executar '{}': {source}.{fallback}",
+                        invocation.label()
+                    );
+                }
+                error = Some(Error::Unavailable {
+                    runtime,
+                    invocation: invocation.label(),
+                    source,
+                });
+            }
+        }
+    }
+    Err(error.expect("every runtime has a way of running Compose"))
+}
+
+/// Compose commands on the manifest of a project, run with the fallbacks of
+/// its runtime. Each returns the way of running Compose that worked.
+pub struct Runner<'a> {
+    project_dir: &'a Path,
+    runtime: Runtime,
+    files: Vec<&'a Path>,
+    profiles: String,
+    /// The way of running Compose that answered `version`, once asked
+    working: OnceCell<&'static Invocation>,
+}
+
+impl<'a> Runner<'a> {
+    /// Runner of the compose `file`, with every profile it defines active.
+    pub fn new(project_dir: &'a Path, runtime: Runtime, file: &'a Path) -> Self {
+        Runner {
+            project_dir,
+            runtime,
+            files: vec![file],
+            profiles: crate::dev_services::all_profiles(file),
+            working: OnceCell::new(),
+        }
+    }
+
+    /// Also merge the compose `file` (`-f`) over the previous ones.
+    pub fn file(mut self, file: &'a Path) -> Self {
+        self.files.push(file);
+        self
+    }
+
+    /// Activate `profiles` (`COMPOSE_PROFILES`, comma-separated) only.
+    pub fn profiles(mut self, profiles: impl Into<String>) -> Self {
+        self.profiles = profiles.into();
+        self
+    }
+
+    /// `args` run with `invocation` on the files and profiles, not spawned.
+    fn command_with(&self, invocation: &Invocation, args: &[&str]) -> Command {
+        tracing::debug!(
+            "executando: COMPOSE_PROFILES={} {}",
+            self.profiles,
+            invocation.command_line(&self.files, args)
+        );
+        let mut command = invocation.command(self.project_dir);
+        for file in &self.files {
+            command.arg("-f").arg(file);
+        }
+        command.env("COMPOSE_PROFILES", &self.profiles).args(args);
+        command
+    }
+
+    /// Run `args` and then `services` on the terminal, timed under `label`.
+    fn attached(
+        &self,
+        label: &str,
+        args: &[&str],
+        services: &[String],
+    ) -> Result<&'static Invocation, Error> {
+        let attempt = |invocation: &Invocation| {
+            crate::progress::timed(label, || {
+                self.command_with(invocation, args)
+                    .args(services)
+                    .stdin(Stdio::inherit())
+                    .stdout(Stdio::inherit())
+                    .stderr(Stdio::inherit())
+                    .status()
+            })
+        };
+        first(self.runtime, true, attempt, ExitStatus::success).map(|(invocation, _)| invocation)
+    }
+
+    /// `up -d` of `services` (all when empty).
+    pub fn up(&self, services: &[String]) -> Result<&'static Invocation, Error> {
+        self.attached("compose up", &["up", "-d"], services)
+    }
+
+    /// `stop` of `services` (all when empty).
+    pub fn stop(&self, services: &[String]) -> Result<&'static Invocation, Error> {
+        self.attached("compose stop", &["stop"], services)
+    }
+
+    /// `restart` of `services` (all when empty).
+    pub fn restart(&self, services: &[String]) -> Result<&'static Invocation, Error> {
+        self.attached("compose restart", &["restart"], services)
+    }
+
+    /// `up -d --no-deps` of the services of `scales`, each with its number of
+    /// containers.
+    pub fn scale(&self, scales: &[(String, u32)]) -> Result<&'static Invocation, Error> {
+        let mut args = vec!["up", "-d", "--no-deps"];
+        let scales: Vec<(&str, String)> = scales
+            .iter()
+            .map(|(name, n)| (name.as_str(), format!("{name}={n}")))
+            .collect();
+        for (_, scale) in &scales {
+            args.extend(["--scale", scale]);
+        }
+        args.extend(scales.iter().map(|(name, _)| *name));
+        self.attached("compose up", &args, &[])
+    }
+
+    /// `down`, also removing the named volumes when `volumes`.
+    pub fn down(&self, volumes: bool) -> Result<&'static Invocation, Error> {
+        let args: &[&str] = if volumes { &["down", "-v"] } else { &["down"] };
+        self.attached("compose down", args, &[])
+    }
+
+    /// Output of `ps --all --format json`, asked quietly.
+    pub fn ps(&self) -> Result<String, Error> {
+        let attempt = |invocation: &Invocation| {
+            self.command_with(invocation, &["ps", "--all", "--format", "json"])
+                .stdin(Stdio::null())
+                .stderr(Stdio::null())
+                .output()
+        };
+        let (_, output) = first(self.runtime, false, attempt, |o: &Output| {
+            o.status.success()
+        })?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// `logs` with `args`, each line shown with its service's prefix. Logs
+    /// that were shown count as success even when they end abnormally (e.g.
+    /// Ctrl+C during `--follow`).
+    pub fn logs(&self, args: &[&str], prefixer: &Prefixer) -> Result<&'static Invocation, Error> {
+        let attempt = |invocation: &Invocation| {
+            crate::logs::stream(&mut self.command_with(invocation, args), prefixer)
+        };
+        let succeeded = |(status, printed): &(ExitStatus, bool)| status.success() || *printed;
+        first(self.runtime, true, attempt, succeeded).map(|(invocation, _)| invocation)
+    }
+
+    /// Output of `logs` of `services` written in the last `since`, read
+    /// quietly.
+    pub fn recent_logs(&self, since: Duration, services: &[String]) -> Result<String, Error> {
+        let invocation = self.working()?;
+        let since = format!("{}s", since.as_secs() + 1);
+        let output = self
+            .command_with(invocation, &["logs", "--no-color", "--since", &since])
+            .args(services)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|source| Error::Unavailable {
+                runtime: self.runtime,
+                invocation: invocation.label(),
+                source,
+            })?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// The first way of running Compose that answers `version`, asked once.
+    fn working(&self) -> Result<&'static Invocation, Error> {
+        if let Some(invocation) = self.working.get() {
+            return Ok(invocation);
+        }
+        let attempt = |invocation: &Invocation| {
+            invocation
+                .command(self.project_dir)
+                .arg("version")
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+        };
+        let (invocation, _) = first(self.runtime, false, attempt, ExitStatus::success)?;
+        Ok(self.working.get_or_init(|| invocation))
+    }
+
+    /// `args` on the files and profiles with the way of running Compose that
+    /// works, not spawned: the caller picks its stdio.
+    pub fn command(&self, args: &[&str]) -> Result<Command, Error> {
+        Ok(self.command_with(self.working()?, args))
+    }
+
+    /// `args` on the files as typed in the platform's shell, with the way of
+    /// running Compose that answered (the preferred one until one did).
+    pub fn command_line(&self, args: &[&str]) -> String {
+        let invocation = match self.working.get() {
+            Some(invocation) => invocation,
+            None => &self.runtime.invocations()[0],
+        };
+        invocation.command_line(&self.files, args)
+    }
+
+    /// The runtime's own CLI (`docker run`...) for what Compose doesn't do,
+    /// not spawned.
+    pub fn runtime_command(&self) -> Command {
+        self.runtime.command(self.project_dir)
+    }
+
+    /// `exec -T` into `service` with the `env` variables, not spawned: the
+    /// caller adds the command and picks its stdio.
+    pub fn exec(&self, service: &str, env: &[(&str, &str)]) -> Result<Command, Error> {
+        let mut command = self.command(&["exec", "-T"])?;
+        for (key, value) in env {
+            command.arg("-e").arg(format!("{key}={value}"));
+        }
+        command.arg(service);
+        Ok(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command: &Command) -> Vec<String> {
+        std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn runtimes_fall_back_to_their_legacy_cli() {
+        let labels = |runtime: Runtime| -> Vec<String> {
+            runtime
+                .invocations()
+                .iter()
+                .map(Invocation::label)
+                .collect()
+        };
+        assert_eq!(
+            labels(Runtime::Docker),
+            ["docker compose", "docker-compose"]
+        );
+        assert_eq!(
+            labels(Runtime::Podman),
+            ["podman compose", "podman-compose"]
+        );
+        assert_eq!(labels(Runtime::Nerdctl), ["nerdctl compose"]);
+        assert_eq!(Runtime::parse(" Podman "), Some(Runtime::Podman));
+        assert_eq!(Runtime::parse("lxc"), None);
+    }
+
+    #[test]
+    fn commands_carry_the_files_and_profiles() {
+        let dir = Path::new("/project");
+        let manifest = Path::new("/project/.dx/docker-compose.yml");
+        let labels = Path::new("/project/.dx/auto-stop/labels.yml");
+        let runner = Runner::new(dir, Runtime::Podman, manifest)
+            .file(labels)
+            .profiles("db,cache");
+        let command = runner.command_with(&PODMAN[1], &["up", "-d"]);
+        assert_eq!(
+            args(&command),
+            [
+                "podman-compose",
+                "-f",
+                "/project/.dx/docker-compose.yml",
+                "-f",
+                "/project/.dx/auto-stop/labels.yml",
+                "up",
+                "-d"
+            ]
+        );
+        let profiles = command
+            .get_envs()
+            .find(|(k, _)| *k == "COMPOSE_PROFILES")
+            .and_then(|(_, v)| v);
+        assert_eq!(profiles, Some(OsStr::new("db,cache")));
+        assert_eq!(
+            args(&runner.command_with(&NERDCTL[0], &["ps"])),
+            [
+                "nerdctl",
+                "compose",
+                "-f",
+                "/project/.dx/docker-compose.yml",
+                "-f",
+                "/project/.dx/auto-stop/labels.yml",
+                "ps"
+            ]
+        );
+    }
+
+    #[test]
+    fn command_lines_name_the_runtime() {
+        let dir = Path::new("/project");
+        let manifest = Path::new("/project/.dx/docker-compose.yml");
+        let runner = Runner::new(dir, Runtime::Podman, manifest);
+        assert_eq!(
+            runner.command_line(&["ps"]),
+            "podman compose -f /project/.dx/docker-compose.yml ps"
+        );
+        runner.working.set(&PODMAN[1]).unwrap();
+        assert_eq!(
+            runner.command_line(&["ps"]),
+            "podman-compose -f /project/.dx/docker-compose.yml ps"
+        );
+    }
+
+    #[test]
+    fn the_last_failure_is_the_error() {
+        let mut tried = Vec::new();
+        let result = first(
+            Runtime::Docker,
+            false,
+            |invocation| {
+                tried.push(invocation.label());
+                match invocation.program {
+                    "docker" => Ok(false),
+                    _ => Err(io::Error::from(io::ErrorKind::NotFound)),
+                }
+            },
+            |ok| *ok,
+        );
+        assert_eq!(tried, ["docker compose", "docker-compose"]);
+        let error = result.unwrap_err();
+        assert!(matches!(
+            &error,
+            Error::Unavailable { runtime: Runtime::Docker, invocation, .. } if invocation == "docker-compose"
+        ));
+        let error = DxError::from(error);
+        assert_eq!(error.exit_code(), 7);
+        assert!(
+            error
+                .to_string()
+                .starts_with("Erro ao tentar executar 'docker-compose': ")
+        );
+
+        let error = first(Runtime::Nerdctl, false, |_| Ok(1), |code| *code == 0).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Falha ao executar 'nerdctl compose'. Verifique se o containerd (Rancher Desktop, Lima ou colima) está instalado e em execução."
+        );
+    }
+
+    #[test]
+    fn the_first_working_invocation_wins() {
+        let (invocation, result) = first(
+            Runtime::Podman,
+            false,
+            |invocation| Ok(invocation.program == "podman-compose"),
+            |ok| *ok,
+        )
+        .unwrap();
+        assert_eq!(invocation, &PODMAN[1]);
+        assert!(result);
+    }
+}
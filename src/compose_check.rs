@@ -0,0 +1,398 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Validation of the Dev Services manifest before `dx dev-services run`.
+//!
+//! Compose reports a broken manifest halfway through `up`, with messages that
+//! name neither the file nor the line. The manifest is checked against the
+//! serde model of [`crate::compose`] instead of `docker compose config`, so
+//! the check needs no Docker and applies the same to the containerized and
+//! remote variants derived from it. Only mistakes Compose rejects are
+//! reported: YAML and model errors, services without an image, malformed
+//! ports and volumes, references to undeclared services, volumes and
+//! networks, invalid restart policies and host ports published twice by the
+//! services of the run. Values with `${...}` interpolation are left to
+//! Compose. Each problem carries the line of the file it was found at and a
+//! hint.
+use std::path::Path;
+
+use crate::compose::{ComposeFile, Service};
+use crate::error::{DxError, DxResult};
+
+/// Top-level keys of the Compose specification, besides `x-*` extensions.
+const TOP_LEVEL: &[&str] = &[
+    "version", "name", "services", "volumes", "networks", "configs", "secrets", "include", "models",
+];
+
+/// A problem of the manifest.
+#[derive(Debug, PartialEq)]
+pub struct Problem {
+    /// 1-based line, when it could be found
+    pub line: Option<usize>,
+    pub message: String,
+    pub hint: String,
+}
+
+/// A step of the path to a node of the YAML document.
+#[derive(Clone, Copy)]
+enum Key<'a> {
+    Name(&'a str),
+    Item(usize),
+}
+
+/// Line (1-based) of the node at `path`, or of its deepest ancestor found.
+/// Only the block style is followed; entries of flow collections (`[a, b]`)
+/// point to their key.
+fn locate(yaml: &str, path: &[Key]) -> Option<usize> {
+    let lines: Vec<&str> = yaml.lines().collect();
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let content = |line: &&str| {
+        let trimmed = line.trim_start();
+        !trimmed.is_empty() && !trimmed.starts_with('#') && !trimmed.starts_with("---")
+    };
+    let mut found = None;
+    let mut start = 0;
+    let mut parent: Option<usize> = None;
+    for key in path {
+        // Lines of the parent's block; a list may sit at the parent's indent
+        let block = lines
+            .iter()
+            .enumerate()
+            .skip(start)
+            .filter(|(_, line)| content(line))
+            .take_while(|(_, line)| {
+                parent.is_none_or(|p| {
+                    indent(line) > p || (indent(line) == p && line.trim_start().starts_with('-'))
+                })
+            });
+        let hit = match *key {
+            Key::Name(name) => {
+                let mut child = None;
+                block
+                    .filter(|(_, line)| *child.get_or_insert(indent(line)) == indent(line))
+                    .find(|(_, line)| {
+                        let trimmed = line.trim_start();
+                        [name.to_string(), format!("\"{name}\""), format!("'{name}'")]
+                            .iter()
+                            .any(|k| {
+                                trimmed
+                                    .strip_prefix(k.as_str())
+                                    .is_some_and(|r| r.trim_start().starts_with(':'))
+                            })
+                    })
+            }
+            Key::Item(index) => {
+                let mut child = None;
+                block
+                    .filter(|(_, line)| line.trim_start().starts_with('-'))
+                    .filter(|(_, line)| *child.get_or_insert(indent(line)) == indent(line))
+                    .nth(index)
+            }
+        };
+        let Some((number, line)) = hit else {
+            break;
+        };
+        found = Some(number + 1);
+        start = number + 1;
+        parent = Some(indent(line));
+    }
+    found
+}
+
+fn problem(line: Option<usize>, message: String, hint: impl Into<String>) -> Problem {
+    Problem {
+        line,
+        message,
+        hint: hint.into(),
+    }
+}
+
+/// Whether `value` is left to Compose's interpolation.
+fn interpolated(value: &str) -> bool {
+    value.contains('$')
+}
+
+fn port_number(value: &str) -> bool {
+    value.parse::<u16>().is_ok_and(|p| p > 0)
+}
+
+/// `port` or `first-last`.
+fn port_range(value: &str) -> Option<(u16, u16)> {
+    let (first, last) = value.split_once('-').unwrap_or((value, value));
+    if !port_number(first) || !port_number(last) {
+        return None;
+    }
+    let (first, last) = (first.parse().ok()?, last.parse().ok()?);
+    (first <= last).then_some((first, last))
+}
+
+/// A published port: host IP (empty when unset), host ports, protocol.
+struct Published {
+    ip: String,
+    host: Option<(u16, u16)>,
+    protocol: String,
+}
+
+/// Parse the short syntax `[ip:][host:]container[/protocol]`.
+impl Published {
+    /// Whether both bind the same host port.
+    fn clashes(&self, other: &Published) -> bool {
+        let (Some(range), Some(other_range)) = (self.host, other.host) else {
+            return false;
+        };
+        self.protocol == other.protocol
+            && (self.ip.is_empty() || other.ip.is_empty() || self.ip == other.ip)
+            && range.0 <= other_range.1
+            && other_range.0 <= range.1
+    }
+}
+
+fn parse_port(port: &str) -> Option<Published> {
+    let (port, protocol) = port.split_once('/').unwrap_or((port, "tcp"));
+    if !matches!(protocol, "tcp" | "udp" | "sctp") {
+        return None;
+    }
+    // IPv6 host addresses are bracketed: [::1]:8080:80
+    let (ip, rest) = match port.strip_prefix('[') {
+        Some(rest) => {
+            let (ip, rest) = rest.split_once("]:")?;
+            (ip, rest)
+        }
+        None => match port.matches(':').count() {
+            2 => port.split_once(':')?,
+            _ => ("", port),
+        },
+    };
+    let (host, container) = rest.rsplit_once(':').unwrap_or(("", rest));
+    port_range(container)?;
+    let host = match host {
+        "" => None,
+        host => Some(port_range(host)?),
+    };
+    let ip = if ip == "0.0.0.0" { "" } else { ip };
+    Some(Published {
+        ip: ip.to_string(),
+        host,
+        protocol: protocol.to_string(),
+    })
+}
+
+/// Whether the volume source names a path of the machine rather than a
+/// named volume.
+fn is_path(source: &str) -> bool {
+    source.starts_with(['.', '/', '~', '\\']) || source.contains('/')
+}
+
+fn restart_policy(value: &str) -> bool {
+    match value.strip_prefix("on-failure") {
+        Some(retries) => {
+            retries.is_empty()
+                || retries
+                    .strip_prefix(':')
+                    .is_some_and(|n| n.parse::<u32>().is_ok())
+        }
+        None => matches!(value, "no" | "always" | "unless-stopped"),
+    }
+}
+
+/// Whether `service` starts when `profiles` are requested (none: all).
+fn active(service: &Service, profiles: &[String]) -> bool {
+    profiles.is_empty()
+        || service.profiles.is_empty()
+        || service.profiles.iter().any(|p| profiles.contains(p))
+}
+
+/// Problems of the manifest `yaml` for a run of `profiles` (none: all).
+pub fn check(yaml: &str, profiles: &[String]) -> Vec<Problem> {
+    let file = match ComposeFile::parse(yaml) {
+        Ok(file) => file,
+        Err(e) => {
+            let message = e.to_string();
+            let message = message
+                .rsplit_once(" at line ")
+                .map_or(message.as_str(), |(message, _)| message);
+            let tabs = yaml.lines().any(|l| {
+                l.chars()
+                    .take_while(|c| c.is_whitespace())
+                    .any(|c| c == '\t')
+            });
+            let hint = if tabs {
+                "Use espaços na indentação; o YAML não aceita tabs."
+            } else {
+                "Verifique a indentação e as aspas; valores com ':' ou '#' precisam estar entre aspas."
+            };
+            return vec![problem(
+                e.location().map(|l| l.line()),
+                format!("YAML inválido: {message}"),
+                hint,
+            )];
+        }
+    };
+    let mut problems = Vec::new();
+
+    for key in file.extra.keys() {
+        if !TOP_LEVEL.contains(&key.as_str()) && !key.starts_with("x-") {
+            problems.push(problem(
+                locate(yaml, &[Key::Name(key)]),
+                format!("chave desconhecida no topo do arquivo: '{key}'"),
+                "Serviços ficam dentro de 'services:' (verifique a indentação); no topo valem version, name, services, volumes, networks, configs, secrets, include e extensões x-*.",
+            ));
+        }
+    }
+
+    let names: Vec<&str> = file.services.keys().map(String::as_str).collect();
+    // Host ports published by the services of the run, with their lines
+    let mut published: Vec<(&str, Option<usize>, Published)> = Vec::new();
+    for (name, service) in &file.services {
+        let at = |path: &[Key]| {
+            let mut full = vec![Key::Name("services"), Key::Name(name)];
+            full.extend_from_slice(path);
+            locate(yaml, &full)
+        };
+
+        if service.image.is_empty() && !service.extra.contains_key("build") {
+            problems.push(problem(
+                at(&[]),
+                format!("serviço '{name}' sem 'image' nem 'build'"),
+                "Defina a imagem (ex.: image: postgres:16) ou o contexto de build (build: ./app).",
+            ));
+        }
+
+        for (index, port) in service.ports.iter().enumerate() {
+            if interpolated(port) {
+                continue;
+            }
+            let line = at(&[Key::Name("ports"), Key::Item(index)]);
+            match parse_port(port) {
+                None => problems.push(problem(
+                    line,
+                    format!("serviço '{name}': porta inválida '{port}'"),
+                    "Use [ip:][porta do host:]porta do container[/protocolo], entre aspas (ex.: \"8080:80\"); portas vão de 1 a 65535.",
+                )),
+                Some(port) if port.host.is_some() && active(service, profiles) => {
+                    published.push((name.as_str(), line, port))
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (index, volume) in service.volumes.iter().enumerate() {
+            if interpolated(volume) {
+                continue;
+            }
+            let line = at(&[Key::Name("volumes"), Key::Item(index)]);
+            let mut parts = volume.split(':');
+            let first = parts.next().unwrap_or_default();
+            // Windows paths: C:\dados:/data
+            let (source, target) = match parts.next() {
+                Some(rest) if first.len() == 1 && rest.starts_with('\\') => {
+                    (Some(volume.as_str()), parts.next())
+                }
+                Some(target) => (Some(first), Some(target)),
+                None => (None, Some(first)),
+            };
+            if !target.is_some_and(|t| t.starts_with('/')) {
+                problems.push(problem(
+                    line,
+                    format!("serviço '{name}': volume '{volume}' sem caminho absoluto no container"),
+                    "Use origem:destino[:modo], com o destino absoluto (ex.: ./init.sql:/docker-entrypoint-initdb.d/init.sql:ro).",
+                ));
+                continue;
+            }
+            if let Some(source) = source
+                && !is_path(source)
+                && !file.volumes.contains_key(source)
+            {
+                problems.push(problem(
+                    line,
+                    format!("serviço '{name}': volume nomeado '{source}' não declarado"),
+                    format!(
+                        "Declare-o em 'volumes:' no topo do arquivo ou use um caminho (./{source})."
+                    ),
+                ));
+            }
+        }
+
+        for (index, dependency) in service.depends_on.0.keys().enumerate() {
+            if !file.services.contains_key(dependency) {
+                // Map syntax, then list syntax
+                let line = at(&[Key::Name("depends_on"), Key::Name(dependency)])
+                    .filter(|&l| l > at(&[Key::Name("depends_on")]).unwrap_or(0))
+                    .or_else(|| at(&[Key::Name("depends_on"), Key::Item(index)]));
+                problems.push(problem(
+                    line,
+                    format!("serviço '{name}' depende de '{dependency}', que não existe"),
+                    format!("Serviços do manifesto: {}.", names.join(", ")),
+                ));
+            }
+        }
+
+        for network in &service.networks {
+            if network != "default" && !file.networks.contains_key(network) {
+                problems.push(problem(
+                    at(&[Key::Name("networks")]),
+                    format!("serviço '{name}': rede '{network}' não declarada"),
+                    "Declare-a em 'networks:' no topo do arquivo.",
+                ));
+            }
+        }
+
+        if let Some(restart) = &service.restart
+            && !interpolated(restart)
+            && !restart_policy(restart)
+        {
+            problems.push(problem(
+                at(&[Key::Name("restart")]),
+                format!("serviço '{name}': restart inválido '{restart}'"),
+                "Valores aceitos: no, always, on-failure[:tentativas] e unless-stopped.",
+            ));
+        }
+    }
+
+    // Compose would stop at the second container with "port is already allocated"
+    for (i, (name, line, port)) in published.iter().enumerate() {
+        let clash = published[..i]
+            .iter()
+            .find(|(other_name, _, other)| other_name != name && port.clashes(other));
+        if let Some((other_name, _, other)) = clash {
+            let (first, last) = other.host.unwrap_or_default();
+            let port = if first == last {
+                first.to_string()
+            } else {
+                format!("{first}-{last}")
+            };
+            problems.push(problem(
+                *line,
+                format!("serviços '{other_name}' e '{name}' publicam a mesma porta do host ({port})"),
+                "Troque a porta do host de um deles (ex.: \"15432:5432\") no .dx/docker-compose.override.yml e rode 'dx dev-services'.",
+            ));
+        }
+    }
+    problems.sort_by_key(|p| p.line);
+    problems
+}
+
+/// Check the manifest at `path` (shown as `shown`) before a run of
+/// `profiles`; every problem is listed in the error.
+pub fn validate(path: &Path, shown: &Path, profiles: &[String]) -> DxResult {
+    let yaml = std::fs::read_to_string(path).unwrap_or_default();
+    let problems = check(&yaml, profiles);
+    if problems.is_empty() {
+        return Ok(());
+    }
+    let mut message = format!(
+        "{} tem {} problema(s); o Docker não foi chamado:",
+        shown.display(),
+        problems.len()
+    );
+    for problem in &problems {
+        let place = match problem.line {
+            Some(line) => format!("{}:{line}", shown.display()),
+            None => shown.display().to_string(),
+        };
+        message.push_str(&format!(
+            "\n  {place}: {}\n    Dica: {}",
+            problem.message, problem.hint
+        ));
+    }
+    Err(DxError::InvalidInput(message))
+}
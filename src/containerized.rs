@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! dx running inside a container (`dx dev-services run --containerized-cli`).
+//!
+//! The official image ships dx with the Docker CLI and talks to the host's
+//! daemon through its socket (`-v /var/run/docker.sock:/var/run/docker.sock`).
+//! Bind mounts in the manifest are resolved by the daemon on the host, so
+//! `./telemetry/...` (relative to `.dx` inside the container) must become the
+//! path of the same folder on the host. The host path of the project comes from
+//! `DX_HOST_PROJECT_DIR` or, when unset, from the mounts of dx's own container
+//! (`docker inspect $HOSTNAME`). The translated manifest is written next to the
+//! original, so Compose keeps the same project name.
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::Deserialize;
+
+use crate::compose::ComposeFile;
+use crate::error::{DxError, DxResult, IoContext};
+
+/// Set by the official image: every `run` is containerized.
+pub const ENV: &str = "DX_CONTAINERIZED";
+/// Path of the project on the host, when it can't be found from the mounts.
+pub const HOST_DIR_ENV: &str = "DX_HOST_PROJECT_DIR";
+
+/// Whether `run` talks to the host daemon from inside a container.
+pub fn enabled(flag: bool) -> bool {
+    flag || std::env::var(ENV).is_ok_and(|v| matches!(v.as_str(), "1" | "true"))
+}
+
+#[derive(Debug, Deserialize)]
+struct Mount {
+    #[serde(rename = "Source")]
+    source: String,
+    #[serde(rename = "Destination")]
+    destination: String,
+}
+
+/// `path` without `.` and `..` components, without touching the filesystem.
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Host path of `path`, given the mounts of the container (the longest
+/// destination containing it wins).
+fn through_mounts(path: &Path, mounts: &[Mount]) -> Option<String> {
+    mounts
+        .iter()
+        .filter_map(|m| {
+            let rest = path.strip_prefix(&m.destination).ok()?;
+            Some((m.destination.len(), join(&m.source, rest)))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, host)| host)
+}
+
+/// `base/rest` with forward slashes, as Docker expects on every host.
+fn join(base: &str, rest: &Path) -> String {
+    let rest = rest.to_string_lossy().replace('\\', "/");
+    match rest.as_str() {
+        "" => base.to_string(),
+        _ => format!("{}/{rest}", base.trim_end_matches(['/', '\\'])),
+    }
+}
+
+/// Mounts of the container dx runs in; `None` outside a container or when
+/// Docker can't be asked.
+fn own_mounts() -> Option<Vec<Mount>> {
+    let id = std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())?;
+    let output = Command::new("docker")
+        .args(["inspect", "--format", "{{json .Mounts}}"])
+        .arg(id.trim())
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+/// Path of `project_dir` on the Docker host.
+pub fn host_dir(project_dir: &Path) -> DxResult<String> {
+    if let Ok(dir) = std::env::var(HOST_DIR_ENV)
+        && !dir.trim().is_empty()
+    {
+        return Ok(dir.trim().trim_end_matches(['/', '\\']).to_string());
+    }
+    let project_dir = normalize(&std::path::absolute(project_dir).unwrap_or_default());
+    own_mounts()
+        .and_then(|mounts| through_mounts(&project_dir, &mounts))
+        .ok_or_else(|| {
+            DxError::InvalidInput(format!(
+                "Não foi possível descobrir o caminho de {} no host do Docker.\nDica: monte o projeto no mesmo caminho (-v \"$PWD\":\"$PWD\" -w \"$PWD\") ou informe -e {HOST_DIR_ENV}=\"$PWD\".",
+                project_dir.display()
+            ))
+        })
+}
+
+/// Fail early when the container has no access to a Docker daemon.
+pub fn check_socket() -> DxResult {
+    if std::env::var_os("DOCKER_HOST").is_some() || Path::new("/var/run/docker.sock").exists() {
+        return Ok(());
+    }
+    Err(DxError::NotFound(
+        "Socket do Docker não encontrado em /var/run/docker.sock.\nDica: execute o container com -v /var/run/docker.sock:/var/run/docker.sock (ou defina DOCKER_HOST)."
+            .to_string(),
+    ))
+}
+
+/// `source` of a bind mount in the manifest at `dx_dir` as seen by the host,
+/// or `None` for named volumes and paths outside the project.
+fn host_source(source: &str, dx_dir: &Path, project_dir: &Path, host: &str) -> Option<String> {
+    let path = if source.starts_with("./") || source.starts_with("../") || source == "." {
+        dx_dir.join(source)
+    } else if source.starts_with('/') {
+        PathBuf::from(source)
+    } else {
+        return None;
+    };
+    let rest = normalize(&path)
+        .strip_prefix(normalize(project_dir))
+        .ok()?
+        .to_path_buf();
+    Some(join(host, &rest))
+}
+
+/// The manifest with its bind mounts pointing to `host`, the project on the host.
+pub fn translate(yaml: &str, project_dir: &Path, host: &str) -> DxResult<String> {
+    let dx_dir = project_dir.join(".dx");
+    let mut file = ComposeFile::parse(yaml)
+        .map_err(|e| DxError::InvalidInput(format!("Manifesto inválido: {e}")))?;
+    for service in file.services.values_mut() {
+        for volume in &mut service.volumes {
+            if let Some((source, target)) = volume.split_once(':')
+                && let Some(source) = host_source(source, &dx_dir, project_dir, host)
+            {
+                *volume = format!("{source}:{target}");
+            }
+        }
+    }
+    Ok(file.to_yaml())
+}
+
+/// Write the manifest for the host daemon next to `compose_path` and return its path.
+pub fn write_host_compose(project_dir: &Path, compose_path: &Path) -> DxResult<PathBuf> {
+    check_socket()?;
+    let host = host_dir(project_dir)?;
+    let yaml = fs::read_to_string(compose_path)
+        .context(format!("Erro ao ler {}", compose_path.display()))?;
+    let project_dir = normalize(&std::path::absolute(project_dir).unwrap_or_default());
+    let content = format!(
+        "# Gerado pelo dx (dev-services run --containerized-cli); não edite.\n# Caminhos do host: {host}\n{}",
+        translate(&yaml, &project_dir, &host)?
+    );
+    let path = compose_path.with_file_name(crate::compose::CONTAINERIZED_FILE);
+    fs::write(&path, content).context(format!("Erro ao salvar {}", path.display()))?;
+    tracing::debug!(host = %host, path = %path.display(), "manifesto traduzido para o host");
+    Ok(path)
+}
@@ -0,0 +1,568 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Long-lived JSON-RPC server for editor plugins and the portal (`dx daemon`).
+//!
+//! Speaks JSON-RPC 2.0 over stdio (the default) or a Unix socket
+//! (`--socket`). A message is either one JSON object per line or a body
+//! framed with LSP `Content-Length` headers; replies use the framing of the
+//! last message received on the connection.
+//!
+//! The project state is split into sections (stack, dependencies, services,
+//! env) that are computed on first request and cached. File changes only
+//! invalidate the sections they can affect, and clients that subscribed get a
+//! `project/changed` notification with the paths and sections involved.
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{RecvTimeoutError, channel};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher, recommended_watcher};
+use serde_json::{Value, json};
+
+use crate::error::{DxError, DxResult, IoContext};
+use crate::pipeline::Pipeline;
+
+/// Sections of the project state, in the order they are reported.
+const SECTIONS: &[&str] = &["stack", "dependencies", "services", "env"];
+
+/// Methods answered by the daemon (reported by `initialize`).
+const METHODS: &[&str] = &[
+    "initialize",
+    "project/state",
+    "project/subscribe",
+    "project/unsubscribe",
+    "command/run",
+    "data/generate",
+    "history/list",
+    "shutdown",
+    "exit",
+];
+
+/// Quiet period after the last file event before notifying.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Directories whose changes never affect the project state.
+const IGNORED_DIRS: &[&str] = &[".git", "target", "node_modules", "build", "dist", "vendor"];
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn invalid_params(message: impl Into<String>) -> Self {
+        RpcError {
+            code: INVALID_PARAMS,
+            message: message.into(),
+        }
+    }
+}
+
+type RpcResult = Result<Value, RpcError>;
+
+fn response(id: Value, result: RpcResult) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": e.code, "message": e.message },
+        }),
+    }
+}
+
+/// One connected client.
+struct Peer {
+    out: Mutex<Box<dyn Write + Send>>,
+    /// Whether the client frames its messages with `Content-Length` headers
+    framed: AtomicBool,
+}
+
+impl Peer {
+    fn new(out: Box<dyn Write + Send>) -> Arc<Self> {
+        Arc::new(Peer {
+            out: Mutex::new(out),
+            framed: AtomicBool::new(false),
+        })
+    }
+
+    fn send(&self, message: &Value) {
+        let body = message.to_string();
+        let mut out = self.out.lock().unwrap();
+        let sent = if self.framed.load(Ordering::Relaxed) {
+            write!(out, "Content-Length: {}\r\n\r\n{body}", body.len())
+        } else {
+            writeln!(out, "{body}")
+        };
+        if let Err(e) = sent.and_then(|_| out.flush()) {
+            tracing::debug!(error = %e, "falha ao enviar mensagem ao cliente");
+        }
+    }
+}
+
+/// Next message from `reader` (`None` at end of input), recording in
+/// `framed` whether it came with LSP headers.
+fn read_message(reader: &mut impl BufRead, framed: &AtomicBool) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let length = trimmed
+            .split_once(':')
+            .filter(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+            .map(|(_, value)| value.trim().to_string());
+        let Some(length) = length else {
+            framed.store(false, Ordering::Relaxed);
+            return Ok(Some(trimmed.to_string()));
+        };
+        let length: usize = length
+            .parse()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Content-Length inválido"))?;
+        // Remaining headers, up to the blank line before the body
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        framed.store(true, Ordering::Relaxed);
+        return Ok(Some(String::from_utf8_lossy(&body).into_owned()));
+    }
+}
+
+/// Sections that a change to `path` (relative to the project) can affect.
+fn affected(path: &Path) -> &'static [&'static str] {
+    if path.starts_with(".dx") {
+        // dev-config and the Dev Services manifest
+        &["services", "env"]
+    } else if path.components().count() == 1 {
+        // Manifests, lockfiles and version files live at the project root
+        &["stack", "dependencies"]
+    } else {
+        // Sources are scanned for dependency hints
+        &["dependencies"]
+    }
+}
+
+fn ignored(path: &Path) -> bool {
+    path.components().any(|c| {
+        let c = c.as_os_str().to_string_lossy();
+        IGNORED_DIRS.contains(&c.as_ref())
+    })
+}
+
+fn compute(project: &Path, section: &str) -> Value {
+    match section {
+        "stack" => {
+            let pipeline = Pipeline::detect(project, project);
+            json!({
+                "name": pipeline.stack().name(),
+                "version": pipeline.toolchain.as_ref().map(|t| t.version.clone()),
+                "steps": pipeline
+                    .steps
+                    .iter()
+                    .map(|s| json!({ "stage": s.stage.name(), "name": s.name, "run": s.run }))
+                    .collect::<Vec<_>>(),
+            })
+        }
+        "dependencies" => json!(crate::dev_services::detect_dependencies(project).service_names()),
+        "services" => {
+            let yaml = std::fs::read_to_string(project.join(".dx").join("docker-compose.yml"))
+                .unwrap_or_default();
+            crate::dev_services::manifest_services(&yaml)
+                .iter()
+                .map(|s| {
+                    json!({
+                        "name": s.name,
+                        "image": s.image,
+                        "ports": s
+                            .ports
+                            .iter()
+                            .map(|(host, container)| json!({ "host": host, "container": container }))
+                            .collect::<Vec<_>>(),
+                    })
+                })
+                .collect()
+        }
+        _ => json!(crate::shell_env::project_vars(project)),
+    }
+}
+
+struct Daemon {
+    project: PathBuf,
+    /// Computed sections; a missing entry is recomputed on the next request
+    cache: Mutex<BTreeMap<&'static str, Value>>,
+    subscribers: Mutex<Vec<Arc<Peer>>>,
+}
+
+impl Daemon {
+    fn section(&self, name: &'static str) -> Value {
+        // Held while computing so an invalidation can't be overwritten by a
+        // result computed before the change
+        let mut cache = self.cache.lock().unwrap();
+        cache
+            .entry(name)
+            .or_insert_with(|| compute(&self.project, name))
+            .clone()
+    }
+
+    fn state(&self, params: &Value) -> RpcResult {
+        let sections: Vec<&'static str> = match params.get("sections") {
+            None | Some(Value::Null) => SECTIONS.to_vec(),
+            Some(Value::Array(names)) => names
+                .iter()
+                .map(|n| {
+                    SECTIONS
+                        .iter()
+                        .find(|s| n.as_str() == Some(**s))
+                        .copied()
+                        .ok_or_else(|| {
+                            RpcError::invalid_params(format!(
+                                "Seção desconhecida: {n} (disponíveis: {})",
+                                SECTIONS.join(", ")
+                            ))
+                        })
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => {
+                return Err(RpcError::invalid_params(
+                    "`sections` deve ser uma lista de nomes",
+                ));
+            }
+        };
+        let mut state = json!({ "project": self.project.display().to_string() });
+        for section in sections {
+            state[section] = self.section(section);
+        }
+        Ok(state)
+    }
+
+    /// `command/run`: run `dx --json <args>` in the project and return its
+    /// exit code and JSON envelope.
+    fn run_command(&self, params: &Value) -> RpcResult {
+        let args: Vec<String> = params["args"]
+            .as_array()
+            .and_then(|a| a.iter().map(|v| v.as_str().map(str::to_string)).collect())
+            .ok_or_else(|| RpcError::invalid_params("`args` deve ser uma lista de strings"))?;
+        if args.first().is_some_and(|a| a == "daemon") {
+            return Err(RpcError::invalid_params(
+                "`dx daemon` não pode ser executado pelo próprio daemon",
+            ));
+        }
+        let server_error = |message: String| RpcError {
+            code: SERVER_ERROR,
+            message,
+        };
+        let exe = std::env::current_exe()
+            .map_err(|e| server_error(format!("Executável do dx não encontrado: {e}")))?;
+        let output = Command::new(exe)
+            .arg("--json")
+            .args(&args)
+            .current_dir(&self.project)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| server_error(format!("Erro ao executar dx: {e}")))?;
+        let envelope: Value = serde_json::from_slice(&output.stdout).unwrap_or(Value::Null);
+        Ok(json!({
+            "exit_code": output.status.code(),
+            "envelope": envelope,
+            "log": String::from_utf8_lossy(&output.stderr),
+        }))
+    }
+
+    fn handle(&self, method: &str, params: &Value, peer: &Arc<Peer>) -> RpcResult {
+        match method {
+            "initialize" => Ok(json!({
+                "serverInfo": { "name": "dx", "version": env!("CARGO_PKG_VERSION") },
+                "project": self.project.display().to_string(),
+                "methods": METHODS,
+                "sections": SECTIONS,
+            })),
+            "project/state" => self.state(params),
+            "data/generate" => self.generate_data(params),
+            "history/list" => {
+                let limit = params.get("limit").and_then(Value::as_u64).unwrap_or(50) as usize;
+                let entries = crate::history::entries(&self.project);
+                let latest: Vec<_> = entries.iter().rev().take(limit).collect();
+                Ok(json!({ "entries": latest }))
+            }
+            "project/subscribe" => {
+                let mut subscribers = self.subscribers.lock().unwrap();
+                if !subscribers.iter().any(|p| Arc::ptr_eq(p, peer)) {
+                    subscribers.push(peer.clone());
+                }
+                Ok(json!(true))
+            }
+            "project/unsubscribe" => {
+                self.unsubscribe(peer);
+                Ok(json!(true))
+            }
+            "shutdown" => Ok(Value::Null),
+            _ => Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("Método desconhecido: {method}"),
+            }),
+        }
+    }
+
+    /// Fake records for the portal's data seeding (see [`crate::datagen`]).
+    fn generate_data(&self, params: &Value) -> RpcResult {
+        let Some(schema) = params.get("schema").and_then(Value::as_str) else {
+            return Err(RpcError::invalid_params(
+                "`schema` deve ser o caminho de um JSON Schema ou de um DDL SQL",
+            ));
+        };
+        let count = params
+            .get("count")
+            .and_then(Value::as_u64)
+            .map_or(crate::datagen::DEFAULT_COUNT, |c| c as usize);
+        let seed = params
+            .get("seed")
+            .and_then(Value::as_u64)
+            .unwrap_or(crate::datagen::DEFAULT_SEED);
+        let invalid = |e: DxError| RpcError::invalid_params(e.to_string());
+        let locale = match params.get("locale").and_then(Value::as_str) {
+            Some(locale) => crate::datagen::Locale::parse(locale).map_err(invalid)?,
+            None => crate::datagen::Locale::PtBr,
+        };
+        let (_, tables) = crate::datagen::load(&self.project.join(schema)).map_err(invalid)?;
+        let data = crate::datagen::generate(&tables, count, seed, locale).map_err(invalid)?;
+        Ok(json!({ "seed": seed, "locale": locale, "tables": data }))
+    }
+
+    fn unsubscribe(&self, peer: &Arc<Peer>) {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|p| !Arc::ptr_eq(p, peer));
+    }
+
+    /// Invalidate the sections affected by `paths` and notify the subscribers.
+    fn changed(&self, paths: BTreeSet<PathBuf>) {
+        let sections: BTreeSet<&str> = paths.iter().flat_map(|p| affected(p)).copied().collect();
+        {
+            let mut cache = self.cache.lock().unwrap();
+            for section in &sections {
+                cache.remove(section);
+            }
+        }
+        tracing::debug!(?sections, "arquivos alterados");
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "project/changed",
+            "params": {
+                "paths": paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>(),
+                "sections": sections,
+            },
+        });
+        for peer in self.subscribers.lock().unwrap().iter() {
+            peer.send(&notification);
+        }
+    }
+}
+
+/// Answer the requests of one client until it disconnects, sends `exit` or
+/// `shutdown`.
+fn serve(daemon: &Arc<Daemon>, input: impl Read, peer: Arc<Peer>) -> io::Result<()> {
+    let mut reader = BufReader::new(input);
+    while let Some(message) = read_message(&mut reader, &peer.framed)? {
+        let request: Value = match serde_json::from_str(&message) {
+            Ok(request) => request,
+            Err(e) => {
+                let error = RpcError {
+                    code: PARSE_ERROR,
+                    message: format!("JSON inválido: {e}"),
+                };
+                peer.send(&response(Value::Null, Err(error)));
+                continue;
+            }
+        };
+        // Requests without an id are notifications and get no response
+        let id = request.get("id").cloned();
+        let Some(method) = request["method"].as_str() else {
+            let error = RpcError {
+                code: INVALID_REQUEST,
+                message: "Requisição sem `method`".to_string(),
+            };
+            peer.send(&response(id.unwrap_or_default(), Err(error)));
+            continue;
+        };
+        let params = request.get("params").cloned().unwrap_or_default();
+        match method {
+            "exit" => break,
+            "command/run" => {
+                // Commands can take a while; keep answering meanwhile
+                let (daemon, peer) = (daemon.clone(), peer.clone());
+                std::thread::spawn(move || {
+                    let result = daemon.run_command(&params);
+                    if let Some(id) = id {
+                        peer.send(&response(id, result));
+                    }
+                });
+            }
+            _ => {
+                let result = daemon.handle(method, &params, &peer);
+                if let Some(id) = id {
+                    peer.send(&response(id, result));
+                }
+                if method == "shutdown" {
+                    break;
+                }
+            }
+        }
+    }
+    daemon.unsubscribe(&peer);
+    Ok(())
+}
+
+/// Watch the project and report changes to `daemon` after each quiet period.
+fn watch(daemon: Arc<Daemon>) -> DxResult<RecommendedWatcher> {
+    let (tx, rx) = channel();
+    let mut watcher = recommended_watcher(move |res| {
+        tx.send(res).ok();
+    })
+    .map_err(|e| DxError::Tool(format!("não foi possível iniciar watcher: {e}")))?;
+    watcher
+        .watch(&daemon.project, RecursiveMode::Recursive)
+        .map_err(|e| DxError::Tool(format!("não foi possível observar diretório: {e}")))?;
+
+    std::thread::spawn(move || {
+        let mut pending = BTreeSet::new();
+        loop {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(Ok(event)) => {
+                    if !matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    ) {
+                        continue;
+                    }
+                    pending.extend(
+                        event
+                            .paths
+                            .iter()
+                            .filter_map(|p| p.strip_prefix(&daemon.project).ok())
+                            .filter(|p| !p.as_os_str().is_empty() && !ignored(p))
+                            .map(Path::to_path_buf),
+                    );
+                }
+                Ok(Err(e)) => tracing::warn!(error = %e, "erro do watcher"),
+                Err(RecvTimeoutError::Timeout) if !pending.is_empty() => {
+                    daemon.changed(std::mem::take(&mut pending));
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+    Ok(watcher)
+}
+
+#[cfg(unix)]
+fn listen(daemon: &Arc<Daemon>, path: &Path) -> DxResult {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    if path.exists() {
+        if UnixStream::connect(path).is_ok() {
+            return Err(DxError::InvalidInput(format!(
+                "Já existe um daemon escutando em {}",
+                path.display()
+            )));
+        }
+        // Left behind by a daemon that didn't shut down cleanly
+        std::fs::remove_file(path).context(format!("Erro ao remover {}", path.display()))?;
+    }
+    let listener =
+        UnixListener::bind(path).context(format!("Erro ao abrir o socket {}", path.display()))?;
+    // The state includes credentials from the dev-config and the manifest
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .context(format!("Erro ao ajustar permissões de {}", path.display()))?;
+    outln!(
+        "dx daemon escutando em {} (projeto: {})",
+        path.display(),
+        daemon.project.display()
+    );
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!(error = %e, "falha ao aceitar conexão");
+                continue;
+            }
+        };
+        let daemon = daemon.clone();
+        std::thread::spawn(move || {
+            let peer = match stream.try_clone() {
+                Ok(writer) => Peer::new(Box::new(writer)),
+                Err(e) => {
+                    tracing::warn!(error = %e, "falha ao abrir conexão");
+                    return;
+                }
+            };
+            if let Err(e) = serve(&daemon, stream, peer) {
+                tracing::debug!(error = %e, "conexão encerrada");
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn listen(_daemon: &Arc<Daemon>, _path: &Path) -> DxResult {
+    Err(DxError::Unsupported(
+        "--socket só é suportado em sistemas Unix; use o modo stdio (padrão).".to_string(),
+    ))
+}
+
+pub struct DaemonOptions {
+    /// Unix socket to listen on instead of stdio
+    pub socket: Option<PathBuf>,
+}
+
+/// `dx daemon`: serve the state of the project at `dir` until the client
+/// (stdio) or the process (socket) ends.
+pub fn run(dir: Option<PathBuf>, opts: DaemonOptions) -> DxResult {
+    let dir = dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    // Canonical, so the watcher's event paths can be made relative to it
+    let project = dir
+        .canonicalize()
+        .ok()
+        .filter(|d| d.is_dir())
+        .ok_or_else(|| DxError::InvalidInput(format!("Diretório inválido: {}", dir.display())))?;
+    let daemon = Arc::new(Daemon {
+        project,
+        cache: Mutex::new(BTreeMap::new()),
+        subscribers: Mutex::new(Vec::new()),
+    });
+    let _watcher = watch(daemon.clone())?;
+
+    match opts.socket {
+        Some(path) => listen(&daemon, &path),
+        None => {
+            tracing::info!(project = %daemon.project.display(), "dx daemon em stdio");
+            serve(&daemon, io::stdin(), Peer::new(Box::new(io::stdout())))
+                .context("Erro de comunicação com o cliente")
+        }
+    }
+}
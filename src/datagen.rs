@@ -0,0 +1,1281 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Fake data for seeds and test fixtures.
+//!
+//! Records are generated from a schema: a JSON Schema (one object, nested
+//! objects and arrays included) or the `CREATE TABLE` statements of a SQL DDL
+//! file. Each field gets a value of its type, refined by its name (`email`,
+//! `first_name`, `city`, `created_at`...), its `enum`/`CHECK ... IN` values and
+//! its length and range limits; integer keys count from 1 and `*_id`
+//! references point to existing rows. Names, addresses and phones follow the
+//! locale (pt-BR or en-US), and the fields of a record agree with each other
+//! (the email is built from the name, the state matches the city). E-mails and
+//! URLs use the reserved `example.*` domains.
+//!
+//! Generation is deterministic: every value derives from the seed, the table,
+//! the field and the row, so the same seed always yields the same data and
+//! adding a column doesn't change the others. Used by `dx tests fixtures` and
+//! by the daemon's `data/generate` (portal data seeding).
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{DxError, DxResult, IoContext};
+use crate::output::Render;
+
+/// Records per table when none is given.
+pub const DEFAULT_COUNT: usize = 10;
+/// Seed when none is given, so fixtures are reproducible by default.
+pub const DEFAULT_SEED: u64 = 42;
+/// Upper bound of `count`, to keep fixtures reviewable.
+const MAX_COUNT: usize = 10_000;
+
+/// 2024-01-01T00:00:00Z; generated timestamps fall in the year after it.
+const EPOCH: i64 = 1_704_067_200;
+const DAY: i64 = 86_400;
+
+/// Deterministic pseudo-random numbers (SplitMix64).
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        if n == 0 { 0 } else { self.next_u64() % n }
+    }
+
+    /// Integer in `lo..=hi`.
+    fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        if hi <= lo {
+            return lo;
+        }
+        lo + self.below(hi.abs_diff(lo) + 1) as i64
+    }
+
+    fn pick<T: Clone>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize].clone()
+    }
+
+    fn chance(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    fn digits(&mut self, n: usize) -> String {
+        (0..n)
+            .map(|_| char::from(b'0' + self.below(10) as u8))
+            .collect()
+    }
+}
+
+/// Seed of the values of `parts` (table, field...) at `row`: FNV-1a over them.
+fn mix(seed: u64, parts: &[&str], row: usize) -> Rng {
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325 ^ seed;
+    let bytes = parts
+        .iter()
+        .flat_map(|p| p.bytes().chain([0]))
+        .chain(row.to_le_bytes());
+    for byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01B3);
+    }
+    Rng(hash)
+}
+
+/// Language and country of the names, addresses and phones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Locale {
+    #[serde(rename = "pt-BR")]
+    PtBr,
+    #[serde(rename = "en-US")]
+    EnUs,
+}
+
+impl Locale {
+    pub fn parse(value: &str) -> DxResult<Locale> {
+        match value.to_ascii_lowercase().replace('_', "-").as_str() {
+            "pt-br" | "pt" => Ok(Locale::PtBr),
+            "en-us" | "en" => Ok(Locale::EnUs),
+            _ => Err(DxError::InvalidInput(format!(
+                "Locale não suportado: '{value}'. Disponíveis: pt-BR, en-US"
+            ))),
+        }
+    }
+
+    pub fn id(self) -> &'static str {
+        match self {
+            Locale::PtBr => "pt-BR",
+            Locale::EnUs => "en-US",
+        }
+    }
+
+    fn data(self) -> &'static LocaleData {
+        match self {
+            Locale::PtBr => &PT_BR,
+            Locale::EnUs => &EN_US,
+        }
+    }
+}
+
+struct LocaleData {
+    first_names: &'static [&'static str],
+    last_names: &'static [&'static str],
+    streets: &'static [&'static str],
+    /// City, state and phone area code
+    cities: &'static [(&'static str, &'static str, &'static str)],
+    country: &'static str,
+}
+
+const PT_BR: LocaleData = LocaleData {
+    first_names: &[
+        "Ana",
+        "Beatriz",
+        "Camila",
+        "Fernanda",
+        "Gabriela",
+        "Juliana",
+        "Larissa",
+        "Mariana",
+        "Patrícia",
+        "Renata",
+        "Bruno",
+        "Carlos",
+        "Diego",
+        "Eduardo",
+        "Felipe",
+        "Gustavo",
+        "João",
+        "Lucas",
+        "Marcos",
+        "Rafael",
+    ],
+    last_names: &[
+        "Silva",
+        "Santos",
+        "Oliveira",
+        "Souza",
+        "Rodrigues",
+        "Ferreira",
+        "Alves",
+        "Pereira",
+        "Lima",
+        "Gomes",
+        "Costa",
+        "Ribeiro",
+        "Martins",
+        "Carvalho",
+        "Almeida",
+        "Araújo",
+    ],
+    streets: &[
+        "Rua das Flores",
+        "Rua XV de Novembro",
+        "Avenida Paulista",
+        "Rua da Consolação",
+        "Avenida Brasil",
+        "Rua Sete de Setembro",
+        "Avenida Atlântica",
+        "Rua Augusta",
+        "Rua dos Andradas",
+        "Avenida Getúlio Vargas",
+    ],
+    cities: &[
+        ("São Paulo", "SP", "11"),
+        ("Rio de Janeiro", "RJ", "21"),
+        ("Belo Horizonte", "MG", "31"),
+        ("Curitiba", "PR", "41"),
+        ("Porto Alegre", "RS", "51"),
+        ("Salvador", "BA", "71"),
+        ("Recife", "PE", "81"),
+        ("Fortaleza", "CE", "85"),
+        ("Florianópolis", "SC", "48"),
+        ("Brasília", "DF", "61"),
+    ],
+    country: "Brasil",
+};
+
+const EN_US: LocaleData = LocaleData {
+    first_names: &[
+        "Emma",
+        "Olivia",
+        "Ava",
+        "Sophia",
+        "Mia",
+        "Charlotte",
+        "Amelia",
+        "Harper",
+        "Evelyn",
+        "Abigail",
+        "Liam",
+        "Noah",
+        "James",
+        "William",
+        "Benjamin",
+        "Lucas",
+        "Henry",
+        "Alexander",
+        "Mason",
+        "Ethan",
+    ],
+    last_names: &[
+        "Smith",
+        "Johnson",
+        "Williams",
+        "Brown",
+        "Jones",
+        "Garcia",
+        "Miller",
+        "Davis",
+        "Rodriguez",
+        "Martinez",
+        "Wilson",
+        "Anderson",
+        "Taylor",
+        "Thomas",
+        "Moore",
+        "Jackson",
+    ],
+    streets: &[
+        "Main St",
+        "Oak Ave",
+        "Maple St",
+        "Cedar Ln",
+        "Elm St",
+        "Pine St",
+        "Washington Ave",
+        "Lake Dr",
+        "Park Ave",
+        "Sunset Blvd",
+    ],
+    cities: &[
+        ("Springfield", "IL", "217"),
+        ("Austin", "TX", "512"),
+        ("Portland", "OR", "503"),
+        ("Denver", "CO", "303"),
+        ("Boston", "MA", "617"),
+        ("Seattle", "WA", "206"),
+        ("Chicago", "IL", "312"),
+        ("Atlanta", "GA", "404"),
+        ("Phoenix", "AZ", "602"),
+        ("Nashville", "TN", "615"),
+    ],
+    country: "United States",
+};
+
+/// Filler text, the same for every locale.
+const WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+];
+
+const EMAIL_DOMAINS: &[&str] = &["example.com", "example.org", "example.net"];
+
+/// The person and place of a record, shared by its fields.
+struct Identity {
+    first: &'static str,
+    last: &'static str,
+    street: &'static str,
+    number: i64,
+    city: &'static str,
+    state: &'static str,
+    area: &'static str,
+    postal: String,
+    domain: &'static str,
+}
+
+impl Identity {
+    fn new(rng: &mut Rng, locale: Locale) -> Self {
+        let data = locale.data();
+        let (city, state, area) = rng.pick(data.cities);
+        Identity {
+            first: rng.pick(data.first_names),
+            last: rng.pick(data.last_names),
+            street: rng.pick(data.streets),
+            number: rng.range(1, 2999),
+            city,
+            state,
+            area,
+            postal: match locale {
+                Locale::PtBr => format!("{}-{}", rng.digits(5), rng.digits(3)),
+                Locale::EnUs => rng.digits(5),
+            },
+            domain: rng.pick(EMAIL_DOMAINS),
+        }
+    }
+
+    fn name(&self) -> String {
+        format!("{} {}", self.first, self.last)
+    }
+
+    /// `first.last` in ASCII, for e-mails and usernames.
+    fn handle(&self) -> String {
+        format!("{}.{}", ascii(self.first), ascii(self.last))
+    }
+
+    fn street_line(&self, locale: Locale) -> String {
+        match locale {
+            Locale::PtBr => format!("{}, {}", self.street, self.number),
+            Locale::EnUs => format!("{} {}", self.number, self.street),
+        }
+    }
+
+    fn address(&self, locale: Locale) -> String {
+        match locale {
+            Locale::PtBr => format!(
+                "{} - {}/{}, {}",
+                self.street_line(locale),
+                self.city,
+                self.state,
+                self.postal
+            ),
+            Locale::EnUs => format!(
+                "{}, {}, {} {}",
+                self.street_line(locale),
+                self.city,
+                self.state,
+                self.postal
+            ),
+        }
+    }
+
+    fn phone(&self, rng: &mut Rng, locale: Locale) -> String {
+        match locale {
+            Locale::PtBr => format!("+55 {} 9{}-{}", self.area, rng.digits(4), rng.digits(4)),
+            // 555-0100 to 555-0199 are reserved for fiction
+            Locale::EnUs => format!("+1 {}-555-01{}", self.area, rng.digits(2)),
+        }
+    }
+}
+
+/// Lowercase ASCII of a name (accents dropped).
+fn ascii(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+            'é' | 'ê' | 'è' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            'ñ' => 'n',
+            c => c,
+        })
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+fn uuid(rng: &mut Rng) -> String {
+    let hex = format!("{:016x}{:016x}", rng.next_u64(), rng.next_u64());
+    // Version 4, RFC 4122 variant
+    let variant = ['8', '9', 'a', 'b'][(rng.below(4)) as usize];
+    format!(
+        "{}-{}-4{}-{variant}{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[13..16],
+        &hex[17..20],
+        &hex[20..32]
+    )
+}
+
+/// Civil date of `days` since 1970-01-01 (proleptic Gregorian).
+fn civil(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn date(secs: i64) -> String {
+    let (year, month, day) = civil(secs.div_euclid(DAY));
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn timestamp(secs: i64) -> String {
+    let time = secs.rem_euclid(DAY);
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        date(secs),
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+fn words(rng: &mut Rng, min: i64, max: i64) -> Vec<&'static str> {
+    (0..rng.range(min, max)).map(|_| rng.pick(WORDS)).collect()
+}
+
+fn sentence(rng: &mut Rng, min: i64, max: i64) -> String {
+    let mut text = words(rng, min, max).join(" ");
+    if let Some(first) = text.get(..1) {
+        text.replace_range(..1, &first.to_uppercase());
+    }
+    text
+}
+
+/// A generated value.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Datum {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Num(f64),
+    Text(String),
+    List(Vec<Datum>),
+    Record(IndexMap<String, Datum>),
+}
+
+impl Datum {
+    fn from_json(value: &Value) -> Datum {
+        match value {
+            Value::Null => Datum::Null,
+            Value::Bool(b) => Datum::Bool(*b),
+            Value::Number(n) => n
+                .as_i64()
+                .map_or_else(|| Datum::Num(n.as_f64().unwrap_or_default()), Datum::Int),
+            Value::String(s) => Datum::Text(s.clone()),
+            Value::Array(items) => Datum::List(items.iter().map(Datum::from_json).collect()),
+            Value::Object(map) => Datum::Record(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), Datum::from_json(v)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// SQL literal of the value; lists and records go as JSON text.
+    fn sql(&self) -> String {
+        match self {
+            Datum::Null => "NULL".to_string(),
+            Datum::Bool(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
+            Datum::Int(n) => n.to_string(),
+            Datum::Num(n) => n.to_string(),
+            Datum::Text(s) => quote(s),
+            Datum::List(_) | Datum::Record(_) => {
+                quote(&serde_json::to_string(self).unwrap_or_default())
+            }
+        }
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// Type of a field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Kind {
+    Text,
+    Email,
+    Url,
+    Uuid,
+    Integer,
+    Number,
+    Boolean,
+    Timestamp,
+    Date,
+    Time,
+    Json,
+    Object(Vec<Field>),
+    /// Items and their count range
+    Array(Box<Field>, usize, usize),
+}
+
+/// A field of a table (or of a nested object).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub kind: Kind,
+    /// Allowed values (`enum`, `ENUM(...)`, `CHECK (... IN (...))`)
+    pub choices: Vec<Datum>,
+    pub max_len: Option<usize>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Counts from 1 (`SERIAL`, `AUTO_INCREMENT`, identity, integer `id`)
+    pub serial: bool,
+    /// Foreign key: a row number of the generated tables
+    pub reference: bool,
+}
+
+impl Field {
+    fn new(name: &str, kind: Kind) -> Self {
+        Field {
+            name: name.to_string(),
+            kind,
+            choices: Vec::new(),
+            max_len: None,
+            min: None,
+            max: None,
+            serial: false,
+            reference: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Table {
+    pub name: String,
+    pub fields: Vec<Field>,
+}
+
+/// Format of a schema file, also the default format of its fixtures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Json,
+    Sql,
+}
+
+/// Load the tables of the schema at `path`: a JSON Schema (`.json`) or a SQL
+/// DDL file (`.sql`).
+pub fn load(path: &Path) -> DxResult<(Format, Vec<Table>)> {
+    if !path.is_file() {
+        return Err(DxError::NotFound(format!(
+            "Schema não encontrado: {}",
+            path.display()
+        )));
+    }
+    let content =
+        std::fs::read_to_string(path).context(format!("Erro ao ler {}", path.display()))?;
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "records".to_string());
+    let (format, tables) = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => (Format::Json, vec![json_schema(&content, &stem)?]),
+        Some("sql") => (Format::Sql, sql_ddl(&content)),
+        _ => {
+            return Err(DxError::InvalidInput(format!(
+                "Schema não suportado: {} (use um JSON Schema .json ou um DDL .sql).",
+                path.display()
+            )));
+        }
+    };
+    if tables.is_empty() || tables.iter().all(|t| t.fields.is_empty()) {
+        return Err(DxError::InvalidInput(format!(
+            "Nenhuma tabela ou propriedade encontrada em {}.",
+            path.display()
+        )));
+    }
+    Ok((format, tables))
+}
+
+/// The keywords of a JSON Schema read here. Properties keep their order,
+/// which becomes the order of the generated fields.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct JsonSchema {
+    title: Option<String>,
+    /// A type name or a list of them
+    #[serde(rename = "type")]
+    kind: Value,
+    format: Option<String>,
+    properties: Option<IndexMap<String, JsonSchema>>,
+    items: Option<Box<JsonSchema>>,
+    #[serde(rename = "enum")]
+    choices: Vec<Value>,
+    #[serde(rename = "maxLength")]
+    max_length: Option<usize>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    #[serde(rename = "minItems")]
+    min_items: Option<usize>,
+    #[serde(rename = "maxItems")]
+    max_items: Option<usize>,
+}
+
+/// The table described by a JSON Schema object, named by its `title` (or
+/// `default_name`).
+fn json_schema(content: &str, default_name: &str) -> DxResult<Table> {
+    let schema: JsonSchema = serde_json::from_str(content)
+        .map_err(|e| DxError::InvalidInput(format!("JSON Schema inválido: {e}")))?;
+    let name = schema
+        .title
+        .as_ref()
+        .map_or_else(|| default_name.to_string(), |t| t.replace(' ', "_"));
+    // A schema for a list of records describes its items
+    let object = match &schema.items {
+        Some(items) if schema.kind == "array" => items,
+        _ => &schema,
+    };
+    Ok(Table {
+        name,
+        fields: json_fields(object),
+    })
+}
+
+fn json_fields(object: &JsonSchema) -> Vec<Field> {
+    object
+        .properties
+        .iter()
+        .flatten()
+        .map(|(name, property)| json_field(name, property))
+        .collect()
+}
+
+fn json_field(name: &str, property: &JsonSchema) -> Field {
+    // `["string", "null"]` is a string that may be missing
+    let kind_name = match &property.kind {
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null")
+            .unwrap_or("string"),
+        Value::String(t) => t.as_str(),
+        _ if property.properties.is_some() => "object",
+        _ => "string",
+    };
+    let kind = match (kind_name, property.format.as_deref()) {
+        ("string", Some("email" | "idn-email")) => Kind::Email,
+        ("string", Some("uri" | "url" | "iri")) => Kind::Url,
+        ("string", Some("uuid")) => Kind::Uuid,
+        ("string", Some("date-time")) => Kind::Timestamp,
+        ("string", Some("date")) => Kind::Date,
+        ("string", Some("time")) => Kind::Time,
+        ("integer", _) => Kind::Integer,
+        ("number", _) => Kind::Number,
+        ("boolean", _) => Kind::Boolean,
+        ("object", _) => Kind::Object(json_fields(property)),
+        ("array", _) => {
+            let min = property.min_items.unwrap_or(1);
+            let max = property.max_items.unwrap_or(min.max(3));
+            let item = match &property.items {
+                Some(items) => json_field(name, items),
+                None => Field::new(name, Kind::Text),
+            };
+            Kind::Array(Box::new(item), min, max.max(min))
+        }
+        _ => Kind::Text,
+    };
+    let mut field = Field::new(name, kind);
+    field.serial = name == "id" && field.kind == Kind::Integer;
+    field.choices = property.choices.iter().map(Datum::from_json).collect();
+    field.max_len = property.max_length;
+    field.min = property.minimum;
+    field.max = property.maximum;
+    field
+}
+
+/// SQL without `--` and `/* */` comments.
+fn strip_comments(sql: &str) -> String {
+    let mut out = String::new();
+    let mut rest = sql;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find("*/")
+            .map_or("", |end| &rest[start + end + 2..]);
+    }
+    out.push_str(rest);
+    out.lines()
+        .map(|line| match line.find("--") {
+            Some(i) if line[..i].matches('\'').count() % 2 == 0 => &line[..i],
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `text` split at the commas outside parentheses and quotes.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut quoted, mut start) = (0i32, false, 0);
+    for (i, c) in text.char_indices() {
+        match c {
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+fn unquote(name: &str) -> String {
+    name.trim_matches(|c| matches!(c, '"' | '`' | '[' | ']'))
+        .to_string()
+}
+
+/// Quoted literals of `text` (`'a', 'b'`).
+fn literals(text: &str) -> Vec<Datum> {
+    text.split('\'')
+        .skip(1)
+        .step_by(2)
+        .map(|s| Datum::Text(s.to_string()))
+        .collect()
+}
+
+/// Tables of the `CREATE TABLE` statements of `sql`.
+fn sql_ddl(sql: &str) -> Vec<Table> {
+    let sql = strip_comments(sql);
+    let lower = sql.to_ascii_lowercase();
+    let mut tables = Vec::new();
+    let mut from = 0;
+    while let Some(found) = lower[from..].find("create table") {
+        let start = from + found + "create table".len();
+        let Some(open) = lower[start..].find('(').map(|i| start + i) else {
+            break;
+        };
+        let mut name = sql[start..open].trim();
+        if name.to_ascii_lowercase().starts_with("if not exists") {
+            name = name["if not exists".len()..].trim();
+        }
+        // Body up to the matching parenthesis
+        let mut depth = 0;
+        let mut close = sql.len();
+        for (i, c) in sql[open..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = open + i;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let body = &sql[(open + 1).min(close)..close];
+        let name = name.split('.').map(unquote).collect::<Vec<_>>().join(".");
+        tables.push(Table {
+            name,
+            fields: sql_fields(body),
+        });
+        from = close.max(start);
+    }
+    tables
+}
+
+fn sql_fields(body: &str) -> Vec<Field> {
+    let mut fields: Vec<Field> = Vec::new();
+    let mut primary_keys = Vec::new();
+    for item in split_top_level(body) {
+        let item = item.trim();
+        let lower = item.to_ascii_lowercase();
+        let first = lower.split_whitespace().next().unwrap_or_default();
+        if matches!(
+            first,
+            "constraint" | "primary" | "foreign" | "unique" | "check" | "key" | "index" | "exclude"
+        ) {
+            // PRIMARY KEY (id), also after CONSTRAINT <name>
+            if let Some(columns) = lower
+                .find("primary key")
+                .and_then(|i| lower[i + "primary key".len()..].trim().strip_prefix('('))
+                .and_then(|rest| rest.split(')').next())
+            {
+                primary_keys.extend(columns.split(',').map(|c| unquote(c.trim())));
+            }
+            continue;
+        }
+        let Some(name) = item.split_whitespace().next() else {
+            continue;
+        };
+        fields.push(sql_field(&unquote(name), item[name.len()..].trim()));
+    }
+    for field in &mut fields {
+        if primary_keys.contains(&field.name.to_ascii_lowercase()) && field.kind == Kind::Integer {
+            field.serial = true;
+        }
+    }
+    fields
+}
+
+/// Field of a column definition (`definition` is what follows the name).
+fn sql_field(name: &str, definition: &str) -> Field {
+    // Two-word types as one
+    let definition = definition
+        .replacen("character varying", "varchar", 1)
+        .replacen("CHARACTER VARYING", "varchar", 1)
+        .replacen("double precision", "double", 1)
+        .replacen("DOUBLE PRECISION", "double", 1);
+    let rest = definition.to_ascii_lowercase();
+    let rest = rest.as_str();
+    // Type name and arguments: varchar(120), numeric(10,2), enum('a','b')
+    let type_end = rest
+        .find(|c: char| c == '(' || c.is_whitespace())
+        .unwrap_or(rest.len());
+    let base = &rest[..type_end];
+    // Same offsets in the original text, whose literals keep their case
+    let args = definition[type_end..]
+        .trim_start()
+        .strip_prefix('(')
+        .and_then(|a| a.find(')').map(|end| &a[..end]))
+        .unwrap_or_default();
+    let kind = match base {
+        "smallint" | "int" | "integer" | "bigint" | "int2" | "int4" | "int8" | "mediumint"
+        | "serial" | "bigserial" | "smallserial" => Kind::Integer,
+        "tinyint" if args.trim() == "1" => Kind::Boolean,
+        "tinyint" => Kind::Integer,
+        "decimal" | "numeric" | "real" | "float" | "double" | "money" | "float4" | "float8" => {
+            Kind::Number
+        }
+        "bool" | "boolean" | "bit" => Kind::Boolean,
+        "uuid" | "uniqueidentifier" => Kind::Uuid,
+        "timestamp" | "timestamptz" | "datetime" | "datetime2" | "datetimeoffset" => {
+            Kind::Timestamp
+        }
+        "date" => Kind::Date,
+        "time" | "timetz" => Kind::Time,
+        "json" | "jsonb" => Kind::Json,
+        _ => Kind::Text,
+    };
+    let mut field = Field::new(name, kind);
+    if base == "enum" {
+        field.choices = literals(args);
+    }
+    if field.kind == Kind::Text
+        && let Ok(len) = args.trim().parse()
+    {
+        field.max_len = Some(len);
+    }
+    if field.kind == Kind::Integer && base.ends_with("serial")
+        || rest.contains("auto_increment")
+        || rest.contains("autoincrement")
+        || rest.contains("generated") && rest.contains("identity")
+        || rest.contains("nextval(")
+        || field.kind == Kind::Integer && rest.contains("primary key")
+    {
+        field.serial = true;
+    }
+    field.reference = rest.contains("references");
+    // CHECK (status IN ('a', 'b'))
+    if let Some(check) = rest.find("check")
+        && let Some(list) = rest[check..].find(" in ").map(|i| &definition[check + i..])
+    {
+        let values = literals(list.split(')').next().unwrap_or_default());
+        if !values.is_empty() {
+            field.choices = values;
+        }
+    }
+    field
+}
+
+/// Name of `field` in snake_case words, for the heuristics.
+fn normalized(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.char_indices() {
+        if c.is_ascii_uppercase() && i > 0 && !out.ends_with('_') {
+            out.push('_');
+        }
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    out
+}
+
+/// Values of the records being generated.
+struct Generator<'a> {
+    seed: u64,
+    locale: Locale,
+    count: usize,
+    table: &'a str,
+}
+
+impl Generator<'_> {
+    /// The record `row`, or an object nested in it at `path`; `identity` is
+    /// shared by the whole record.
+    fn record(
+        &self,
+        fields: &[Field],
+        identity: &Identity,
+        row: usize,
+        path: &str,
+    ) -> IndexMap<String, Datum> {
+        fields
+            .iter()
+            .map(|field| {
+                let path = format!("{path}.{}", field.name);
+                let mut rng = mix(self.seed, &[self.table, &path], row);
+                let value = self.value(field, identity, &mut rng, row, &path);
+                (field.name.clone(), value)
+            })
+            .collect()
+    }
+
+    fn value(
+        &self,
+        field: &Field,
+        identity: &Identity,
+        rng: &mut Rng,
+        row: usize,
+        path: &str,
+    ) -> Datum {
+        if !field.choices.is_empty() {
+            return rng.pick(&field.choices);
+        }
+        if field.serial {
+            return Datum::Int(row as i64 + 1);
+        }
+        let name = normalized(&field.name);
+        let has = |words: &[&str]| {
+            name.split('_').any(|part| words.contains(&part)) || words.contains(&name.as_str())
+        };
+        let value = match &field.kind {
+            Kind::Object(fields) => Datum::Record(self.record(fields, identity, row, path)),
+            Kind::Array(item, min, max) => {
+                let n = rng.range(*min as i64, *max as i64);
+                Datum::List(
+                    (0..n)
+                        .map(|i| {
+                            let path = format!("{path}[{i}]");
+                            let mut rng = mix(self.seed, &[self.table, &path], row);
+                            self.value(item, identity, &mut rng, row, &path)
+                        })
+                        .collect(),
+                )
+            }
+            Kind::Boolean => Datum::Bool(rng.chance()),
+            Kind::Integer => Datum::Int(self.integer(field, &name, rng)),
+            Kind::Number => {
+                let min = field.min.unwrap_or(1.0);
+                let max = field.max.unwrap_or(1000.0).max(min);
+                let cents = rng.range((min * 100.0).ceil() as i64, (max * 100.0).floor() as i64);
+                Datum::Num(cents as f64 / 100.0)
+            }
+            Kind::Uuid => Datum::Text(uuid(rng)),
+            Kind::Email => Datum::Text(email(identity, row)),
+            Kind::Url => Datum::Text(url(identity, rng)),
+            Kind::Timestamp => Datum::Text(timestamp(EPOCH + rng.range(0, 365 * DAY - 1))),
+            Kind::Date if has(&["birth", "birthday", "dob", "nascimento"]) => {
+                // Born between 1950 and 2005
+                Datum::Text(date(rng.range(-20 * 365, 35 * 365) * DAY))
+            }
+            Kind::Date => Datum::Text(date(EPOCH + rng.range(0, 364) * DAY)),
+            Kind::Time => Datum::Text(timestamp(rng.range(0, DAY - 1))[11..19].to_string()),
+            Kind::Json => Datum::Record(IndexMap::new()),
+            Kind::Text => Datum::Text(self.text(&name, &has, identity, rng, row)),
+        };
+        match (value, field.max_len) {
+            (Datum::Text(text), Some(max)) => Datum::Text(text.chars().take(max).collect()),
+            (value, _) => value,
+        }
+    }
+
+    fn integer(&self, field: &Field, name: &str, rng: &mut Rng) -> i64 {
+        if let (None, None) = (field.min, field.max) {
+            if field.reference || name.ends_with("_id") {
+                return rng.range(1, self.count as i64);
+            }
+            if name == "age" || name == "idade" {
+                return rng.range(18, 80);
+            }
+            if name.contains("quantity") || name.contains("qty") || name.contains("quantidade") {
+                return rng.range(1, 20);
+            }
+            if name == "year" || name == "ano" {
+                return rng.range(1990, 2024);
+            }
+        }
+        let min = field.min.map_or(1, |m| m.ceil() as i64);
+        let max = field.max.map_or(min.max(1) * 1000, |m| m.floor() as i64);
+        rng.range(min, max)
+    }
+
+    fn text(
+        &self,
+        name: &str,
+        has: &dyn Fn(&[&str]) -> bool,
+        identity: &Identity,
+        rng: &mut Rng,
+        row: usize,
+    ) -> String {
+        let locale = self.locale;
+        let is = |names: &[&str]| names.contains(&name);
+        if has(&["email", "mail"]) {
+            email(identity, row)
+        } else if is(&["first_name", "firstname", "given_name", "primeiro_nome"]) {
+            identity.first.to_string()
+        } else if is(&[
+            "last_name",
+            "lastname",
+            "surname",
+            "family_name",
+            "sobrenome",
+        ]) {
+            identity.last.to_string()
+        } else if is(&["username", "user_name", "login", "handle", "usuario"]) {
+            format!("{}{}", identity.handle(), row + 1)
+        } else if is(&[
+            "name",
+            "nome",
+            "full_name",
+            "fullname",
+            "display_name",
+            "nome_completo",
+        ]) || has(&["name", "nome"])
+            && has(&[
+                "customer", "cliente", "user", "usuario", "author", "autor", "owner", "contact",
+                "contato",
+            ])
+        {
+            identity.name()
+        } else if has(&[
+            "phone",
+            "telephone",
+            "mobile",
+            "cell",
+            "telefone",
+            "celular",
+            "fone",
+            "whatsapp",
+        ]) {
+            identity.phone(rng, locale)
+        } else if is(&[
+            "street",
+            "address_line",
+            "address1",
+            "address_line1",
+            "logradouro",
+            "rua",
+        ]) {
+            identity.street_line(locale)
+        } else if has(&["address", "endereco"]) {
+            identity.address(locale)
+        } else if has(&["city", "cidade", "town"]) {
+            identity.city.to_string()
+        } else if is(&["state", "estado", "uf", "province"]) {
+            identity.state.to_string()
+        } else if has(&["zip", "zipcode", "postal", "postcode", "cep"]) {
+            identity.postal.clone()
+        } else if has(&["country", "pais"]) {
+            locale.data().country.to_string()
+        } else if has(&["url", "website", "site", "homepage", "link"]) {
+            url(identity, rng)
+        } else if has(&["uuid", "guid"]) {
+            uuid(rng)
+        } else if name.ends_with("_at") || has(&["timestamp"]) {
+            timestamp(EPOCH + rng.range(0, 365 * DAY - 1))
+        } else if has(&["date"]) {
+            date(EPOCH + rng.range(0, 364) * DAY)
+        } else if has(&["title", "titulo", "subject", "assunto"]) {
+            sentence(rng, 3, 6)
+        } else if has(&[
+            "description",
+            "descricao",
+            "bio",
+            "body",
+            "content",
+            "conteudo",
+            "comment",
+            "notes",
+            "message",
+            "mensagem",
+        ]) {
+            format!("{}.", sentence(rng, 8, 16))
+        } else if has(&["slug"]) {
+            words(rng, 2, 4).join("-")
+        } else if has(&["password", "senha", "hash"]) {
+            format!("changeme-{:08x}", rng.next_u64() as u32)
+        } else if has(&["color", "colour", "cor"]) {
+            format!("#{:06x}", rng.below(0x100_0000))
+        } else if has(&["name", "nome"]) {
+            // Product, company or file names: capitalized words
+            sentence(rng, 1, 3)
+        } else {
+            words(rng, 2, 4).join(" ")
+        }
+    }
+}
+
+fn email(identity: &Identity, row: usize) -> String {
+    // The row keeps e-mails unique
+    format!("{}{}@{}", identity.handle(), row + 1, identity.domain)
+}
+
+fn url(identity: &Identity, rng: &mut Rng) -> String {
+    format!("https://{}/{}", identity.domain, words(rng, 1, 2).join("-"))
+}
+
+/// Records of each table, by table name.
+pub type Dataset = IndexMap<String, Vec<IndexMap<String, Datum>>>;
+
+/// `count` records for each of `tables`.
+pub fn generate(tables: &[Table], count: usize, seed: u64, locale: Locale) -> DxResult<Dataset> {
+    if count == 0 || count > MAX_COUNT {
+        return Err(DxError::InvalidInput(format!(
+            "Quantidade de registros inválida: {count} (use de 1 a {MAX_COUNT})."
+        )));
+    }
+    Ok(tables
+        .iter()
+        .map(|table| {
+            let generator = Generator {
+                seed,
+                locale,
+                count,
+                table: &table.name,
+            };
+            let rows = (0..count)
+                .map(|row| {
+                    let identity = Identity::new(&mut mix(seed, &[&table.name], row), locale);
+                    generator.record(&table.fields, &identity, row, "")
+                })
+                .collect();
+            (table.name.clone(), rows)
+        })
+        .collect())
+}
+
+/// `INSERT` statements of `data`, one per table.
+pub fn to_sql(data: &Dataset) -> String {
+    let mut out = String::new();
+    for (table, rows) in data {
+        let Some(first) = rows.first() else {
+            continue;
+        };
+        let columns: Vec<&str> = first.keys().map(String::as_str).collect();
+        out.push_str(&format!(
+            "INSERT INTO {table} ({}) VALUES\n",
+            columns.join(", ")
+        ));
+        let values: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let values: Vec<String> = row.values().map(Datum::sql).collect();
+                format!("  ({})", values.join(", "))
+            })
+            .collect();
+        out.push_str(&values.join(",\n"));
+        out.push_str(";\n\n");
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+/// JSON of `data`: the records of a single table, or an object by table.
+pub fn to_json(data: &Dataset) -> String {
+    let json = match data.values().next() {
+        Some(rows) if data.len() == 1 => serde_json::to_string_pretty(rows),
+        _ => serde_json::to_string_pretty(data),
+    };
+    json.unwrap_or_default() + "\n"
+}
+
+pub struct FixtureOptions {
+    pub count: usize,
+    pub seed: u64,
+    pub locale: Locale,
+    /// Defaults to the format of the schema
+    pub format: Option<Format>,
+    /// Written instead of printed (relative to the project)
+    pub output: Option<PathBuf>,
+}
+
+/// Result of `dx tests fixtures`.
+#[derive(Serialize)]
+pub struct Fixtures {
+    schema: String,
+    format: Format,
+    locale: Locale,
+    seed: u64,
+    count: usize,
+    output: Option<String>,
+    tables: Dataset,
+    #[serde(skip)]
+    content: String,
+}
+
+impl Render for Fixtures {
+    fn render_text(&self) {
+        match &self.output {
+            Some(output) => {
+                let tables: Vec<&str> = self.tables.keys().map(String::as_str).collect();
+                outln!(
+                    "{} registro(s) por tabela ({}) gravados em {} (seed {}, {}).",
+                    self.count,
+                    tables.join(", "),
+                    output,
+                    self.seed,
+                    self.locale.id()
+                );
+            }
+            None => out!("{}", self.content),
+        }
+    }
+}
+
+/// `dx tests fixtures`: fake records for the tables of `schema`.
+pub fn fixtures(dir: Option<PathBuf>, schema: &Path, options: FixtureOptions) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    let schema_path = project_dir.join(schema);
+    let (schema_format, tables) = load(&schema_path)?;
+    let data = generate(&tables, options.count, options.seed, options.locale)?;
+    let format = options.format.unwrap_or(schema_format);
+    let content = match format {
+        Format::Json => to_json(&data),
+        Format::Sql => to_sql(&data),
+    };
+    let output = match &options.output {
+        Some(output) => {
+            let path = project_dir.join(output);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .context(format!("Erro ao criar {}", parent.display()))?;
+            }
+            std::fs::write(&path, &content)
+                .context(format!("Erro ao salvar {}", path.display()))?;
+            Some(path.display().to_string())
+        }
+        None => None,
+    };
+    crate::output::render(&Fixtures {
+        schema: schema_path.display().to_string(),
+        format,
+        locale: options.locale,
+        seed: options.seed,
+        count: options.count,
+        output,
+        tables: data,
+        content,
+    });
+    Ok(())
+}
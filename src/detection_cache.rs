@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Project detection cache (`.dx/cache/detection.json`).
+//!
+//! Detection results are stored per project with a fingerprint of the files
+//! detection may read (the project's sources and its `.dx/services.d`
+//! catalog): the path, size and modification time of each one. A
+//! run over an unchanged project reuses the stored result instead of reading
+//! the sources again. Files modified in the last seconds also contribute their
+//! content, since a second write within the filesystem's timestamp resolution
+//! leaves the mtime unchanged. `--no-cache` skips the cache; in CI mode it is
+//! read but never written.
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Directories no detector reads.
+const SKIP_DIRS: &[&str] = &[
+    ".git",
+    ".dx",
+    ".terraform",
+    "cdk.out",
+    "node_modules",
+    "target",
+    "build",
+    "dist",
+    "vendor",
+];
+
+/// Files modified more recently than this are fingerprinted by content.
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// Apply `--no-cache`.
+pub fn init(no_cache: bool) {
+    DISABLED.store(no_cache, Ordering::Relaxed);
+}
+
+fn cache_path(project_dir: &Path) -> PathBuf {
+    project_dir.join(".dx").join("cache").join("detection.json")
+}
+
+/// Fingerprint of the files under `project_dir`.
+fn fingerprint(project_dir: &Path) -> String {
+    let mut walker = ignore::WalkBuilder::new(project_dir);
+    walker
+        .standard_filters(false)
+        .follow_links(false)
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_some_and(|t| t.is_dir())
+                || !entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| SKIP_DIRS.contains(&name))
+        });
+
+    let mut files: Vec<(PathBuf, u64, SystemTime)> = walker
+        .build()
+        .flatten()
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            let modified = meta.modified().unwrap_or(UNIX_EPOCH);
+            Some((e.into_path(), meta.len(), modified))
+        })
+        .collect();
+    // The project's service catalog is read from `.dx`, skipped above
+    let catalog = std::fs::read_dir(crate::catalog::dir(project_dir));
+    files.extend(catalog.into_iter().flatten().flatten().filter_map(|e| {
+        let meta = e.metadata().ok().filter(|m| m.is_file())?;
+        let modified = meta.modified().unwrap_or(UNIX_EPOCH);
+        Some((e.path(), meta.len(), modified))
+    }));
+    files.sort();
+
+    let now = SystemTime::now();
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    for (path, len, modified) in &files {
+        path.strip_prefix(project_dir)
+            .unwrap_or(path)
+            .hash(&mut hasher);
+        len.hash(&mut hasher);
+        modified.hash(&mut hasher);
+        let recent = now
+            .duration_since(*modified)
+            .map_or(true, |age| age < RACY_WINDOW);
+        if recent && let Ok(content) = std::fs::read(path) {
+            content.hash(&mut hasher);
+        }
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Result of `detect` for `project_dir`, from the cache when the project's
+/// files haven't changed since it was stored under `key`.
+pub fn cached<T: Serialize + DeserializeOwned>(
+    project_dir: &Path,
+    key: &str,
+    detect: impl FnOnce() -> T,
+) -> T {
+    if DISABLED.load(Ordering::Relaxed) {
+        return detect();
+    }
+    let path = cache_path(project_dir);
+    let fingerprint = fingerprint(project_dir);
+    let mut cache: Value = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .filter(Value::is_object)
+        .unwrap_or_else(|| json!({}));
+
+    let entry = &cache[key];
+    if entry["fingerprint"] == fingerprint.as_str()
+        && let Ok(value) = T::deserialize(&entry["value"])
+    {
+        tracing::debug!(
+            key,
+            path = %path.display(),
+            "cache de detecção válido (--no-cache refaz a varredura)"
+        );
+        return value;
+    }
+
+    let value = detect();
+    if crate::ci::is_ci() {
+        return value;
+    }
+    match serde_json::to_value(&value) {
+        Ok(stored) => {
+            cache[key] = json!({ "fingerprint": fingerprint, "value": stored });
+            let written = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&path, format!("{cache:#}\n")));
+            if let Err(e) = written {
+                tracing::debug!(path = %path.display(), "não foi possível gravar o cache de detecção: {e}");
+            }
+        }
+        Err(e) => tracing::debug!(key, "resultado de detecção não serializável: {e}"),
+    }
+    value
+}
@@ -4,53 +4,34 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::dev_services;
+use crate::error::{DxResult, IoContext};
+use crate::i18n::DocLanguage;
 
 const START_MARKER: &str = "<!-- dx-cli:badges:start -->";
 const END_MARKER: &str = "<!-- dx-cli:badges:end -->";
 
 /// Generate a Markdown line with badges for the given services
-pub fn generate_badges_markdown(services: &[String]) -> String {
+pub fn generate_badges_markdown(project_dir: &Path, services: &[String]) -> String {
     use std::collections::HashSet;
 
-    // Build the same badges as the Analyzer report
-    let mut badges: HashSet<&str> = HashSet::new();
+    // Alt text in the project's documentation language
+    let lang = DocLanguage::of(project_dir);
+    let mut badges: HashSet<String> = HashSet::new();
 
+    let detectors = crate::detectors::catalog(project_dir);
     for s in services {
         let kl = s.to_lowercase();
-        match kl.as_str() {
-            // Databases
-            "postgres" | "postgresql" => {
-                badges.insert("[![PostgreSQL](https://img.shields.io/badge/PostgreSQL-Dev_Service-blue?logo=postgresql)](#)");
-            }
-            "mysql" | "mariadb" => {
-                badges.insert(
-                    "[![MySQL](https://img.shields.io/badge/MySQL-Dev_Service-blue?logo=mysql)](#)",
-                );
-            }
-            "mongodb" => {
-                badges.insert("[![MongoDB](https://img.shields.io/badge/MongoDB-Dev_Service-green?logo=mongodb)](#)");
-            }
-            // Cache
-            "redis" => {
-                badges.insert(
-                    "[![Redis](https://img.shields.io/badge/Redis-Dev_Service-red?logo=redis)](#)",
-                );
-            }
-            // Streaming (Kafka API)
-            "kafka" => {
-                badges.insert("[![Kafka](https://img.shields.io/badge/Kafka-Dev_Service-black?logo=apachekafka)](#)");
-            }
-            // Data processing (Flink detected by jobmanager/taskmanager too)
-            "flink" | "jobmanager" | "taskmanager" => {
-                badges.insert("[![Apache Flink](https://img.shields.io/badge/Flink-Dev_Service-orange?logo=apacheflink)](#)");
-            }
-            // Skip tools like kafka-ui
-            "kafka-ui" => {}
-            _ => {}
+        // Each detector's badge, also for its companion services (kafka-ui, taskmanager, ...)
+        if let Some(badge) = detectors
+            .iter()
+            .find(|d| d.provides(&kl))
+            .and_then(|d| d.badge())
+        {
+            badges.insert(lang.badge(badge));
         }
     }
 
-    let mut badge_lines: Vec<&str> = badges.into_iter().collect();
+    let mut badge_lines: Vec<String> = badges.into_iter().collect();
     badge_lines.sort();
 
     // Always append the dx-anywhere badge at the end (using repo logo)
@@ -112,7 +93,8 @@ pub fn upsert_badges_in_readme(project_dir: &Path, badges_line: &str) -> std::io
     } else {
         // Create a minimal README with badges
         let mut content = String::new();
-        content.push_str("# Projeto\n\n");
+        let title = DocLanguage::of(project_dir).pick("Projeto", "Project", "Proyecto");
+        content.push_str(&format!("# {title}\n\n"));
         content.push_str(&replacement_block);
         fs::write(&readme_path, content)?;
     }
@@ -121,38 +103,35 @@ pub fn upsert_badges_in_readme(project_dir: &Path, badges_line: &str) -> std::io
 }
 
 /// Process one directory: detect services and apply badges (print or save)
-pub fn process_directory(save_file: bool, project_dir: &Path) {
+pub fn process_directory(save_file: bool, project_dir: &Path) -> DxResult {
     let config = dev_services::detect_dependencies(project_dir);
-    let mut services: Vec<String> = config.services.keys().cloned().collect();
-    services.sort();
+    let services = config.service_names();
 
-    let badges = generate_badges_markdown(&services);
+    let badges = generate_badges_markdown(project_dir, &services);
 
-    println!(
+    outln!(
         "Badges detectados para {}:\n{}\n",
         project_dir.display(),
         badges
     );
 
     if save_file {
-        match upsert_badges_in_readme(project_dir, &badges) {
-            Ok(path) => println!("README atualizado: {}", path.display()),
-            Err(e) => eprintln!(
-                "Erro ao atualizar README em {}: {}",
-                project_dir.display(),
-                e
-            ),
-        }
+        let path = upsert_badges_in_readme(project_dir, &badges).context(format!(
+            "Erro ao atualizar README em {}",
+            project_dir.display()
+        ))?;
+        outln!("README atualizado: {}", path.display());
     } else {
-        println!("Execução em modo --no-save. Para salvar badges, execute: dx-cli dev-badges");
+        outln!("Execução em modo --no-save. Para salvar badges, execute: dx-cli dev-badges");
     }
+    Ok(())
 }
 
 /// Remove the badges block from README.md if present. Returns (path, removed?)
 pub fn remove_badges_in_readme(project_dir: &Path) -> std::io::Result<(PathBuf, bool)> {
     let readme_path = project_dir.join("README.md");
     if !readme_path.exists() {
-        println!(
+        outln!(
             "README inexistente em {} — nada para limpar.",
             project_dir.display()
         );
@@ -161,14 +140,14 @@ pub fn remove_badges_in_readme(project_dir: &Path) -> std::io::Result<(PathBuf,
 
     let content = fs::read_to_string(&readme_path)?;
     let Some(start_idx) = content.find(START_MARKER) else {
-        println!(
+        outln!(
             "Nenhum bloco de badges encontrado em {}.",
             readme_path.display()
         );
         return Ok((readme_path, false));
     };
     let Some(end_start) = content.find(END_MARKER) else {
-        println!(
+        outln!(
             "Marcador inicial encontrado mas o final não existe em {} — nenhuma alteração.",
             readme_path.display()
         );
@@ -185,7 +164,7 @@ pub fn remove_badges_in_readme(project_dir: &Path) -> std::io::Result<(PathBuf,
     let cleaned = collapse_blank_lines(&new_content);
 
     fs::write(&readme_path, cleaned)?;
-    println!("Badges removidos de {}", readme_path.display());
+    outln!("Badges removidos de {}", readme_path.display());
     Ok((readme_path, true))
 }
 
@@ -206,13 +185,10 @@ fn collapse_blank_lines(s: &str) -> String {
 }
 
 /// Orchestrates cleaning for a directory
-pub fn process_clean_directory(project_dir: &Path) {
-    match remove_badges_in_readme(project_dir) {
-        Ok((_path, removed)) => {
-            if !removed {
-                // nothing removed
-            }
-        }
-        Err(e) => eprintln!("Erro ao limpar badges em {}: {}", project_dir.display(), e),
-    }
+pub fn process_clean_directory(project_dir: &Path) -> DxResult {
+    remove_badges_in_readme(project_dir).context(format!(
+        "Erro ao limpar badges em {}",
+        project_dir.display()
+    ))?;
+    Ok(())
 }
@@ -9,6 +9,9 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use crate::error::{DxError, DxResult, IoContext};
+use crate::output::Render;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Stack {
     Rust,
@@ -17,9 +20,31 @@ enum Stack {
     Go,
     JavaMaven,
     JavaGradle,
+    Kotlin,
+    KotlinMultiplatform,
+    Scala,
+    Swift,
+    Cpp,
     Unknown,
 }
 
+/// Gradle projects applying the Kotlin (or Kotlin Multiplatform) plugin.
+fn gradle_stack(dir: &Path) -> Stack {
+    let build: String = ["build.gradle.kts", "build.gradle"]
+        .iter()
+        .filter_map(|f| fs::read_to_string(dir.join(f)).ok())
+        .collect();
+    if build.contains("kotlin(\"multiplatform\")")
+        || build.contains("org.jetbrains.kotlin.multiplatform")
+    {
+        Stack::KotlinMultiplatform
+    } else if build.contains("kotlin(\"") || build.contains("org.jetbrains.kotlin") {
+        Stack::Kotlin
+    } else {
+        Stack::JavaGradle
+    }
+}
+
 impl Stack {
     fn detect(dir: &Path) -> Self {
         if dir.join("Cargo.toml").exists() {
@@ -33,7 +58,16 @@ impl Stack {
         } else if dir.join("pom.xml").exists() {
             Stack::JavaMaven
         } else if dir.join("build.gradle").exists() || dir.join("build.gradle.kts").exists() {
-            Stack::JavaGradle
+            gradle_stack(dir)
+        } else if dir.join("build.sbt").exists() {
+            Stack::Scala
+        } else if dir.join("Package.swift").exists() {
+            Stack::Swift
+        } else if ["CMakeLists.txt", "conanfile.txt", "conanfile.py"]
+            .iter()
+            .any(|f| dir.join(f).exists())
+        {
+            Stack::Cpp
         } else {
             Stack::Unknown
         }
@@ -49,6 +83,11 @@ impl fmt::Display for Stack {
             Stack::Go => "Go",
             Stack::JavaMaven => "Java (Maven)",
             Stack::JavaGradle => "Java (Gradle)",
+            Stack::Kotlin => "Kotlin (Gradle)",
+            Stack::KotlinMultiplatform => "Kotlin Multiplatform",
+            Stack::Scala => "Scala (sbt)",
+            Stack::Swift => "Swift (SwiftPM)",
+            Stack::Cpp => "C/C++ (CMake)",
             Stack::Unknown => "Desconhecida",
         };
         write!(f, "{name}")
@@ -84,75 +123,87 @@ fn project_dir(dir: Option<PathBuf>) -> PathBuf {
     dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
 }
 
-pub fn list(dir: Option<PathBuf>) {
-    let project_dir = project_dir(dir);
-    let stack = Stack::detect(&project_dir);
-    println!("Stack detectada: {}", stack);
+/// Keys currently stored in the project's `.dx/config.json`.
+pub fn keys(project_dir: &Path) -> Vec<String> {
+    Config::load(&config_path(project_dir)).0.into_keys().collect()
+}
 
-    let path = config_path(&project_dir);
-    let cfg = Config::load(&path);
-    if cfg.0.is_empty() {
-        println!("Nenhuma configuração encontrada.");
-    } else {
-        for (k, v) in cfg.0 {
-            println!("- {k} = {v}");
+/// Key/value pairs stored in the project's `.dx/config.json`.
+pub fn entries(project_dir: &Path) -> BTreeMap<String, String> {
+    Config::load(&config_path(project_dir)).0
+}
+
+/// Result of `dx dev-config list`.
+#[derive(Serialize)]
+pub struct ConfigList {
+    pub stack: String,
+    pub entries: BTreeMap<String, String>,
+}
+
+impl Render for ConfigList {
+    fn render_text(&self) {
+        outln!("Stack detectada: {}", self.stack);
+        if self.entries.is_empty() {
+            outln!("Nenhuma configuração encontrada.");
+        }
+        for (k, v) in &self.entries {
+            outln!("- {k} = {v}");
         }
     }
 }
 
-pub fn add(dir: Option<PathBuf>, key: String, value: String) {
+pub fn list(dir: Option<PathBuf>) -> DxResult {
+    let project_dir = project_dir(dir);
+    crate::output::render(&ConfigList {
+        stack: Stack::detect(&project_dir).to_string(),
+        entries: entries(&project_dir),
+    });
+    Ok(())
+}
+
+pub fn add(dir: Option<PathBuf>, key: String, value: String) -> DxResult {
     let project_dir = project_dir(dir);
     let stack = Stack::detect(&project_dir);
-    println!("Stack detectada: {}", stack);
+    outln!("Stack detectada: {}", stack);
 
     let path = config_path(&project_dir);
     let mut cfg = Config::load(&path);
     if cfg.0.contains_key(&key) {
-        println!("Configuração '{key}' já existe.");
-        return;
+        return Err(DxError::InvalidInput(format!("Configuração '{key}' já existe.")));
     }
     cfg.0.insert(key.clone(), value);
-    if let Err(e) = cfg.save(&path) {
-        eprintln!("Erro ao salvar configurações: {e}");
-    } else {
-        println!("Configuração '{key}' criada.");
-    }
+    cfg.save(&path).context("Erro ao salvar configurações")?;
+    outln!("Configuração '{key}' criada.");
+    Ok(())
 }
 
-pub fn update(dir: Option<PathBuf>, key: String, value: String) {
+pub fn update(dir: Option<PathBuf>, key: String, value: String) -> DxResult {
     let project_dir = project_dir(dir);
     let stack = Stack::detect(&project_dir);
-    println!("Stack detectada: {}", stack);
+    outln!("Stack detectada: {}", stack);
 
     let path = config_path(&project_dir);
     let mut cfg = Config::load(&path);
     if !cfg.0.contains_key(&key) {
-        println!("Configuração '{key}' não existe.");
-        return;
+        return Err(DxError::NotFound(format!("Configuração '{key}' não existe.")));
     }
     cfg.0.insert(key.clone(), value);
-    if let Err(e) = cfg.save(&path) {
-        eprintln!("Erro ao salvar configurações: {e}");
-    } else {
-        println!("Configuração '{key}' atualizada.");
-    }
+    cfg.save(&path).context("Erro ao salvar configurações")?;
+    outln!("Configuração '{key}' atualizada.");
+    Ok(())
 }
 
-pub fn delete(dir: Option<PathBuf>, key: String) {
+pub fn delete(dir: Option<PathBuf>, key: String) -> DxResult {
     let project_dir = project_dir(dir);
     let stack = Stack::detect(&project_dir);
-    println!("Stack detectada: {}", stack);
+    outln!("Stack detectada: {}", stack);
 
     let path = config_path(&project_dir);
     let mut cfg = Config::load(&path);
-    if cfg.0.remove(&key).is_some() {
-        if let Err(e) = cfg.save(&path) {
-            eprintln!("Erro ao salvar configurações: {e}");
-        } else {
-            println!("Configuração '{key}' removida.");
-        }
-    } else {
-        println!("Configuração '{key}' não existe.");
+    if cfg.0.remove(&key).is_none() {
+        return Err(DxError::NotFound(format!("Configuração '{key}' não existe.")));
     }
+    cfg.save(&path).context("Erro ao salvar configurações")?;
+    outln!("Configuração '{key}' removida.");
+    Ok(())
 }
-
@@ -5,8 +5,15 @@ use serde_json::Value;
 use std::collections::BTreeMap;
 use std::fs;
 use std::io;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
-use toml_edit::{value, Document};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use toml_edit::{value, DocumentMut};
+
+use crate::error::{DxError, DxResult, IoContext};
+use crate::output::Render;
+use crate::settings::RegistrySettings;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Stack {
@@ -18,10 +25,31 @@ enum Stack {
     Gradle,
     Php,
     Ruby,
+    Sbt,
+    Swift,
+    Cpp,
     Unknown,
 }
 
 impl Stack {
+    /// Name of the stack in JSON output.
+    fn id(self) -> &'static str {
+        match self {
+            Stack::Node => "node",
+            Stack::Rust => "rust",
+            Stack::Python => "python",
+            Stack::Go => "go",
+            Stack::Maven => "maven",
+            Stack::Gradle => "gradle",
+            Stack::Php => "php",
+            Stack::Ruby => "ruby",
+            Stack::Sbt => "sbt",
+            Stack::Swift => "swift",
+            Stack::Cpp => "cpp",
+            Stack::Unknown => "unknown",
+        }
+    }
+
     fn detect(dir: &Path) -> Stack {
         if dir.join("package.json").exists() {
             Stack::Node
@@ -42,16 +70,132 @@ impl Stack {
             Stack::Php
         } else if dir.join("Gemfile").exists() {
             Stack::Ruby
+        } else if dir.join("build.sbt").exists() {
+            Stack::Sbt
+        } else if dir.join("Package.swift").exists() {
+            Stack::Swift
+        } else if ["conanfile.txt", "conanfile.py", "vcpkg.json", "CMakeLists.txt"]
+            .iter()
+            .any(|f| dir.join(f).exists())
+        {
+            Stack::Cpp
         } else {
             Stack::Unknown
         }
     }
 }
 
+/// Timing/progress label shared by every registry lookup.
+const REGISTRY_PHASE: &str = "Consultando registries";
+
+/// Registry mirrors (`[registries]` in dx.toml) of the project being processed.
+static MIRRORS: Mutex<Option<RegistrySettings>> = Mutex::new(None);
+
+/// `--offline`: no registry is queried, latest versions stay unknown.
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Set by `update`: latest versions come from the registries, not the cache.
+static REFRESH: AtomicBool = AtomicBool::new(false);
+
+/// Apply the registry mirrors and network limits of `dir`'s dx.toml.
+fn use_settings(dir: &Path) {
+    let settings = crate::settings::get(dir);
+    crate::net::configure(&settings.network);
+    *MIRRORS.lock().unwrap() = Some(settings.registries);
+}
+
+/// Base URL of a registry: the configured mirror, or `default`.
+fn registry_base(default: &str, mirror: fn(&RegistrySettings) -> &Option<String>) -> String {
+    MIRRORS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|m| mirror(m).as_deref())
+        .map(|base| base.trim_end_matches('/').to_string())
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Map `items` through `f` on up to `[network] concurrency` threads, so the
+/// registry lookups of a project overlap; `crate::net` still limits the
+/// requests per host. The results keep the order of `items`.
+fn in_parallel<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let workers = crate::net::concurrency().min(items.len());
+    if workers <= 1 || OFFLINE.load(Ordering::Relaxed) {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(i) else {
+                            return done;
+                        };
+                        done.push((i, f(item)));
+                    }
+                })
+            })
+            .collect();
+        handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+    });
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+fn unknown_stack() -> DxError {
+    DxError::Unsupported("Stack não suportada ou não detectada.".to_string())
+}
+
+fn unsupported(stack: &str) -> DxError {
+    DxError::Unsupported(format!("Operação não suportada para {stack}."))
+}
+
+fn not_found(name: &str) -> DxError {
+    DxError::NotFound(format!("Dependência '{name}' não encontrada."))
+}
+
 fn project_dir(dir: Option<PathBuf>) -> PathBuf {
     dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
 }
 
+/// A dev dependency as declared in the project's manifest.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DeclaredDependency {
+    pub name: String,
+    pub version: String,
+}
+
+impl From<(String, String)> for DeclaredDependency {
+    fn from((name, version): (String, String)) -> Self {
+        DeclaredDependency { name, version }
+    }
+}
+
+/// Result of `dx dev-dependencies list`.
+#[derive(serde::Serialize)]
+pub struct DependencyList {
+    pub stack: &'static str,
+    pub dependencies: Vec<DeclaredDependency>,
+}
+
+impl Render for DependencyList {
+    fn render_text(&self) {
+        if self.dependencies.is_empty() {
+            outln!("Nenhuma dependência encontrada.");
+        }
+        for dep in &self.dependencies {
+            outln!("- {} = {}", dep.name, dep.version);
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DependencyInfo {
     pub name: String,
@@ -67,9 +211,10 @@ impl DependencyInfo {
     }
 }
 
-pub fn list(dir: Option<PathBuf>) {
+pub fn list(dir: Option<PathBuf>) -> DxResult {
     let project_dir = project_dir(dir);
-    match Stack::detect(&project_dir) {
+    let stack = Stack::detect(&project_dir);
+    let dependencies = match stack {
         Stack::Node => list_node(&project_dir),
         Stack::Rust => list_rust(&project_dir),
         Stack::Python => list_python(&project_dir),
@@ -78,12 +223,19 @@ pub fn list(dir: Option<PathBuf>) {
         Stack::Gradle => list_gradle(&project_dir),
         Stack::Php => list_php(&project_dir),
         Stack::Ruby => list_ruby(&project_dir),
-        Stack::Unknown => println!("Stack não suportada ou não detectada."),
-    }
+        Stack::Sbt => list_sbt(&project_dir),
+        Stack::Swift => list_swift(&project_dir),
+        Stack::Cpp => list_cpp(&project_dir),
+        Stack::Unknown => return Err(unknown_stack()),
+    };
+    crate::output::render(&DependencyList { stack: stack.id(), dependencies });
+    Ok(())
 }
 
-pub fn add(dir: Option<PathBuf>, name: String, version: Option<String>) {
+pub fn add(dir: Option<PathBuf>, name: String, version: Option<String>) -> DxResult {
     let project_dir = project_dir(dir);
+    // Without a version, the latest comes from the registry
+    use_settings(&project_dir);
     match Stack::detect(&project_dir) {
         Stack::Node => add_node(&project_dir, name, version),
         Stack::Rust => add_rust(&project_dir, name, version),
@@ -93,13 +245,23 @@ pub fn add(dir: Option<PathBuf>, name: String, version: Option<String>) {
         Stack::Maven => add_maven(&project_dir, name, version),
         Stack::Gradle => add_gradle(&project_dir, name, version),
         Stack::Ruby => add_ruby(&project_dir, name, version),
-        Stack::Unknown => println!("Stack não suportada ou não detectada."),
+        Stack::Sbt => Err(unsupported("sbt")),
+        Stack::Swift => Err(unsupported("Swift")),
+        Stack::Cpp => Err(unsupported("C/C++")),
+        Stack::Unknown => Err(unknown_stack()),
     }
 }
 
-pub fn update(dir: Option<PathBuf>, name: Option<String>) {
+pub fn update(dir: Option<PathBuf>, name: Option<String>) -> DxResult {
+    if OFFLINE.load(Ordering::Relaxed) {
+        return Err(DxError::InvalidInput(
+            "dev-dependencies update consulta os registries; não pode ser usado com --offline.".to_string(),
+        ));
+    }
     let project_dir = project_dir(dir);
-    match Stack::detect(&project_dir) {
+    use_settings(&project_dir);
+    REFRESH.store(true, Ordering::Relaxed);
+    crate::progress::phase(REGISTRY_PHASE, || match Stack::detect(&project_dir) {
         Stack::Node => update_node(&project_dir, name),
         Stack::Rust => update_rust(&project_dir, name),
         Stack::Python => update_python(&project_dir, name),
@@ -108,11 +270,14 @@ pub fn update(dir: Option<PathBuf>, name: Option<String>) {
         Stack::Maven => update_maven(&project_dir, name),
         Stack::Gradle => update_gradle(&project_dir, name),
         Stack::Ruby => update_ruby(&project_dir, name),
-        Stack::Unknown => println!("Stack não suportada ou não detectada."),
-    }
+        Stack::Sbt => Err(unsupported("sbt")),
+        Stack::Swift => Err(unsupported("Swift")),
+        Stack::Cpp => Err(unsupported("C/C++")),
+        Stack::Unknown => Err(unknown_stack()),
+    })
 }
 
-pub fn delete(dir: Option<PathBuf>, name: String) {
+pub fn delete(dir: Option<PathBuf>, name: String) -> DxResult {
     let project_dir = project_dir(dir);
     match Stack::detect(&project_dir) {
         Stack::Node => delete_node(&project_dir, name),
@@ -123,11 +288,66 @@ pub fn delete(dir: Option<PathBuf>, name: String) {
         Stack::Maven => delete_maven(&project_dir, name),
         Stack::Gradle => delete_gradle(&project_dir, name),
         Stack::Ruby => delete_ruby(&project_dir, name),
-        Stack::Unknown => println!("Stack não suportada ou não detectada."),
+        Stack::Sbt => Err(unsupported("sbt")),
+        Stack::Swift => Err(unsupported("Swift")),
+        Stack::Cpp => Err(unsupported("C/C++")),
+        Stack::Unknown => Err(unknown_stack()),
     }
 }
 
 pub fn get_dependencies(dir: &Path) -> io::Result<Vec<DependencyInfo>> {
+    use_settings(dir);
+    crate::progress::phase(REGISTRY_PHASE, || collect_dependencies(dir))
+}
+
+/// Declared versions of the dev dependencies `update` can change, read from
+/// the manifest without querying registries.
+pub fn versions(dir: &Path) -> BTreeMap<String, String> {
+    let declared = match Stack::detect(dir) {
+        Stack::Node => list_node(dir),
+        Stack::Rust => list_rust(dir),
+        Stack::Python => list_python(dir),
+        Stack::Php => list_php(dir),
+        Stack::Go => list_go(dir),
+        Stack::Maven => list_maven(dir),
+        Stack::Gradle => list_gradle(dir),
+        Stack::Ruby => list_ruby(dir),
+        _ => Vec::new(),
+    };
+    declared.into_iter().map(|d| (d.name, d.version)).collect()
+}
+
+fn string_map(object: Option<&Value>) -> BTreeMap<String, String> {
+    object
+        .and_then(|o| o.as_object())
+        .map(|o| {
+            o.iter()
+                .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Registry page of `version` of `name`, which links its changelog/release notes.
+pub fn release_url(dir: &Path, name: &str, version: &str) -> Option<String> {
+    // `~> 7.1`, `^8.0`: the version without its operator
+    let version = version.trim_start_matches(|c: char| !c.is_ascii_alphanumeric());
+    match Stack::detect(dir) {
+        Stack::Node => Some(format!("https://www.npmjs.com/package/{name}/v/{version}")),
+        Stack::Rust => Some(format!("https://crates.io/crates/{name}/{version}")),
+        Stack::Python => Some(format!("https://pypi.org/project/{name}/{version}/")),
+        Stack::Php => Some(format!("https://packagist.org/packages/{name}#{version}")),
+        Stack::Go => Some(format!("https://pkg.go.dev/{name}@{version}")),
+        Stack::Ruby => Some(format!("https://rubygems.org/gems/{name}/versions/{version}")),
+        Stack::Maven | Stack::Gradle => {
+            let (group, artifact) = name.split_once(':')?;
+            Some(format!("https://search.maven.org/artifact/{group}/{artifact}/{version}/jar"))
+        }
+        _ => None,
+    }
+}
+
+fn collect_dependencies(dir: &Path) -> io::Result<Vec<DependencyInfo>> {
     match Stack::detect(dir) {
         Stack::Node => Ok(get_node_dependencies(dir)),
         Stack::Rust => Ok(get_rust_dependencies(dir)),
@@ -137,6 +357,9 @@ pub fn get_dependencies(dir: &Path) -> io::Result<Vec<DependencyInfo>> {
         Stack::Gradle => Ok(get_gradle_dependencies(dir)),
         Stack::Php => Ok(get_php_dependencies(dir)),
         Stack::Ruby => Ok(get_ruby_dependencies(dir)),
+        Stack::Sbt => Ok(get_sbt_dependencies(dir)),
+        Stack::Swift => Ok(get_swift_dependencies(dir)),
+        Stack::Cpp => Ok(get_cpp_dependencies(dir)),
         Stack::Unknown => Ok(Vec::new()),
     }
 }
@@ -151,29 +374,19 @@ fn load_package_json(path: &Path) -> Value {
     serde_json::from_str(&data).unwrap_or_else(|_| Value::Object(Default::default()))
 }
 
-fn save_package_json(path: &Path, v: &Value) {
-    if let Ok(data) = serde_json::to_string_pretty(v) {
-        if let Err(e) = fs::write(path, data) {
-            eprintln!("Erro ao salvar package.json: {e}");
-        }
-    }
+fn save_package_json(path: &Path, v: &Value) -> DxResult {
+    let data = serde_json::to_string_pretty(v).unwrap_or_default();
+    fs::write(path, data).context("Erro ao salvar package.json")
 }
 
-fn list_node(dir: &Path) {
-    let path = node_package_json(dir);
-    let v = load_package_json(&path);
-    if let Some(obj) = v.get("devDependencies").and_then(|d| d.as_object()) {
-        for (k, v) in obj {
-            if let Some(ver) = v.as_str() {
-                println!("- {k} = {ver}");
-            }
-        }
-    } else {
-        println!("Nenhuma dependência encontrada.");
-    }
+fn list_node(dir: &Path) -> Vec<DeclaredDependency> {
+    string_map(load_package_json(&node_package_json(dir)).get("devDependencies"))
+        .into_iter()
+        .map(DeclaredDependency::from)
+        .collect()
 }
 
-fn add_node(dir: &Path, name: String, version: Option<String>) {
+fn add_node(dir: &Path, name: String, version: Option<String>) -> DxResult {
     let path = node_package_json(dir);
     let mut v = load_package_json(&path);
     let obj = v
@@ -183,23 +396,54 @@ fn add_node(dir: &Path, name: String, version: Option<String>) {
         .or_insert_with(|| Value::Object(Default::default()));
     if let Some(map) = obj.as_object_mut() {
         map.insert(name.clone(), Value::String(version.unwrap_or("*".into())));
-        save_package_json(&path, &v);
-        println!("Dependência '{name}' adicionada.");
+        save_package_json(&path, &v)?;
+        outln!("Dependência '{name}' adicionada.");
+    }
+    Ok(())
+}
+
+/// GET a registry endpoint, logging the request and its outcome.
+fn registry_get(url: &str) -> Option<String> {
+    if OFFLINE.load(Ordering::Relaxed) {
+        return None;
     }
+    tracing::debug!(url, "consultando registry");
+    crate::progress::detail(url);
+    crate::net::fetch_text(url)
+        .inspect_err(|e| tracing::warn!(url, error = %e, "falha ao consultar registry"))
+        .ok()
+}
+
+/// GET a registry endpoint that answers JSON.
+fn registry_json(url: &str) -> Option<Value> {
+    serde_json::from_str(&registry_get(url)?).ok()
+}
+
+/// Latest version published at `url`: from the registry cache while fresh,
+/// else `lookup`'s answer, which is stored. `update` always asks the
+/// registry.
+fn cached_latest(url: &str, lookup: impl FnOnce() -> Option<String>) -> Option<String> {
+    if !REFRESH.load(Ordering::Relaxed)
+        && let Some(version) = crate::registry_cache::get(url)
+    {
+        return Some(version);
+    }
+    let version = lookup()?;
+    crate::registry_cache::put(url, &version);
+    Some(version)
 }
 
 fn fetch_latest_node(name: &str) -> Option<String> {
-    let url = format!("https://registry.npmjs.org/{}/latest", name);
-    reqwest::blocking::get(url)
-        .ok()?
-        .json::<Value>()
-        .ok()?
-        .get("version")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
+    let url = format!("{}/{}/latest", registry_base("https://registry.npmjs.org", |m| &m.npm), name);
+    cached_latest(&url, || {
+        registry_json(&url)?
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    })
 }
 
-fn update_node(dir: &Path, name: Option<String>) {
+fn update_node(dir: &Path, name: Option<String>) -> DxResult {
     let path = node_package_json(dir);
     let mut v = load_package_json(&path);
     let obj = v
@@ -211,54 +455,58 @@ fn update_node(dir: &Path, name: Option<String>) {
         if let Some(n) = name {
             if let Some(latest) = fetch_latest_node(&n) {
                 map.insert(n.clone(), Value::String(latest));
-                println!("Dependência '{n}' atualizada.");
+                outln!("Dependência '{n}' atualizada.");
+            } else {
+                tracing::warn!(dependency = %n, "não foi possível obter a última versão; mantendo a atual");
             }
         } else {
-            for (k, val) in map.iter_mut() {
-                if let Some(latest) = fetch_latest_node(k) {
+            let names: Vec<String> = map.keys().cloned().collect();
+            let latest = in_parallel(&names, |n| fetch_latest_node(n));
+            for (val, latest) in map.values_mut().zip(latest) {
+                if let Some(latest) = latest {
                     *val = Value::String(latest);
                 }
             }
-            println!("Todas as dependências atualizadas.");
+            outln!("Todas as dependências atualizadas.");
         }
-        save_package_json(&path, &v);
+        save_package_json(&path, &v)?;
     }
+    Ok(())
 }
 
-fn delete_node(dir: &Path, name: String) {
+fn delete_node(dir: &Path, name: String) -> DxResult {
     let path = node_package_json(dir);
     let mut v = load_package_json(&path);
     if let Some(obj) = v
         .as_object_mut()
         .and_then(|o| o.get_mut("devDependencies"))
         .and_then(|d| d.as_object_mut())
+        && obj.remove(&name).is_some()
     {
-        if obj.remove(&name).is_some() {
-            println!("Dependência '{name}' removida.");
-        }
+        save_package_json(&path, &v)?;
+        outln!("Dependência '{name}' removida.");
+        return Ok(());
     }
-    save_package_json(&path, &v);
+    Err(not_found(&name))
 }
 
 fn get_node_dependencies(dir: &Path) -> Vec<DependencyInfo> {
     let path = node_package_json(dir);
     let v = load_package_json(&path);
-    let mut deps = Vec::new();
-    if let Some(obj) = v.get("devDependencies").and_then(|d| d.as_object()) {
-        for (k, v) in obj {
-            if let Some(ver) = v.as_str() {
-                let latest = fetch_latest_node(k);
-                deps.push(DependencyInfo {
-                    name: k.clone(),
-                    current_version: ver.to_string(),
-                    latest_version: latest.clone(),
-                    update_command: format!("npm install {}@latest -D", k),
-                    url: format!("https://www.npmjs.com/package/{}", k),
-                });
-            }
-        }
-    }
-    deps
+    let declared: Vec<(&String, &str)> = v
+        .get("devDependencies")
+        .and_then(|d| d.as_object())
+        .into_iter()
+        .flatten()
+        .filter_map(|(k, v)| Some((k, v.as_str()?)))
+        .collect();
+    in_parallel(&declared, |&(k, ver)| DependencyInfo {
+        name: k.clone(),
+        current_version: ver.to_string(),
+        latest_version: fetch_latest_node(k),
+        update_command: format!("npm install {}@latest -D", k),
+        url: format!("https://www.npmjs.com/package/{}", k),
+    })
 }
 
 // Rust helpers
@@ -266,30 +514,35 @@ fn cargo_toml(path: &Path) -> PathBuf {
     path.join("Cargo.toml")
 }
 
-fn load_cargo_toml(path: &Path) -> Document {
+fn load_cargo_toml(path: &Path) -> DocumentMut {
     let data = fs::read_to_string(path).unwrap_or_default();
-    data.parse::<Document>().unwrap_or_default()
-}
-
-fn save_cargo_toml(path: &Path, doc: &Document) {
-    if let Err(e) = fs::write(path, doc.to_string()) {
-        eprintln!("Erro ao salvar Cargo.toml: {e}");
-    }
+    data.parse::<DocumentMut>().unwrap_or_default()
 }
 
-fn list_rust(dir: &Path) {
-    let path = cargo_toml(dir);
-    let doc = load_cargo_toml(&path);
-    if let Some(table) = doc.get("dev-dependencies").and_then(|t| t.as_table()) {
-        for (k, v) in table.iter() {
-            println!("- {} = {}", k, v.as_value().map(|v| v.to_string()).unwrap_or_default());
-        }
-    } else {
-        println!("Nenhuma dependência encontrada.");
-    }
+fn save_cargo_toml(path: &Path, doc: &DocumentMut) -> DxResult {
+    fs::write(path, doc.to_string()).context("Erro ao salvar Cargo.toml")
 }
 
-fn add_rust(dir: &Path, name: String, version: Option<String>) {
+fn list_rust(dir: &Path) -> Vec<DeclaredDependency> {
+    let doc = load_cargo_toml(&cargo_toml(dir));
+    let Some(table) = doc.get("dev-dependencies").and_then(|t| t.as_table_like()) else {
+        return Vec::new();
+    };
+    table
+        .iter()
+        .map(|(k, item)| {
+            // `"1.0"`, `{ version = "1.0", ... }`, or the spec as written (`{ path = ".." }`)
+            let version = item
+                .as_str()
+                .or_else(|| item.get("version")?.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| item.to_string().trim().to_string());
+            DeclaredDependency::from((k.to_string(), version))
+        })
+        .collect()
+}
+
+fn add_rust(dir: &Path, name: String, version: Option<String>) -> DxResult {
     let path = cargo_toml(dir);
     let mut doc = load_cargo_toml(&path);
     let tbl = doc
@@ -298,75 +551,85 @@ fn add_rust(dir: &Path, name: String, version: Option<String>) {
         .or_insert(toml_edit::Item::Table(Default::default()))
         .as_table_mut()
         .unwrap();
-    tbl.insert(name.clone(), value(version.unwrap_or("*".into())));
-    save_cargo_toml(&path, &doc);
-    println!("Dependência '{name}' adicionada.");
+    tbl.insert(&name, value(version.unwrap_or("*".into())));
+    save_cargo_toml(&path, &doc)?;
+    outln!("Dependência '{name}' adicionada.");
+    Ok(())
 }
 
 fn fetch_latest_crate(name: &str) -> Option<String> {
-    let url = format!("https://crates.io/api/v1/crates/{}", name);
-    reqwest::blocking::get(url)
-        .ok()?
-        .json::<Value>()
-        .ok()?
-        .get("crate")
-        .and_then(|c| c.get("max_stable_version"))
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-}
-
-fn update_rust(dir: &Path, name: Option<String>) {
+    let url = format!("{}/api/v1/crates/{}", registry_base("https://crates.io", |m| &m.crates), name);
+    cached_latest(&url, || {
+        registry_json(&url)?
+            .get("crate")
+            .and_then(|c| c.get("max_stable_version"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    })
+}
+
+fn update_rust(dir: &Path, name: Option<String>) -> DxResult {
     let path = cargo_toml(dir);
     let mut doc = load_cargo_toml(&path);
     if let Some(table) = doc.get_mut("dev-dependencies").and_then(|t| t.as_table_mut()) {
         if let Some(n) = name {
             if let Some(latest) = fetch_latest_crate(&n) {
                 table.insert(&n, value(latest));
-                println!("Dependência '{n}' atualizada.");
+                outln!("Dependência '{n}' atualizada.");
+            } else {
+                tracing::warn!(dependency = %n, "não foi possível obter a última versão; mantendo a atual");
             }
         } else {
-            for (k, item) in table.iter_mut() {
-                if let Some(latest) = fetch_latest_crate(k) {
-                    *item.value_mut() = value(latest);
+            let names: Vec<String> = table.iter().map(|(k, _)| k.to_string()).collect();
+            let latest = in_parallel(&names, |n| fetch_latest_crate(n));
+            for ((_, item), latest) in table.iter_mut().zip(latest) {
+                if let Some(latest) = latest {
+                    *item = value(latest);
                 }
             }
-            println!("Todas as dependências atualizadas.");
+            outln!("Todas as dependências atualizadas.");
         }
     }
-    save_cargo_toml(&path, &doc);
+    save_cargo_toml(&path, &doc)
 }
 
-fn delete_rust(dir: &Path, name: String) {
+fn delete_rust(dir: &Path, name: String) -> DxResult {
     let path = cargo_toml(dir);
     let mut doc = load_cargo_toml(&path);
-    if let Some(table) = doc.get_mut("dev-dependencies").and_then(|t| t.as_table_mut()) {
-        table.remove(&name);
-        println!("Dependência '{name}' removida.");
+    if let Some(table) = doc.get_mut("dev-dependencies").and_then(|t| t.as_table_mut())
+        && table.remove(&name).is_some()
+    {
+        save_cargo_toml(&path, &doc)?;
+        outln!("Dependência '{name}' removida.");
+        return Ok(());
     }
-    save_cargo_toml(&path, &doc);
+    Err(not_found(&name))
 }
 
 fn get_rust_dependencies(dir: &Path) -> Vec<DependencyInfo> {
     let path = cargo_toml(dir);
     let doc = load_cargo_toml(&path);
-    let mut deps = Vec::new();
-    if let Some(table) = doc.get("dev-dependencies").and_then(|t| t.as_table()) {
-        for (k, v) in table.iter() {
-            let ver = v.as_value().map(|v| v.to_string()).unwrap_or_default();
-            let latest = fetch_latest_crate(k);
-            deps.push(DependencyInfo {
-                name: k.to_string(),
-                current_version: ver.clone(),
-                latest_version: latest.clone(),
-                update_command: format!("cargo update -p {} --precise {}", k, latest.clone().unwrap_or_default()),
-                url: format!("https://crates.io/crates/{}", k),
-            });
+    let declared: Vec<(&str, String)> = doc
+        .get("dev-dependencies")
+        .and_then(|t| t.as_table())
+        .into_iter()
+        .flat_map(|table| table.iter())
+        .map(|(k, v)| (k, v.as_value().map(|v| v.to_string()).unwrap_or_default()))
+        .collect();
+    in_parallel(&declared, |(k, ver)| {
+        let latest = fetch_latest_crate(k);
+        DependencyInfo {
+            name: k.to_string(),
+            current_version: ver.clone(),
+            latest_version: latest.clone(),
+            update_command: format!("cargo update -p {} --precise {}", k, latest.unwrap_or_default()),
+            url: format!("https://crates.io/crates/{}", k),
         }
-    }
-    deps
+    })
 }
 
-// Python helpers
+// Python helpers: requirements files, or pyproject.toml (Poetry or PEP 621)
+// edited with toml_edit so its formatting survives
 fn requirements_path(dir: &Path) -> PathBuf {
     if dir.join("requirements-dev.txt").exists() {
         dir.join("requirements-dev.txt")
@@ -391,7 +654,7 @@ fn parse_requirements(content: &str) -> BTreeMap<String, String> {
     map
 }
 
-fn write_requirements(path: &Path, map: &BTreeMap<String, String>) {
+fn write_requirements(path: &Path, map: &BTreeMap<String, String>) -> DxResult {
     let mut out = String::new();
     for (k, v) in map {
         if v == "*" {
@@ -400,102 +663,511 @@ fn write_requirements(path: &Path, map: &BTreeMap<String, String>) {
             out.push_str(&format!("{}=={}\n", k, v));
         }
     }
-    if let Err(e) = fs::write(path, out) {
-        eprintln!("Erro ao salvar requirements: {e}");
+    fs::write(path, out).context("Erro ao salvar requirements")
+}
+
+/// Groups of development dependencies, the first one receiving `add`.
+const PYTHON_DEV_GROUPS: &[&str] = &["dev", "test", "tests", "testing"];
+
+/// Where a Python project declares its dev dependencies.
+enum PythonManifest {
+    /// `[tool.poetry.group.<dev group>.dependencies]`, or the legacy
+    /// `[tool.poetry.dev-dependencies]`
+    Poetry(DocumentMut),
+    /// PEP 621 `[project.optional-dependencies]` dev groups
+    Pep621(DocumentMut),
+    Requirements,
+}
+
+fn pyproject_path(dir: &Path) -> PathBuf {
+    dir.join("pyproject.toml")
+}
+
+fn python_manifest(dir: &Path) -> PythonManifest {
+    let Some(doc) = fs::read_to_string(pyproject_path(dir)).ok().and_then(|data| data.parse::<DocumentMut>().ok()) else {
+        return PythonManifest::Requirements;
+    };
+    let has_requirements = dir.join("requirements-dev.txt").exists() || dir.join("requirements.txt").exists();
+    if doc.get("tool").and_then(|t| t.get("poetry")).is_some() {
+        PythonManifest::Poetry(doc)
+    } else if doc.get("project").is_some() && (!has_requirements || !pep621_groups(&doc).is_empty()) {
+        PythonManifest::Pep621(doc)
+    } else {
+        PythonManifest::Requirements
     }
 }
 
-fn list_python(dir: &Path) {
-    let path = requirements_path(dir);
-    if let Ok(data) = fs::read_to_string(&path) {
-        let map = parse_requirements(&data);
-        if map.is_empty() {
-            println!("Nenhuma dependência encontrada.");
-        } else {
-            for (k, v) in map {
-                println!("- {} = {}", k, v);
+/// `path` of the pyproject.toml holds something other than `kind`.
+fn malformed(path: &str, kind: &str) -> DxError {
+    DxError::InvalidInput(format!("[{path}] não é {kind} no pyproject.toml."))
+}
+
+fn save_pyproject(dir: &Path, doc: &DocumentMut) -> DxResult {
+    fs::write(pyproject_path(dir), doc.to_string()).context("Erro ao salvar pyproject.toml")
+}
+
+/// Normalized package name (PEP 503), so `Pytest_Cov` finds `pytest-cov`.
+fn pep503(name: &str) -> String {
+    let mut normalized = String::new();
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
             }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
         }
-    } else {
-        println!("Nenhuma dependência encontrada.");
     }
+    normalized
+}
+
+/// A PEP 508 requirement (`pytest[cov]>=7.4 ; python_version > "3.8"`): the
+/// name and the byte range of its version specifier.
+fn pep508(requirement: &str) -> (&str, Range<usize>) {
+    let name_end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    let mut start = name_end;
+    if requirement[start..].trim_start().starts_with('[') {
+        start = requirement[start..].find(']').map_or(requirement.len(), |i| start + i + 1);
+    }
+    let end = requirement[start..].find(';').map_or(requirement.len(), |i| start + i);
+    let spec = &requirement[start..end];
+    let spec_start = start + (spec.len() - spec.trim_start().len());
+    (&requirement[..name_end], spec_start..spec_start.max(start + spec.trim_end().len()))
 }
 
-fn add_python(dir: &Path, name: String, version: Option<String>) {
-    let path = requirements_path(dir);
-    let mut map = if let Ok(data) = fs::read_to_string(&path) {
-        parse_requirements(&data)
+/// Version specifier of a PEP 508 requirement, `*` when it has none.
+fn pep508_version(requirement: &str) -> String {
+    let (_, spec) = pep508(requirement);
+    let spec = requirement[spec].trim_start_matches('(').trim_end_matches(')').trim();
+    if spec.is_empty() { "*".to_string() } else { spec.to_string() }
+}
+
+/// The PEP 621 dev groups present, in [`PYTHON_DEV_GROUPS`] order.
+fn pep621_groups(doc: &DocumentMut) -> Vec<&'static str> {
+    let optional = doc.get("project").and_then(|p| p.get("optional-dependencies"));
+    PYTHON_DEV_GROUPS
+        .iter()
+        .copied()
+        .filter(|g| optional.and_then(|o| o.get(g)).is_some_and(|a| a.is_array()))
+        .collect()
+}
+
+fn pep621_array<'a>(doc: &'a mut DocumentMut, group: &str) -> Option<&'a mut toml_edit::Array> {
+    doc.get_mut("project")?.get_mut("optional-dependencies")?.get_mut(group)?.as_array_mut()
+}
+
+/// The array of the PEP 621 dev `group`, which must be there.
+fn pep621_group_mut<'a>(doc: &'a mut DocumentMut, group: &str) -> DxResult<&'a mut toml_edit::Array> {
+    pep621_array(doc, group).ok_or_else(|| malformed(&format!("project.optional-dependencies.{group}"), "uma lista"))
+}
+
+/// Group and index of `name` among the PEP 621 dev groups.
+fn pep621_find(doc: &DocumentMut, name: &str) -> Option<(&'static str, usize)> {
+    let optional = doc.get("project")?.get("optional-dependencies")?;
+    pep621_groups(doc).into_iter().find_map(|group| {
+        let array = optional.get(group)?.as_array()?;
+        let index = array.iter().position(|v| v.as_str().is_some_and(|r| pep503(pep508(r).0) == pep503(name)))?;
+        Some((group, index))
+    })
+}
+
+/// Replace the version specifier of the requirement string `item`.
+fn pep508_set(item: &mut toml_edit::Value, spec: &str) {
+    let Some(requirement) = item.as_str() else {
+        return;
+    };
+    let (_, range) = pep508(requirement);
+    let mut requirement = requirement.to_string();
+    requirement.replace_range(range, spec);
+    let decor = item.decor().clone();
+    *item = toml_edit::Value::from(requirement);
+    *item.decor_mut() = decor;
+}
+
+/// Append `requirement` to `array`, laid out like its last element.
+fn push_like_last(array: &mut toml_edit::Array, requirement: String) {
+    let mut item = toml_edit::Value::from(requirement);
+    if let Some(last) = array.iter().last() {
+        *item.decor_mut() = last.decor().clone();
+    }
+    array.push_formatted(item);
+}
+
+/// The Poetry dev dependency tables present: `Some(group)` for
+/// `[tool.poetry.group.<group>.dependencies]`, `None` for the legacy
+/// `[tool.poetry.dev-dependencies]`.
+fn poetry_groups(doc: &DocumentMut) -> Vec<Option<&'static str>> {
+    let Some(poetry) = doc.get("tool").and_then(|t| t.get("poetry")) else {
+        return Vec::new();
+    };
+    let mut groups: Vec<Option<&'static str>> = PYTHON_DEV_GROUPS
+        .iter()
+        .copied()
+        .filter(|g| {
+            poetry
+                .get("group")
+                .and_then(|groups| groups.get(g))
+                .and_then(|group| group.get("dependencies"))
+                .is_some_and(|d| d.is_table_like())
+        })
+        .map(Some)
+        .collect();
+    if poetry.get("dev-dependencies").is_some_and(|d| d.is_table_like()) {
+        groups.push(None);
+    }
+    groups
+}
+
+fn poetry_table<'a>(doc: &'a DocumentMut, group: Option<&str>) -> Option<&'a dyn toml_edit::TableLike> {
+    let poetry = doc.get("tool")?.get("poetry")?;
+    match group {
+        Some(group) => poetry.get("group")?.get(group)?.get("dependencies")?.as_table_like(),
+        None => poetry.get("dev-dependencies")?.as_table_like(),
+    }
+}
+
+fn poetry_table_mut<'a>(doc: &'a mut DocumentMut, group: Option<&str>) -> DxResult<&'a mut dyn toml_edit::TableLike> {
+    let poetry = doc.get_mut("tool").and_then(|t| t.get_mut("poetry"));
+    let (table, path) = match group {
+        Some(group) => (
+            poetry.and_then(|p| p.get_mut("group")?.get_mut(group)?.get_mut("dependencies")),
+            format!("tool.poetry.group.{group}.dependencies"),
+        ),
+        None => (poetry.and_then(|p| p.get_mut("dev-dependencies")), "tool.poetry.dev-dependencies".to_string()),
+    };
+    table.and_then(|t| t.as_table_like_mut()).ok_or_else(|| malformed(&path, "uma tabela"))
+}
+
+/// The dependency `key` of a Poetry dev dependency table.
+fn poetry_item_mut<'a>(doc: &'a mut DocumentMut, group: Option<&str>, key: &str) -> DxResult<&'a mut toml_edit::Item> {
+    poetry_table_mut(doc, group)?.get_mut(key).ok_or_else(|| not_found(key))
+}
+
+/// Group and key of `name` among the Poetry dev dependency tables.
+fn poetry_find(doc: &DocumentMut, name: &str) -> Option<(Option<&'static str>, String)> {
+    poetry_groups(doc).into_iter().find_map(|group| {
+        let key = poetry_table(doc, group)?.iter().map(|(k, _)| k).find(|k| pep503(k) == pep503(name))?;
+        Some((group, key.to_string()))
+    })
+}
+
+/// Version of a Poetry dependency: `"^7.4"`, `{ version = "^7.4", ... }`, or
+/// the spec as written (`{ git = "..." }`).
+fn poetry_version(item: &toml_edit::Item) -> String {
+    item.as_str()
+        .or_else(|| item.get("version")?.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| item.to_string().trim().to_string())
+}
+
+/// Set the version of a Poetry dependency; false for git/path ones.
+fn poetry_set(item: &mut toml_edit::Item, version: &str) -> bool {
+    if item.is_str() {
+        let decor = item.as_value().map(|v| v.decor().clone()).unwrap_or_default();
+        *item = value(version);
+        if let Some(v) = item.as_value_mut() {
+            *v.decor_mut() = decor;
+        }
+        true
+    } else if let Some(table) = item.as_table_like_mut()
+        && table.get("version").is_some_and(|v| v.is_str())
+    {
+        let decor = table.get("version").and_then(|v| v.as_value()).map(|v| v.decor().clone()).unwrap_or_default();
+        table.insert("version", value(version));
+        if let Some(v) = table.get_mut("version").and_then(|v| v.as_value_mut()) {
+            *v.decor_mut() = decor;
+        }
+        true
     } else {
-        BTreeMap::new()
+        false
+    }
+}
+
+/// The `[tool.poetry.group.dev.dependencies]` table, created if missing
+/// (the legacy table when the project still uses it).
+fn poetry_dev_table(doc: &mut DocumentMut) -> DxResult<&mut dyn toml_edit::TableLike> {
+    if poetry_groups(doc) == [None] {
+        return poetry_table_mut(doc, None);
+    }
+    let poetry = doc
+        .get_mut("tool")
+        .and_then(|t| t.get_mut("poetry"))
+        .and_then(|p| p.as_table_like_mut())
+        .ok_or_else(|| malformed("tool.poetry", "uma tabela"))?;
+    let implicit = || {
+        let mut table = toml_edit::Table::new();
+        table.set_implicit(true);
+        toml_edit::Item::Table(table)
     };
-    map.insert(name.clone(), version.unwrap_or("*".into()));
-    write_requirements(&path, &map);
-    println!("Dependência '{name}' adicionada.");
+    let group = poetry
+        .entry("group")
+        .or_insert_with(implicit)
+        .as_table_like_mut()
+        .ok_or_else(|| malformed("tool.poetry.group", "uma tabela"))?;
+    let dev = group
+        .entry("dev")
+        .or_insert_with(implicit)
+        .as_table_like_mut()
+        .ok_or_else(|| malformed("tool.poetry.group.dev", "uma tabela"))?;
+    dev.entry("dependencies")
+        .or_insert_with(|| toml_edit::Item::Table(toml_edit::Table::new()))
+        .as_table_like_mut()
+        .ok_or_else(|| malformed("tool.poetry.group.dev.dependencies", "uma tabela"))
+}
+
+fn list_python(dir: &Path) -> Vec<DeclaredDependency> {
+    match python_manifest(dir) {
+        PythonManifest::Poetry(doc) => poetry_groups(&doc)
+            .into_iter()
+            .filter_map(|group| poetry_table(&doc, group))
+            .flat_map(|table| table.iter().map(|(k, item)| DeclaredDependency::from((k.to_string(), poetry_version(item)))))
+            .collect(),
+        PythonManifest::Pep621(doc) => {
+            let optional = doc.get("project").and_then(|p| p.get("optional-dependencies"));
+            pep621_groups(&doc)
+                .into_iter()
+                .filter_map(|group| optional?.get(group)?.as_array())
+                .flat_map(|array| array.iter().filter_map(|v| v.as_str()))
+                .map(|r| DeclaredDependency::from((pep508(r).0.to_string(), pep508_version(r))))
+                .collect()
+        }
+        PythonManifest::Requirements => fs::read_to_string(requirements_path(dir))
+            .map(|data| parse_requirements(&data).into_iter().map(DeclaredDependency::from).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// Latest version of `name`, for `add` without a version.
+fn require_latest_pypi(name: &str) -> DxResult<String> {
+    fetch_latest_pypi(name)
+        .ok_or_else(|| DxError::Network(format!("Não foi possível obter a última versão de '{name}' no PyPI.")))
+}
+
+fn add_python(dir: &Path, name: String, version: Option<String>) -> DxResult {
+    let version = version.filter(|v| v != "latest");
+    match python_manifest(dir) {
+        PythonManifest::Poetry(mut doc) => {
+            // `poetry add` pins with a caret
+            let version = match version {
+                Some(v) => v,
+                None => format!("^{}", require_latest_pypi(&name)?),
+            };
+            match poetry_find(&doc, &name) {
+                Some((group, key)) => {
+                    let item = poetry_item_mut(&mut doc, group, &key)?;
+                    if !poetry_set(item, &version) {
+                        return Err(DxError::InvalidInput(format!(
+                            "'{name}' vem de um repositório git ou caminho local; altere-o no pyproject.toml."
+                        )));
+                    }
+                }
+                None => {
+                    poetry_dev_table(&mut doc)?.insert(&name, value(&version));
+                }
+            }
+            save_pyproject(dir, &doc)?;
+            outln!("Dependência '{name}' adicionada ({version}).");
+        }
+        PythonManifest::Pep621(mut doc) => {
+            // A bare version pins it, as in requirements files
+            let spec = match version {
+                Some(v) if v.starts_with(['<', '>', '=', '!', '~']) => v,
+                Some(v) => format!("=={v}"),
+                None => format!(">={}", require_latest_pypi(&name)?),
+            };
+            match pep621_find(&doc, &name) {
+                Some((group, index)) => {
+                    let array = pep621_group_mut(&mut doc, group)?;
+                    pep508_set(array.get_mut(index).ok_or_else(|| not_found(&name))?, &spec);
+                }
+                None => {
+                    let group = pep621_groups(&doc).first().copied().unwrap_or(PYTHON_DEV_GROUPS[0]);
+                    if pep621_array(&mut doc, group).is_none() {
+                        let project = doc
+                            .get_mut("project")
+                            .and_then(|p| p.as_table_like_mut())
+                            .ok_or_else(|| malformed("project", "uma tabela"))?;
+                        let optional = project
+                            .entry("optional-dependencies")
+                            .or_insert_with(|| toml_edit::Item::Table(toml_edit::Table::new()));
+                        optional
+                            .as_table_like_mut()
+                            .ok_or_else(|| malformed("project.optional-dependencies", "uma tabela"))?
+                            .insert(group, value(toml_edit::Array::new()));
+                    }
+                    let array = pep621_group_mut(&mut doc, group)?;
+                    push_like_last(array, format!("{name}{spec}"));
+                }
+            }
+            save_pyproject(dir, &doc)?;
+            outln!("Dependência '{name}' adicionada ({spec}).");
+        }
+        PythonManifest::Requirements => {
+            let path = requirements_path(dir);
+            let mut map = if let Ok(data) = fs::read_to_string(&path) {
+                parse_requirements(&data)
+            } else {
+                BTreeMap::new()
+            };
+            map.insert(name.clone(), version.unwrap_or("*".into()));
+            write_requirements(&path, &map)?;
+            outln!("Dependência '{name}' adicionada.");
+        }
+    }
+    Ok(())
 }
 
 fn fetch_latest_pypi(name: &str) -> Option<String> {
-    let url = format!("https://pypi.org/pypi/{}/json", name);
-    reqwest::blocking::get(url)
-        .ok()?
-        .json::<Value>()
-        .ok()?
-        .get("info")
-        .and_then(|i| i.get("version"))
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-}
-
-fn update_python(dir: &Path, name: Option<String>) {
+    let url = format!("{}/pypi/{}/json", registry_base("https://pypi.org", |m| &m.pypi), name);
+    cached_latest(&url, || {
+        registry_json(&url)?
+            .get("info")
+            .and_then(|i| i.get("version"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    })
+}
+
+/// `spec` moved to `latest`, or `None` when it doesn't name a single version
+/// (`*`, ranges like `>=7,<8`, URLs).
+fn bump_python_spec(spec: &str, latest: &str) -> Option<String> {
+    let single = spec != "*" && !spec.contains([',', '@', '*']) && spec.contains(|c: char| c.is_ascii_digit());
+    single.then(|| bump_requirement(spec, latest))
+}
+
+fn update_pyproject(dir: &Path, mut doc: DocumentMut, poetry: bool, name: Option<String>) -> DxResult {
+    let declared: Vec<String> = list_python(dir).into_iter().map(|d| d.name).collect();
+    let names = match &name {
+        Some(n) => vec![declared.into_iter().find(|d| pep503(d) == pep503(n)).ok_or_else(|| not_found(n))?],
+        None => declared,
+    };
+    let latest = in_parallel(&names, |n| fetch_latest_pypi(n));
+    let mut updated = false;
+    for (n, latest) in names.iter().zip(latest) {
+        let Some(latest) = latest else {
+            tracing::warn!(dependency = %n, "não foi possível obter a última versão; mantendo a atual");
+            continue;
+        };
+        let changed = if poetry {
+            let (group, key) = poetry_find(&doc, n).ok_or_else(|| not_found(n))?;
+            let item = poetry_item_mut(&mut doc, group, &key)?;
+            match bump_python_spec(&poetry_version(item), &latest) {
+                Some(spec) => poetry_set(item, &spec),
+                None => false,
+            }
+        } else {
+            let (group, index) = pep621_find(&doc, n).ok_or_else(|| not_found(n))?;
+            let item = pep621_group_mut(&mut doc, group)?.get_mut(index).ok_or_else(|| not_found(n))?;
+            match item.as_str().and_then(|r| bump_python_spec(&pep508_version(r), &latest)) {
+                Some(spec) => {
+                    pep508_set(item, &spec);
+                    true
+                }
+                None => false,
+            }
+        };
+        if !changed {
+            tracing::warn!(dependency = %n, "sem versão única (faixa, git ou caminho local); mantendo");
+        }
+        updated |= changed;
+    }
+    match &name {
+        Some(n) if updated => outln!("Dependência '{n}' atualizada."),
+        Some(_) => {}
+        None => outln!("Todas as dependências atualizadas."),
+    }
+    save_pyproject(dir, &doc)
+}
+
+fn update_python(dir: &Path, name: Option<String>) -> DxResult {
+    match python_manifest(dir) {
+        PythonManifest::Poetry(doc) => return update_pyproject(dir, doc, true, name),
+        PythonManifest::Pep621(doc) => return update_pyproject(dir, doc, false, name),
+        PythonManifest::Requirements => {}
+    }
     let path = requirements_path(dir);
     if let Ok(data) = fs::read_to_string(&path) {
         let mut map = parse_requirements(&data);
         if let Some(n) = name {
             if let Some(latest) = fetch_latest_pypi(&n) {
                 map.insert(n.clone(), latest);
-                println!("Dependência '{n}' atualizada.");
+                outln!("Dependência '{n}' atualizada.");
+            } else {
+                tracing::warn!(dependency = %n, "não foi possível obter a última versão; mantendo a atual");
             }
         } else {
-            for (k, v) in map.iter_mut() {
-                if let Some(latest) = fetch_latest_pypi(k) {
+            let names: Vec<String> = map.keys().cloned().collect();
+            let latest = in_parallel(&names, |n| fetch_latest_pypi(n));
+            for (v, latest) in map.values_mut().zip(latest) {
+                if let Some(latest) = latest {
                     *v = latest;
                 }
             }
-            println!("Todas as dependências atualizadas.");
+            outln!("Todas as dependências atualizadas.");
         }
-        write_requirements(&path, &map);
-    }
-}
-
-fn delete_python(dir: &Path, name: String) {
-    let path = requirements_path(dir);
-    if let Ok(data) = fs::read_to_string(&path) {
-        let mut map = parse_requirements(&data);
-        map.remove(&name);
-        write_requirements(&path, &map);
-        println!("Dependência '{name}' removida.");
+        write_requirements(&path, &map)?;
     }
+    Ok(())
 }
 
-fn get_python_dependencies(dir: &Path) -> Vec<DependencyInfo> {
-    let path = requirements_path(dir);
-    let mut deps = Vec::new();
-    if let Ok(data) = fs::read_to_string(&path) {
-        let map = parse_requirements(&data);
-        for (k, v) in map {
-            let latest = fetch_latest_pypi(&k);
-            deps.push(DependencyInfo {
-                name: k.clone(),
-                current_version: v.clone(),
-                latest_version: latest.clone(),
-                update_command: format!("pip install -U {}", k),
-                url: format!("https://pypi.org/project/{}/", k),
-            });
+fn delete_python(dir: &Path, name: String) -> DxResult {
+    let removed = match python_manifest(dir) {
+        PythonManifest::Poetry(mut doc) => match poetry_find(&doc, &name) {
+            Some((group, key)) => {
+                poetry_table_mut(&mut doc, group)?.remove(&key);
+                save_pyproject(dir, &doc)?;
+                true
+            }
+            None => false,
+        },
+        PythonManifest::Pep621(mut doc) => match pep621_find(&doc, &name) {
+            Some((group, index)) => {
+                let array = pep621_group_mut(&mut doc, group)?;
+                // The next element takes the layout of the removed one
+                let decor = array.get(index).map(|v| v.decor().clone());
+                array.remove(index);
+                if index == 0
+                    && let (Some(decor), Some(next)) = (decor, array.get_mut(0))
+                {
+                    *next.decor_mut() = decor;
+                }
+                save_pyproject(dir, &doc)?;
+                true
+            }
+            None => false,
+        },
+        PythonManifest::Requirements => {
+            let path = requirements_path(dir);
+            let mut map = fs::read_to_string(&path).map(|data| parse_requirements(&data)).unwrap_or_default();
+            let found = map.remove(&name).is_some();
+            if found {
+                write_requirements(&path, &map)?;
+            }
+            found
         }
+    };
+    if !removed {
+        return Err(not_found(&name));
     }
-    deps
+    outln!("Dependência '{name}' removida.");
+    Ok(())
 }
 
-// Go helpers (listing only)
+fn get_python_dependencies(dir: &Path) -> Vec<DependencyInfo> {
+    let poetry = matches!(python_manifest(dir), PythonManifest::Poetry(_));
+    let declared: Vec<(String, String)> = list_python(dir).into_iter().map(|d| (d.name, d.version)).collect();
+    in_parallel(&declared, |(k, v)| DependencyInfo {
+        name: k.clone(),
+        current_version: v.clone(),
+        latest_version: fetch_latest_pypi(k),
+        update_command: if poetry { format!("poetry update {}", k) } else { format!("pip install -U {}", k) },
+        url: format!("https://pypi.org/project/{}/", k),
+    })
+}
+
+// Go helpers: go.mod is edited in place; `--tidy` runs `go mod tidy` afterwards
 fn go_mod_path(dir: &Path) -> PathBuf {
     dir.join("go.mod")
 }
@@ -530,150 +1202,531 @@ fn parse_go_mod(data: &str) -> BTreeMap<String, String> {
     map
 }
 
-fn list_go(dir: &Path) {
-    let path = go_mod_path(dir);
-    if let Ok(data) = fs::read_to_string(&path) {
-        let map = parse_go_mod(&data);
-        if map.is_empty() {
-            println!("Nenhuma dependência encontrada.");
-        } else {
-            for (k, v) in map {
-                println!("- {} = {}", k, v);
-            }
-        }
-    } else {
-        println!("Nenhuma dependência encontrada.");
-    }
+fn list_go(dir: &Path) -> Vec<DeclaredDependency> {
+    fs::read_to_string(go_mod_path(dir))
+        .map(|data| parse_go_mod(&data).into_iter().map(DeclaredDependency::from).collect())
+        .unwrap_or_default()
 }
 
 fn fetch_latest_go(name: &str) -> Option<String> {
-    let url = format!("https://proxy.golang.org/{}/@latest", name);
-    reqwest::blocking::get(url)
-        .ok()?
-        .json::<Value>()
-        .ok()?
-        .get("Version")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
+    let url = format!("{}/{}/@latest", registry_base("https://proxy.golang.org", |m| &m.go), name);
+    cached_latest(&url, || {
+        registry_json(&url)?
+            .get("Version")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    })
+}
+
+/// A `require` of go.mod, single-line or inside a `require ( ... )` block.
+struct GoRequire {
+    /// Index of its line
+    line: usize,
+    path: String,
+    /// Comment after `//`, `indirect` for dependencies of dependencies
+    comment: Option<String>,
+    /// Lines of the enclosing block's `require (` and `)`
+    block: Option<(usize, usize)>,
+}
+
+impl GoRequire {
+    fn indirect(&self) -> bool {
+        self.comment.as_deref().is_some_and(|c| c.split(';').next().unwrap_or_default().trim() == "indirect")
+    }
+
+    /// Its line with `version`, as gofmt writes it.
+    fn render(&self, version: &str, comment: Option<&str>) -> String {
+        let entry = match comment {
+            Some(comment) => format!("{} {} // {}", self.path, version, comment),
+            None => format!("{} {}", self.path, version),
+        };
+        if self.block.is_some() { format!("\t{entry}") } else { format!("require {entry}") }
+    }
 }
 
-fn add_go(_dir: &Path, _name: String, _version: Option<String>) {
-    println!("Operação não suportada para Go.");
+fn go_requires(lines: &[String]) -> Vec<GoRequire> {
+    let mut requires = Vec::new();
+    let mut open: Option<usize> = None;
+    let mut in_block: Vec<GoRequire> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if let Some(start) = open {
+            if trimmed.starts_with(')') {
+                for mut require in in_block.drain(..) {
+                    require.block = Some((start, i));
+                    requires.push(require);
+                }
+                open = None;
+                continue;
+            }
+        } else if trimmed.strip_prefix("require").is_some_and(|r| r.trim_start().starts_with('(')) {
+            open = Some(i);
+            continue;
+        }
+        let entry = match open {
+            Some(_) => Some(trimmed),
+            None => trimmed.strip_prefix("require ").map(str::trim),
+        };
+        let Some(entry) = entry.filter(|e| !e.is_empty() && !e.starts_with("//")) else {
+            continue;
+        };
+        let (entry, comment) = match entry.split_once("//") {
+            Some((entry, comment)) => (entry, Some(comment.trim().to_string())),
+            None => (entry, None),
+        };
+        if let [path, _version] = entry.split_whitespace().collect::<Vec<_>>()[..] {
+            let require = GoRequire {
+                line: i,
+                path: path.trim_matches('"').to_string(),
+                comment,
+                block: None,
+            };
+            if open.is_some() { in_block.push(require) } else { requires.push(require) }
+        }
+    }
+    requires.sort_by_key(|r| r.line);
+    requires
 }
 
-fn update_go(_dir: &Path, _name: Option<String>) {
-    println!("Operação não suportada para Go.");
+fn load_go_mod(path: &Path) -> DxResult<Vec<String>> {
+    let data = fs::read_to_string(path).context("Erro ao ler go.mod")?;
+    Ok(data.lines().map(str::to_string).collect())
 }
 
-fn delete_go(_dir: &Path, _name: String) {
-    println!("Operação não suportada para Go.");
+fn save_go_mod(path: &Path, lines: &[String]) -> DxResult {
+    fs::write(path, lines.join("\n") + "\n").context("Erro ao salvar go.mod")
 }
 
-fn get_go_dependencies(dir: &Path) -> Vec<DependencyInfo> {
-    let path = go_mod_path(dir);
-    let mut deps = Vec::new();
-    if let Ok(data) = fs::read_to_string(&path) {
-        let map = parse_go_mod(&data);
-        for (k, v) in map {
-            let latest = fetch_latest_go(&k);
-            deps.push(DependencyInfo {
-                name: k.clone(),
-                current_version: v.clone(),
-                latest_version: latest.clone(),
-                update_command: format!("go get {}@latest", k),
-                url: format!("https://pkg.go.dev/{}", k),
-            });
-        }
+/// Module versions are semver tags: `1.2.3` means `v1.2.3`.
+fn go_version(version: &str) -> String {
+    if version.starts_with('v') || !version.starts_with(|c: char| c.is_ascii_digit()) {
+        version.to_string()
+    } else {
+        format!("v{version}")
     }
-    deps
 }
 
-// Maven helpers
-fn pom_xml_path(dir: &Path) -> PathBuf {
-    dir.join("pom.xml")
+fn go_latest(name: &str) -> DxResult<String> {
+    fetch_latest_go(name).ok_or_else(|| {
+        DxError::Network(format!("Não foi possível obter a última versão de '{name}' no proxy de módulos Go."))
+    })
 }
 
-fn parse_maven_deps(data: &str) -> Vec<(String, String, String)> {
-    let mut deps = Vec::new();
-    let mut rest = data;
-    while let Some(start) = rest.find("<dependency>") {
-        rest = &rest[start + "<dependency>".len()..];
-        if let Some(end) = rest.find("</dependency>") {
-            let block = &rest[..end];
-            rest = &rest[end + "</dependency>".len()..];
-            if block.contains("<scope>test</scope>") {
-                let group = extract_between(block, "<groupId>", "</groupId>").unwrap_or_default();
-                let artifact = extract_between(block, "<artifactId>", "</artifactId>").unwrap_or_default();
-                let version = extract_between(block, "<version>", "</version>").unwrap_or_default();
-                deps.push((group.to_string(), artifact.to_string(), version.to_string()));
-            }
-        } else {
-            break;
-        }
+fn add_go(dir: &Path, name: String, version: Option<String>) -> DxResult {
+    let path = go_mod_path(dir);
+    let mut lines = load_go_mod(&path)?;
+    let version = match version.filter(|v| v != "latest") {
+        Some(v) => go_version(&v),
+        None => go_latest(&name)?,
+    };
+    let requires = go_requires(&lines);
+    if let Some(require) = requires.iter().find(|r| r.path == name) {
+        // Required by a dependency so far: now a direct one
+        let comment = require.comment.as_deref().filter(|_| !require.indirect());
+        lines[require.line] = require.render(&version, comment);
+    } else if let Some((open, close)) = requires
+        .iter()
+        .filter_map(|r| r.block)
+        .find(|block| requires.iter().all(|r| r.block != Some(*block) || !r.indirect()))
+    {
+        // Into the block of direct dependencies, in order
+        let at = (open + 1..close)
+            .find(|i| requires.iter().any(|r| r.line == *i && r.path > name))
+            .unwrap_or(close);
+        lines.insert(at, format!("\t{name} {version}"));
+    } else {
+        // After the `go`/`toolchain` directives (or the module line)
+        let at = lines
+            .iter()
+            .rposition(|l| ["module ", "go ", "toolchain "].iter().any(|d| l.starts_with(d)))
+            .map_or(lines.len(), |i| i + 1);
+        lines.splice(at..at, ["".to_string(), format!("require {name} {version}")]);
     }
-    deps
-}
-
-fn extract_between<'a>(hay: &'a str, start: &str, end: &str) -> Option<&'a str> {
-    let s = hay.find(start)? + start.len();
-    let e = hay[s..].find(end)? + s;
-    Some(&hay[s..e])
+    save_go_mod(&path, &lines)?;
+    outln!("Dependência '{name}' adicionada ({version}).");
+    Ok(())
 }
 
-fn list_maven(dir: &Path) {
-    let path = pom_xml_path(dir);
-    if let Ok(data) = fs::read_to_string(&path) {
-        let deps = parse_maven_deps(&data);
-        if deps.is_empty() {
-            println!("Nenhuma dependência encontrada.");
+fn update_go(dir: &Path, name: Option<String>) -> DxResult {
+    let path = go_mod_path(dir);
+    let mut lines = load_go_mod(&path)?;
+    let requires = go_requires(&lines);
+    if let Some(n) = name {
+        let require = requires.iter().find(|r| r.path == n).ok_or_else(|| not_found(&n))?;
+        if let Some(latest) = fetch_latest_go(&n) {
+            lines[require.line] = require.render(&latest, require.comment.as_deref());
+            outln!("Dependência '{n}' atualizada.");
         } else {
-            for (g, a, v) in deps {
-                println!("- {}:{} = {}", g, a, v);
-            }
+            tracing::warn!(dependency = %n, "não foi possível obter a última versão; mantendo a atual");
         }
     } else {
-        println!("Nenhuma dependência encontrada.");
+        // Indirect requirements follow from the direct ones (`go mod tidy`)
+        let direct: Vec<&GoRequire> = requires.iter().filter(|r| !r.indirect()).collect();
+        let latest = in_parallel(&direct, |r| fetch_latest_go(&r.path));
+        for (require, latest) in direct.iter().zip(latest) {
+            if let Some(latest) = latest {
+                lines[require.line] = require.render(&latest, require.comment.as_deref());
+            }
+        }
+        outln!("Todas as dependências atualizadas.");
     }
+    save_go_mod(&path, &lines)
 }
 
-fn fetch_latest_maven(group: &str, artifact: &str) -> Option<String> {
-    let path = group.replace('.', "/");
-    let url = format!("https://repo1.maven.org/maven2/{}/{}/maven-metadata.xml", path, artifact);
-    let text = reqwest::blocking::get(url).ok()?.text().ok()?;
-    extract_between(&text, "<latest>", "</latest>")
-        .or_else(|| extract_between(&text, "<release>", "</release>"))
-        .map(|s| s.to_string())
-}
-
-fn add_maven(_dir: &Path, _name: String, _version: Option<String>) {
-    println!("Operação não suportada para Maven.");
+fn delete_go(dir: &Path, name: String) -> DxResult {
+    let path = go_mod_path(dir);
+    let mut lines = load_go_mod(&path)?;
+    let requires = go_requires(&lines);
+    let require = requires.iter().find(|r| r.path == name).ok_or_else(|| not_found(&name))?;
+    match require.block {
+        // Last entry of its block: the block goes too
+        Some((open, close)) if requires.iter().filter(|r| r.block == require.block).count() == 1 => {
+            let blank = open > 0 && lines[open - 1].trim().is_empty();
+            lines.drain(open - usize::from(blank)..=close);
+        }
+        _ => {
+            lines.remove(require.line);
+        }
+    }
+    save_go_mod(&path, &lines)?;
+    outln!("Dependência '{name}' removida.");
+    Ok(())
 }
 
-fn update_maven(_dir: &Path, _name: Option<String>) {
-    println!("Operação não suportada para Maven.");
+/// `go mod tidy` after `add`/`update`/`delete --tidy`, so go.sum and the
+/// indirect requirements follow the edited go.mod.
+pub fn tidy(dir: Option<PathBuf>) -> DxResult {
+    let project_dir = project_dir(dir);
+    if Stack::detect(&project_dir) != Stack::Go {
+        return Err(DxError::InvalidInput("--tidy só se aplica a projetos Go (go.mod).".to_string()));
+    }
+    tracing::debug!("executando: go mod tidy");
+    let status = std::process::Command::new("go")
+        .args(["mod", "tidy"])
+        .current_dir(&project_dir)
+        .status()
+        .map_err(|e| DxError::Tool(format!("Erro ao executar 'go mod tidy' (o Go está no PATH?): {e}")))?;
+    if !status.success() {
+        return Err(DxError::Tool(format!("'go mod tidy' falhou ({status}).")));
+    }
+    outln!("go.mod e go.sum organizados (go mod tidy).");
+    Ok(())
 }
 
-fn delete_maven(_dir: &Path, _name: String) {
-    println!("Operação não suportada para Maven.");
+fn get_go_dependencies(dir: &Path) -> Vec<DependencyInfo> {
+    let path = go_mod_path(dir);
+    let declared: Vec<(String, String)> = fs::read_to_string(&path)
+        .map(|data| parse_go_mod(&data).into_iter().collect())
+        .unwrap_or_default();
+    in_parallel(&declared, |(k, v)| DependencyInfo {
+        name: k.clone(),
+        current_version: v.clone(),
+        latest_version: fetch_latest_go(k),
+        update_command: format!("go get {}@latest", k),
+        url: format!("https://pkg.go.dev/{}", k),
+    })
 }
 
-fn get_maven_dependencies(dir: &Path) -> Vec<DependencyInfo> {
+// Maven helpers
+fn pom_xml_path(dir: &Path) -> PathBuf {
+    dir.join("pom.xml")
+}
+
+/// A `<dependency>` of the project's own `<dependencies>` (not those of
+/// `<dependencyManagement>`, profiles or plugins).
+struct PomDependency {
+    group: String,
+    artifact: String,
+    scope: Option<String>,
+    /// Version, with a `${property}` resolved from `<properties>`
+    version: Option<String>,
+    /// Where the version is written: the `<version>` text or the property's
+    version_range: Option<Range<usize>>,
+    /// End of `<artifactId>`: a missing `<version>` goes after it
+    artifact_end: usize,
+    /// The whole `<dependency>` element
+    range: Range<usize>,
+}
+
+impl PomDependency {
+    fn name(&self) -> String {
+        format!("{}:{}", self.group, self.artifact)
+    }
+}
+
+/// pom.xml read through a DOM that keeps the byte range of every node:
+/// edits replace those ranges, so the rest of the file stays as written.
+struct Pom {
+    data: String,
+    dependencies: Vec<PomDependency>,
+    /// The project's `<dependencies>` element
+    dependencies_range: Option<Range<usize>>,
+    /// Start of `</project>`
+    project_end: usize,
+    /// Indentation of one nesting level
+    indent: String,
+}
+
+fn xml_child<'a, 'i>(node: roxmltree::Node<'a, 'i>, name: &str) -> Option<roxmltree::Node<'a, 'i>> {
+    node.children().find(|c| c.is_element() && c.tag_name().name() == name)
+}
+
+/// Text of `node` and its byte range.
+fn xml_text(node: roxmltree::Node) -> Option<(String, Range<usize>)> {
+    let text = node.first_child().filter(|t| t.is_text())?;
+    Some((text.text()?.trim().to_string(), text.range()))
+}
+
+/// Whitespace before `pos` on its line.
+fn line_indent(data: &str, pos: usize) -> &str {
+    let line = &data[data[..pos].rfind('\n').map_or(0, |i| i + 1)..pos];
+    if line.trim().is_empty() { line } else { "" }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+impl Pom {
+    fn parse(data: String) -> DxResult<Self> {
+        let doc = roxmltree::Document::parse(&data)
+            .map_err(|e| DxError::InvalidInput(format!("pom.xml inválido: {e}")))?;
+        let project = doc.root_element();
+        let properties: BTreeMap<String, (String, Range<usize>)> = xml_child(project, "properties")
+            .into_iter()
+            .flat_map(|p| p.children().filter(|c| c.is_element()))
+            .filter_map(|p| Some((p.tag_name().name().to_string(), xml_text(p)?)))
+            .collect();
+        let indent = project
+            .children()
+            .find(|c| c.is_element())
+            .map(|c| line_indent(&data, c.range().start).to_string())
+            .filter(|i| !i.is_empty())
+            .unwrap_or_else(|| "    ".to_string());
+        let dependencies_node = xml_child(project, "dependencies");
+        let dependencies = dependencies_node
+            .into_iter()
+            .flat_map(|d| d.children().filter(|c| c.is_element() && c.tag_name().name() == "dependency"))
+            .filter_map(|d| {
+                let text = |name| xml_child(d, name).and_then(xml_text);
+                let artifact = xml_child(d, "artifactId")?;
+                let (version, version_range) = match text("version") {
+                    Some((v, range)) => match v.strip_prefix("${").and_then(|p| p.strip_suffix('}')) {
+                        Some(property) => match properties.get(property) {
+                            Some((value, range)) => (Some(value.clone()), Some(range.clone())),
+                            // `${project.version}` and the like: not ours to bump
+                            None => (Some(v), None),
+                        },
+                        None => (Some(v), Some(range)),
+                    },
+                    None => (None, None),
+                };
+                Some(PomDependency {
+                    group: text("groupId")?.0,
+                    artifact: xml_text(artifact)?.0,
+                    scope: text("scope").map(|(s, _)| s),
+                    version,
+                    version_range,
+                    artifact_end: artifact.range().end,
+                    range: d.range(),
+                })
+            })
+            .collect();
+        let dependencies_range = dependencies_node.map(|d| d.range());
+        let project_end = data[..project.range().end].rfind("</").unwrap_or(data.len());
+        Ok(Pom { data, dependencies, dependencies_range, project_end, indent })
+    }
+
+    fn load(path: &Path) -> DxResult<Self> {
+        Self::parse(fs::read_to_string(path).context("Erro ao ler pom.xml")?)
+    }
+
+    fn find(&self, name: &str) -> Option<&PomDependency> {
+        self.dependencies.iter().find(|d| d.name() == name)
+    }
+
+    /// Test dependencies, as `dev-dependencies list` shows them.
+    fn test_dependencies(&self) -> impl Iterator<Item = &PomDependency> {
+        self.dependencies.iter().filter(|d| d.scope.as_deref() == Some("test"))
+    }
+
+    /// Edit that sets the version of `dep` (inserting `<version>` if missing).
+    fn set_version(&self, dep: &PomDependency, version: &str) -> (Range<usize>, String) {
+        match &dep.version_range {
+            Some(range) => (range.clone(), xml_escape(version)),
+            None => {
+                let start = self.data[..dep.artifact_end].rfind("<artifactId").unwrap_or(dep.artifact_end);
+                let indent = line_indent(&self.data, start);
+                (dep.artifact_end..dep.artifact_end, format!("\n{indent}<version>{}</version>", xml_escape(version)))
+            }
+        }
+    }
+
+    /// Edit that adds a test-scoped `<dependency>`, creating `<dependencies>`
+    /// if the project has none.
+    fn insert(&self, group: &str, artifact: &str, version: &str) -> (Range<usize>, String) {
+        let unit = &self.indent;
+        let element = |indent: &str| {
+            format!(
+                "{indent}<dependency>\n{indent}{unit}<groupId>{}</groupId>\n{indent}{unit}<artifactId>{}</artifactId>\n\
+                 {indent}{unit}<version>{}</version>\n{indent}{unit}<scope>test</scope>\n{indent}</dependency>\n",
+                xml_escape(group),
+                xml_escape(artifact),
+                xml_escape(version)
+            )
+        };
+        let Some(range) = &self.dependencies_range else {
+            let at = self.project_end - line_indent(&self.data, self.project_end).len();
+            let block = element(&format!("{unit}{unit}"));
+            return (at..at, format!("\n{unit}<dependencies>\n{block}{unit}</dependencies>\n"));
+        };
+        let outer = line_indent(&self.data, range.start);
+        let indent = match self.dependencies.last() {
+            Some(last) => line_indent(&self.data, last.range.start).to_string(),
+            None => format!("{outer}{unit}"),
+        };
+        if self.data[range.clone()].ends_with("/>") {
+            return (range.clone(), format!("<dependencies>\n{}{outer}</dependencies>", element(&indent)));
+        }
+        let close = self.data[..range.end].rfind("</").unwrap_or(range.end);
+        let before = line_indent(&self.data, close);
+        if before.is_empty() {
+            // `</dependencies>` shares its line with something else
+            (close..close, format!("\n{}{outer}", element(&indent)))
+        } else {
+            let at = close - before.len();
+            (at..at, element(&indent))
+        }
+    }
+
+    /// Edit that removes `dep` along with its line.
+    fn remove(&self, dep: &PomDependency) -> (Range<usize>, String) {
+        (whole_lines(&self.data, dep.range.clone()), String::new())
+    }
+
+    /// Apply `edits` (non-overlapping) and write the file.
+    fn save(mut self, path: &Path, mut edits: Vec<(Range<usize>, String)>) -> DxResult {
+        edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+        for (range, text) in edits {
+            self.data.replace_range(range, &text);
+        }
+        fs::write(path, self.data).context("Erro ao salvar pom.xml")
+    }
+}
+
+fn extract_between<'a>(hay: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let s = hay.find(start)? + start.len();
+    let e = hay[s..].find(end)? + s;
+    Some(&hay[s..e])
+}
+
+fn list_maven(dir: &Path) -> Vec<DeclaredDependency> {
+    Pom::load(&pom_xml_path(dir))
+        .map(|pom| {
+            pom.test_dependencies()
+                .map(|d| DeclaredDependency::from((d.name(), d.version.clone().unwrap_or_default())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn fetch_latest_maven(group: &str, artifact: &str) -> Option<String> {
+    let path = group.replace('.', "/");
+    let url = format!(
+        "{}/{}/{}/maven-metadata.xml",
+        registry_base("https://repo1.maven.org/maven2", |m| &m.maven),
+        path,
+        artifact
+    );
+    cached_latest(&url, || {
+        let text = registry_get(&url)?;
+        extract_between(&text, "<latest>", "</latest>")
+            .or_else(|| extract_between(&text, "<release>", "</release>"))
+            .map(|s| s.to_string())
+    })
+}
+
+/// `groupId:artifactId`
+fn maven_coordinates(name: &str) -> DxResult<(&str, &str)> {
+    name.split_once(':')
+        .filter(|(g, a)| !g.is_empty() && !a.is_empty() && !a.contains(':'))
+        .ok_or_else(|| DxError::InvalidInput(format!("Informe a dependência Maven como groupId:artifactId (recebido: '{name}').")))
+}
+
+fn add_maven(dir: &Path, name: String, version: Option<String>) -> DxResult {
+    let (group, artifact) = maven_coordinates(&name)?;
     let path = pom_xml_path(dir);
-    let mut deps = Vec::new();
-    if let Ok(data) = fs::read_to_string(&path) {
-        for (g, a, v) in parse_maven_deps(&data) {
-            let latest = fetch_latest_maven(&g, &a);
-            let name = format!("{}:{}", g, a);
-            deps.push(DependencyInfo {
-                name: name.clone(),
-                current_version: v.clone(),
-                latest_version: latest.clone(),
-                update_command: format!("mvn dependency:get -Dartifact={}:{}:LATEST", g, a),
-                url: format!("https://search.maven.org/artifact/{}/{}", g, a),
-            });
+    let pom = Pom::load(&path)?;
+    let version = match version.filter(|v| v != "latest") {
+        Some(v) => v,
+        None => fetch_latest_maven(group, artifact).ok_or_else(|| {
+            DxError::Network(format!("Não foi possível obter a última versão de '{name}' no Maven Central."))
+        })?,
+    };
+    let edit = match pom.find(&name) {
+        Some(dep) => pom.set_version(dep, &version),
+        None => pom.insert(group, artifact, &version),
+    };
+    pom.save(&path, vec![edit])?;
+    outln!("Dependência '{name}' adicionada ({version}).");
+    Ok(())
+}
+
+fn update_maven(dir: &Path, name: Option<String>) -> DxResult {
+    let path = pom_xml_path(dir);
+    let pom = Pom::load(&path)?;
+    let targets: Vec<&PomDependency> = match &name {
+        Some(n) => vec![pom.find(n).ok_or_else(|| not_found(n))?],
+        None => pom.test_dependencies().collect(),
+    };
+    let latest = in_parallel(&targets, |d| fetch_latest_maven(&d.group, &d.artifact));
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    for (dep, latest) in targets.iter().zip(latest) {
+        let Some(range) = &dep.version_range else {
+            tracing::warn!(dependency = %dep.name(), "versão definida fora do pom.xml (parent, BOM ou propriedade herdada); mantendo");
+            continue;
+        };
+        let Some(latest) = latest else {
+            tracing::warn!(dependency = %dep.name(), "não foi possível obter a última versão; mantendo a atual");
+            continue;
+        };
+        // Dependencies sharing a version property bump it once
+        if !edits.iter().any(|(r, _)| r == range) {
+            edits.push(pom.set_version(dep, &latest));
         }
     }
-    deps
+    match &name {
+        Some(n) if !edits.is_empty() => outln!("Dependência '{n}' atualizada."),
+        Some(_) => {}
+        None => outln!("Todas as dependências atualizadas."),
+    }
+    pom.save(&path, edits)
+}
+
+fn delete_maven(dir: &Path, name: String) -> DxResult {
+    let path = pom_xml_path(dir);
+    let pom = Pom::load(&path)?;
+    let edit = pom.remove(pom.find(&name).ok_or_else(|| not_found(&name))?);
+    pom.save(&path, vec![edit])?;
+    outln!("Dependência '{name}' removida.");
+    Ok(())
+}
+
+fn get_maven_dependencies(dir: &Path) -> Vec<DependencyInfo> {
+    let declared: Vec<(String, String, String)> = Pom::load(&pom_xml_path(dir))
+        .map(|pom| {
+            pom.test_dependencies()
+                .map(|d| (d.group.clone(), d.artifact.clone(), d.version.clone().unwrap_or_default()))
+                .collect()
+        })
+        .unwrap_or_default();
+    in_parallel(&declared, |(g, a, v)| DependencyInfo {
+        name: format!("{}:{}", g, a),
+        current_version: v.clone(),
+        latest_version: fetch_latest_maven(g, a),
+        update_command: format!("mvn dependency:get -Dartifact={}:{}:LATEST", g, a),
+        url: format!("https://search.maven.org/artifact/{}/{}", g, a),
+    })
 }
 
 // Gradle helpers
@@ -685,91 +1738,643 @@ fn gradle_build_path(dir: &Path) -> PathBuf {
     }
 }
 
-fn parse_gradle_deps(data: &str) -> Vec<(String, String, String)> {
-    let mut deps = Vec::new();
-    let mut in_block = false;
-    for line in data.lines() {
-        let l = line.trim();
-        if l.starts_with("dependencies") {
-            in_block = true;
-            continue;
+/// Configurations holding test dependencies in a `dependencies` block.
+const GRADLE_TEST_CONFIGS: &[&str] = &[
+    "testImplementation",
+    "testCompile",
+    "testRuntimeOnly",
+    "testCompileOnly",
+];
+
+/// Configurations that hold test dependencies inside a Kotlin Multiplatform
+/// test source set (`commonTest`, `jvmTest`, ...).
+const GRADLE_SOURCE_SET_CONFIGS: &[&str] = &["implementation", "api", "compileOnly", "runtimeOnly"];
+
+/// First string literal (`"..."` or `'...'`) in `s`.
+fn quoted(s: &str) -> Option<&str> {
+    let start = s.find(['"', '\''])?;
+    let quote = &s[start..start + 1];
+    let rest = &s[start + 1..];
+    rest.find(quote).map(|end| &rest[..end])
+}
+
+/// Version of the Kotlin Gradle plugin (`kotlin("jvm") version "2.0.0"`).
+fn kotlin_plugin_version(data: &str) -> String {
+    data.lines()
+        .filter(|l| l.contains("kotlin(\"") || l.contains("org.jetbrains.kotlin"))
+        .find_map(|l| quoted(&l[l.find(" version ")?..]))
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Where the version of a Gradle dependency is written.
+enum GradleVersion {
+    /// In the coordinates string: the version, or an empty range after the
+    /// artifact when a platform/BOM manages it
+    Inline(Range<usize>),
+    /// In the version catalog, under this library alias
+    Catalog(String),
+    /// Out of dx's reach: the Kotlin plugin's version, a variable, a rich version
+    Managed,
+}
+
+/// A test dependency of a Gradle build script.
+struct GradleDependency {
+    group: String,
+    artifact: String,
+    /// Version, with catalog references resolved; empty when a platform/BOM manages it
+    version: String,
+    at: GradleVersion,
+    /// The statement (`testImplementation("group:artifact:version")`)
+    range: Range<usize>,
+    /// Declared in a test source set (`commonTest`, `jvmTest`, ...)
+    source_set: bool,
+}
+
+impl GradleDependency {
+    fn name(&self) -> String {
+        format!("{}:{}", self.group, self.artifact)
+    }
+}
+
+/// Byte offset of `part`, a slice of `data`.
+fn offset_in(data: &str, part: &str) -> usize {
+    part.as_ptr() as usize - data.as_ptr() as usize
+}
+
+/// Dependency declared by a statement: `"group:artifact:version"`, a version
+/// catalog accessor (`libs.junit.jupiter`) or Kotlin's `kotlin("test")`
+/// shorthand, versioned with the Kotlin plugin. Ranges are relative to the
+/// statement.
+fn gradle_dependency(
+    statement: &str,
+    kotlin_version: &str,
+    catalog: Option<&VersionCatalog>,
+) -> Option<GradleDependency> {
+    let dependency = |group: &str, artifact: &str, version: &str, at| GradleDependency {
+        group: group.to_string(),
+        artifact: artifact.to_string(),
+        version: version.to_string(),
+        at,
+        range: 0..statement.len(),
+        source_set: false,
+    };
+    if let Some(start) = statement.find("kotlin(\"") {
+        let rest = &statement[start + "kotlin(\"".len()..];
+        let end = rest.find('"')?;
+        let version = quoted(&rest[end + 1..]).unwrap_or(kotlin_version);
+        let artifact = format!("kotlin-{}", &rest[..end]);
+        return Some(dependency("org.jetbrains.kotlin", &artifact, version, GradleVersion::Managed));
+    }
+    if let Some(start) = statement.find("libs.") {
+        let accessor = statement[start + "libs.".len()..]
+            .split(|c: char| !c.is_alphanumeric() && c != '.' && c != '_')
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('.');
+        let catalog = catalog?;
+        let alias = catalog.alias(accessor)?;
+        let (group, artifact, version) = catalog.library(&alias)?;
+        let at = match version {
+            Some(_) => GradleVersion::Catalog(alias),
+            None => GradleVersion::Managed,
+        };
+        return Some(dependency(&group, &artifact, &version.unwrap_or_default(), at));
+    }
+    let coordinates = quoted(statement)?;
+    let start = offset_in(statement, coordinates);
+    let mut parts = coordinates.split(':');
+    let g = parts.next().unwrap_or("");
+    let a = parts.next().unwrap_or("");
+    if g.is_empty() || a.is_empty() {
+        return None;
+    }
+    // `group:artifact:version[:classifier][@extension]`
+    let version = parts.next().map(|v| v.split('@').next().unwrap_or(v));
+    let at = match version {
+        Some(v) if v.contains('$') => GradleVersion::Managed,
+        Some(v) => {
+            let start = start + g.len() + a.len() + 2;
+            GradleVersion::Inline(start..start + v.len())
         }
-        if in_block && l.starts_with('}') {
-            in_block = false;
-            continue;
+        None => {
+            let end = start + coordinates.split('@').next().unwrap_or(coordinates).len();
+            GradleVersion::Inline(end..end)
+        }
+    };
+    Some(dependency(g, a, version.unwrap_or_default(), at))
+}
+
+/// Test dependency declared by `statement` inside the `blocks` it is nested in.
+fn test_dependency(
+    blocks: &[String],
+    statement: &str,
+    kotlin_version: &str,
+    catalog: Option<&VersionCatalog>,
+) -> Option<GradleDependency> {
+    if !blocks.iter().any(|b| b.ends_with("dependencies")) {
+        return None;
+    }
+    let in_test_source_set = blocks.iter().any(|b| {
+        b.split(|c: char| !c.is_alphanumeric())
+            .any(|w| w.ends_with("Test"))
+    });
+    let declares = |cfg: &&str| {
+        statement
+            .strip_prefix(*cfg)
+            .is_some_and(|rest| rest.starts_with([' ', '(', '"', '\'']))
+    };
+    if GRADLE_TEST_CONFIGS.iter().any(declares) {
+        gradle_dependency(statement, kotlin_version, catalog)
+    } else if in_test_source_set && GRADLE_SOURCE_SET_CONFIGS.iter().any(declares) {
+        gradle_dependency(statement, kotlin_version, catalog).map(|d| GradleDependency {
+            source_set: true,
+            ..d
+        })
+    } else {
+        None
+    }
+}
+
+/// The version catalog (`gradle/libs.versions.toml`), edited in place so its
+/// formatting and comments are kept.
+struct VersionCatalog {
+    path: PathBuf,
+    doc: DocumentMut,
+    changed: bool,
+}
+
+/// Replace the string in `item`, keeping its surrounding whitespace and comments.
+fn set_toml_str(item: &mut toml_edit::Item, s: &str) {
+    if let Some(v) = item.as_value_mut() {
+        let decor = v.decor().clone();
+        *v = s.into();
+        *v.decor_mut() = decor;
+    }
+}
+
+impl VersionCatalog {
+    fn load(dir: &Path) -> DxResult<Option<Self>> {
+        let path = dir.join("gradle").join("libs.versions.toml");
+        let Ok(data) = fs::read_to_string(&path) else {
+            return Ok(None);
+        };
+        let doc = data
+            .parse::<DocumentMut>()
+            .map_err(|e| DxError::InvalidInput(format!("gradle/libs.versions.toml inválido: {e}")))?;
+        Ok(Some(VersionCatalog { path, doc, changed: false }))
+    }
+
+    fn libraries(&self) -> impl Iterator<Item = (&str, &toml_edit::Item)> {
+        self.doc
+            .get("libraries")
+            .and_then(|l| l.as_table_like())
+            .into_iter()
+            .flat_map(|l| l.iter())
+    }
+
+    /// Alias of the library reached by `accessor` (`junit.jupiter` reaches
+    /// `junit-jupiter`, `junit_jupiter` and `junit.jupiter`).
+    fn alias(&self, accessor: &str) -> Option<String> {
+        self.libraries()
+            .map(|(alias, _)| alias)
+            .find(|alias| alias.replace(['-', '_'], ".") == accessor)
+            .map(str::to_string)
+    }
+
+    /// Group, artifact and version of the library `alias`; no version when a
+    /// platform manages it or it is a rich version (`strictly`, `prefer`...).
+    fn library(&self, alias: &str) -> Option<(String, String, Option<String>)> {
+        let item = self.libraries().find(|(a, _)| *a == alias)?.1;
+        if let Some(notation) = item.as_str() {
+            let mut parts = notation.split(':');
+            let group = parts.next()?.to_string();
+            let artifact = parts.next()?.to_string();
+            return Some((group, artifact, parts.next().map(str::to_string)));
+        }
+        let (group, artifact) = match item.get("module").and_then(|m| m.as_str()) {
+            Some(module) => {
+                let (group, artifact) = module.split_once(':')?;
+                (group.to_string(), artifact.to_string())
+            }
+            None => (
+                item.get("group")?.as_str()?.to_string(),
+                item.get("name")?.as_str()?.to_string(),
+            ),
+        };
+        let version = match item.get("version") {
+            Some(v) if v.is_str() => v.as_str().map(str::to_string),
+            _ => self
+                .version_ref(alias)
+                .and_then(|r| self.doc.get("versions")?.get(&r)?.as_str().map(str::to_string)),
+        };
+        Some((group, artifact, version))
+    }
+
+    /// Entry of `[versions]` the library `alias` takes its version from.
+    fn version_ref(&self, alias: &str) -> Option<String> {
+        let item = self.libraries().find(|(a, _)| *a == alias)?.1;
+        let version = item.get("version")?;
+        let reference = version.get("ref").or_else(|| item.get("version.ref"))?;
+        reference.as_str().map(str::to_string)
+    }
+
+    /// Alias of the library `group:artifact`, if the catalog has one.
+    fn find(&self, group: &str, artifact: &str) -> Option<String> {
+        self.libraries()
+            .map(|(alias, _)| alias)
+            .find(|alias| {
+                self.library(alias)
+                    .is_some_and(|(g, a, _)| g == group && a == artifact)
+            })
+            .map(str::to_string)
+    }
+
+    /// Set the version of the library `alias`; false when it isn't written in
+    /// the catalog.
+    fn set_version(&mut self, alias: &str, version: &str) -> bool {
+        let reference = self.version_ref(alias);
+        let item = match &reference {
+            Some(reference) => self.doc.get_mut("versions").and_then(|v| v.get_mut(reference)),
+            None => self.doc.get_mut("libraries").and_then(|l| l.get_mut(alias)),
+        };
+        let Some(item) = item else {
+            return false;
+        };
+        if reference.is_some() {
+            set_toml_str(item, version);
+        } else if let Some(notation) = item.as_str() {
+            let mut parts: Vec<&str> = notation.split(':').collect();
+            if parts.len() < 3 {
+                return false;
+            }
+            parts[2] = version;
+            let notation = parts.join(":");
+            set_toml_str(item, &notation);
+        } else {
+            match item.get_mut("version") {
+                Some(v) if v.is_str() => set_toml_str(v, version),
+                _ => return false,
+            }
+        }
+        self.changed = true;
+        true
+    }
+
+    /// Add the library `group:artifact` and return its alias: the artifact,
+    /// prefixed with the last segment of the group if that alias is taken.
+    fn insert(&mut self, group: &str, artifact: &str, version: &str) -> String {
+        let slug = |s: &str| -> String {
+            s.to_lowercase()
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+                .collect()
+        };
+        let mut alias = slug(artifact);
+        if self.libraries().any(|(a, _)| a == alias) {
+            alias = format!("{}-{alias}", slug(group.rsplit('.').next().unwrap_or(group)));
+        }
+        let mut library = toml_edit::InlineTable::new();
+        library.insert("module", format!("{group}:{artifact}").into());
+        library.insert("version", version.into());
+        let libraries = self
+            .doc
+            .entry("libraries")
+            .or_insert(toml_edit::Item::Table(Default::default()));
+        if let Some(libraries) = libraries.as_table_like_mut() {
+            libraries.insert(&alias, toml_edit::Item::Value(library.into()));
         }
-        if in_block {
-            let configs = [
-                "testImplementation",
-                "testCompile",
-                "testRuntimeOnly",
-                "testCompileOnly",
-            ];
-            for cfg in configs {
-                if l.starts_with(cfg) {
-                    if let Some(start) = l.find("'").or_else(|| l.find("\"")) {
-                        let quote = l.chars().nth(start).unwrap();
-                        if let Some(end) = l[start + 1..].find(quote) {
-                            let dep = &l[start + 1..start + 1 + end];
-                            let mut parts = dep.split(':');
-                            let g = parts.next().unwrap_or("").to_string();
-                            let a = parts.next().unwrap_or("").to_string();
-                            let v = parts.next().unwrap_or("").to_string();
-                            deps.push((g, a, v));
-                        }
+        self.changed = true;
+        alias
+    }
+}
+
+/// Lines of a range: `range` grown to its whole lines when nothing else
+/// shares them, so removing it leaves no blank line behind.
+fn whole_lines(data: &str, mut range: Range<usize>) -> Range<usize> {
+    range.start -= line_indent(data, range.start).len();
+    let rest = &data[range.end..];
+    let line_end = rest.find('\n').map_or(rest.len(), |i| i + 1);
+    if rest[..line_end].trim().is_empty() {
+        range.end += line_end;
+    }
+    range
+}
+
+/// A Gradle build script (`build.gradle` or `build.gradle.kts`) and its
+/// version catalog, edited as text so the rest of the script stays as written.
+struct GradleBuild {
+    path: PathBuf,
+    data: String,
+    dependencies: Vec<GradleDependency>,
+    /// Body of the top-level `dependencies { }` block
+    block: Option<Range<usize>>,
+    catalog: Option<VersionCatalog>,
+    edits: Vec<(Range<usize>, String)>,
+}
+
+impl GradleBuild {
+    fn parse(path: PathBuf, data: String, catalog: Option<VersionCatalog>) -> Self {
+        let kotlin_version = kotlin_plugin_version(&data);
+        let mut dependencies = Vec::new();
+        let mut block = None;
+        let mut open = None;
+        // Headers of the enclosing blocks (`dependencies`, `sourceSets`, `commonTest`, ...)
+        let mut blocks: Vec<String> = Vec::new();
+        for line in data.lines() {
+            let l = line.trim();
+            if l.starts_with("//") {
+                continue;
+            }
+            // Braces of `${...}` templates open and close on the same line
+            let templates = l.matches("${").count();
+            let mut rest = l;
+            loop {
+                let brace = rest
+                    .char_indices()
+                    .find(|&(i, c)| c == '{' && !rest[..i].ends_with('$'))
+                    .map(|(i, _)| i);
+                let statement = rest[..brace.unwrap_or(rest.len())].trim();
+                if let Some(mut dep) = test_dependency(&blocks, statement, &kotlin_version, catalog.as_ref()) {
+                    let start = offset_in(&data, statement);
+                    dep.range = start..start + statement.len();
+                    if let GradleVersion::Inline(range) = &mut dep.at {
+                        *range = range.start + start..range.end + start;
                     }
+                    dependencies.push(dep);
+                }
+                let Some(brace) = brace else {
+                    break;
+                };
+                if blocks.is_empty() && statement == "dependencies" && block.is_none() {
+                    open = Some(offset_in(&data, rest) + brace + 1);
                 }
+                blocks.push(statement.to_string());
+                rest = &rest[brace + 1..];
+            }
+
+            for _ in templates..l.matches('}').count() {
+                if blocks.len() == 1
+                    && let Some(start) = open.take()
+                {
+                    let close = offset_in(&data, l) + l.rfind('}').unwrap_or(0);
+                    block = Some(start..close);
+                }
+                blocks.pop();
             }
         }
+        GradleBuild { path, data, dependencies, block, catalog, edits: Vec::new() }
     }
-    deps
-}
 
-fn list_gradle(dir: &Path) {
-    let path = gradle_build_path(dir);
-    if let Ok(data) = fs::read_to_string(&path) {
-        let deps = parse_gradle_deps(&data);
-        if deps.is_empty() {
-            println!("Nenhuma dependência encontrada.");
+    fn load(dir: &Path) -> DxResult<Self> {
+        let path = gradle_build_path(dir);
+        let data = fs::read_to_string(&path).context(format!("Erro ao ler {}", path.display()))?;
+        Ok(Self::parse(path, data, VersionCatalog::load(dir)?))
+    }
+
+    fn find(&self, name: &str) -> Option<usize> {
+        self.dependencies.iter().position(|d| d.name() == name)
+    }
+
+    /// Set the version of the dependency at `index`; false when it is
+    /// written outside the script and its catalog.
+    fn set_version(&mut self, index: usize, version: &str) -> bool {
+        match &self.dependencies[index].at {
+            GradleVersion::Inline(range) if range.is_empty() => {
+                self.edits.push((range.clone(), format!(":{version}")));
+                true
+            }
+            GradleVersion::Inline(range) => {
+                self.edits.push((range.clone(), version.to_string()));
+                true
+            }
+            GradleVersion::Catalog(alias) => {
+                let alias = alias.clone();
+                self.catalog.as_mut().is_some_and(|c| c.set_version(&alias, version))
+            }
+            GradleVersion::Managed => false,
+        }
+    }
+
+    /// `config(notation)` in the style of the script: Kotlin always calls
+    /// with parentheses, Groovy follows the test dependencies already declared
+    /// (`testImplementation 'g:a:v'` by default).
+    fn statement(&self, config: &str, coordinates: Option<&str>, accessor: Option<&str>) -> String {
+        let kotlin = self.path.extension().is_some_and(|e| e == "kts");
+        let existing = self.dependencies.iter().map(|d| &self.data[d.range.clone()]);
+        let parens = kotlin
+            || existing
+                .clone()
+                .any(|s| s.trim_start_matches(|c: char| c.is_alphanumeric()).starts_with('('));
+        let quote = if kotlin {
+            '"'
+        } else {
+            existing
+                .flat_map(|s| s.chars().find(|c| matches!(c, '"' | '\'')))
+                .next()
+                .unwrap_or('\'')
+        };
+        let notation = match (accessor, coordinates) {
+            (Some(accessor), _) => format!("libs.{accessor}"),
+            (None, coordinates) => format!("{quote}{}{quote}", coordinates.unwrap_or_default()),
+        };
+        if parens {
+            format!("{config}({notation})")
         } else {
-            for (g, a, v) in deps {
-                println!("- {}:{} = {}", g, a, v);
+            format!("{config} {notation}")
+        }
+    }
+
+    /// Declare the test dependency `group:artifact` (through the version
+    /// catalog when the project has one): after the last test dependency, or
+    /// at the end of the `dependencies` block, created if missing.
+    fn insert(&mut self, group: &str, artifact: &str, version: &str) {
+        let accessor = self.catalog.as_mut().map(|catalog| {
+            let alias = match catalog.find(group, artifact) {
+                Some(alias) => {
+                    catalog.set_version(&alias, version);
+                    alias
+                }
+                None => catalog.insert(group, artifact, version),
+            };
+            alias.replace(['-', '_'], ".")
+        });
+        let coordinates = format!("{group}:{artifact}:{version}");
+        let last = self
+            .dependencies
+            .iter()
+            .rfind(|d| !d.source_set)
+            .or(self.dependencies.last());
+        let config = match last {
+            Some(dep) if dep.source_set => "implementation",
+            _ => "testImplementation",
+        };
+        let statement = self.statement(config, Some(&coordinates), accessor.as_deref());
+        let edit = match (last, &self.block) {
+            (Some(dep), _) => {
+                let indent = line_indent(&self.data, dep.range.start);
+                let rest = &self.data[dep.range.end..];
+                let line_end = rest.find('\n').unwrap_or(rest.len());
+                // `dependencies { testImplementation(...) }` on one line
+                let at = if rest[..line_end].contains('}') {
+                    dep.range.end
+                } else {
+                    dep.range.end + line_end
+                };
+                (at..at, format!("\n{indent}{statement}"))
+            }
+            (None, Some(body)) => {
+                let outer = line_indent(&self.data, body.end);
+                let indent = self.data[body.clone()]
+                    .lines()
+                    .skip(1)
+                    .find(|l| !l.trim().is_empty())
+                    .map(|l| l[..l.len() - l.trim_start().len()].to_string())
+                    .unwrap_or_else(|| format!("{outer}    "));
+                if outer.is_empty() && !self.data[..body.end].ends_with('\n') {
+                    // `}` shares its line with something else
+                    (body.end..body.end, format!("\n{indent}{statement}\n"))
+                } else {
+                    let at = body.end - outer.len();
+                    (at..at, format!("{indent}{statement}\n"))
+                }
             }
+            (None, None) => {
+                let at = self.data.len();
+                let separator = if self.data.ends_with('\n') { "\n" } else { "\n\n" };
+                (at..at, format!("{separator}dependencies {{\n    {statement}\n}}\n"))
+            }
+        };
+        self.edits.push(edit);
+    }
+
+    /// Remove the statement of the dependency at `index` (along with its
+    /// line). Its catalog entry stays: other modules may use it.
+    fn remove(&mut self, index: usize) {
+        let range = whole_lines(&self.data, self.dependencies[index].range.clone());
+        self.edits.push((range, String::new()));
+    }
+
+    /// Apply the edits (non-overlapping) and write the script and the catalog.
+    fn save(mut self) -> DxResult {
+        if let Some(catalog) = &self.catalog
+            && catalog.changed
+        {
+            fs::write(&catalog.path, catalog.doc.to_string())
+                .context("Erro ao salvar gradle/libs.versions.toml")?;
         }
-    } else {
-        println!("Nenhuma dependência encontrada.");
+        if self.edits.is_empty() {
+            return Ok(());
+        }
+        self.edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+        for (range, text) in std::mem::take(&mut self.edits) {
+            self.data.replace_range(range, &text);
+        }
+        fs::write(&self.path, self.data).context(format!("Erro ao salvar {}", self.path.display()))
     }
 }
 
-fn add_gradle(_dir: &Path, _name: String, _version: Option<String>) {
-    println!("Operação não suportada para Gradle.");
+fn list_gradle(dir: &Path) -> Vec<DeclaredDependency> {
+    GradleBuild::load(dir)
+        .map(|build| {
+            build
+                .dependencies
+                .into_iter()
+                .map(|d| DeclaredDependency::from((d.name(), d.version)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn add_gradle(dir: &Path, name: String, version: Option<String>) -> DxResult {
+    let (group, artifact) = maven_coordinates(&name)?;
+    let mut build = GradleBuild::load(dir)?;
+    let version = match version.filter(|v| v != "latest") {
+        Some(v) => v,
+        None => fetch_latest_maven(group, artifact).ok_or_else(|| {
+            DxError::Network(format!("Não foi possível obter a última versão de '{name}' no Maven Central."))
+        })?,
+    };
+    match build.find(&name) {
+        Some(index) if !build.set_version(index, &version) => {
+            return Err(DxError::InvalidInput(format!(
+                "A versão de '{name}' é definida fora do script (plugin do Kotlin, variável ou versão rica do catálogo); altere-a lá."
+            )));
+        }
+        Some(_) => {}
+        None => build.insert(group, artifact, &version),
+    }
+    build.save()?;
+    outln!("Dependência '{name}' adicionada ({version}).");
+    Ok(())
 }
 
-fn update_gradle(_dir: &Path, _name: Option<String>) {
-    println!("Operação não suportada para Gradle.");
+fn update_gradle(dir: &Path, name: Option<String>) -> DxResult {
+    let mut build = GradleBuild::load(dir)?;
+    let targets: Vec<usize> = match &name {
+        Some(n) => vec![build.find(n).ok_or_else(|| not_found(n))?],
+        None => (0..build.dependencies.len()).collect(),
+    };
+    let coordinates: Vec<(String, String)> = targets
+        .iter()
+        .map(|&i| (build.dependencies[i].group.clone(), build.dependencies[i].artifact.clone()))
+        .collect();
+    let latest = in_parallel(&coordinates, |(g, a)| fetch_latest_maven(g, a));
+    // Libraries sharing a `[versions]` entry bump it once
+    let mut bumped = std::collections::BTreeSet::new();
+    let mut updated = false;
+    for (&index, latest) in targets.iter().zip(latest) {
+        let dep = &build.dependencies[index];
+        if dep.version.is_empty() || matches!(dep.at, GradleVersion::Managed) {
+            tracing::warn!(dependency = %dep.name(), "versão definida fora do script (plugin, BOM/platform ou variável); mantendo");
+            continue;
+        }
+        let Some(latest) = latest else {
+            tracing::warn!(dependency = %dep.name(), "não foi possível obter a última versão; mantendo a atual");
+            continue;
+        };
+        if let GradleVersion::Catalog(alias) = &dep.at
+            && let Some(reference) = build.catalog.as_ref().and_then(|c| c.version_ref(alias))
+            && !bumped.insert(reference)
+        {
+            continue;
+        }
+        updated |= build.set_version(index, &latest);
+    }
+    match &name {
+        Some(n) if updated => outln!("Dependência '{n}' atualizada."),
+        Some(_) => {}
+        None => outln!("Todas as dependências atualizadas."),
+    }
+    build.save()
 }
 
-fn delete_gradle(_dir: &Path, _name: String) {
-    println!("Operação não suportada para Gradle.");
+fn delete_gradle(dir: &Path, name: String) -> DxResult {
+    let mut build = GradleBuild::load(dir)?;
+    let index = build.find(&name).ok_or_else(|| not_found(&name))?;
+    build.remove(index);
+    build.save()?;
+    outln!("Dependência '{name}' removida.");
+    Ok(())
 }
 
 fn get_gradle_dependencies(dir: &Path) -> Vec<DependencyInfo> {
-    let path = gradle_build_path(dir);
-    let mut deps = Vec::new();
-    if let Ok(data) = fs::read_to_string(&path) {
-        for (g, a, v) in parse_gradle_deps(&data) {
-            let latest = fetch_latest_maven(&g, &a);
-            let name = format!("{}:{}", g, a);
-            deps.push(DependencyInfo {
-                name: name.clone(),
-                current_version: v.clone(),
-                latest_version: latest.clone(),
-                update_command: "./gradlew --refresh-dependencies".into(),
-                url: format!("https://search.maven.org/artifact/{}/{}", g, a),
-            });
-        }
-    }
-    deps
+    let declared: Vec<(String, String, String)> = GradleBuild::load(dir)
+        .map(|build| {
+            build
+                .dependencies
+                .into_iter()
+                .map(|d| (d.group, d.artifact, d.version))
+                .collect()
+        })
+        .unwrap_or_default();
+    in_parallel(&declared, |(g, a, v)| DependencyInfo {
+        name: format!("{}:{}", g, a),
+        current_version: v.clone(),
+        latest_version: fetch_latest_maven(g, a),
+        update_command: "./gradlew --refresh-dependencies".into(),
+        url: format!("https://search.maven.org/artifact/{}/{}", g, a),
+    })
 }
 
 // PHP helpers
@@ -782,27 +2387,19 @@ fn load_composer_json(path: &Path) -> Value {
     serde_json::from_str(&data).unwrap_or_else(|_| Value::Object(Default::default()))
 }
 
-fn save_composer_json(path: &Path, v: &Value) {
-    if let Ok(data) = serde_json::to_string_pretty(v) {
-        let _ = fs::write(path, data);
-    }
+fn save_composer_json(path: &Path, v: &Value) -> DxResult {
+    let data = serde_json::to_string_pretty(v).unwrap_or_default();
+    fs::write(path, data).context("Erro ao salvar composer.json")
 }
 
-fn list_php(dir: &Path) {
-    let path = composer_json_path(dir);
-    let v = load_composer_json(&path);
-    if let Some(obj) = v.get("require-dev").and_then(|d| d.as_object()) {
-        for (k, v) in obj {
-            if let Some(ver) = v.as_str() {
-                println!("- {} = {}", k, ver);
-            }
-        }
-    } else {
-        println!("Nenhuma dependência encontrada.");
-    }
+fn list_php(dir: &Path) -> Vec<DeclaredDependency> {
+    string_map(load_composer_json(&composer_json_path(dir)).get("require-dev"))
+        .into_iter()
+        .map(DeclaredDependency::from)
+        .collect()
 }
 
-fn add_php(dir: &Path, name: String, version: Option<String>) {
+fn add_php(dir: &Path, name: String, version: Option<String>) -> DxResult {
     let path = composer_json_path(dir);
     let mut v = load_composer_json(&path);
     let obj = v
@@ -812,71 +2409,79 @@ fn add_php(dir: &Path, name: String, version: Option<String>) {
         .or_insert_with(|| Value::Object(Default::default()));
     if let Some(map) = obj.as_object_mut() {
         map.insert(name.clone(), Value::String(version.unwrap_or("*".into())));
-        save_composer_json(&path, &v);
-        println!("Dependência '{name}' adicionada.");
+        save_composer_json(&path, &v)?;
+        outln!("Dependência '{name}' adicionada.");
     }
+    Ok(())
 }
 
 fn fetch_latest_packagist(name: &str) -> Option<String> {
-    let url = format!("https://repo.packagist.org/p2/{}.json", name);
-    let v = reqwest::blocking::get(url).ok()?.json::<Value>().ok()?;
-    v.get("packages")?.as_object()?.get(name)?.get(0)?.get("version")?.as_str().map(|s| s.trim_start_matches('v').to_string())
+    let url = format!("{}/p2/{}.json", registry_base("https://repo.packagist.org", |m| &m.packagist), name);
+    cached_latest(&url, || {
+        let v = registry_json(&url)?;
+        v.get("packages")?.as_object()?.get(name)?.get(0)?.get("version")?.as_str().map(|s| s.trim_start_matches('v').to_string())
+    })
 }
 
-fn update_php(dir: &Path, name: Option<String>) {
+fn update_php(dir: &Path, name: Option<String>) -> DxResult {
     let path = composer_json_path(dir);
     let mut v = load_composer_json(&path);
     if let Some(map) = v.get_mut("require-dev").and_then(|d| d.as_object_mut()) {
         if let Some(n) = name {
             if let Some(latest) = fetch_latest_packagist(&n) {
                 map.insert(n.clone(), Value::String(latest));
-                println!("Dependência '{n}' atualizada.");
+                outln!("Dependência '{n}' atualizada.");
+            } else {
+                tracing::warn!(dependency = %n, "não foi possível obter a última versão; mantendo a atual");
             }
         } else {
-            for (k, val) in map.iter_mut() {
-                if let Some(latest) = fetch_latest_packagist(k) {
+            let names: Vec<String> = map.keys().cloned().collect();
+            let latest = in_parallel(&names, |n| fetch_latest_packagist(n));
+            for (val, latest) in map.values_mut().zip(latest) {
+                if let Some(latest) = latest {
                     *val = Value::String(latest);
                 }
             }
-            println!("Todas as dependências atualizadas.");
+            outln!("Todas as dependências atualizadas.");
         }
-        save_composer_json(&path, &v);
+        save_composer_json(&path, &v)?;
     }
+    Ok(())
 }
 
-fn delete_php(dir: &Path, name: String) {
+fn delete_php(dir: &Path, name: String) -> DxResult {
     let path = composer_json_path(dir);
     let mut v = load_composer_json(&path);
     if let Some(map) = v
         .as_object_mut()
         .and_then(|o| o.get_mut("require-dev"))
         .and_then(|d| d.as_object_mut())
+        && map.remove(&name).is_some()
     {
-        map.remove(&name);
-        println!("Dependência '{name}' removida.");
+        save_composer_json(&path, &v)?;
+        outln!("Dependência '{name}' removida.");
+        return Ok(());
     }
-    save_composer_json(&path, &v);
+    Err(not_found(&name))
 }
 
 fn get_php_dependencies(dir: &Path) -> Vec<DependencyInfo> {
     let path = composer_json_path(dir);
-    let mut deps = Vec::new();
     let v = load_composer_json(&path);
-    if let Some(map) = v.get("require-dev").and_then(|d| d.as_object()) {
-        for (k, val) in map {
-            if let Some(ver) = val.as_str() {
-                let latest = fetch_latest_packagist(k);
-                deps.push(DependencyInfo {
-                    name: k.clone(),
-                    current_version: ver.to_string(),
-                    latest_version: latest.clone(),
-                    update_command: format!("composer update {}", k),
-                    url: format!("https://packagist.org/packages/{}", k),
-                });
-            }
-        }
-    }
-    deps
+    let declared: Vec<(&String, &str)> = v
+        .get("require-dev")
+        .and_then(|d| d.as_object())
+        .into_iter()
+        .flatten()
+        .filter_map(|(k, val)| Some((k, val.as_str()?)))
+        .collect();
+    in_parallel(&declared, |&(k, ver)| DependencyInfo {
+        name: k.clone(),
+        current_version: ver.to_string(),
+        latest_version: fetch_latest_packagist(k),
+        update_command: format!("composer update {}", k),
+        url: format!("https://packagist.org/packages/{}", k),
+    })
 }
 
 // Ruby helpers
@@ -884,95 +2489,1445 @@ fn gemfile_path(dir: &Path) -> PathBuf {
     dir.join("Gemfile")
 }
 
-fn parse_gemfile(data: &str) -> BTreeMap<String, String> {
-    let mut map = BTreeMap::new();
-    let mut in_group = false;
-    for line in data.lines() {
-        let l = line.trim();
-        if l.starts_with("group") {
-            in_group = l.contains(":development") || l.contains(":test");
-            continue;
+/// A `gem` line of the Gemfile.
+struct Gem {
+    name: String,
+    /// Version requirements as written, without quotes (`~> 6.0`)
+    requirements: Vec<String>,
+    /// The requirement arguments (`'~> 6.0'`, `'>= 1', '< 2'`), or an empty
+    /// range after the name
+    requirements_range: Range<usize>,
+    /// Quote of the gem's strings
+    quote: char,
+    /// The `gem` call, without a trailing comment
+    range: Range<usize>,
+    /// In a `:development`/`:test` group (block or `group:` option)
+    development: bool,
+    /// The `group ... do ... end` block around it
+    block: Option<usize>,
+}
+
+/// Gemfile edited as text, so indentation and comments stay as written.
+struct Gemfile {
+    data: String,
+    gems: Vec<Gem>,
+    /// `group ... do ... end` blocks, from the header to the end of `end`
+    blocks: Vec<Range<usize>>,
+    /// The `group :development, :test do` block
+    development_block: Option<usize>,
+    edits: Vec<(Range<usize>, String)>,
+}
+
+/// Arguments of a Ruby call (`'rspec', '~> 6.0', require: false`), split on
+/// the commas outside strings, with their byte ranges in `args`.
+fn ruby_args(args: &str) -> Vec<(&str, Range<usize>)> {
+    let mut list = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, ',') => {
+                list.push(start..i);
+                start = i + 1;
+            }
+            // Comment after the call
+            (None, '#') => {
+                list.push(start..i);
+                start = args.len();
+                break;
+            }
+            _ => {}
         }
-        if l == "end" {
-            in_group = false;
-            continue;
+    }
+    if start < args.len() {
+        list.push(start..args.len());
+    }
+    list.into_iter()
+        .filter_map(|range| {
+            let arg = &args[range.clone()];
+            let trimmed = arg.trim();
+            if trimmed.is_empty() {
+                return None;
+            }
+            let start = range.start + (arg.len() - arg.trim_start().len());
+            Some((trimmed, start..start + trimmed.len()))
+        })
+        .collect()
+}
+
+/// A string literal's content.
+fn ruby_string(arg: &str) -> Option<&str> {
+    let quote = arg.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    arg.strip_prefix(quote)?.strip_suffix(quote)
+}
+
+fn development_group(groups: &str) -> bool {
+    groups.contains(":development") || groups.contains(":test")
+}
+
+impl Gemfile {
+    fn parse(data: String) -> Self {
+        let mut gems = Vec::new();
+        let mut blocks = Vec::new();
+        let mut development_block = None;
+        // Open `do` blocks: start, whether it is a development/test group
+        let mut open: Vec<(usize, bool, Option<usize>)> = Vec::new();
+        let mut offset = 0;
+        for line in data.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            let l = line.trim();
+            let in_development = open.iter().any(|(_, development, _)| *development);
+            if l == "end" {
+                if let Some((block_start, _, Some(index))) = open.pop() {
+                    blocks[index] = block_start..offset;
+                }
+                continue;
+            }
+            if l.ends_with(" do") || l.contains(" do |") {
+                let group = l.strip_prefix("group ").map(|groups| {
+                    let groups = groups.trim_end_matches(" do");
+                    (development_group(groups), groups.contains(":development") && groups.contains(":test"))
+                });
+                let index = group.map(|_| {
+                    blocks.push(start..offset);
+                    blocks.len() - 1
+                });
+                if let (Some((_, true)), None) = (group, development_block) {
+                    development_block = index;
+                }
+                open.push((start, group.is_some_and(|(development, _)| development), index));
+                continue;
+            }
+            let Some(args) = l.strip_prefix("gem ") else {
+                continue;
+            };
+            let args_start = start + offset_in(line, args);
+            let args = ruby_args(args);
+            let Some((name, quote)) = args.first().and_then(|(a, _)| Some((ruby_string(a)?, a.chars().next()?))) else {
+                continue;
+            };
+            let positional: Vec<&(&str, Range<usize>)> =
+                args[1..].iter().take_while(|(a, _)| ruby_string(a).is_some()).collect();
+            let options = &args[1 + positional.len()..];
+            let name_end = args_start + args[0].1.end;
+            let requirements_range = match (positional.first(), positional.last()) {
+                (Some((_, first)), Some((_, last))) => args_start + first.start..args_start + last.end,
+                _ => name_end..name_end,
+            };
+            let end = args.last().map_or(name_end, |(_, r)| args_start + r.end);
+            gems.push(Gem {
+                name: name.to_string(),
+                requirements: positional.iter().filter_map(|(a, _)| ruby_string(a)).map(str::to_string).collect(),
+                requirements_range,
+                quote,
+                range: offset_in(&data, l)..end,
+                development: in_development
+                    || options.iter().any(|(o, _)| {
+                        (o.starts_with("group:") || o.starts_with("groups:") || o.starts_with(":group"))
+                            && development_group(o)
+                    }),
+                block: open.iter().rev().find_map(|(_, _, index)| *index),
+            });
         }
-        if in_group && l.starts_with("gem ") {
-            let mut parts = l.splitn(2, ' ');
-            parts.next();
-            if let Some(rest) = parts.next() {
-                let rest = rest.trim();
-                let mut pieces = rest.split(',');
-                let name = pieces
-                    .next()
-                    .unwrap_or("")
-                    .trim()
-                    .trim_matches(|c| "\"'".contains(c))
-                    .to_string();
-                let version = pieces
-                    .next()
-                    .map(|v| v.trim().trim_matches(|c| "\"'".contains(c)).to_string())
-                    .unwrap_or_else(|| "*".into());
-                map.insert(name, version);
+        Gemfile { data, gems, blocks, development_block, edits: Vec::new() }
+    }
+
+    fn load(path: &Path) -> DxResult<Self> {
+        Ok(Self::parse(fs::read_to_string(path).context("Erro ao ler Gemfile")?))
+    }
+
+    fn find(&self, name: &str) -> Option<usize> {
+        self.gems.iter().position(|g| g.name == name && g.development)
+    }
+
+    fn development(&self) -> impl Iterator<Item = &Gem> {
+        self.gems.iter().filter(|g| g.development)
+    }
+
+    fn set_requirement(&mut self, index: usize, requirement: &str) {
+        let gem = &self.gems[index];
+        let q = gem.quote;
+        let text = if gem.requirements.is_empty() {
+            format!(", {q}{requirement}{q}")
+        } else {
+            format!("{q}{requirement}{q}")
+        };
+        self.edits.push((gem.requirements_range.clone(), text));
+    }
+
+    /// Add `gem 'name', 'requirement'` to the `group :development, :test`
+    /// block, creating it at the end of the file if missing.
+    fn insert(&mut self, name: &str, requirement: &str) {
+        let q = self.gems.first().map_or('\'', |g| g.quote);
+        let gem = format!("gem {q}{name}{q}, {q}{requirement}{q}");
+        let edit = match self.development_block.map(|i| self.blocks[i].clone()) {
+            Some(block) => {
+                let end = self.data[..block.end].trim_end().len() - "end".len();
+                let outer = line_indent(&self.data, end);
+                let indent = self
+                    .gems
+                    .iter()
+                    .rfind(|g| g.block == self.development_block)
+                    .map(|g| line_indent(&self.data, g.range.start).to_string())
+                    .unwrap_or_else(|| format!("{outer}  "));
+                let at = end - outer.len();
+                (at..at, format!("{indent}{gem}\n"))
+            }
+            None => {
+                let at = self.data.len();
+                let separator = if self.data.is_empty() || self.data.ends_with("\n\n") {
+                    ""
+                } else if self.data.ends_with('\n') {
+                    "\n"
+                } else {
+                    "\n\n"
+                };
+                (at..at, format!("{separator}group :development, :test do\n  {gem}\nend\n"))
+            }
+        };
+        self.edits.push(edit);
+    }
+
+    /// Remove the gem at `index` with its line, and its group if nothing
+    /// else is left in it.
+    fn remove(&mut self, index: usize) {
+        let gem = &self.gems[index];
+        // Along with its trailing comment
+        let line_end = self.data[gem.range.end..].find('\n').map_or(self.data.len(), |i| gem.range.end + i);
+        let line = whole_lines(&self.data, gem.range.start..line_end);
+        let block = gem.block.map(|i| self.blocks[i].clone()).filter(|block| {
+            let body = &self.data[block.clone()];
+            let inner = body.lines().count().saturating_sub(2);
+            body.lines().skip(1).take(inner).filter(|l| !l.trim().is_empty()).count() == 1
+        });
+        let range = match block {
+            Some(mut block) => {
+                // Along with the blank line that separated it
+                if self.data[..block.start].ends_with("\n\n") {
+                    block.start -= 1;
+                }
+                block
             }
+            None => line,
+        };
+        self.edits.push((range, String::new()));
+    }
+
+    fn save(mut self, path: &Path) -> DxResult {
+        self.edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+        for (range, text) in std::mem::take(&mut self.edits) {
+            self.data.replace_range(range, &text);
         }
+        fs::write(path, self.data).context("Erro ao salvar Gemfile")
     }
-    map
 }
 
-fn list_ruby(dir: &Path) {
+/// `~> major.minor` of `version`, as `bundle add` pins.
+fn pessimistic(version: &str) -> String {
+    let segments: Vec<&str> = version.split('.').take(2).collect();
+    format!("~> {}", segments.join("."))
+}
+
+/// `requirement` moved to `latest`, keeping its operator and precision
+/// (`~> 6.0` becomes `~> 7.1`, `= 1.2.3` becomes `= 7.1.3`).
+fn bump_requirement(requirement: &str, latest: &str) -> String {
+    let version_start = requirement.find(|c: char| c.is_ascii_digit()).unwrap_or(requirement.len());
+    let (operator, version) = requirement.split_at(version_start);
+    let precision = version.split('.').count();
+    let latest: Vec<&str> = latest.split('.').take(precision.max(1)).collect();
+    format!("{operator}{}", latest.join("."))
+}
+
+fn list_ruby(dir: &Path) -> Vec<DeclaredDependency> {
+    Gemfile::load(&gemfile_path(dir))
+        .map(|gemfile| {
+            gemfile
+                .development()
+                .map(|g| DeclaredDependency::from((g.name.clone(), g.requirements.first().cloned().unwrap_or_else(|| "*".into()))))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn add_ruby(dir: &Path, name: String, version: Option<String>) -> DxResult {
     let path = gemfile_path(dir);
-    if let Ok(data) = fs::read_to_string(&path) {
-        let map = parse_gemfile(&data);
-        if map.is_empty() {
-            println!("Nenhuma dependência encontrada.");
+    let mut gemfile = Gemfile::load(&path)?;
+    let requirement = match version.filter(|v| v != "latest") {
+        Some(v) => v,
+        None => pessimistic(&fetch_latest_ruby(&name).ok_or_else(|| {
+            DxError::Network(format!("Não foi possível obter a última versão de '{name}' no RubyGems."))
+        })?),
+    };
+    match gemfile.find(&name) {
+        Some(index) => gemfile.set_requirement(index, &requirement),
+        // Bundler refuses a gem declared twice
+        None if gemfile.gems.iter().any(|g| g.name == name) => {
+            return Err(DxError::InvalidInput(format!(
+                "'{name}' já está no Gemfile fora dos grupos :development/:test; mova-o para o grupo ou altere a versão lá."
+            )));
+        }
+        None => gemfile.insert(&name, &requirement),
+    }
+    gemfile.save(&path)?;
+    outln!("Dependência '{name}' adicionada ({requirement}).");
+    Ok(())
+}
+
+fn update_ruby(dir: &Path, name: Option<String>) -> DxResult {
+    let path = gemfile_path(dir);
+    let mut gemfile = Gemfile::load(&path)?;
+    let targets: Vec<usize> = match &name {
+        Some(n) => vec![gemfile.find(n).ok_or_else(|| not_found(n))?],
+        None => (0..gemfile.gems.len()).filter(|&i| gemfile.gems[i].development).collect(),
+    };
+    let names: Vec<String> = targets.iter().map(|&i| gemfile.gems[i].name.clone()).collect();
+    let latest = in_parallel(&names, |n| fetch_latest_ruby(n));
+    let mut updated = false;
+    for (&index, latest) in targets.iter().zip(latest) {
+        let gem = &gemfile.gems[index];
+        let requirement = match gem.requirements.as_slice() {
+            [requirement] => requirement.clone(),
+            // Unconstrained: `bundle update` already takes the latest
+            [] => continue,
+            _ => {
+                tracing::warn!(dependency = %gem.name, "faixa de versões composta; mantendo");
+                continue;
+            }
+        };
+        let Some(latest) = latest else {
+            tracing::warn!(dependency = %gem.name, "não foi possível obter a última versão; mantendo a atual");
+            continue;
+        };
+        gemfile.set_requirement(index, &bump_requirement(&requirement, &latest));
+        updated = true;
+    }
+    match &name {
+        Some(n) if updated => outln!("Dependência '{n}' atualizada."),
+        Some(_) => {}
+        None => outln!("Todas as dependências atualizadas."),
+    }
+    gemfile.save(&path)
+}
+
+fn delete_ruby(dir: &Path, name: String) -> DxResult {
+    let path = gemfile_path(dir);
+    let mut gemfile = Gemfile::load(&path)?;
+    let index = gemfile.find(&name).ok_or_else(|| not_found(&name))?;
+    gemfile.remove(index);
+    gemfile.save(&path)?;
+    outln!("Dependência '{name}' removida.");
+    Ok(())
+}
+
+fn fetch_latest_ruby(name: &str) -> Option<String> {
+    let url = format!("{}/api/v1/gems/{}.json", registry_base("https://rubygems.org", |m| &m.rubygems), name);
+    cached_latest(&url, || {
+        registry_json(&url)?
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    })
+}
+
+fn get_ruby_dependencies(dir: &Path) -> Vec<DependencyInfo> {
+    let declared: Vec<(String, String)> = list_ruby(dir).into_iter().map(|d| (d.name, d.version)).collect();
+    in_parallel(&declared, |(k, v)| DependencyInfo {
+        name: k.clone(),
+        current_version: v.clone(),
+        latest_version: fetch_latest_ruby(k),
+        update_command: format!("bundle update {}", k),
+        url: format!("https://rubygems.org/gems/{}", k),
+    })
+}
+
+// sbt helpers (listing only)
+fn build_sbt_path(dir: &Path) -> PathBuf {
+    dir.join("build.sbt")
+}
+
+/// A test-scoped `libraryDependencies` entry of build.sbt.
+struct SbtDependency {
+    group: String,
+    artifact: String,
+    /// `%%`: the artifact is published once per Scala binary version
+    cross: bool,
+    version: String,
+}
+
+impl SbtDependency {
+    /// Name in sbt/coursier notation (`org.scalatest::scalatest`).
+    fn name(&self) -> String {
+        let sep = if self.cross { "::" } else { ":" };
+        format!("{}{}{}", self.group, sep, self.artifact)
+    }
+
+    /// Artifact id on Maven Central (`scalatest_2.13`).
+    fn maven_artifact(&self, scala_binary: &str) -> String {
+        if self.cross {
+            format!("{}_{}", self.artifact, scala_binary)
         } else {
-            for (k, v) in map {
-                println!("- {} = {}", k, v);
+            self.artifact.clone()
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SbtToken<'a> {
+    Str(&'a str),
+    Op(&'a str),
+    Word(&'a str),
+}
+
+/// String literals, `%` operators and identifiers of a build.sbt line.
+fn sbt_tokens(line: &str) -> Vec<SbtToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '"' => match rest[1..].find('"') {
+                Some(end) => {
+                    tokens.push(SbtToken::Str(&rest[1..end + 1]));
+                    end + 2
+                }
+                None => break,
+            },
+            '%' => {
+                let len = rest.len() - rest.trim_start_matches('%').len();
+                tokens.push(SbtToken::Op(&rest[..len]));
+                len
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                    .unwrap_or(rest.len());
+                tokens.push(SbtToken::Word(&rest[..len]));
+                len
+            }
+            '/' if rest.starts_with("//") => break,
+            c => c.len_utf8(),
+        };
+        rest = &rest[len..];
+    }
+    tokens
+}
+
+/// Scala binary version of `scalaVersion` (`2.13`, `3`); sbt 1.x defaults to 2.12.
+fn scala_binary_version(data: &str) -> String {
+    data.lines()
+        .filter(|l| l.contains("scalaVersion") && l.contains(":="))
+        .find_map(quoted)
+        .map(|v| match v.split('.').collect::<Vec<_>>()[..] {
+            ["3", ..] => "3".to_string(),
+            [major, minor, ..] => format!("{major}.{minor}"),
+            _ => v.to_string(),
+        })
+        .unwrap_or_else(|| "2.12".to_string())
+}
+
+fn parse_sbt_deps(data: &str) -> Vec<SbtDependency> {
+    // `val scalatestVersion = "3.2.18"` may stand in for a literal
+    let vals: BTreeMap<&str, &str> = data
+        .lines()
+        .filter_map(|l| match sbt_tokens(l)[..] {
+            [SbtToken::Word("val"), SbtToken::Word(name), SbtToken::Str(value)]
+            | [SbtToken::Word("lazy"), SbtToken::Word("val"), SbtToken::Word(name), SbtToken::Str(value)] => {
+                Some((name, value))
+            }
+            _ => None,
+        })
+        .collect();
+    let resolve = |token: SbtToken| match token {
+        SbtToken::Str(s) => Some(s.to_string()),
+        SbtToken::Word(w) => vals.get(w).map(|s| s.to_string()),
+        SbtToken::Op(_) => None,
+    };
+
+    let mut deps = Vec::new();
+    for line in data.lines() {
+        let tokens = sbt_tokens(line);
+        for window in tokens.windows(7) {
+            let [group, SbtToken::Op(op), artifact, SbtToken::Op("%"), version, SbtToken::Op("%"), scope] =
+                *window
+            else {
+                continue;
+            };
+            let test = match scope {
+                SbtToken::Word(w) => w == "Test" || w == "IntegrationTest",
+                SbtToken::Str(s) => s.contains("test"),
+                SbtToken::Op(_) => false,
+            };
+            if !test || !matches!(op, "%" | "%%" | "%%%") {
+                continue;
+            }
+            if let (Some(group), Some(artifact), Some(version)) =
+                (resolve(group), resolve(artifact), resolve(version))
+            {
+                deps.push(SbtDependency {
+                    group,
+                    artifact,
+                    cross: op != "%",
+                    version,
+                });
             }
         }
-    } else {
-        println!("Nenhuma dependência encontrada.");
     }
+    deps
 }
 
-fn add_ruby(_dir: &Path, _name: String, _version: Option<String>) {
-    println!("Operação não suportada para Ruby.");
+fn list_sbt(dir: &Path) -> Vec<DeclaredDependency> {
+    fs::read_to_string(build_sbt_path(dir))
+        .map(|data| {
+            parse_sbt_deps(&data)
+                .into_iter()
+                .map(|dep| DeclaredDependency::from((dep.name(), dep.version)))
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
-fn update_ruby(_dir: &Path, _name: Option<String>) {
-    println!("Operação não suportada para Ruby.");
+fn get_sbt_dependencies(dir: &Path) -> Vec<DependencyInfo> {
+    let path = build_sbt_path(dir);
+    let Ok(data) = fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    let scala_binary = scala_binary_version(&data);
+    in_parallel(&parse_sbt_deps(&data), |dep| {
+        let artifact = dep.maven_artifact(&scala_binary);
+        DependencyInfo {
+            name: dep.name(),
+            current_version: dep.version.clone(),
+            latest_version: fetch_latest_maven(&dep.group, &artifact),
+            update_command: "sbt update".into(),
+            url: format!("https://search.maven.org/artifact/{}/{}", dep.group, artifact),
+        }
+    })
 }
 
-fn delete_ruby(_dir: &Path, _name: String) {
-    println!("Operação não suportada para Ruby.");
+// Swift Package Manager helpers (listing only)
+fn package_swift_path(dir: &Path) -> PathBuf {
+    dir.join("Package.swift")
 }
 
-fn fetch_latest_ruby(name: &str) -> Option<String> {
-    let url = format!("https://rubygems.org/api/v1/gems/{}.json", name);
-    reqwest::blocking::get(url)
-        .ok()?
-        .json::<Value>()
-        .ok()?
-        .get("version")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
+/// Remote packages of Package.swift: name, repository URL and version
+/// requirement (`1.2.0`, `1.0.0..<2.0.0`, a branch or revision).
+fn parse_package_swift(data: &str) -> Vec<(String, String, String)> {
+    let mut deps = Vec::new();
+    let mut rest = data;
+    while let Some(start) = rest.find(".package(") {
+        rest = &rest[start + ".package(".len()..];
+        let mut depth = 1;
+        let end = rest
+            .char_indices()
+            .find(|&(_, c)| {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0
+            })
+            .map_or(rest.len(), |(i, _)| i);
+        let args = &rest[..end];
+        rest = &rest[end..];
+
+        // Local packages (`path:`) have no version
+        let Some(url_at) = args.find("url:") else {
+            continue;
+        };
+        let Some(url) = quoted(&args[url_at..]) else {
+            continue;
+        };
+        let after = &args[url_at..];
+        let after = &after[after.find(url).unwrap_or(0) + url.len() + 1..];
+        let versions: Vec<&str> = after.split('"').skip(1).step_by(2).collect();
+        let requirement = match (versions.first(), versions.get(1)) {
+            (Some(low), Some(high)) if after.contains("..<") => format!("{low}..<{high}"),
+            (Some(low), Some(high)) if after.contains("...") => format!("{low}...{high}"),
+            (Some(version), _) => version.to_string(),
+            (None, _) => "*".to_string(),
+        };
+        let url = url.trim_end_matches('/').trim_end_matches(".git");
+        let name = url.rsplit('/').next().unwrap_or(url).to_string();
+        deps.push((name, url.to_string(), requirement));
+    }
+    deps
 }
 
-fn get_ruby_dependencies(dir: &Path) -> Vec<DependencyInfo> {
-    let path = gemfile_path(dir);
+fn list_swift(dir: &Path) -> Vec<DeclaredDependency> {
+    fs::read_to_string(package_swift_path(dir))
+        .map(|data| {
+            parse_package_swift(&data)
+                .into_iter()
+                .map(|(name, _, requirement)| DeclaredDependency::from((name, requirement)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Latest release of a package hosted on GitHub (SwiftPM has no central registry).
+fn fetch_latest_swift(url: &str) -> Option<String> {
+    let repo = url.strip_prefix("https://github.com/")?;
+    let url = format!("https://api.github.com/repos/{repo}/releases/latest");
+    cached_latest(&url, || {
+        registry_json(&url)?
+            .get("tag_name")
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim_start_matches('v').to_string())
+    })
+}
+
+fn get_swift_dependencies(dir: &Path) -> Vec<DependencyInfo> {
+    let path = package_swift_path(dir);
+    let declared = fs::read_to_string(&path)
+        .map(|data| parse_package_swift(&data))
+        .unwrap_or_default();
+    in_parallel(&declared, |(name, url, requirement)| DependencyInfo {
+        latest_version: fetch_latest_swift(url),
+        update_command: format!("swift package update {}", name),
+        name: name.clone(),
+        current_version: requirement.clone(),
+        url: url.clone(),
+    })
+}
+
+// C/C++ helpers: Conan and vcpkg manifests (listing only)
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CppManager {
+    Conan,
+    Vcpkg,
+}
+
+/// Conan reference `name/version[@user/channel][#revision]`; the version may
+/// be a range (`[>=1.2 <2]`).
+fn parse_conan_ref(reference: &str) -> Option<(String, String)> {
+    let (name, version) = reference.trim().split_once('/')?;
+    let version = version.split(['@', '#']).next().unwrap_or(version);
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_.+-".contains(c));
+    let valid_version = version.starts_with(|c: char| c.is_ascii_digit() || c == '[');
+    (valid_name && valid_version).then(|| (name.to_string(), version.to_string()))
+}
+
+/// `[requires]`, `[test_requires]` and `[tool_requires]` of conanfile.txt.
+fn parse_conanfile_txt(data: &str) -> Vec<(String, String)> {
     let mut deps = Vec::new();
-    if let Ok(data) = fs::read_to_string(&path) {
-        for (k, v) in parse_gemfile(&data) {
-            let latest = fetch_latest_ruby(&k);
-            deps.push(DependencyInfo {
-                name: k.clone(),
-                current_version: v.clone(),
-                latest_version: latest.clone(),
-                update_command: format!("bundle update {}", k),
-                url: format!("https://rubygems.org/gems/{}", k),
-            });
+    let mut in_requires = false;
+    for line in data.lines() {
+        let l = line.split('#').next().unwrap_or("").trim();
+        if l.starts_with('[') && l.ends_with(']') && !l.contains('/') {
+            in_requires = l.ends_with("requires]");
+            continue;
+        }
+        if in_requires && let Some(dep) = parse_conan_ref(l) {
+            deps.push(dep);
         }
     }
     deps
 }
+
+/// References quoted anywhere in conanfile.py (`requires = ...`,
+/// `self.requires("...")`, `self.test_requires("...")`).
+fn parse_conanfile_py(data: &str) -> Vec<(String, String)> {
+    data.lines()
+        .filter(|l| !l.trim_start().starts_with('#'))
+        .flat_map(|l| l.split(['"', '\'']).skip(1).step_by(2))
+        .filter_map(parse_conan_ref)
+        .collect()
+}
+
+/// `dependencies` of vcpkg.json, with the `version>=` constraint or the
+/// pinned `overrides` version.
+fn parse_vcpkg_json(data: &str) -> Vec<(String, String)> {
+    let Ok(manifest) = serde_json::from_str::<Value>(data) else {
+        return Vec::new();
+    };
+    let overrides: BTreeMap<&str, &str> = manifest["overrides"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|o| Some((o["name"].as_str()?, o["version"].as_str()?)))
+        .collect();
+    manifest["dependencies"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|dep| {
+            let name = dep.as_str().or_else(|| dep["name"].as_str())?;
+            let version = overrides
+                .get(name)
+                .map(|v| v.to_string())
+                .or_else(|| dep["version>="].as_str().map(|v| format!(">={v}")))
+                .unwrap_or_else(|| "*".into());
+            Some((name.to_string(), version))
+        })
+        .collect()
+}
+
+fn cpp_dependencies(dir: &Path) -> Vec<(CppManager, String, String)> {
+    let read = |file: &str| fs::read_to_string(dir.join(file)).ok();
+    let conan = read("conanfile.txt")
+        .map(|d| parse_conanfile_txt(&d))
+        .or_else(|| read("conanfile.py").map(|d| parse_conanfile_py(&d)))
+        .unwrap_or_default();
+    let vcpkg = read("vcpkg.json")
+        .map(|d| parse_vcpkg_json(&d))
+        .unwrap_or_default();
+    conan
+        .into_iter()
+        .map(|(n, v)| (CppManager::Conan, n, v))
+        .chain(vcpkg.into_iter().map(|(n, v)| (CppManager::Vcpkg, n, v)))
+        .collect()
+}
+
+fn list_cpp(dir: &Path) -> Vec<DeclaredDependency> {
+    cpp_dependencies(dir)
+        .into_iter()
+        .map(|(_, name, version)| DeclaredDependency::from((name, version)))
+        .collect()
+}
+
+/// Latest version of a ConanCenter recipe: the first entry of `versions` in
+/// its `config.yml` (newest first).
+fn fetch_latest_conan(name: &str) -> Option<String> {
+    let url = format!(
+        "https://raw.githubusercontent.com/conan-io/conan-center-index/master/recipes/{name}/config.yml"
+    );
+    cached_latest(&url, || {
+        let text = registry_get(&url)?;
+        text.lines()
+            .skip_while(|l| !l.starts_with("versions:"))
+            .skip(1)
+            .find_map(|l| l.trim().strip_suffix(':'))
+            .map(|v| v.trim_matches(['"', '\'']).to_string())
+    })
+}
+
+/// Latest version of a vcpkg port, from its manifest in the curated registry.
+fn fetch_latest_vcpkg(name: &str) -> Option<String> {
+    let url = format!("https://raw.githubusercontent.com/microsoft/vcpkg/master/ports/{name}/vcpkg.json");
+    cached_latest(&url, || {
+        let port = registry_json(&url)?;
+        ["version", "version-semver", "version-date", "version-string"]
+            .iter()
+            .find_map(|k| port.get(*k).and_then(|v| v.as_str()))
+            .map(|s| s.to_string())
+    })
+}
+
+fn get_cpp_dependencies(dir: &Path) -> Vec<DependencyInfo> {
+    in_parallel(&cpp_dependencies(dir), |(manager, name, version)| match manager {
+        CppManager::Conan => DependencyInfo {
+            latest_version: fetch_latest_conan(name),
+            update_command: "conan install . --update".into(),
+            url: format!("https://conan.io/center/recipes/{}", name),
+            name: name.clone(),
+            current_version: version.clone(),
+        },
+        CppManager::Vcpkg => DependencyInfo {
+            latest_version: fetch_latest_vcpkg(name),
+            update_command: "vcpkg x-update-baseline".into(),
+            url: format!("https://vcpkg.io/en/package/{}", name),
+            name: name.clone(),
+            current_version: version.clone(),
+        },
+    })
+}
+
+// Vulnerability audit: declared versions checked against OSV.dev
+/// OSV ecosystem of the stack's packages; `None` when OSV.dev doesn't cover it.
+fn osv_ecosystem(stack: Stack) -> Option<&'static str> {
+    match stack {
+        Stack::Node => Some("npm"),
+        Stack::Rust => Some("crates.io"),
+        Stack::Python => Some("PyPI"),
+        Stack::Go => Some("Go"),
+        Stack::Maven | Stack::Gradle | Stack::Sbt => Some("Maven"),
+        Stack::Php => Some("Packagist"),
+        Stack::Ruby => Some("RubyGems"),
+        Stack::Swift | Stack::Cpp | Stack::Unknown => None,
+    }
+}
+
+/// Declared dev dependencies with their name in OSV (sbt's cross-built
+/// artifacts carry the Scala version there).
+fn audit_packages(dir: &Path, stack: Stack) -> Vec<(DeclaredDependency, String)> {
+    if stack == Stack::Sbt {
+        let data = fs::read_to_string(build_sbt_path(dir)).unwrap_or_default();
+        let scala_binary = scala_binary_version(&data);
+        return parse_sbt_deps(&data)
+            .into_iter()
+            .map(|dep| {
+                let osv_name = format!("{}:{}", dep.group, dep.maven_artifact(&scala_binary));
+                (DeclaredDependency::from((dep.name(), dep.version)), osv_name)
+            })
+            .collect();
+    }
+    let declared = match stack {
+        Stack::Node => list_node(dir),
+        Stack::Rust => list_rust(dir),
+        Stack::Python => list_python(dir),
+        Stack::Go => list_go(dir),
+        Stack::Maven => list_maven(dir),
+        Stack::Gradle => list_gradle(dir),
+        Stack::Php => list_php(dir),
+        Stack::Ruby => list_ruby(dir),
+        _ => Vec::new(),
+    };
+    declared.into_iter().map(|d| (d.clone(), d.name)).collect()
+}
+
+/// Version a declared spec starts from (`^8.0` is checked as `8.0.0`,
+/// `~> 6.0` as `6.0`, `>=7,<8` as `7`); `None` for `*`, wildcards, tags, URLs
+/// and unresolved properties.
+fn audit_version(spec: &str, ecosystem: &str) -> Option<String> {
+    let spec = spec.trim().trim_start_matches(|c: char| "^~=<>! ".contains(c));
+    let spec = spec
+        .strip_prefix('v')
+        .filter(|s| s.starts_with(|c: char| c.is_ascii_digit()))
+        .unwrap_or(spec);
+    let end = spec
+        .find(|c: char| !(c.is_ascii_alphanumeric() || ".-+_".contains(c)))
+        .unwrap_or(spec.len());
+    let version = &spec[..end];
+    let release = version.split(['-', '+']).next().unwrap_or_default();
+    if !version.starts_with(|c: char| c.is_ascii_digit())
+        || release.split('.').any(|part| part.is_empty() || part.eq_ignore_ascii_case("x"))
+    {
+        return None;
+    }
+    let mut version = version.to_string();
+    // Semver ecosystems compare complete versions
+    if matches!(ecosystem, "npm" | "crates.io" | "Go") && version == release {
+        while version.matches('.').count() < 2 {
+            version.push_str(".0");
+        }
+    }
+    Some(version)
+}
+
+/// A dev dependency with known vulnerabilities.
+#[derive(serde::Serialize)]
+pub struct VulnerableDependency {
+    pub name: String,
+    pub version: String,
+    /// Most severe first
+    pub advisories: Vec<crate::osv::Advisory>,
+    /// Moves to the first version fixing all of them; `None` when one of
+    /// them has no fix yet
+    pub update_command: Option<String>,
+}
+
+/// Result of `dx dev-dependencies audit`.
+#[derive(serde::Serialize)]
+pub struct AuditReport {
+    pub stack: &'static str,
+    pub ecosystem: &'static str,
+    /// Dependencies checked against OSV.dev
+    pub checked: usize,
+    pub vulnerable: Vec<VulnerableDependency>,
+    /// Declared without a version to check (`*`, tags, URLs)
+    pub unchecked: Vec<DeclaredDependency>,
+}
+
+impl AuditReport {
+    fn count(&self, severity: crate::osv::Severity) -> usize {
+        self.vulnerable
+            .iter()
+            .flat_map(|d| &d.advisories)
+            .filter(|a| a.severity == severity)
+            .count()
+    }
+}
+
+impl Render for AuditReport {
+    fn render_text(&self) {
+        use crate::osv::Severity;
+        for dep in &self.vulnerable {
+            outln!("{} {}", dep.name, dep.version);
+            for advisory in &dep.advisories {
+                let aliases = if advisory.aliases.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", advisory.aliases.join(", "))
+                };
+                outln!(
+                    "  [{}] {}{aliases}: {}",
+                    advisory.severity.label().to_uppercase(),
+                    advisory.id,
+                    advisory.summary
+                );
+                match &advisory.fixed {
+                    Some(fixed) => outln!("      corrigida em {fixed}"),
+                    None => outln!("      sem versão corrigida publicada"),
+                }
+            }
+            if let Some(command) = &dep.update_command {
+                outln!("  Atualize com: {command}");
+            }
+        }
+        if !self.unchecked.is_empty() {
+            let names: Vec<String> = self.unchecked.iter().map(|d| format!("{} ({})", d.name, d.version)).collect();
+            outln!("Sem versão exata, não verificadas: {}", names.join(", "));
+        }
+        let total: usize = self.vulnerable.iter().map(|d| d.advisories.len()).sum();
+        if total == 0 {
+            outln!(
+                "Nenhuma vulnerabilidade conhecida em {} dependência(s) ({} no OSV.dev).",
+                self.checked,
+                self.ecosystem
+            );
+            return;
+        }
+        let counts: Vec<String> = [Severity::Critical, Severity::High, Severity::Moderate, Severity::Low, Severity::Unknown]
+            .into_iter()
+            .map(|s| (s, self.count(s)))
+            .filter(|(_, n)| *n > 0)
+            .map(|(s, n)| format!("{n} {}", s.label()))
+            .collect();
+        outln!(
+            "{total} vulnerabilidade(s) em {} de {} dependência(s): {}.",
+            self.vulnerable.len(),
+            self.checked,
+            counts.join(", ")
+        );
+    }
+}
+
+/// `dx dev-dependencies audit`: look the declared dev dependencies up in
+/// OSV.dev; fails when any has a critical vulnerability.
+pub fn audit(dir: Option<PathBuf>) -> DxResult {
+    if OFFLINE.load(Ordering::Relaxed) {
+        return Err(DxError::InvalidInput(
+            "dev-dependencies audit consulta o OSV.dev; não pode ser usado com --offline.".to_string(),
+        ));
+    }
+    let project_dir = project_dir(dir);
+    let stack = Stack::detect(&project_dir);
+    let ecosystem = match (stack, osv_ecosystem(stack)) {
+        (_, Some(ecosystem)) => ecosystem,
+        (Stack::Swift, None) => return Err(unsupported("Swift")),
+        (Stack::Cpp, None) => return Err(unsupported("C/C++")),
+        _ => return Err(unknown_stack()),
+    };
+    use_settings(&project_dir);
+    let base = registry_base(crate::osv::DEFAULT_URL, |m| &m.osv);
+
+    let mut checked = Vec::new();
+    let mut unchecked = Vec::new();
+    for (dep, osv_name) in audit_packages(&project_dir, stack) {
+        match audit_version(&dep.version, ecosystem) {
+            Some(version) => checked.push((dep, osv_name, version)),
+            None => unchecked.push(dep),
+        }
+    }
+    let queries: Vec<crate::osv::Query> = checked
+        .iter()
+        .map(|(_, name, version)| crate::osv::Query { ecosystem, name, version })
+        .collect();
+    let vulnerable = crate::progress::phase("Consultando OSV.dev", || -> DxResult<Vec<VulnerableDependency>> {
+        if queries.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids = crate::osv::query_batch(&base, &queries)?;
+        let mut unique: Vec<&String> = ids.iter().flatten().collect();
+        unique.sort();
+        unique.dedup();
+        let records: BTreeMap<&String, Value> = unique
+            .iter()
+            .copied()
+            .zip(in_parallel(&unique, |id| crate::osv::vulnerability(&base, id)))
+            .map(|(id, record)| Ok((id, record?)))
+            .collect::<DxResult<_>>()?;
+        Ok(checked
+            .iter()
+            .zip(&queries)
+            .zip(&ids)
+            .filter(|(_, ids)| !ids.is_empty())
+            .map(|(((dep, _, _), query), ids)| {
+                let mut advisories: Vec<crate::osv::Advisory> =
+                    ids.iter().map(|id| crate::osv::Advisory::parse(&records[id], query)).collect();
+                advisories.sort_by(|a, b| b.severity.cmp(&a.severity).then_with(|| a.id.cmp(&b.id)));
+                let fixed: Option<Vec<&String>> = advisories.iter().map(|a| a.fixed.as_ref()).collect();
+                let update_command = fixed
+                    .and_then(|fixed| fixed.into_iter().max_by(|a, b| crate::osv::compare_versions(a, b)))
+                    .map(|version| format!("dx dev-dependencies add {} {version}", dep.name));
+                VulnerableDependency {
+                    name: dep.name.clone(),
+                    version: dep.version.clone(),
+                    advisories,
+                    update_command,
+                }
+            })
+            .collect())
+    })?;
+
+    let report = AuditReport { stack: stack.id(), ecosystem, checked: checked.len(), vulnerable, unchecked };
+    crate::output::render(&report);
+    let critical = report.count(crate::osv::Severity::Critical);
+    if critical > 0 {
+        return Err(DxError::CheckFailed(format!(
+            "{critical} vulnerabilidade(s) crítica(s) nas dependências de desenvolvimento.\nDica: atualize com os comandos sugeridos acima."
+        )));
+    }
+    Ok(())
+}
+
+// License report: license metadata of each dev dependency from its registry
+/// dev-config key with the allowed licenses (comma-separated SPDX ids).
+const LICENSE_ALLOW_KEY: &str = "licenses.allow";
+
+/// SPDX ids of common license names (`MIT License`, `Apache 2.0`,
+/// `GNU General Public License v3`...), matched on their words.
+fn spdx_license(name: &str) -> String {
+    let name = name.trim();
+    if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || ".+-".contains(c)) {
+        return name.to_string();
+    }
+    let lower = name.to_ascii_lowercase();
+    let words: Vec<&str> = lower.split(|c: char| !c.is_ascii_alphanumeric() && c != '.').filter(|w| !w.is_empty()).collect();
+    let has = |w: &str| words.contains(&w);
+    let version = |v: &str| words.iter().any(|w| w.trim_start_matches('v').starts_with(v));
+    let id = if has("mit") || has("expat") {
+        "MIT"
+    } else if has("apache") && !version("1") {
+        "Apache-2.0"
+    } else if has("isc") {
+        "ISC"
+    } else if has("bsd") && (version("2") || has("simplified")) {
+        "BSD-2-Clause"
+    } else if has("bsd") {
+        "BSD-3-Clause"
+    } else if (has("mpl") || has("mozilla")) && version("2") {
+        "MPL-2.0"
+    } else if has("agpl") || has("affero") {
+        "AGPL-3.0"
+    } else if has("lgpl") || has("lesser") {
+        if version("2") { "LGPL-2.1" } else { "LGPL-3.0" }
+    } else if has("gpl") || (has("general") && has("public")) {
+        if version("2") { "GPL-2.0" } else { "GPL-3.0" }
+    } else if (has("epl") || has("eclipse")) && version("1") {
+        "EPL-1.0"
+    } else if has("epl") || has("eclipse") {
+        "EPL-2.0"
+    } else if has("unlicense") {
+        "Unlicense"
+    } else if has("cc0") {
+        "CC0-1.0"
+    } else {
+        return name.to_string();
+    };
+    id.to_string()
+}
+
+/// A license expression with each license as an SPDX id; several licenses
+/// (`["MIT", "Apache 2.0"]`) are alternatives.
+fn license_expression(licenses: &[&str]) -> Option<String> {
+    let parts: Vec<String> = licenses
+        .iter()
+        .flat_map(|l| l.split(" OR ").flat_map(|l| l.split(" or ")))
+        .map(|l| l.trim().trim_start_matches('(').trim_end_matches(')'))
+        .filter(|l| !l.is_empty() && !l.eq_ignore_ascii_case("UNKNOWN") && !l.eq_ignore_ascii_case("NOASSERTION"))
+        .map(|l| l.split(" AND ").map(spdx_license).collect::<Vec<_>>().join(" AND "))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" OR "))
+}
+
+/// Whether `expression` allows a use under the `allow` list: one of its
+/// alternatives has all its licenses allowed.
+fn license_allowed(expression: &str, allow: &[String]) -> bool {
+    expression.split(" OR ").any(|alternative| {
+        alternative.split(" AND ").all(|license| {
+            // `GPL-2.0 WITH Classpath-exception-2.0` is GPL-2.0 with an extra permission
+            let license = license.split(" WITH ").next().unwrap_or_default().trim();
+            allow.iter().any(|a| a.eq_ignore_ascii_case(license))
+        })
+    })
+}
+
+fn json_strings(value: Option<&Value>) -> Vec<&str> {
+    match value {
+        Some(Value::String(s)) => vec![s.as_str()],
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|i| i.as_str().or_else(|| i.get("type")?.as_str()))
+            .collect(),
+        Some(Value::Object(o)) => o.get("type").and_then(|t| t.as_str()).into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn fetch_license_node(name: &str, version: Option<&str>) -> Option<String> {
+    let base = registry_base("https://registry.npmjs.org", |m| &m.npm);
+    let v = registry_json(&format!("{base}/{name}/{}", version.unwrap_or("latest")))?;
+    license_expression(&json_strings(v.get("license").or_else(|| v.get("licenses"))))
+}
+
+fn fetch_license_crate(name: &str, version: Option<&str>) -> Option<String> {
+    let base = format!("{}/api/v1/crates/{name}", registry_base("https://crates.io", |m| &m.crates));
+    let license = match version {
+        Some(version) => registry_json(&format!("{base}/{version}"))?.get("version")?.get("license")?.as_str()?.to_string(),
+        None => registry_json(&base)?.get("versions")?.get(0)?.get("license")?.as_str()?.to_string(),
+    };
+    // Cargo's old `MIT/Apache-2.0` means either
+    license_expression(&[&license.replace('/', " OR ")])
+}
+
+fn fetch_license_pypi(name: &str, version: Option<&str>) -> Option<String> {
+    let base = registry_base("https://pypi.org", |m| &m.pypi);
+    let url = match version {
+        Some(version) => format!("{base}/pypi/{name}/{version}/json"),
+        None => format!("{base}/pypi/{name}/json"),
+    };
+    let v = registry_json(&url)?;
+    let info = v.get("info")?;
+    let field = |key: &str| info.get(key).and_then(|l| l.as_str()).map(str::trim).filter(|l| !l.is_empty());
+    if let Some(expression) = field("license_expression") {
+        return license_expression(&[expression]);
+    }
+    // `license` sometimes holds the whole license text; the classifiers name it
+    let classifiers: Vec<&str> = json_strings(info.get("classifiers"))
+        .into_iter()
+        .filter(|c| c.starts_with("License ::"))
+        .filter_map(|c| c.rsplit(" :: ").next())
+        .filter(|c| *c != "OSI Approved")
+        .collect();
+    match field("license") {
+        Some(license) if !license.contains('\n') && license.len() <= 60 => license_expression(&[license]),
+        _ => license_expression(&classifiers),
+    }
+}
+
+fn fetch_license_maven(group: &str, artifact: &str, version: Option<&str>) -> Option<String> {
+    let version = match version {
+        Some(version) => version.to_string(),
+        None => fetch_latest_maven(group, artifact)?,
+    };
+    let url = format!(
+        "{}/{}/{artifact}/{version}/{artifact}-{version}.pom",
+        registry_base("https://repo1.maven.org/maven2", |m| &m.maven),
+        group.replace('.', "/"),
+    );
+    let text = registry_get(&url)?;
+    let doc = roxmltree::Document::parse(&text).ok()?;
+    let names: Vec<&str> = doc
+        .descendants()
+        .filter(|n| n.is_element() && n.tag_name().name() == "license")
+        .filter_map(|n| xml_child(n, "name")?.text())
+        .collect();
+    license_expression(&names)
+}
+
+fn fetch_license_packagist(name: &str, version: Option<&str>) -> Option<String> {
+    let url = format!("{}/p2/{}.json", registry_base("https://repo.packagist.org", |m| &m.packagist), name);
+    let v = registry_json(&url)?;
+    let releases = v.get("packages")?.get(name)?.as_array()?;
+    // Minified metadata: a release only lists the fields that changed from the previous one
+    let mut license = None;
+    for release in releases {
+        if let Some(l) = release.get("license") {
+            license = Some(l);
+        }
+        let number = release.get("version").and_then(|v| v.as_str()).unwrap_or_default();
+        if version.is_none_or(|v| number.trim_start_matches('v') == v) {
+            break;
+        }
+    }
+    license_expression(&json_strings(license))
+}
+
+fn fetch_license_ruby(name: &str, version: Option<&str>) -> Option<String> {
+    let base = registry_base("https://rubygems.org", |m| &m.rubygems);
+    let url = match version {
+        Some(version) => format!("{base}/api/v2/rubygems/{name}/versions/{version}.json"),
+        None => format!("{base}/api/v1/gems/{name}.json"),
+    };
+    license_expression(&json_strings(registry_json(&url)?.get("licenses")))
+}
+
+/// License of `name` (its name in the registry) at `version`, or of its
+/// latest version; `None` when the registry doesn't tell (Go modules).
+fn fetch_license(stack: Stack, name: &str, version: Option<&str>) -> Option<String> {
+    match stack {
+        Stack::Node => fetch_license_node(name, version),
+        Stack::Rust => fetch_license_crate(name, version),
+        Stack::Python => fetch_license_pypi(name, version),
+        Stack::Maven | Stack::Gradle | Stack::Sbt => {
+            let (group, artifact) = name.split_once(':')?;
+            fetch_license_maven(group, artifact, version)
+        }
+        Stack::Php => fetch_license_packagist(name, version),
+        Stack::Ruby => fetch_license_ruby(name, version),
+        _ => None,
+    }
+}
+
+/// Dependencies under one license.
+#[derive(serde::Serialize)]
+pub struct LicenseGroup {
+    /// SPDX expression; `None`: unknown
+    pub license: Option<String>,
+    pub dependencies: Vec<DeclaredDependency>,
+}
+
+/// A dependency whose license isn't in the allowlist.
+#[derive(serde::Serialize)]
+pub struct LicenseViolation {
+    pub name: String,
+    pub version: String,
+    pub license: String,
+}
+
+/// Result of `dx dev-dependencies licenses`.
+#[derive(serde::Serialize)]
+pub struct LicenseReport {
+    pub stack: &'static str,
+    /// `licenses.allow` of the dev-config; `None` when not configured
+    pub allow: Option<Vec<String>>,
+    /// Largest groups first; unknown licenses last
+    pub licenses: Vec<LicenseGroup>,
+    pub violations: Vec<LicenseViolation>,
+}
+
+impl Render for LicenseReport {
+    fn render_text(&self) {
+        if self.licenses.is_empty() {
+            outln!("Nenhuma dependência encontrada.");
+            return;
+        }
+        for group in &self.licenses {
+            outln!(
+                "{} ({})",
+                group.license.as_deref().unwrap_or("Desconhecida"),
+                group.dependencies.len()
+            );
+            for dep in &group.dependencies {
+                outln!("  - {} {}", dep.name, dep.version);
+            }
+        }
+        match &self.allow {
+            None => outln!(
+                "Defina as licenças permitidas para verificá-las: dx dev-config add {LICENSE_ALLOW_KEY} \"MIT, Apache-2.0, BSD-3-Clause\""
+            ),
+            Some(_) if self.violations.is_empty() => outln!("Todas as licenças conhecidas estão em {LICENSE_ALLOW_KEY}."),
+            Some(_) => {
+                outln!("Fora de {LICENSE_ALLOW_KEY}:");
+                for violation in &self.violations {
+                    outln!("  - {} {} ({})", violation.name, violation.version, violation.license);
+                }
+            }
+        }
+    }
+}
+
+/// `dx dev-dependencies licenses`: group the dev dependencies by the license
+/// their registry declares; fails when one isn't in `licenses.allow`.
+pub fn licenses(dir: Option<PathBuf>) -> DxResult {
+    if OFFLINE.load(Ordering::Relaxed) {
+        return Err(DxError::InvalidInput(
+            "dev-dependencies licenses consulta os registries; não pode ser usado com --offline.".to_string(),
+        ));
+    }
+    let project_dir = project_dir(dir);
+    let stack = Stack::detect(&project_dir);
+    match stack {
+        Stack::Swift => return Err(unsupported("Swift")),
+        Stack::Cpp => return Err(unsupported("C/C++")),
+        Stack::Unknown => return Err(unknown_stack()),
+        _ => {}
+    }
+    use_settings(&project_dir);
+    let allow: Option<Vec<String>> = crate::dev_config::entries(&project_dir).get(LICENSE_ALLOW_KEY).map(|list| {
+        list.split(',').map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect()
+    });
+
+    let packages = audit_packages(&project_dir, stack);
+    let ecosystem = osv_ecosystem(stack).unwrap_or_default();
+    let found = crate::progress::phase(REGISTRY_PHASE, || {
+        in_parallel(&packages, |(dep, name)| {
+            fetch_license(stack, name, audit_version(&dep.version, ecosystem).as_deref())
+        })
+    });
+
+    let mut groups: BTreeMap<Option<String>, Vec<DeclaredDependency>> = BTreeMap::new();
+    let mut violations = Vec::new();
+    for ((dep, _), license) in packages.into_iter().zip(found) {
+        if let (Some(allow), Some(license)) = (&allow, &license)
+            && !license_allowed(license, allow)
+        {
+            violations.push(LicenseViolation { name: dep.name.clone(), version: dep.version.clone(), license: license.clone() });
+        }
+        groups.entry(license).or_default().push(dep);
+    }
+    let mut licenses: Vec<LicenseGroup> =
+        groups.into_iter().map(|(license, dependencies)| LicenseGroup { license, dependencies }).collect();
+    licenses.sort_by_key(|g| (g.license.is_none(), std::cmp::Reverse(g.dependencies.len())));
+
+    let report = LicenseReport { stack: stack.id(), allow, licenses, violations };
+    crate::output::render(&report);
+    if report.violations.is_empty() {
+        return Ok(());
+    }
+    let names: Vec<&str> = report.violations.iter().map(|v| v.name.as_str()).collect();
+    Err(DxError::CheckFailed(format!(
+        "{} dependência(s) com licença fora de {LICENSE_ALLOW_KEY}: {}.",
+        names.len(),
+        names.join(", ")
+    )))
+}
+
+// Outdated report: declared vs latest version, classified by the semver jump
+/// Size of the jump to the latest version. Under `0.x` a new minor is a
+/// major, as semver makes no promise before 1.0 (Cargo and npm's `^` agree).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    pub fn label(self) -> &'static str {
+        match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        }
+    }
+}
+
+/// Jump from `current` to `latest`; `None` when `latest` isn't newer.
+fn bump(current: &str, latest: &str) -> Option<Bump> {
+    if crate::osv::compare_versions(latest, current) != std::cmp::Ordering::Greater {
+        return None;
+    }
+    let parts = |v: &str| -> Vec<u64> {
+        let release = v.trim_start_matches('v').split(['-', '+']).next().unwrap_or_default();
+        let mut parts: Vec<u64> = release.split('.').map(|p| p.parse().unwrap_or(0)).collect();
+        parts.resize(3, 0);
+        parts
+    };
+    let (current, latest) = (parts(current), parts(latest));
+    Some(if current[0] != latest[0] || (current[0] == 0 && current[1] != latest[1]) {
+        Bump::Major
+    } else if current[1] != latest[1] {
+        Bump::Minor
+    } else {
+        Bump::Patch
+    })
+}
+
+/// A dev dependency with a newer version published.
+#[derive(serde::Serialize)]
+pub struct OutdatedDependency {
+    pub name: String,
+    /// As declared
+    pub current: String,
+    pub latest: String,
+    pub bump: Bump,
+    pub update_command: String,
+}
+
+/// Result of `dx dev-dependencies outdated`.
+#[derive(serde::Serialize)]
+pub struct OutdatedReport {
+    pub stack: &'static str,
+    /// Largest jump first
+    pub outdated: Vec<OutdatedDependency>,
+    /// Without an exact version or a known latest version
+    pub unchecked: Vec<DeclaredDependency>,
+}
+
+impl OutdatedReport {
+    fn count(&self, bump: Bump) -> usize {
+        self.outdated.iter().filter(|d| d.bump == bump).count()
+    }
+}
+
+impl Render for OutdatedReport {
+    fn render_text(&self) {
+        use std::io::IsTerminal;
+        let colors = std::io::stdout().is_terminal()
+            && std::env::var_os("NO_COLOR").is_none()
+            && !crate::ci::is_ci()
+            && !crate::output::is_json();
+        let width = self.outdated.iter().map(|d| d.name.len()).max().unwrap_or(0);
+        for dep in &self.outdated {
+            let bump = match dep.bump {
+                Bump::Major if colors => format!("\x1b[1;31m{}\x1b[0m", dep.bump.label()),
+                _ => dep.bump.label().to_string(),
+            };
+            outln!("{:<width$}  {} -> {} ({bump})", dep.name, dep.current, dep.latest);
+        }
+        if !self.unchecked.is_empty() {
+            let names: Vec<String> = self.unchecked.iter().map(|d| format!("{} ({})", d.name, d.version)).collect();
+            outln!("Sem versão exata ou última versão conhecida, não verificadas: {}", names.join(", "));
+        }
+        if self.outdated.is_empty() {
+            outln!("Todas as dependências de desenvolvimento estão na última versão.");
+            return;
+        }
+        outln!(
+            "{} desatualizada(s): {} major, {} minor, {} patch. Atualize com: dx dev-dependencies update [<nome>]",
+            self.outdated.len(),
+            self.count(Bump::Major),
+            self.count(Bump::Minor),
+            self.count(Bump::Patch)
+        );
+    }
+}
+
+/// `dx dev-dependencies outdated`: the dev dependencies behind their latest
+/// version; with `fail_on`, fails when a jump is at least that large.
+pub fn outdated(dir: Option<PathBuf>, fail_on: Option<Bump>) -> DxResult {
+    let project_dir = project_dir(dir);
+    let stack = Stack::detect(&project_dir);
+    if stack == Stack::Unknown {
+        return Err(unknown_stack());
+    }
+    let ecosystem = osv_ecosystem(stack).unwrap_or_default();
+    let dependencies = get_dependencies(&project_dir).context("Erro ao ler as dependências")?;
+    let mut outdated = Vec::new();
+    let mut unchecked = Vec::new();
+    for dep in dependencies {
+        let current = audit_version(&dep.current_version, ecosystem);
+        match (current, &dep.latest_version) {
+            (Some(current), Some(latest)) => {
+                if let Some(bump) = bump(&current, latest) {
+                    outdated.push(OutdatedDependency {
+                        name: dep.name,
+                        current: dep.current_version,
+                        latest: latest.clone(),
+                        bump,
+                        update_command: dep.update_command,
+                    });
+                }
+            }
+            _ => unchecked.push(DeclaredDependency { name: dep.name, version: dep.current_version }),
+        }
+    }
+    outdated.sort_by(|a, b| b.bump.cmp(&a.bump).then_with(|| a.name.cmp(&b.name)));
+
+    let report = OutdatedReport { stack: stack.id(), outdated, unchecked };
+    crate::output::render(&report);
+    let Some(fail_on) = fail_on else {
+        return Ok(());
+    };
+    let failing: Vec<&str> = report.outdated.iter().filter(|d| d.bump >= fail_on).map(|d| d.name.as_str()).collect();
+    if failing.is_empty() {
+        return Ok(());
+    }
+    Err(DxError::CheckFailed(format!(
+        "{} dependência(s) com atualização {} ou maior (--fail-on {}): {}.",
+        failing.len(),
+        fail_on.label(),
+        fail_on.label(),
+        failing.join(", ")
+    )))
+}
@@ -1,539 +1,791 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright (c) 2025 The dx-cli Contributors
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
+use ignore::WalkState;
+
+use crate::compose::{ComposeFile, DependsOn, Healthcheck};
+use crate::detectors::Keyword;
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
 pub struct DockerService {
     pub image: String,
     pub env: HashMap<String, String>,
     pub ports: Vec<u16>,
     pub volumes: Vec<String>,
     pub command: Option<String>,
+    /// Compose profiles the service belongs to (`db`, `messaging`, `observability`...)
+    #[serde(default)]
+    pub profiles: Vec<String>,
+    /// Services started before this one, with the condition to wait for
+    #[serde(default)]
+    pub depends_on: DependsOn,
+    #[serde(default)]
+    pub healthcheck: Option<Healthcheck>,
+    /// Networks the service joins (the default network when empty)
+    #[serde(default)]
+    pub networks: Vec<String>,
+    /// Restart policy (`unless-stopped`, `on-failure`...)
+    #[serde(default)]
+    pub restart: Option<String>,
+    /// Container labels (the proxy's routes)
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// `host:ip` entries added to the container's `/etc/hosts`
+    #[serde(default)]
+    pub extra_hosts: Vec<String>,
+    /// Host ports published for container ports taken on this machine
+    /// (container -> host), see [`crate::ports`]
+    #[serde(default)]
+    pub host_ports: BTreeMap<u16, u16>,
+}
+
+impl DockerService {
+    /// Host port publishing `container`.
+    pub fn host_port(&self, container: u16) -> u16 {
+        self.host_ports.get(&container).copied().unwrap_or(container)
+    }
 }
 
-#[derive(Default)]
+#[derive(Default, serde::Serialize, serde::Deserialize)]
 pub struct DockerComposeConfig {
     pub version: String,
     pub services: HashMap<String, DockerService>,
+    /// Managed resources from the project's IaC that the services stand in for
+    pub iac: Vec<crate::iac::Resource>,
+    /// Framework preset applied to the database services
+    pub preset: Option<crate::presets::Preset>,
+    /// Background job frameworks and the services their jobs go through
+    #[serde(default)]
+    pub jobs: Vec<crate::jobs::Job>,
+    /// `.dx/docker-compose.override.yml`, merged into the manifest (read on
+    /// every render, never cached)
+    #[serde(skip)]
+    pub overrides: Option<ComposeFile>,
+    /// Detected services left out by `[dev_services] exclude` or `--without`
+    #[serde(skip)]
+    pub excluded: Vec<String>,
 }
 
-impl DockerComposeConfig {
-    pub fn new() -> Self {
-        DockerComposeConfig {
-            version: "3.8".to_string(),
-            services: HashMap::new(),
-        }
-    }
-
-    pub fn add_service(&mut self, name: &str, service: DockerService) {
-        self.services.insert(name.to_string(), service);
-    }
-
-    pub fn to_yaml(&self) -> String {
-        let mut yaml = format!("version: '{}'\nservices:\n", self.version);
-
-        // Collect all defined volumes
-        let mut volumes = Vec::new();
+/// Service names declared in a compose manifest, in file order.
+pub fn manifest_service_names(yaml: &str) -> Vec<String> {
+    parse_manifest(yaml)
+        .map(|file| file.services.into_keys().collect())
+        .unwrap_or_default()
+}
 
-        for (name, service) in &self.services {
-            yaml.push_str(&format!("  {}:\n", name));
-            yaml.push_str(&format!("    image: {}\n", service.image));
+/// `yaml` as a compose file; `None` (logged) when it isn't valid.
+fn parse_manifest(yaml: &str) -> Option<ComposeFile> {
+    ComposeFile::parse(yaml)
+        .map_err(|e| tracing::warn!("manifesto do Compose inválido: {e}"))
+        .ok()
+}
 
-            if let Some(cmd) = &service.command {
-                yaml.push_str(&format!("    command: {}\n", cmd));
-            }
+/// A service as declared in a compose manifest.
+#[derive(Debug, Default)]
+pub struct ManifestService {
+    pub name: String,
+    pub image: String,
+    /// `environment:` entries
+    pub env: BTreeMap<String, String>,
+    /// `ports:` mappings as (host, container)
+    pub ports: Vec<(u16, u16)>,
+    /// `volumes:` mappings as (source, container path)
+    pub volumes: Vec<(String, String)>,
+    /// `profiles:` the service belongs to
+    pub profiles: Vec<String>,
+}
 
-            if !service.env.is_empty() {
-                yaml.push_str("    environment:\n");
-                for (key, value) in &service.env {
-                    let force_block = key == "FLINK_PROPERTIES";
-                    if force_block || value.contains('\n') {
-                        yaml.push_str(&format!("      {}: |\n", key));
-                        for line in value.lines() {
-                            yaml.push_str(&format!("        {}\n", line));
-                        }
-                    } else {
-                        yaml.push_str(&format!("      {}: {}\n", key, value));
-                    }
-                }
-            }
+impl ManifestService {
+    /// Host port of the first mapping, or `default`.
+    pub fn host_port(&self, default: u16) -> u16 {
+        self.ports.first().map_or(default, |(host, _)| *host)
+    }
 
-            if !service.ports.is_empty() {
-                yaml.push_str("    ports:\n");
-                for port in &service.ports {
-                    yaml.push_str(&format!("      - '{}:{}'\n", port, port));
-                }
-            }
+    /// Host port published for `container`, if any.
+    pub fn published(&self, container: u16) -> Option<u16> {
+        self.ports
+            .iter()
+            .find(|(_, c)| *c == container)
+            .map(|(host, _)| *host)
+    }
 
-            if !service.volumes.is_empty() {
-                yaml.push_str("    volumes:\n");
-                for volume in &service.volumes {
-                    yaml.push_str(&format!("      - {}\n", volume));
-
-                    // Extract the volume name (before the colon)
-                    if let Some(volume_name) = volume.split(':').next() {
-                        if !volume_name.contains('/') && !volume_name.contains('\\') {
-                            // Likely a named volume, not a bind mount
-                            volumes.push(volume_name);
-                        }
-                    }
-                }
-            }
+    /// Connection details when the service is a PostgreSQL or MySQL/MariaDB
+    /// database (matched by service name or image). Databases that publish no
+    /// port are internal to another service (e.g. Unleash's) and don't count.
+    pub fn database(&self) -> Option<Database> {
+        if self.ports.is_empty() {
+            return None;
         }
-
-        // Add volumes section if there are any named volumes
-        if !volumes.is_empty() {
-            yaml.push_str("\nvolumes:\n");
-            for volume in volumes.iter().collect::<std::collections::HashSet<_>>() {
-                yaml.push_str(&format!("  {}:\n", volume));
-            }
+        let env = |key: &str| self.env.get(key).map(String::as_str);
+        let kind = |names: &[&str]| {
+            names
+                .iter()
+                .any(|n| self.name == *n || self.image.starts_with(&format!("{n}:")))
+        };
+        if kind(&["postgres", "postgresql"]) {
+            let user = env("POSTGRES_USER").unwrap_or("postgres");
+            Some(Database {
+                engine: DatabaseEngine::Postgres,
+                service: self.name.clone(),
+                port: self.host_port(5432),
+                user: user.to_string(),
+                password: env("POSTGRES_PASSWORD").unwrap_or_default().to_string(),
+                name: env("POSTGRES_DB").unwrap_or(user).to_string(),
+            })
+        } else if kind(&["mysql", "mariadb"]) {
+            // An application user, when one is created, otherwise root
+            let user = env("MARIADB_USER").or(env("MYSQL_USER"));
+            let password = match user {
+                Some(_) => env("MARIADB_PASSWORD").or(env("MYSQL_PASSWORD")),
+                None => env("MARIADB_ROOT_PASSWORD").or(env("MYSQL_ROOT_PASSWORD")),
+            };
+            Some(Database {
+                engine: DatabaseEngine::Mysql,
+                service: self.name.clone(),
+                port: self.host_port(3306),
+                user: user.unwrap_or("root").to_string(),
+                password: password.unwrap_or_default().to_string(),
+                name: env("MARIADB_DATABASE")
+                    .or(env("MYSQL_DATABASE"))
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+        } else {
+            None
         }
-
-        yaml
     }
 }
 
-pub fn detect_dependencies(project_dir: &Path) -> DockerComposeConfig {
-    let mut config = DockerComposeConfig::new();
-
-    // Check for common dependencies in project files
-    if has_postgres_dependency(project_dir) {
-        let mut env = HashMap::new();
-        env.insert("POSTGRES_PASSWORD".to_string(), "example".to_string());
-        env.insert("POSTGRES_DB".to_string(), "app".to_string());
-
-        config.add_service(
-            "postgres",
-            DockerService {
-                image: "postgres:16-alpine".to_string(),
-                env,
-                ports: vec![5432],
-                volumes: vec!["postgres-data:/var/lib/postgresql/data".to_string()],
-                command: None,
-            },
-        );
-    }
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DatabaseEngine {
+    Postgres,
+    Mysql,
+}
 
-    if has_mysql_dependency(project_dir) {
-        let mut env = HashMap::new();
-        env.insert("MARIADB_ROOT_PASSWORD".to_string(), "example".to_string());
-        env.insert("MARIADB_DATABASE".to_string(), "app".to_string());
-
-        // Use MariaDB for a fully open-source, lighter MySQL-compatible server
-        config.add_service(
-            "mysql",
-            DockerService {
-                image: "mariadb:11".to_string(),
-                env,
-                ports: vec![3306],
-                volumes: vec!["mariadb-data:/var/lib/mysql".to_string()],
-                command: None,
-            },
-        );
-    }
+/// A database Dev Service as seen from the host.
+#[derive(Clone, Debug)]
+pub struct Database {
+    pub engine: DatabaseEngine,
+    /// Service name in the manifest
+    pub service: String,
+    /// Host port
+    pub port: u16,
+    pub user: String,
+    pub password: String,
+    /// Database created by the container
+    pub name: String,
+}
 
-    if has_kafka_dependency(project_dir) {
-        // Use Redpanda: Kafka API-compatible, lightweight, no-cost for local dev
-        let env = HashMap::new();
-        let redpanda_cmd = "redpanda start --overprovisioned --smp 1 --memory 512M --reserve-memory 0M --node-id 0 --check=false --kafka-addr PLAINTEXT://0.0.0.0:9092,PLAINTEXT_HOST://0.0.0.0:29092 --advertise-kafka-addr PLAINTEXT://kafka:9092,PLAINTEXT_HOST://localhost:29092".to_string();
-
-        config.add_service(
-            "kafka",
-            DockerService {
-                image: "redpandadata/redpanda:latest".to_string(),
-                env,
-                ports: vec![9092, 29092],
-                volumes: vec!["redpanda-data:/var/lib/redpanda/data".to_string()],
-                command: Some(redpanda_cmd),
-            },
-        );
-
-        // Add Kafka UI for local inspection when Kafka is present
-        let mut ui_env = HashMap::new();
-        ui_env.insert("KAFKA_CLUSTERS_0_NAME".to_string(), "local".to_string());
-        ui_env.insert(
-            "KAFKA_CLUSTERS_0_BOOTSTRAPSERVERS".to_string(),
-            "kafka:9092".to_string(),
-        );
-        // Change default UI port to 9093 to avoid conflicts and match N:N mapping
-        ui_env.insert("SERVER_PORT".to_string(), "9093".to_string());
-        config.add_service(
-            "kafka-ui",
-            DockerService {
-                image: "provectuslabs/kafka-ui:latest".to_string(),
-                env: ui_env,
-                ports: vec![9093],
-                volumes: vec![],
-                command: None,
-            },
-        );
+impl Database {
+    /// URL with the given scheme (`postgres`, `postgresql`, `mysql`, ...).
+    pub fn url_with_scheme(&self, scheme: &str) -> String {
+        format!(
+            "{scheme}://{}:{}@localhost:{}/{}",
+            self.user, self.password, self.port, self.name
+        )
     }
 
-    if has_redis_dependency(project_dir) {
-        config.add_service(
-            "redis",
-            DockerService {
-                image: "redis:alpine".to_string(),
-                env: HashMap::new(),
-                ports: vec![6379],
-                volumes: vec!["redis-data:/data".to_string()],
-                command: None,
-            },
-        );
+    /// URL with the engine's usual scheme.
+    pub fn url(&self) -> String {
+        match self.engine {
+            DatabaseEngine::Postgres => self.url_with_scheme("postgres"),
+            DatabaseEngine::Mysql => self.url_with_scheme("mysql"),
+        }
     }
 
-    if has_mongodb_dependency(project_dir) {
-        let mut env = HashMap::new();
-        env.insert("MONGO_INITDB_ROOT_USERNAME".to_string(), "root".to_string());
-        env.insert(
-            "MONGO_INITDB_ROOT_PASSWORD".to_string(),
-            "example".to_string(),
-        );
-
-        config.add_service(
-            "mongodb",
-            DockerService {
-                image: "mongo:7.0".to_string(),
-                env,
-                ports: vec![27017],
-                volumes: vec!["mongodb-data:/data/db".to_string()],
-                command: None,
-            },
-        );
+    /// JDBC URL (credentials passed separately).
+    pub fn jdbc_url(&self) -> String {
+        let driver = match self.engine {
+            DatabaseEngine::Postgres => "postgresql",
+            DatabaseEngine::Mysql => "mysql",
+        };
+        format!("jdbc:{driver}://localhost:{}/{}", self.port, self.name)
     }
+}
 
-    if has_flink_dependency(project_dir) {
-        // Apache Flink dependencies typically require multiple services
-
-        // JobManager service
-        let mut jobmanager_env = HashMap::new();
-        jobmanager_env.insert(
-            "FLINK_PROPERTIES".to_string(),
-            "jobmanager.rpc.address: jobmanager".to_string(),
-        );
-
-        config.add_service(
-            "jobmanager",
-            DockerService {
-                image: "apache/flink:latest".to_string(),
-                env: jobmanager_env,
-                ports: vec![8081], // UI port
-                volumes: vec!["flink-data:/opt/flink/data".to_string()],
-                command: None,
-            },
-        );
-
-        // TaskManager service
-        let mut taskmanager_env = HashMap::new();
-        taskmanager_env.insert(
-            "FLINK_PROPERTIES".to_string(),
-            "jobmanager.rpc.address: jobmanager\ntaskmanager.numberOfTaskSlots: 1".to_string(),
-        );
-
-        config.add_service(
-            "taskmanager",
-            DockerService {
-                image: "apache/flink:latest".to_string(),
-                env: taskmanager_env,
-                ports: vec![],
-                volumes: vec!["flink-data:/opt/flink/data".to_string()],
-                command: None,
-            },
-        );
-    }
+/// (host, container) ports of a `ports:` entry: `container`, `host:container`
+/// or `ip:host:container`, optionally with `/protocol`.
+fn port_mapping(mapping: &str) -> Option<(u16, u16)> {
+    let port = |p: &str| p.split('/').next().unwrap_or(p).parse::<u16>().ok();
+    let parts: Vec<&str> = mapping.split(':').collect();
+    let host = port(parts[parts.len().saturating_sub(2)])?;
+    let container = port(parts[parts.len() - 1])?;
+    Some((host, container))
+}
 
-    // Add volumes section if there are services with volumes
-    let has_volumes = config.services.values().any(|s| !s.volumes.is_empty());
-    if has_volumes {
-        // In a real implementation, we would add volumes section to the YAML
-        // This is handled in the to_yaml method for simplicity
-    }
+/// Services of a compose manifest, in file order.
+pub fn manifest_services(yaml: &str) -> Vec<ManifestService> {
+    parse_manifest(yaml).map(compose_services).unwrap_or_default()
+}
 
-    config
+fn compose_services(file: ComposeFile) -> Vec<ManifestService> {
+    file.services
+        .into_iter()
+        .map(|(name, service)| ManifestService {
+            name,
+            image: service.image,
+            env: service.environment,
+            ports: service
+                .ports
+                .iter()
+                .filter_map(|p| port_mapping(p))
+                .collect(),
+            // `source:target[:mode]`; anonymous volumes have no source
+            volumes: service
+                .volumes
+                .iter()
+                .filter_map(|v| v.split_once(':'))
+                .map(|(source, rest)| {
+                    let target = rest.split(':').next().unwrap_or(rest);
+                    (source.to_string(), target.to_string())
+                })
+                .collect(),
+            profiles: service.profiles,
+        })
+        .collect()
 }
 
-fn has_postgres_dependency(project_dir: &Path) -> bool {
-    // Search for common Postgres-related strings
-    search_for_dependency(
-        project_dir,
-        &[
-            "postgres",
-            "pg",
-            "postgresql",
-            "psycopg",
-            "POSTGRES_URL",
-            "DATABASE_URL",
-        ],
-    )
-}
-
-fn has_mysql_dependency(project_dir: &Path) -> bool {
-    // Search for common MySQL-related strings
-    search_for_dependency(
-        project_dir,
-        &[
-            "mysql",
-            "mariadb",
-            "innodb",
-            "MYSQL_",
-            "DB_CONNECTION=mysql",
-        ],
-    )
-}
-
-fn has_kafka_dependency(project_dir: &Path) -> bool {
-    // Search for Kafka-related strings
-    search_for_dependency(
-        project_dir,
-        &["kafka", "KAFKA_BROKERS", "kafka-go", "spring-kafka"],
-    )
-}
-
-fn has_redis_dependency(project_dir: &Path) -> bool {
-    // Search for Redis-related strings
-    search_for_dependency(
-        project_dir,
-        &["redis", "REDIS_URL", "REDIS_HOST", "redis-client", "predis"],
-    )
-}
-
-fn has_mongodb_dependency(project_dir: &Path) -> bool {
-    // Search for MongoDB-related strings
-    search_for_dependency(
-        project_dir,
-        &["mongodb", "mongo", "MONGO_URI", "mongoose", "mongo-driver"],
-    )
-}
-
-fn has_flink_dependency(project_dir: &Path) -> bool {
-    // Search for Apache Flink-related strings
-    search_for_dependency(
-        project_dir,
-        &[
-            "flink",
-            "org.apache.flink",
-            "flink-connector",
-            "StreamExecutionEnvironment",
-            "DataStream",
-        ],
-    )
-}
-
-fn search_for_dependency(project_dir: &Path, keywords: &[&str]) -> bool {
-    // Check configuration files and package manager files first
-    if check_config_files(project_dir, keywords) {
-        return true;
-    }
+/// Profiles used by the services of a compose manifest, sorted.
+pub fn manifest_profiles(yaml: &str) -> Vec<String> {
+    let profiles: std::collections::BTreeSet<String> = manifest_services(yaml)
+        .into_iter()
+        .flat_map(|s| s.profiles)
+        .collect();
+    profiles.into_iter().collect()
+}
 
-    // Then do a more thorough recursive scan of source directories
-    recursive_scan_directories(project_dir, keywords)
+/// `COMPOSE_PROFILES` enabling every profile of the manifest at `compose`.
+/// Services in a profile are invisible to Compose while it is inactive, so
+/// commands that address the whole stack (stop, down, ps, exec) set it.
+pub fn all_profiles(compose: &Path) -> String {
+    manifest_profiles(&fs::read_to_string(compose).unwrap_or_default()).join(",")
 }
 
-// Check common configuration files and package manager files
-fn check_config_files(project_dir: &Path, keywords: &[&str]) -> bool {
-    // Check common .env files first (used across many languages)
-    let env_path = project_dir.join(".env");
-    if check_file_for_keywords(&env_path, keywords) {
-        return true;
+/// Compose keys of `service` without a field of their own in
+/// [`crate::compose::Service`].
+fn compose_extra(service: &DockerService) -> indexmap::IndexMap<String, serde_yaml::Value> {
+    let mut extra = indexmap::IndexMap::new();
+    if !service.labels.is_empty() {
+        let labels: serde_yaml::Mapping = service
+            .labels
+            .iter()
+            .map(|(k, v)| (k.clone().into(), v.clone().into()))
+            .collect();
+        extra.insert("labels".into(), labels.into());
     }
-
-    // Rust - Cargo.toml
-    let cargo_toml_path = project_dir.join("Cargo.toml");
-    if check_file_for_keywords(&cargo_toml_path, keywords) {
-        return true;
+    if !service.extra_hosts.is_empty() {
+        let hosts: Vec<serde_yaml::Value> = service.extra_hosts.iter().map(|h| h.clone().into()).collect();
+        extra.insert("extra_hosts".into(), hosts.into());
     }
+    extra
+}
 
-    // Node.js - package.json
-    let package_json_path = project_dir.join("package.json");
-    if check_file_for_keywords(&package_json_path, keywords) {
-        return true;
+impl DockerComposeConfig {
+    pub fn new() -> Self {
+        DockerComposeConfig {
+            version: "3.8".to_string(),
+            services: HashMap::new(),
+            iac: Vec::new(),
+            preset: None,
+            jobs: Vec::new(),
+            overrides: None,
+            excluded: Vec::new(),
+        }
     }
 
-    // Python - requirements.txt, setup.py, pyproject.toml
-    let requirements_path = project_dir.join("requirements.txt");
-    if check_file_for_keywords(&requirements_path, keywords) {
-        return true;
+    /// The services as they are written to the manifest (sorted by name,
+    /// with the overrides applied).
+    pub fn manifest_services(&self) -> Vec<ManifestService> {
+        compose_services(self.to_compose())
     }
 
-    let setup_py_path = project_dir.join("setup.py");
-    if check_file_for_keywords(&setup_py_path, keywords) {
-        return true;
+    /// Service names in sorted order, for reproducible listings.
+    pub fn service_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.services.keys().cloned().collect();
+        names.sort();
+        names
     }
 
-    let pyproject_path = project_dir.join("pyproject.toml");
-    if check_file_for_keywords(&pyproject_path, keywords) {
-        return true;
+    pub fn add_service(&mut self, name: &str, service: DockerService) {
+        self.services.insert(name.to_string(), service);
     }
 
-    // Java - pom.xml, build.gradle
-    let pom_xml_path = project_dir.join("pom.xml");
-    if check_file_for_keywords(&pom_xml_path, keywords) {
-        return true;
+    /// Drop the services named in `names` and the `depends_on` entries that
+    /// point to them, recording the ones that were there in `excluded`.
+    pub fn exclude(&mut self, names: &[String]) {
+        for name in names {
+            if self.services.remove(name).is_some() && !self.excluded.contains(name) {
+                self.excluded.push(name.clone());
+            }
+        }
+        for service in self.services.values_mut() {
+            service.depends_on.0.retain(|dep, _| !self.excluded.contains(dep));
+        }
     }
 
-    let gradle_path = project_dir.join("build.gradle");
-    if check_file_for_keywords(&gradle_path, keywords) {
-        return true;
+    /// Remove the healthchecks of the services, so that their dependents wait
+    /// for them to start only.
+    pub fn drop_healthchecks(&mut self) {
+        for service in self.services.values_mut() {
+            service.healthcheck = None;
+            for condition in service.depends_on.0.values_mut() {
+                if condition == crate::compose::HEALTHY {
+                    *condition = crate::compose::STARTED.to_string();
+                }
+            }
+        }
     }
 
-    // Ruby - Gemfile
-    let gemfile_path = project_dir.join("Gemfile");
-    if check_file_for_keywords(&gemfile_path, keywords) {
-        return true;
+    /// Replace the image of each service listed in `pins` (service name -> image).
+    pub fn pin_images(&mut self, pins: &BTreeMap<String, String>) {
+        for (name, image) in pins {
+            if let Some(service) = self.services.get_mut(name) {
+                service.image = image.clone();
+            }
+        }
     }
 
-    // Go - go.mod
-    let go_mod_path = project_dir.join("go.mod");
-    if check_file_for_keywords(&go_mod_path, keywords) {
-        return true;
+    /// The manifest as a compose file: services sorted by name, plus the
+    /// named volumes and networks they use.
+    pub fn to_compose(&self) -> ComposeFile {
+        let mut file = ComposeFile {
+            version: (!self.version.is_empty()).then(|| self.version.clone()),
+            ..ComposeFile::default()
+        };
+        let mut volumes = std::collections::BTreeSet::new();
+        let mut networks = std::collections::BTreeSet::new();
+        for name in self.service_names() {
+            let service = &self.services[&name];
+            for volume in &service.volumes {
+                // Bind mounts are paths; anything else is a named volume
+                if let Some((source, _)) = volume.split_once(':')
+                    && !source.contains(['/', '\\'])
+                    && !source.starts_with(['.', '~', '$'])
+                {
+                    volumes.insert(source.to_string());
+                }
+            }
+            networks.extend(service.networks.iter().filter(|n| *n != "default").cloned());
+            file.services.insert(
+                name,
+                crate::compose::Service {
+                    image: service.image.clone(),
+                    command: service.command.clone().map(crate::compose::Command::Shell),
+                    profiles: service.profiles.clone(),
+                    environment: service.env.clone().into_iter().collect(),
+                    ports: service.ports.iter().map(|p| format!("{}:{p}", service.host_port(*p))).collect(),
+                    volumes: service.volumes.clone(),
+                    depends_on: service.depends_on.clone(),
+                    healthcheck: service.healthcheck.clone(),
+                    networks: service.networks.clone(),
+                    restart: service.restart.clone(),
+                    extra: compose_extra(service),
+                },
+            );
+        }
+        file.volumes = volumes.into_iter().map(|v| (v, Some(Default::default()))).collect();
+        file.networks = networks.into_iter().map(|n| (n, Some(Default::default()))).collect();
+        if let Some(overrides) = &self.overrides {
+            file.merge(overrides);
+        }
+        file
     }
 
-    // PHP - composer.json
-    let composer_json_path = project_dir.join("composer.json");
-    if check_file_for_keywords(&composer_json_path, keywords) {
-        return true;
+    pub fn to_yaml(&self) -> String {
+        crate::jobs::with_worker_templates(self.to_compose().to_yaml(), self)
     }
+}
+
+/// What dx detected in a project: the manifest part of `dx dev-services`
+/// and the `data` of `dx dev-services` and `dx analyzer` in JSON mode.
+#[derive(serde::Serialize)]
+pub struct Detection {
+    pub project: String,
+    pub services: Vec<DetectedService>,
+    pub iac: Vec<crate::iac::Resource>,
+    pub preset: Option<crate::presets::Preset>,
+    pub jobs: Vec<crate::jobs::Job>,
+    /// Detected services left out of the manifest
+    pub excluded: Vec<String>,
+    /// Manifest written by the command
+    pub compose: Option<String>,
+    /// Analyzer report written by the command
+    pub report: Option<String>,
+    /// Manifest printed in text mode (empty when nothing was detected)
+    #[serde(skip)]
+    yaml: String,
+}
 
-    // Check application-specific config files
-    // Java Spring - application.properties, application.yml
-    let spring_properties_path = project_dir.join("src/main/resources/application.properties");
-    if check_file_for_keywords(&spring_properties_path, keywords) {
-        return true;
+#[derive(serde::Serialize)]
+pub struct DetectedService {
+    pub name: String,
+    pub image: String,
+    pub profiles: Vec<String>,
+    pub ports: Vec<PublishedPort>,
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct PublishedPort {
+    pub host: u16,
+    pub container: u16,
+}
+
+impl Detection {
+    pub fn new(project_dir: &Path, config: &DockerComposeConfig) -> Self {
+        let services = if config.services.is_empty() {
+            Vec::new()
+        } else {
+            config
+                .manifest_services()
+                .into_iter()
+                .map(|s| DetectedService {
+                    name: s.name,
+                    image: s.image,
+                    profiles: s.profiles,
+                    ports: s
+                        .ports
+                        .into_iter()
+                        .map(|(host, container)| PublishedPort { host, container })
+                        .collect(),
+                    env: s.env,
+                })
+                .collect()
+        };
+        Detection {
+            project: project_dir.display().to_string(),
+            services,
+            iac: config.iac.clone(),
+            preset: config.preset.clone(),
+            jobs: config.jobs.clone(),
+            excluded: config.excluded.clone(),
+            compose: None,
+            report: None,
+            yaml: if config.services.is_empty() { String::new() } else { config.to_yaml() },
+        }
     }
+}
 
-    let spring_yml_path = project_dir.join("src/main/resources/application.yml");
-    if check_file_for_keywords(&spring_yml_path, keywords) {
-        return true;
+impl crate::output::Render for Detection {
+    fn render_text(&self) {
+        outln!("Manifesto Dev Services (detectado) para {}:\n", self.project);
+
+        if !self.iac.is_empty() {
+            outln!("Recursos de infraestrutura (IaC) mapeados para serviços locais:");
+            for r in &self.iac {
+                outln!("- {} ({}) -> {}", r.kind, r.file, r.service.name());
+            }
+            outln!();
+        }
+
+        if let Some(preset) = &self.preset {
+            outln!(
+                "Preset de framework: {} (banco '{}'; conexão em {})\n",
+                preset.framework.name(),
+                preset.database,
+                preset.env_file
+            );
+        }
+
+        if !self.excluded.is_empty() {
+            outln!("Serviços excluídos do manifesto: {}\n", self.excluded.join(", "));
+        }
+
+        if self.yaml.is_empty() {
+            outln!("Nenhuma dependência detectada no projeto atual.");
+        } else {
+            outln!("---");
+            outln!("{}", self.yaml);
+        }
     }
+}
 
-    // Ruby on Rails - config/database.yml
-    let rails_db_path = project_dir.join("config/database.yml");
-    if check_file_for_keywords(&rails_db_path, keywords) {
-        return true;
+pub fn detect_dependencies(project_dir: &Path) -> DockerComposeConfig {
+    let mut config = crate::progress::phase("Detectando dependências", || {
+        crate::detection_cache::cached(project_dir, "dev-services", || {
+            scan_dependencies(project_dir)
+        })
+    });
+    crate::catalog::resolve(project_dir, &mut config);
+    let settings = crate::settings::get(project_dir).dev_services;
+    config.exclude(&settings.exclude);
+    if settings.healthchecks == Some(false) {
+        config.drop_healthchecks();
     }
+    config.pin_images(&image_pins(project_dir));
+    config
+}
 
-    // Python Django - settings.py
-    let django_settings_paths = vec![
-        project_dir.join("settings.py"),
-        project_dir.join("config/settings.py"),
-        project_dir.join("app/settings.py"),
-    ];
+/// `[images]` of dx.toml, with their `${VAR}` placeholders resolved (a team
+/// can pin `"${PG_IMAGE:-postgres:16}"`).
+pub fn image_pins(project_dir: &Path) -> BTreeMap<String, String> {
+    use crate::interpolate::Target;
+    crate::settings::get(project_dir)
+        .images
+        .into_iter()
+        .map(|(name, image)| (name, crate::interpolate::project(project_dir, &image, Target::Compose)))
+        .collect()
+}
 
-    for path in django_settings_paths {
-        if check_file_for_keywords(&path, keywords) {
-            return true;
+fn scan_dependencies(project_dir: &Path) -> DockerComposeConfig {
+    let mut config = DockerComposeConfig::new();
+    // Managed resources declared in Terraform/CloudFormation/CDK
+    config.iac = crate::iac::scan(project_dir);
+
+    // Check for common dependencies in project files (one pass for all
+    // detectors and job frameworks)
+    let detectors = crate::detectors::catalog(project_dir);
+    let mut keywords: Vec<&[Keyword]> = detectors.iter().map(|d| d.keywords()).collect();
+    let job_keywords = crate::jobs::keywords();
+    let jobs_end = detectors.len() + job_keywords.len();
+    keywords.extend(job_keywords);
+    keywords.extend(crate::localstack::keywords());
+    let found = search_for_dependencies(project_dir, &keywords);
+
+    for (detector, found) in detectors.iter().zip(&found) {
+        let in_iac = detector
+            .iac()
+            .is_some_and(|service| config.iac.iter().any(|r| r.service == service));
+        if *found || in_iac {
+            provision(&mut config, detector.as_ref());
         }
     }
+    crate::jobs::apply(&found[detectors.len()..jobs_end], &mut config);
+    crate::localstack::apply(&found[jobs_end..], &mut config);
 
-    // No matches found in config files
-    false
-}
-
-// Recursively scan directories for source files that might indicate dependencies
-fn recursive_scan_directories(project_dir: &Path, keywords: &[&str]) -> bool {
-    // Define common source directories to scan
-    let source_dirs = vec![
-        "src",      // Generic source directory
-        "app",      // Common for many frameworks
-        "lib",      // Ruby, PHP
-        "internal", // Go
-        "tests", "test",   // Test directories might have dependencies
-        "config", // Configuration files
-    ];
-
-    // Define file extensions to check
-    let file_extensions = vec![
-        ".rs", // Rust
-        ".js", ".jsx", ".ts", ".tsx", // JavaScript/TypeScript
-        ".py",  // Python
-        ".java", ".kt",  // Java, Kotlin
-        ".rb",  // Ruby
-        ".go",  // Go
-        ".php", // PHP
-        ".yml", ".yaml", // YAML config
-        ".json", // JSON config
-        ".xml",  // XML config
-        ".toml", // TOML config
-        ".ini", ".conf", ".cfg", // Other config formats
-    ];
-
-    // Skip directories that are commonly large and not useful for dependency detection
-    let skip_dirs = vec![
-        "node_modules",
-        "target",
-        "build",
-        "dist",
-        "vendor",
-        ".git",
-        ".github",
-        ".idea",
-        ".vscode",
-    ];
-
-    // Recursively walk the directory
-    if let Ok(entries) = fs::read_dir(project_dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
+    // Mock servers for the project's OpenAPI and gRPC specs
+    let specs = crate::api_mocks::scan(project_dir);
+    for (name, service) in crate::api_mocks::services(&specs) {
+        config.add_service(&name, service);
+    }
+    // A GraphQL schema reveals a GraphQL server even without a known library
+    let schemas: Vec<String> = specs
+        .iter()
+        .filter(|s| s.kind == crate::api_mocks::SpecKind::GraphQl)
+        .map(|s| s.file.clone())
+        .collect();
+    if !schemas.is_empty()
+        && !config.services.contains_key(crate::graphql::SERVICE)
+        && let Some(detector) = crate::detectors::for_service(crate::graphql::SERVICE)
+    {
+        provision(&mut config, detector.as_ref());
+    }
+    crate::graphql::apply(project_dir, &schemas, &mut config);
+    crate::minio::apply(project_dir, &mut config);
+    crate::schema_registry::apply(project_dir, &mut config);
+    crate::proxy::apply(project_dir, &mut config);
+
+    config.preset = crate::presets::detect(project_dir);
+    if let Some(preset) = config.preset.clone() {
+        preset.apply(&mut config);
+    }
+    config
+}
 
-            // Skip if path is in the skip list
-            if path.is_dir() {
-                let dir_name = path
-                    .file_name()
-                    .and_then(|name| name.to_str())
-                    .unwrap_or("");
+/// Add the services of `detector` to `config`, in the detector's profile.
+pub fn provision(config: &mut DockerComposeConfig, detector: &dyn crate::detectors::Detector) {
+    for (name, mut service) in detector.services() {
+        service.profiles = vec![detector.profile().to_string()];
+        config.add_service(&name, service);
+    }
+}
 
-                if skip_dirs.iter().any(|skip| skip == &dir_name) {
-                    continue;
-                }
+// Keywords of one detector and whether some file matched them
+struct KeywordSet<'a> {
+    keywords: &'a [Keyword],
+    found: AtomicBool,
+}
 
-                // Recursively check subdirectories
-                if recursive_scan_directories(&path, keywords) {
-                    return true;
-                }
+fn all_found(sets: &[KeywordSet]) -> bool {
+    sets.iter().all(|set| set.found.load(Ordering::Relaxed))
+}
 
-                // Continue if this directory isn't in our source_dirs list
-                // Only for top-level directories - we check all subdirectories
-                if path.parent() == Some(project_dir)
-                    && !source_dirs.iter().any(|src| path.ends_with(src))
-                {
-                    continue;
-                }
-            } else if path.is_file() {
-                // Check if the file has an extension we're interested in
-                if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                    if file_extensions.iter().any(|e| e.ends_with(ext)) {
-                        if check_file_for_keywords(&path, keywords) {
-                            return true;
-                        }
-                    }
-                }
-            }
-        }
+// Which keyword sets appear in the project. Every file is read once and
+// matched against all the sets still missing.
+fn search_for_dependencies(project_dir: &Path, keywords: &[&[Keyword]]) -> Vec<bool> {
+    let sets: Vec<KeywordSet> = keywords
+        .iter()
+        .map(|keywords| KeywordSet { keywords, found: AtomicBool::new(false) })
+        .collect();
+
+    // Check configuration files and package manager files first
+    check_config_files(project_dir, &sets);
+
+    // Then do a more thorough recursive scan of source directories
+    if !all_found(&sets) {
+        recursive_scan_directories(project_dir, &sets);
     }
+    sets.iter().map(|set| set.found.load(Ordering::Relaxed)).collect()
+}
 
-    false
+// Common configuration files and package manager files
+const CONFIG_FILES: &[&str] = &[
+    // .env files (used across many languages) and the committed template
+    ".env",
+    ".env.example",
+    // Rust
+    "Cargo.toml",
+    // Node.js
+    "package.json",
+    // Python
+    "requirements.txt",
+    "setup.py",
+    "pyproject.toml",
+    // Java
+    "pom.xml",
+    "build.gradle",
+    // Ruby
+    "Gemfile",
+    // Go
+    "go.mod",
+    // PHP
+    "composer.json",
+    // C/C++
+    "CMakeLists.txt",
+    "conanfile.txt",
+    "conanfile.py",
+    "vcpkg.json",
+    // Java Spring
+    "src/main/resources/application.properties",
+    "src/main/resources/application.yml",
+    // Ruby on Rails
+    "config/database.yml",
+    // Python Django
+    "settings.py",
+    "config/settings.py",
+    "app/settings.py",
+];
+
+fn check_config_files(project_dir: &Path, sets: &[KeywordSet]) {
+    for file in CONFIG_FILES {
+        if all_found(sets) {
+            return;
+        }
+        check_file_for_keywords(&project_dir.join(file), sets);
+    }
 }
 
-// Helper function to check a file for keywords
-fn check_file_for_keywords(file_path: &Path, keywords: &[&str]) -> bool {
-    if file_path.exists() {
-        if let Ok(content) = fs::read_to_string(file_path) {
-            let content_lower = content.to_lowercase();
-            for keyword in keywords {
-                if content_lower.contains(&keyword.to_lowercase()) {
-                    return true;
-                }
+// File extensions of the source scan
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", // Rust
+    "js", "jsx", "ts", "tsx", // JavaScript/TypeScript
+    "py",  // Python
+    "java", "kt",  // Java, Kotlin
+    "rb",  // Ruby
+    "go",  // Go
+    "php", // PHP
+    "c", "cc", "cpp", "cxx", "h", "hpp", // C/C++
+    "cmake", // CMake modules
+    "yml", "yaml", // YAML config
+    "json", // JSON config
+    "xml",  // XML config
+    "toml", // TOML config
+    "ini", "conf", "cfg", // Other config formats
+];
+
+// Scan the project's source files for dependency hints, in parallel. The walk
+// honours .gitignore/.ignore files (also outside git repositories) and skips
+// hidden entries, so build output, virtualenvs and vendored trees are left
+// out. It stops as soon as every keyword set has been found.
+fn recursive_scan_directories(project_dir: &Path, sets: &[KeywordSet]) {
+    // Still skipped when the project has no .gitignore covering them
+    let skip_dirs = ["node_modules", "target", "build", "dist", "vendor"];
+
+    let mut builder = ignore::WalkBuilder::new(project_dir);
+    builder
+        .follow_links(false)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_some_and(|t| t.is_dir())
+                || !entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| skip_dirs.contains(&name))
+        });
+
+    builder.build_parallel().run(|| {
+        Box::new(|result| {
+            let Ok(entry) = result else {
+                return WalkState::Continue;
+            };
+            let path = entry.path();
+            if entry.file_type().is_some_and(|t| t.is_file())
+                && (path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext))
+                    || entry.file_name() == "CMakeLists.txt")
+            {
+                check_file_for_keywords(path, sets);
+            }
+            if all_found(sets) {
+                WalkState::Quit
+            } else {
+                WalkState::Continue
             }
+        })
+    });
+}
+
+// Files larger than this are not scanned (bundles, dumps, generated code)
+const MAX_SCAN_BYTES: u64 = 2 * 1024 * 1024;
+
+// Leading bytes checked for NUL to tell binary files apart
+const SNIFF_BYTES: usize = 8 * 1024;
+
+// Time budget per file; slow files (network mounts, ...) are cut short
+const FILE_SCAN_TIMEOUT: Duration = Duration::from_millis(500);
+
+// Helper function to match a file against the keyword sets not found yet,
+// line by line. Large and binary files are skipped.
+fn check_file_for_keywords(file_path: &Path, sets: &[KeywordSet]) {
+    let Ok(file) = fs::File::open(file_path) else {
+        return;
+    };
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if size > MAX_SCAN_BYTES {
+        tracing::debug!(file = %file_path.display(), size, "arquivo grande ignorado na detecção");
+        return;
+    }
+    let mut reader = BufReader::with_capacity(SNIFF_BYTES, file);
+    match reader.fill_buf() {
+        Ok(head) if head.contains(&0) => {
+            tracing::debug!(file = %file_path.display(), "arquivo binário ignorado na detecção");
+            return;
         }
+        Ok(_) => {}
+        Err(_) => return,
     }
-    false
-}
 
-pub fn create_docker_compose_file(
-    config: &DockerComposeConfig,
-    output_path: &Path,
-) -> std::io::Result<()> {
-    let yaml = config.to_yaml();
-    fs::write(output_path, yaml)
+    let start = Instant::now();
+    let mut line = Vec::new();
+    while matches!(reader.read_until(b'\n', &mut line), Ok(n) if n > 0) {
+        let text = String::from_utf8_lossy(&line);
+        let lower = text.to_lowercase();
+        for set in sets {
+            if set.found.load(Ordering::Relaxed) {
+                continue;
+            }
+            if let Some(keyword) = set.keywords.iter().find(|k| k.matches(&text, &lower))
+                && !set.found.swap(true, Ordering::Relaxed)
+            {
+                tracing::debug!(file = %file_path.display(), keyword = %keyword.text, "dependência detectada");
+            }
+        }
+        if all_found(sets) {
+            return;
+        }
+        if start.elapsed() > FILE_SCAN_TIMEOUT {
+            tracing::debug!(file = %file_path.display(), "tempo limite na leitura; restante do arquivo ignorado");
+            return;
+        }
+        line.clear();
+    }
 }
@@ -2,6 +2,7 @@
 // Copyright (c) 2025 The dx-cli Contributors
 
 use std::{
+    collections::BTreeMap,
     fmt,
     path::{Path, PathBuf},
     process::Command,
@@ -11,6 +12,9 @@ use std::{
 
 use notify::{recommended_watcher, EventKind, RecursiveMode, Watcher};
 
+use crate::error::{DxError, DxResult};
+use crate::toolchain::Installer;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Stack {
     Rust,
@@ -19,9 +23,32 @@ enum Stack {
     Go,
     JavaMaven,
     JavaGradle,
+    Kotlin,
+    KotlinMultiplatform,
+    Scala,
+    Swift,
+    Cpp,
     Unknown,
 }
 
+/// Gradle projects applying the Kotlin plugin are reported as Kotlin; the
+/// multiplatform plugin runs its tests through the `allTests` task.
+fn gradle_stack(dir: &Path) -> Stack {
+    let build: String = ["build.gradle.kts", "build.gradle"]
+        .iter()
+        .filter_map(|f| std::fs::read_to_string(dir.join(f)).ok())
+        .collect();
+    if build.contains("kotlin(\"multiplatform\")")
+        || build.contains("org.jetbrains.kotlin.multiplatform")
+    {
+        Stack::KotlinMultiplatform
+    } else if build.contains("kotlin(\"") || build.contains("org.jetbrains.kotlin") {
+        Stack::Kotlin
+    } else {
+        Stack::JavaGradle
+    }
+}
+
 impl Stack {
     fn detect(dir: &Path) -> Self {
         if dir.join("Cargo.toml").exists() {
@@ -35,7 +62,16 @@ impl Stack {
         } else if dir.join("pom.xml").exists() {
             Stack::JavaMaven
         } else if dir.join("build.gradle").exists() || dir.join("build.gradle.kts").exists() {
-            Stack::JavaGradle
+            gradle_stack(dir)
+        } else if dir.join("build.sbt").exists() {
+            Stack::Scala
+        } else if dir.join("Package.swift").exists() {
+            Stack::Swift
+        } else if ["CMakeLists.txt", "conanfile.txt", "conanfile.py"]
+            .iter()
+            .any(|f| dir.join(f).exists())
+        {
+            Stack::Cpp
         } else {
             Stack::Unknown
         }
@@ -48,16 +84,60 @@ impl Stack {
             Stack::Python => Some(("python".into(), vec!["-m".into(), "pytest".into()])),
             Stack::Go => Some(("go".into(), vec!["test".into(), "./...".into()])),
             Stack::JavaMaven => Some(("mvn".into(), vec!["test".into()])),
-            Stack::JavaGradle => {
-                if dir.join("gradlew").exists() {
-                    Some(("./gradlew".into(), vec!["test".into()]))
-                } else {
-                    Some(("gradle".into(), vec!["test".into()]))
+            Stack::JavaGradle | Stack::Kotlin => Some((gradle(dir), vec!["test".into()])),
+            Stack::KotlinMultiplatform => Some((gradle(dir), vec!["allTests".into()])),
+            Stack::Scala => Some(("sbt".into(), vec!["test".into()])),
+            Stack::Swift => Some(("swift".into(), vec!["test".into()])),
+            // CTest runs from the CMake build tree
+            Stack::Cpp => {
+                let mut args = vec!["--output-on-failure".to_string()];
+                if dir.join("build").is_dir() {
+                    args.extend(["--test-dir".into(), "build".into()]);
                 }
+                Some(("ctest".into(), args))
             }
             Stack::Unknown => None,
         }
     }
+
+    /// Command that also runs the integration tests: the `test:integration`
+    /// script, the `integration` build tag, Maven's `verify` phase, Gradle's
+    /// `integrationTest` task or sbt's `IntegrationTest` configuration, when
+    /// the project has them.
+    fn integration_command(self, dir: &Path) -> Option<(String, Vec<String>)> {
+        let contains = |file: &str, needle: &str| {
+            std::fs::read_to_string(dir.join(file)).is_ok_and(|c| c.contains(needle))
+        };
+        match self {
+            Stack::Node if contains("package.json", "\"test:integration\"") => Some((
+                "npm".into(),
+                vec!["run".into(), "test:integration".into()],
+            )),
+            Stack::Go => Some((
+                "go".into(),
+                vec!["test".into(), "-tags=integration".into(), "./...".into()],
+            )),
+            Stack::JavaMaven => Some(("mvn".into(), vec!["verify".into()])),
+            Stack::JavaGradle | Stack::Kotlin | Stack::KotlinMultiplatform
+                if contains("build.gradle", "integrationTest")
+                    || contains("build.gradle.kts", "integrationTest") =>
+            {
+                Some((gradle(dir), vec!["integrationTest".into()]))
+            }
+            Stack::Scala if contains("build.sbt", "IntegrationTest") => Some((
+                "sbt".into(),
+                vec!["test".into(), "IntegrationTest/test".into()],
+            )),
+            _ => self.test_command(dir),
+        }
+    }
+}
+
+/// The project's Gradle wrapper for this platform (`gradlew` or
+/// `gradlew.bat`) when it has one, spawned by path.
+fn gradle(dir: &Path) -> String {
+    crate::platform::wrapper(dir, "gradlew")
+        .map_or_else(|| "gradle".into(), |p| p.display().to_string())
 }
 
 impl fmt::Display for Stack {
@@ -69,18 +149,65 @@ impl fmt::Display for Stack {
             Stack::Go => "Go",
             Stack::JavaMaven => "Java (Maven)",
             Stack::JavaGradle => "Java (Gradle)",
+            Stack::Kotlin => "Kotlin (Gradle)",
+            Stack::KotlinMultiplatform => "Kotlin Multiplatform",
+            Stack::Scala => "Scala (sbt)",
+            Stack::Swift => "Swift (SwiftPM)",
+            Stack::Cpp => "C/C++ (CMake)",
             Stack::Unknown => "Desconhecida",
         };
         write!(f, "{name}")
     }
 }
 
-fn run_tests(dir: &Path, cmd: &str, args: &[String]) {
-    println!("> Executando testes: {} {:?}", cmd, args);
-    match Command::new(cmd).args(args).current_dir(dir).status() {
-        Ok(status) if status.success() => println!("> Testes concluídos com sucesso"),
-        Ok(status) => println!("> Testes falharam (status {status})"),
-        Err(e) => eprintln!("Erro ao executar comando de teste: {e}"),
+/// The test command through the project's version manager, so the toolchain
+/// pinned in `mise.toml` or `.tool-versions` runs rather than the first one on
+/// PATH: mise's `task` when the project defines it, else `mise exec`; with
+/// asdf, its shims go first in the PATH.
+fn pinned(
+    dir: &Path,
+    task: &str,
+    (cmd, args): (String, Vec<String>),
+    env: &mut BTreeMap<String, String>,
+) -> (String, Vec<String>) {
+    match crate::toolchain::version_manager(dir) {
+        Some(Installer::Mise) if crate::toolchain::mise_task(dir, task) => {
+            ("mise".into(), vec!["run".into(), task.into()])
+        }
+        Some(Installer::Mise) => {
+            let exec = ["exec".to_string(), "--".into(), cmd];
+            ("mise".into(), exec.into_iter().chain(args).collect())
+        }
+        Some(Installer::Asdf) => {
+            let Some(shims) = crate::toolchain::asdf_shims() else {
+                return (cmd, args);
+            };
+            let path = std::env::var_os("PATH").unwrap_or_default();
+            if let Ok(path) = std::env::join_paths(std::iter::once(shims.clone()).chain(std::env::split_paths(&path))) {
+                outln!("Usando os shims do asdf ({}) para as versões do .tool-versions.", shims.display());
+                env.insert("PATH".into(), path.to_string_lossy().into_owned());
+            }
+            (cmd, args)
+        }
+        _ => (cmd, args),
+    }
+}
+
+fn run_tests(dir: &Path, cmd: &str, args: &[String], env: &BTreeMap<String, String>) -> DxResult {
+    outln!(
+        "> Executando testes: {}",
+        crate::platform::command_line(std::iter::once(cmd).chain(args.iter().map(String::as_str)))
+    );
+    match Command::new(cmd).args(args).current_dir(dir).envs(env).status() {
+        Ok(status) if status.success() => {
+            outln!("> Testes concluídos com sucesso");
+            Ok(())
+        }
+        Ok(status) => {
+            outln!("> Testes falharam (status {status})");
+            Err(DxError::CheckFailed(format!("Testes falharam (status {status})")))
+        }
+        Err(e) => Err(DxError::Tool(format!("Erro ao executar comando de teste: {e}"))),
     }
 }
 
@@ -95,33 +222,49 @@ fn should_ignore(path: &Path) -> bool {
 
 /// Watch files in `dir` and re-run unit tests on changes.
 /// Detects the project stack automatically to choose the test command.
-pub fn watch_and_test(dir: Option<PathBuf>) {
+/// With `once` or in CI mode the tests run once and the command fails if they
+/// fail.
+pub fn watch_and_test(dir: Option<PathBuf>, once: bool) -> DxResult {
     let project_dir =
         dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
     let stack = Stack::detect(&project_dir);
-    let Some((cmd, args)) = stack.test_command(&project_dir) else {
-        eprintln!("Stack não reconhecida em {}", project_dir.display());
-        return;
+    let Some(command) = stack.test_command(&project_dir) else {
+        return Err(DxError::Unsupported(format!(
+            "Stack não reconhecida em {}",
+            project_dir.display()
+        )));
     };
 
-    println!("Stack detectada: {}", stack);
-    println!(
+    outln!("Stack detectada: {}", stack);
+    let mut env = BTreeMap::new();
+    let (cmd, args) = pinned(&project_dir, "test", command, &mut env);
+    if once {
+        return run_tests(&project_dir, &cmd, &args, &env);
+    }
+    if crate::ci::is_ci() {
+        outln!("Modo CI: executando os testes uma única vez (sem monitorar alterações).");
+        return run_tests(&project_dir, &cmd, &args, &env);
+    }
+    outln!(
         "Monitorando alterações em {} (Ctrl-C para sair)",
         project_dir.display()
     );
 
-    run_tests(&project_dir, &cmd, &args);
+    // Failures are reported and watching continues
+    if let Err(DxError::Tool(e)) = run_tests(&project_dir, &cmd, &args, &env) {
+        eprintln!("{e}");
+    }
 
     let (tx, rx) = channel();
 
     let mut watcher = recommended_watcher(move |res| {
         tx.send(res).ok();
     })
-    .expect("não foi possível iniciar watcher");
+    .map_err(|e| DxError::Tool(format!("não foi possível iniciar watcher: {e}")))?;
 
     watcher
         .watch(&project_dir, RecursiveMode::Recursive)
-        .expect("não foi possível observar diretório");
+        .map_err(|e| DxError::Tool(format!("não foi possível observar diretório: {e}")))?;
 
     const DEBOUNCE_MS: u64 = 500;
     let mut last_run = Instant::now();
@@ -132,18 +275,35 @@ pub fn watch_and_test(dir: Option<PathBuf>) {
                 if matches!(
                     event.kind,
                     EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
-                ) {
-                    if event.paths.iter().any(|p| !should_ignore(p))
-                        && last_run.elapsed() >= Duration::from_millis(DEBOUNCE_MS)
-                    {
-                        last_run = Instant::now();
-                        println!("Alterações detectadas. Executando testes...");
-                        run_tests(&project_dir, &cmd, &args);
+                ) && event.paths.iter().any(|p| !should_ignore(p))
+                    && last_run.elapsed() >= Duration::from_millis(DEBOUNCE_MS)
+                {
+                    last_run = Instant::now();
+                    outln!("Alterações detectadas. Executando testes...");
+                    if let Err(DxError::Tool(e)) = run_tests(&project_dir, &cmd, &args, &env) {
+                        eprintln!("{e}");
                     }
                 }
             }
             Err(e) => eprintln!("Erro do watcher: {e}"),
         }
     }
+    Ok(())
 }
 
+
+/// Run the integration tests of the project in `dir` once, with the `dx env`
+/// variables (Dev Services connections and dev-config) in the environment.
+pub fn run_integration(dir: &Path) -> DxResult {
+    let stack = Stack::detect(dir);
+    let Some(command) = stack.integration_command(dir) else {
+        return Err(DxError::Unsupported(format!(
+            "Stack não reconhecida em {}",
+            dir.display()
+        )));
+    };
+    outln!("Stack detectada: {}", stack);
+    let mut env = crate::shell_env::project_vars(dir);
+    let (cmd, args) = pinned(dir, "test:integration", command, &mut env);
+    run_tests(dir, &cmd, &args, &env)
+}
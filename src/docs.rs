@@ -0,0 +1,610 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Quickstart verification (`dx docs verify-quickstart`).
+//!
+//! The shell commands of the README's quickstart section (fenced `sh`,
+//! `bash`, `shell`, `console` or unlabeled blocks) are checked one by one.
+//! By default they are only linted: shell syntax (`sh -n`), the program being
+//! on `PATH` or in the project, `cd` targets existing and `dx` arguments
+//! parsing. With `--run` they are executed in order in a copy of the project,
+//! so documented steps can't touch the working tree.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+
+use crate::error::{DxError, DxResult, IoContext};
+
+/// Headings recognised as the quickstart section (lowercase).
+const QUICKSTART_HEADINGS: &[&str] = &[
+    "quickstart",
+    "quick start",
+    "início rápido",
+    "inicio rápido",
+    "getting started",
+    "primeiros passos",
+];
+
+/// Fence languages whose content is run by `sh`.
+const SHELL_LANGUAGES: &[&str] = &["", "sh", "bash", "shell", "console", "zsh"];
+
+/// Commands handled by the shell itself.
+const BUILTINS: &[&str] = &[
+    ".", ":", "[", "alias", "cd", "echo", "eval", "exec", "exit", "export", "false", "printf",
+    "pwd", "read", "set", "source", "test", "true", "unset",
+];
+
+/// A command of the quickstart, with its line in the README.
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub line: usize,
+    pub command: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Broken,
+    Skipped,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::Broken => "QUEBRADO",
+            Status::Skipped => "IGNORADO",
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Broken => "broken",
+            Status::Skipped => "skipped",
+        }
+    }
+}
+
+/// Heading text and level (`## Quickstart` -> (2, "Quickstart")).
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim()))
+}
+
+fn is_quickstart(title: &str, section: Option<&str>) -> bool {
+    let title = title.to_lowercase();
+    match section {
+        Some(wanted) => title.contains(&wanted.to_lowercase()),
+        None => QUICKSTART_HEADINGS.iter().any(|h| title.contains(h)),
+    }
+}
+
+/// Title of the quickstart section and its shell commands.
+pub fn extract(readme: &str, section: Option<&str>) -> Option<(String, Vec<Step>)> {
+    let mut title = None;
+    let mut level = 0;
+    let mut steps = Vec::new();
+    // (closing marker, runs in sh, `$ ` prompts) inside a fence
+    let mut fence: Option<(String, bool, bool)> = None;
+    let mut pending: Option<Step> = None;
+
+    for (index, line) in readme.lines().enumerate() {
+        let number = index + 1;
+        let trimmed = line.trim();
+
+        if let Some((marker, shell, console)) = &fence {
+            if trimmed.starts_with(marker.as_str()) {
+                fence = None;
+                if let Some(step) = pending.take() {
+                    steps.push(step);
+                }
+                continue;
+            }
+            if title.is_none() || !shell {
+                continue;
+            }
+            let mut text = trimmed;
+            if let Some(step) = pending.as_mut() {
+                let continued = text.strip_suffix('\\');
+                step.command.push(' ');
+                step.command.push_str(continued.unwrap_or(text).trim());
+                if continued.is_none() {
+                    steps.extend(pending.take());
+                }
+                continue;
+            }
+            if *console {
+                match text.strip_prefix("$ ") {
+                    Some(command) => text = command,
+                    None => continue,
+                }
+            } else if let Some(command) = text.strip_prefix("$ ") {
+                text = command;
+            }
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            match text.strip_suffix('\\') {
+                Some(start) => {
+                    pending = Some(Step {
+                        line: number,
+                        command: start.trim().to_string(),
+                    })
+                }
+                None => steps.push(Step {
+                    line: number,
+                    command: text.to_string(),
+                }),
+            }
+            continue;
+        }
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            let marker: String = trimmed
+                .chars()
+                .take_while(|c| *c == '`' || *c == '~')
+                .collect();
+            let language = trimmed[marker.len()..]
+                .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+                .next()
+                .unwrap_or("")
+                .to_lowercase();
+            let shell = SHELL_LANGUAGES.contains(&language.as_str());
+            fence = Some((marker, shell, language == "console"));
+            continue;
+        }
+
+        if let Some((depth, text)) = heading(line) {
+            if title.is_some() {
+                if depth <= level {
+                    break;
+                }
+            } else if is_quickstart(text, section) {
+                title = Some(text.to_string());
+                level = depth;
+            }
+        }
+    }
+    steps.extend(pending);
+    title.map(|t| (t, steps))
+}
+
+/// Split a command into words, honouring quotes (enough for linting).
+fn words(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_word = false;
+    let mut chars = command.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') => current.extend(chars.next()),
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, '\\') => {
+                current.extend(chars.next());
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, '&' | '|' | ';') => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+                if !matches!(
+                    words.last().map(String::as_str),
+                    Some("&&" | "||" | "|" | ";")
+                ) {
+                    words.push(
+                        match (c, chars.peek()) {
+                            ('&', Some('&')) | ('|', Some('|')) => {
+                                chars.next();
+                                if c == '&' { "&&" } else { "||" }
+                            }
+                            ('|', _) => "|",
+                            _ => ";",
+                        }
+                        .to_string(),
+                    );
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+/// Simple commands of a line (split on `&&`, `||`, `|` and `;`), without
+/// leading `VAR=value` assignments.
+fn simple_commands(command: &str) -> Vec<Vec<String>> {
+    words(command)
+        .split(|w| matches!(w.as_str(), "&&" | "||" | "|" | ";"))
+        .map(|words| {
+            words
+                .iter()
+                .skip_while(|w| {
+                    w.split_once('=').is_some_and(|(name, _)| {
+                        !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+                    })
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .filter(|words| !words.is_empty())
+        .collect()
+}
+
+/// Whether `cargo run` in `project_dir` builds `dx` (this repository's own README).
+fn cargo_runs_dx(project_dir: &Path) -> bool {
+    fs::read_to_string(project_dir.join("Cargo.toml")).is_ok_and(|manifest| {
+        manifest
+            .lines()
+            .any(|l| l.replace(' ', "") == "name=\"dx\"")
+    })
+}
+
+/// Arguments given to `dx`, for `dx ...` and `cargo run -- ...` in the dx repository.
+fn dx_args(words: &[String], project_dir: &Path) -> Option<Vec<String>> {
+    match words.first().map(String::as_str) {
+        Some("dx") => Some(words[1..].to_vec()),
+        Some("cargo") if words.get(1).is_some_and(|w| w == "run") && cargo_runs_dx(project_dir) => {
+            let separator = words.iter().position(|w| w == "--")?;
+            Some(words[separator + 1..].to_vec())
+        }
+        _ => None,
+    }
+}
+
+/// Directories a command creates (`mkdir`, `git clone`), so later `cd`s into
+/// them aren't reported when linting.
+fn created_dirs(words: &[String]) -> Vec<String> {
+    let args: Vec<&String> = words[1..].iter().filter(|w| !w.starts_with('-')).collect();
+    match words[0].as_str() {
+        "mkdir" => args.into_iter().cloned().collect(),
+        "git" if args.first().is_some_and(|w| *w == "clone") => match args[1..] {
+            [_, dir, ..] => vec![dir.clone()],
+            [url] => url
+                .trim_end_matches('/')
+                .rsplit(['/', ':'])
+                .next()
+                .map(|name| vec![name.trim_end_matches(".git").to_string()])
+                .unwrap_or_default(),
+            [] => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// Lint state: the directory the steps are in and those created by earlier steps.
+struct Lint<'a> {
+    project_dir: &'a Path,
+    cwd: PathBuf,
+    created: Vec<PathBuf>,
+}
+
+impl Lint<'_> {
+    /// Problem with one simple command, if any.
+    fn command(&mut self, words: &[String]) -> Option<String> {
+        let program = words[0].as_str();
+        if program == "cd" {
+            let target = words.get(1)?;
+            if target == "-" || target.starts_with('~') || target.contains('$') {
+                return None;
+            }
+            let next = self.cwd.join(target);
+            if !next.is_dir() && !self.created.iter().any(|d| next.starts_with(d)) {
+                return Some(format!("diretório '{target}' não existe"));
+            }
+            self.cwd = next;
+            return None;
+        }
+        let cwd = &self.cwd;
+        self.created
+            .extend(created_dirs(words).into_iter().map(|d| cwd.join(d)));
+        if program.contains('$') || program.contains('`') || BUILTINS.contains(&program) {
+            return None;
+        }
+        if let Some(args) = dx_args(words, self.project_dir) {
+            let argv = std::iter::once("dx".to_string()).chain(args);
+            if let Err(e) = crate::Cli::try_parse_from(argv) {
+                use clap::error::ErrorKind;
+                if !matches!(e.kind(), ErrorKind::DisplayHelp | ErrorKind::DisplayVersion) {
+                    let message = e.to_string();
+                    let first = message.lines().next().unwrap_or_default();
+                    return Some(format!(
+                        "argumentos do dx inválidos: {}",
+                        first.trim_start_matches("error: ")
+                    ));
+                }
+            }
+        }
+        // Paths like `./target/release/dx` may be produced by a previous step,
+        // and `dx` itself may not be installed yet
+        if !program.contains('/')
+            && program != "dx"
+            && crate::plugins::find_on_path(program).is_none()
+        {
+            return Some(format!("programa '{program}' não encontrado no PATH"));
+        }
+        None
+    }
+}
+
+/// `sh -n` syntax check; `None` when the command parses (or `sh` is unavailable).
+fn syntax_error(command: &str) -> Option<String> {
+    crate::plugins::find_on_path("sh")?;
+    let output = Command::new("sh")
+        .arg("-n")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    if output.status.success() {
+        return None;
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Some(format!(
+        "erro de sintaxe: {}",
+        stderr.lines().next().unwrap_or("sh -n falhou").trim()
+    ))
+}
+
+fn lint(steps: &[Step], project_dir: &Path) -> Vec<(Status, Option<String>)> {
+    let mut lint = Lint {
+        project_dir,
+        cwd: project_dir.to_path_buf(),
+        created: Vec::new(),
+    };
+    steps
+        .iter()
+        .map(|step| {
+            let problem = syntax_error(&step.command).or_else(|| {
+                simple_commands(&step.command)
+                    .iter()
+                    .find_map(|words| lint.command(words))
+            });
+            match problem {
+                Some(problem) => (Status::Broken, Some(problem)),
+                None => (Status::Ok, None),
+            }
+        })
+        .collect()
+}
+
+/// Copy the project to `sandbox`, skipping what `.gitignore` ignores and `.git`.
+fn copy_project(project_dir: &Path, sandbox: &Path) -> DxResult<()> {
+    let mut walker = ignore::WalkBuilder::new(project_dir);
+    walker
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| entry.file_name() != ".git");
+    for entry in walker.build().flatten() {
+        let Ok(rel) = entry.path().strip_prefix(project_dir) else {
+            continue;
+        };
+        let target = sandbox.join(rel);
+        if entry.file_type().is_some_and(|t| t.is_dir()) {
+            fs::create_dir_all(&target).context(format!("Erro ao criar {}", target.display()))?;
+        } else if entry.file_type().is_some_and(|t| t.is_file()) {
+            fs::copy(entry.path(), &target)
+                .context(format!("Erro ao copiar {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Run one step in `cwd` and return the working directory it leaves behind.
+fn run_step(command: &str, cwd: &Path, timeout: Duration) -> Result<PathBuf, String> {
+    let pwd_file = std::env::temp_dir().join(format!("dx-quickstart-pwd-{}", std::process::id()));
+    let script = format!(
+        "{command}\n__dx_status=$?\npwd > '{}'\nexit $__dx_status",
+        pwd_file.display()
+    );
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(&script)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("falha ao executar sh: {e}"))?;
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("tempo esgotado após {}s", timeout.as_secs()));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = std::io::Read::read_to_string(&mut pipe, &mut stderr);
+    }
+    let next = fs::read_to_string(&pwd_file)
+        .map(|p| PathBuf::from(p.trim()))
+        .unwrap_or_else(|_| cwd.to_path_buf());
+    let _ = fs::remove_file(&pwd_file);
+    if status.success() {
+        return Ok(next);
+    }
+    let code = status.code().map_or_else(
+        || "encerrado por sinal".to_string(),
+        |c| format!("código {c}"),
+    );
+    match stderr.lines().rev().find(|l| !l.trim().is_empty()) {
+        Some(last) => Err(format!("falhou ({code}): {}", last.trim())),
+        None => Err(format!("falhou ({code})")),
+    }
+}
+
+fn run(steps: &[Step], sandbox: &Path, timeout: Duration) -> Vec<(Status, Option<String>)> {
+    let mut cwd = sandbox.to_path_buf();
+    let mut failed = false;
+    steps
+        .iter()
+        .map(|step| {
+            // Later steps usually depend on earlier ones
+            if failed {
+                return (
+                    Status::Skipped,
+                    Some("não executado: um passo anterior falhou".to_string()),
+                );
+            }
+            match run_step(&step.command, &cwd, timeout) {
+                Ok(next) => {
+                    cwd = next;
+                    (Status::Ok, None)
+                }
+                Err(problem) => {
+                    failed = true;
+                    (Status::Broken, Some(problem))
+                }
+            }
+        })
+        .collect()
+}
+
+pub struct VerifyOptions {
+    pub readme: PathBuf,
+    pub section: Option<String>,
+    pub run: bool,
+    pub timeout: u64,
+}
+
+pub fn verify_quickstart(dir: Option<PathBuf>, opts: VerifyOptions) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !project_dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            project_dir.display()
+        )));
+    }
+    let readme_path = project_dir.join(&opts.readme);
+    if !readme_path.is_file() {
+        return Err(DxError::NotFound(format!(
+            "README não encontrado: {}",
+            readme_path.display()
+        )));
+    }
+    let content = fs::read_to_string(&readme_path)
+        .context(format!("Erro ao ler {}", readme_path.display()))?;
+    let Some((section, steps)) = extract(&content, opts.section.as_deref()) else {
+        return Err(DxError::NotFound(format!(
+            "Seção de quickstart não encontrada em {}{}",
+            opts.readme.display(),
+            opts.section
+                .as_deref()
+                .map(|s| format!(" (--section \"{s}\")"))
+                .unwrap_or_default()
+        )));
+    };
+
+    let mode = if opts.run { "run" } else { "lint" };
+    outln!(
+        "Verificando quickstart de {} (seção \"{section}\", {})\n",
+        opts.readme.display(),
+        if opts.run {
+            "execução em sandbox"
+        } else {
+            "lint, sem executar"
+        }
+    );
+
+    let results = if opts.run {
+        if crate::plugins::find_on_path("sh").is_none() {
+            return Err(DxError::Unsupported(
+                "--run requer 'sh' no PATH para executar os passos.".to_string(),
+            ));
+        }
+        let sandbox = std::env::temp_dir().join(format!("dx-quickstart-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&sandbox);
+        copy_project(&project_dir, &sandbox)?;
+        let results = run(&steps, &sandbox, Duration::from_secs(opts.timeout));
+        let _ = fs::remove_dir_all(&sandbox);
+        results
+    } else {
+        lint(&steps, &project_dir)
+    };
+
+    for (step, (status, detail)) in steps.iter().zip(&results) {
+        match detail {
+            Some(detail) => outln!(
+                "- [{}] linha {}: {} — {detail}",
+                status.label(),
+                step.line,
+                step.command
+            ),
+            None => outln!(
+                "- [{}] linha {}: {}",
+                status.label(),
+                step.line,
+                step.command
+            ),
+        }
+    }
+
+    crate::output::set_data(serde_json::json!({
+        "readme": opts.readme.display().to_string(),
+        "section": section,
+        "mode": mode,
+        "steps": steps
+            .iter()
+            .zip(&results)
+            .map(|(step, (status, detail))| serde_json::json!({
+                "line": step.line,
+                "command": step.command,
+                "status": status.key(),
+                "detail": detail,
+            }))
+            .collect::<Vec<_>>(),
+    }));
+
+    if steps.is_empty() {
+        outln!("Nenhum comando de shell na seção \"{section}\".");
+        return Ok(());
+    }
+    let broken = results.iter().filter(|(s, _)| *s == Status::Broken).count();
+    if broken == 0 {
+        outln!("\n{} passo(s) do quickstart verificados.", steps.len());
+        return Ok(());
+    }
+    Err(DxError::CheckFailed(format!(
+        "{broken} passo(s) do quickstart quebrado(s) em {}.",
+        opts.readme.display()
+    )))
+}
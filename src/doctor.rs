@@ -0,0 +1,492 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! `dx doctor`: whether this machine can run the project.
+//!
+//! Each check reports `ok`, `aviso` or `erro` with a hint to fix it:
+//! - the container engine (`docker version`, or Podman or nerdctl when there
+//!   is no Docker) and Docker Compose;
+//! - the toolchain of the detected stack (`node`, `python3`, `java`, `go`,
+//!   `rustc`, ...) against the version the project pins (`.nvmrc`,
+//!   `.python-version`, `rust-toolchain.toml`, the `go` directive of go.mod);
+//! - the host ports the Dev Services publish, free or taken by the project's
+//!   own containers;
+//! - the images of the Dev Services without a build for this machine's
+//!   architecture (emulated on Apple Silicon, see [`crate::arch`]);
+//! - free disk space;
+//! - the package registry of the stack (or its `[registries]` mirror), reached
+//!   through the proxy of `HTTPS_PROXY`/`HTTP_PROXY` when set.
+//!
+//! Any `erro` ends the command with exit code 1, so it can gate scripts.
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use reqwest::Method;
+use serde::Serialize;
+
+use crate::arch::{self, Arch};
+use crate::error::{DxError, DxResult};
+use crate::output::Render;
+use crate::pipeline::Stack;
+use crate::toolchain::{self, Installer, Pin, Tool};
+
+/// Below this much free disk, images and volumes won't fit (MiB).
+const DISK_ERROR_MB: u64 = 1024;
+/// Below this much free disk, pulling a few images may fill it (MiB).
+const DISK_WARNING_MB: u64 = 5 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Warning => "aviso",
+            Status::Error => "erro",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    /// `runtime`, `compose`, `toolchain`, `port`, `image`, `disk` or `network`
+    kind: &'static str,
+    name: String,
+    status: Status,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+}
+
+impl Check {
+    fn new(kind: &'static str, name: impl Into<String>, status: Status, detail: String) -> Check {
+        Check {
+            kind,
+            name: name.into(),
+            status,
+            detail,
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Check {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DoctorReport {
+    stack: &'static str,
+    checks: Vec<Check>,
+}
+
+impl DoctorReport {
+    fn count(&self, status: Status) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+}
+
+impl Render for DoctorReport {
+    fn render_text(&self) {
+        for check in &self.checks {
+            outln!(
+                "[{}] {}: {}",
+                check.status.label(),
+                check.name,
+                check.detail
+            );
+            if let Some(hint) = &check.hint {
+                outln!("    Dica: {hint}");
+            }
+        }
+        outln!(
+            "\n{} verificação(ões): {} ok, {} aviso(s), {} erro(s).",
+            self.checks.len(),
+            self.count(Status::Ok),
+            self.count(Status::Warning),
+            self.count(Status::Error)
+        );
+    }
+}
+
+/// Output of `command` when it ran and succeeded (its standard error when
+/// nothing went to standard output, as `java -version` does); `Err(None)`
+/// when it couldn't start, `Err(Some(stderr))` when it failed.
+fn run(command: &mut Command) -> Result<String, Option<String>> {
+    let output = command.stdin(Stdio::null()).output().map_err(|_| None)?;
+    let text = |bytes: &[u8]| String::from_utf8_lossy(bytes).trim().to_string();
+    if output.status.success() {
+        let stdout = text(&output.stdout);
+        Ok(if stdout.is_empty() {
+            text(&output.stderr)
+        } else {
+            stdout
+        })
+    } else {
+        Err(Some(text(&output.stderr)))
+    }
+}
+
+fn container_checks(project_dir: &Path) -> Vec<Check> {
+    let mut checks = Vec::new();
+    let docker = run(crate::remote::command(project_dir, "docker").args([
+        "version",
+        "--format",
+        "{{.Server.Version}}",
+    ]));
+    match docker {
+        Ok(version) => {
+            let engine = crate::remote::engine(project_dir)
+                .map(|e| format!(" em {}", e.describe()))
+                .unwrap_or_default();
+            checks.push(Check::new(
+                "runtime",
+                "Docker",
+                Status::Ok,
+                format!("{} (daemon em execução{engine})", version.trim()),
+            ));
+        }
+        Err(Some(stderr)) => checks.push(
+            Check::new(
+                "runtime",
+                "Docker",
+                Status::Error,
+                format!(
+                    "o daemon não responde: {}",
+                    stderr.lines().last().unwrap_or("sem detalhes")
+                ),
+            )
+            .hint("inicie o Docker (Docker Desktop, ou 'sudo systemctl start docker') e confira 'docker version'."),
+        ),
+        Err(None) => {
+            // Without Docker, the Dev Services run on Podman or nerdctl (see
+            // crate::compose::Runtime)
+            let other = [("Podman", "podman"), ("nerdctl", "nerdctl")]
+                .into_iter()
+                .find_map(|(name, program)| {
+                    run(Command::new(program).args(["version", "--format", "{{.Client.Version}}"]))
+                        .or_else(|_| run(Command::new(program).args(["--version"])))
+                        .ok()
+                        .map(|version| (name, program, version))
+                });
+            checks.push(match other {
+                Some((name, program, version)) => Check::new(
+                    "runtime",
+                    name,
+                    Status::Ok,
+                    format!(
+                        "{} (Docker não encontrado; o dx usa '{program} compose')",
+                        version.trim()
+                    ),
+                ),
+                None => Check::new(
+                    "runtime",
+                    "Docker",
+                    Status::Error,
+                    "nem docker, podman ou nerdctl encontrados no PATH".to_string(),
+                )
+                .hint("instale o Docker: https://docs.docker.com/get-docker/"),
+            });
+            return checks;
+        }
+    }
+    let compose =
+        run(crate::remote::command(project_dir, "docker").args(["compose", "version", "--short"]))
+            .map(|v| format!("{} (docker compose)", v.trim()))
+            .or_else(|_| {
+                run(Command::new("docker-compose").args(["version", "--short"]))
+                    .map(|v| format!("{} (docker-compose v1)", v.trim()))
+            });
+    checks.push(match compose {
+        Ok(version) => Check::new("compose", "Docker Compose", Status::Ok, version),
+        Err(_) => Check::new(
+            "compose",
+            "Docker Compose",
+            Status::Error,
+            "não encontrado".to_string(),
+        )
+        .hint("instale o plugin do Compose: https://docs.docker.com/compose/install/"),
+    });
+    checks
+}
+
+/// How to get `version` of `tool`: the command of the version manager on
+/// this machine, or of the tool's usual one.
+fn install_hint(tool: Tool, version: &str) -> String {
+    match Installer::find(tool) {
+        Some(installer) => format!(
+            "{}, ou rode 'dx doctor --fix'.",
+            installer.command_line(tool, version)
+        ),
+        None => toolchain::manual_hint(tool, version),
+    }
+}
+
+fn toolchain_check(project_dir: &Path, tool: Tool, pin: Option<&Pin>) -> Check {
+    let (program, _) = tool.program();
+    let check = |status, detail| Check::new("toolchain", program, status, detail);
+    match (toolchain::installed(project_dir, tool), pin) {
+        (None, Some(pin)) => check(Status::Error, "não encontrado no PATH".to_string())
+            .hint(install_hint(tool, &pin.version)),
+        (None, None) => check(Status::Error, "não encontrado no PATH".to_string())
+            .hint(format!("instale o {program}.")),
+        (Some(None), _) => check(Status::Warning, "versão não reconhecida".to_string()),
+        (Some(Some(installed)), Some(pin)) if !toolchain::satisfies(pin, &installed) => check(
+            Status::Warning,
+            format!(
+                "{installed} instalado; o projeto pede {} ({})",
+                pin.version, pin.source
+            ),
+        )
+        .hint(install_hint(tool, &pin.version)),
+        (Some(Some(installed)), Some(pin)) => check(
+            Status::Ok,
+            format!(
+                "{installed} (o projeto pede {} em {})",
+                pin.version, pin.source
+            ),
+        ),
+        (Some(Some(installed)), None) => check(Status::Ok, installed),
+    }
+}
+
+/// The toolchain of the stack and every other one the project pins; with
+/// `fix`, the missing or different pinned versions are installed first.
+fn toolchain_checks(project_dir: &Path, stack: Stack, fix: bool) -> Vec<Check> {
+    let mut tools: Vec<(Tool, Option<Pin>)> = Tool::for_stack(stack)
+        .map(|tool| (tool, toolchain::pin(project_dir, tool)))
+        .into_iter()
+        .collect();
+    for pin in toolchain::pins(project_dir) {
+        if !tools.iter().any(|(tool, _)| *tool == pin.tool) {
+            tools.push((pin.tool, Some(pin)));
+        }
+    }
+    let mut checks = Vec::new();
+    for (tool, pin) in tools {
+        let check = toolchain_check(project_dir, tool, pin.as_ref());
+        let installer = Installer::find(tool);
+        let (Some(pin), Some(installer), true) =
+            (&pin, installer, fix && check.status != Status::Ok)
+        else {
+            checks.push(check);
+            continue;
+        };
+        let (program, _) = tool.program();
+        outln!(
+            "Instalando {program} {} com {}...",
+            pin.version,
+            installer.name()
+        );
+        let installed = installer
+            .install(project_dir, tool, &pin.version)
+            .unwrap_or_else(|e| {
+                tracing::warn!("falha ao executar {}: {e}", installer.name());
+                false
+            });
+        let command = installer.command_line(tool, &pin.version);
+        let check = toolchain_check(project_dir, tool, Some(pin));
+        checks.push(match (installed, check.status) {
+            (false, _) => check.hint(format!("a instalação falhou; tente manualmente: {command}")),
+            (true, Status::Ok) => check,
+            // nvm and pyenv select versions per shell or directory
+            (true, _) => check.hint(format!(
+                "{} {} instalado com {}; ative-o no shell (ex.: 'nvm use', 'pyenv local {}') e rode 'dx doctor' de novo.",
+                program,
+                pin.version,
+                installer.name(),
+                pin.version
+            )),
+        });
+    }
+    checks
+}
+
+/// The host ports published by the generated manifest.
+fn port_checks(project_dir: &Path) -> Vec<Check> {
+    let compose = project_dir.join(".dx").join("docker-compose.yml");
+    let Ok(yaml) = std::fs::read_to_string(&compose) else {
+        return Vec::new();
+    };
+    // On a remote engine the ports are published there, not here
+    if crate::remote::engine(project_dir).is_some() {
+        return Vec::new();
+    }
+    let services = crate::dev_services::manifest_services(&yaml);
+    let mut containers = None;
+    let mut checks = Vec::new();
+    for service in &services {
+        for (host, _) in &service.ports {
+            let name = format!("Porta {host} ({})", service.name);
+            if crate::ports::free(*host) {
+                checks.push(Check::new("port", name, Status::Ok, "livre".to_string()));
+                continue;
+            }
+            let containers = containers.get_or_insert_with(|| {
+                crate::compose::Runtime::resolve(project_dir, None)
+                    .ok()
+                    .and_then(|runtime| {
+                        let runner = crate::compose::Runner::new(project_dir, runtime, &compose);
+                        crate::status::containers(&runner)
+                    })
+                    .unwrap_or_default()
+            });
+            let own = containers
+                .get(&service.name)
+                .is_some_and(|c| c.state == "running");
+            checks.push(if own {
+                Check::new(
+                    "port",
+                    name,
+                    Status::Ok,
+                    "em uso pelo próprio serviço".to_string(),
+                )
+            } else {
+                Check::new(
+                    "port",
+                    name,
+                    Status::Error,
+                    "em uso por outro processo".to_string(),
+                )
+                .hint(format!(
+                    "encerre o processo ('lsof -i :{host}') ou publique o serviço em outra porta no .dx/docker-compose.override.yml."
+                ))
+            });
+        }
+    }
+    checks
+}
+
+/// Images of the generated manifest that run emulated on this machine.
+fn image_checks(project_dir: &Path) -> Vec<Check> {
+    let compose = project_dir.join(".dx").join("docker-compose.yml");
+    let Ok(yaml) = std::fs::read_to_string(&compose) else {
+        return Vec::new();
+    };
+    let arch = Arch::host();
+    crate::dev_services::manifest_services(&yaml)
+        .into_iter()
+        .filter(|s| arch::emulated(&s.image, arch))
+        .map(|service| {
+            Check::new(
+                "image",
+                format!("Imagem {} ({})", service.image, service.name),
+                Status::Warning,
+                format!(
+                    "sem build para {}; roda emulada ({}) e mais lenta",
+                    arch.name(),
+                    arch::AMD64_PLATFORM
+                ),
+            )
+            .hint(format!(
+                "troque por uma imagem com build para {} no .dx/docker-compose.override.yml, se houver.",
+                arch.name()
+            ))
+        })
+        .collect()
+}
+
+fn disk_check(project_dir: &Path) -> Option<Check> {
+    let free = crate::resources::machine(project_dir).disk_free_mb?;
+    let detail = format!("{} livres", crate::clean::format_size(free * 1024 * 1024));
+    let status = match free {
+        f if f < DISK_ERROR_MB => Status::Error,
+        f if f < DISK_WARNING_MB => Status::Warning,
+        _ => Status::Ok,
+    };
+    let check = Check::new("disk", "Disco", status, detail);
+    Some(if status == Status::Ok {
+        check
+    } else {
+        check.hint(
+            "libere espaço com 'dx clean' e 'docker system prune' (imagens e volumes sem uso).",
+        )
+    })
+}
+
+/// Package registry of `stack`: (label, base URL with the configured mirror).
+fn registry(project_dir: &Path, stack: Stack) -> Option<(&'static str, String)> {
+    let mirrors = crate::settings::get(project_dir).registries;
+    let (label, default, mirror) = match stack {
+        Stack::Node => ("npm", "https://registry.npmjs.org", mirrors.npm),
+        Stack::Rust => ("crates.io", "https://crates.io", mirrors.crates),
+        Stack::Python => ("PyPI", "https://pypi.org", mirrors.pypi),
+        Stack::Go => ("Go proxy", "https://proxy.golang.org", mirrors.go),
+        Stack::Maven | Stack::Gradle => (
+            "Maven Central",
+            "https://repo1.maven.org/maven2",
+            mirrors.maven,
+        ),
+        Stack::Php => ("Packagist", "https://repo.packagist.org", mirrors.packagist),
+        Stack::Ruby => ("RubyGems", "https://rubygems.org", mirrors.rubygems),
+        Stack::Unknown => return None,
+    };
+    let base = mirror.unwrap_or_else(|| default.to_string());
+    Some((label, base.trim_end_matches('/').to_string()))
+}
+
+fn network_check(project_dir: &Path, stack: Stack) -> Option<Check> {
+    let (label, base) = registry(project_dir, stack)?;
+    crate::net::configure(&crate::settings::get(project_dir).network);
+    let proxy = ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()));
+    let via = proxy
+        .as_deref()
+        .map(|p| format!(" via proxy {p}"))
+        .unwrap_or_default();
+    let name = format!("Registry {label}");
+    // Any HTTP answer means the registry is reachable
+    Some(
+        match crate::net::send(Method::GET, &format!("{base}/"), &[], None) {
+            Ok((status, _)) => Check::new(
+                "network",
+                name,
+                Status::Ok,
+                format!("{base} respondeu HTTP {status}{via}"),
+            ),
+            Err(e) => Check::new(
+                "network",
+                name,
+                Status::Error,
+                format!("{base} inacessível{via}: {e}"),
+            )
+            .hint("verifique a conexão e o proxy (HTTPS_PROXY/NO_PROXY) ou configure um mirror em [registries] no dx.toml."),
+        },
+    )
+}
+
+/// `dx doctor`: check the container engine, toolchain, ports, disk and
+/// network this machine needs for the project at `dir`; with `fix`, install
+/// the pinned toolchain versions that are missing.
+pub fn doctor(dir: Option<PathBuf>, fix: bool, offline: bool) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    let stack = Stack::detect(&project_dir);
+    let mut checks = container_checks(&project_dir);
+    checks.extend(toolchain_checks(&project_dir, stack, fix));
+    checks.extend(port_checks(&project_dir));
+    checks.extend(image_checks(&project_dir));
+    checks.extend(disk_check(&project_dir));
+    if !offline {
+        checks.extend(network_check(&project_dir, stack));
+    }
+    let report = DoctorReport {
+        stack: stack.name(),
+        checks,
+    };
+    crate::output::render(&report);
+    let errors = report.count(Status::Error);
+    if errors > 0 {
+        return Err(DxError::CheckFailed(format!(
+            "O ambiente tem {errors} problema(s) a corrigir."
+        )));
+    }
+    Ok(())
+}
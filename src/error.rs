@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Error type shared by every command and its mapping to process exit codes.
+//!
+//! | code | kind            | meaning                                               |
+//! |------|-----------------|-------------------------------------------------------|
+//! | 0    |                 | success                                               |
+//! | 1    | `check_failed`  | a check ran and failed (policies, manifest drift)     |
+//! | 2    | `invalid_input` | invalid arguments or configuration (same as clap)     |
+//! | 3    | `not_found`     | expected file, directory or entry does not exist      |
+//! | 4    | `unsupported`   | operation not supported for the detected stack        |
+//! | 5    | `io`            | filesystem error                                      |
+//! | 6    | `network`       | registry/HTTP request failed                          |
+//! | 7    | `tool`          | external tool failed (docker, git, opa, test runner)  |
+//! | 8    | `aborted`       | cancelled by the user or confirmation required        |
+use std::io;
+
+pub type DxResult<T = ()> = Result<T, DxError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DxError {
+    #[error("{0}")]
+    CheckFailed(String),
+    #[error("{0}")]
+    InvalidInput(String),
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Unsupported(String),
+    #[error("{context}: {source}")]
+    Io {
+        context: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("{0}")]
+    Network(String),
+    #[error("{0}")]
+    Tool(String),
+    #[error("{0}")]
+    Aborted(String),
+}
+
+impl DxError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            DxError::CheckFailed(_) => 1,
+            DxError::InvalidInput(_) => 2,
+            DxError::NotFound(_) => 3,
+            DxError::Unsupported(_) => 4,
+            DxError::Io { .. } => 5,
+            DxError::Network(_) => 6,
+            DxError::Tool(_) => 7,
+            DxError::Aborted(_) => 8,
+        }
+    }
+
+    /// Stable identifier used in the JSON envelope.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DxError::CheckFailed(_) => "check_failed",
+            DxError::InvalidInput(_) => "invalid_input",
+            DxError::NotFound(_) => "not_found",
+            DxError::Unsupported(_) => "unsupported",
+            DxError::Io { .. } => "io",
+            DxError::Network(_) => "network",
+            DxError::Tool(_) => "tool",
+            DxError::Aborted(_) => "aborted",
+        }
+    }
+}
+
+/// Attach a human-readable context to I/O errors.
+pub trait IoContext<T> {
+    fn context(self, context: impl Into<String>) -> DxResult<T>;
+}
+
+impl<T> IoContext<T> for io::Result<T> {
+    fn context(self, context: impl Into<String>) -> DxResult<T> {
+        self.map_err(|source| DxError::Io {
+            context: context.into(),
+            source,
+        })
+    }
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! SDK settings of the feature flag services (Unleash, Flagsmith and the
+//! LaunchDarkly Relay Proxy), shared by `dx env` and the project's `.env`.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::dev_services::{DockerComposeConfig, ManifestService};
+use crate::error::{DxResult, IoContext};
+
+const ENV_FILE: &str = ".env";
+const HEADER: &str = "# Feature flags (dx dev-services)";
+
+/// Variables the SDKs read to reach `service` from the host; empty when it
+/// isn't a feature flag service.
+pub fn vars(service: &ManifestService) -> Vec<(String, String)> {
+    let var = |key: &str, value: String| (key.to_string(), value);
+    match service.name.as_str() {
+        "unleash" => {
+            // The first client token, without the other projects/environments
+            let token = service
+                .env
+                .get("INIT_CLIENT_API_TOKENS")
+                .and_then(|t| t.split(',').next())
+                .unwrap_or(crate::detectors::UNLEASH_CLIENT_TOKEN);
+            vec![
+                var(
+                    "UNLEASH_URL",
+                    format!("http://localhost:{}/api", service.host_port(4242)),
+                ),
+                var("UNLEASH_API_TOKEN", token.trim().to_string()),
+            ]
+        }
+        "flagsmith" => vec![var(
+            "FLAGSMITH_API_URL",
+            format!("http://localhost:{}/api/v1/", service.host_port(8000)),
+        )],
+        "ld-relay" => {
+            let url = format!("http://localhost:{}", service.host_port(8030));
+            vec![
+                var("LD_BASE_URI", url.clone()),
+                var("LD_STREAM_URI", url.clone()),
+                var("LD_EVENTS_URI", url),
+            ]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Write the SDK settings of the flag services in `config` into the project's
+/// `.env`. Returns the file when it changed.
+pub fn write_env_file(
+    project_dir: &Path,
+    config: &DockerComposeConfig,
+) -> DxResult<Option<PathBuf>> {
+    let settings: Vec<(String, String)> =
+        config.manifest_services().iter().flat_map(vars).collect();
+    if settings.is_empty() {
+        return Ok(None);
+    }
+    let path = project_dir.join(ENV_FILE);
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let content = crate::presets::upsert(&existing, &settings, HEADER);
+    if content == existing {
+        return Ok(None);
+    }
+    fs::write(&path, content).context(format!("Erro ao escrever {}", path.display()))?;
+    Ok(Some(path))
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Running git for commands that commit and push (`--create-pr`, `publish`).
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{DxError, DxResult};
+
+/// Run `git args` in `dir` and return its trimmed stdout; failures are
+/// [`DxError::Tool`] with git's message.
+pub fn run(dir: &Path, args: &[&str]) -> DxResult<String> {
+    tracing::debug!("executando: git {}", args.join(" "));
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| DxError::Tool(format!("Não foi possível executar git: {e}")))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(DxError::Tool(format!(
+            "git {} falhou: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Host (without user and port) and repository path of a remote URL; local
+/// remotes have no host and use their last two path segments.
+pub fn parse_remote(url: &str) -> (Option<String>, String) {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    let (host, path) = if let Some((_, rest)) = url.split_once("://") {
+        match rest.split_once('/') {
+            Some((host, path)) if !url.starts_with("file://") => (Some(host), path),
+            _ => (None, rest),
+        }
+    } else {
+        match url.split_once(':') {
+            // scp-like `git@host:owner/repo`; `C:\...` is a Windows path
+            Some((host, path)) if host.len() > 1 && !host.contains(['/', '\\']) => {
+                (Some(host), path)
+            }
+            _ => (None, url),
+        }
+    };
+    let host = host.map(|h| {
+        let h = h.rsplit_once('@').map_or(h, |(_, h)| h);
+        h.split(':').next().unwrap_or(h).to_string()
+    });
+    let path = if host.is_some() {
+        path.trim_start_matches('/').to_string()
+    } else {
+        let segments: Vec<&str> = path.split(['/', '\\']).filter(|s| !s.is_empty()).collect();
+        segments[segments.len().saturating_sub(2)..].join("/")
+    };
+    (host, path)
+}
@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! `.gitignore` management for dx artifacts.
+//!
+//! dx only edits `.gitignore` when asked to (`dx ignore setup` or
+//! `dx analyzer --write-gitignore`), never in `--ci` runs.
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::{DxError, DxResult, IoContext};
+
+/// Entry that ignores the whole `.dx` folder (the default).
+pub const DX_DIR: &str = ".dx";
+
+/// Generated artifacts, for projects that version `.dx` (manifests, policies)
+/// but not what dx produces locally.
+pub const ARTIFACTS: &[&str] = &[".dx/backups/", ".dx/cache/", ".dx/.env", ".dx/badges/*.svg", ".dx/analyzer-report.md", ".dx/instances/", ".dx/docker-compose.arch.yml"];
+
+fn gitignore_path(dir: &Path) -> PathBuf {
+    dir.join(".gitignore")
+}
+
+/// Compare patterns ignoring the optional leading and trailing `/`.
+fn normalize(pattern: &str) -> &str {
+    pattern.trim().trim_start_matches('/').trim_end_matches('/')
+}
+
+/// Whether `content` already has a line equivalent to `entry`.
+pub fn contains(content: &str, entry: &str) -> bool {
+    content.lines().any(|l| normalize(l) == normalize(entry))
+}
+
+/// Append the missing `entries` to `dir/.gitignore` (created if needed) and
+/// return the ones that were added.
+pub fn ensure_entries(dir: &Path, entries: &[String]) -> io::Result<Vec<String>> {
+    let path = gitignore_path(dir);
+    let mut content = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    let mut added = Vec::new();
+    for entry in entries {
+        if contains(&content, entry) || added.contains(entry) {
+            continue;
+        }
+        // Avoid gluing the new entry to a last line without newline
+        if !content.is_empty() && !content.ends_with(['\n', '\r']) {
+            content.push('\n');
+        }
+        content.push_str(entry);
+        content.push('\n');
+        added.push(entry.clone());
+    }
+    if !added.is_empty() {
+        fs::write(&path, content)?;
+    }
+    Ok(added)
+}
+
+fn project_dir(dir: Option<PathBuf>) -> PathBuf {
+    dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+}
+
+fn requested_entries(artifacts: bool, extra: Vec<String>) -> Vec<String> {
+    let mut entries: Vec<String> = if artifacts {
+        ARTIFACTS.iter().map(|e| e.to_string()).collect()
+    } else {
+        vec![DX_DIR.to_string()]
+    };
+    entries.extend(extra);
+    entries
+}
+
+/// `dx ignore setup`: add `.dx` (or only the generated artifacts) plus any
+/// extra patterns to `.gitignore`.
+pub fn setup(dir: Option<PathBuf>, artifacts: bool, extra: Vec<String>) -> DxResult {
+    let project_dir = project_dir(dir);
+    if !project_dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            project_dir.display()
+        )));
+    }
+    let entries = requested_entries(artifacts, extra);
+    let path = gitignore_path(&project_dir);
+    if crate::ci::is_ci() {
+        return Err(DxError::Aborted(format!(
+            "Modo CI: {} não foi alterado. Execute 'dx ignore setup' fora do CI (ou com DX_CI=0).",
+            path.display()
+        )));
+    }
+
+    let added = ensure_entries(&project_dir, &entries)
+        .context(format!("Erro ao atualizar {}", path.display()))?;
+    if added.is_empty() {
+        outln!("{} já contém todas as entradas.", path.display());
+    } else {
+        outln!("Entradas adicionadas em {}:", path.display());
+        for entry in &added {
+            outln!("- {entry}");
+        }
+    }
+    crate::output::set_data(serde_json::json!({
+        "path": path.display().to_string(),
+        "entries": entries,
+        "added": added,
+    }));
+    Ok(())
+}
+
+/// `dx ignore status`: show which dx entries are present in `.gitignore`.
+pub fn status(dir: Option<PathBuf>) -> DxResult {
+    let project_dir = project_dir(dir);
+    let path = gitignore_path(&project_dir);
+    let content = fs::read_to_string(&path).unwrap_or_default();
+
+    let mut known = vec![DX_DIR];
+    known.extend(ARTIFACTS);
+    let mut report = Vec::new();
+    outln!("{}:", path.display());
+    for entry in known {
+        let present = contains(&content, entry);
+        outln!("- [{}] {entry}", if present { "x" } else { " " });
+        report.push(serde_json::json!({ "entry": entry, "present": present }));
+    }
+    if !contains(&content, DX_DIR) {
+        outln!("\nDica: execute 'dx ignore setup' (ou '--artifacts' para versionar .dx e ignorar só os artefatos gerados).");
+    }
+    crate::output::set_data(serde_json::json!({
+        "path": path.display().to_string(),
+        "exists": path.is_file(),
+        "entries": report,
+    }));
+    Ok(())
+}
+
+/// `dx hook gitignore`: fail when the project has a `.dx` folder that
+/// `.gitignore` leaves versioned (neither `.dx` nor all the artifacts).
+pub fn check(dir: Option<PathBuf>) -> DxResult {
+    let project_dir = project_dir(dir);
+    let path = gitignore_path(&project_dir);
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let missing: Vec<&str> = if contains(&content, DX_DIR) {
+        Vec::new()
+    } else {
+        ARTIFACTS.iter().copied().filter(|e| !contains(&content, e)).collect()
+    };
+    let has_dx = project_dir.join(DX_DIR).is_dir();
+    crate::output::set_data(serde_json::json!({
+        "path": path.display().to_string(),
+        "dx_dir": has_dx,
+        "missing": missing,
+    }));
+    if !has_dx || missing.is_empty() {
+        outln!("- [OK] artefatos do dx ignorados pelo git");
+        return Ok(());
+    }
+    for entry in &missing {
+        outln!("- [FALTANDO] {entry}");
+    }
+    Err(DxError::CheckFailed(format!(
+        "{} não ignora os artefatos do dx; execute 'dx ignore setup' (ou '--artifacts').",
+        path.display()
+    )))
+}
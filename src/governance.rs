@@ -0,0 +1,747 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{DxError, DxResult, IoContext};
+use crate::{cel, dev_services, report, sarif, telemetry};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyKind {
+    Cel,
+    Rego,
+}
+
+impl PolicyKind {
+    fn label(self) -> &'static str {
+        match self {
+            PolicyKind::Cel => "cel",
+            PolicyKind::Rego => "rego",
+        }
+    }
+}
+
+pub struct Policy {
+    pub name: String,
+    pub kind: PolicyKind,
+    pub path: PathBuf,
+    /// First comment line of the policy file, used as the violation message
+    pub description: Option<String>,
+    pub source: String,
+}
+
+pub enum Outcome {
+    Passed,
+    Failed(Vec<String>),
+    Skipped(String),
+    Error(String),
+}
+
+pub struct PolicyResult {
+    pub policy: Policy,
+    pub outcome: Outcome,
+}
+
+impl PolicyResult {
+    /// Stable JSON shape used by `dx governance check --output json`.
+    pub fn to_json(&self) -> Value {
+        let (outcome, messages) = match &self.outcome {
+            Outcome::Passed => ("passed", Vec::new()),
+            Outcome::Failed(m) => ("failed", m.clone()),
+            Outcome::Skipped(reason) => ("skipped", vec![reason.clone()]),
+            Outcome::Error(e) => ("error", vec![e.clone()]),
+        };
+        serde_json::json!({
+            "name": self.policy.name,
+            "kind": self.policy.kind.label(),
+            "path": self.policy.path.display().to_string(),
+            "description": self.policy.description,
+            "outcome": outcome,
+            "messages": messages,
+        })
+    }
+}
+
+fn project_dir(dir: Option<PathBuf>) -> PathBuf {
+    dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+}
+
+pub fn policies_dir(project_dir: &Path) -> PathBuf {
+    project_dir.join(".dx").join("policies")
+}
+
+/// Load `*.cel` and `*.rego` policies from `.dx/policies` (including synced
+/// bundles in subdirectories), sorted by path.
+pub fn load_policies(project_dir: &Path) -> Vec<Policy> {
+    let root = policies_dir(project_dir);
+    let mut policies = Vec::new();
+    collect_policies(&root, &root, &mut policies);
+    policies.sort_by(|a, b| a.path.cmp(&b.path));
+    policies
+}
+
+fn collect_policies(root: &Path, dir: &Path, policies: &mut Vec<Policy>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(ft) = entry.file_type() else { continue };
+        if ft.is_dir() {
+            collect_policies(root, &path, policies);
+            continue;
+        }
+        let kind = match path.extension().and_then(|e| e.to_str()) {
+            Some("cel") => PolicyKind::Cel,
+            Some("rego") => PolicyKind::Rego,
+            _ => continue,
+        };
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        // Policies from synced bundles are named after their subdirectory, e.g. `org/no-latest`
+        let name = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .with_extension("")
+            .to_string_lossy()
+            .replace('\\', "/");
+        let description = source
+            .lines()
+            .map(str::trim)
+            .take_while(|l| l.is_empty() || l.starts_with('#') || l.starts_with("//"))
+            .find_map(|l| {
+                let text = l.trim_start_matches('#').trim_start_matches("//").trim();
+                (!text.is_empty()).then(|| text.to_string())
+            });
+        policies.push(Policy {
+            name,
+            kind,
+            path,
+            description,
+            source,
+        });
+    }
+}
+
+/// Strip comment lines from a CEL policy and join the rest into one expression.
+fn cel_expression(source: &str) -> String {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with("//"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn evaluate_cel(policy: &Policy, model: &Value) -> Outcome {
+    let expr = cel_expression(&policy.source);
+    if expr.is_empty() {
+        return Outcome::Error("expressão vazia".to_string());
+    }
+    match cel::evaluate(&expr, model) {
+        Ok(Value::Bool(true)) => Outcome::Passed,
+        Ok(Value::Bool(false)) => Outcome::Failed(vec![
+            policy
+                .description
+                .clone()
+                .unwrap_or_else(|| format!("expressão avaliada como false: {expr}")),
+        ]),
+        Ok(other) => Outcome::Error(format!("a expressão deve retornar bool, obtido: {other}")),
+        Err(e) => Outcome::Error(e),
+    }
+}
+
+/// Evaluate a Rego policy with the `opa` CLI. Policies must declare `package dx`
+/// and a `deny` set of messages; an empty set means the policy passed.
+fn evaluate_rego(policy: &Policy, model: &Value) -> Outcome {
+    tracing::debug!(policy = %policy.path.display(), "executando: opa eval data.dx.deny");
+    let child = Command::new("opa")
+        .arg("eval")
+        .arg("--stdin-input")
+        .arg("--format")
+        .arg("json")
+        .arg("--data")
+        .arg(&policy.path)
+        .arg("data.dx.deny")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(c) => c,
+        Err(_) => {
+            return Outcome::Skipped(
+                "'opa' não encontrado no PATH (necessário para policies Rego)".to_string(),
+            );
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(model.to_string().as_bytes());
+    }
+    let output = match child.wait_with_output() {
+        Ok(o) => o,
+        Err(e) => return Outcome::Error(format!("falha ao executar opa: {e}")),
+    };
+    if !output.status.success() {
+        return Outcome::Error(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let parsed: Value = match serde_json::from_slice(&output.stdout) {
+        Ok(v) => v,
+        Err(e) => return Outcome::Error(format!("saída inválida do opa: {e}")),
+    };
+    let deny = parsed
+        .pointer("/result/0/expressions/0/value")
+        .cloned()
+        .unwrap_or(Value::Array(Vec::new()));
+    let messages: Vec<String> = match deny {
+        Value::Array(items) => items
+            .into_iter()
+            .map(|v| v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string()))
+            .collect(),
+        other => return Outcome::Error(format!("data.dx.deny deve ser um conjunto: {other}")),
+    };
+    if messages.is_empty() {
+        Outcome::Passed
+    } else {
+        Outcome::Failed(messages)
+    }
+}
+
+/// Evaluate every policy of `project_dir` against its analyzer JSON model.
+pub fn evaluate_policies(project_dir: &Path) -> Vec<PolicyResult> {
+    let ds_config = dev_services::detect_dependencies(project_dir);
+    let model = report::build_analyzer_model(project_dir, &ds_config);
+    load_policies(project_dir)
+        .into_iter()
+        .map(|policy| {
+            let outcome = match policy.kind {
+                PolicyKind::Cel => evaluate_cel(&policy, &model),
+                PolicyKind::Rego => evaluate_rego(&policy, &model),
+            };
+            PolicyResult { policy, outcome }
+        })
+        .collect()
+}
+
+/// Policy results as SARIF: one rule per policy, one result per violation
+/// (located at the policy file) and a notification per policy that could not
+/// be evaluated.
+fn sarif_log(project_dir: &Path, results: &[PolicyResult]) -> sarif::Log {
+    let mut log = sarif::Log::default();
+    for r in results {
+        let id = format!("policy/{}", r.policy.name);
+        let path = r.policy.path.strip_prefix(project_dir).unwrap_or(&r.policy.path);
+        match &r.outcome {
+            Outcome::Failed(messages) => log.findings.extend(messages.iter().map(|m| sarif::Finding {
+                rule: id.clone(),
+                level: sarif::Level::Error,
+                message: m.clone(),
+                path: path.to_path_buf(),
+                line: 1,
+            })),
+            Outcome::Error(e) => log.notifications.push(format!("{}: {e}", r.policy.name)),
+            Outcome::Passed | Outcome::Skipped(_) => {}
+        }
+        log.rules.push(sarif::Rule {
+            description: r.policy.description.clone().unwrap_or_else(|| r.policy.name.clone()),
+            name: r.policy.name.clone(),
+            id,
+            level: sarif::Level::Error,
+            security: false,
+        });
+    }
+    log
+}
+
+/// Run all policies and print the results (and write them as SARIF to
+/// `sarif`). Fails if any policy failed or could not be evaluated.
+pub fn check(dir: Option<PathBuf>, sarif: Option<PathBuf>) -> DxResult {
+    let project_dir = project_dir(dir);
+    outln!("Governança leve, guardrails fortes");
+    outln!("Policies em: {}\n", policies_dir(&project_dir).display());
+
+    let results = evaluate_policies(&project_dir);
+    crate::output::set_data(serde_json::json!({
+        "policies": results.iter().map(PolicyResult::to_json).collect::<Vec<_>>(),
+    }));
+    let write_sarif = || match &sarif {
+        Some(path) => sarif_log(&project_dir, &results).write(&project_dir.join(path)),
+        None => Ok(()),
+    };
+    if results.is_empty() {
+        write_sarif()?;
+        outln!("Nenhuma policy encontrada.");
+        outln!("Dica: crie arquivos .cel ou .rego em .dx/policies/ para codificar seus guardrails.");
+        return Ok(());
+    }
+
+    let mut failed = 0usize;
+    for r in &results {
+        let label = format!("{} ({})", r.policy.name, r.policy.kind.label());
+        match &r.outcome {
+            Outcome::Passed => outln!("- [OK] {label}"),
+            Outcome::Failed(messages) => {
+                failed += 1;
+                outln!("- [FALHOU] {label}");
+                for m in messages {
+                    outln!("    {m}");
+                }
+            }
+            Outcome::Skipped(reason) => outln!("- [IGNORADA] {label}: {reason}"),
+            Outcome::Error(e) => {
+                failed += 1;
+                outln!("- [ERRO] {label}: {e}");
+            }
+        }
+    }
+
+    outln!(
+        "\nPolicies avaliadas: {} | falhas: {}",
+        results.len(),
+        failed
+    );
+    write_sarif()?;
+    if failed > 0 {
+        return Err(DxError::CheckFailed(format!("{failed} policy(s) com falha ou erro.")));
+    }
+    Ok(())
+}
+
+/// Shared policy bundle pinned by `dx governance sync`, stored in `.dx/governance.json`.
+#[derive(Serialize, Deserialize, Default)]
+struct SyncConfig {
+    source: Option<String>,
+    version: Option<String>,
+}
+
+/// Subdirectory of `.dx/policies` that receives the synced bundle.
+const SHARED_DIR: &str = "shared";
+
+fn sync_config_path(project_dir: &Path) -> PathBuf {
+    project_dir.join(".dx").join("governance.json")
+}
+
+impl SyncConfig {
+    fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self).unwrap())
+    }
+}
+
+/// Cache directory name of a source or version: readable, plus a hash so that
+/// names differing only in punctuation (`v1.2` and `v1-2`) get their own.
+fn cache_key(text: &str) -> String {
+    let slug: String = text
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let hash = &crate::sha256::hex(text.as_bytes())[..16];
+    match slug.trim_matches('-') {
+        "" => hash.to_string(),
+        slug => format!("{slug}-{hash}"),
+    }
+}
+
+/// Whether the git pin `version` is a commit (full or abbreviated SHA), the
+/// only kind of ref that can't move.
+fn is_commit(version: &str) -> bool {
+    (7..=40).contains(&version.len()) && version.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn is_http_source(source: &str) -> bool {
+    (source.starts_with("http://") || source.starts_with("https://")) && !source.ends_with(".git")
+}
+
+fn is_policy_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("cel") | Some("rego")
+    )
+}
+
+/// Copy every policy file under `from` into `to`, preserving relative paths.
+/// Symlinks are skipped: they could point anywhere on this machine.
+fn copy_policies(from: &Path, to: &Path) -> std::io::Result<usize> {
+    let mut copied = 0;
+    for entry in fs::read_dir(from)?.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            tracing::warn!("link simbólico ignorado no bundle: {}", path.display());
+            continue;
+        }
+        if file_type.is_dir() {
+            copied += copy_policies(&path, &to.join(&name))?;
+        } else if is_policy_file(&path) {
+            fs::create_dir_all(to)?;
+            fs::copy(&path, to.join(&name))?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+fn git(args: &[&str], cwd: Option<&Path>) -> Result<String, String> {
+    tracing::debug!("executando: git {}", args.join(" "));
+    let mut cmd = Command::new("git");
+    cmd.args(args);
+    if let Some(dir) = cwd {
+        cmd.current_dir(dir);
+    }
+    let output = cmd
+        .output()
+        .map_err(|e| format!("não foi possível executar git: {e}"))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Fetch a git bundle into `dest`; returns the resolved commit.
+fn fetch_git(source: &str, version: Option<&str>, dest: &Path) -> Result<String, String> {
+    let dest_str = dest.to_string_lossy().to_string();
+    let shallow = match version {
+        Some(v) => git(
+            &[
+                "clone", "--quiet", "--depth", "1", "--branch", v, "--", source, &dest_str,
+            ],
+            None,
+        ),
+        None => git(
+            &["clone", "--quiet", "--depth", "1", "--", source, &dest_str],
+            None,
+        ),
+    };
+    if shallow.is_err() {
+        // A pinned commit SHA cannot be cloned with --branch: fall back to a full clone
+        let _ = fs::remove_dir_all(dest);
+        git(&["clone", "--quiet", "--", source, &dest_str], None)?;
+        if let Some(v) = version {
+            // `--` alone would make `v` a path: the revision goes between the two
+            git(
+                &["checkout", "--quiet", "--end-of-options", v, "--"],
+                Some(dest),
+            )?;
+        }
+    }
+    let commit = git(&["rev-parse", "HEAD"], Some(dest))?;
+    let _ = fs::remove_dir_all(dest.join(".git"));
+    Ok(commit)
+}
+
+/// Fetch an HTTP bundle into `dest`. The endpoint must return
+/// `{"version": "...", "policies": {"<file>.cel": "<source>", ...}}`; a
+/// `{version}` placeholder in the URL is replaced by the pinned version.
+fn fetch_http(source: &str, version: Option<&str>, dest: &Path) -> Result<String, String> {
+    let url = match version {
+        Some(v) => source.replace("{version}", v),
+        None => source.replace("{version}", "latest"),
+    };
+    tracing::debug!(url = %url, "baixando bundle de policies");
+    let body = crate::net::fetch_text(&url).map_err(|e| format!("falha ao baixar {url}: {e}"))?;
+    let bundle: Value =
+        serde_json::from_str(&body).map_err(|e| format!("bundle inválido em {url}: {e}"))?;
+    let bundle_version = bundle
+        .get("version")
+        .and_then(|v| v.as_str())
+        .ok_or("bundle sem campo 'version'")?
+        .to_string();
+    if let Some(v) = version
+        && v != bundle_version
+    {
+        return Err(format!(
+            "versão do bundle ({bundle_version}) difere da versão fixada ({v})"
+        ));
+    }
+    let policies = bundle
+        .get("policies")
+        .and_then(|p| p.as_object())
+        .ok_or("bundle sem campo 'policies'")?;
+    fs::create_dir_all(dest).map_err(|e| e.to_string())?;
+    for (file, content) in policies {
+        let file_path = Path::new(file);
+        // Never let a bundle write outside its cache directory
+        if file_path.is_absolute()
+            || file_path
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+            || !is_policy_file(file_path)
+        {
+            continue;
+        }
+        let target = dest.join(file_path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::write(&target, content.as_str().unwrap_or_default()).map_err(|e| e.to_string())?;
+    }
+    Ok(bundle_version)
+}
+
+/// Fetch (or reuse from cache) the shared policy bundle and install it under
+/// `.dx/policies/shared`.
+pub fn sync(dir: Option<PathBuf>, source: Option<String>, version: Option<String>) -> DxResult {
+    let project_dir = project_dir(dir);
+    crate::net::configure(&crate::settings::load(&project_dir)?.network);
+    let config_path = sync_config_path(&project_dir);
+    let mut config = SyncConfig::load(&config_path);
+    if let Some(s) = source {
+        // A new source invalidates the previous pin unless a version was given
+        if config.source.as_deref() != Some(s.as_str()) {
+            config.version = None;
+        }
+        config.source = Some(s);
+    }
+    if version.is_some() {
+        config.version = version;
+    }
+    let Some(source) = config.source.clone() else {
+        return Err(DxError::InvalidInput(
+            "Nenhuma origem de policies configurada.\nUse: dx governance sync --source <url-git-ou-http> [--version <versão>]".to_string(),
+        ));
+    };
+
+    // Given to git and to the bundle URL: neither may pass for an option
+    if source.starts_with('-') {
+        return Err(DxError::InvalidInput(format!(
+            "Origem de policies inválida: '{source}'."
+        )));
+    }
+    if let Some(v) = config.version.as_deref().filter(|v| v.starts_with('-')) {
+        return Err(DxError::InvalidInput(format!(
+            "Versão de policies inválida: '{v}' (use uma tag, branch ou commit)."
+        )));
+    }
+
+    let bundle_cache = crate::cache::category_dir("governance").join(cache_key(&source));
+    let cached = config
+        .version
+        .as_ref()
+        .map(|v| bundle_cache.join(cache_key(v)))
+        .filter(|p| p.is_dir());
+    // Branches (and tags) can move, so a git pin other than a commit is
+    // fetched again; the cached copy only serves when that fails
+    let reusable = config
+        .version
+        .as_deref()
+        .is_some_and(|v| is_http_source(&source) || is_commit(v));
+    let reused = cached
+        .clone()
+        .zip(config.version.clone())
+        .filter(|_| reusable);
+
+    let (resolved, bundle_dir) = if let Some((path, v)) = reused {
+        outln!("Usando bundle em cache: {} @ {}", source, v);
+        (v, path)
+    } else {
+        outln!("Baixando bundle de policies: {}", source);
+        let staging = bundle_cache.join(format!(".download-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&staging);
+        if let Some(parent) = staging.parent() {
+            fs::create_dir_all(parent)
+                .context(format!("Erro ao criar cache em {}", parent.display()))?;
+        }
+        let fetched = crate::progress::phase("Baixando bundle de policies", || {
+            if is_http_source(&source) {
+                fetch_http(&source, config.version.as_deref(), &staging)
+            } else {
+                fetch_git(&source, config.version.as_deref(), &staging)
+            }
+        });
+        match fetched {
+            Ok(resolved) => {
+                // Keep the user-facing pin (tag/branch) when given; otherwise pin what was resolved
+                let pin = config.version.clone().unwrap_or(resolved);
+                let final_dir = bundle_cache.join(cache_key(&pin));
+                let _ = fs::remove_dir_all(&final_dir);
+                fs::rename(&staging, &final_dir)
+                    .context(format!("Erro ao gravar cache em {}", final_dir.display()))?;
+                (pin, final_dir)
+            }
+            Err(e) => {
+                let _ = fs::remove_dir_all(&staging);
+                let message = format!("Erro ao sincronizar policies: {e}");
+                match (cached, config.version.clone()) {
+                    (Some(path), Some(v)) => {
+                        tracing::warn!("{message}; usando o bundle em cache de {v}");
+                        (v, path)
+                    }
+                    _ if is_http_source(&source) => return Err(DxError::Network(message)),
+                    _ => return Err(DxError::Tool(message)),
+                }
+            }
+        }
+    };
+
+    let shared = policies_dir(&project_dir).join(SHARED_DIR);
+    let _ = fs::remove_dir_all(&shared);
+    let copied = copy_policies(&bundle_dir, &shared)
+        .context(format!("Erro ao instalar policies em {}", shared.display()))?;
+
+    config.version = Some(resolved.clone());
+    config
+        .save(&config_path)
+        .context(format!("Erro ao salvar {}", config_path.display()))?;
+
+    crate::output::set_data(serde_json::json!({
+        "source": source,
+        "version": resolved,
+        "installed": copied,
+        "path": shared.display().to_string(),
+    }));
+    outln!(
+        "Policies sincronizadas: {} arquivo(s) em {} (versão {})",
+        copied,
+        shared.display(),
+        resolved
+    );
+    outln!("Versão fixada em {}", config_path.display());
+    Ok(())
+}
+
+/// Files under `.dx` that users may edit by hand; never reported as drift.
+const MANIFEST_ALLOWLIST: [&str; 2] = crate::compose::OVERRIDE_FILES;
+
+/// 1-based number of the first line that differs between two texts.
+fn first_diff_line(a: &str, b: &str) -> usize {
+    let mut la = a.lines();
+    let mut lb = b.lines();
+    let mut n = 1;
+    loop {
+        match (la.next(), lb.next()) {
+            (Some(x), Some(y)) if x == y => n += 1,
+            _ => return n,
+        }
+    }
+}
+
+fn list_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(ft) = entry.file_type() else { continue };
+        if ft.is_dir() {
+            list_files(&entry.path(), out);
+        } else if ft.is_file() {
+            out.push(entry.path());
+        }
+    }
+}
+
+/// Regenerate the compose and telemetry artifacts in memory and compare them to
+/// the files in `.dx`. Fails if any of them diverge.
+pub fn verify_manifests(dir: Option<PathBuf>) -> DxResult {
+    let project_dir = project_dir(dir);
+    let dx_dir = project_dir.join(".dx");
+    outln!("Verificando manifestos em: {}\n", dx_dir.display());
+
+    if !dx_dir.join("docker-compose.yml").exists() {
+        outln!("Nenhum manifesto encontrado em .dx. Gere com: dx dev-services");
+        crate::output::set_data(
+            serde_json::json!({ "checked": 0, "allowed": [], "derived": [], "drift": [] }),
+        );
+        return Ok(());
+    }
+
+    let (expected, _) = telemetry::render(&project_dir);
+    let normalize = |s: &str| s.replace("\r\n", "\n");
+    // (path relative to .dx, status, first differing line)
+    let mut drift: Vec<(String, &str, Option<usize>)> = Vec::new();
+    let mut allowed: Vec<String> = Vec::new();
+    let mut derived: Vec<String> = Vec::new();
+
+    for f in &expected {
+        let path = dx_dir.join(&f.path);
+        let shown = f.path.display().to_string().replace('\\', "/");
+        match fs::read_to_string(&path) {
+            Ok(actual) => {
+                let (actual, wanted) = (normalize(&actual), normalize(&f.content));
+                if actual != wanted {
+                    let line = first_diff_line(&actual, &wanted);
+                    drift.push((shown, "modified", Some(line)));
+                }
+            }
+            Err(_) => drift.push((shown, "missing", None)),
+        }
+    }
+
+    // Anything else under .dx/telemetry, or an extra compose file, was not generated by dx
+    let mut present = Vec::new();
+    list_files(&dx_dir.join("telemetry"), &mut present);
+    if let Ok(entries) = fs::read_dir(&dx_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with("docker-compose") && entry.path().is_file() {
+                present.push(entry.path());
+            }
+        }
+    }
+    for path in present {
+        let Ok(rel) = path.strip_prefix(&dx_dir) else { continue };
+        let shown = rel.display().to_string().replace('\\', "/");
+        if MANIFEST_ALLOWLIST.contains(&shown.as_str()) {
+            outln!("- [PERMITIDO] {shown}");
+            allowed.push(shown);
+            continue;
+        }
+        if crate::compose::RUN_FILES.contains(&shown.as_str()) {
+            outln!("- [DERIVADO] {shown} (gravado pelo dev-services run)");
+            derived.push(shown);
+            continue;
+        }
+        if !expected.iter().any(|f| f.path == rel) {
+            drift.push((shown, "unexpected", None));
+        }
+    }
+
+    crate::output::set_data(serde_json::json!({
+        "checked": expected.len(),
+        "allowed": allowed,
+        "derived": derived,
+        "drift": drift
+            .iter()
+            .map(|(path, status, line)| serde_json::json!({ "path": path, "status": status, "line": line }))
+            .collect::<Vec<_>>(),
+    }));
+
+    if drift.is_empty() {
+        outln!("- [OK] {} arquivo(s) idênticos ao que o dx geraria", expected.len());
+        return Ok(());
+    }
+
+    for (path, status, line) in &drift {
+        let detail = match (*status, line) {
+            ("modified", Some(line)) => format!("modificado (primeira diferença na linha {line})"),
+            ("missing", _) => "ausente".to_string(),
+            _ => "arquivo não gerado pelo dx".to_string(),
+        };
+        outln!("- [DIVERGENTE] {path}: {detail}");
+    }
+    outln!("\nPara regenerar: dx dev-services | Ajustes locais: use .dx/docker-compose.override.yml");
+    Err(DxError::CheckFailed(format!(
+        "Os manifestos em .dx divergem do que o dx geraria ({} problema(s)).",
+        drift.len()
+    )))
+}
@@ -0,0 +1,283 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! `dx dev-services telemetry export-dashboards --to <grafana-url>`: promotes
+//! the telemetry dashboards of the project to a team Grafana (self-hosted or
+//! Grafana Cloud).
+//!
+//! The dashboards are read from `.dx/telemetry/grafana/dashboards`, so local
+//! edits go along, and saved through the HTTP API with `overwrite`: each one
+//! keeps its `uid` (`dx-<file name>` when it has none), so exporting again
+//! updates the same dashboard instead of creating a copy. The datasources the
+//! dashboards use by name (`.dx/telemetry/grafana/provisioning/datasources`)
+//! are created when the instance doesn't have them; existing ones are kept,
+//! as the team's point at its own backends while the generated ones point at
+//! the local containers. The token (a service account token with the Editor
+//! role) comes from `GRAFANA_TOKEN`.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use reqwest::Method;
+use serde_json::{Value, json};
+
+use crate::error::{DxError, DxResult, IoContext};
+
+const TOKEN_ENV: &str = "GRAFANA_TOKEN";
+const DASHBOARDS_DIR: &str = "telemetry/grafana/dashboards";
+const DATASOURCES_DIR: &str = "telemetry/grafana/provisioning/datasources";
+/// Grafana limits dashboard and folder uids to 40 characters
+const MAX_UID: usize = 40;
+
+pub struct ExportOptions {
+    /// Base URL of the Grafana instance
+    pub to: String,
+    /// Folder the dashboards go to (created if missing); `None`: General
+    pub folder: Option<String>,
+}
+
+/// A Grafana instance reached through its HTTP API.
+struct Grafana {
+    url: String,
+    token: String,
+}
+
+impl Grafana {
+    fn new(url: &str) -> DxResult<Grafana> {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(DxError::InvalidInput(format!(
+                "--to espera a URL do Grafana (ex.: https://time.grafana.net), recebido: '{url}'."
+            )));
+        }
+        let token = std::env::var(TOKEN_ENV)
+            .ok()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| {
+                DxError::InvalidInput(format!(
+                    "Token do Grafana não encontrado: defina {TOKEN_ENV}.\nDica: crie um token de service account com o papel Editor em Administration > Service accounts."
+                ))
+            })?;
+        Ok(Grafana {
+            url: url.trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    /// Call the API; returns the status and the JSON response.
+    fn call(&self, method: Method, path: &str, body: Option<&Value>) -> DxResult<(u16, Value)> {
+        let url = format!("{}{path}", self.url);
+        let authorization = format!("Bearer {}", self.token);
+        let headers = [
+            ("Authorization", authorization.as_str()),
+            ("Accept", "application/json"),
+        ];
+        let (status, response) = crate::net::send(method, &url, &headers, body)
+            .map_err(|e| DxError::Network(format!("Falha ao acessar o Grafana em {url}: {e}")))?;
+        let response = serde_json::from_str(&response).unwrap_or(Value::String(response));
+        match status {
+            401 | 403 => Err(DxError::Network(format!(
+                "O Grafana recusou o token (HTTP {status}): {}\nDica: confira {TOKEN_ENV}; o service account precisa do papel Editor.",
+                message(&response)
+            ))),
+            _ => Ok((status, response)),
+        }
+    }
+}
+
+fn message(response: &Value) -> String {
+    response
+        .get("message")
+        .and_then(|m| m.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| response.to_string())
+}
+
+fn refused(what: &str, status: u16, response: &Value) -> DxError {
+    DxError::Network(format!(
+        "O Grafana recusou {what} (HTTP {status}): {}",
+        message(response)
+    ))
+}
+
+/// Files of `dir` with `extensions`, in order.
+fn files(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.extension()
+                        .is_some_and(|e| extensions.iter().any(|x| e == *x))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// A uid Grafana accepts, from a file or folder name.
+fn uid(name: &str) -> String {
+    let slug: String = format!("dx-{name}")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    slug.chars().take(MAX_UID).collect()
+}
+
+/// Datasources of the provisioning files.
+fn datasources(dx_dir: &Path) -> DxResult<Vec<serde_yaml::Mapping>> {
+    let mut list = Vec::new();
+    for path in files(&dx_dir.join(DATASOURCES_DIR), &["yaml", "yml"]) {
+        let data = fs::read_to_string(&path).context(format!("Erro ao ler {}", path.display()))?;
+        let file: serde_yaml::Value = serde_yaml::from_str(&data)
+            .map_err(|e| DxError::InvalidInput(format!("{} inválido: {e}", path.display())))?;
+        if let Some(entries) = file.get("datasources").and_then(|d| d.as_sequence()) {
+            list.extend(entries.iter().filter_map(|d| d.as_mapping().cloned()));
+        }
+    }
+    Ok(list)
+}
+
+/// Create the datasources the instance doesn't have yet.
+fn export_datasources(grafana: &Grafana, dx_dir: &Path) -> DxResult {
+    for datasource in datasources(dx_dir)? {
+        let Some(name) = datasource.get("name").and_then(|n| n.as_str()) else {
+            continue;
+        };
+        let path = format!("/api/datasources/name/{}", name.replace(' ', "%20"));
+        match grafana.call(Method::GET, &path, None)? {
+            (200, _) => {
+                outln!("Datasource '{name}' já existe; mantido.");
+                continue;
+            }
+            (404, _) => {}
+            (status, response) => {
+                return Err(refused(
+                    &format!("a consulta ao datasource '{name}'"),
+                    status,
+                    &response,
+                ));
+            }
+        }
+        let mut body = serde_json::to_value(&datasource).unwrap_or_default();
+        if let Some(body) = body.as_object_mut() {
+            // The team's default datasource stays the default
+            body.remove("isDefault");
+            body.entry("access").or_insert_with(|| json!("proxy"));
+        }
+        let (status, response) = grafana.call(Method::POST, "/api/datasources", Some(&body))?;
+        if !(200..300).contains(&status) {
+            return Err(refused(
+                &format!("o datasource '{name}'"),
+                status,
+                &response,
+            ));
+        }
+        let url = datasource
+            .get("url")
+            .and_then(|u| u.as_str())
+            .unwrap_or_default();
+        outln!("Datasource '{name}' criado ({url}); ajuste a URL para o backend do time.");
+    }
+    Ok(())
+}
+
+/// Uid of the folder titled `title`, created if missing.
+fn folder(grafana: &Grafana, title: &str) -> DxResult<String> {
+    let (status, response) = grafana.call(Method::GET, "/api/folders", None)?;
+    if !(200..300).contains(&status) {
+        return Err(refused("a listagem de pastas", status, &response));
+    }
+    let existing = response.as_array().and_then(|folders| {
+        folders
+            .iter()
+            .find(|f| f.get("title").and_then(|t| t.as_str()) == Some(title))
+            .and_then(|f| f.get("uid").and_then(|u| u.as_str()))
+            .map(str::to_string)
+    });
+    if let Some(uid) = existing {
+        return Ok(uid);
+    }
+    let body = json!({"title": title, "uid": uid(title)});
+    let (status, response) = grafana.call(Method::POST, "/api/folders", Some(&body))?;
+    if !(200..300).contains(&status) {
+        return Err(refused(&format!("a pasta '{title}'"), status, &response));
+    }
+    outln!("Pasta '{title}' criada.");
+    Ok(response
+        .get("uid")
+        .and_then(|u| u.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| uid(title)))
+}
+
+/// `dx dev-services telemetry export-dashboards`: push the dashboards and
+/// datasources of the project at `dir` to the Grafana at `options.to`.
+pub fn export(dir: Option<PathBuf>, options: ExportOptions) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    let dx_dir = project_dir.join(".dx");
+    let dashboards = files(&dx_dir.join(DASHBOARDS_DIR), &["json"]);
+    if dashboards.is_empty() {
+        return Err(DxError::NotFound(format!(
+            "Nenhum dashboard em .dx/{DASHBOARDS_DIR}.\nDica: rode 'dx dev-services' com [telemetry] level diferente de \"off\" para gerá-los."
+        )));
+    }
+    let grafana = Grafana::new(&options.to)?;
+
+    export_datasources(&grafana, &dx_dir)?;
+    let folder_uid = match &options.folder {
+        Some(title) => Some(folder(&grafana, title)?),
+        None => None,
+    };
+    for path in dashboards {
+        let data = fs::read_to_string(&path).context(format!("Erro ao ler {}", path.display()))?;
+        let mut dashboard: Value = serde_json::from_str(&data)
+            .map_err(|e| DxError::InvalidInput(format!("{} inválido: {e}", path.display())))?;
+        let Some(fields) = dashboard.as_object_mut() else {
+            return Err(DxError::InvalidInput(format!(
+                "{} não é um dashboard (objeto JSON).",
+                path.display()
+            )));
+        };
+        // The instance numbers its dashboards; the uid identifies it across exports
+        fields.insert("id".to_string(), Value::Null);
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        fields
+            .entry("uid")
+            .or_insert_with(|| Value::String(uid(&stem)));
+        let title = fields
+            .get("title")
+            .and_then(|t| t.as_str())
+            .unwrap_or(&stem)
+            .to_string();
+        let mut body = json!({
+            "dashboard": dashboard,
+            "overwrite": true,
+            "message": "dx dev-services telemetry export-dashboards",
+        });
+        if let Some(uid) = &folder_uid {
+            body["folderUid"] = json!(uid);
+        }
+        let (status, response) = grafana.call(Method::POST, "/api/dashboards/db", Some(&body))?;
+        if !(200..300).contains(&status) {
+            return Err(refused(
+                &format!("o dashboard '{title}'"),
+                status,
+                &response,
+            ));
+        }
+        let link = response
+            .get("url")
+            .and_then(|u| u.as_str())
+            .unwrap_or_default();
+        outln!("Dashboard '{title}' exportado: {}{link}", grafana.url);
+    }
+    Ok(())
+}
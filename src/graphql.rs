@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! GraphQL sandbox for the project's GraphQL server.
+//!
+//! A GraphQL server library or a schema file (`.graphql`, `.graphqls`, `.gql`)
+//! adds `graphql-sandbox`: nginx serving a page with the Apollo Sandbox
+//! (Explorer) embedded, pointed at the app's GraphQL endpoint. The app runs on
+//! the host, so the browser calls it directly and the app must accept CORS from
+//! the sandbox's origin. The endpoint is the `GRAPHQL_ENDPOINT` variable of
+//! the service, which nginx serves as `/endpoint.json`; dx fills it in from the
+//! app's port (`PORT` in `.env`, Spring's `server.port`, or the stack's usual
+//! port) and path (`spring.graphql.path`, else `/graphql`). Schema files are
+//! mounted read-only under `/schema/` of the sandbox.
+use std::fs;
+use std::path::Path;
+
+use crate::dev_services::DockerComposeConfig;
+
+pub const SERVICE: &str = "graphql-sandbox";
+/// Port of the sandbox page (nginx listens on it too, see `NGINX_TEMPLATE`).
+pub const PORT: u16 = 4080;
+/// Endpoint used until the app's one is known.
+pub const DEFAULT_ENDPOINT: &str = "http://localhost:4000/graphql";
+
+const SPRING_PROPERTIES: &str = "src/main/resources/application.properties";
+
+const PAGE: &str = r##"<!DOCTYPE html>
+<html lang="pt-BR">
+<head>
+  <meta charset="utf-8">
+  <title>GraphQL Sandbox (dx)</title>
+  <style>html, body, #sandbox { height: 100%; margin: 0; }</style>
+</head>
+<body>
+  <div id="sandbox"></div>
+  <script src="https://embeddable-sandbox.cdn.apollographql.com/_latest/embeddable-sandbox.umd.production.min.js"></script>
+  <script>
+    // ?endpoint=... overrides GRAPHQL_ENDPOINT of the manifest
+    const query = new URLSearchParams(location.search).get("endpoint");
+    fetch("/endpoint.json")
+      .then((r) => r.json())
+      .then(({ endpoint }) => {
+        new window.EmbeddedSandbox({
+          target: "#sandbox",
+          initialEndpoint: query || endpoint,
+          includeCookies: false,
+        });
+      });
+  </script>
+</body>
+</html>
+"##;
+
+/// nginx template; the image's entrypoint substitutes the variables defined in
+/// the container (`$uri` is left alone).
+const NGINX_TEMPLATE: &str = r#"server {
+    listen 4080;
+    root /usr/share/nginx/html;
+
+    location = /endpoint.json {
+        default_type application/json;
+        return 200 '{"endpoint": "${GRAPHQL_ENDPOINT}"}';
+    }
+
+    location / {
+        try_files $uri $uri/ =404;
+    }
+}
+"#;
+
+/// Files the sandbox mounts, relative to `.dx`.
+pub fn files() -> Vec<(String, String)> {
+    vec![
+        ("graphql/index.html".to_string(), PAGE.to_string()),
+        (
+            "graphql/default.conf.template".to_string(),
+            NGINX_TEMPLATE.to_string(),
+        ),
+    ]
+}
+
+fn read(dir: &Path, file: &str) -> String {
+    fs::read_to_string(dir.join(file)).unwrap_or_default()
+}
+
+/// Value of `key=value` in a properties file.
+fn property(content: &str, key: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let (k, v) = line.trim().split_once('=')?;
+        (k.trim() == key).then(|| v.trim().to_string())
+    })
+}
+
+/// Port the app listens on.
+pub fn app_port(project_dir: &Path) -> u16 {
+    let configured = crate::presets::dotenv_value(&read(project_dir, ".env"), "PORT")
+        .or_else(|| property(&read(project_dir, SPRING_PROPERTIES), "server.port"))
+        .and_then(|port| port.parse().ok());
+    if let Some(port) = configured {
+        return port;
+    }
+    let (language, _) = crate::telemetry::detect_language_and_framework(project_dir);
+    match language.as_str() {
+        "Java" | "Go" => 8080,
+        "Python" | "PHP" | "Rust" => 8000,
+        "Ruby" => 3000,
+        // Apollo Server, GraphQL Yoga, Absinthe
+        _ => 4000,
+    }
+}
+
+/// GraphQL endpoint of the app, as seen from the host.
+pub fn endpoint(project_dir: &Path) -> String {
+    let path = property(&read(project_dir, SPRING_PROPERTIES), "spring.graphql.path")
+        .unwrap_or_else(|| "/graphql".to_string());
+    format!("http://localhost:{}{path}", app_port(project_dir))
+}
+
+/// Point the sandbox of `config` (when there is one) at the app and mount the
+/// schema files (paths relative to the project).
+pub fn apply(project_dir: &Path, schemas: &[String], config: &mut DockerComposeConfig) {
+    let Some(sandbox) = config.services.get_mut(SERVICE) else {
+        return;
+    };
+    let endpoint = endpoint(project_dir);
+    tracing::debug!(endpoint, "endpoint GraphQL do app");
+    sandbox.env.insert("GRAPHQL_ENDPOINT".to_string(), endpoint);
+    sandbox.volumes.extend(
+        schemas
+            .iter()
+            .map(|file| format!("../{file}:/usr/share/nginx/html/schema/{file}:ro")),
+    );
+}
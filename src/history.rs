@@ -0,0 +1,287 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Log of the dx invocations of a project (`.dx/history.jsonl`) and
+//! `dx history`.
+//!
+//! Each command run on a project that has a `.dx` folder appends one line
+//! with the command, its arguments, when it started, how long it took, the
+//! exit code and the error, if any: what support asks for, what the portal
+//! shows as the activity feed (daemon method `history/list`) and the raw data
+//! of the feedback loop metrics (`dx history --stats`). The project is the
+//! command's `dir` argument or the current directory. Values given to
+//! `dev-config add/update` are masked. Nothing is recorded in `--ci` runs, with
+//! `DX_HISTORY=0`, for `dx history` itself or for the commands that run on
+//! every prompt or keep running (`env`, `daemon`, `completions`,
+//! `manpages`). The file is trimmed to its latest entries as it grows.
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DxError, DxResult};
+
+pub const FILE: &str = "history.jsonl";
+/// Commands never recorded.
+const SKIPPED: &[&str] = &["history", "env", "daemon", "completions", "manpages"];
+/// Arguments whose values are masked (`dev-config add KEY VALUE`).
+const SECRET_ARGS: &[&str] = &["value"];
+const MASK: &str = "***";
+/// Size above which the file is trimmed to its latest [`KEEP`] entries.
+const MAX_BYTES: u64 = 1024 * 1024;
+const KEEP: usize = 1000;
+
+/// One dx invocation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    /// Unix time (seconds) the command started
+    pub started_at: u64,
+    /// Subcommand path (`dev-services run`)
+    pub command: String,
+    /// Command line after `dx`
+    pub args: Vec<String>,
+    pub duration_ms: u64,
+    pub exit_code: i32,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// dx version that ran it
+    pub version: String,
+}
+
+/// The running command, recorded when it ends.
+struct Pending {
+    project: PathBuf,
+    command: String,
+    args: Vec<String>,
+    started: Instant,
+    started_at: u64,
+}
+
+static PENDING: Mutex<Option<Pending>> = Mutex::new(None);
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn path(project_dir: &Path) -> PathBuf {
+    project_dir.join(".dx").join(FILE)
+}
+
+/// Start timing the command in `matches` (`command` is its subcommand
+/// path). Call after `ci::init`.
+pub fn init(matches: &clap::ArgMatches, command: &str) {
+    let top = command.split(' ').next().unwrap_or_default();
+    if top.is_empty()
+        || SKIPPED.contains(&top)
+        || crate::ci::is_ci()
+        || std::env::var("DX_HISTORY").is_ok_and(|v| v == "0")
+    {
+        return;
+    }
+    let mut args: Vec<String> = std::env::args_os()
+        .skip(1)
+        .map(|a| a.to_string_lossy().into_owned())
+        .collect();
+    // The deepest `dir` is the one the command uses (`dx dev-services run <dir>`)
+    let mut dir = None;
+    let mut current = matches;
+    loop {
+        if let Ok(Some(d)) = current.try_get_one::<PathBuf>("dir") {
+            dir = Some(d.clone());
+        }
+        for id in SECRET_ARGS {
+            if let Ok(Some(secret)) = current.try_get_one::<String>(id) {
+                for arg in args.iter_mut().filter(|a| *a == secret) {
+                    *arg = MASK.to_string();
+                }
+            }
+        }
+        match current.subcommand() {
+            Some((_, sub)) => current = sub,
+            None => break,
+        }
+    }
+    let project =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    *PENDING.lock().unwrap() = Some(Pending {
+        project,
+        command: command.to_string(),
+        args,
+        started: Instant::now(),
+        started_at: now(),
+    });
+}
+
+/// Append the running command, ended with `exit_code`, to its project's
+/// history.
+pub fn record(exit_code: i32) {
+    let Some(pending) = PENDING.lock().unwrap().take() else {
+        return;
+    };
+    // Never creates `.dx` in a directory that isn't a dx project
+    if !pending.project.join(".dx").is_dir() {
+        return;
+    }
+    let entry = Entry {
+        started_at: pending.started_at,
+        command: pending.command,
+        args: pending.args,
+        duration_ms: pending.started.elapsed().as_millis() as u64,
+        exit_code,
+        ok: exit_code == 0 && crate::output::is_ok(),
+        error: crate::output::error_message(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    if let Err(e) = append(&pending.project, &entry) {
+        tracing::debug!("histórico não gravado: {e}");
+    }
+}
+
+fn append(project_dir: &Path, entry: &Entry) -> std::io::Result<()> {
+    let path = path(project_dir);
+    let line = serde_json::to_string(entry).expect("entry serializes") + "\n";
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    file.write_all(line.as_bytes())?;
+    if file.metadata()?.len() > MAX_BYTES {
+        let content = fs::read_to_string(&path)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let kept = lines[lines.len().saturating_sub(KEEP)..].join("\n");
+        fs::write(&path, kept + "\n")?;
+    }
+    Ok(())
+}
+
+/// Entries of the history of `project_dir`, oldest first. Lines that don't
+/// parse are skipped.
+pub fn entries(project_dir: &Path) -> Vec<Entry> {
+    fs::read_to_string(path(project_dir))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Runs, failures and durations of one command.
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub command: String,
+    pub runs: usize,
+    pub failures: usize,
+    pub median_ms: u64,
+    pub max_ms: u64,
+}
+
+/// Per-command stats of `entries`, by command.
+pub fn stats(entries: &[Entry]) -> Vec<Stats> {
+    let mut by_command: BTreeMap<&str, Vec<&Entry>> = BTreeMap::new();
+    for entry in entries {
+        by_command.entry(&entry.command).or_default().push(entry);
+    }
+    by_command
+        .into_iter()
+        .map(|(command, entries)| {
+            let mut durations: Vec<u64> = entries.iter().map(|e| e.duration_ms).collect();
+            durations.sort_unstable();
+            Stats {
+                command: command.to_string(),
+                runs: entries.len(),
+                failures: entries.iter().filter(|e| !e.ok).count(),
+                median_ms: durations[durations.len() / 2],
+                max_ms: durations.last().copied().unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+pub struct HistoryOptions {
+    pub limit: usize,
+    /// Only commands starting with this (`dev-services`)
+    pub command: Option<String>,
+    pub failed: bool,
+    pub stats: bool,
+}
+
+fn duration(ms: u64) -> String {
+    crate::progress::format_duration(Duration::from_millis(ms))
+}
+
+/// `dx history`: the latest operations of the project, newest first.
+pub fn list(dir: Option<PathBuf>, opts: HistoryOptions) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !project_dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            project_dir.display()
+        )));
+    }
+    if opts.limit == 0 {
+        return Err(DxError::InvalidInput(
+            "--limit deve ser maior que zero.".to_string(),
+        ));
+    }
+    let history = path(&project_dir);
+    let matching: Vec<Entry> = entries(&project_dir)
+        .into_iter()
+        .filter(|e| {
+            opts.command
+                .as_ref()
+                .is_none_or(|c| e.command.starts_with(c.as_str()))
+        })
+        .filter(|e| !opts.failed || !e.ok)
+        .collect();
+    let latest: Vec<&Entry> = matching.iter().rev().take(opts.limit).collect();
+    let stats = opts.stats.then(|| stats(&matching));
+    let mut data = serde_json::json!({ "entries": latest });
+    if let Some(stats) = &stats {
+        data["stats"] = serde_json::json!(stats);
+    }
+    crate::output::set_data(data);
+
+    if matching.is_empty() {
+        outln!("Nenhuma operação registrada em {}.", history.display());
+        return Ok(());
+    }
+    let now = now();
+    for entry in &latest {
+        let result = match (&entry.error, entry.ok) {
+            (_, true) => "ok".to_string(),
+            (Some(error), false) => format!(
+                "falhou (código {}): {}",
+                entry.exit_code,
+                error.lines().next().unwrap_or_default()
+            ),
+            (None, false) => format!("falhou (código {})", entry.exit_code),
+        };
+        outln!(
+            "- {} dx {} ({}): {result}",
+            crate::snapshots::age(now.saturating_sub(entry.started_at)),
+            entry.args.join(" "),
+            duration(entry.duration_ms)
+        );
+    }
+    if let Some(stats) = stats {
+        outln!("\n| Comando | Execuções | Falhas | Mediana | Máximo |");
+        outln!("|---------|-----------|--------|---------|--------|");
+        for s in stats {
+            outln!(
+                "| {} | {} | {} | {} | {} |",
+                s.command,
+                s.runs,
+                s.failures,
+                duration(s.median_ms),
+                duration(s.max_ms)
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Git hook entry points (`dx hook <name>`).
+//!
+//! Each hook runs one dx check and exits non-zero when it fails, which is all
+//! hook frameworks need. The repository's `.pre-commit-hooks.yaml` publishes
+//! them for the Python `pre-commit` framework, one `dx-<name>` id per hook, so
+//! teams can enable the guardrails one at a time.
+use std::path::PathBuf;
+
+use crate::error::DxResult;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Hook {
+    /// Policies de .dx/policies (dx governance check)
+    Governance,
+    /// Manifestos de .dx iguais ao que o dx geraria (dx governance verify-manifests)
+    Manifests,
+    /// Artefatos do dx ignorados pelo git
+    Gitignore,
+}
+
+const HOOKS: &[Hook] = &[Hook::Governance, Hook::Manifests, Hook::Gitignore];
+
+impl Hook {
+    fn name(self) -> &'static str {
+        match self {
+            Hook::Governance => "governance",
+            Hook::Manifests => "manifests",
+            Hook::Gitignore => "gitignore",
+        }
+    }
+
+    /// Hook id in `.pre-commit-hooks.yaml`.
+    fn id(self) -> String {
+        format!("dx-{}", self.name())
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Hook::Governance => "Avalia as policies de .dx/policies",
+            Hook::Manifests => "Verifica se os manifestos de .dx divergem do que o dx geraria",
+            Hook::Gitignore => "Verifica se os artefatos do dx estão no .gitignore",
+        }
+    }
+}
+
+/// `dx hook <name>`: run the check behind the hook.
+pub fn run(hook: Hook, dir: Option<PathBuf>) -> DxResult {
+    match hook {
+        Hook::Governance => crate::governance::check(dir, None),
+        Hook::Manifests => crate::governance::verify_manifests(dir),
+        Hook::Gitignore => crate::gitignore::check(dir),
+    }
+}
+
+/// `dx hook`: list the hooks and how to enable them with pre-commit.
+pub fn list() -> DxResult {
+    outln!("Hooks disponíveis (dx hook <nome>):");
+    for hook in HOOKS {
+        outln!("- {:<11} {}", hook.name(), hook.description());
+    }
+    outln!("\nPara usar com o pre-commit, adicione ao .pre-commit-config.yaml:\n");
+    outln!("repos:");
+    outln!("  - repo: https://github.com/dx-anywhere/dx-cli");
+    outln!("    rev: v{}", env!("CARGO_PKG_VERSION"));
+    outln!("    hooks:");
+    for hook in HOOKS {
+        outln!("      - id: {}", hook.id());
+    }
+    crate::output::set_data(serde_json::json!({
+        "hooks": HOOKS
+            .iter()
+            .map(|h| serde_json::json!({
+                "name": h.name(),
+                "id": h.id(),
+                "description": h.description(),
+            }))
+            .collect::<Vec<_>>(),
+    }));
+    Ok(())
+}
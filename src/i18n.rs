@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Language of the documentation dx generates for a project.
+//!
+//! `[docs] language` in dx.toml (`pt`, `en` or `es`) sets the language of
+//! what dx writes for the team to read: the analyzer report (also embedded by
+//! `dev-services`), its runbook sections (jobs, LocalStack, proxy URLs), the
+//! ports section of the README and the alt text of the README badges. It is
+//! independent of the CLI messages, which stay in Portuguese. Portuguese is
+//! the default.
+use serde::Deserialize;
+
+/// Language of the generated reports, runbooks and README sections.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocLanguage {
+    #[default]
+    Pt,
+    En,
+    Es,
+}
+
+impl DocLanguage {
+    /// The documentation language configured for `project_dir`.
+    pub fn of(project_dir: &std::path::Path) -> Self {
+        crate::settings::get(project_dir).docs.language
+    }
+
+    /// The text for this language among its Portuguese, English and Spanish
+    /// versions.
+    pub fn pick<'a>(self, pt: &'a str, en: &'a str, es: &'a str) -> &'a str {
+        match self {
+            DocLanguage::Pt => pt,
+            DocLanguage::En => en,
+            DocLanguage::Es => es,
+        }
+    }
+
+    /// `badge` (`[![PostgreSQL](...)](#)`) with its alt text describing it in
+    /// this language. Portuguese keeps the technology name alone, as the
+    /// badges blocks already written have it.
+    pub fn badge(self, badge: &str) -> String {
+        let suffix = match self {
+            DocLanguage::Pt => return badge.to_string(),
+            DocLanguage::En => "dev service",
+            DocLanguage::Es => "servicio de desarrollo",
+        };
+        match badge
+            .strip_prefix("[![")
+            .and_then(|rest| rest.split_once("]("))
+        {
+            Some((alt, rest)) => format!("[![{alt} ({suffix})]({rest}"),
+            None => badge.to_string(),
+        }
+    }
+}
+
+/// GitHub's anchor of the Markdown heading `title` (`Próximas Ações` →
+/// `próximas-ações`).
+pub fn anchor(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
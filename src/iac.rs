@@ -0,0 +1,364 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Infrastructure-as-code scanning for Dev Services.
+//!
+//! Managed cloud resources declared in Terraform (`*.tf`), CloudFormation
+//! templates or CDK apps are mapped to the container that stands in for them
+//! locally: RDS/Cloud SQL/Azure Database → Postgres or MariaDB, ElastiCache
+//! and other managed Redis → Redis, MSK → Redpanda, S3 → MinIO. The scan is
+//! textual; resources whose engine comes from a variable are not mapped.
+use std::fs;
+use std::path::Path;
+
+/// Local service standing in for a managed resource.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum LocalService {
+    Postgres,
+    Mysql,
+    Redis,
+    Kafka,
+    S3,
+}
+
+impl LocalService {
+    /// Name of the service in the generated compose.
+    pub fn name(self) -> &'static str {
+        match self {
+            LocalService::Postgres => "postgres",
+            LocalService::Mysql => "mysql",
+            LocalService::Redis => "redis",
+            LocalService::Kafka => "kafka",
+            LocalService::S3 => "minio",
+        }
+    }
+}
+
+/// A managed resource found in the project's infrastructure code.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Resource {
+    /// Resource type as written in the source (`aws_db_instance`,
+    /// `AWS::RDS::DBCluster`, `rds.DatabaseInstanceEngine.postgres`, ...)
+    pub kind: String,
+    /// File relative to the project directory, with `/` separators
+    pub file: String,
+    pub service: LocalService,
+}
+
+/// Directories never scanned (dependencies, build output, Terraform/CDK caches).
+const SKIP_DIRS: &[&str] = &[
+    ".git",
+    ".dx",
+    ".terraform",
+    "cdk.out",
+    "node_modules",
+    "target",
+    "build",
+    "dist",
+    "vendor",
+];
+
+/// Service for a database engine name (`postgres`, `aurora-mysql`, `POSTGRES_15`, ...).
+fn database_engine(engine: &str) -> Option<LocalService> {
+    let engine = engine.to_ascii_lowercase();
+    if engine.contains("postgres") {
+        Some(LocalService::Postgres)
+    } else if engine.contains("mysql") || engine.contains("mariadb") {
+        Some(LocalService::Mysql)
+    } else {
+        None
+    }
+}
+
+/// ElastiCache engines other than memcached are Redis-compatible (redis, valkey).
+fn cache_engine(engine: Option<String>) -> Option<LocalService> {
+    match engine {
+        Some(e) if e.eq_ignore_ascii_case("memcached") => None,
+        _ => Some(LocalService::Redis),
+    }
+}
+
+/// String value of `name = "..."` (Terraform) or `Name: ...` / `"Name": "..."`
+/// (CloudFormation) in `block`, when it is a literal.
+fn attribute(block: &str, name: &str) -> Option<String> {
+    block.lines().find_map(|line| {
+        let (key, value) = line.split_once(['=', ':'])?;
+        if key.trim().trim_matches('"') != name {
+            return None;
+        }
+        let literal = value
+            .trim()
+            .trim_end_matches(',')
+            .trim()
+            .trim_matches(['"', '\'']);
+        // References (var.engine, !Ref Engine, ${...}) can't be resolved here
+        (!literal.is_empty()
+            && !literal.contains(['$', '{', '!'])
+            && !literal.starts_with("var.")
+            && !literal.starts_with("local."))
+        .then(|| literal.to_string())
+    })
+}
+
+/// Body of the block opened by the first `{` at or after `start`.
+fn block(content: &str, start: usize) -> &str {
+    let Some(open) = content[start..].find('{').map(|i| start + i) else {
+        return "";
+    };
+    let mut depth = 0usize;
+    for (i, c) in content[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &content[open + 1..open + i];
+                }
+            }
+            _ => {}
+        }
+    }
+    &content[open + 1..]
+}
+
+fn terraform_resource(kind: &str, body: &str) -> Option<LocalService> {
+    match kind {
+        "aws_db_instance" | "aws_rds_cluster" => database_engine(&attribute(body, "engine")?),
+        "google_sql_database_instance" => database_engine(&attribute(body, "database_version")?),
+        "azurerm_postgresql_server" | "azurerm_postgresql_flexible_server" => {
+            Some(LocalService::Postgres)
+        }
+        "azurerm_mysql_server" | "azurerm_mysql_flexible_server" | "azurerm_mariadb_server" => {
+            Some(LocalService::Mysql)
+        }
+        "aws_elasticache_cluster"
+        | "aws_elasticache_replication_group"
+        | "aws_elasticache_serverless_cache" => cache_engine(attribute(body, "engine")),
+        "google_redis_instance" | "azurerm_redis_cache" => Some(LocalService::Redis),
+        "aws_msk_cluster" | "aws_msk_serverless_cluster" => Some(LocalService::Kafka),
+        "aws_s3_bucket" => Some(LocalService::S3),
+        _ => None,
+    }
+}
+
+/// Modules of the terraform-aws-modules registry namespace, by source.
+fn terraform_module(source: &str, body: &str) -> Option<LocalService> {
+    let name = source.strip_prefix("terraform-aws-modules/")?;
+    let name = name.split('/').next()?;
+    match name {
+        "rds" | "rds-aurora" => database_engine(&attribute(body, "engine")?),
+        "elasticache" => cache_engine(attribute(body, "engine")),
+        "msk-kafka-cluster" => Some(LocalService::Kafka),
+        "s3-bucket" => Some(LocalService::S3),
+        _ => None,
+    }
+}
+
+fn scan_terraform(content: &str) -> Vec<(String, LocalService)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let mut words = line.split_whitespace();
+        let keyword = words.next();
+        let Some(label) = words.next().map(|w| w.trim_matches('"')) else {
+            continue;
+        };
+        match keyword {
+            Some("resource") => {
+                if let Some(service) = terraform_resource(label, block(content, start)) {
+                    found.push((label.to_string(), service));
+                }
+            }
+            Some("module") => {
+                let body = block(content, start);
+                if let Some(source) = attribute(body, "source")
+                    && let Some(service) = terraform_module(&source, body)
+                {
+                    found.push((source, service));
+                }
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+/// CloudFormation resource types; RDS and ElastiCache also look at `Engine`.
+const CLOUDFORMATION: &[&str] = &[
+    "AWS::RDS::DBInstance",
+    "AWS::RDS::DBCluster",
+    "AWS::ElastiCache::CacheCluster",
+    "AWS::ElastiCache::ReplicationGroup",
+    "AWS::ElastiCache::ServerlessCache",
+    "AWS::MSK::Cluster",
+    "AWS::MSK::ServerlessCluster",
+    "AWS::S3::Bucket",
+];
+
+fn scan_cloudformation(content: &str) -> Vec<(String, LocalService)> {
+    if !content.contains("AWS::") || !content.contains("Resources") {
+        return Vec::new();
+    }
+    let mut found = Vec::new();
+    for kind in CLOUDFORMATION {
+        for (start, _) in content.match_indices(kind) {
+            let rest = &content[start + kind.len()..];
+            // AWS::S3::Bucket is also the prefix of AWS::S3::BucketPolicy
+            if rest.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+                continue;
+            }
+            // Properties run until the next resource type
+            let end = rest.find("AWS::").unwrap_or(rest.len());
+            let properties = &rest[..end];
+            let service = match kind.split("::").nth(1) {
+                Some("RDS") => attribute(properties, "Engine").and_then(|e| database_engine(&e)),
+                Some("ElastiCache") => cache_engine(attribute(properties, "Engine")),
+                Some("MSK") => Some(LocalService::Kafka),
+                _ => Some(LocalService::S3),
+            };
+            if let Some(service) = service {
+                found.push((kind.to_string(), service));
+            }
+        }
+    }
+    found
+}
+
+/// CDK constructs (TypeScript/JavaScript/Python spelling, lowercased) and the
+/// service they stand for.
+const CDK: &[(&str, &str, LocalService)] = &[
+    (
+        "databaseinstanceengine.postgres",
+        "rds.DatabaseInstanceEngine.postgres",
+        LocalService::Postgres,
+    ),
+    (
+        "databaseclusterengine.aurorapostgres",
+        "rds.DatabaseClusterEngine.auroraPostgres",
+        LocalService::Postgres,
+    ),
+    (
+        "databaseclusterengine.aurora_postgres",
+        "rds.DatabaseClusterEngine.aurora_postgres",
+        LocalService::Postgres,
+    ),
+    (
+        "databaseinstanceengine.mysql",
+        "rds.DatabaseInstanceEngine.mysql",
+        LocalService::Mysql,
+    ),
+    (
+        "databaseinstanceengine.maria_db",
+        "rds.DatabaseInstanceEngine.maria_db",
+        LocalService::Mysql,
+    ),
+    (
+        "databaseinstanceengine.mariadb",
+        "rds.DatabaseInstanceEngine.mariaDb",
+        LocalService::Mysql,
+    ),
+    (
+        "databaseclusterengine.auroramysql",
+        "rds.DatabaseClusterEngine.auroraMysql",
+        LocalService::Mysql,
+    ),
+    (
+        "databaseclusterengine.aurora_mysql",
+        "rds.DatabaseClusterEngine.aurora_mysql",
+        LocalService::Mysql,
+    ),
+    (
+        "elasticache.cfncachecluster",
+        "elasticache.CfnCacheCluster",
+        LocalService::Redis,
+    ),
+    (
+        "elasticache.cfnreplicationgroup",
+        "elasticache.CfnReplicationGroup",
+        LocalService::Redis,
+    ),
+    (
+        "elasticache.cfnserverlesscache",
+        "elasticache.CfnServerlessCache",
+        LocalService::Redis,
+    ),
+    ("msk.cfncluster", "msk.CfnCluster", LocalService::Kafka),
+    (
+        "msk.cfnserverlesscluster",
+        "msk.CfnServerlessCluster",
+        LocalService::Kafka,
+    ),
+    ("s3.bucket(", "s3.Bucket", LocalService::S3),
+];
+
+fn scan_cdk(content: &str) -> Vec<(String, LocalService)> {
+    if !["aws-cdk-lib", "aws_cdk", "@aws-cdk/"]
+        .iter()
+        .any(|m| content.contains(m))
+    {
+        return Vec::new();
+    }
+    let lower = content.to_lowercase();
+    CDK.iter()
+        .flat_map(|(pattern, kind, service)| {
+            lower.matches(pattern).map(|_| (kind.to_string(), *service))
+        })
+        .collect()
+}
+
+fn scan_file(path: &Path) -> Vec<(String, LocalService)> {
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return Vec::new();
+    };
+    let scanner: fn(&str) -> Vec<(String, LocalService)> = match ext {
+        "tf" => scan_terraform,
+        "yml" | "yaml" | "json" | "template" => scan_cloudformation,
+        "ts" | "js" | "mjs" | "py" => scan_cdk,
+        _ => return Vec::new(),
+    };
+    fs::read_to_string(path)
+        .map(|content| scanner(&content))
+        .unwrap_or_default()
+}
+
+fn walk(project_dir: &Path, dir: &Path, found: &mut Vec<Resource>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    entries.sort();
+    for path in entries {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if path.is_dir() {
+            if !SKIP_DIRS.contains(&name) {
+                walk(project_dir, &path, found);
+            }
+            continue;
+        }
+        for (kind, service) in scan_file(&path) {
+            let file = path.strip_prefix(project_dir).unwrap_or(&path);
+            let file = file.display().to_string().replace('\\', "/");
+            tracing::debug!(
+                file,
+                kind,
+                service = service.name(),
+                "recurso de IaC detectado"
+            );
+            found.push(Resource {
+                kind,
+                file,
+                service,
+            });
+        }
+    }
+}
+
+/// Managed resources declared under `project_dir`, in file order.
+pub fn scan(project_dir: &Path) -> Vec<Resource> {
+    let mut found = Vec::new();
+    walk(project_dir, project_dir, &mut found);
+    found
+}
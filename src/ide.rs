@@ -0,0 +1,393 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! IDE integration (`dx ide generate`): VS Code tasks, launch configurations
+//! and recommended extensions for the detected stack.
+//!
+//! The JSON files are merged rather than overwritten: dx owns the tasks and
+//! configurations labelled `dx: ...` and only adds missing extension
+//! recommendations, so entries added by hand survive regeneration.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
+
+use crate::dev_services::ManifestService;
+use crate::error::{DxError, DxResult, IoContext};
+use crate::pipeline::{Pipeline, Stack, Stage};
+
+/// Prefix of the labels and names managed by dx.
+const PREFIX: &str = "dx: ";
+
+/// Environment file shared by the launch configurations (see `dx env`).
+const ENV_FILE: &str = ".dx/.env";
+
+fn task(label: &str, command: &str) -> Value {
+    json!({
+        "label": format!("{PREFIX}{label}"),
+        "type": "shell",
+        "command": command,
+        "problemMatcher": [],
+    })
+}
+
+/// Commands of the pipeline steps in `stage`, joined into one shell line.
+fn stage_command(pipeline: &Pipeline, stage: Stage) -> Option<String> {
+    let commands: Vec<&str> = pipeline
+        .steps
+        .iter()
+        .filter(|s| s.stage == stage)
+        .flat_map(|s| s.run.lines())
+        .collect();
+    (!commands.is_empty()).then(|| commands.join(" && "))
+}
+
+fn tasks(pipeline: &Pipeline) -> Vec<Value> {
+    let mut tasks = Vec::new();
+    if let Some(build) = stage_command(pipeline, Stage::Build) {
+        let mut t = task("build", &build);
+        t["group"] = json!({ "kind": "build", "isDefault": true });
+        tasks.push(t);
+    }
+    if let Some(test) = stage_command(pipeline, Stage::Test) {
+        let mut t = task("test", &test);
+        t["group"] = json!({ "kind": "test", "isDefault": true });
+        tasks.push(t);
+    }
+    if let Some(lint) = stage_command(pipeline, Stage::Lint) {
+        tasks.push(task("lint", &lint));
+    }
+    let mut watch = task("dev-test", "dx dev-test");
+    watch["isBackground"] = json!(true);
+    tasks.push(watch);
+    tasks.push(task("dev-services up", "dx dev-services run"));
+    tasks.push(task("dev-services stop", "dx dev-services stop"));
+    tasks.push(task("analyze", "dx analyzer"));
+    tasks
+}
+
+/// Debug port of the stack's runtime (inspector, debugpy, delve, JDWP).
+fn debug_port(stack: Stack) -> Option<u16> {
+    match stack {
+        Stack::Node => Some(9229),
+        Stack::Python => Some(5678),
+        Stack::Go => Some(2345),
+        Stack::Maven | Stack::Gradle => Some(5005),
+        _ => None,
+    }
+}
+
+/// Port to attach to: the host port the manifest publishes for the debug port,
+/// or the debug port itself.
+fn attach_port(stack: Stack, services: &[ManifestService]) -> Option<u16> {
+    let port = debug_port(stack)?;
+    Some(
+        services
+            .iter()
+            .find_map(|s| s.published(port))
+            .unwrap_or(port),
+    )
+}
+
+fn node_launch(dir: &Path) -> Value {
+    let package: Value = fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+    if package["scripts"]["start"].is_string() {
+        json!({
+            "type": "node",
+            "request": "launch",
+            "name": format!("{PREFIX}npm start"),
+            "runtimeExecutable": "npm",
+            "runtimeArgs": ["run-script", "start"],
+        })
+    } else {
+        let main = package["main"].as_str().unwrap_or("index.js");
+        json!({
+            "type": "node",
+            "request": "launch",
+            "name": format!("{PREFIX}launch"),
+            "program": format!("${{workspaceFolder}}/{main}"),
+        })
+    }
+}
+
+/// Launch and attach configurations; the launches read the dx environment
+/// from [`ENV_FILE`] when `with_env` is set.
+fn launch_configurations(
+    stack: Stack,
+    dir: &Path,
+    services: &[ManifestService],
+    with_env: bool,
+) -> Vec<Value> {
+    let mut configs = match stack {
+        Stack::Rust => vec![
+            json!({
+                "type": "lldb",
+                "request": "launch",
+                "name": format!("{PREFIX}launch"),
+                "cargo": { "args": ["build"] }
+            }),
+            json!({
+                "type": "lldb",
+                "request": "launch",
+                "name": format!("{PREFIX}tests"),
+                "cargo": { "args": ["test", "--no-run"] }
+            }),
+        ],
+        Stack::Node => vec![node_launch(dir)],
+        Stack::Python => vec![json!({
+            "type": "debugpy",
+            "request": "launch",
+            "name": format!("{PREFIX}current file"),
+            "program": "${file}"
+        })],
+        Stack::Go => vec![json!({
+            "type": "go",
+            "request": "launch",
+            "name": format!("{PREFIX}launch"),
+            "mode": "auto",
+            "program": "${workspaceFolder}"
+        })],
+        Stack::Php => vec![json!({
+            "type": "php",
+            "request": "launch",
+            "name": format!("{PREFIX}listen for Xdebug"),
+            "port": 9003,
+        })],
+        Stack::Ruby => vec![json!({
+            "type": "rdbg",
+            "request": "launch",
+            "name": format!("{PREFIX}current file"),
+            "script": "${file}",
+        })],
+        Stack::Maven | Stack::Gradle | Stack::Unknown => Vec::new(),
+    };
+    if with_env {
+        // The PHP and Ruby debuggers have no envFile setting
+        for config in configs
+            .iter_mut()
+            .filter(|c| matches!(c["type"].as_str(), Some("lldb" | "node" | "debugpy" | "go")))
+        {
+            config["envFile"] = json!(format!("${{workspaceFolder}}/{ENV_FILE}"));
+        }
+    }
+    if let Some(port) = attach_port(stack, services) {
+        let name = format!("{PREFIX}attach :{port}");
+        configs.push(match stack {
+            Stack::Node => json!({
+                "type": "node", "request": "attach", "name": name, "port": port,
+            }),
+            Stack::Python => json!({
+                "type": "debugpy", "request": "attach", "name": name,
+                "connect": { "host": "localhost", "port": port },
+            }),
+            Stack::Go => json!({
+                "type": "go", "request": "attach", "name": name, "mode": "remote",
+                "host": "localhost", "port": port,
+            }),
+            _ => json!({
+                "type": "java", "request": "attach", "name": name,
+                "hostName": "localhost", "port": port,
+            }),
+        });
+    }
+    configs
+}
+
+fn extensions(stack: Stack, has_services: bool) -> Vec<&'static str> {
+    let mut ids = match stack {
+        Stack::Rust => vec![
+            "rust-lang.rust-analyzer",
+            "vadimcn.vscode-lldb",
+            "tamasfe.even-better-toml",
+        ],
+        Stack::Node => vec!["dbaeumer.vscode-eslint", "esbenp.prettier-vscode"],
+        Stack::Python => vec![
+            "ms-python.python",
+            "ms-python.debugpy",
+            "charliermarsh.ruff",
+        ],
+        Stack::Go => vec!["golang.go"],
+        Stack::Maven => vec!["vscjava.vscode-java-pack"],
+        Stack::Gradle => vec!["vscjava.vscode-java-pack", "vscjava.vscode-gradle"],
+        Stack::Php => vec!["xdebug.php-debug", "bmewburn.vscode-intelephense-client"],
+        Stack::Ruby => vec!["shopify.ruby-lsp"],
+        Stack::Unknown => Vec::new(),
+    };
+    if has_services {
+        ids.push("ms-azuretools.vscode-docker");
+    }
+    ids
+}
+
+/// Existing JSON object at `path` (empty when the file doesn't exist).
+fn read_json(path: &Path, force: bool) -> DxResult<serde_json::Map<String, Value>> {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Default::default()),
+        Err(e) => {
+            return Err(DxError::Io {
+                context: format!("Erro ao ler {}", path.display()),
+                source: e,
+            });
+        }
+    };
+    match serde_json::from_str::<Value>(&content) {
+        Ok(Value::Object(map)) => Ok(map),
+        _ if force => Ok(Default::default()),
+        _ => Err(DxError::InvalidInput(format!(
+            "{} não é um objeto JSON válido (comentários não são suportados); use --force para sobrescrever.",
+            path.display()
+        ))),
+    }
+}
+
+/// Replace the dx-managed entries (`key` starting with the prefix) of the
+/// array `field` with `managed`, keeping the others first.
+fn merge_entries(
+    doc: &mut serde_json::Map<String, Value>,
+    field: &str,
+    key: &str,
+    managed: Vec<Value>,
+) {
+    let mut entries: Vec<Value> = doc
+        .get(field)
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    entries.retain(|e| !e[key].as_str().is_some_and(|l| l.starts_with(PREFIX)));
+    entries.extend(managed);
+    doc.insert(field.to_string(), Value::Array(entries));
+}
+
+/// Write `doc` to `path` when it changed; returns whether it was written.
+fn write_json(path: &Path, doc: serde_json::Map<String, Value>, dry_run: bool) -> DxResult<bool> {
+    let content = serde_json::to_string_pretty(&Value::Object(doc)).unwrap_or_default() + "\n";
+    if dry_run {
+        outln!("\n{}:\n{content}", path.display());
+        return Ok(false);
+    }
+    if fs::read_to_string(path).is_ok_and(|c| c == content) {
+        outln!("{} já está atualizado.", path.display());
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context(format!("Erro ao criar {}", parent.display()))?;
+    }
+    fs::write(path, content).context(format!("Erro ao escrever {}", path.display()))?;
+    outln!("Arquivo salvo: {}", path.display());
+    Ok(true)
+}
+
+fn env_file_content(vars: &BTreeMap<String, String>) -> String {
+    vars.iter().map(|(k, v)| format!("{k}={v}\n")).collect()
+}
+
+pub struct GenerateOptions {
+    /// Print the files instead of writing them
+    pub dry_run: bool,
+    /// Overwrite files that aren't valid JSON
+    pub force: bool,
+}
+
+/// `dx ide generate`: write the VS Code files of the project at `dir`.
+pub fn generate(dir: Option<PathBuf>, opts: GenerateOptions) -> DxResult {
+    let dir = dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            dir.display()
+        )));
+    }
+    let pipeline = Pipeline::detect(&dir, &dir);
+    let stack = pipeline.stack();
+    let services = fs::read_to_string(dir.join(".dx").join("docker-compose.yml"))
+        .map(|yaml| crate::dev_services::manifest_services(&yaml))
+        .unwrap_or_default();
+
+    let vscode = dir.join(".vscode");
+    let tasks_path = vscode.join("tasks.json");
+    let launch_path = vscode.join("launch.json");
+    let extensions_path = vscode.join("extensions.json");
+    // Read everything first so an invalid file leaves all of them untouched
+    let mut tasks_doc = read_json(&tasks_path, opts.force)?;
+    let mut launch_doc = read_json(&launch_path, opts.force)?;
+    let mut extensions_doc = read_json(&extensions_path, opts.force)?;
+
+    let tasks = tasks(&pipeline);
+    let vars = crate::shell_env::project_vars(&dir);
+    let configs = launch_configurations(stack, &dir, &services, !vars.is_empty());
+    let recommended = extensions(stack, !services.is_empty());
+    outln!("VS Code para stack {}:", stack.name());
+    outln!(
+        "- tasks.json: {}",
+        tasks
+            .iter()
+            .filter_map(|t| t["label"].as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    outln!(
+        "- launch.json: {}",
+        configs
+            .iter()
+            .filter_map(|c| c["name"].as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    outln!("- extensions.json: {}", recommended.join(", "));
+
+    tasks_doc.insert("version".to_string(), json!("2.0.0"));
+    merge_entries(&mut tasks_doc, "tasks", "label", tasks.clone());
+    launch_doc.insert("version".to_string(), json!("0.2.0"));
+    merge_entries(&mut launch_doc, "configurations", "name", configs.clone());
+    let mut ids: Vec<Value> = extensions_doc
+        .get("recommendations")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for id in &recommended {
+        if !ids.iter().any(|v| v.as_str() == Some(id)) {
+            ids.push(json!(id));
+        }
+    }
+    extensions_doc.insert("recommendations".to_string(), Value::Array(ids));
+
+    let mut written = Vec::new();
+    for (path, doc) in [
+        (&tasks_path, tasks_doc),
+        (&launch_path, launch_doc),
+        (&extensions_path, extensions_doc),
+    ] {
+        if write_json(path, doc, opts.dry_run)? {
+            written.push(path.display().to_string());
+        }
+    }
+    // The launch configurations read the dx environment from this file
+    let env_path = dir.join(ENV_FILE);
+    let env = env_file_content(&vars);
+    if !opts.dry_run
+        && !vars.is_empty()
+        && fs::read_to_string(&env_path).ok().as_deref() != Some(env.as_str())
+    {
+        if let Some(parent) = env_path.parent() {
+            fs::create_dir_all(parent).context(format!("Erro ao criar {}", parent.display()))?;
+        }
+        fs::write(&env_path, &env).context(format!("Erro ao escrever {}", env_path.display()))?;
+        written.push(env_path.display().to_string());
+    }
+    if opts.dry_run {
+        outln!("Execução em modo --dry-run: nenhum arquivo foi alterado.");
+    }
+
+    crate::output::set_data(json!({
+        "stack": stack.name(),
+        "tasks": tasks.iter().filter_map(|t| t["label"].as_str()).collect::<Vec<_>>(),
+        "configurations": configs.iter().filter_map(|c| c["name"].as_str()).collect::<Vec<_>>(),
+        "extensions": recommended,
+        "written": written,
+    }));
+    Ok(())
+}
@@ -0,0 +1,267 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Named copies of a project's Dev Services (`dx dev-services run --instance
+//! <name>`), e.g. main and a feature branch's worktree side by side.
+//!
+//! An instance is its own Compose project (`<dir>-<name>`, set with `name:`
+//! in its manifest), so containers, networks and named volumes don't clash
+//! with the default stack or other instances. Its host ports are the
+//! manifest's shifted by an offset, a multiple of 100 chosen so every shifted
+//! port is free on this machine and unused by the other instances. The
+//! offset is recorded in `.dx/instances/<name>.json` and kept on later runs,
+//! so an instance's ports don't move; `remove --instance` releases it. The
+//! instance's manifest (`.dx/instances/<name>.yml`) is derived from
+//! `.dx/docker-compose.yml` on each run: bind mounts point to absolute paths
+//! and fixed `container_name`s are dropped.
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::compose::ComposeFile;
+use crate::error::{DxError, DxResult, IoContext};
+
+const DIR: &str = "instances";
+/// Offsets are multiples of this.
+const STEP: u16 = 100;
+/// Offsets tried before giving up.
+const MAX_STEPS: u16 = 50;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Instance {
+    pub name: String,
+    /// Compose project name
+    pub project: String,
+    /// Added to every host port of the manifest
+    pub offset: u16,
+    pub ports: Vec<Port>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Port {
+    pub service: String,
+    pub host: u16,
+    pub container: u16,
+}
+
+fn dir(project_dir: &Path) -> PathBuf {
+    project_dir.join(".dx").join(DIR)
+}
+
+fn record_path(project_dir: &Path, name: &str) -> PathBuf {
+    dir(project_dir).join(format!("{name}.json"))
+}
+
+/// Manifest of the instance `name`.
+pub fn compose_path(project_dir: &Path, name: &str) -> PathBuf {
+    dir(project_dir).join(format!("{name}.yml"))
+}
+
+/// Check `name` is usable in a Compose project name: lowercase letters,
+/// digits, `-` and `_`, starting with a letter or digit.
+pub fn validate(name: &str) -> DxResult {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(DxError::InvalidInput(format!(
+            "Nome de instância inválido: '{name}' (use letras minúsculas, dígitos, '-' e '_', ex.: feature-x)."
+        )))
+    }
+}
+
+/// Compose project of the instance `name` of `project_dir`.
+fn project_name(project_dir: &Path, name: &str) -> String {
+    let absolute = std::path::absolute(project_dir).unwrap_or_else(|_| project_dir.to_path_buf());
+    let base: String = crate::containerized::normalize(&absolute)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    let base = base.trim_start_matches(['-', '_']);
+    if base.is_empty() {
+        format!("dx-{name}")
+    } else {
+        format!("{base}-{name}")
+    }
+}
+
+/// `mapping` (short syntax, `[ip:][host:]container[/protocol]`) with its host
+/// port shifted by `offset`, plus the shifted host port and the container
+/// port; `None` when it publishes no fixed host port (ranges, container port
+/// only).
+fn shift(mapping: &str, offset: u16) -> Option<(String, u16, u16)> {
+    let (ports, protocol) = match mapping.split_once('/') {
+        Some((ports, protocol)) => (ports, format!("/{protocol}")),
+        None => (mapping, String::new()),
+    };
+    let (head, container) = ports.rsplit_once(':')?;
+    let (ip, host) = match head.rsplit_once(':') {
+        // [::1]:8080 or 127.0.0.1:8080
+        Some((ip, host)) if !host.ends_with(']') => (format!("{ip}:"), host),
+        _ if head.starts_with('[') => return None,
+        _ => (String::new(), head),
+    };
+    let shifted = host.parse::<u16>().ok()?.checked_add(offset)?;
+    let container_port = container.parse().ok()?;
+    Some((
+        format!("{ip}{shifted}:{container}{protocol}"),
+        shifted,
+        container_port,
+    ))
+}
+
+/// Offsets of the instances recorded in `project_dir`, except `name`.
+fn taken_offsets(project_dir: &Path, name: &str) -> BTreeSet<u16> {
+    let Ok(entries) = fs::read_dir(dir(project_dir)) else {
+        return BTreeSet::new();
+    };
+    entries
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|x| x == "json"))
+        .filter(|e| e.path().file_stem().is_some_and(|s| s != name))
+        .filter_map(|e| fs::read_to_string(e.path()).ok())
+        .filter_map(|data| serde_json::from_str::<Instance>(&data).ok())
+        .map(|i| i.offset)
+        .collect()
+}
+
+/// Recorded instance `name`, if any.
+fn load(project_dir: &Path, name: &str) -> Option<Instance> {
+    let data = fs::read_to_string(record_path(project_dir, name)).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+/// Host ports published by `file`.
+fn host_ports(file: &ComposeFile) -> Vec<u16> {
+    file.services
+        .values()
+        .flat_map(|s| &s.ports)
+        .filter_map(|p| shift(p, 0).map(|(_, port, _)| port))
+        .collect()
+}
+
+/// First offset whose shifted `ports` are all free and not `taken`.
+fn allocate(ports: &[u16], taken: &BTreeSet<u16>) -> Option<u16> {
+    (1..=MAX_STEPS).map(|step| step * STEP).find(|offset| {
+        !taken.contains(offset)
+            && ports
+                .iter()
+                .all(|port| port.checked_add(*offset).is_some_and(crate::ports::free))
+    })
+}
+
+/// Write the manifest of the instance `name` from `compose` (the project's
+/// manifest) and record it. The recorded offset is kept; a new instance gets
+/// the first free one.
+pub fn prepare(project_dir: &Path, compose: &Path, name: &str) -> DxResult<(Instance, PathBuf)> {
+    validate(name)?;
+    let yaml = fs::read_to_string(compose).context(format!("Erro ao ler {}", compose.display()))?;
+    let mut file = ComposeFile::parse(&yaml)
+        .map_err(|e| DxError::InvalidInput(format!("Manifesto inválido: {e}")))?;
+
+    let offset = match load(project_dir, name) {
+        Some(instance) => instance.offset,
+        None => allocate(&host_ports(&file), &taken_offsets(project_dir, name)).ok_or_else(|| {
+            DxError::Unsupported(format!(
+                "Nenhum deslocamento de portas livre para a instância '{name}' (testados +{STEP} a +{}).\nDica: pare outras instâncias com: dx dev-services remove --instance <nome>",
+                STEP * MAX_STEPS
+            ))
+        })?,
+    };
+
+    let dx_dir = std::path::absolute(project_dir.join(".dx")).unwrap_or_default();
+    let mut ports = Vec::new();
+    for (service_name, service) in file.services.iter_mut() {
+        for mapping in &mut service.ports {
+            if let Some((shifted, host, container)) = shift(mapping, offset) {
+                ports.push(Port {
+                    service: service_name.clone(),
+                    host,
+                    container,
+                });
+                *mapping = shifted;
+            }
+        }
+        for volume in &mut service.volumes {
+            if let Some((source, target)) = volume.split_once(':')
+                && (source == "." || source.starts_with("./") || source.starts_with("../"))
+            {
+                let absolute = crate::containerized::normalize(&dx_dir.join(source));
+                *volume = format!("{}:{target}", absolute.display());
+            }
+        }
+        service.extra.shift_remove("container_name");
+    }
+    let project = project_name(project_dir, name);
+    file.extra.insert("name".into(), project.clone().into());
+
+    let instance = Instance {
+        name: name.to_string(),
+        project,
+        offset,
+        ports,
+    };
+    fs::create_dir_all(dir(project_dir))
+        .context(format!("Erro ao criar {}", dir(project_dir).display()))?;
+    let path = compose_path(project_dir, name);
+    let content = format!(
+        "# Gerado pelo dx (dev-services run --instance {name}); não edite.\n{}",
+        file.to_yaml()
+    );
+    fs::write(&path, content).context(format!("Erro ao salvar {}", path.display()))?;
+    let record = record_path(project_dir, name);
+    let data = serde_json::to_string_pretty(&instance).expect("instance serializes");
+    fs::write(&record, data + "\n").context(format!("Erro ao salvar {}", record.display()))?;
+    Ok((instance, path))
+}
+
+/// Manifest of the existing instance `name`, for `stop`, `remove` and `seed`.
+pub fn existing(project_dir: &Path, name: &str) -> DxResult<PathBuf> {
+    validate(name)?;
+    let path = compose_path(project_dir, name);
+    if path.is_file() {
+        Ok(path)
+    } else {
+        Err(DxError::NotFound(format!(
+            "Instância '{name}' não encontrada em {}.\nDica: suba-a com: dx dev-services run --instance {name}",
+            dir(project_dir).display()
+        )))
+    }
+}
+
+/// Forget the instance `name`, releasing its offset.
+pub fn release(project_dir: &Path, name: &str) -> DxResult {
+    for path in [
+        record_path(project_dir, name),
+        compose_path(project_dir, name),
+    ] {
+        if path.exists() {
+            fs::remove_file(&path).context(format!("Erro ao remover {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+impl Instance {
+    /// One line per published port, for the output of `run`.
+    pub fn describe(&self) -> String {
+        let mut out = format!(
+            "Instância '{}': projeto Compose '{}', portas do manifesto +{}",
+            self.name, self.project, self.offset
+        );
+        for port in &self.ports {
+            out.push_str(&format!(
+                "\n  {}: localhost:{} -> {}",
+                port.service, port.host, port.container
+            ));
+        }
+        out
+    }
+}
@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! `${VAR}` placeholders in values dx renders, so files shared by a team
+//! (the compose override, `[images]` pins, dev-config entries) can be
+//! parameterized per developer.
+//!
+//! `${VAR}` is the value of `VAR`, `${VAR:-default}` falls back to `default`
+//! when `VAR` is unset or empty and `${VAR-default}` only when it is unset,
+//! as in Compose and the shell. A variable comes from the process environment
+//! or, failing that, from the project's dev-config (`.dx/config.json`, by key
+//! or by its variable name: `db.user` is also `DB_USER`). `$$` is a literal
+//! `$`. What happens to a placeholder without value depends on the target:
+//! in the Compose manifest it is kept, for Compose to resolve from its own
+//! environment and `.env` when the services start; in exported variables it
+//! becomes empty, as the shell would do.
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// Rendered into the Compose manifest, which Compose interpolates again
+    Compose,
+    /// Exported to the environment (`dx env`, test and migration commands)
+    Env,
+}
+
+/// Variables of the project's dev-config: each key as written and as a
+/// variable name.
+pub fn config_vars(project_dir: &Path) -> BTreeMap<String, String> {
+    let mut vars = BTreeMap::new();
+    for (key, value) in crate::dev_config::entries(project_dir) {
+        vars.insert(crate::shell_env::env_name(&key), value.clone());
+        vars.insert(key, value);
+    }
+    vars
+}
+
+/// `text` with its placeholders resolved from the process environment and
+/// the dev-config of `project_dir`.
+pub fn project(project_dir: &Path, text: &str, target: Target) -> String {
+    let config = config_vars(project_dir);
+    interpolate(text, target, |name| config.get(name).cloned())
+}
+
+/// `text` with its placeholders resolved from the process environment and,
+/// failing that, `lookup`.
+pub fn interpolate(text: &str, target: Target, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let value = |name: &str| std::env::var(name).ok().or_else(|| lookup(name));
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("$$") {
+            out.push_str(if target == Target::Compose { "$$" } else { "$" });
+            rest = after;
+            continue;
+        }
+        let Some(end) = rest.strip_prefix("${").and_then(|body| body.find('}')) else {
+            out.push('$');
+            rest = &rest[1..];
+            continue;
+        };
+        let (placeholder, after) = (&rest[2..2 + end], &rest[3 + end..]);
+        let (name, default) = match placeholder.find([':', '-']) {
+            Some(i) if placeholder[i..].starts_with(":-") => {
+                (&placeholder[..i], Some((&placeholder[i + 2..], true)))
+            }
+            Some(i) if placeholder[i..].starts_with('-') => {
+                (&placeholder[..i], Some((&placeholder[i + 1..], false)))
+            }
+            _ => (placeholder, None),
+        };
+        let valid = !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+        let resolved = match (valid.then(|| value(name)).flatten(), default) {
+            (Some(v), Some((d, true))) if v.is_empty() => Some(d.to_string()),
+            (Some(v), _) => Some(v),
+            (None, Some((d, _))) if valid => Some(d.to_string()),
+            _ => None,
+        };
+        match resolved {
+            Some(v) => out.push_str(&v),
+            // Not a placeholder dx knows, or one for Compose to resolve
+            None if !valid || target == Target::Compose => {
+                out.push_str(&rest[..rest.len() - after.len()])
+            }
+            None => tracing::warn!("variável '{name}' não definida; usando valor vazio"),
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
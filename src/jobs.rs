@@ -0,0 +1,332 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Background job frameworks (Sidekiq, Celery, BullMQ and Quartz).
+//!
+//! A detected framework makes sure the service its jobs go through is in the
+//! manifest (Redis, RabbitMQ or, for Quartz's JDBC job store, the database),
+//! leaves a commented worker service in the manifest for running the workers
+//! in a container and gets a section of the analyzer report on running them
+//! locally.
+use serde::{Deserialize, Serialize};
+
+use crate::detectors::{Keyword, prefix, word};
+use crate::dev_services::{DockerComposeConfig, ManifestService};
+use crate::i18n::DocLanguage;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Framework {
+    Sidekiq,
+    Celery,
+    Bullmq,
+    Quartz,
+}
+
+const FRAMEWORKS: [Framework; 4] = [
+    Framework::Sidekiq,
+    Framework::Celery,
+    Framework::Bullmq,
+    Framework::Quartz,
+];
+
+// sidekiq, sidekiq-cron, sidekiq-scheduler
+const SIDEKIQ: &[Keyword] = &[prefix("sidekiq")];
+// celery[redis], django-celery-beat, CELERY_BROKER_URL
+const CELERY: &[Keyword] = &[prefix("celery")];
+// bullmq, @nestjs/bullmq
+const BULLMQ: &[Keyword] = &[word("bullmq")];
+// org.quartz-scheduler:quartz, import org.quartz.*
+const QUARTZ: &[Keyword] = &[prefix("org.quartz"), prefix("spring-boot-starter-quartz")];
+
+/// Quartz keeps its jobs in memory unless configured with the JDBC job store.
+const QUARTZ_JDBC: &[Keyword] = &[
+    word("jobstoretx"),
+    word("jobstorecmt"),
+    prefix("job-store-type=jdbc"),
+    prefix("job-store-type: jdbc"),
+];
+
+/// A detected framework and the manifest service its jobs go through
+/// (`None` for Quartz with the in-memory job store).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Job {
+    pub framework: Framework,
+    pub broker: Option<String>,
+}
+
+impl Framework {
+    pub fn name(self) -> &'static str {
+        match self {
+            Framework::Sidekiq => "Sidekiq",
+            Framework::Celery => "Celery",
+            Framework::Bullmq => "BullMQ",
+            Framework::Quartz => "Quartz",
+        }
+    }
+
+    fn keywords(self) -> &'static [Keyword] {
+        match self {
+            Framework::Sidekiq => SIDEKIQ,
+            Framework::Celery => CELERY,
+            Framework::Bullmq => BULLMQ,
+            Framework::Quartz => QUARTZ,
+        }
+    }
+
+    /// Name of the commented worker service in the manifest.
+    fn worker(self) -> &'static str {
+        match self {
+            Framework::Sidekiq => "sidekiq-worker",
+            Framework::Celery => "celery-worker",
+            Framework::Bullmq => "bullmq-worker",
+            Framework::Quartz => "quartz-app",
+        }
+    }
+
+    /// Command starting the workers (placeholders where it depends on the project).
+    fn command(self) -> &'static str {
+        match self {
+            Framework::Sidekiq => "bundle exec sidekiq",
+            Framework::Celery => "celery -A app worker --loglevel=info",
+            Framework::Bullmq => "node worker.js",
+            // Quartz schedules inside the application itself
+            Framework::Quartz => "java -jar app.jar",
+        }
+    }
+
+    /// Manifest service the jobs go through.
+    fn broker(self, config: &DockerComposeConfig, jdbc: bool) -> Option<&'static str> {
+        let has = |name: &str| config.services.contains_key(name);
+        match self {
+            Framework::Sidekiq | Framework::Bullmq => Some("redis"),
+            Framework::Celery if has("rabbitmq") => Some("rabbitmq"),
+            Framework::Celery => Some("redis"),
+            Framework::Quartz if !jdbc => None,
+            Framework::Quartz if has("mysql") && !has("postgres") => Some("mysql"),
+            Framework::Quartz => Some("postgres"),
+        }
+    }
+
+    /// Variables pointing the workers to `broker`, reached at `host`
+    /// (`localhost` or the service name inside the Compose network).
+    fn env(self, broker: &ManifestService, host: &str) -> Vec<(String, String)> {
+        let var = |key: &str, value: String| (key.to_string(), value);
+        let local = host == "localhost";
+        let port = |container: u16| match local {
+            true => broker.published(container).unwrap_or(container),
+            false => container,
+        };
+        if let Some(db) = broker.database() {
+            let (driver, container) = match db.engine {
+                crate::dev_services::DatabaseEngine::Postgres => ("postgresql", 5432),
+                crate::dev_services::DatabaseEngine::Mysql => ("mysql", 3306),
+            };
+            return vec![
+                var(
+                    "SPRING_DATASOURCE_URL",
+                    format!("jdbc:{driver}://{host}:{}/{}", port(container), db.name),
+                ),
+                var("SPRING_DATASOURCE_USERNAME", db.user),
+                var("SPRING_DATASOURCE_PASSWORD", db.password),
+            ];
+        }
+        if broker.name == "rabbitmq" {
+            let get = |key: &str| broker.env.get(key).map_or("guest", String::as_str);
+            return vec![var(
+                "CELERY_BROKER_URL",
+                format!(
+                    "amqp://{}:{}@{host}:{}//",
+                    get("RABBITMQ_DEFAULT_USER"),
+                    get("RABBITMQ_DEFAULT_PASS"),
+                    port(5672)
+                ),
+            )];
+        }
+        let url = format!("redis://{host}:{}/0", port(6379));
+        match self {
+            Framework::Celery => vec![var("CELERY_BROKER_URL", url)],
+            Framework::Bullmq => vec![
+                var("REDIS_HOST", host.to_string()),
+                var("REDIS_PORT", port(6379).to_string()),
+            ],
+            _ => vec![var("REDIS_URL", url)],
+        }
+    }
+}
+
+/// Keywords of each framework, then the ones of Quartz's JDBC job store, for
+/// the dependency scan.
+pub fn keywords() -> Vec<&'static [Keyword]> {
+    let mut keywords: Vec<&[Keyword]> = FRAMEWORKS.iter().map(|f| f.keywords()).collect();
+    keywords.push(QUARTZ_JDBC);
+    keywords
+}
+
+/// Record the frameworks found by the scan (`found` follows [`keywords`]) and
+/// add the brokers missing from `config`.
+pub fn apply(found: &[bool], config: &mut DockerComposeConfig) {
+    let jdbc = found.get(FRAMEWORKS.len()).copied().unwrap_or(false);
+    for (framework, found) in FRAMEWORKS.iter().zip(found) {
+        if !found {
+            continue;
+        }
+        let broker = framework.broker(config, jdbc);
+        if let Some(broker) = broker
+            && !config.services.contains_key(broker)
+            && let Some(detector) = crate::detectors::for_service(broker)
+        {
+            tracing::debug!(
+                framework = framework.name(),
+                broker,
+                "broker dos jobs adicionado"
+            );
+            crate::dev_services::provision(config, detector.as_ref());
+        }
+        config.jobs.push(Job {
+            framework: *framework,
+            broker: broker.map(str::to_string),
+        });
+    }
+}
+
+fn broker_service(config: &DockerComposeConfig, job: &Job) -> Option<ManifestService> {
+    let broker = job.broker.as_deref()?;
+    config
+        .manifest_services()
+        .into_iter()
+        .find(|s| s.name == broker)
+}
+
+/// Commented worker services for the jobs of `config`, indented to go under
+/// `services:` (uncommenting the lines enables them).
+fn worker_templates(config: &DockerComposeConfig) -> String {
+    let mut out = String::new();
+    for job in &config.jobs {
+        let framework = job.framework;
+        let broker = broker_service(config, job);
+        let mut lines = vec![
+            format!("{}:", framework.worker()),
+            "  build: ..".to_string(),
+            format!("  command: {}", framework.command()),
+        ];
+        let env = broker
+            .as_ref()
+            .map(|b| framework.env(b, &b.name))
+            .unwrap_or_default();
+        if !env.is_empty() {
+            lines.push("  environment:".to_string());
+            lines.extend(env.iter().map(|(k, v)| format!("    {k}: {v}")));
+        }
+        if let Some(broker) = &broker {
+            lines.push("  depends_on:".to_string());
+            // Waits for the broker to accept connections when it has a healthcheck
+            if config
+                .services
+                .get(&broker.name)
+                .is_some_and(|s| s.healthcheck.is_some())
+            {
+                lines.push(format!("    {}:", broker.name));
+                lines.push(format!("      condition: {}", crate::compose::HEALTHY));
+            } else {
+                lines.push(format!("  - {}", broker.name));
+            }
+        }
+        out.push_str(&format!(
+            "  # Worker do {} (modelo): descomente para rodá-lo em um container com os\n  # Dev Services; ajuste o build e o comando ao projeto.\n",
+            framework.name()
+        ));
+        for line in lines {
+            out.push_str(&format!("  # {line}\n"));
+        }
+    }
+    out
+}
+
+/// `yaml` (the manifest of `config`) with the worker templates at the end of
+/// its `services:` section.
+pub fn with_worker_templates(yaml: String, config: &DockerComposeConfig) -> String {
+    if config.jobs.is_empty() {
+        return yaml;
+    }
+    let templates = worker_templates(config);
+    // First top-level key after `services:`
+    let mut in_services = false;
+    let mut offset = 0;
+    for line in yaml.split_inclusive('\n') {
+        if !line.starts_with([' ', '#']) {
+            if in_services {
+                return format!("{}{templates}{}", &yaml[..offset], &yaml[offset..]);
+            }
+            in_services = line.trim_end() == "services:";
+        }
+        offset += line.len();
+    }
+    yaml + &templates
+}
+
+/// Analyzer report section on running the workers locally.
+pub fn runbook(config: &DockerComposeConfig, lang: DocLanguage) -> String {
+    if config.jobs.is_empty() {
+        return String::new();
+    }
+    let t = |pt, en, es| lang.pick(pt, en, es);
+    let mut out = format!(
+        "\n### {}\n\n",
+        t(
+            "Jobs em segundo plano",
+            "Background jobs",
+            "Jobs en segundo plano"
+        )
+    );
+    out.push_str(t(
+        "Com os Dev Services no ar (`dx dev-services run`), rode os workers na máquina apontando para o broker:\n\n",
+        "With the Dev Services up (`dx dev-services run`), run the workers on the machine pointing at the broker:\n\n",
+        "Con los Dev Services arriba (`dx dev-services run`), ejecute los workers en la máquina apuntando al broker:\n\n",
+    ));
+    out.push_str("| Framework | Broker | Worker local |\n");
+    out.push_str("|-----------|--------|--------------|\n");
+    for job in &config.jobs {
+        let framework = job.framework;
+        let (broker, worker) = match broker_service(config, job) {
+            Some(broker) => {
+                let env: Vec<String> = framework
+                    .env(&broker, "localhost")
+                    .into_iter()
+                    .map(|(k, v)| format!("{k}={v}"))
+                    .collect();
+                (
+                    broker.name,
+                    format!("`{} {}`", env.join(" "), framework.command()),
+                )
+            }
+            None => (
+                "-".to_string(),
+                t(
+                    "roda dentro da aplicação (job store em memória)",
+                    "runs inside the application (in-memory job store)",
+                    "se ejecuta dentro de la aplicación (job store en memoria)",
+                )
+                .to_string(),
+            ),
+        };
+        out.push_str(&format!("| {} | {broker} | {worker} |\n", framework.name()));
+    }
+    let workers = config
+        .jobs
+        .iter()
+        .map(|j| format!("`{}`", j.framework.worker()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    out.push_str(&match lang {
+        DocLanguage::Pt => format!(
+            "\nPara rodar um worker em container, descomente o modelo correspondente no manifesto ({workers}) e ajuste o build e o comando.\n"
+        ),
+        DocLanguage::En => format!(
+            "\nTo run a worker in a container, uncomment its template in the manifest ({workers}) and adjust the build and the command.\n"
+        ),
+        DocLanguage::Es => format!(
+            "\nPara ejecutar un worker en un contenedor, descomente la plantilla correspondiente en el manifiesto ({workers}) y ajuste el build y el comando.\n"
+        ),
+    });
+    out
+}
@@ -0,0 +1,229 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! LocalStack for the AWS services the project's SDK clients use.
+//!
+//! Clients of SQS, SNS, DynamoDB and Lambda (the SDK packages of each language
+//! and boto3's `client('sqs')`-style calls) add `localstack`, with `SERVICES`
+//! limited to the detected ones so it starts only what the app talks to. S3
+//! stays with MinIO (see [`crate::minio`]). Lambda runs functions in sibling
+//! containers, so it also gets the Docker socket. Every service answers on the
+//! single edge port; the analyzer report lists the endpoint and the SDK
+//! variable of each one, and `dx env` exports them.
+use crate::detectors::{Keyword, exact, word};
+use crate::dev_services::{DockerComposeConfig, DockerService};
+use crate::i18n::DocLanguage;
+
+pub const SERVICE: &str = "localstack";
+/// Edge port every emulated service answers on.
+pub const PORT: u16 = 4566;
+/// Credentials and region LocalStack accepts by default.
+pub const ACCESS_KEY: &str = "test";
+pub const SECRET_KEY: &str = "test";
+pub const REGION: &str = "us-east-1";
+
+const DOCKER_SOCKET: &str = "/var/run/docker.sock:/var/run/docker.sock";
+
+/// An AWS service LocalStack emulates for the project.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AwsService {
+    Sqs,
+    Sns,
+    Dynamodb,
+    Lambda,
+}
+
+pub const AWS_SERVICES: [AwsService; 4] = [
+    AwsService::Sqs,
+    AwsService::Sns,
+    AwsService::Dynamodb,
+    AwsService::Lambda,
+];
+
+// @aws-sdk/client-sqs, aws-sdk-sqs (Rust), software.amazon.awssdk:sqs,
+// aws-sdk-go-v2/service/sqs, boto3.client('sqs')
+const SQS: &[Keyword] = &[
+    word("@aws-sdk/client-sqs"),
+    word("aws-sdk-sqs"),
+    word("awssdk:sqs"),
+    word("software.amazon.awssdk.services.sqs"),
+    word("aws-sdk-go-v2/service/sqs"),
+    word("client('sqs')"),
+    word("client(\"sqs\")"),
+    exact("SQS_QUEUE_URL"),
+];
+const SNS: &[Keyword] = &[
+    word("@aws-sdk/client-sns"),
+    word("aws-sdk-sns"),
+    word("awssdk:sns"),
+    word("software.amazon.awssdk.services.sns"),
+    word("aws-sdk-go-v2/service/sns"),
+    word("client('sns')"),
+    word("client(\"sns\")"),
+    exact("SNS_TOPIC_ARN"),
+];
+// Also @aws-sdk/lib-dynamodb and boto3's resource('dynamodb')
+const DYNAMODB: &[Keyword] = &[
+    word("@aws-sdk/client-dynamodb"),
+    word("@aws-sdk/lib-dynamodb"),
+    word("aws-sdk-dynamodb"),
+    word("awssdk:dynamodb"),
+    word("software.amazon.awssdk.services.dynamodb"),
+    word("aws-sdk-go-v2/service/dynamodb"),
+    word("client('dynamodb')"),
+    word("client(\"dynamodb\")"),
+    word("resource('dynamodb')"),
+    word("resource(\"dynamodb\")"),
+    exact("DYNAMODB_TABLE"),
+];
+const LAMBDA: &[Keyword] = &[
+    word("@aws-sdk/client-lambda"),
+    word("aws-sdk-lambda"),
+    word("awssdk:lambda"),
+    word("software.amazon.awssdk.services.lambda"),
+    word("aws-sdk-go-v2/service/lambda"),
+    word("client('lambda')"),
+    word("client(\"lambda\")"),
+];
+
+impl AwsService {
+    /// Name in LocalStack's `SERVICES`.
+    pub fn id(self) -> &'static str {
+        match self {
+            AwsService::Sqs => "sqs",
+            AwsService::Sns => "sns",
+            AwsService::Dynamodb => "dynamodb",
+            AwsService::Lambda => "lambda",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AwsService::Sqs => "SQS",
+            AwsService::Sns => "SNS",
+            AwsService::Dynamodb => "DynamoDB",
+            AwsService::Lambda => "Lambda",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<AwsService> {
+        AWS_SERVICES.into_iter().find(|s| s.id() == id)
+    }
+
+    fn keywords(self) -> &'static [Keyword] {
+        match self {
+            AwsService::Sqs => SQS,
+            AwsService::Sns => SNS,
+            AwsService::Dynamodb => DYNAMODB,
+            AwsService::Lambda => LAMBDA,
+        }
+    }
+
+    /// Service-specific endpoint variable of the AWS SDKs.
+    pub fn endpoint_var(self) -> String {
+        format!("AWS_ENDPOINT_URL_{}", self.id().to_ascii_uppercase())
+    }
+}
+
+/// Services listed in a `SERVICES` value.
+pub fn services(value: Option<&String>) -> Vec<AwsService> {
+    value
+        .map(|s| {
+            s.split(',')
+                .filter_map(|id| AwsService::from_id(id.trim()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Keywords of each AWS service, for the dependency scan.
+pub fn keywords() -> Vec<&'static [Keyword]> {
+    AWS_SERVICES.iter().map(|s| s.keywords()).collect()
+}
+
+/// Add LocalStack to `config` for the AWS services found by the scan (`found`
+/// follows [`keywords`]).
+pub fn apply(found: &[bool], config: &mut DockerComposeConfig) {
+    let detected: Vec<AwsService> = AWS_SERVICES
+        .iter()
+        .zip(found)
+        .filter(|(_, found)| **found)
+        .map(|(s, _)| *s)
+        .collect();
+    if detected.is_empty() {
+        return;
+    }
+    if let Some(detector) = crate::detectors::for_service(SERVICE) {
+        crate::dev_services::provision(config, detector.as_ref());
+    }
+    if let Some(localstack) = config.services.get_mut(SERVICE) {
+        configure(localstack, &detected);
+    }
+}
+
+/// Point `svc` at `services`.
+pub fn configure(svc: &mut DockerService, services: &[AwsService]) {
+    let ids: Vec<&str> = services.iter().map(|s| s.id()).collect();
+    svc.env.insert("SERVICES".into(), ids.join(","));
+    let socket = svc.volumes.iter().position(|v| v == DOCKER_SOCKET);
+    match (services.contains(&AwsService::Lambda), socket) {
+        (true, None) => svc.volumes.push(DOCKER_SOCKET.to_string()),
+        (false, Some(i)) => {
+            svc.volumes.remove(i);
+        }
+        _ => {}
+    }
+}
+
+/// Analyzer report section with the endpoint of each emulated service.
+pub fn endpoints(config: &DockerComposeConfig, lang: DocLanguage) -> String {
+    let Some(svc) = config.services.get(SERVICE) else {
+        return String::new();
+    };
+    let port = svc.ports.first().copied().unwrap_or(PORT);
+    let mut out = String::from("\n### AWS (LocalStack)\n\n");
+    out.push_str(&match lang {
+        DocLanguage::Pt => format!(
+            "Os SDKs falam com o LocalStack em `http://localhost:{port}` (credenciais `{ACCESS_KEY}`/`{SECRET_KEY}`, região `{REGION}`):\n\n"
+        ),
+        DocLanguage::En => format!(
+            "The SDKs talk to LocalStack at `http://localhost:{port}` (credentials `{ACCESS_KEY}`/`{SECRET_KEY}`, region `{REGION}`):\n\n"
+        ),
+        DocLanguage::Es => format!(
+            "Los SDKs hablan con LocalStack en `http://localhost:{port}` (credenciales `{ACCESS_KEY}`/`{SECRET_KEY}`, región `{REGION}`):\n\n"
+        ),
+    });
+    out.push_str(lang.pick(
+        "| Serviço AWS | Endpoint | Variável do SDK |\n",
+        "| AWS service | Endpoint | SDK variable |\n",
+        "| Servicio AWS | Endpoint | Variable del SDK |\n",
+    ));
+    out.push_str("|-------------|----------|-----------------|\n");
+    let services = services(svc.env.get("SERVICES"));
+    for service in &services {
+        out.push_str(&format!(
+            "| {} | http://localhost:{port} | `{}` |\n",
+            service.name(),
+            service.endpoint_var()
+        ));
+    }
+    out.push_str(lang.pick(
+        "\n`dx env` exporta as variáveis acima.",
+        "\n`dx env` exports the variables above.",
+        "\n`dx env` exporta las variables de arriba.",
+    ));
+    if services.contains(&AwsService::Sqs) {
+        out.push_str(&match lang {
+            DocLanguage::Pt => format!(
+                " As filas SQS ficam em `http://sqs.{REGION}.localhost.localstack.cloud:{port}/000000000000/<fila>`."
+            ),
+            DocLanguage::En => format!(
+                " The SQS queues are at `http://sqs.{REGION}.localhost.localstack.cloud:{port}/000000000000/<queue>`."
+            ),
+            DocLanguage::Es => format!(
+                " Las colas SQS están en `http://sqs.{REGION}.localhost.localstack.cloud:{port}/000000000000/<cola>`."
+            ),
+        });
+    }
+    out.push('\n');
+    out
+}
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Diagnostic logging (detection evidence, docker invocations, HTTP calls).
+//!
+//! Logs always go to stderr so stdout stays reserved for the command's primary
+//! output (and the JSON envelope in `--output json`).
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable with an explicit filter (e.g. `DX_LOG=debug`),
+/// taking precedence over `-v`/`--quiet`.
+pub const LOG_ENV: &str = "DX_LOG";
+
+fn default_level(verbose: u8, quiet: bool) -> &'static str {
+    if quiet {
+        return "error";
+    }
+    match verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    }
+}
+
+/// Install the global subscriber. `verbose` is the number of `-v` flags.
+pub fn init(verbose: u8, quiet: bool) {
+    let filter = std::env::var(LOG_ENV)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .and_then(|v| EnvFilter::try_new(v).ok())
+        .unwrap_or_else(|| match default_level(verbose, quiet) {
+            "error" => EnvFilter::new("error"),
+            // Only our own crate gets chatty; dependencies stay at warn
+            level => EnvFilter::new(format!("warn,dx={level}")),
+        });
+
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_target(verbose >= 3)
+        .with_ansi(!crate::ci::is_ci())
+        .without_time()
+        .try_init();
+}
@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! `dx dev-services logs`: `docker compose logs` with one color per service.
+//!
+//! Compose prefixes each line with the container name (`postgres-1  | ` on
+//! V2, `project_postgres_1  | ` on V1) and colors it by container. dx asks for
+//! plain output and rewrites the prefix as the service name, aligned to the
+//! longest service of the manifest and colored by the service's position in
+//! it, so a service keeps its color across runs and Compose versions. Colors
+//! are only used on a terminal, and never with `NO_COLOR`, `--ci` or
+//! `--output json`.
+use std::io::{BufRead, BufReader, IsTerminal};
+use std::process::{Command, ExitStatus, Stdio};
+
+/// ANSI foreground colors (red is left for errors).
+const COLORS: [u8; 10] = [36, 33, 32, 35, 34, 96, 93, 92, 95, 94];
+
+/// Rewrites the prefix of Compose log lines.
+pub struct Prefixer {
+    services: Vec<String>,
+    width: usize,
+    colors: bool,
+}
+
+impl Prefixer {
+    /// Prefixer for the services of the manifest, in file order.
+    pub fn new(services: Vec<String>) -> Self {
+        let width = services.iter().map(|s| s.len()).max().unwrap_or(0);
+        let colors = std::io::stdout().is_terminal()
+            && std::env::var_os("NO_COLOR").is_none()
+            && !crate::ci::is_ci()
+            && !crate::output::is_json();
+        Prefixer {
+            services,
+            width,
+            colors,
+        }
+    }
+
+    /// Service and replica of a container name (`postgres-1`, `app_postgres_2`).
+    fn service<'a>(&'a self, container: &'a str) -> (&'a str, Option<&'a str>) {
+        let (name, replica) = match container.rsplit_once(['-', '_']) {
+            Some((name, n)) if !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()) => {
+                (name, Some(n))
+            }
+            _ => (container, None),
+        };
+        // V1 names start with the project; the longest match wins (`kafka-ui`
+        // over `ui`)
+        let service = self
+            .services
+            .iter()
+            .filter(|s| {
+                name == s.as_str()
+                    || name
+                        .strip_suffix(s.as_str())
+                        .is_some_and(|p| p.ends_with(['-', '_']))
+            })
+            .max_by_key(|s| s.len())
+            .map_or(name, String::as_str);
+        (service, replica)
+    }
+
+    /// Service, replica and text of a Compose log line; `None` for lines
+    /// without a prefix.
+    fn split<'a>(&'a self, line: &'a str) -> Option<(&'a str, Option<&'a str>, &'a str)> {
+        let (container, text) = line.split_once('|')?;
+        let text = text.strip_prefix(' ').unwrap_or(text);
+        let (service, replica) = self.service(container.trim());
+        // `|` in a line Compose didn't prefix
+        if replica.is_none() && !self.services.iter().any(|s| s == service) {
+            return None;
+        }
+        Some((service, replica, text))
+    }
+
+    /// Service and text of a Compose log line; `None` for lines without a
+    /// prefix.
+    pub fn parse<'a>(&'a self, line: &'a str) -> Option<(&'a str, &'a str)> {
+        self.split(line).map(|(service, _, text)| (service, text))
+    }
+
+    /// `line` with the service name as prefix; lines without a prefix are
+    /// kept as they are.
+    pub fn format(&self, line: &str) -> String {
+        let Some((service, replica, text)) = self.split(line) else {
+            return line.to_string();
+        };
+        let label = match replica {
+            Some(n) if n != "1" => format!("{service}-{n}"),
+            _ => service.to_string(),
+        };
+        if !self.colors {
+            return format!("{label:<width$} | {text}", width = self.width);
+        }
+        let index = self
+            .services
+            .iter()
+            .position(|s| s == service)
+            .unwrap_or(self.services.len());
+        format!(
+            "\x1b[{}m{label:<width$} |\x1b[0m {text}",
+            COLORS[index % COLORS.len()],
+            width = self.width
+        )
+    }
+}
+
+/// Run `command` (a `compose logs --no-color`) and print its output through
+/// `prefixer`. Returns the exit status and whether any line was printed.
+pub fn stream(command: &mut Command, prefixer: &Prefixer) -> std::io::Result<(ExitStatus, bool)> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+    let mut printed = false;
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).split(b'\n') {
+            let line = String::from_utf8_lossy(&line?).into_owned();
+            outln!("{}", prefixer.format(line.trim_end_matches('\r')));
+            printed = true;
+        }
+    }
+    Ok((child.wait()?, printed))
+}
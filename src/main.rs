@@ -1,18 +1,45 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright (c) 2025 The dx-cli Contributors
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::ArgValueCandidates;
+use std::ffi::OsString;
+
+use crate::error::{DxError, DxResult, IoContext};
 
 #[derive(Parser)]
 #[command(
     name = "dx",
     version,
     about = "DX em qualquer stack",
-    long_about = "Projeto: DX em qualquer stack\n\nObjetivo: criar um conjunto de padrões, toolkits e automações que reduzam atrito do primeiro commit ao deploy — em qualquer stack — com IA promovendo ciclos de feedback curtos e decisões melhores.\n\nPilares (com IA embutida):\n- Ambiente instantâneo (\"Dev Services\" universais)\n- Dev UI portátil (portal do dev)\n- Testes Contínuos & Inteligentes\n- Configuração sem dor\n- Docs vivas + Q&A no código\n- Governança leve, guardrails fortes\n- Telemetria e feedback loops curtos",
-    arg_required_else_help = true
+    long_about = "Projeto: DX em qualquer stack\n\nSem subcomando, `dx` resume o que pode fazer pelo projeto do diretório atual e sugere os próximos comandos.\n\nObjetivo: criar um conjunto de padrões, toolkits e automações que reduzam atrito do primeiro commit ao deploy — em qualquer stack — com IA promovendo ciclos de feedback curtos e decisões melhores.\n\nPilares (com IA embutida):\n- Ambiente instantâneo (\"Dev Services\" universais)\n- Dev UI portátil (portal do dev)\n- Testes Contínuos & Inteligentes\n- Configuração sem dor\n- Docs vivas + Q&A no código\n- Governança leve, guardrails fortes\n- Telemetria e feedback loops curtos"
 )]
 struct Cli {
+    /// Formato de saída: texto (padrão) ou JSON estruturado em stdout
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    output: output::OutputFormat,
+    /// Atalho para `--output json`
+    #[arg(long, global = true)]
+    json: bool,
+    /// Aumenta o detalhamento dos logs em stderr (-v: info, -vv: debug, -vvv: trace). DX_LOG tem precedência.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+    /// Exibe apenas erros nos logs
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    /// Mostra ao final o tempo gasto em cada fase (detecção, registries, docker, ...)
+    #[arg(long, global = true)]
+    timings: bool,
+    /// Modo não interativo para CI: sem prompts, cores ou alterações de arquivos como efeito colateral (detectado automaticamente; DX_CI=0 desativa)
+    #[arg(long, global = true)]
+    ci: bool,
+    /// Ignora o cache de detecção (.dx/cache/detection.json) e refaz a varredura do projeto
+    #[arg(long, global = true)]
+    no_cache: bool,
+    /// Não consulta os registries de pacotes: o relatório sai sem as últimas versões das dev-dependencies
+    #[arg(long, global = true)]
+    offline: bool,
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
@@ -25,6 +52,12 @@ enum Commands {
         /// Não salva o manifesto detectado como docker-compose.yml (por padrão, o manifesto é salvo)
         #[arg(long)]
         no_save: bool,
+        /// Processa cada subprojeto do diretório (mesma detecção do analyzer)
+        #[arg(long, short = 'r', visible_alias = "all-projects")]
+        recursive: bool,
+        /// Deixa serviços fora do manifesto mesmo quando detectados, ex.: --without kafka-ui,grafana (salvo em [dev_services] exclude do dx.toml)
+        #[arg(long, value_name = "SERVICE", value_delimiter = ',')]
+        without: Vec<String>,
         /// Diretório raiz no qual detectar dependências e gerar .dx/docker-compose.yml (opcional; padrão: diretório atual)
         dir: Option<std::path::PathBuf>,
     },
@@ -36,11 +69,22 @@ enum Commands {
         /// Não salva no README (apenas imprime as badges). Por padrão, salva. Apenas para a ação de aplicar.
         #[arg(long, default_value_t = false)]
         no_save: bool,
+        /// Processa cada subprojeto do diretório (mesma detecção do analyzer)
+        #[arg(long, short = 'r', visible_alias = "all-projects")]
+        recursive: bool,
         /// Diretório alvo (padrão: diretório atual). Para `clean`, também pode ser informado após o subcomando.
         dir: Option<std::path::PathBuf>,
     },
     /// Executa testes unitários continuamente ao detectar mudanças nos arquivos
     DevTest {
+        /// Executa os testes uma única vez, sem monitorar alterações, e falha se falharem
+        #[arg(long)]
+        once: bool,
+        /// Diretório raiz do projeto a ser monitorado (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Mantém o manifesto dos Dev Services, as badges do README e o relatório do analyzer em dia enquanto os arquivos mudam
+    Watch {
         /// Diretório raiz do projeto a ser monitorado (opcional; padrão: diretório atual)
         dir: Option<std::path::PathBuf>,
     },
@@ -63,37 +107,316 @@ enum Commands {
     /// Portal/plug-in do desenvolvedor (Dev UI)
     Portal,
     /// Testes contínuos e inteligentes (geração/execução)
-    Tests,
+    Tests {
+        /// Ação opcional (ex.: `integration`). Se omitida, mostra a visão geral.
+        #[command(subcommand)]
+        action: Option<TestsAction>,
+    },
     /// Configuração tipada com wizards em linguagem natural
     Config,
     /// Documentação viva e Q&A no código
-    Docs,
-    /// Governança leve com guardrails
-    Governance,
+    Docs {
+        /// Ação opcional (ex.: `verify-quickstart`). Se omitida, mostra a visão geral.
+        #[command(subcommand)]
+        action: Option<DocsAction>,
+    },
+    /// Governança leve com guardrails (policies CEL/Rego em .dx/policies)
+    Governance {
+        /// Ação opcional (ex.: `check`). Se omitida, avalia as policies.
+        #[command(subcommand)]
+        action: Option<GovernanceAction>,
+        /// Diretório raiz do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
     /// Limpa pastas .dx recursivamente a partir do diretório informado (ou do diretório atual se omitido)
     Clean {
+        /// Apenas lista as pastas .dx (e tamanhos) que seriam removidas
+        #[arg(long)]
+        dry_run: bool,
+        /// Remove sem pedir confirmação
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Profundidade máxima de diretórios percorridos (0 = apenas a raiz)
+        #[arg(long)]
+        max_depth: Option<usize>,
+        /// Glob de diretórios a ignorar (pode repetir), ex.: --exclude 'node_modules' --exclude 'vendor/**'
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
+        /// Não percorre diretórios ignorados pelo git (node_modules, target, ...)
+        #[arg(long)]
+        respect_gitignore: bool,
+        /// Move as pastas para um backup com timestamp (expira em 7 dias) em vez de apagá-las
+        #[arg(long)]
+        backup: bool,
+        /// Diretório dos backups (padrão: $DX_BACKUP_DIR ou <temp>/dx-backups)
+        #[arg(long, value_name = "DIR", requires = "backup")]
+        backup_dir: Option<std::path::PathBuf>,
         /// Diretório raiz a partir do qual limpar .dx (opcional; padrão: diretório atual)
         dir: Option<std::path::PathBuf>,
     },
+    /// Gerencia o cache global do dx (~/.cache/dx): estatísticas, limpeza e expiração por TTL
+    Cache {
+        /// Ação opcional (ex.: `stats`). Se omitida, mostra as estatísticas.
+        #[command(subcommand)]
+        action: Option<CacheAction>,
+    },
+    /// Lista plugins: executáveis `dx-<nome>` no PATH e entradas de .dx/plugins.json
+    Plugins,
+    /// Gerencia as entradas do dx no .gitignore (nunca alterado sem pedido explícito)
+    Ignore {
+        /// Ação opcional (ex.: `setup`). Se omitida, mostra o status.
+        #[command(subcommand)]
+        action: Option<IgnoreAction>,
+        /// Diretório do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Imprime o script de autocompletar do dx para o shell informado
+    Completions {
+        /// Shell alvo
+        #[arg(value_enum)]
+        shell: completions::Shell,
+    },
+    /// Gera as páginas de manual (man) do dx e de cada subcomando no diretório informado
+    Manpages {
+        /// Diretório de saída (criado se não existir)
+        dir: std::path::PathBuf,
+    },
     /// Analisa o projeto e resume o que o dx-cli aplicaria (todas as capabilities)
     #[command(alias = "test-stacks", hide = true)]
-    #[command(alias = "doctor", hide = true)]
     Analyzer {
         /// Não salva o relatório (por padrão, o relatório é salvo)
         #[arg(long)]
         no_save: bool,
-        /// Caminho para salvar o relatório (padrão: analyzer-report.md)
-        #[arg(long, default_value = "analyzer-report.md")]
-        report_path: String,
+        /// Caminho para salvar o relatório (padrão: report_path do dx.toml ou analyzer-report.md)
+        #[arg(long)]
+        report_path: Option<String>,
+        /// Adiciona .dx ao .gitignore do projeto analisado (ignorado em --ci)
+        #[arg(long, conflicts_with = "no_save")]
+        write_gitignore: bool,
+        /// Grava os achados (healthchecks ausentes, segredos, .gitignore) em SARIF 2.1.0 (relativo ao projeto)
+        #[arg(long, value_name = "FILE")]
+        sarif: Option<std::path::PathBuf>,
         /// Diretório do projeto a ser analisado (opcional; padrão: diretório atual)
         dir: Option<std::path::PathBuf>,
     },
+    /// Verifica se a máquina roda o projeto: Docker/Podman, versão do SDK, portas livres, disco e acesso aos registries
+    Doctor {
+        /// Instala as versões de SDK fixadas pelo projeto que faltam (mise, asdf, rustup, nvm ou pyenv)
+        #[arg(long)]
+        fix: bool,
+        /// Diretório do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Executa um comando do dx em cada subprojeto, em paralelo (ex.: `dx all analyzer`)
+    #[command(visible_alias = "run-all")]
+    All {
+        /// Quantidade máxima de projetos processados ao mesmo tempo (padrão: número de CPUs)
+        #[arg(long, short = 'j')]
+        jobs: Option<usize>,
+        /// Profundidade máxima da busca por subprojetos
+        #[arg(long, default_value_t = 2)]
+        max_depth: usize,
+        /// Diretório raiz do workspace (padrão: diretório atual)
+        #[arg(long, value_name = "DIR")]
+        dir: Option<std::path::PathBuf>,
+        /// Comando do dx e seus argumentos (ex.: `analyzer --no-save`)
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true, value_name = "COMANDO")]
+        command: Vec<OsString>,
+    },
+    /// Gera a configuração de CI do projeto (build, lint, testes, dx analyzer e auditoria de dependências)
+    Ci {
+        #[command(subcommand)]
+        action: CiAction,
+    },
+    /// Empacotamento para distribuição (Homebrew, Scoop, .deb/.rpm) a partir do Cargo.toml
+    Release {
+        #[command(subcommand)]
+        action: ReleaseAction,
+    },
+    /// Publica artefatos do projeto (painel com relatório, badges e docs no GitHub Pages)
+    Publish {
+        #[command(subcommand)]
+        action: PublishAction,
+    },
+    /// Gera o arquivo de tarefas do projeto (justfile ou Makefile) com alvos padronizados
+    Tasks {
+        #[command(subcommand)]
+        action: TasksAction,
+    },
+    /// Exporta o ambiente dx do projeto (dev-config + variáveis de conexão dos Dev Services) para o shell
+    Env {
+        /// Shell de destino dos comandos de exportação
+        #[arg(long, value_enum, default_value = "bash")]
+        shell: shell_env::Shell,
+        /// Imprime o hook que mantém o shell sincronizado (ex.: eval "$(dx env --hook zsh)")
+        #[arg(long, value_enum, value_name = "SHELL", conflicts_with = "envrc")]
+        hook: Option<shell_env::Shell>,
+        /// Gera ou atualiza o .envrc do direnv no diretório do projeto
+        #[arg(long)]
+        envrc: bool,
+        /// Com --envrc: mostra o arquivo sem gravá-lo
+        #[arg(long, requires = "envrc")]
+        dry_run: bool,
+        /// Com --envrc: sobrescreve um .envrc que não foi gerado pelo dx
+        #[arg(long, requires = "envrc")]
+        force: bool,
+        /// Só imprime algo se o ambiente mudou desde a última exportação (usado pelos hooks)
+        #[arg(long, hide = true)]
+        if_changed: bool,
+        /// Diretório do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Integração com IDEs: tarefas, depuração e extensões recomendadas do VS Code
+    Ide {
+        #[command(subcommand)]
+        action: IdeAction,
+    },
+    /// Servidor JSON-RPC de longa duração para editores e o portal (stdio ou socket Unix)
+    Daemon {
+        /// Escuta neste socket Unix em vez de stdin/stdout
+        #[arg(long, value_name = "PATH")]
+        socket: Option<std::path::PathBuf>,
+        /// Diretório do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Executa um check do dx como hook de git (ex.: pelo pre-commit); sem nome, lista os hooks
+    Hook {
+        /// Hook a executar
+        #[arg(value_enum)]
+        name: Option<hooks::Hook>,
+        /// Diretório do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Últimas operações do dx no projeto (comando, duração e resultado), de .dx/history.jsonl
+    History {
+        /// Quantas operações mostrar (as mais recentes)
+        #[arg(long, short = 'n', default_value_t = 20)]
+        limit: usize,
+        /// Só os comandos que começam com este (ex.: dev-services)
+        #[arg(long)]
+        command: Option<String>,
+        /// Só as operações que falharam
+        #[arg(long)]
+        failed: bool,
+        /// Resume execuções, falhas e durações por comando
+        #[arg(long)]
+        stats: bool,
+        /// Diretório do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Atualiza a pasta .dx para o formato desta versão do dx, aplicando as migrações pendentes
+    Upgrade {
+        /// Lista as migrações pendentes sem aplicá-las
+        #[arg(long)]
+        dry_run: bool,
+        /// Diretório do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Plugin externo (`dx <nome>` executa `dx-<nome>` ou a entrada de .dx/plugins.json)
+    #[command(external_subcommand)]
+    External(Vec<OsString>),
+}
+
+#[derive(Subcommand)]
+enum CiAction {
+    /// Gera ou atualiza o pipeline do provedor (GitHub Actions, GitLab CI, CircleCI ou Azure Pipelines) conforme a stack detectada
+    Generate {
+        /// Provedor de CI
+        #[arg(long, value_enum, default_value = "github")]
+        provider: pipeline::Provider,
+        /// Mostra o pipeline sem gravar o arquivo
+        #[arg(long)]
+        dry_run: bool,
+        /// Sobrescreve um arquivo existente que não foi gerado pelo dx
+        #[arg(long)]
+        force: bool,
+        /// Diretório do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PublishAction {
+    /// Gera o painel do projeto (relatório do analyzer, badge de cobertura e docs) e faz push para a branch do GitHub Pages
+    Pages {
+        /// Branch publicada. Padrão: [pages] branch do dx.toml ou gh-pages
+        #[arg(long)]
+        branch: Option<String>,
+        /// Remote git. Padrão: [pages] remote do dx.toml ou origin
+        #[arg(long)]
+        remote: Option<String>,
+        /// Gera o site em .dx/pages sem publicá-lo
+        #[arg(long)]
+        dry_run: bool,
+        /// Diretório do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReleaseAction {
+    /// Gera ou atualiza a fórmula do Homebrew, o manifesto do Scoop e as definições .deb/.rpm (nFPM) a partir do Cargo.toml e dos artefatos
+    Package {
+        /// Pasta com os artefatos de release (nomes com SO e arquitetura, ex.: dx-linux-x86_64.tar.gz)
+        #[arg(long, default_value = "dist")]
+        artifacts: std::path::PathBuf,
+        /// URL base de download dos artefatos (padrão: release do GitHub da versão, a partir de package.repository)
+        #[arg(long)]
+        base_url: Option<String>,
+        /// Pasta de saída das definições
+        #[arg(long, default_value = "packaging")]
+        out: std::path::PathBuf,
+        /// Mostra os arquivos sem gravá-los
+        #[arg(long)]
+        dry_run: bool,
+        /// Sobrescreve arquivos existentes que não foram gerados pelo dx
+        #[arg(long)]
+        force: bool,
+        /// Diretório do crate (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TasksAction {
+    /// Gera ou atualiza o justfile/Makefile com os alvos setup, services-up, test, lint, analyze e clean
+    Generate {
+        /// Formato do arquivo de tarefas
+        #[arg(long, value_enum, default_value = "just")]
+        format: tasks::TaskFormat,
+        /// Mostra o arquivo sem gravá-lo
+        #[arg(long)]
+        dry_run: bool,
+        /// Sobrescreve um arquivo existente que não foi gerado pelo dx
+        #[arg(long)]
+        force: bool,
+        /// Diretório do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum IdeAction {
+    /// Gera ou atualiza .vscode/tasks.json, launch.json e extensions.json para a stack detectada
+    Generate {
+        /// Mostra os arquivos sem gravá-los
+        #[arg(long)]
+        dry_run: bool,
+        /// Sobrescreve arquivos que não são JSON válido (ex.: com comentários)
+        #[arg(long)]
+        force: bool,
+        /// Diretório do projeto (opcional; padrão: diretório atual)
+        dir: Option<std::path::PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
 enum DevBadgesAction {
     /// Limpa os badges do README.md entre os marcadores padrão
     Clean {
+        /// Limpa cada subprojeto do diretório (mesma detecção do analyzer)
+        #[arg(long, short = 'r', visible_alias = "all-projects")]
+        recursive: bool,
         /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
         dir: Option<std::path::PathBuf>,
     },
@@ -103,21 +426,357 @@ enum DevBadgesAction {
 enum DevServicesAction {
     /// Executa o docker compose localizado em .dx/docker-compose.yml (sobe serviços em segundo plano)
     Run {
+        /// Restringe a ação a um serviço do manifesto (pode repetir)
+        #[arg(long, short = 's', value_name = "SERVICE", add = ArgValueCandidates::new(completions::service_names))]
+        service: Vec<String>,
+        /// Sobe apenas os serviços de um profile do compose (db, cache, messaging, observability...; pode repetir)
+        #[arg(long, short = 'p', value_name = "PROFILE")]
+        profile: Vec<String>,
+        /// Para os containers após esse tempo sem conexões (ex.: 2h, 30m; off desativa). Padrão: auto_stop do dx.toml
+        #[arg(long, value_name = "DURAÇÃO")]
+        auto_stop: Option<String>,
+        /// O dx roda em um container e usa o Docker do host pelo socket (traduz os bind mounts para caminhos do host)
+        #[arg(long)]
+        containerized_cli: bool,
+        /// Sobe os serviços no Docker de um contexto (`docker context ls`), ex.: uma máquina remota
+        #[arg(long, value_name = "CONTEXTO", conflicts_with_all = ["host", "containerized_cli"])]
+        context: Option<String>,
+        /// Sobe os serviços no Docker de outra máquina (ex.: ssh://usuario@maquina ou tcp://maquina:2376)
+        #[arg(long, value_name = "URL", conflicts_with = "containerized_cli")]
+        host: Option<String>,
+        /// Sobe uma cópia nomeada dos serviços, com projeto Compose, volumes e portas próprios (ex.: feature-x)
+        #[arg(long, value_name = "NOME", conflicts_with_all = ["context", "host", "containerized_cli"])]
+        instance: Option<String>,
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
         /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
         dir: Option<std::path::PathBuf>,
     },
     /// Para (stop) os containers definidos em .dx/docker-compose.yml
     Stop {
+        /// Restringe a ação a um serviço do manifesto (pode repetir)
+        #[arg(long, short = 's', value_name = "SERVICE", add = ArgValueCandidates::new(completions::service_names))]
+        service: Vec<String>,
+        /// Para a instância nomeada (de `run --instance`) em vez dos serviços padrão
+        #[arg(long, value_name = "NOME")]
+        instance: Option<String>,
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
         /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
         dir: Option<std::path::PathBuf>,
     },
     /// Reinicia (restart) os containers definidos em .dx/docker-compose.yml
     Restart {
+        /// Restringe a ação a um serviço do manifesto (pode repetir)
+        #[arg(long, short = 's', value_name = "SERVICE", add = ArgValueCandidates::new(completions::service_names))]
+        service: Vec<String>,
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
         /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
         dir: Option<std::path::PathBuf>,
     },
     /// Remove (down) os containers definidos em .dx/docker-compose.yml (não remove volumes)
     Remove {
+        /// Remove a instância nomeada (de `run --instance`), seus volumes e o deslocamento de portas reservado
+        #[arg(long, value_name = "NOME")]
+        instance: Option<String>,
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Mostra os logs dos containers definidos em .dx/docker-compose.yml, com um prefixo colorido por serviço
+    Logs {
+        /// Serviço do manifesto (padrão: todos)
+        #[arg(add = ArgValueCandidates::new(completions::service_names))]
+        service: Option<String>,
+        /// Continua acompanhando os logs novos (Ctrl+C para sair)
+        #[arg(long, short = 'f')]
+        follow: bool,
+        /// Número de linhas do fim dos logs de cada container (ou 'all')
+        #[arg(long, short = 'n', value_name = "N")]
+        tail: Option<String>,
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        #[arg(long)]
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Mostra o estado dos containers e se cada serviço já aceita conexões (porta, protocolo e healthcheck)
+    Status {
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Variáveis de conexão dos serviços do manifesto (DATABASE_URL, REDIS_URL, KAFKA_BROKERS, MONGO_URI, OTEL_EXPORTER_OTLP_ENDPOINT...)
+    Env {
+        /// Formato da saída
+        #[arg(long, value_enum, default_value = "dotenv")]
+        format: shell_env::EnvFormat,
+        /// Grava (ou atualiza) as variáveis neste arquivo .env do projeto em vez de imprimi-las
+        #[arg(long, value_name = "PATH", conflicts_with = "format")]
+        env_file: Option<std::path::PathBuf>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Executa as migrações do projeto (Flyway, Liquibase, Alembic, Diesel, sqlx, Prisma, Rails) no banco dos Dev Services
+    Migrate {
+        /// Segundos de espera até o banco aceitar conexões
+        #[arg(long, default_value_t = 60, value_name = "SECS")]
+        timeout: u64,
+        /// Apenas mostra a ferramenta detectada e o comando, sem executar
+        #[arg(long)]
+        dry_run: bool,
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Carrega os seeds de .dx/seed (*.sql, *.js, *.cypher) nos bancos em execução, uma vez por arquivo
+    Seed {
+        /// Restringe a um banco do manifesto; repetido, define a ordem (pode repetir)
+        #[arg(long, short = 's', value_name = "SERVICE", add = ArgValueCandidates::new(completions::service_names))]
+        service: Vec<String>,
+        /// Gera os registros a partir deste schema (JSON Schema .json ou DDL .sql, relativo ao diretório) em vez de ler .dx/seed
+        #[arg(long, value_name = "FILE")]
+        schema: Option<std::path::PathBuf>,
+        /// Registros por tabela gerados com --schema
+        #[arg(long, short = 'n', default_value_t = datagen::DEFAULT_COUNT, value_name = "N", requires = "schema")]
+        count: usize,
+        /// Semente dos valores gerados com --schema
+        #[arg(long, default_value_t = datagen::DEFAULT_SEED, requires = "schema")]
+        seed: u64,
+        /// Idioma dos nomes, endereços e telefones gerados com --schema (pt-BR ou en-US)
+        #[arg(long, default_value = "pt-BR", requires = "schema")]
+        locale: String,
+        /// Reaplica os seeds já registrados no banco, inclusive os alterados desde então
+        #[arg(long)]
+        force: bool,
+        /// Apenas mostra os seeds e os serviços em que seriam aplicados, sem executar
+        #[arg(long)]
+        dry_run: bool,
+        /// Carrega os seeds na instância nomeada (de `run --instance`) em vez dos serviços padrão
+        #[arg(long, value_name = "NOME")]
+        instance: Option<String>,
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Vigia as portas dos serviços e os para após o tempo de inatividade (iniciado por `run --auto-stop`)
+    #[command(hide = true)]
+    Watchdog {
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Salva e restaura os dados dos serviços (dumps e volumes) em .dx/snapshots
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+    /// Define o número de containers de serviços (ex.: consumidores Kafka); salvo no override do manifesto
+    Scale {
+        /// Escala no formato serviço=N (pode repetir)
+        #[arg(required = true, value_name = "SERVIÇO=N")]
+        scale: Vec<String>,
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        #[arg(long)]
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Publica os dashboards de Telemetry em outro Grafana (time, Grafana Cloud)
+    Telemetry {
+        #[command(subcommand)]
+        action: TelemetryAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum TelemetryAction {
+    /// Envia os dashboards e datasources de .dx/telemetry/grafana para um Grafana pela API HTTP (token em GRAFANA_TOKEN)
+    ExportDashboards {
+        /// URL do Grafana de destino (ex.: https://time.grafana.net)
+        #[arg(long, value_name = "URL")]
+        to: String,
+        /// Pasta dos dashboards no destino (criada se não existir). Padrão: General
+        #[arg(long, value_name = "NOME")]
+        folder: Option<String>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SnapshotAction {
+    /// Salva os dados dos serviços em execução em .dx/snapshots/<nome>
+    Create {
+        /// Nome do snapshot
+        name: String,
+        /// Restringe o snapshot a um serviço do manifesto (pode repetir)
+        #[arg(long, short = 's', value_name = "SERVICE", add = ArgValueCandidates::new(completions::service_names))]
+        service: Vec<String>,
+        /// Substitui um snapshot existente com o mesmo nome
+        #[arg(long)]
+        force: bool,
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Restaura os dados salvos em .dx/snapshots/<nome> (substitui os dados atuais)
+    Restore {
+        /// Nome do snapshot
+        name: String,
+        /// Restringe a restauração a um serviço do snapshot (pode repetir)
+        #[arg(long, short = 's', value_name = "SERVICE", add = ArgValueCandidates::new(completions::service_names))]
+        service: Vec<String>,
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Lista os snapshots salvos
+    List {
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TestsAction {
+    /// Sobe os Dev Services, aplica as migrações e executa os testes de integração
+    Integration {
+        /// Não executa as migrações antes dos testes
+        #[arg(long)]
+        no_migrate: bool,
+        /// Segundos de espera até o banco aceitar conexões
+        #[arg(long, default_value_t = 60, value_name = "SECS")]
+        timeout: u64,
+        /// Runtime de contêineres: docker, podman ou nerdctl (padrão: container.runtime do .dx/config.json, senão o primeiro instalado)
+        #[arg(long, value_enum)]
+        runtime: Option<compose::Runtime>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Gera registros fictícios (nomes, e-mails, endereços, UUIDs, datas) a partir de um JSON Schema ou DDL SQL
+    Fixtures {
+        /// JSON Schema (.json) ou DDL com CREATE TABLE (.sql), relativo ao diretório
+        schema: std::path::PathBuf,
+        /// Registros por tabela
+        #[arg(long, short = 'n', default_value_t = datagen::DEFAULT_COUNT, value_name = "N")]
+        count: usize,
+        /// Semente dos valores: a mesma semente gera os mesmos dados
+        #[arg(long, default_value_t = datagen::DEFAULT_SEED)]
+        seed: u64,
+        /// Idioma dos nomes, endereços e telefones (pt-BR ou en-US)
+        #[arg(long, default_value = "pt-BR")]
+        locale: String,
+        /// Formato da saída (padrão: o do schema; json para JSON Schema, sql para DDL)
+        #[arg(long, value_enum)]
+        format: Option<datagen::Format>,
+        /// Grava os registros neste arquivo (relativo ao diretório) em vez de imprimi-los
+        #[arg(long, short = 'o', value_name = "FILE")]
+        out: Option<std::path::PathBuf>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        #[arg(long)]
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DocsAction {
+    /// Verifica os comandos de shell da seção de quickstart do README (lint ou execução em sandbox)
+    VerifyQuickstart {
+        /// README a verificar, relativo ao diretório
+        #[arg(long, default_value = "README.md")]
+        readme: std::path::PathBuf,
+        /// Título (ou trecho) da seção; padrão: Quickstart, Início rápido, Getting started...
+        #[arg(long)]
+        section: Option<String>,
+        /// Executa os passos em uma cópia do projeto em vez de só validá-los
+        #[arg(long)]
+        run: bool,
+        /// Segundos máximos por passo com --run
+        #[arg(long, default_value_t = 300, value_name = "SECS")]
+        timeout: u64,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum GovernanceAction {
+    /// Avalia as policies (.cel/.rego) de .dx/policies contra o modelo JSON do analyzer
+    Check {
+        /// Grava as violações em SARIF 2.1.0 para o GitHub code scanning (relativo ao projeto)
+        #[arg(long, value_name = "FILE")]
+        sarif: Option<std::path::PathBuf>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Verifica se os manifestos em .dx (compose/telemetry) divergem do que o dx geraria
+    VerifyManifests {
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Sincroniza um bundle de policies compartilhado (git ou HTTP) para .dx/policies/shared
+    Sync {
+        /// Origem do bundle: URL/caminho git ou endpoint HTTP (padrão: valor salvo em .dx/governance.json)
+        #[arg(long)]
+        source: Option<String>,
+        /// Versão a fixar (tag, branch ou commit no git; versão do bundle no HTTP)
+        #[arg(long)]
+        version: Option<String>,
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Mostra entradas, tamanho e TTL por categoria
+    Stats {
+        /// Restringe a uma categoria (ex.: registry, governance, dashboards, indexes)
+        #[arg(long)]
+        category: Option<String>,
+    },
+    /// Remove o cache inteiro ou apenas uma categoria
+    Clear {
+        /// Restringe a uma categoria (ex.: registry, governance, dashboards, indexes)
+        #[arg(long)]
+        category: Option<String>,
+    },
+    /// Remove as entradas expiradas (mais antigas que o TTL da categoria)
+    Prune,
+}
+
+#[derive(Subcommand)]
+enum IgnoreAction {
+    /// Mostra quais entradas do dx existem no .gitignore
+    Status {
+        /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Adiciona .dx (ou apenas os artefatos gerados, com --artifacts) ao .gitignore
+    Setup {
+        /// Mantém .dx versionado e ignora só artefatos locais (.dx/backups/, .dx/.env, .dx/badges/*.svg, .dx/analyzer-report.md, .dx/instances/, .dx/docker-compose.arch.yml)
+        #[arg(long)]
+        artifacts: bool,
+        /// Padrão adicional a ignorar (pode repetir)
+        #[arg(long, value_name = "PATTERN")]
+        entry: Vec<String>,
         /// Diretório alvo (opcional). Se omitido, usa o diretório atual.
         dir: Option<std::path::PathBuf>,
     },
@@ -137,6 +796,7 @@ enum DevConfigAction {
     /// Atualiza configuração existente
     Update {
         /// Chave da configuração
+        #[arg(add = ArgValueCandidates::new(completions::config_keys))]
         key: String,
         /// Novo valor da configuração
         value: String,
@@ -144,6 +804,7 @@ enum DevConfigAction {
     /// Remove uma configuração
     Delete {
         /// Chave da configuração
+        #[arg(add = ArgValueCandidates::new(completions::config_keys))]
         key: String,
     },
 }
@@ -158,44 +819,239 @@ enum DevDependenciesAction {
         name: String,
         /// Versão (opcional)
         version: Option<String>,
+        /// Executa `go mod tidy` depois de editar o go.mod (projetos Go)
+        #[arg(long)]
+        tidy: bool,
     },
     /// Atualiza uma dependência específica ou todas se omitido
     Update {
         /// Nome da dependência (opcional)
         name: Option<String>,
+        /// Aplica as atualizações numa branch nova, faz push e abre um PR (GitHub) ou MR (GitLab)
+        #[arg(long)]
+        create_pr: bool,
+        /// Remote git do PR. Padrão: [updates] remote do dx.toml ou origin
+        #[arg(long, requires = "create_pr")]
+        remote: Option<String>,
+        /// Branch de destino do PR. Padrão: [updates] base do dx.toml ou a branch atual
+        #[arg(long, requires = "create_pr")]
+        base: Option<String>,
+        /// Executa `go mod tidy` depois de editar o go.mod (projetos Go)
+        #[arg(long, conflicts_with = "create_pr")]
+        tidy: bool,
+    },
+    /// Procura vulnerabilidades conhecidas (OSV.dev) nas versões declaradas; falha se houver alguma crítica
+    Audit,
+    /// Agrupa as dependências pela licença informada nos registries; falha se alguma estiver fora de licenses.allow (dev-config)
+    Licenses,
+    /// Mostra só as dependências desatualizadas, com o salto até a última versão (patch, minor ou major)
+    Outdated {
+        /// Falha (código 1) se alguma atualização for deste tamanho ou maior (ex.: major, para gates de CI)
+        #[arg(long, value_enum, value_name = "SALTO")]
+        fail_on: Option<dev_dependencies::Bump>,
     },
     /// Remove uma dependência de desenvolvimento
     Delete {
         /// Nome da dependência
         name: String,
+        /// Executa `go mod tidy` depois de editar o go.mod (projetos Go)
+        #[arg(long)]
+        tidy: bool,
     },
 }
 
-
+#[macro_use]
+mod output;
+mod api_mocks;
+mod arch;
+mod auto_stop;
+mod cache;
+mod catalog;
+mod ci;
+mod clean;
+mod completions;
+mod compose;
+mod compose_check;
+mod containerized;
+mod daemon;
+mod datagen;
+mod detection_cache;
+mod detectors;
+mod docs;
+mod doctor;
+mod error;
+mod feature_flags;
+mod git;
+mod gitignore;
+mod grafana;
+mod graphql;
+mod history;
+mod hooks;
+mod i18n;
+mod iac;
+mod ide;
+mod instances;
+mod interpolate;
+mod jobs;
+mod localstack;
+mod logging;
+mod logs;
+mod managed;
+mod migrations;
+mod minio;
+mod net;
+mod onboarding;
+mod osv;
+mod pages;
+mod pipeline;
+mod platform;
+mod plugins;
+mod port_docs;
+mod ports;
+mod presets;
+mod progress;
+mod proxy;
+mod registry_cache;
+mod release;
+mod remote;
+mod resources;
+mod sarif;
+mod scale;
+mod schema_registry;
+mod seed;
+mod settings;
+mod sha256;
+mod shell_env;
+mod smoke;
+mod snapshots;
+mod status;
+mod tasks;
+mod toolchain;
+mod update_pr;
+mod upgrade;
+mod watch;
+mod workspace;
 mod dev_badges;
 mod dev_config;
 mod dev_test;
 mod dev_dependencies;
 
+/// Full subcommand path (e.g. "governance check") used in JSON output.
+fn command_path(matches: &clap::ArgMatches) -> String {
+    let mut names = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        names.push(name.to_string());
+        current = sub;
+    }
+    names.join(" ")
+}
+
 fn main() {
-    let cli = Cli::parse();
-    match cli.command {
-        Commands::DevServices { action, no_save, dir } => {
+    // Answers shell completion requests (COMPLETE=<shell> dx ...) and exits
+    clap_complete::CompleteEnv::with_factory(Cli::command).complete();
+
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let format = if cli.json {
+        output::OutputFormat::Json
+    } else {
+        cli.output
+    };
+    output::init(format, &command_path(&matches));
+    ci::init(cli.ci);
+    history::init(&matches, &command_path(&matches));
+    logging::init(cli.verbose, cli.quiet);
+    progress::init(cli.quiet, cli.timings);
+    detection_cache::init(cli.no_cache);
+    dev_dependencies::set_offline(cli.offline);
+    if ci::is_ci() {
+        tracing::debug!(detected_by = ci::detect(), "modo CI ativo");
+    } else {
+        cache::auto_prune();
+    }
+    // Global flags repeated on the runs started by `dx all`
+    let mut global_args = Vec::new();
+    if output::is_json() {
+        global_args.extend(["--output".to_string(), "json".to_string()]);
+    }
+    if ci::is_ci() {
+        global_args.push("--ci".to_string());
+    }
+    if cli.quiet {
+        global_args.push("--quiet".to_string());
+    }
+    if cli.no_cache {
+        global_args.push("--no-cache".to_string());
+    }
+    if cli.offline {
+        global_args.push("--offline".to_string());
+    }
+    if cli.verbose > 0 {
+        global_args.push(format!("-{}", "v".repeat(cli.verbose as usize)));
+    }
+    // Bare `dx`: what dx can do for the project in the current directory
+    let Some(command) = cli.command else {
+        return finish(onboarding::summary());
+    };
+    let result = match command {
+        Commands::DevServices { action, no_save, recursive, without, dir } => {
             match action {
-                Some(DevServicesAction::Run { dir: d2 }) => cmd_dev_services_run(d2.or(dir)),
-                Some(DevServicesAction::Stop { dir: d2 }) => cmd_dev_services_stop(d2.or(dir)),
-                Some(DevServicesAction::Restart { dir: d2 }) => cmd_dev_services_restart(d2.or(dir)),
-                Some(DevServicesAction::Remove { dir: d2 }) => cmd_dev_services_remove(d2.or(dir)),
-                None => cmd_dev_services(!no_save, dir),
+                Some(DevServicesAction::Run { service, profile, auto_stop, containerized_cli, context, host, instance, runtime, dir: d2 }) => {
+                    remote::Engine::from_flags(context, host).and_then(|engine| {
+                        cmd_dev_services_run(d2.or(dir), service, profile, auto_stop, containerized::enabled(containerized_cli), engine, instance, runtime)
+                    })
+                }
+                Some(DevServicesAction::Watchdog { dir: d2 }) => auto_stop::watchdog(d2.or(dir)),
+                Some(DevServicesAction::Stop { service, instance, runtime, dir: d2 }) => {
+                    cmd_dev_services_stop(d2.or(dir), service, instance, runtime)
+                }
+                Some(DevServicesAction::Restart { service, runtime, dir: d2 }) => cmd_dev_services_restart(d2.or(dir), service, runtime),
+                Some(DevServicesAction::Remove { instance, runtime, dir: d2 }) => cmd_dev_services_remove(d2.or(dir), instance, runtime),
+                Some(DevServicesAction::Logs { service, follow, tail, runtime, dir: d2 }) => {
+                    cmd_dev_services_logs(d2.or(dir), service, follow, tail, runtime)
+                }
+                Some(DevServicesAction::Status { runtime, dir: d2 }) => status::status(d2.or(dir), runtime),
+                Some(DevServicesAction::Env { format, env_file, dir: d2 }) => {
+                    shell_env::print_connections(d2.or(dir), shell_env::ConnectionOptions { format, env_file })
+                }
+                Some(DevServicesAction::Migrate { timeout, dry_run, runtime, dir: d2 }) => {
+                    migrations::migrate(d2.or(dir), migrations::MigrateOptions { timeout, dry_run, runtime })
+                }
+                Some(DevServicesAction::Seed { service, schema, count, seed, locale, force, dry_run, instance, runtime, dir: d2 }) => {
+                    datagen::Locale::parse(&locale).and_then(|locale| {
+                        seed::seed(
+                            d2.or(dir),
+                            seed::SeedOptions { services: service, schema, count, seed, locale, force, dry_run, instance, runtime },
+                        )
+                    })
+                }
+                Some(DevServicesAction::Snapshot { action }) => match action {
+                    SnapshotAction::Create { name, service, force, runtime, dir: d2 } => {
+                        snapshots::create(d2.or(dir), &name, snapshots::SnapshotOptions { services: service, force, runtime })
+                    }
+                    SnapshotAction::Restore { name, service, runtime, dir: d2 } => {
+                        snapshots::restore(d2.or(dir), &name, snapshots::SnapshotOptions { services: service, force: false, runtime })
+                    }
+                    SnapshotAction::List { dir: d2 } => snapshots::list(d2.or(dir)),
+                },
+                Some(DevServicesAction::Scale { scale, runtime, dir: d2 }) => scale::scale(d2.or(dir), scale, runtime),
+                Some(DevServicesAction::Telemetry { action }) => match action {
+                    TelemetryAction::ExportDashboards { to, folder, dir: d2 } => {
+                        grafana::export(d2.or(dir), grafana::ExportOptions { to, folder })
+                    }
+                },
+                None => cmd_dev_services(!no_save, recursive, &without, dir),
             }
         }
-        Commands::DevBadges { action, no_save, dir } => {
+        Commands::DevBadges { action, no_save, recursive, dir } => {
             match action {
-                Some(DevBadgesAction::Clean { dir: d2 }) => cmd_dev_badges_clean(d2.or(dir)),
-                None => cmd_dev_badges(!no_save, dir),
+                Some(DevBadgesAction::Clean { recursive: r2, dir: d2 }) => cmd_dev_badges_clean(d2.or(dir), recursive || r2),
+                None => cmd_dev_badges(!no_save, recursive, dir),
             }
         }
-        Commands::DevTest { dir } => dev_test::watch_and_test(dir),
+        Commands::DevTest { once, dir } => dev_test::watch_and_test(dir, once),
+        Commands::Watch { dir } => watch::run(dir, watch::WatchOptions { global_args }),
         Commands::DevConfig { action, dir } => match action.unwrap_or(DevConfigAction::List) {
             DevConfigAction::List => dev_config::list(dir),
             DevConfigAction::Add { key, value } => dev_config::add(dir, key, value),
@@ -204,569 +1060,663 @@ fn main() {
         },
         Commands::DevDependencies { action, dir } => match action.unwrap_or(DevDependenciesAction::List) {
             DevDependenciesAction::List => dev_dependencies::list(dir),
-            DevDependenciesAction::Add { name, version } => dev_dependencies::add(dir, name, version),
-            DevDependenciesAction::Update { name } => dev_dependencies::update(dir, name),
-            DevDependenciesAction::Delete { name } => dev_dependencies::delete(dir, name),
+            DevDependenciesAction::Add { name, version, tidy } => {
+                dev_dependencies::add(dir.clone(), name, version).and_then(|()| tidy_go(tidy, dir))
+            }
+            DevDependenciesAction::Update { name, create_pr: false, tidy, .. } => {
+                dev_dependencies::update(dir.clone(), name).and_then(|()| tidy_go(tidy, dir))
+            }
+            DevDependenciesAction::Update { name, create_pr: true, remote, base, .. } => {
+                update_pr::create(dir, name, update_pr::PrOptions { remote, base })
+            }
+            DevDependenciesAction::Audit => dev_dependencies::audit(dir),
+            DevDependenciesAction::Licenses => dev_dependencies::licenses(dir),
+            DevDependenciesAction::Outdated { fail_on } => dev_dependencies::outdated(dir, fail_on),
+            DevDependenciesAction::Delete { name, tidy } => {
+                dev_dependencies::delete(dir.clone(), name).and_then(|()| tidy_go(tidy, dir))
+            }
+        },
+        Commands::Ignore { action, dir } => match action {
+            Some(IgnoreAction::Setup { artifacts, entry, dir: d2 }) => gitignore::setup(d2.or(dir), artifacts, entry),
+            Some(IgnoreAction::Status { dir: d2 }) => gitignore::status(d2.or(dir)),
+            None => gitignore::status(dir),
+        },
+        Commands::Plugins => plugins::list(),
+        Commands::All {
+            jobs,
+            max_depth,
+            dir,
+            command,
+        } => workspace::run_all(
+            dir,
+            workspace::AllOptions {
+                command,
+                jobs,
+                max_depth,
+                global_args,
+            },
+        ),
+        Commands::Ci { action } => match action {
+            CiAction::Generate { provider, dry_run, force, dir } => {
+                pipeline::generate(dir, pipeline::GenerateOptions { provider, dry_run, force })
+            }
+        },
+        Commands::Release { action } => match action {
+            ReleaseAction::Package { artifacts, base_url, out, dry_run, force, dir } => {
+                release::package(dir, release::PackageOptions { artifacts, base_url, out, dry_run, force })
+            }
         },
+        Commands::Publish { action } => match action {
+            PublishAction::Pages { branch, remote, dry_run, dir } => {
+                pages::publish(dir, pages::PublishOptions { branch, remote, dry_run })
+            }
+        },
+        Commands::Tasks { action } => match action {
+            TasksAction::Generate { format, dry_run, force, dir } => {
+                tasks::generate(dir, tasks::GenerateOptions { format, dry_run, force })
+            }
+        },
+        Commands::Env { shell, hook, envrc, dry_run, force, if_changed, dir } => match hook {
+            Some(hook) => shell_env::print_hook(hook),
+            None if envrc => shell_env::write_envrc(dir, dry_run, force),
+            None => shell_env::print(dir, shell_env::EnvOptions { shell, if_changed }),
+        },
+        Commands::Ide { action } => match action {
+            IdeAction::Generate { dry_run, force, dir } => {
+                ide::generate(dir, ide::GenerateOptions { dry_run, force })
+            }
+        },
+        Commands::Daemon { socket, dir } => daemon::run(dir, daemon::DaemonOptions { socket }),
+        Commands::Hook { name, dir } => match name {
+            Some(hook) => hooks::run(hook, dir),
+            None => hooks::list(),
+        },
+        Commands::History { limit, command, failed, stats, dir } => {
+            history::list(dir, history::HistoryOptions { limit, command, failed, stats })
+        }
+        Commands::Upgrade { dry_run, dir } => upgrade::run(dir, upgrade::UpgradeOptions { dry_run }),
+        Commands::External(args) => plugins::run(args),
+        Commands::Completions { shell } => completions::print(shell),
+        Commands::Manpages { dir } => completions::manpages(&dir),
         Commands::Portal => cmd_portal(),
-        Commands::Tests => cmd_tests(),
+        Commands::Tests { action } => match action {
+            Some(TestsAction::Integration { no_migrate, timeout, runtime, dir }) => cmd_tests_integration(dir, no_migrate, timeout, runtime),
+            Some(TestsAction::Fixtures { schema, count, seed, locale, format, out, dir }) => {
+                datagen::Locale::parse(&locale).and_then(|locale| {
+                    datagen::fixtures(dir, &schema, datagen::FixtureOptions { count, seed, locale, format, output: out })
+                })
+            }
+            None => cmd_tests(),
+        },
         Commands::Config => cmd_config(),
-        Commands::Docs => cmd_docs(),
-        Commands::Governance => cmd_governance(),
-        Commands::Clean { dir } => cmd_clean(dir),
+        Commands::Docs { action } => match action {
+            Some(DocsAction::VerifyQuickstart { readme, section, run, timeout, dir }) => {
+                docs::verify_quickstart(dir, docs::VerifyOptions { readme, section, run, timeout })
+            }
+            None => cmd_docs(),
+        },
+        Commands::Cache { action } => match action {
+            Some(CacheAction::Stats { category }) => cache::stats(category),
+            Some(CacheAction::Clear { category }) => cache::clear(category),
+            Some(CacheAction::Prune) => cache::prune(),
+            None => cache::stats(None),
+        },
+        Commands::Governance { action, dir } => match action {
+            Some(GovernanceAction::Check { sarif, dir: d2 }) => governance::check(d2.or(dir), sarif),
+            Some(GovernanceAction::VerifyManifests { dir: d2 }) => governance::verify_manifests(d2.or(dir)),
+            Some(GovernanceAction::Sync {
+                source,
+                version,
+                dir: d2,
+            }) => governance::sync(d2.or(dir), source, version),
+            None => governance::check(dir, None),
+        },
+        Commands::Clean {
+            dry_run,
+            yes,
+            max_depth,
+            exclude,
+            respect_gitignore,
+            backup,
+            backup_dir,
+            dir,
+        } => clean::run(
+            dir,
+            clean::CleanOptions {
+                dry_run,
+                yes,
+                max_depth,
+                exclude,
+                respect_gitignore,
+                backup,
+                backup_dir,
+            },
+        ),
         Commands::Analyzer {
             no_save,
             report_path,
+            write_gitignore,
+            sarif,
             dir,
-        } => cmd_analyzer(!no_save, report_path, dir),
+        } => cmd_analyzer(!no_save, report_path, write_gitignore, sarif, dir),
+        Commands::Doctor { fix, dir } => doctor::doctor(dir, fix, cli.offline),
+    };
+    finish(result)
+}
+
+/// Report the outcome of the command and exit with its code.
+fn finish(result: DxResult) {
+    if let Err(e) = result {
+        eprintln!("{e}");
+        output::set_error(&e);
+        output::exit(e.exit_code());
     }
+    history::record(0);
+    output::finish();
 }
 
 
+mod cel;
 mod dev_services;
+mod governance;
 mod telemetry;
 mod report;
 
-fn cmd_dev_services(save_file: bool, dir: Option<std::path::PathBuf>) {
+/// `dev-dependencies add/update/delete --tidy`
+fn tidy_go(tidy: bool, dir: Option<std::path::PathBuf>) -> DxResult {
+    if tidy { dev_dependencies::tidy(dir) } else { Ok(()) }
+}
+
+/// `dx dev-services`: detect the services of the project at `dir` (or of each
+/// project under it with `recursive`) and, with `save_file`, write its
+/// `.dx/docker-compose.yml`.
+fn cmd_dev_services(save_file: bool, recursive: bool, without: &[String], dir: Option<std::path::PathBuf>) -> DxResult {
     use std::env;
-    use std::fs;
-    use std::path::{Path, PathBuf};
+    use std::path::Path;
 
     // Determine target directory (provided or current)
     let target_dir = dir.unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf()));
 
     // Helper: process a single project directory
-    fn process_project_dir(save_file: bool, project_dir: &Path) {
+    fn process_project_dir(save_file: bool, without: &[String], project_dir: &Path) -> DxResult<dev_services::Detection> {
         use crate::dev_services;
+        use crate::output::Render;
         use std::fs;
 
+        // Services left out for good: saved before detecting, so that every
+        // regeneration (watch, CI, teammates) leaves them out too
+        if save_file && !without.is_empty() {
+            let added = crate::settings::exclude_services(project_dir, without)?;
+            if !added.is_empty() {
+                outln!("Serviços adicionados a [dev_services] exclude do dx.toml: {}\n", added.join(", "));
+            }
+        }
+        let settings = crate::settings::load(project_dir)?;
+
         // Detect dependencies
-        let config = dev_services::detect_dependencies(project_dir);
+        let mut config = dev_services::detect_dependencies(project_dir);
+        config.exclude(without);
 
         // Create .dx directory if it doesn't exist
         let dx_dir = project_dir.join(".dx");
         if save_file && !dx_dir.exists() {
-            if let Err(e) = fs::create_dir_all(&dx_dir) {
-                eprintln!(
-                    "Erro ao criar diretório .dx em {}: {}",
-                    project_dir.display(),
-                    e
-                );
-                return;
-            }
+            fs::create_dir_all(&dx_dir).context(format!(
+                "Erro ao criar diretório .dx em {}",
+                project_dir.display()
+            ))?;
         }
 
 
         // Always print the detected dependencies
-        println!(
-            "Manifesto Dev Services (detectado) para {}:\n",
-            project_dir.display()
-        );
-
-        if config.services.is_empty() {
-            println!("Nenhuma dependência detectada no projeto atual.");
-        } else {
-            println!("---");
-            println!("{}", config.to_yaml());
+        let mut detection = dev_services::Detection::new(project_dir, &config);
+        detection.render_text();
 
+        if !config.services.is_empty() {
             // Handle file saving based on save_file parameter
             if save_file {
-                println!("\nSalvando manifesto como .dx/docker-compose.yml...");
+                outln!("\nSalvando manifesto como .dx/docker-compose.yml...");
 
                 match crate::telemetry::apply(project_dir) {
                     Ok(res) => {
-                        println!("Arquivo docker-compose.yml criado com sucesso em:");
-                        println!("{}", res.compose_path.display());
-                        println!("\nPara iniciar os serviços (incluindo Telemetry), execute:");
-                        println!("docker compose -f .dx/docker-compose.yml up -d");
-                        println!("ou, se estiver usando a CLI legada:");
-                        println!("docker-compose -f .dx/docker-compose.yml up -d");
-                        println!("\nDica: você também pode rodar: dx dev-services run");
-                        println!("Para parar os serviços depois: dx dev-services stop");
-                        println!("Para reiniciar os serviços: dx dev-services restart");
-                        println!("Para remover os containers: dx dev-services remove");
+                        outln!("Arquivo docker-compose.yml criado com sucesso em:");
+                        outln!("{}", res.compose_path.display());
+                        detection.compose = Some(res.compose_path.display().to_string());
+                        if let Some((path, _)) = compose::ComposeFile::load_override(&dx_dir) {
+                            outln!("Ajustes locais de {} aplicados ao manifesto.", path.display());
+                        }
+                        outln!("\nPara iniciar os serviços (incluindo Telemetry), execute:");
+                        outln!("docker compose -f .dx/docker-compose.yml up -d");
+                        outln!("ou, se estiver usando a CLI legada:");
+                        outln!("docker-compose -f .dx/docker-compose.yml up -d");
+                        outln!("\nDica: você também pode rodar: dx dev-services run");
+                        outln!("Para parar os serviços depois: dx dev-services stop");
+                        outln!("Para reiniciar os serviços: dx dev-services restart");
+                        outln!("Para remover os containers: dx dev-services remove");
+
+                        // Connection settings in the framework's own env file
+                        if let Some(preset) = &res.config.preset {
+                            if ci::is_ci() {
+                                outln!("\nModo CI: {} não foi alterado.", preset.env_file);
+                            } else if let Some(path) = preset.write_env_file(project_dir, &res.config)? {
+                                outln!("\nConexão dos Dev Services gravada em {} (preset {}).", path.display(), preset.framework.name());
+                            }
+                        }
+                        // SDK endpoints of the feature flag services
+                        if !ci::is_ci() && let Some(path) = crate::feature_flags::write_env_file(project_dir, &res.config)? {
+                            outln!("\nEndpoints de feature flags gravados em {}.", path.display());
+                        }
+
+                        // Ports section of the README, when it has the markers
+                        if !ci::is_ci() && let Some(path) = crate::port_docs::sync(project_dir, &res.compose_path).context("Erro ao atualizar as portas no README.md")? {
+                            outln!("\nPortas dos serviços atualizadas em {}.", path.display());
+                        }
 
                         // Generate analyzer-style report (same as `dx analyzer`)
-                        let report_path = project_dir.join(".dx").join("analyzer-report.md");
+                        let report_path = settings.report_path(project_dir);
                         let report = crate::report::build_analyzer_report(project_dir, &res.config);
                         if let Some(parent) = report_path.parent() { let _ = std::fs::create_dir_all(parent); }
                         match std::fs::write(&report_path, report) {
-                            Ok(_) => println!("\nRelatório (analyzer) gerado: {}", report_path.display()),
+                            Ok(_) => {
+                                outln!("\nRelatório (analyzer) gerado: {}", report_path.display());
+                                detection.report = Some(report_path.display().to_string());
+                            }
                             Err(e) => eprintln!("\nErro ao gerar relatório: {}", e),
                         }
                     }
                     Err(e) => {
-                        eprintln!("Erro ao aplicar Telemetry e criar .dx/docker-compose.yml: {}", e);
+                        return Err(DxError::Io {
+                            context: "Erro ao aplicar Telemetry e criar .dx/docker-compose.yml".to_string(),
+                            source: e,
+                        });
                     }
                 }
 
-                println!("\nPara apenas visualizar sem salvar, execute:");
-                println!("dx dev-services --no-save");
+                outln!("\nPara apenas visualizar sem salvar, execute:");
+                outln!("dx dev-services --no-save");
             } else {
                 // Instructions for saving when using --no-save
-                println!("\nPara salvar este manifesto como .dx/docker-compose.yml, execute:");
-                println!("dx dev-services");
+                outln!("\nPara salvar este manifesto como .dx/docker-compose.yml, execute:");
+                outln!("dx dev-services");
             }
         }
 
-        println!(
+        outln!(
             "Notas: a detecção de dependências atual é simplificada. A versão completa da ferramenta"
         );
-        println!(
+        outln!(
             "analisará código-fonte e IaC, sugerindo configurações específicas para dev local."
         );
-        println!("");
+        outln!();
+        Ok(detection)
     }
 
-    // If the provided dir is the test-projects root, iterate over its immediate subdirectories
-    let is_test_projects = target_dir
-        .file_name()
-        .and_then(|n| n.to_str())
-        .map(|n| n.eq_ignore_ascii_case("test-projects"))
-        .unwrap_or(false);
-
-    if is_test_projects && target_dir.is_dir() {
-        println!(
-            "Executando dev-services em todos os projetos dentro de: {}",
-            target_dir.display()
-        );
-        match fs::read_dir(&target_dir) {
-            Ok(entries) => {
-                for entry in entries.flatten() {
-                    let path: PathBuf = entry.path();
-                    let Ok(ft) = entry.file_type() else { continue };
-                    if ft.is_symlink() {
-                        continue;
-                    }
-                    if ft.is_dir() {
-                        println!("\n== Projeto: {} ==", path.display());
-                        process_project_dir(save_file, &path);
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!(
-                    "Erro ao listar subdiretórios de {}: {}",
-                    target_dir.display(),
-                    e
-                );
-            }
-        }
-        return;
-    }
+    let detections = std::cell::RefCell::new(Vec::new());
+    let result = workspace::for_each_project(&target_dir, recursive, "Executando dev-services", |p| {
+        let detection = process_project_dir(save_file, without, p)?;
+        detections.borrow_mut().push(crate::output::Render::to_json(&detection));
+        Ok(())
+    });
+    output::set_data(projects_data(detections.into_inner(), recursive));
+    result
+}
 
-    // Default: process a single directory
-    process_project_dir(save_file, &target_dir);
+/// `data` of a command run on one project, or `{"projects": [...]}` with
+/// `--recursive`.
+fn projects_data(mut projects: Vec<serde_json::Value>, recursive: bool) -> serde_json::Value {
+    match (recursive, projects.len()) {
+        (false, 0) => serde_json::Value::Null,
+        (false, 1) => projects.remove(0),
+        _ => serde_json::json!({ "projects": projects }),
+    }
 }
 
-fn cmd_dev_services_run(dir: Option<std::path::PathBuf>) {
+#[allow(clippy::too_many_arguments)]
+fn cmd_dev_services_run(
+    dir: Option<std::path::PathBuf>,
+    services: Vec<String>,
+    profiles: Vec<String>,
+    auto_stop: Option<String>,
+    containerized: bool,
+    engine: Option<remote::Engine>,
+    instance: Option<String>,
+    runtime: Option<compose::Runtime>,
+) -> DxResult {
     use std::env;
     use std::path::Path;
-    use std::process::{Command, Stdio};
 
     let project_dir = dir
         .unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf()));
     let compose_path = project_dir.join(".dx").join("docker-compose.yml");
 
+    // Remote engines and the containerized mode go through the Docker CLI
+    let runtime = compose::Runtime::resolve(&project_dir, runtime)?;
+    if runtime != compose::Runtime::Docker && (engine.is_some() || containerized) {
+        return Err(DxError::Unsupported(format!(
+            "{} não é suportado com --context/--host nem no modo conteinerizado (DX_CONTAINERIZED); use --runtime docker.",
+            runtime.name()
+        )));
+    }
+
     if !compose_path.exists() {
         eprintln!(
             "Arquivo não encontrado: {}",
             compose_path.display()
         );
-        println!("Gerando manifesto automaticamente (dx dev-services) para: {}", project_dir.display());
+        outln!("Gerando manifesto automaticamente (dx dev-services) para: {}", project_dir.display());
         // Tenta gerar o manifesto e incorporar Telemetry no mesmo arquivo
         // equivalente a executar: dx dev-services <dir>
-        cmd_dev_services(true, Some(project_dir.clone()));
+        cmd_dev_services(true, false, &[], Some(project_dir.clone()))?;
         // Recheca se foi criado
         if !compose_path.exists() {
-            eprintln!("Falha ao gerar .dx/docker-compose.yml automaticamente. Verifique mensagens acima ou execute 'dx dev-services' manualmente.");
-            return;
+            return Err(DxError::NotFound(
+                "Falha ao gerar .dx/docker-compose.yml automaticamente. Verifique mensagens acima ou execute 'dx dev-services' manualmente.".to_string(),
+            ));
         }
     }
 
-    // Migração: corrigir caminhos legados para evitar erros de montagem
-    // - ".dx/telemetry/" -> "telemetry/"
-    // - "telemetry/" -> "./telemetry/" (força bind mount)
-    if let Ok(content) = std::fs::read_to_string(&compose_path) {
-        let mut fixed = content.clone();
-        let mut changed = false;
-        if fixed.contains(".dx/telemetry/") {
-            fixed = fixed.replace(".dx/telemetry/", "telemetry/");
-            changed = true;
-        }
-        if fixed.contains("telemetry/") && !fixed.contains("./telemetry/") {
-            fixed = fixed.replace("telemetry/", "./telemetry/");
-            changed = true;
-        }
-        if changed && fixed != content {
-            match std::fs::write(&compose_path, fixed) {
-                Ok(_) => println!("Ajustando caminhos de telemetry no compose (bind mounts ./telemetry)."),
-                Err(e) => eprintln!("Aviso: falha ao auto-corrigir caminhos de telemetry no compose: {}", e),
-            }
+    // The override only reaches Compose through the regenerated manifest
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some((path, _)) = compose::ComposeFile::load_override(&project_dir.join(".dx"))
+        && modified(&path) > modified(&compose_path)
+    {
+        tracing::warn!(
+            "{} foi alterado depois do manifesto; execute 'dx dev-services' para aplicá-lo",
+            path.display()
+        );
+    }
+
+    // Folders written by older versions of dx are migrated to the current format
+    if ci::is_ci() {
+        if let Some(version) = upgrade::current_version(&project_dir)?
+            && version < upgrade::SCHEMA_VERSION
+        {
+            outln!("Modo CI: .dx no formato v{} (atual: v{}); execute 'dx upgrade'.", version, upgrade::SCHEMA_VERSION);
         }
+    } else {
+        upgrade::apply_pending(&project_dir)?;
     }
 
-    println!("Iniciando Dev Services usando: {}", compose_path.display());
-
-    // Prefer Docker Compose V2 (docker compose). If it fails to spawn, fallback to legacy docker-compose.
-    let try_docker_compose_v2 = || -> std::io::Result<std::process::ExitStatus> {
-        Command::new("docker")
-            .arg("compose")
-            .arg("-f")
-            .arg(&compose_path)
-            .arg("up")
-            .arg("-d")
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()
-    };
+    // Mistakes in the manifest are reported here, with their lines, rather than halfway through `up`
+    compose_check::validate(&compose_path, compose_path.strip_prefix(&project_dir).unwrap_or(&compose_path), &profiles)?;
 
-    let try_docker_compose_v1 = || -> std::io::Result<std::process::ExitStatus> {
-        Command::new("docker-compose")
-            .arg("-f")
-            .arg(&compose_path)
-            .arg("up")
-            .arg("-d")
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()
+    // Without --profile every profile is active, i.e. the whole stack
+    let available = dev_services::manifest_profiles(&std::fs::read_to_string(&compose_path).unwrap_or_default());
+    if let Some(unknown) = profiles.iter().find(|p| !available.contains(p)) {
+        return Err(DxError::InvalidInput(if available.is_empty() {
+            format!("O manifesto não define profiles ('{}'). Regenere-o com: dx dev-services", unknown)
+        } else {
+            format!("Profile desconhecido: '{}'. Disponíveis: {}", unknown, available.join(", "))
+        }));
+    }
+    let active = if profiles.is_empty() { available.join(",") } else { profiles.join(",") };
+
+    // A named instance runs beside the default stack, from its own manifest
+    let instance = match &instance {
+        Some(_) if containerized => {
+            return Err(DxError::Unsupported(
+                "--instance não é suportado no modo conteinerizado (DX_CONTAINERIZED).".to_string(),
+            ));
+        }
+        Some(name) => {
+            let (instance, path) = instances::prepare(&project_dir, &compose_path, name)?;
+            outln!("{}", instance.describe());
+            Some(path)
+        }
+        None => None,
     };
 
-    match try_docker_compose_v2() {
-        Ok(status) if status.success() => {
-            println!("Serviços iniciados com Docker Compose (V2). Use 'docker compose ps' para ver o status.");
-            return;
-        }
-        Ok(_status) => {
-            eprintln!("Falha ao executar 'docker compose'. Tentando 'docker-compose' (CLI legada)...");
+    // The engine is remembered for stop/logs/status; the report links to its host
+    if instance.is_none() {
+        let previous = remote::address(&project_dir);
+        if let Some(engine) = &engine {
+            outln!("Docker remoto: {}", engine.describe());
         }
-        Err(e) => {
-            eprintln!("Não foi possível executar 'docker compose': {}. Tentando 'docker-compose' (CLI legada)...", e);
+        remote::save(&project_dir, engine.clone())?;
+        let report_path = crate::settings::get(&project_dir).report_path(&project_dir);
+        if remote::address(&project_dir) != previous && report_path.exists() {
+            let report = crate::report::build_analyzer_report(&project_dir, &dev_services::detect_dependencies(&project_dir));
+            std::fs::write(&report_path, report).context(format!("Erro ao atualizar {}", report_path.display()))?;
         }
     }
 
-    match try_docker_compose_v1() {
-        Ok(status) if status.success() => {
-            println!("Serviços iniciados com docker-compose. Use 'docker-compose ps' para ver o status.");
-        }
-        Ok(_status) => {
-            eprintln!("Falha ao executar 'docker-compose'. Verifique se o Docker Desktop está instalado e em execução.");
-        }
-        Err(e) => {
-            eprintln!("Erro ao tentar executar 'docker-compose': {}", e);
-            eprintln!("Dicas:");
-            eprintln!(" - Instale o Docker Desktop para Windows");
-            eprintln!(" - Reabra o terminal após a instalação para atualizar o PATH");
-            eprintln!(" - Teste no terminal: 'docker --version' e 'docker compose version'");
-        }
+    // Auto-stop: labels via an extra compose file and a background watchdog
+    let mut idle = auto_stop::resolve(&project_dir, auto_stop)?;
+    if idle.is_some() && ci::is_ci() {
+        outln!("Modo CI: auto-stop ignorado.");
+        idle = None;
+    }
+    // The watchdog would die with dx's container
+    if idle.is_some() && containerized {
+        outln!("Modo conteinerizado: auto-stop ignorado.");
+        idle = None;
+    }
+    // The watchdog watches connections to ports of this machine
+    if idle.is_some() && engine.is_some() {
+        outln!("Docker remoto: auto-stop ignorado.");
+        idle = None;
+    }
+    // The watchdog belongs to the default stack
+    if idle.is_some() && instance.is_some() {
+        outln!("Instância: auto-stop ignorado.");
+        idle = None;
+    }
+    // The watchdog stops the containers through Docker
+    if idle.is_some() && runtime != compose::Runtime::Docker {
+        outln!("{}: auto-stop ignorado.", runtime.name());
+        idle = None;
+    }
+    // Inside a container, bind mounts must point to the project on the host;
+    // on a remote engine, the project's files go as configs
+    let mut compose_files = vec![match &engine {
+        Some(engine) => remote::write_remote_compose(&project_dir, &compose_path, engine)?,
+        None if containerized => containerized::write_host_compose(&project_dir, &compose_path)?,
+        None => instance.clone().unwrap_or_else(|| compose_path.clone()),
+    }];
+    match idle {
+        Some(idle) => compose_files.push(auto_stop::write_labels(&project_dir, &compose_path, idle)?),
+        None if instance.is_some() => {}
+        None => auto_stop::disable(&project_dir),
+    }
+    // Images without a build for this machine, fitted outside of the generated manifest
+    if let Some(path) = arch::write_override(&project_dir, &compose_path, arch::Arch::host())? {
+        compose_files.push(path);
     }
+    let compose_path = instance.unwrap_or(compose_path);
+    let runner = compose_files[1..]
+        .iter()
+        .fold(compose::Runner::new(&project_dir, runtime, &compose_files[0]), |runner, file| runner.file(file))
+        .profiles(active.clone());
+    let smoke_window = smoke::window(&project_dir)?;
+
+    outln!("Iniciando Dev Services usando: {}", compose_path.display());
+    let started = std::time::Instant::now();
+
+    // Prefer Compose V2 (docker compose); the legacy CLI is the fallback
+    let compose = runner.up(&services)?;
+    outln!("Serviços iniciados com '{}'. Use '{} ps' para ver o status.", compose.label(), compose.label());
+    start_auto_stop(&project_dir, &compose_path, idle)?;
+    // `up -d` returning 0 doesn't mean the services came up
+    smoke::check(&project_dir, runtime, &compose_path, &active, &services, smoke_window, started)
 }
 
-fn cmd_dev_services_stop(dir: Option<std::path::PathBuf>) {
+/// Start the idle watchdog once the containers are up.
+fn start_auto_stop(project_dir: &std::path::Path, compose_path: &std::path::Path, idle: Option<std::time::Duration>) -> DxResult {
+    let Some(idle) = idle else {
+        return Ok(());
+    };
+    auto_stop::start(project_dir, compose_path, idle)?;
+    outln!(
+        "Auto-stop: os serviços param após {} sem conexões nas portas publicadas (log: {}).",
+        auto_stop::format_duration(idle),
+        project_dir.join(".dx").join("auto-stop").join("watchdog.log").display()
+    );
+    Ok(())
+}
+
+fn cmd_dev_services_stop(
+    dir: Option<std::path::PathBuf>,
+    services: Vec<String>,
+    instance: Option<String>,
+    runtime: Option<compose::Runtime>,
+) -> DxResult {
     use std::env;
     use std::path::Path;
-    use std::process::{Command, Stdio};
 
     let project_dir = dir
         .unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf()));
-    let compose_path = project_dir.join(".dx").join("docker-compose.yml");
+    let compose_path = match &instance {
+        Some(name) => instances::existing(&project_dir, name)?,
+        None => project_dir.join(".dx").join("docker-compose.yml"),
+    };
 
     if !compose_path.exists() {
-        eprintln!(
+        return Err(DxError::NotFound(format!(
             "Arquivo não encontrado: {}\nDica: gere o manifesto com:\n  dx dev-services\nOu especifique o diretório correto com:\n  dx dev-services stop <dir>",
             compose_path.display()
-        );
-        return;
+        )));
     }
 
-    println!("Parando Dev Services usando: {}", compose_path.display());
-
-    let try_docker_compose_v2 = || -> std::io::Result<std::process::ExitStatus> {
-        Command::new("docker")
-            .arg("compose")
-            .arg("-f")
-            .arg(&compose_path)
-            .arg("stop")
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()
-    };
+    outln!("Parando Dev Services usando: {}", compose_path.display());
 
-    let try_docker_compose_v1 = || -> std::io::Result<std::process::ExitStatus> {
-        Command::new("docker-compose")
-            .arg("-f")
-            .arg(&compose_path)
-            .arg("stop")
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()
-    };
+    let runtime = compose::Runtime::resolve(&project_dir, runtime)?;
+    let compose = compose::Runner::new(&project_dir, runtime, &compose_path).stop(&services)?;
+    outln!("Serviços parados com '{}'. Para iniciar novamente: 'dx dev-services run'.", compose.label());
+    Ok(())
+}
 
-    match try_docker_compose_v2() {
-        Ok(status) if status.success() => {
-            println!("Serviços parados com Docker Compose (V2). Para iniciar novamente: 'dx dev-services run'.");
-            return;
-        }
-        Ok(_status) => {
-            eprintln!("Falha ao executar 'docker compose'. Tentando 'docker-compose' (CLI legada)...");
-        }
-        Err(e) => {
-            eprintln!("Não foi possível executar 'docker compose': {}. Tentando 'docker-compose' (CLI legada)...", e);
-        }
+fn cmd_dev_services_logs(
+    dir: Option<std::path::PathBuf>,
+    service: Option<String>,
+    follow: bool,
+    tail: Option<String>,
+    runtime: Option<compose::Runtime>,
+) -> DxResult {
+    use std::env;
+    use std::path::Path;
+
+    let project_dir = dir
+        .unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf()));
+    let compose_path = project_dir.join(".dx").join("docker-compose.yml");
+
+    if !compose_path.exists() {
+        return Err(DxError::NotFound(format!(
+            "Arquivo não encontrado: {}\nDica: gere o manifesto com:\n  dx dev-services\nOu especifique o diretório correto com:\n  dx dev-services logs --dir <dir>",
+            compose_path.display()
+        )));
+    }
+    let services = dev_services::manifest_service_names(&std::fs::read_to_string(&compose_path).unwrap_or_default());
+    if let Some(service) = &service
+        && !services.contains(service)
+    {
+        return Err(DxError::InvalidInput(format!(
+            "Serviço desconhecido: '{}'. Disponíveis: {}",
+            service,
+            services.join(", ")
+        )));
+    }
+    if let Some(tail) = &tail
+        && tail != "all"
+        && tail.parse::<u32>().is_err()
+    {
+        return Err(DxError::InvalidInput(format!("--tail deve ser um número ou 'all' (recebido: '{}')", tail)));
     }
 
-    match try_docker_compose_v1() {
-        Ok(status) if status.success() => {
-            println!("Serviços parados com docker-compose. Para iniciar novamente: 'dx dev-services run'.");
-        }
-        Ok(_status) => {
-            eprintln!("Falha ao executar 'docker-compose'. Verifique se o Docker Desktop está instalado e em execução.");
-        }
-        Err(e) => {
-            eprintln!("Erro ao tentar executar 'docker-compose': {}", e);
-            eprintln!("Dicas:");
-            eprintln!(" - Instale o Docker Desktop para Windows");
-            eprintln!(" - Reabra o terminal após a instalação para atualizar o PATH");
-            eprintln!(" - Teste no terminal: 'docker --version' e 'docker compose version'");
-        }
+    let mut args = vec!["logs", "--no-color"];
+    if follow {
+        args.push("--follow");
     }
+    if let Some(tail) = &tail {
+        args.extend(["--tail", tail]);
+    }
+    args.extend(service.as_deref());
+    let prefixer = logs::Prefixer::new(services);
+
+    let runtime = compose::Runtime::resolve(&project_dir, runtime)?;
+    compose::Runner::new(&project_dir, runtime, &compose_path).logs(&args, &prefixer)?;
+    Ok(())
 }
 
-fn cmd_dev_badges(save_file: bool, dir: Option<std::path::PathBuf>) {
+fn cmd_dev_badges(save_file: bool, recursive: bool, dir: Option<std::path::PathBuf>) -> DxResult {
     use std::env;
-    use std::fs;
-    use std::path::{Path, PathBuf};
+    use std::path::Path;
 
     let target_dir = dir.unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf()));
 
     // Helper
-    fn process_project_dir(save_file: bool, project_dir: &Path) {
-        crate::dev_badges::process_directory(save_file, project_dir);
+    fn process_project_dir(save_file: bool, project_dir: &Path) -> DxResult {
+        crate::dev_badges::process_directory(save_file, project_dir)
     }
 
-    let is_test_projects = target_dir
-        .file_name()
-        .and_then(|n| n.to_str())
-        .map(|n| n.eq_ignore_ascii_case("test-projects"))
-        .unwrap_or(false);
-
-    if is_test_projects && target_dir.is_dir() {
-        println!(
-            "Aplicando dev-badges em todos os projetos dentro de: {}",
-            target_dir.display()
-        );
-        match fs::read_dir(&target_dir) {
-            Ok(entries) => {
-                // Collect and sort subdirs for deterministic order
-                let mut dirs: Vec<PathBuf> = Vec::new();
-                for entry in entries.flatten() {
-                    let path: PathBuf = entry.path();
-                    let Ok(ft) = entry.file_type() else { continue };
-                    if ft.is_symlink() { continue; }
-                    if ft.is_dir() {
-                        // skip hidden
-                        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                            if name.starts_with('.') { continue; }
-                        }
-                        dirs.push(path);
-                    }
-                }
-                dirs.sort();
-                for path in dirs {
-                    println!("\n== Projeto: {} ==", path.display());
-                    process_project_dir(save_file, &path);
-                }
-            }
-            Err(e) => {
-                eprintln!(
-                    "Erro ao listar subdiretórios de {}: {}",
-                    target_dir.display(),
-                    e
-                );
-            }
-        }
-        return;
-    }
-
-    process_project_dir(save_file, &target_dir);
+    workspace::for_each_project(&target_dir, recursive, "Aplicando dev-badges", |p| {
+        process_project_dir(save_file, p)
+    })
 }
 
-fn cmd_dev_badges_clean(dir: Option<std::path::PathBuf>) {
+fn cmd_dev_badges_clean(dir: Option<std::path::PathBuf>, recursive: bool) -> DxResult {
     use std::env;
-    use std::fs;
-    use std::path::{Path, PathBuf};
+    use std::path::Path;
 
     let target_dir = dir.unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf()));
 
-    fn process_project_dir(project_dir: &Path) {
-        crate::dev_badges::process_clean_directory(project_dir);
+    fn process_project_dir(project_dir: &Path) -> DxResult {
+        crate::dev_badges::process_clean_directory(project_dir)
     }
 
-    let is_test_projects = target_dir
-        .file_name()
-        .and_then(|n| n.to_str())
-        .map(|n| n.eq_ignore_ascii_case("test-projects"))
-        .unwrap_or(false);
-
-    if is_test_projects && target_dir.is_dir() {
-        println!(
-            "Limpando badges em todos os projetos dentro de: {}",
-            target_dir.display()
-        );
-        match fs::read_dir(&target_dir) {
-            Ok(entries) => {
-                let mut dirs: Vec<PathBuf> = Vec::new();
-                for entry in entries.flatten() {
-                    let path: PathBuf = entry.path();
-                    let Ok(ft) = entry.file_type() else { continue };
-                    if ft.is_symlink() { continue; }
-                    if ft.is_dir() {
-                        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                            if name.starts_with('.') { continue; }
-                        }
-                        dirs.push(path);
-                    }
-                }
-                dirs.sort();
-                for path in dirs {
-                    println!("\n== Projeto: {} ==", path.display());
-                    process_project_dir(&path);
-                }
-            }
-            Err(e) => {
-                eprintln!(
-                    "Erro ao listar subdiretórios de {}: {}",
-                    target_dir.display(),
-                    e
-                );
-            }
-        }
-        return;
-    }
-
-    process_project_dir(&target_dir);
+    workspace::for_each_project(&target_dir, recursive, "Limpando badges", process_project_dir)
 }
 
-fn cmd_portal() {
-    println!(
+fn cmd_portal() -> DxResult {
+    outln!(
         "Portal do Dev (stub)\n- Gerir configs, semear dados, publicar eventos, inspecionar logs/telemetria e acionar fluxos comuns.\n- Exemplos orientados por IA: 'publique 500 eventos válidos neste tópico', 'gere massa de dados conforme este schema'."
     );
+    Ok(())
 }
 
 
-fn cmd_tests() {
-    println!(
+fn cmd_tests() -> DxResult {
+    outln!(
         "Testes Contínuos & Inteligentes (stub)\n- Geração/expansão de testes por IA (unit, contrato, property-based).\n- Fixtures realistas automáticos, priorização de falhas e explicabilidade no portal."
     );
+    Ok(())
 }
 
-fn cmd_config() {
-    println!(
-        "Configuração sem dor (stub)\n- Schema central tipado, wizards em linguagem natural (ex.: 'quero habilitar TLS e rodar em staging').\n- IA valida, propõe padrões e explica impacto das propriedades."
-    );
+fn cmd_tests_integration(dir: Option<std::path::PathBuf>, no_migrate: bool, timeout: u64, runtime: Option<compose::Runtime>) -> DxResult {
+    let project_dir = dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")));
+    if !project_dir.is_dir() {
+        return Err(DxError::InvalidInput(format!("Diretório inválido: {}", project_dir.display())));
+    }
+    cmd_dev_services_run(Some(project_dir.clone()), Vec::new(), Vec::new(), None, containerized::enabled(false), remote::engine(&project_dir), None, runtime)?;
+    if no_migrate {
+        outln!("Migrações ignoradas (--no-migrate).");
+    } else if migrations::detect(&project_dir).is_some() {
+        migrations::migrate(Some(project_dir.clone()), migrations::MigrateOptions { timeout, dry_run: false, runtime })?;
+    } else {
+        tracing::debug!("nenhuma ferramenta de migração detectada");
+    }
+    dev_test::run_integration(&project_dir)
 }
 
-fn cmd_docs() {
-    println!(
-        "Docs vivas + Q&A (stub)\n- Documentação como código, indexada e consultável via chat embutido.\n- IA referencia trechos, PRs e decisões de arquitetura; sugere golden paths."
+fn cmd_config() -> DxResult {
+    outln!(
+        "Configuração sem dor (stub)\n- Schema central tipado, wizards em linguagem natural (ex.: 'quero habilitar TLS e rodar em staging').\n- IA valida, propõe padrões e explica impacto das propriedades."
     );
+    Ok(())
 }
 
-fn cmd_governance() {
-    println!(
-        "Governança leve, guardrails fortes (stub)\n- Scorecards de DX + DORA.\n- Policies automatizadas e padrões opinativos."
+fn cmd_docs() -> DxResult {
+    outln!(
+        "Docs vivas + Q&A (stub)\n- Documentação como código, indexada e consultável via chat embutido.\n- IA referencia trechos, PRs e decisões de arquitetura; sugere golden paths."
     );
+    Ok(())
 }
 
-
-
-
-/// Limpa recursivamente todas as pastas ".dx" a partir de um diretório raiz
-fn clean_dx_from(root: &std::path::Path) -> (usize, Vec<String>) {
-    use std::fs;
-    use std::path::{Path, PathBuf};
-
-    fn walk_and_clean(dir: &Path, removed: &mut usize, errors: &mut Vec<String>) {
-        // First, attempt to remove ".dx" in this directory, if present
-        let dx_here = dir.join(".dx");
-        if dx_here.is_dir() {
-            match fs::remove_dir_all(&dx_here) {
-                Ok(_) => {
-                    *removed += 1;
-                    println!("Removido: {}", dx_here.display());
-                }
-                Err(e) => {
-                    let msg = format!("Falha ao remover {}: {}", dx_here.display(), e);
-                    eprintln!("{}", msg);
-                    errors.push(msg);
-                }
-            }
-        }
-
-        // Walk children
-        match fs::read_dir(dir) {
-            Ok(read_dir) => {
-                for entry in read_dir.flatten() {
-                    let path: PathBuf = entry.path();
-                    // Skip if not a directory
-                    let Ok(ft) = entry.file_type() else { continue };
-                    if ft.is_symlink() {
-                        // avoid following symlinks
-                        continue;
-                    }
-                    if ft.is_dir() {
-                        // Recurse into subdirectory
-                        walk_and_clean(&path, removed, errors);
-                    }
-                }
-            }
-            Err(e) => {
-                let msg = format!("Falha ao listar {}: {}", dir.display(), e);
-                eprintln!("{}", msg);
-                errors.push(msg);
-            }
-        }
-    }
-
-    let mut removed = 0usize;
-    let mut errors = Vec::new();
-    walk_and_clean(root, &mut removed, &mut errors);
-    (removed, errors)
-}
-
-fn cmd_clean(dir: Option<std::path::PathBuf>) {
-    use std::env;
-    use std::path::Path;
-
-    let root = dir.unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf()));
-
-    if !root.exists() || !root.is_dir() {
-        eprintln!("Diretório inválido para limpeza: {}", root.display());
-        return;
-    }
-
-    println!("Limpando pastas .dx a partir de: {}", root.display());
-    let (removed, errors) = clean_dx_from(&root);
-
-    if removed == 0 {
-        println!("Nenhuma pasta .dx encontrada sob {}", root.display());
-    } else {
-        println!("Pasta(s) .dx removidas: {}", removed);
-    }
-
-    if !errors.is_empty() {
-        eprintln!("Ocorreram {} erro(s) durante a limpeza:", errors.len());
-        for e in errors {
-            eprintln!("- {}", e);
-        }
-    }
-}
-
-fn cmd_analyzer(save_report: bool, report_path: String, dir: Option<std::path::PathBuf>) {
+fn cmd_analyzer(
+    save_report: bool,
+    report_path: Option<String>,
+    write_gitignore: bool,
+    sarif: Option<std::path::PathBuf>,
+    dir: Option<std::path::PathBuf>,
+) -> DxResult {
     use std::env;
     use std::fs;
     use std::path::{Path, PathBuf};
@@ -793,75 +1743,21 @@ fn cmd_analyzer(save_report: bool, report_path: String, dir: Option<std::path::P
         }
     }
 
-    // Helper: check if a directory looks like a project root by presence of marker files
-    fn is_project_root(dir: &Path) -> bool {
-        let markers = [
-            "Cargo.toml",
-            "package.json",
-            "requirements.txt",
-            "pyproject.toml",
-            "setup.py",
-            "pom.xml",
-            "build.gradle",
-            "Gemfile",
-            "go.mod",
-            "composer.json",
-        ];
-        markers.iter().any(|m| dir.join(m).is_file())
-    }
-
-    // Helper: list candidate subprojects under a directory following directory rules
-    fn list_subprojects(root: &Path) -> Vec<PathBuf> {
-        let skip = [
-            ".git", ".github", ".idea", ".vscode", ".dx", "node_modules", "target", "build", "dist", "vendor",
-        ];
-        let mut subs = Vec::new();
-        if let Ok(entries) = fs::read_dir(root) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if !path.is_dir() { continue; }
-                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                if name.starts_with('.') { continue; }
-                if skip.iter().any(|s| s.eq_ignore_ascii_case(&name)) { continue; }
-                if is_project_root(&path) {
-                    subs.push(path);
-                }
-            }
+    // Add .dx to the analyzed directory's .gitignore, only when asked to
+    let ensure_gitignore_has_dx = |dir: &Path| {
+        if !write_gitignore {
+            return;
         }
-        subs
-    }
-
-    // Ensure the analyzed directory's .gitignore contains an entry to ignore .dx; create if needed
-    fn ensure_gitignore_has_dx(dir: &Path) {
-        use std::fs::OpenOptions;
-        use std::io::Write;
-
-        let gi_path = dir.join(".gitignore");
-        match fs::read_to_string(&gi_path) {
-            Ok(content) => {
-                let mut has = false;
-                for line in content.lines() {
-                    let t = line.trim();
-                    if t == ".dx" || t == "/.dx" || t == ".dx/" { has = true; break; }
-                }
-                if !has {
-                    let mut file = match OpenOptions::new().create(true).append(true).open(&gi_path) {
-                        Ok(f) => f,
-                        Err(_) => return,
-                    };
-                    // Ensure previous content ends with newline to avoid gluing
-                    if !content.is_empty() && !content.ends_with(['\n', '\r']) {
-                        let _ = writeln!(file);
-                    }
-                    let _ = writeln!(file, ".dx");
-                }
-            }
-            Err(_) => {
-                // No .gitignore: create one with .dx
-                let _ = fs::write(&gi_path, ".dx\n");
-            }
+        if ci::is_ci() {
+            tracing::info!("modo CI: .gitignore de {} não foi alterado", dir.display());
+            return;
         }
-    }
+        match gitignore::ensure_entries(dir, &[gitignore::DX_DIR.to_string()]) {
+            Ok(added) if !added.is_empty() => outln!("Adicionado .dx em {}", dir.join(".gitignore").display()),
+            Ok(_) => {}
+            Err(e) => eprintln!("Aviso: falha ao atualizar {}: {}", dir.join(".gitignore").display(), e),
+        }
+    };
 
     let cwd = env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf());
     let project_dir: PathBuf = if let Some(provided) = dir {
@@ -871,32 +1767,46 @@ fn cmd_analyzer(save_report: bool, report_path: String, dir: Option<std::path::P
     };
 
     if !project_dir.exists() || !project_dir.is_dir() {
-        eprintln!("Diretório inválido para análise: {}", project_dir.display());
-        return;
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido para análise: {}",
+            project_dir.display()
+        )));
     }
 
-    println!("dx analyzer\n");
-    println!("Analisando o projeto em: {}\n", project_dir.display());
+    // --report-path, then dx.toml, then the default (.dx/analyzer-report.md)
+    let report_path = match report_path {
+        Some(path) => path,
+        None => settings::load(&project_dir)?
+            .analyzer
+            .report_path
+            .unwrap_or_else(|| settings::DEFAULT_REPORT.to_string()),
+    };
+
+    outln!("dx analyzer\n");
+    outln!("Analisando o projeto em: {}\n", project_dir.display());
 
     // If the provided directory contains multiple recognizable subprojects, produce per-directory reports
-    let subprojects = list_subprojects(&project_dir);
+    let subprojects = workspace::subprojects(&project_dir, 1);
     let multi = !subprojects.is_empty();
 
     if multi {
-        println!("Detectamos múltiplos projetos dentro de {}. Gerando relatórios por diretório...", project_dir.display());
+        outln!("Detectamos múltiplos projetos dentro de {}. Gerando relatórios por diretório...", project_dir.display());
         let mut count_ok = 0usize;
+        let mut failure = None;
+        let mut projects = Vec::new();
+        let mut findings = Vec::new();
         for sub in &subprojects {
             // Ensure .gitignore ignores .dx in each subproject
             ensure_gitignore_has_dx(sub);
-            println!("\n--- Projeto: {} ---", sub.display());
+            outln!("\n--- Projeto: {} ---", sub.display());
             let ds_config = dev_services::detect_dependencies(sub);
+            let mut detection = dev_services::Detection::new(sub, &ds_config);
 
             // Print a brief console summary per subproject
             if ds_config.services.is_empty() {
-                println!("Nenhuma dependência de serviços detectada.");
+                outln!("Nenhuma dependência de serviços detectada.");
             } else {
-                let services: Vec<_> = ds_config.services.keys().cloned().collect();
-                println!("Dependências detectadas: {:?}", services);
+                outln!("Dependências detectadas: {:?}", ds_config.service_names());
             }
 
             if save_report {
@@ -909,227 +1819,170 @@ fn cmd_analyzer(save_report: bool, report_path: String, dir: Option<std::path::P
                 if let Some(parent) = out_path.parent() { let _ = fs::create_dir_all(parent); }
                 let report = build_report(sub, &ds_config);
                 match fs::write(&out_path, report) {
-                    Ok(_) => { println!("Relatório salvo em: {}", out_path.display()); count_ok += 1; }
-                    Err(e) => eprintln!("Erro ao salvar relatório em {}: {}", out_path.display(), e),
+                    Ok(_) => {
+                        outln!("Relatório salvo em: {}", out_path.display());
+                        detection.report = Some(out_path.display().to_string());
+                        count_ok += 1;
+                    }
+                    Err(e) => {
+                        let e = DxError::Io {
+                            context: format!("Erro ao salvar relatório em {}", out_path.display()),
+                            source: e,
+                        };
+                        eprintln!("{e}");
+                        failure = Some(e);
+                    }
                 }
             }
+            if sarif.is_some() {
+                let prefix = sub.strip_prefix(&project_dir).unwrap_or(sub);
+                findings.extend(sarif::analyzer_findings(sub, &ds_config).into_iter().map(|mut f| {
+                    f.path = prefix.join(&f.path);
+                    f
+                }));
+            }
+            projects.push(output::Render::to_json(&detection));
+        }
+        output::set_data(projects_data(projects, true));
+        if let Some(path) = &sarif {
+            sarif::Log { rules: sarif::analyzer_rules(), findings, ..Default::default() }.write(&project_dir.join(path))?;
         }
         if !save_report {
-            println!("\nPara salvar os relatórios, execute sem --no-save ou forneça --report-path (relativo). Cada relatório será salvo no .dx de cada projeto.");
+            outln!("\nPara salvar os relatórios, execute sem --no-save ou forneça --report-path (relativo). Cada relatório será salvo no .dx de cada projeto.");
         } else {
-            println!("\nRelatórios gerados: {}/{}", count_ok, subprojects.len());
+            outln!("\nRelatórios gerados: {}/{}", count_ok, subprojects.len());
         }
-        return;
+        return failure.map_or(Ok(()), Err);
     }
 
     // Single-project behavior (existing flow)
     // Ensure .gitignore ignores .dx in this project
     ensure_gitignore_has_dx(&project_dir);
     let ds_config = dev_services::detect_dependencies(&project_dir);
-    println!("=== Dev Services ===");
+    let mut detection = dev_services::Detection::new(&project_dir, &ds_config);
+    outln!("=== Dev Services ===");
     if ds_config.services.is_empty() {
-        println!("Nenhuma dependência de serviços detectada.");
-        println!("Sugestão: adicione variáveis/.env ou dependências para Postgres, Redis, Kafka (Redpanda), MongoDB, Flink, etc.\n");
+        outln!("Nenhuma dependência de serviços detectada.");
+        outln!("Sugestão: adicione variáveis/.env ou dependências para Postgres, Redis, Kafka (Redpanda), MongoDB, Flink, etc.\n");
     } else {
-        let services: Vec<_> = ds_config.services.keys().cloned().collect();
-        println!("Dependências detectadas: {:?}", services);
-        println!("\nManifesto gerado (docker-compose.yml):\n");
+        outln!("Dependências detectadas: {:?}", ds_config.service_names());
+        outln!("\nManifesto gerado (docker-compose.yml):\n");
         let yaml = ds_config.to_yaml();
-        println!("{}", yaml);
+        outln!("{}", yaml);
     }
 
     // 2) Dev Badges (stub): quais badges poderíamos aplicar
-    println!("\n=== Dev Badges ===");
+    outln!("\n=== Dev Badges ===");
     if ds_config.services.is_empty() {
-        println!("Sem badges a aplicar no momento.");
+        outln!("Sem badges a aplicar no momento.");
     } else {
-        println!("Badges sugeridas com base nas dependências detectadas: {}",
-                 ds_config.services.keys().cloned().collect::<Vec<_>>().join(", "));
-        println!("Use: dx dev-badges (ou dev-badges clean)");
+        outln!("Badges sugeridas com base nas dependências detectadas: {}",
+                 ds_config.service_names().join(", "));
+        outln!("Use: dx dev-badges (ou dev-badges clean)");
     }
 
     // 3) Portal (stub)
-    println!("\n=== Portal (Dev UI) ===");
-    println!("Integrações e operações do desenvolvedor em um só lugar. Em breve: automações e plugins.\nUse: dx portal");
+    outln!("\n=== Portal (Dev UI) ===");
+    outln!("Integrações e operações do desenvolvedor em um só lugar. Em breve: automações e plugins.\nUse: dx portal");
 
     // 4) Testes (stub)
-    println!("\n=== Testes Contínuos & Inteligentes ===");
-    println!("Geração/execução de testes assistidos por IA (futuro).\nUse: dx tests");
+    outln!("\n=== Testes Contínuos & Inteligentes ===");
+    outln!("Geração/execução de testes assistidos por IA (futuro).\nUse: dx tests");
 
     // 5) Configuração (stub)
-    println!("\n=== Configuração ===");
-    println!("Configuração tipada com wizards em linguagem natural.\nUse: dx config");
+    outln!("\n=== Configuração ===");
+    outln!("Configuração tipada com wizards em linguagem natural.\nUse: dx config");
 
     // 6) Documentação (stub)
-    println!("\n=== Documentação ===");
-    println!("Docs vivas + Q&A no código (busca conversacional).\nUse: dx docs");
+    outln!("\n=== Documentação ===");
+    outln!("Docs vivas + Q&A no código (busca conversacional).\nUse: dx docs | dx docs verify-quickstart");
 
     // 7) Governança (stub)
-    println!("\n=== Governança ===");
-    println!("Guardrails, scorecards e automações de qualidade.\nUse: dx governance");
+    outln!("\n=== Governança ===");
+    outln!("Guardrails, scorecards e automações de qualidade.\nUse: dx governance");
 
     // 8) Telemetria (stub)
-    println!("\n=== Telemetria ===");
-    println!("Observabilidade e feedback loops curtos por padrão.\nUse: dx dev-services");
+    outln!("\n=== Telemetria ===");
+    outln!("Observabilidade e feedback loops curtos por padrão.\nUse: dx dev-services");
 
     if save_report {
         let (final_path, _used_default) = compute_output_path(&project_dir, &report_path);
         // Ensure parent exists
         if let Some(parent) = final_path.parent() { let _ = fs::create_dir_all(parent); }
         let report = build_report(&project_dir, &ds_config);
-        match fs::write(&final_path, report) {
-            Ok(_) => println!("\nRelatório salvo em: {}", final_path.display()),
-            Err(e) => eprintln!("\nErro ao salvar relatório: {}", e),
-        }
+        fs::write(&final_path, report).context("Erro ao salvar relatório")?;
+        outln!("\nRelatório salvo em: {}", final_path.display());
+        detection.report = Some(final_path.display().to_string());
     } else {
-        println!("\nPara salvar este relatório, execute sem --no-save ou use --report-path");
+        outln!("\nPara salvar este relatório, execute sem --no-save ou use --report-path");
     }
+    if let Some(path) = &sarif {
+        let findings = sarif::analyzer_findings(&project_dir, &ds_config);
+        sarif::Log { rules: sarif::analyzer_rules(), findings, ..Default::default() }.write(&project_dir.join(path))?;
+    }
+    output::set_data(output::Render::to_json(&detection));
+    Ok(())
 }
 
 
-fn cmd_dev_services_restart(dir: Option<std::path::PathBuf>) {
+fn cmd_dev_services_restart(
+    dir: Option<std::path::PathBuf>,
+    services: Vec<String>,
+    runtime: Option<compose::Runtime>,
+) -> DxResult {
     use std::env;
     use std::path::Path;
-    use std::process::{Command, Stdio};
 
     let project_dir = dir
         .unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf()));
     let compose_path = project_dir.join(".dx").join("docker-compose.yml");
 
     if !compose_path.exists() {
-        eprintln!(
+        return Err(DxError::NotFound(format!(
             "Arquivo não encontrado: {}\nDica: gere o manifesto com:\n  dx-cli dev-services\nOu especifique o diretório correto com:\n  dx-cli dev-services restart <dir>",
             compose_path.display()
-        );
-        return;
+        )));
     }
 
-    println!("Reiniciando Dev Services usando: {}", compose_path.display());
-
-    let try_docker_compose_v2 = || -> std::io::Result<std::process::ExitStatus> {
-        Command::new("docker")
-            .arg("compose")
-            .arg("-f")
-            .arg(&compose_path)
-            .arg("restart")
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()
-    };
-
-    let try_docker_compose_v1 = || -> std::io::Result<std::process::ExitStatus> {
-        Command::new("docker-compose")
-            .arg("-f")
-            .arg(&compose_path)
-            .arg("restart")
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()
-    };
+    outln!("Reiniciando Dev Services usando: {}", compose_path.display());
 
-    match try_docker_compose_v2() {
-        Ok(status) if status.success() => {
-            println!("Serviços reiniciados com Docker Compose (V2). Use 'docker compose ps' para ver o status.");
-            return;
-        }
-        Ok(_status) => {
-            eprintln!("Falha ao executar 'docker compose'. Tentando 'docker-compose' (CLI legada)...");
-        }
-        Err(e) => {
-            eprintln!("Não foi possível executar 'docker compose': {}. Tentando 'docker-compose' (CLI legada)...", e);
-        }
-    }
-
-    match try_docker_compose_v1() {
-        Ok(status) if status.success() => {
-            println!("Serviços reiniciados com docker-compose. Use 'docker-compose ps' para ver o status.");
-        }
-        Ok(_status) => {
-            eprintln!("Falha ao executar 'docker-compose'. Verifique se o Docker Desktop está instalado e em execução.");
-        }
-        Err(e) => {
-            eprintln!("Erro ao tentar executar 'docker-compose': {}", e);
-            eprintln!("Dicas:");
-            eprintln!(" - Instale o Docker Desktop para Windows");
-            eprintln!(" - Reabra o terminal após a instalação para atualizar o PATH");
-            eprintln!(" - Teste no terminal: 'docker --version' e 'docker compose version'");
-        }
-    }
+    let runtime = compose::Runtime::resolve(&project_dir, runtime)?;
+    let compose = compose::Runner::new(&project_dir, runtime, &compose_path).restart(&services)?;
+    outln!("Serviços reiniciados com '{}'. Use '{} ps' para ver o status.", compose.label(), compose.label());
+    Ok(())
 }
 
 
-fn cmd_dev_services_remove(dir: Option<std::path::PathBuf>) {
+fn cmd_dev_services_remove(
+    dir: Option<std::path::PathBuf>,
+    instance: Option<String>,
+    runtime: Option<compose::Runtime>,
+) -> DxResult {
     use std::env;
     use std::path::Path;
-    use std::process::{Command, Stdio};
 
     let project_dir = dir
         .unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf()));
-    let compose_path = project_dir.join(".dx").join("docker-compose.yml");
+    let compose_path = match &instance {
+        Some(name) => instances::existing(&project_dir, name)?,
+        None => project_dir.join(".dx").join("docker-compose.yml"),
+    };
 
     if !compose_path.exists() {
-        eprintln!(
+        return Err(DxError::NotFound(format!(
             "Arquivo não encontrado: {}\nDica: gere o manifesto com:\n  dx-cli dev-services\nOu especifique o diretório correto com:\n  dx-cli dev-services remove <dir>",
             compose_path.display()
-        );
-        return;
+        )));
     }
 
-    println!("Removendo containers de Dev Services usando: {}", compose_path.display());
-
-    let try_docker_compose_v2 = || -> std::io::Result<std::process::ExitStatus> {
-        Command::new("docker")
-            .arg("compose")
-            .arg("-f")
-            .arg(&compose_path)
-            .arg("down")
-            .arg("-v")
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()
-    };
-
-    let try_docker_compose_v1 = || -> std::io::Result<std::process::ExitStatus> {
-        Command::new("docker-compose")
-            .arg("-f")
-            .arg(&compose_path)
-            .arg("down")
-            .arg("-v")
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()
-    };
-
-    match try_docker_compose_v2() {
-        Ok(status) if status.success() => {
-            println!("Containers e volumes removidos com Docker Compose (V2). Para iniciar novamente: 'dx-cli dev-services run'.");
-            return;
-        }
-        Ok(_status) => {
-            eprintln!("Falha ao executar 'docker compose'. Tentando 'docker-compose' (CLI legada)...");
-        }
-        Err(e) => {
-            eprintln!("Não foi possível executar 'docker compose': {}. Tentando 'docker-compose' (CLI legada)...", e);
-        }
-    }
+    outln!("Removendo containers de Dev Services usando: {}", compose_path.display());
 
-    match try_docker_compose_v1() {
-        Ok(status) if status.success() => {
-            println!("Containers e volumes removidos com docker-compose. Para iniciar novamente: 'dx-cli dev-services run'.");
-        }
-        Ok(_status) => {
-            eprintln!("Falha ao executar 'docker-compose'. Verifique se o Docker Desktop está instalado e em execução.");
-        }
-        Err(e) => {
-            eprintln!("Erro ao tentar executar 'docker-compose': {}", e);
-            eprintln!("Dicas:");
-            eprintln!(" - Instale o Docker Desktop para Windows");
-            eprintln!(" - Reabra o terminal após a instalação para atualizar o PATH");
-            eprintln!(" - Teste no terminal: 'docker --version' e 'docker compose version'");
-        }
+    let runtime = compose::Runtime::resolve(&project_dir, runtime)?;
+    let compose = compose::Runner::new(&project_dir, runtime, &compose_path).down(true)?;
+    outln!("Containers e volumes removidos com '{}'. Para iniciar novamente: 'dx-cli dev-services run'.", compose.label());
+    // A removed instance frees its ports offset for the next one
+    match &instance {
+        Some(name) => instances::release(&project_dir, name),
+        None => Ok(()),
     }
 }
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Generated files with a block managed by dx (CI pipelines, task runners).
+//!
+//! dx owns the lines between a start and an end marker comment: regenerating
+//! replaces only them and keeps the rest of the file. A file without the
+//! markers was not written by dx and is only overwritten with `--force`.
+use std::fs;
+use std::path::Path;
+
+use crate::error::{DxError, DxResult, IoContext};
+
+pub struct Markers {
+    pub start: &'static str,
+    pub end: &'static str,
+}
+
+impl Markers {
+    /// `body` between the markers, with `note` as a comment after the start.
+    pub fn wrap(&self, note: &str, body: &str) -> String {
+        format!("{}\n# {note}\n{body}{}\n", self.start, self.end)
+    }
+
+    /// Replace the marked block of `existing` with `block` (markers included).
+    /// `None` when `existing` has no complete block.
+    pub fn replace(&self, existing: &str, block: &str) -> Option<String> {
+        let start = existing.find(self.start)?;
+        let end = start + existing[start..].find(self.end)?;
+        let end = existing[end..]
+            .find('\n')
+            .map_or(existing.len(), |i| end + i + 1);
+        let mut content = existing.to_string();
+        content.replace_range(start..end, block);
+        Some(content)
+    }
+}
+
+pub struct WriteOptions {
+    /// Print the result instead of writing it
+    pub dry_run: bool,
+    /// Overwrite a file that was not generated by dx
+    pub force: bool,
+}
+
+/// Write `block` (see [`Markers::wrap`]) into `path`, keeping what is outside
+/// the markers. Returns whether the file changed.
+pub fn write(path: &Path, markers: &Markers, block: &str, opts: &WriteOptions) -> DxResult<bool> {
+    let existing = match fs::read_to_string(path) {
+        Ok(c) => Some(c),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => {
+            return Err(DxError::Io {
+                context: format!("Erro ao ler {}", path.display()),
+                source: e,
+            });
+        }
+    };
+    let content = match existing.as_deref().map(|c| markers.replace(c, block)) {
+        None => block.to_string(),
+        Some(Some(updated)) => updated,
+        Some(None) if opts.force || opts.dry_run => block.to_string(),
+        Some(None) => {
+            return Err(DxError::InvalidInput(format!(
+                "{} já existe e não foi gerado pelo dx; use --force para sobrescrever.",
+                path.display()
+            )));
+        }
+    };
+
+    if opts.dry_run {
+        outln!("\n{content}");
+        outln!("Execução em modo --dry-run: nenhum arquivo foi alterado.");
+        return Ok(false);
+    }
+    if existing.as_deref() == Some(content.as_str()) {
+        outln!("\n{} já está atualizado.", path.display());
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context(format!("Erro ao criar {}", parent.display()))?;
+    }
+    fs::write(path, &content).context(format!("Erro ao escrever {}", path.display()))?;
+    outln!("\nArquivo salvo: {}", path.display());
+    Ok(true)
+}
@@ -0,0 +1,381 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Database migrations against the Dev Services (`dx dev-services migrate`).
+//!
+//! The project's migration tool is detected from its files (Prisma, Rails,
+//! Alembic, Diesel, sqlx, Liquibase, Flyway) and run against the database of
+//! `.dx/docker-compose.yml` once it accepts connections. The connection is
+//! passed the way each tool expects it (`DATABASE_URL`, `--database-url`,
+//! `-url`/`-Dflyway.url`), along with the rest of the `dx env` variables.
+use std::net::{SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::compose::{Runner, Runtime};
+use crate::dev_services::{Database, DatabaseEngine};
+use crate::error::{DxError, DxResult};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tool {
+    Prisma,
+    Rails,
+    Alembic,
+    Diesel,
+    Sqlx,
+    Liquibase,
+    Flyway,
+}
+
+impl Tool {
+    pub fn name(self) -> &'static str {
+        match self {
+            Tool::Prisma => "prisma",
+            Tool::Rails => "rails",
+            Tool::Alembic => "alembic",
+            Tool::Diesel => "diesel",
+            Tool::Sqlx => "sqlx",
+            Tool::Liquibase => "liquibase",
+            Tool::Flyway => "flyway",
+        }
+    }
+
+    /// How to install the CLI when it is missing.
+    fn install_hint(self) -> &'static str {
+        match self {
+            Tool::Prisma => "npm install --save-dev prisma",
+            Tool::Rails => "bundle install",
+            Tool::Alembic => "pip install alembic",
+            Tool::Diesel => {
+                "cargo install diesel_cli --no-default-features --features postgres,mysql"
+            }
+            Tool::Sqlx => "cargo install sqlx-cli --no-default-features --features postgres,mysql",
+            Tool::Liquibase => "https://docs.liquibase.com/start/install/",
+            Tool::Flyway => "https://documentation.red-gate.com/fd/command-line-184127404.html",
+        }
+    }
+}
+
+fn contains(dir: &Path, file: &str, needle: &str) -> bool {
+    std::fs::read_to_string(dir.join(file)).is_ok_and(|c| c.contains(needle))
+}
+
+fn build_file_contains(dir: &Path, needle: &str) -> bool {
+    ["pom.xml", "build.gradle", "build.gradle.kts"]
+        .iter()
+        .any(|f| contains(dir, f, needle))
+}
+
+/// Migration tool of the project in `dir`, if any.
+pub fn detect(dir: &Path) -> Option<Tool> {
+    let has = |p: &str| dir.join(p).exists();
+    if has("prisma/schema.prisma") {
+        Some(Tool::Prisma)
+    } else if has("db/migrate") && contains(dir, "Gemfile", "rails") {
+        Some(Tool::Rails)
+    } else if has("alembic.ini") {
+        Some(Tool::Alembic)
+    } else if has("diesel.toml") || (has("migrations") && contains(dir, "Cargo.toml", "diesel")) {
+        Some(Tool::Diesel)
+    } else if has("migrations") && contains(dir, "Cargo.toml", "sqlx") {
+        Some(Tool::Sqlx)
+    } else if has("liquibase.properties")
+        || has("src/main/resources/db/changelog")
+        || build_file_contains(dir, "liquibase")
+    {
+        Some(Tool::Liquibase)
+    } else if has("flyway.conf")
+        || has("flyway.toml")
+        || has("src/main/resources/db/migration")
+        || build_file_contains(dir, "flyway")
+    {
+        Some(Tool::Flyway)
+    } else {
+        None
+    }
+}
+
+/// A command to run in the project directory.
+struct Invocation {
+    program: String,
+    args: Vec<String>,
+    /// `DATABASE_URL` for tools that read it from the environment
+    database_url: Option<String>,
+}
+
+impl Invocation {
+    fn new(program: &str, args: &[&str]) -> Self {
+        Invocation {
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            database_url: None,
+        }
+    }
+
+    fn arg(mut self, arg: String) -> Self {
+        self.args.push(arg);
+        self
+    }
+
+    fn env_url(mut self, url: String) -> Self {
+        self.database_url = Some(url);
+        self
+    }
+
+    fn display(&self) -> String {
+        crate::platform::command_line(
+            std::iter::once(self.program.as_str()).chain(self.args.iter().map(String::as_str)),
+        )
+    }
+}
+
+/// Maven or Gradle (wrapper first) command for the build plugin goals.
+fn build_tool(dir: &Path) -> Option<&'static str> {
+    let wrapper = |name: &'static str| crate::platform::wrapper(dir, name).map(|_| name);
+    if dir.join("pom.xml").is_file() {
+        wrapper("mvnw").or(Some("mvn"))
+    } else if dir.join("build.gradle").is_file() || dir.join("build.gradle.kts").is_file() {
+        wrapper("gradlew").or(Some("gradle"))
+    } else {
+        None
+    }
+}
+
+fn invocation(tool: Tool, dir: &Path, db: &Database) -> DxResult<Invocation> {
+    let on_path = |program: &str| crate::plugins::find_on_path(program).is_some();
+    Ok(match tool {
+        Tool::Prisma => Invocation::new("npx", &["prisma", "migrate", "deploy"]).env_url(db.url()),
+        Tool::Rails => {
+            // ActiveRecord's MySQL adapter is registered as mysql2
+            let url = match db.engine {
+                DatabaseEngine::Postgres => db.url(),
+                DatabaseEngine::Mysql => db.url_with_scheme("mysql2"),
+            };
+            let rails = if dir.join("bin/rails").is_file() {
+                Invocation::new("ruby", &["bin/rails", "db:migrate"])
+            } else {
+                Invocation::new("bundle", &["exec", "rails", "db:migrate"])
+            };
+            rails.env_url(url)
+        }
+        Tool::Alembic => {
+            // SQLAlchemy no longer accepts the postgres:// alias
+            let url = match db.engine {
+                DatabaseEngine::Postgres => db.url_with_scheme("postgresql"),
+                DatabaseEngine::Mysql => db.url(),
+            };
+            Invocation::new("alembic", &["upgrade", "head"]).env_url(url)
+        }
+        Tool::Diesel => Invocation::new("diesel", &["migration", "run", "--database-url"])
+            .arg(db.url())
+            .env_url(db.url()),
+        Tool::Sqlx => Invocation::new("sqlx", &["migrate", "run", "--database-url"])
+            .arg(db.url())
+            .env_url(db.url()),
+        Tool::Flyway if on_path("flyway") || build_tool(dir).is_none() => {
+            Invocation::new("flyway", &["migrate"])
+                .arg(format!("-url={}", db.jdbc_url()))
+                .arg(format!("-user={}", db.user))
+                .arg(format!("-password={}", db.password))
+        }
+        Tool::Flyway => {
+            // The guard above leaves only projects with a build tool
+            let build = build_tool(dir).unwrap_or("mvn");
+            let goal = if build.starts_with("mvn") {
+                "flyway:migrate"
+            } else {
+                "flywayMigrate"
+            };
+            Invocation::new(build, &[goal])
+                .arg(format!("-Dflyway.url={}", db.jdbc_url()))
+                .arg(format!("-Dflyway.user={}", db.user))
+                .arg(format!("-Dflyway.password={}", db.password))
+        }
+        Tool::Liquibase if on_path("liquibase") => Invocation::new("liquibase", &["update"])
+            .arg(format!("--url={}", db.jdbc_url()))
+            .arg(format!("--username={}", db.user))
+            .arg(format!("--password={}", db.password)),
+        Tool::Liquibase => match build_tool(dir) {
+            Some(mvn @ ("mvn" | "mvnw")) => Invocation::new(mvn, &["liquibase:update"])
+                .arg(format!("-Dliquibase.url={}", db.jdbc_url()))
+                .arg(format!("-Dliquibase.username={}", db.user))
+                .arg(format!("-Dliquibase.password={}", db.password)),
+            _ => {
+                return Err(DxError::Tool(format!(
+                    "Liquibase não encontrado no PATH.\nDica: instale a CLI ({})",
+                    tool.install_hint()
+                )));
+            }
+        },
+    })
+}
+
+/// Program to spawn: project wrappers are run from `dir`, everything else is
+/// looked up on `PATH` (with the platform's executable extensions).
+fn resolve(program: &str, dir: &Path) -> Option<PathBuf> {
+    match program {
+        "mvnw" | "gradlew" => Some(dir.join(crate::platform::wrapper_name(program))),
+        _ => crate::plugins::find_on_path(program),
+    }
+}
+
+/// Check run inside the container. The images' init scripts run a temporary
+/// server without TCP, so connecting over TCP only succeeds on the final one.
+fn ready_check(db: &Database) -> Vec<String> {
+    match db.engine {
+        DatabaseEngine::Postgres => vec![
+            "pg_isready".into(),
+            "-h".into(),
+            "127.0.0.1".into(),
+            "-U".into(),
+            db.user.clone(),
+            "-d".into(),
+            db.name.clone(),
+        ],
+        DatabaseEngine::Mysql => vec![
+            "sh".into(),
+            "-c".into(),
+            "mariadb-admin ping -h127.0.0.1 -uroot 2>/dev/null || mysqladmin ping -h127.0.0.1 -uroot"
+                .into(),
+        ],
+    }
+}
+
+/// Whether the database accepts connections; `None` when Compose can't be
+/// run to ask the container.
+fn container_ready(runner: &Runner, db: &Database) -> Option<bool> {
+    let status = runner
+        .exec(&db.service, &[("MYSQL_PWD", &db.password)])
+        .ok()?
+        .args(ready_check(db))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .ok()?;
+    Some(status.success())
+}
+
+/// Waits until `db` is healthy: its host port accepts connections and, when
+/// Compose is reachable, the server inside the container answers.
+fn wait_ready(runner: &Runner, db: &Database, timeout: Duration) -> DxResult {
+    let addr = SocketAddr::from(([127, 0, 0, 1], db.port));
+    let deadline = Instant::now() + timeout;
+    loop {
+        let ready = TcpStream::connect_timeout(&addr, Duration::from_secs(1)).is_ok()
+            && container_ready(runner, db).unwrap_or(true);
+        if ready {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(DxError::Tool(format!(
+                "O banco '{}' não ficou pronto em {}s (localhost:{}).\nDica: verifique os containers com '{}'",
+                db.service,
+                timeout.as_secs(),
+                db.port,
+                runner.command_line(&["ps"])
+            )));
+        }
+        std::thread::sleep(Duration::from_secs(1));
+    }
+}
+
+pub struct MigrateOptions {
+    /// Seconds to wait for the database to become healthy
+    pub timeout: u64,
+    /// Only show the tool and the command
+    pub dry_run: bool,
+    /// `--runtime`
+    pub runtime: Option<Runtime>,
+}
+
+/// `dx dev-services migrate`: runs the project's migrations against the
+/// database Dev Service.
+pub fn migrate(dir: Option<PathBuf>, opts: MigrateOptions) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !project_dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            project_dir.display()
+        )));
+    }
+    let Some(tool) = detect(&project_dir) else {
+        return Err(DxError::NotFound(format!(
+            "Nenhuma ferramenta de migração detectada em {} (Flyway, Liquibase, Alembic, Diesel, sqlx, Prisma, Rails).",
+            project_dir.display()
+        )));
+    };
+    let compose = project_dir.join(".dx").join("docker-compose.yml");
+    let yaml = std::fs::read_to_string(&compose).map_err(|_| {
+        DxError::NotFound(format!(
+            "Arquivo não encontrado: {}\nDica: suba os serviços com:\n  dx dev-services run",
+            compose.display()
+        ))
+    })?;
+    let Some(db) = crate::dev_services::manifest_services(&yaml)
+        .iter()
+        .find_map(|s| s.database())
+    else {
+        return Err(DxError::NotFound(format!(
+            "Nenhum banco de dados (PostgreSQL/MySQL) em {}.",
+            compose.display()
+        )));
+    };
+
+    let invocation = invocation(tool, &project_dir, &db)?;
+    outln!("Ferramenta de migração detectada: {}", tool.name());
+    outln!("Banco: {} (localhost:{}/{})", db.service, db.port, db.name);
+    crate::output::set_data(serde_json::json!({
+        "tool": tool.name(),
+        "command": invocation.display(),
+        "database": {
+            "service": db.service,
+            "port": db.port,
+            "name": db.name,
+        },
+        "dry_run": opts.dry_run,
+    }));
+    if opts.dry_run {
+        outln!("Comando: {}", invocation.display());
+        return Ok(());
+    }
+
+    let Some(program) = resolve(&invocation.program, &project_dir) else {
+        return Err(DxError::Tool(format!(
+            "'{}' não encontrado no PATH.\nDica: {}",
+            invocation.program,
+            tool.install_hint()
+        )));
+    };
+    let runtime = Runtime::resolve(&project_dir, opts.runtime)?;
+    let runner = Runner::new(&project_dir, runtime, &compose);
+    crate::progress::timed("aguardando o banco", || {
+        wait_ready(&runner, &db, Duration::from_secs(opts.timeout))
+    })?;
+
+    outln!("Executando: {}", invocation.display());
+    let mut command = Command::new(program);
+    command
+        .args(&invocation.args)
+        .current_dir(&project_dir)
+        .envs(crate::shell_env::project_vars(&project_dir));
+    if let Some(url) = &invocation.database_url {
+        command.env("DATABASE_URL", url);
+    }
+    match command.status() {
+        Ok(status) if status.success() => {
+            outln!("Migrações aplicadas com {}.", tool.name());
+            Ok(())
+        }
+        Ok(status) => Err(DxError::Tool(format!(
+            "Migração falhou ({}): {status}",
+            invocation.display()
+        ))),
+        Err(e) => Err(DxError::Tool(format!(
+            "Erro ao executar '{}': {e}\nDica: {}",
+            invocation.program,
+            tool.install_hint()
+        ))),
+    }
+}
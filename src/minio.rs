@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Local S3 buckets for the project's S3 clients.
+//!
+//! S3 SDK usage (`aws-sdk-s3`, `boto3`, `@aws-sdk/client-s3`, `S3_BUCKET`...)
+//! or an S3 bucket in the IaC adds `minio`, MinIO's S3-compatible API with its
+//! console, plus `minio-init`: a one-shot `minio/mc` that creates the buckets
+//! the app expects once MinIO is healthy. The buckets are the values of the
+//! `*_BUCKET`/`*_BUCKET_NAME` variables of the project's env files (`app` when
+//! there are none); `mc mb --ignore-existing` keeps reruns harmless. The root
+//! credentials double as the app's access keys, exported by `dx env` as the
+//! standard AWS variables.
+use std::path::Path;
+
+use crate::dev_services::DockerComposeConfig;
+
+pub const SERVICE: &str = "minio";
+pub const INIT_SERVICE: &str = "minio-init";
+/// Root user, also the access key of the app.
+pub const ACCESS_KEY: &str = "minioadmin";
+/// Root password, also the secret key of the app.
+pub const SECRET_KEY: &str = "minioadmin";
+/// Bucket created when the project names none.
+pub const DEFAULT_BUCKET: &str = "app";
+
+/// Env files whose bucket variables name the buckets to create.
+const ENV_FILES: &[&str] = &[".env", ".env.example", ".env.sample", ".env.local"];
+
+/// `mc` command of `minio-init` creating `buckets`. `mc` reaches MinIO through
+/// the `MC_HOST_local` alias of the service.
+pub fn init_command(buckets: &[String]) -> String {
+    let targets: Vec<String> = buckets.iter().map(|b| format!("local/{b}")).collect();
+    format!("mb --ignore-existing {}", targets.join(" "))
+}
+
+/// Buckets named by `command` (see [`init_command`]).
+pub fn command_buckets(command: &str) -> Vec<&str> {
+    command
+        .split_whitespace()
+        .filter_map(|arg| arg.strip_prefix("local/"))
+        .collect()
+}
+
+/// Whether `name` is a valid S3 bucket name (lowercase letters, digits, `.`
+/// and `-`, 3 to 63 characters, starting and ending with a letter or digit).
+fn valid_bucket(name: &str) -> bool {
+    (3..=63).contains(&name.len())
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'.' || b == b'-')
+        && name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name.ends_with(|c: char| c.is_ascii_alphanumeric())
+}
+
+/// Buckets named in the project's env files, sorted and deduplicated.
+pub fn buckets(project_dir: &Path) -> Vec<String> {
+    let mut buckets = std::collections::BTreeSet::new();
+    for file in ENV_FILES {
+        let content = std::fs::read_to_string(project_dir.join(file)).unwrap_or_default();
+        for line in content.lines() {
+            let line = line.trim();
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim();
+            let value = value.trim().trim_matches(['"', '\'']);
+            if (key.ends_with("BUCKET") || key.ends_with("BUCKET_NAME")) && valid_bucket(value) {
+                buckets.insert(value.to_string());
+            }
+        }
+    }
+    buckets.into_iter().collect()
+}
+
+/// Create the project's buckets in the `minio-init` of `config` (when there is
+/// one).
+pub fn apply(project_dir: &Path, config: &mut DockerComposeConfig) {
+    let buckets = buckets(project_dir);
+    if buckets.is_empty() {
+        return;
+    }
+    if let Some(init) = config.services.get_mut(INIT_SERVICE) {
+        init.command = Some(init_command(&buckets));
+    }
+}
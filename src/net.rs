@@ -0,0 +1,182 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Network I/O (package registries, policy bundles, Git hosting APIs).
+//!
+//! Every request goes through one async client on a shared Tokio runtime:
+//! at most `[network] concurrency` requests are in flight, each one times out
+//! after `[network] timeout` seconds and each registry host receives at most
+//! `[network] rate_limit` requests per second. Ctrl-C cancels the requests in
+//! flight and ends dx with exit code 8 before anything is written to disk.
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use tokio::runtime::Runtime;
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
+
+use crate::error::DxError;
+use crate::settings::NetworkSettings;
+
+struct Net {
+    client: reqwest::Client,
+    permits: Arc<Semaphore>,
+    concurrency: usize,
+    /// Minimum interval between two requests to the same host.
+    interval: Option<Duration>,
+    /// Next free slot per host.
+    slots: Mutex<HashMap<String, Instant>>,
+}
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+static NET: Mutex<Option<Arc<Net>>> = Mutex::new(None);
+
+fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("falha ao iniciar o runtime de rede")
+    })
+}
+
+impl Net {
+    fn new(settings: &NetworkSettings) -> Net {
+        let client = reqwest::Client::builder()
+            .user_agent(concat!("dx-cli/", env!("CARGO_PKG_VERSION")))
+            .timeout(Duration::from_secs(settings.timeout))
+            .build()
+            .expect("falha ao criar o cliente HTTP");
+        Net {
+            client,
+            permits: Arc::new(Semaphore::new(settings.concurrency.max(1))),
+            concurrency: settings.concurrency.max(1),
+            interval: (settings.rate_limit > 0)
+                .then(|| Duration::from_secs(1) / settings.rate_limit),
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait for the next request slot of `host`.
+    async fn throttle(&self, host: &str) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        let slot = {
+            let mut slots = self.slots.lock().unwrap();
+            let now = Instant::now();
+            let slot = slots.get(host).copied().filter(|t| *t > now).unwrap_or(now);
+            slots.insert(host.to_string(), slot + interval);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// Apply `[network]` from dx.toml to the requests made from now on.
+pub fn configure(settings: &NetworkSettings) {
+    *NET.lock().unwrap() = Some(Arc::new(Net::new(settings)));
+}
+
+fn net() -> Arc<Net> {
+    NET.lock()
+        .unwrap()
+        .get_or_insert_with(|| Arc::new(Net::new(&NetworkSettings::default())))
+        .clone()
+}
+
+/// Maximum number of requests in flight (`[network] concurrency`).
+pub fn concurrency() -> usize {
+    net().concurrency
+}
+
+/// Run `future` on the shared runtime. Ctrl-C cancels it and ends dx with
+/// exit code 8.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    runtime().block_on(async {
+        tokio::select! {
+            output = future => output,
+            _ = tokio::signal::ctrl_c() => {
+                let e = DxError::Aborted("Operação cancelada (Ctrl-C).".to_string());
+                eprintln!("\n{e}");
+                crate::output::set_error(&e);
+                crate::output::exit(e.exit_code())
+            }
+        }
+    })
+}
+
+/// GET `url` and return its body; non-2xx statuses are errors.
+pub async fn get_text(url: &str) -> Result<String, reqwest::Error> {
+    let net = net();
+    let _permit = net.permits.acquire().await.expect("semaphore closed");
+    if let Some(host) = reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string))
+    {
+        net.throttle(&host).await;
+    }
+    let response = net.client.get(url).send().await?;
+    tracing::debug!(url, status = response.status().as_u16(), "resposta HTTP");
+    response.error_for_status()?.text().await
+}
+
+/// Blocking wrapper around [`get_text`] for synchronous callers.
+pub fn fetch_text(url: &str) -> Result<String, reqwest::Error> {
+    block_on(get_text(url))
+}
+
+/// POST `body` as JSON to `url` with extra `headers`; returns the status and
+/// the body of the response, so callers can report API errors.
+pub async fn post_json(
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &serde_json::Value,
+) -> Result<(u16, String), reqwest::Error> {
+    request_json(reqwest::Method::POST, url, headers, Some(body)).await
+}
+
+/// Send a `method` request to `url` with extra `headers` and, if any, `body`
+/// as JSON; returns the status and the body of the response.
+pub async fn request_json(
+    method: reqwest::Method,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&serde_json::Value>,
+) -> Result<(u16, String), reqwest::Error> {
+    let net = net();
+    let _permit = net.permits.acquire().await.expect("semaphore closed");
+    let mut request = net.client.request(method, url);
+    if let Some(body) = body {
+        request = request
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string());
+    }
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = request.send().await?;
+    let status = response.status().as_u16();
+    tracing::debug!(url, status, "resposta HTTP");
+    Ok((status, response.text().await?))
+}
+
+/// Blocking wrapper around [`post_json`].
+pub fn send_json(
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &serde_json::Value,
+) -> Result<(u16, String), reqwest::Error> {
+    block_on(post_json(url, headers, body))
+}
+
+/// Blocking wrapper around [`request_json`].
+pub fn send(
+    method: reqwest::Method,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&serde_json::Value>,
+) -> Result<(u16, String), reqwest::Error> {
+    block_on(request_json(method, url, headers, body))
+}
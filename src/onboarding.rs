@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! `dx` without a subcommand: what dx can do for the project in the current
+//! directory, with the next three commands to try.
+//!
+//! The summary comes from the same detection as `dx dev-services`, served
+//! from `.dx/cache/detection.json` while the project is unchanged, and from
+//! files dx reads anyway (the manifest, CI configuration, toolchain pins).
+//! Nothing is sent anywhere. A directory that isn't a project (the home
+//! directory, a monorepo root) isn't scanned: the summary points to its
+//! subprojects instead.
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::error::DxResult;
+use crate::output::Render;
+use crate::pipeline::Stack;
+
+/// How many next commands are suggested.
+const SUGGESTIONS: usize = 3;
+
+/// CI configurations `dx ci generate` writes or would find.
+const CI_FILES: &[&str] = &[
+    ".github/workflows",
+    ".gitlab-ci.yml",
+    ".circleci/config.yml",
+    "azure-pipelines.yml",
+];
+
+#[derive(Debug, Serialize)]
+struct Suggestion {
+    command: String,
+    reason: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Summary {
+    project: PathBuf,
+    /// `None` when the directory isn't a project
+    stack: Option<&'static str>,
+    services: Vec<String>,
+    /// Whether `.dx/docker-compose.yml` was generated
+    manifest: bool,
+    subprojects: Vec<PathBuf>,
+    suggestions: Vec<Suggestion>,
+}
+
+impl Render for Summary {
+    fn render_text(&self) {
+        outln!("dx — DX em qualquer stack\n");
+        match self.stack {
+            Some(stack) => outln!("Projeto: {} ({stack})", self.project.display()),
+            None => outln!("Nenhum projeto reconhecido em {}.", self.project.display()),
+        }
+        if !self.services.is_empty() {
+            outln!("Serviços detectados: {}", self.services.join(", "));
+        }
+        if self.stack.is_some() {
+            outln!(
+                "Manifesto dos Dev Services: {}",
+                if self.manifest {
+                    ".dx/docker-compose.yml"
+                } else {
+                    "não gerado"
+                }
+            );
+        }
+        if !self.subprojects.is_empty() {
+            let names: Vec<String> = self
+                .subprojects
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            outln!("Subprojetos: {}", names.join(", "));
+        }
+        outln!("\nPróximos passos:");
+        let width = self
+            .suggestions
+            .iter()
+            .map(|s| s.command.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (i, suggestion) in self.suggestions.iter().enumerate() {
+            outln!(
+                "  {}. {:<width$}  {}",
+                i + 1,
+                suggestion.command,
+                suggestion.reason
+            );
+        }
+        outln!("\nTodos os comandos: dx --help");
+    }
+}
+
+fn suggestion(command: &str, reason: String) -> Suggestion {
+    Suggestion {
+        command: command.to_string(),
+        reason,
+    }
+}
+
+/// `names` as prose: `a`, `a e b`, `a, b e c`.
+fn enumerate(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [one] => one.clone(),
+        [rest @ .., last] => format!("{} e {last}", rest.join(", ")),
+    }
+}
+
+/// Next commands for a project, most useful first.
+fn project_suggestions(
+    dir: &Path,
+    stack: Stack,
+    services: &[String],
+    manifest: bool,
+) -> Vec<Suggestion> {
+    let mut suggestions = Vec::new();
+    if manifest {
+        suggestions.push(suggestion(
+            "dx dev-services run",
+            "sobe os serviços do .dx/docker-compose.yml".to_string(),
+        ));
+    } else if !services.is_empty() {
+        suggestions.push(suggestion(
+            "dx dev-services",
+            format!("gera .dx/docker-compose.yml com {}", enumerate(services)),
+        ));
+    }
+    if !crate::toolchain::pins(dir).is_empty() {
+        suggestions.push(suggestion(
+            "dx doctor",
+            "confere Docker, SDK fixado, portas e rede nesta máquina".to_string(),
+        ));
+    }
+    if matches!(
+        stack,
+        Stack::Rust | Stack::Node | Stack::Python | Stack::Go | Stack::Maven | Stack::Gradle
+    ) {
+        suggestions.push(suggestion(
+            "dx dev-test",
+            "roda os testes a cada mudança nos arquivos".to_string(),
+        ));
+    }
+    if stack != Stack::Unknown && !CI_FILES.iter().any(|f| dir.join(f).exists()) {
+        suggestions.push(suggestion(
+            "dx ci generate",
+            format!("cria o pipeline de CI para {}", stack.name()),
+        ));
+    }
+    suggestions.push(suggestion(
+        "dx analyzer",
+        "resume tudo o que o dx detecta e aplicaria no projeto".to_string(),
+    ));
+    suggestions.push(suggestion(
+        "dx dev-dependencies outdated",
+        "lista as dependências de desenvolvimento desatualizadas".to_string(),
+    ));
+    suggestions.truncate(SUGGESTIONS);
+    suggestions
+}
+
+/// `dx` without a subcommand: a summary of the project in the current
+/// directory and the next commands to try.
+pub fn summary() -> DxResult {
+    let project = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let manifest = project.join(".dx").join("docker-compose.yml").is_file();
+    let stack = Stack::detect(&project);
+    if stack == Stack::Unknown && !manifest {
+        let subprojects: Vec<PathBuf> = crate::workspace::subprojects(&project, 2)
+            .into_iter()
+            .map(|p| p.strip_prefix(&project).map(Path::to_path_buf).unwrap_or(p))
+            .collect();
+        let mut suggestions = Vec::new();
+        if let Some(first) = subprojects.first() {
+            suggestions.push(suggestion(
+                "dx all analyzer",
+                format!("analisa os {} subprojetos em paralelo", subprojects.len()),
+            ));
+            suggestions.push(suggestion(
+                &format!("cd {} && dx", first.display()),
+                "mostra o resumo de um subprojeto".to_string(),
+            ));
+        }
+        suggestions.push(suggestion(
+            "dx doctor",
+            "confere Docker, portas, disco e rede nesta máquina".to_string(),
+        ));
+        suggestions.truncate(SUGGESTIONS);
+        crate::output::render(&Summary {
+            project,
+            stack: None,
+            services: Vec::new(),
+            manifest,
+            subprojects,
+            suggestions,
+        });
+        return Ok(());
+    }
+
+    let services = crate::dev_services::detect_dependencies(&project).service_names();
+    let suggestions = project_suggestions(&project, stack, &services, manifest);
+    crate::output::render(&Summary {
+        project,
+        stack: Some(stack.name()),
+        services,
+        manifest,
+        subprojects: Vec::new(),
+        suggestions,
+    });
+    Ok(())
+}
@@ -0,0 +1,288 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Vulnerability advisories from the OSV.dev API, for
+//! `dx dev-dependencies audit`.
+//!
+//! The dependencies are sent in one `querybatch` request, which answers the
+//! ids of the advisories affecting each version; the details (summary,
+//! severity, fixed versions) come from `/v1/vulns/<id>`. The severity is the
+//! one the database assigns (GitHub advisories: `CRITICAL`, `HIGH`,
+//! `MODERATE`, `LOW`) or, without it, the rating of the CVSS v3 base score.
+use std::cmp::Ordering;
+
+use reqwest::Method;
+use serde_json::{Value, json};
+
+use crate::error::{DxError, DxResult};
+
+pub const DEFAULT_URL: &str = "https://api.osv.dev";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Unknown,
+    Low,
+    Moderate,
+    High,
+    Critical,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Critical => "crítica",
+            Severity::High => "alta",
+            Severity::Moderate => "média",
+            Severity::Low => "baixa",
+            Severity::Unknown => "desconhecida",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Severity> {
+        match label.to_ascii_uppercase().as_str() {
+            "CRITICAL" => Some(Severity::Critical),
+            "HIGH" => Some(Severity::High),
+            "MODERATE" | "MEDIUM" => Some(Severity::Moderate),
+            "LOW" => Some(Severity::Low),
+            _ => None,
+        }
+    }
+
+    /// Qualitative rating of a CVSS score.
+    fn from_score(score: f64) -> Severity {
+        match score {
+            s if s >= 9.0 => Severity::Critical,
+            s if s >= 7.0 => Severity::High,
+            s if s >= 4.0 => Severity::Moderate,
+            s if s > 0.0 => Severity::Low,
+            _ => Severity::Unknown,
+        }
+    }
+}
+
+/// A package version to check.
+pub struct Query<'a> {
+    /// OSV ecosystem (`npm`, `crates.io`, `PyPI`, `Maven`, `Go`, ...)
+    pub ecosystem: &'a str,
+    pub name: &'a str,
+    pub version: &'a str,
+}
+
+/// An advisory affecting a package version.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Advisory {
+    pub id: String,
+    /// CVE and other ids of the same vulnerability
+    pub aliases: Vec<String>,
+    pub summary: String,
+    pub severity: Severity,
+    /// First version fixing it after the checked one
+    pub fixed: Option<String>,
+}
+
+impl Advisory {
+    /// Advisory from an OSV vulnerability record, for `query`.
+    pub fn parse(record: &Value, query: &Query) -> Advisory {
+        let strings = |value: Option<&Value>| -> Vec<String> {
+            value
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        };
+        let affected: Vec<&Value> = record
+            .get("affected")
+            .and_then(|a| a.as_array())
+            .into_iter()
+            .flatten()
+            .filter(|a| {
+                let package = &a["package"];
+                package["name"].as_str() == Some(query.name)
+                    && package["ecosystem"]
+                        .as_str()
+                        .is_some_and(|e| e.eq_ignore_ascii_case(query.ecosystem))
+            })
+            .collect();
+        let summary = record["summary"]
+            .as_str()
+            .or_else(|| record["details"].as_str()?.lines().next())
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        Advisory {
+            id: record["id"].as_str().unwrap_or_default().to_string(),
+            aliases: strings(record.get("aliases")),
+            summary,
+            severity: severity(record, &affected),
+            fixed: fixed(&affected, query.version),
+        }
+    }
+}
+
+fn severity(record: &Value, affected: &[&Value]) -> Severity {
+    let labeled = std::iter::once(&record["database_specific"]["severity"])
+        .chain(
+            affected
+                .iter()
+                .map(|a| &a["ecosystem_specific"]["severity"]),
+        )
+        .chain(affected.iter().map(|a| &a["database_specific"]["severity"]))
+        .find_map(|s| Severity::from_label(s.as_str()?));
+    if let Some(severity) = labeled {
+        return severity;
+    }
+    record["severity"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|s| {
+            let score = s["score"].as_str()?;
+            score.parse().ok().or_else(|| cvss3_score(score))
+        })
+        .map(Severity::from_score)
+        .max()
+        .unwrap_or(Severity::Unknown)
+}
+
+/// Lowest `fixed` event above `version` among the affected ranges.
+fn fixed(affected: &[&Value], version: &str) -> Option<String> {
+    affected
+        .iter()
+        .filter_map(|a| a["ranges"].as_array())
+        .flatten()
+        .filter_map(|r| r["events"].as_array())
+        .flatten()
+        .filter_map(|e| e["fixed"].as_str())
+        .filter(|fixed| compare_versions(fixed, version) == Ordering::Greater)
+        .min_by(|a, b| compare_versions(a, b))
+        .map(str::to_string)
+}
+
+/// Order of two versions by their numeric parts (`1.10.0` > `1.9.2`); a
+/// pre-release sorts before its release.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |v: &str| -> (Vec<u64>, bool) {
+        let v = v.trim_start_matches('v');
+        let (release, pre) = match v.find(['-', '+']) {
+            Some(i) => (&v[..i], v[i..].starts_with('-')),
+            None => (v, false),
+        };
+        let numbers = release
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|p| !p.is_empty())
+            .map(|p| p.parse().unwrap_or(u64::MAX))
+            .collect();
+        (numbers, pre)
+    };
+    let ((mut a, a_pre), (mut b, b_pre)) = (parts(a), parts(b));
+    let len = a.len().max(b.len());
+    a.resize(len, 0);
+    b.resize(len, 0);
+    a.cmp(&b).then(b_pre.cmp(&a_pre))
+}
+
+/// Base score of a CVSS v3.x vector (`CVSS:3.1/AV:N/AC:L/...`).
+fn cvss3_score(vector: &str) -> Option<f64> {
+    if !vector.starts_with("CVSS:3") {
+        return None;
+    }
+    let metric = |name: &str| {
+        vector
+            .split('/')
+            .find_map(|m| m.strip_prefix(name)?.strip_prefix(':'))
+    };
+    let changed = metric("S")? == "C";
+    let av = match metric("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        _ => 0.2,
+    };
+    let ac = if metric("AC")? == "L" { 0.77 } else { 0.44 };
+    let pr = match (metric("PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        (_, false) => 0.27,
+        (_, true) => 0.5,
+    };
+    let ui = if metric("UI")? == "N" { 0.85 } else { 0.62 };
+    let cia = |name: &str| {
+        Some(match metric(name)? {
+            "H" => 0.56,
+            "L" => 0.22,
+            _ => 0.0,
+        })
+    };
+    let iss = 1.0 - (1.0 - cia("C")?) * (1.0 - cia("I")?) * (1.0 - cia("A")?);
+    let impact = if changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02_f64).powi(15)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let exploitability = 8.22 * av * ac * pr * ui;
+    let base = if changed {
+        1.08 * (impact + exploitability)
+    } else {
+        impact + exploitability
+    };
+    // "Round up" to one decimal as the specification defines it
+    let scaled = (base.min(10.0) * 100_000.0).round() as u64;
+    Some(if scaled.is_multiple_of(10_000) {
+        scaled as f64 / 100_000.0
+    } else {
+        (scaled / 10_000 + 1) as f64 / 10.0
+    })
+}
+
+/// Ids of the advisories affecting each query, in order.
+pub fn query_batch(base: &str, queries: &[Query]) -> DxResult<Vec<Vec<String>>> {
+    let url = format!("{base}/v1/querybatch");
+    let body = json!({
+        "queries": queries
+            .iter()
+            .map(|q| json!({
+                "package": {"name": q.name, "ecosystem": q.ecosystem},
+                "version": q.version,
+            }))
+            .collect::<Vec<_>>(),
+    });
+    tracing::debug!(url, queries = queries.len(), "consultando OSV.dev");
+    crate::progress::detail(&url);
+    let (status, response) = crate::net::send(Method::POST, &url, &[], Some(&body))
+        .map_err(|e| DxError::Network(format!("Falha ao consultar o OSV.dev em {url}: {e}")))?;
+    if !(200..300).contains(&status) {
+        return Err(DxError::Network(format!(
+            "O OSV.dev respondeu HTTP {status} em {url}: {}",
+            response.trim()
+        )));
+    }
+    let response: Value = serde_json::from_str(&response)
+        .map_err(|e| DxError::Network(format!("Resposta inválida do OSV.dev em {url}: {e}")))?;
+    let results = response["results"].as_array().cloned().unwrap_or_default();
+    Ok((0..queries.len())
+        .map(|i| {
+            results
+                .get(i)
+                .and_then(|r| r["vulns"].as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v["id"].as_str().map(str::to_string))
+                .collect()
+        })
+        .collect())
+}
+
+/// The vulnerability record `id`.
+pub fn vulnerability(base: &str, id: &str) -> DxResult<Value> {
+    let url = format!("{base}/v1/vulns/{id}");
+    crate::progress::detail(&url);
+    let text = crate::net::fetch_text(&url)
+        .map_err(|e| DxError::Network(format!("Falha ao consultar o OSV.dev em {url}: {e}")))?;
+    serde_json::from_str(&text)
+        .map_err(|e| DxError::Network(format!("Resposta inválida do OSV.dev em {url}: {e}")))
+}
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Output layer shared by every command.
+//!
+//! In text mode human-readable lines go to stdout as usual. In JSON mode those
+//! lines are sent to stderr and stdout carries a single JSON document per
+//! invocation:
+//!
+//! ```json
+//! {"schema_version": 1, "command": "clean", "ok": true, "data": { ... }, "error": null}
+//! ```
+//!
+//! `data` is command specific (see README) and `null` for commands that have no
+//! structured result yet; results with both forms implement [`Render`].
+//! `error` carries `kind`, `message` and `exit_code` when the command fails
+//! with a [`DxError`]. With `--timings` the envelope also gets a `timings`
+//! object.
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde_json::Value;
+
+use crate::error::DxError;
+
+/// Version of the JSON envelope; bump on breaking changes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+static JSON: AtomicBool = AtomicBool::new(false);
+static OK: AtomicBool = AtomicBool::new(true);
+static COMMAND: Mutex<String> = Mutex::new(String::new());
+static DATA: Mutex<Option<Value>> = Mutex::new(None);
+static ERROR: Mutex<Option<Value>> = Mutex::new(None);
+
+/// Select the output format and the command name reported in the envelope.
+pub fn init(format: OutputFormat, command: &str) {
+    JSON.store(format == OutputFormat::Json, Ordering::Relaxed);
+    *COMMAND.lock().unwrap() = command.to_string();
+}
+
+pub fn is_json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+/// Structured result of the running command (the envelope's `data`).
+pub fn set_data(data: Value) {
+    *DATA.lock().unwrap() = Some(data);
+}
+
+/// Result of a command that has both forms: prose for people and `data` for
+/// scripts and editors.
+pub trait Render: serde::Serialize {
+    /// Print the human-readable form (with `outln!`, so stderr in JSON mode).
+    fn render_text(&self);
+
+    /// The envelope's `data`; the serialized result by default.
+    fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+}
+
+/// Print `result` and record it as the command's `data`.
+pub fn render(result: &impl Render) {
+    result.render_text();
+    set_data(result.to_json());
+}
+
+/// Mark the running command as failed (`"ok": false`).
+pub fn set_failed() {
+    OK.store(false, Ordering::Relaxed);
+}
+
+/// Record the error that ended the command.
+pub fn set_error(error: &DxError) {
+    set_failed();
+    *ERROR.lock().unwrap() = Some(serde_json::json!({
+        "kind": error.kind(),
+        "message": error.to_string(),
+        "exit_code": error.exit_code(),
+    }));
+}
+
+/// Whether the running command hasn't failed so far.
+pub fn is_ok() -> bool {
+    OK.load(Ordering::Relaxed)
+}
+
+/// Message of the error recorded with [`set_error`].
+pub fn error_message() -> Option<String> {
+    ERROR.lock().unwrap().as_ref().and_then(|e| e["message"].as_str().map(str::to_string))
+}
+
+/// Print the `--timings` summary and the JSON envelope (JSON mode only).
+pub fn finish() {
+    let timings = crate::progress::summary();
+    if !is_json() {
+        return;
+    }
+    let mut envelope = serde_json::json!({
+        "schema_version": SCHEMA_VERSION,
+        "command": *COMMAND.lock().unwrap(),
+        "ok": OK.load(Ordering::Relaxed),
+        "data": DATA.lock().unwrap().take().unwrap_or(Value::Null),
+        "error": ERROR.lock().unwrap().take().unwrap_or(Value::Null),
+    });
+    if let Some(timings) = timings {
+        envelope["timings"] = timings;
+    }
+    println!("{}", serde_json::to_string_pretty(&envelope).unwrap());
+}
+
+/// Finish the output and exit with `code`.
+pub fn exit(code: i32) -> ! {
+    if code != 0 {
+        set_failed();
+    }
+    crate::history::record(code);
+    finish();
+    std::process::exit(code)
+}
+
+/// Human-readable line: stdout in text mode, stderr in JSON mode.
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        if $crate::output::is_json() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Like `outln!` without the trailing newline.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        if $crate::output::is_json() {
+            eprint!($($arg)*);
+        } else {
+            print!($($arg)*);
+        }
+    };
+}
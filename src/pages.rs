@@ -0,0 +1,411 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! `dx publish pages`: the project dashboard on a GitHub Pages branch.
+//!
+//! The site is built in `.dx/pages`:
+//!
+//! - `index.html`: dashboard with the Dev Services badges, the coverage badge
+//!   and links to the other pages;
+//! - `report.html` and `report.md`: the analyzer report;
+//! - `badges/coverage.svg` and `badges/coverage.json` (shields.io endpoint),
+//!   from the project's lcov, Istanbul, Cobertura or JaCoCo report;
+//! - `docs/`: the built docs site (`[pages] docs`, else `site/`, `book/`, ...).
+//!
+//! Markdown pages are rendered in the browser (marked), so no site generator
+//! is needed. `.dx/pages` is also a git repository: dx fetches the published
+//! branch, commits the new site on top of it and pushes, so the branch keeps
+//! its history and the project's working tree is never touched.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::error::{DxError, DxResult, IoContext};
+use crate::git;
+
+const DEFAULT_BRANCH: &str = "gh-pages";
+const DEFAULT_DOCS: &[&str] = &[
+    "site",
+    "book",
+    "docs/_site",
+    "docs/.vitepress/dist",
+    "website/build",
+];
+
+/// Command line overrides of `[pages]`.
+pub struct PublishOptions {
+    pub branch: Option<String>,
+    pub remote: Option<String>,
+    pub dry_run: bool,
+}
+
+/// Markdown page rendered by the browser.
+const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="pt-BR">
+<head>
+  <meta charset="utf-8">
+  <meta name="viewport" content="width=device-width, initial-scale=1">
+  <title>{title}</title>
+  <style>
+    body { max-width: 980px; margin: 2rem auto; padding: 0 1rem; font-family: system-ui, sans-serif; line-height: 1.5; }
+    table { border-collapse: collapse; } th, td { border: 1px solid #d0d7de; padding: 4px 8px; }
+    pre, code { background: #f6f8fa; } pre { padding: 1rem; overflow: auto; }
+  </style>
+</head>
+<body>
+  <main id="content"></main>
+  <script src="https://cdn.jsdelivr.net/npm/marked/marked.min.js"></script>
+  <script>
+    const markdown = {markdown};
+    document.getElementById("content").innerHTML = marked.parse(markdown);
+  </script>
+</body>
+</html>
+"#;
+
+fn page(title: &str, markdown: &str) -> String {
+    // `</script>` inside the string would end the script element
+    let markdown = serde_json::to_string(markdown)
+        .unwrap_or_default()
+        .replace("</", "<\\/");
+    PAGE.replace("{title}", title)
+        .replace("{markdown}", &markdown)
+}
+
+/// Line coverage (percent) and the report it came from.
+fn coverage(project_dir: &Path) -> Option<(f64, &'static str)> {
+    let read = |file: &str| fs::read_to_string(project_dir.join(file)).ok();
+    for file in ["coverage/lcov.info", "lcov.info"] {
+        if let Some(data) = read(file) {
+            let total = |key: &str| -> f64 {
+                data.lines()
+                    .filter_map(|l| l.strip_prefix(key)?.trim().parse::<f64>().ok())
+                    .sum()
+            };
+            let (found, hit) = (total("LF:"), total("LH:"));
+            if found > 0.0 {
+                return Some((hit * 100.0 / found, file));
+            }
+        }
+    }
+    let file = "coverage/coverage-summary.json";
+    if let Some(pct) = read(file)
+        .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+        .and_then(|v| v["total"]["lines"]["pct"].as_f64())
+    {
+        return Some((pct, file));
+    }
+    for file in ["coverage.xml", "coverage/cobertura-coverage.xml"] {
+        if let Some(rate) = read(file).and_then(|data| {
+            let tag = &data[data.find("<coverage")?..];
+            attribute(tag, "line-rate")?.parse::<f64>().ok()
+        }) {
+            return Some((rate * 100.0, file));
+        }
+    }
+    for file in [
+        "target/site/jacoco/jacoco.xml",
+        "build/reports/jacoco/test/jacocoTestReport.xml",
+    ] {
+        // The report's own counters come last
+        if let Some((missed, covered)) = read(file).and_then(|data| {
+            let tag = &data[data.rfind(r#"<counter type="LINE""#)?..];
+            let count = |name| attribute(tag, name)?.parse::<f64>().ok();
+            Some((count("missed")?, count("covered")?))
+        }) && missed + covered > 0.0
+        {
+            return Some((covered * 100.0 / (missed + covered), file));
+        }
+    }
+    None
+}
+
+/// Value of `name="..."` in the first tag of `xml`.
+fn attribute<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let tag = &xml[..xml.find('>')?];
+    let start = tag.find(&format!(" {name}=\""))? + name.len() + 3;
+    Some(&tag[start..start + tag[start..].find('"')?])
+}
+
+/// shields.io colors by coverage.
+fn color(percent: f64) -> &'static str {
+    match percent {
+        p if p >= 90.0 => "#4c1",
+        p if p >= 75.0 => "#97ca00",
+        p if p >= 60.0 => "#dfb317",
+        p if p >= 40.0 => "#fe7d37",
+        _ => "#e05d44",
+    }
+}
+
+/// Flat badge in the shields.io style.
+fn badge_svg(label: &str, message: &str, color: &str) -> String {
+    let width = |text: &str| text.chars().count() as u32 * 7 + 10;
+    let (left, right) = (width(label), width(message));
+    let total = left + right;
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{total}" height="20" role="img" aria-label="{label}: {message}">
+  <rect width="{left}" height="20" fill="#555"/>
+  <rect x="{left}" width="{right}" height="20" fill="{color}"/>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{lx}" y="14">{label}</text>
+    <text x="{rx}" y="14">{message}</text>
+  </g>
+</svg>
+"##,
+        lx = left / 2,
+        rx = left + right / 2,
+    )
+}
+
+fn docs_dir(project_dir: &Path, configured: Option<&str>) -> Option<PathBuf> {
+    match configured {
+        Some(dir) => Some(project_dir.join(dir)).filter(|d| d.is_dir()),
+        None => DEFAULT_DOCS
+            .iter()
+            .map(|d| project_dir.join(d))
+            .find(|d| d.join("index.html").is_file()),
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<usize> {
+    let mut copied = 0;
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copied += copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// Files of the site, relative to `site` (the `.git` of the checkout is kept).
+fn build(project_dir: &Path, site: &Path, docs: Option<&Path>) -> DxResult<Vec<String>> {
+    if site.is_dir() {
+        for entry in fs::read_dir(site).context("Erro ao limpar .dx/pages")? {
+            let path = entry.context("Erro ao limpar .dx/pages")?.path();
+            if path.file_name().is_some_and(|n| n == ".git") {
+                continue;
+            } else if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            }
+            .context(format!("Erro ao remover {}", path.display()))?;
+        }
+    }
+
+    let name = project_dir
+        .canonicalize()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "projeto".to_string());
+    let config = crate::dev_services::detect_dependencies(project_dir);
+    let report = crate::report::build_analyzer_report(project_dir, &config);
+    let (language, framework) = crate::telemetry::detect_language_and_framework(project_dir);
+    let commit = git::run(project_dir, &["rev-parse", "--short", "HEAD"]).ok();
+
+    let mut files = vec![
+        ("report.md".to_string(), report.clone()),
+        (
+            "report.html".to_string(),
+            page(&format!("{name}: relatório do dx"), &report),
+        ),
+        (".nojekyll".to_string(), String::new()),
+    ];
+    let mut index = format!("# {name}\n\n");
+    index.push_str(&crate::dev_badges::generate_badges_markdown(
+        project_dir,
+        &config.service_names(),
+    ));
+    if let Some((percent, source)) = coverage(project_dir) {
+        let message = format!("{percent:.1}%");
+        files.push((
+            "badges/coverage.svg".to_string(),
+            badge_svg("coverage", &message, color(percent)),
+        ));
+        let endpoint = json!({
+            "schemaVersion": 1,
+            "label": "coverage",
+            "message": message,
+            "color": color(percent).trim_start_matches('#'),
+        });
+        files.push((
+            "badges/coverage.json".to_string(),
+            format!("{endpoint:#}\n"),
+        ));
+        index.push_str(" ![coverage](badges/coverage.svg)");
+        tracing::debug!(source, percent, "cobertura encontrada");
+    }
+    index.push_str("\n\n");
+    match framework {
+        Some(framework) => index.push_str(&format!("Stack: {language} ({framework})\n\n")),
+        None => index.push_str(&format!("Stack: {language}\n\n")),
+    }
+    index.push_str("- [Relatório do dx (analyzer)](report.html)\n");
+    if docs.is_some() {
+        index.push_str("- [Documentação](docs/index.html)\n");
+    }
+    if let Some(commit) = &commit {
+        index.push_str(&format!(
+            "\nGerado por `dx publish pages` a partir de `{commit}`.\n"
+        ));
+    }
+    files.push(("index.html".to_string(), page(&name, &index)));
+
+    let mut written = Vec::new();
+    for (file, content) in files {
+        let path = site.join(&file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context(format!("Erro ao criar {}", parent.display()))?;
+        }
+        fs::write(&path, content).context(format!("Erro ao salvar {}", path.display()))?;
+        written.push(file);
+    }
+    if let Some(docs) = docs {
+        let copied = copy_dir(docs, &site.join("docs"))
+            .context(format!("Erro ao copiar {}", docs.display()))?;
+        written.push(format!("docs/ ({copied} arquivos)"));
+    }
+    written.sort();
+    Ok(written)
+}
+
+/// GitHub Pages URL of a github.com remote.
+fn pages_url(remote_url: &str) -> Option<String> {
+    let (host, path) = git::parse_remote(remote_url);
+    let (owner, repo) = path.split_once('/')?;
+    (host.as_deref() == Some("github.com")).then(|| {
+        if repo.eq_ignore_ascii_case(&format!("{owner}.github.io")) {
+            format!("https://{repo}/")
+        } else {
+            format!("https://{}.github.io/{repo}/", owner.to_lowercase())
+        }
+    })
+}
+
+/// Commit the site in `site` on top of `branch` of `remote_url` and push it.
+/// Returns the new commit, or `None` when the branch is already up to date.
+fn push(
+    project_dir: &Path,
+    site: &Path,
+    remote_url: &str,
+    branch: &str,
+) -> DxResult<Option<String>> {
+    if !site.join(".git").exists() {
+        git::run(site, &["init", "--quiet"])?;
+    }
+    // The author of the project's commits publishes the site
+    for key in ["user.name", "user.email"] {
+        let value = git::run(project_dir, &["config", key]).unwrap_or_default();
+        let value = match (value.is_empty(), key) {
+            (false, _) => value,
+            (true, "user.name") => "dx".to_string(),
+            (true, _) => "dx@localhost".to_string(),
+        };
+        git::run(site, &["config", key, &value])?;
+    }
+    let heads = git::run(site, &["ls-remote", "--heads", remote_url, branch])?;
+    let exists = !heads.is_empty();
+    if exists {
+        git::run(
+            site,
+            &["fetch", "--quiet", "--depth", "1", remote_url, branch],
+        )?;
+        git::run(site, &["reset", "--quiet", "--soft", "FETCH_HEAD"])?;
+    } else if git::run(site, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_ok() {
+        // The branch was deleted: start it over
+        git::run(site, &["update-ref", "-d", "HEAD"])?;
+    }
+    git::run(site, &["add", "--all"])?;
+    if exists && git::run(site, &["diff", "--cached", "--quiet"]).is_ok() {
+        return Ok(None);
+    }
+    let message = match git::run(project_dir, &["rev-parse", "--short", "HEAD"]) {
+        Ok(commit) => format!("dx publish pages ({commit})"),
+        Err(_) => "dx publish pages".to_string(),
+    };
+    git::run(site, &["commit", "--quiet", "-m", &message])?;
+    let refspec = format!("HEAD:refs/heads/{branch}");
+    git::run(site, &["push", "--quiet", remote_url, &refspec])?;
+    git::run(site, &["rev-parse", "--short", "HEAD"]).map(Some)
+}
+
+pub fn publish(dir: Option<PathBuf>, opts: PublishOptions) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    let settings = crate::settings::load(&project_dir)?.pages;
+    let branch = opts
+        .branch
+        .or(settings.branch)
+        .unwrap_or_else(|| DEFAULT_BRANCH.to_string());
+    let remote = opts
+        .remote
+        .or(settings.remote)
+        .unwrap_or_else(|| "origin".to_string());
+    let docs = docs_dir(&project_dir, settings.docs.as_deref());
+    if settings.docs.is_some() && docs.is_none() {
+        return Err(DxError::NotFound(format!(
+            "Pasta de documentação não encontrada: {}",
+            project_dir
+                .join(settings.docs.unwrap_or_default())
+                .display()
+        )));
+    }
+    // Before building, so a missing remote fails fast
+    let remote_url = if opts.dry_run {
+        None
+    } else {
+        Some(
+            git::run(&project_dir, &["remote", "get-url", &remote]).map_err(|_| {
+                DxError::InvalidInput(format!(
+                    "Remote '{remote}' não configurado em {}.",
+                    project_dir.display()
+                ))
+            })?,
+        )
+    };
+
+    let site = project_dir.join(".dx").join("pages");
+    let files = build(&project_dir, &site, docs.as_deref())?;
+    outln!("Site gerado em {}:", site.display());
+    for file in &files {
+        outln!("- {file}");
+    }
+
+    let Some(remote_url) = remote_url else {
+        outln!("\nModo --dry-run: nada foi publicado.");
+        crate::output::set_data(json!({
+            "branch": branch,
+            "remote": remote,
+            "site": site.display().to_string(),
+            "files": files,
+            "commit": null,
+            "url": null,
+        }));
+        return Ok(());
+    };
+    let commit = push(&project_dir, &site, &remote_url, &branch)?;
+    match &commit {
+        Some(commit) => outln!("\nPublicado em '{branch}' de '{remote}' ({commit})."),
+        None => outln!("\n'{branch}' de '{remote}' já está atualizada."),
+    }
+    let url = pages_url(&remote_url);
+    if let Some(url) = &url {
+        outln!("GitHub Pages: {url}");
+    }
+    crate::output::set_data(json!({
+        "branch": branch,
+        "remote": remote,
+        "site": site.display().to_string(),
+        "files": files,
+        "commit": commit,
+        "url": url,
+    }));
+    Ok(())
+}
@@ -0,0 +1,995 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! CI pipeline generation (`dx ci generate`).
+//!
+//! The pipeline (toolchain, dependency cache and steps) is derived once from
+//! the detected stack and then rendered for a CI provider (GitHub Actions,
+//! GitLab CI, CircleCI or Azure Pipelines), so every provider runs the same
+//! steps, including the `dx` commands they mirror. The generated
+//! configuration sits between markers so it can be regenerated in place;
+//! anything outside them is kept.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{DxError, DxResult};
+use crate::managed::{self, Markers};
+
+const MARKERS: Markers = Markers {
+    start: "# dx-cli:ci:start",
+    end: "# dx-cli:ci:end",
+};
+
+/// Image for projects without a known stack: enough to install and run dx.
+const UNKNOWN_IMAGE: &str = "buildpack-deps:bookworm-curl";
+
+/// Installs the latest dx release as `./dx`.
+const INSTALL_DX: &str =
+    "curl -fsSL https://raw.githubusercontent.com/dx-anywhere/dx-cli/main/scripts/install.sh | sh";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Provider {
+    /// GitHub Actions (.github/workflows/dx.yml)
+    Github,
+    /// GitLab CI (.gitlab-ci.yml)
+    Gitlab,
+    /// CircleCI (.circleci/config.yml)
+    Circleci,
+    /// Azure Pipelines (azure-pipelines.yml)
+    Azure,
+}
+
+impl Provider {
+    fn name(self) -> &'static str {
+        match self {
+            Provider::Github => "github",
+            Provider::Gitlab => "gitlab",
+            Provider::Circleci => "circleci",
+            Provider::Azure => "azure",
+        }
+    }
+
+    /// Configuration file, relative to the repository root.
+    fn path(self) -> &'static str {
+        match self {
+            Provider::Github => ".github/workflows/dx.yml",
+            Provider::Gitlab => ".gitlab-ci.yml",
+            Provider::Circleci => ".circleci/config.yml",
+            Provider::Azure => "azure-pipelines.yml",
+        }
+    }
+
+    fn render(self, pipeline: &Pipeline) -> String {
+        match self {
+            Provider::Github => render_github(pipeline),
+            Provider::Gitlab => render_gitlab(pipeline),
+            Provider::Circleci => render_circleci(pipeline),
+            Provider::Azure => render_azure(pipeline),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stack {
+    Rust,
+    Node,
+    Python,
+    Go,
+    Maven,
+    Gradle,
+    Php,
+    Ruby,
+    Unknown,
+}
+
+impl Stack {
+    pub fn detect(dir: &Path) -> Stack {
+        let has = |f: &str| dir.join(f).is_file();
+        if has("Cargo.toml") {
+            Stack::Rust
+        } else if has("package.json") {
+            Stack::Node
+        } else if has("pyproject.toml") || has("requirements.txt") || has("setup.py") {
+            Stack::Python
+        } else if has("go.mod") {
+            Stack::Go
+        } else if has("pom.xml") {
+            Stack::Maven
+        } else if has("build.gradle") || has("build.gradle.kts") {
+            Stack::Gradle
+        } else if has("composer.json") {
+            Stack::Php
+        } else if has("Gemfile") {
+            Stack::Ruby
+        } else {
+            Stack::Unknown
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Stack::Rust => "rust",
+            Stack::Node => "node",
+            Stack::Python => "python",
+            Stack::Go => "go",
+            Stack::Maven => "maven",
+            Stack::Gradle => "gradle",
+            Stack::Php => "php",
+            Stack::Ruby => "ruby",
+            Stack::Unknown => "unknown",
+        }
+    }
+}
+
+/// Pipeline phase of a step; providers with stages group steps by it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Install,
+    Build,
+    Lint,
+    Test,
+    Analyze,
+    Audit,
+}
+
+impl Stage {
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Install => "install",
+            Stage::Build => "build",
+            Stage::Lint => "lint",
+            Stage::Test => "test",
+            Stage::Analyze => "analyze",
+            Stage::Audit => "audit",
+        }
+    }
+}
+
+pub struct Step {
+    pub stage: Stage,
+    pub name: &'static str,
+    /// Commands that install the tools the step needs on a fresh CI machine
+    pub tools: Option<&'static str>,
+    /// Shell commands, one per line
+    pub run: String,
+}
+
+impl Step {
+    /// Commands to run in CI: tool installation, then the step itself.
+    pub fn script(&self) -> String {
+        match self.tools {
+            Some(tools) => format!("{tools}\n{}", self.run),
+            None => self.run.clone(),
+        }
+    }
+}
+
+/// Language toolchain installed before the steps.
+pub struct Toolchain {
+    pub stack: Stack,
+    /// Version read from the project's version files, or a default
+    pub version: String,
+}
+
+impl Toolchain {
+    /// Docker image with the toolchain, for container-based providers.
+    pub fn image(&self) -> String {
+        let v = self.version.as_str();
+        match self.stack {
+            Stack::Rust if v == "stable" => "rust:latest".to_string(),
+            Stack::Rust if v == "nightly" => "rustlang/rust:nightly".to_string(),
+            Stack::Rust => format!("rust:{v}"),
+            Stack::Node if v.starts_with("lts") => "node:lts".to_string(),
+            Stack::Node => format!("node:{v}"),
+            Stack::Python => format!("python:{v}"),
+            Stack::Go => format!("golang:{v}"),
+            Stack::Maven => format!("maven:3-eclipse-temurin-{v}"),
+            Stack::Gradle => format!("gradle:jdk{v}"),
+            // The official PHP images don't ship Composer
+            Stack::Php => "composer:2".to_string(),
+            Stack::Ruby => format!("ruby:{v}"),
+            Stack::Unknown => UNKNOWN_IMAGE.to_string(),
+        }
+    }
+
+    /// Commands that complete the toolchain image before the steps.
+    pub fn container_setup(&self) -> Option<&'static str> {
+        match self.stack {
+            Stack::Rust => Some("rustup component add clippy rustfmt"),
+            _ => None,
+        }
+    }
+}
+
+/// Dependency cache: directories to keep and the files whose content keys them.
+pub struct Cache {
+    /// Directories (`~` for the home directory, others relative to the project)
+    pub paths: Vec<&'static str>,
+    /// The home-directory caches moved into the project, for providers that
+    /// only keep project paths: (environment variable, directory)
+    pub relocated: Vec<(&'static str, &'static str)>,
+    pub key_files: Vec<String>,
+}
+
+impl Cache {
+    /// Variables that move the caches into `base` (the project directory as
+    /// seen by the provider).
+    pub fn env(&self, base: &str) -> Vec<(&'static str, String)> {
+        self.relocated
+            .iter()
+            .map(|(var, path)| {
+                let dir = format!("{base}/{path}");
+                let value = match *var {
+                    "MAVEN_OPTS" => format!("-Dmaven.repo.local={dir}"),
+                    _ => dir,
+                };
+                (*var, value)
+            })
+            .collect()
+    }
+
+    /// Cached directories inside the project (relocated or already local).
+    pub fn project_paths(&self) -> Vec<&'static str> {
+        self.relocated
+            .iter()
+            .map(|(_, path)| *path)
+            .chain(self.paths.iter().copied().filter(|p| !p.starts_with('~')))
+            .collect()
+    }
+}
+
+pub struct Pipeline {
+    pub toolchain: Option<Toolchain>,
+    pub cache: Option<Cache>,
+    pub steps: Vec<Step>,
+    /// Project directory relative to the repository root, when not the root
+    pub working_dir: Option<String>,
+}
+
+/// Toolchain version the project pins (`.nvmrc`, `rust-toolchain.toml`,
+/// `.tool-versions`, the `go` directive of go.mod, ...), if any.
+pub fn pinned_version(stack: Stack, dir: &Path) -> Option<String> {
+    let tool = crate::toolchain::Tool::for_stack(stack)?;
+    crate::toolchain::pin(dir, tool).map(|pin| pin.version)
+}
+
+fn toolchain_version(stack: Stack, dir: &Path) -> String {
+    pinned_version(stack, dir).unwrap_or_else(|| {
+        match stack {
+            Stack::Rust => "stable",
+            Stack::Node => "22",
+            Stack::Python => "3.12",
+            Stack::Go => "1.23",
+            Stack::Maven | Stack::Gradle => "21",
+            Stack::Php => "8.3",
+            Stack::Ruby => "3.3",
+            Stack::Unknown => "",
+        }
+        .to_string()
+    })
+}
+
+/// Existing files among `candidates`, or the first candidate if none exists.
+fn key_files(dir: &Path, candidates: &[&str]) -> Vec<String> {
+    let found: Vec<String> = candidates
+        .iter()
+        .filter(|f| dir.join(f).is_file())
+        .map(|f| f.to_string())
+        .collect();
+    if found.is_empty() {
+        vec![candidates[0].to_string()]
+    } else {
+        found
+    }
+}
+
+fn step(stage: Stage, name: &'static str, run: impl Into<String>) -> Step {
+    Step {
+        stage,
+        name,
+        tools: None,
+        run: run.into(),
+    }
+}
+
+/// Step that needs `tools` installed first.
+fn tool_step(
+    stage: Stage,
+    name: &'static str,
+    tools: &'static str,
+    run: impl Into<String>,
+) -> Step {
+    Step {
+        tools: Some(tools),
+        ..step(stage, name, run)
+    }
+}
+
+/// Scripts declared in package.json.
+fn npm_scripts(dir: &Path) -> serde_json::Map<String, serde_json::Value> {
+    fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        .and_then(|v| v.get("scripts").and_then(|s| s.as_object()).cloned())
+        .unwrap_or_default()
+}
+
+fn node_steps(dir: &Path, steps: &mut Vec<Step>) -> Cache {
+    let has = |f: &str| dir.join(f).is_file();
+    let (install, run, audit, cache_path, relocated, lock) = if has("pnpm-lock.yaml") {
+        (
+            "corepack enable\npnpm install --frozen-lockfile",
+            "pnpm run",
+            "pnpm audit --audit-level high",
+            "~/.local/share/pnpm/store",
+            ("npm_config_store_dir", ".pnpm-store"),
+            "pnpm-lock.yaml",
+        )
+    } else if has("yarn.lock") {
+        (
+            "corepack enable\nyarn install --frozen-lockfile",
+            "yarn run",
+            "yarn audit --level high",
+            "~/.cache/yarn",
+            ("YARN_CACHE_FOLDER", ".yarn-cache"),
+            "yarn.lock",
+        )
+    } else if has("package-lock.json") {
+        (
+            "npm ci",
+            "npm run",
+            "npm audit --audit-level=high",
+            "~/.npm",
+            ("npm_config_cache", ".npm"),
+            "package-lock.json",
+        )
+    } else {
+        (
+            "npm install",
+            "npm run",
+            "npm audit --audit-level=high",
+            "~/.npm",
+            ("npm_config_cache", ".npm"),
+            "package.json",
+        )
+    };
+    let scripts = npm_scripts(dir);
+    // `npm init` writes a test script that always fails
+    let has_script = |name: &str| {
+        scripts
+            .get(name)
+            .and_then(|s| s.as_str())
+            .is_some_and(|s| !s.contains("no test specified"))
+    };
+    steps.push(step(Stage::Install, "Instalar dependências", install));
+    if has_script("build") {
+        steps.push(step(Stage::Build, "Build", format!("{run} build")));
+    }
+    if has_script("lint") {
+        steps.push(step(Stage::Lint, "Lint", format!("{run} lint")));
+    }
+    if has_script("test") {
+        steps.push(step(Stage::Test, "Testes", format!("{run} test")));
+    }
+    steps.push(step(Stage::Audit, "Auditoria de dependências", audit));
+    Cache {
+        paths: vec![cache_path],
+        relocated: vec![relocated],
+        key_files: vec![lock.to_string()],
+    }
+}
+
+fn stack_steps(stack: Stack, dir: &Path, steps: &mut Vec<Step>) -> Option<Cache> {
+    let has = |f: &str| dir.join(f).is_file();
+    let cache = match stack {
+        Stack::Rust => {
+            let locked = if has("Cargo.lock") { " --locked" } else { "" };
+            steps.push(step(
+                Stage::Build,
+                "Build",
+                format!("cargo build --all-targets{locked}"),
+            ));
+            steps.push(step(
+                Stage::Lint,
+                "Lint",
+                "cargo fmt --all -- --check\ncargo clippy --all-targets -- -D warnings",
+            ));
+            steps.push(step(Stage::Test, "Testes", format!("cargo test{locked}")));
+            steps.push(tool_step(
+                Stage::Audit,
+                "Auditoria de dependências",
+                "cargo install cargo-audit --locked",
+                "cargo audit",
+            ));
+            Cache {
+                paths: vec!["~/.cargo/registry", "~/.cargo/git", "target"],
+                relocated: vec![("CARGO_HOME", ".cargo")],
+                key_files: key_files(dir, &["Cargo.lock", "Cargo.toml"]),
+            }
+        }
+        Stack::Node => node_steps(dir, steps),
+        Stack::Python => {
+            let mut install = vec!["python -m pip install --upgrade pip".to_string()];
+            if has("requirements.txt") {
+                install.push("pip install -r requirements.txt".to_string());
+            } else {
+                install.push("pip install .".to_string());
+            }
+            if has("requirements-dev.txt") {
+                install.push("pip install -r requirements-dev.txt".to_string());
+            }
+            let audit = if has("requirements.txt") {
+                "pip-audit -r requirements.txt"
+            } else {
+                "pip-audit"
+            };
+            steps.push(step(
+                Stage::Install,
+                "Instalar dependências",
+                install.join("\n"),
+            ));
+            steps.push(tool_step(
+                Stage::Lint,
+                "Lint",
+                "pip install ruff",
+                "ruff check .",
+            ));
+            steps.push(tool_step(
+                Stage::Test,
+                "Testes",
+                "pip install pytest",
+                "python -m pytest",
+            ));
+            steps.push(tool_step(
+                Stage::Audit,
+                "Auditoria de dependências",
+                "pip install pip-audit",
+                audit,
+            ));
+            Cache {
+                paths: vec!["~/.cache/pip"],
+                relocated: vec![("PIP_CACHE_DIR", ".pip-cache")],
+                key_files: key_files(
+                    dir,
+                    &["requirements.txt", "requirements-dev.txt", "pyproject.toml"],
+                ),
+            }
+        }
+        Stack::Go => {
+            steps.push(step(Stage::Build, "Build", "go build ./..."));
+            steps.push(step(Stage::Lint, "Lint", "go vet ./..."));
+            steps.push(step(Stage::Test, "Testes", "go test ./..."));
+            steps.push(tool_step(
+                Stage::Audit,
+                "Auditoria de dependências",
+                "go install golang.org/x/vuln/cmd/govulncheck@latest",
+                "govulncheck ./...",
+            ));
+            Cache {
+                paths: vec!["~/go/pkg/mod", "~/.cache/go-build"],
+                relocated: vec![("GOMODCACHE", ".go/pkg/mod"), ("GOCACHE", ".go/cache")],
+                key_files: key_files(dir, &["go.sum", "go.mod"]),
+            }
+        }
+        Stack::Maven => {
+            let mvn = if has("mvnw") { "./mvnw -B" } else { "mvn -B" };
+            steps.push(step(
+                Stage::Build,
+                "Build",
+                format!("{mvn} -DskipTests package"),
+            ));
+            steps.push(step(Stage::Test, "Testes", format!("{mvn} test")));
+            steps.push(step(
+                Stage::Audit,
+                "Auditoria de dependências",
+                format!("{mvn} org.owasp:dependency-check-maven:check"),
+            ));
+            Cache {
+                paths: vec!["~/.m2/repository"],
+                relocated: vec![("MAVEN_OPTS", ".m2/repository")],
+                key_files: vec!["pom.xml".to_string()],
+            }
+        }
+        Stack::Gradle => {
+            let gradle = if has("gradlew") {
+                "./gradlew"
+            } else {
+                "gradle"
+            };
+            steps.push(step(Stage::Build, "Build", format!("{gradle} assemble")));
+            steps.push(step(Stage::Lint, "Lint", format!("{gradle} check -x test")));
+            steps.push(step(Stage::Test, "Testes", format!("{gradle} test")));
+            Cache {
+                paths: vec!["~/.gradle/caches", "~/.gradle/wrapper"],
+                relocated: vec![("GRADLE_USER_HOME", ".gradle")],
+                key_files: key_files(
+                    dir,
+                    &[
+                        "build.gradle",
+                        "build.gradle.kts",
+                        "settings.gradle",
+                        "settings.gradle.kts",
+                        "gradle/wrapper/gradle-wrapper.properties",
+                    ],
+                ),
+            }
+        }
+        Stack::Php => {
+            steps.push(step(
+                Stage::Install,
+                "Instalar dependências",
+                "composer install --no-interaction --prefer-dist",
+            ));
+            if has("phpunit.xml") || has("phpunit.xml.dist") {
+                steps.push(step(Stage::Test, "Testes", "vendor/bin/phpunit"));
+            }
+            steps.push(step(
+                Stage::Audit,
+                "Auditoria de dependências",
+                "composer audit",
+            ));
+            Cache {
+                paths: vec!["~/.cache/composer"],
+                relocated: vec![("COMPOSER_CACHE_DIR", ".composer-cache")],
+                key_files: key_files(dir, &["composer.lock", "composer.json"]),
+            }
+        }
+        Stack::Ruby => {
+            steps.push(step(
+                Stage::Install,
+                "Instalar dependências",
+                "bundle config set --local path vendor/bundle\nbundle install",
+            ));
+            if has(".rubocop.yml") {
+                steps.push(step(Stage::Lint, "Lint", "bundle exec rubocop"));
+            }
+            let test = if dir.join("spec").is_dir() {
+                "bundle exec rspec"
+            } else {
+                "bundle exec rake test"
+            };
+            steps.push(step(Stage::Test, "Testes", test));
+            steps.push(tool_step(
+                Stage::Audit,
+                "Auditoria de dependências",
+                "gem install bundler-audit",
+                "bundle-audit check --update",
+            ));
+            Cache {
+                paths: vec!["vendor/bundle"],
+                relocated: Vec::new(),
+                key_files: key_files(dir, &["Gemfile.lock", "Gemfile"]),
+            }
+        }
+        Stack::Unknown => return None,
+    };
+    Some(cache)
+}
+
+/// Directory containing `.git` at or above `dir`.
+fn repo_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|d| d.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+impl Pipeline {
+    /// Pipeline for the project at `dir`, whose repository starts at `root`.
+    pub fn detect(dir: &Path, root: &Path) -> Pipeline {
+        let stack = Stack::detect(dir);
+        let mut steps = Vec::new();
+        let cache = stack_steps(stack, dir, &mut steps);
+        // Mirrors `dx analyzer` as run locally, without writing the report
+        steps.push(tool_step(
+            Stage::Analyze,
+            "dx analyzer",
+            INSTALL_DX,
+            "./dx --ci analyzer --no-save",
+        ));
+        if dir.join(".dx").join("policies").is_dir() {
+            steps.push(step(
+                Stage::Analyze,
+                "dx governance check",
+                "./dx --ci governance check",
+            ));
+        }
+        steps.sort_by_key(|s| s.stage);
+        let working_dir = dir
+            .strip_prefix(root)
+            .ok()
+            .map(|p| p.display().to_string().replace('\\', "/"))
+            .filter(|p| !p.is_empty());
+        Pipeline {
+            toolchain: (stack != Stack::Unknown).then(|| Toolchain {
+                stack,
+                version: toolchain_version(stack, dir),
+            }),
+            cache,
+            steps,
+            working_dir,
+        }
+    }
+
+    pub fn stack(&self) -> Stack {
+        self.toolchain.as_ref().map_or(Stack::Unknown, |t| t.stack)
+    }
+
+    /// `path` relative to the repository root.
+    fn repo_path(&self, path: &str) -> String {
+        match &self.working_dir {
+            Some(wd) if !path.starts_with('~') => format!("{wd}/{path}"),
+            _ => path.to_string(),
+        }
+    }
+
+    /// Project directory given the repository root `root` (e.g. `$CI_PROJECT_DIR`).
+    fn project_dir(&self, root: &str) -> String {
+        match &self.working_dir {
+            Some(wd) => format!("{root}/{wd}"),
+            None => root.to_string(),
+        }
+    }
+
+    fn image(&self) -> String {
+        self.toolchain
+            .as_ref()
+            .map_or_else(|| UNKNOWN_IMAGE.to_string(), Toolchain::image)
+    }
+
+    /// Commands run before every step in container-based providers: toolchain
+    /// setup and dependency installation.
+    fn prelude(&self) -> Vec<&str> {
+        let setup = self.toolchain.as_ref().and_then(Toolchain::container_setup);
+        setup
+            .into_iter()
+            .chain(
+                self.steps
+                    .iter()
+                    .filter(|s| s.stage == Stage::Install)
+                    .flat_map(|s| s.run.lines()),
+            )
+            .collect()
+    }
+
+    /// Steps after dependency installation, grouped by stage.
+    fn stages(&self) -> Vec<(Stage, Vec<&Step>)> {
+        let mut stages: Vec<(Stage, Vec<&Step>)> = Vec::new();
+        for s in self.steps.iter().filter(|s| s.stage != Stage::Install) {
+            match stages.last_mut() {
+                Some((stage, steps)) if *stage == s.stage => steps.push(s),
+                _ => stages.push((s.stage, vec![s])),
+            }
+        }
+        stages
+    }
+}
+
+/// Quote `value` for YAML when it could be read as something other than a string.
+fn yaml_str(value: &str) -> String {
+    let lower = value.to_ascii_lowercase();
+    let quote = value.is_empty()
+        || value.parse::<f64>().is_ok()
+        || matches!(
+            lower.as_str(),
+            "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~"
+        )
+        || value.starts_with(|c: char| "-?:,[]{}#&*!|>'\"%@`".contains(c) || c.is_whitespace())
+        || value.ends_with(|c: char| c == ':' || c.is_whitespace())
+        || value.contains(": ")
+        || value.contains(" #");
+    if quote {
+        format!("'{}'", value.replace('\'', "''"))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Write `key: run` after `prefix`, as a block scalar when `run` has several lines.
+fn push_run(y: &mut String, prefix: &str, key: &str, run: &str) {
+    if run.contains('\n') {
+        y.push_str(&format!("{prefix}{key}: |\n"));
+        let indent = " ".repeat(prefix.len() + 2);
+        for line in run.lines() {
+            y.push_str(&format!("{indent}{line}\n"));
+        }
+    } else {
+        y.push_str(&format!("{prefix}{key}: {}\n", yaml_str(run)));
+    }
+}
+
+/// `uses:` step that installs the toolchain, with its `with:` inputs.
+fn github_setup(toolchain: &Toolchain) -> (&'static str, Vec<(&'static str, String)>) {
+    let version = toolchain.version.clone();
+    match toolchain.stack {
+        Stack::Rust => (
+            "dtolnay/rust-toolchain@master",
+            vec![
+                ("toolchain", version),
+                ("components", "clippy, rustfmt".to_string()),
+            ],
+        ),
+        Stack::Node => ("actions/setup-node@v4", vec![("node-version", version)]),
+        Stack::Python => ("actions/setup-python@v5", vec![("python-version", version)]),
+        // The dependency cache below already covers the module cache
+        Stack::Go => (
+            "actions/setup-go@v5",
+            vec![("go-version", version), ("cache", "false".to_string())],
+        ),
+        Stack::Maven | Stack::Gradle => (
+            "actions/setup-java@v4",
+            vec![
+                ("distribution", "temurin".to_string()),
+                ("java-version", version),
+            ],
+        ),
+        Stack::Php => ("shivammathur/setup-php@v2", vec![("php-version", version)]),
+        Stack::Ruby => ("ruby/setup-ruby@v1", vec![("ruby-version", version)]),
+        Stack::Unknown => unreachable!("no toolchain for unknown stacks"),
+    }
+}
+
+fn render_github(pipeline: &Pipeline) -> String {
+    let mut y = String::new();
+    y.push_str("name: dx\n\non:\n  push:\n    branches: [main, master]\n  pull_request:\n\n");
+    y.push_str("permissions:\n  contents: read\n\njobs:\n  dx:\n    runs-on: ubuntu-latest\n");
+    if let Some(wd) = &pipeline.working_dir {
+        y.push_str(&format!(
+            "    defaults:\n      run:\n        working-directory: {}\n",
+            yaml_str(wd)
+        ));
+    }
+    y.push_str("    steps:\n      - uses: actions/checkout@v4\n");
+    if let Some(toolchain) = &pipeline.toolchain {
+        let (uses, with) = github_setup(toolchain);
+        y.push_str(&format!("      - uses: {uses}\n        with:\n"));
+        for (key, value) in with {
+            y.push_str(&format!("          {key}: {}\n", yaml_str(&value)));
+        }
+    }
+    if let (Some(cache), Some(toolchain)) = (&pipeline.cache, &pipeline.toolchain) {
+        let files = cache
+            .key_files
+            .iter()
+            .map(|f| format!("'{}'", pipeline.repo_path(f)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        y.push_str("      - uses: actions/cache@v4\n        with:\n          path: |\n");
+        for path in &cache.paths {
+            y.push_str(&format!("            {}\n", pipeline.repo_path(path)));
+        }
+        y.push_str(&format!(
+            "          key: ${{{{ runner.os }}}}-{stack}-${{{{ hashFiles({files}) }}}}\n          restore-keys: ${{{{ runner.os }}}}-{stack}-\n",
+            stack = toolchain.stack.name()
+        ));
+    }
+    for s in &pipeline.steps {
+        y.push_str(&format!("      - name: {}\n", yaml_str(s.name)));
+        push_run(&mut y, "        ", "run", &s.script());
+    }
+    y
+}
+
+fn render_gitlab(pipeline: &Pipeline) -> String {
+    let stages = pipeline.stages();
+    let mut y = String::from("stages:\n");
+    for (stage, _) in &stages {
+        y.push_str(&format!("  - {}\n", stage.name()));
+    }
+    if let Some(cache) = &pipeline.cache {
+        let env = cache.env(&pipeline.project_dir("$CI_PROJECT_DIR"));
+        if !env.is_empty() {
+            y.push_str("\nvariables:\n");
+            for (var, value) in env {
+                y.push_str(&format!("  {var}: {}\n", yaml_str(&value)));
+            }
+        }
+    }
+    y.push_str(&format!("\ndefault:\n  image: {}\n", pipeline.image()));
+    if let (Some(cache), Some(toolchain)) = (&pipeline.cache, &pipeline.toolchain) {
+        // GitLab keys a cache by at most two files
+        y.push_str("  cache:\n    key:\n      files:\n");
+        for file in cache.key_files.iter().take(2) {
+            y.push_str(&format!(
+                "        - {}\n",
+                yaml_str(&pipeline.repo_path(file))
+            ));
+        }
+        y.push_str(&format!(
+            "      prefix: {}\n    paths:\n",
+            toolchain.stack.name()
+        ));
+        for path in cache.project_paths() {
+            y.push_str(&format!("      - {}/\n", pipeline.repo_path(path)));
+        }
+    }
+    let mut prelude = pipeline.prelude();
+    let cd = pipeline.working_dir.as_ref().map(|wd| format!("cd {wd}"));
+    if let Some(cd) = &cd {
+        prelude.insert(0, cd);
+    }
+    if !prelude.is_empty() {
+        y.push_str("  before_script:\n");
+        for line in prelude {
+            y.push_str(&format!("    - {}\n", yaml_str(line)));
+        }
+    }
+    for (stage, steps) in &stages {
+        y.push_str(&format!(
+            "\n{name}:\n  stage: {name}\n  script:\n",
+            name = stage.name()
+        ));
+        let script = steps.iter().map(|s| s.script()).collect::<Vec<_>>();
+        for line in script.iter().flat_map(|s| s.lines()) {
+            y.push_str(&format!("    - {}\n", yaml_str(line)));
+        }
+    }
+    y
+}
+
+/// CircleCI cache key: the stack plus the checksum of each key file.
+fn circleci_cache_key(pipeline: &Pipeline, cache: &Cache, stack: Stack) -> String {
+    let checksums = cache
+        .key_files
+        .iter()
+        .map(|f| format!("{{{{ checksum \"{}\" }}}}", pipeline.repo_path(f)))
+        .collect::<Vec<_>>()
+        .join("-");
+    format!("{}-v1-{checksums}", stack.name())
+}
+
+fn render_circleci(pipeline: &Pipeline) -> String {
+    let mut y = String::from("version: 2.1\n\njobs:\n  dx:\n    docker:\n");
+    y.push_str(&format!(
+        "      - image: {}\n    steps:\n      - checkout\n",
+        pipeline.image()
+    ));
+    let cache = pipeline.cache.as_ref().zip(pipeline.toolchain.as_ref());
+    if let Some((cache, toolchain)) = cache {
+        y.push_str(&format!(
+            "      - restore_cache:\n          keys:\n            - '{}'\n            - {}-v1-\n",
+            circleci_cache_key(pipeline, cache, toolchain.stack),
+            toolchain.stack.name()
+        ));
+    }
+    let setup = pipeline
+        .toolchain
+        .as_ref()
+        .and_then(Toolchain::container_setup);
+    let steps = setup
+        .map(|run| ("Setup", run.to_string()))
+        .into_iter()
+        .chain(pipeline.steps.iter().map(|s| (s.name, s.script())));
+    for (name, run) in steps {
+        y.push_str(&format!(
+            "      - run:\n          name: {}\n",
+            yaml_str(name)
+        ));
+        if let Some(wd) = &pipeline.working_dir {
+            y.push_str(&format!("          working_directory: {}\n", yaml_str(wd)));
+        }
+        push_run(&mut y, "          ", "command", &run);
+    }
+    if let Some((cache, toolchain)) = cache {
+        y.push_str(&format!(
+            "      - save_cache:\n          key: '{}'\n          paths:\n",
+            circleci_cache_key(pipeline, cache, toolchain.stack)
+        ));
+        for path in &cache.paths {
+            y.push_str(&format!("            - {}\n", pipeline.repo_path(path)));
+        }
+    }
+    y.push_str("\nworkflows:\n  dx:\n    jobs:\n      - dx\n");
+    y
+}
+
+fn render_azure(pipeline: &Pipeline) -> String {
+    let mut y = String::from(
+        "trigger:\n  branches:\n    include:\n      - main\n      - master\n\npr:\n  branches:\n    include:\n      - '*'\n\n",
+    );
+    y.push_str(&format!(
+        "pool:\n  vmImage: ubuntu-latest\n\ncontainer: {}\n",
+        pipeline.image()
+    ));
+    let project_dir = pipeline.project_dir("$(Build.SourcesDirectory)");
+    if let Some(cache) = &pipeline.cache {
+        let env = cache.env(&project_dir);
+        if !env.is_empty() {
+            y.push_str("\nvariables:\n");
+            for (var, value) in env {
+                y.push_str(&format!("  {var}: {}\n", yaml_str(&value)));
+            }
+        }
+    }
+    y.push_str("\nsteps:\n  - checkout: self\n");
+    if let (Some(cache), Some(toolchain)) = (&pipeline.cache, &pipeline.toolchain) {
+        let files = cache
+            .key_files
+            .iter()
+            .map(|f| pipeline.repo_path(f))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        // Cache@2 keeps a single directory per task
+        for path in cache.project_paths() {
+            let prefix = format!("{} | {path} | \"$(Agent.OS)\"", toolchain.stack.name());
+            y.push_str(&format!(
+                "  - task: Cache@2\n    displayName: {}\n    inputs:\n      key: {}\n      restoreKeys: {}\n      path: {project_dir}/{path}\n",
+                yaml_str(&format!("Cache {path}")),
+                yaml_str(&format!("{prefix} | {files}")),
+                yaml_str(&prefix)
+            ));
+        }
+    }
+    let setup = pipeline
+        .toolchain
+        .as_ref()
+        .and_then(Toolchain::container_setup);
+    let steps = setup
+        .map(|run| ("Setup", run.to_string()))
+        .into_iter()
+        .chain(pipeline.steps.iter().map(|s| (s.name, s.script())));
+    for (name, run) in steps {
+        push_run(&mut y, "  - ", "script", &run);
+        y.push_str(&format!("    displayName: {}\n", yaml_str(name)));
+        if pipeline.working_dir.is_some() {
+            y.push_str(&format!("    workingDirectory: {project_dir}\n"));
+        }
+    }
+    y
+}
+
+pub struct GenerateOptions {
+    pub provider: Provider,
+    /// Print the configuration instead of writing it
+    pub dry_run: bool,
+    /// Overwrite a file that was not generated by dx
+    pub force: bool,
+}
+
+/// `dx ci generate`: write the provider's configuration for the project at `dir`.
+pub fn generate(dir: Option<PathBuf>, opts: GenerateOptions) -> DxResult {
+    let dir = dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            dir.display()
+        )));
+    }
+    let dir = dir.canonicalize().unwrap_or(dir);
+    let root = repo_root(&dir).unwrap_or_else(|| dir.clone());
+    let pipeline = Pipeline::detect(&dir, &root);
+    let block = MARKERS.wrap(
+        &format!(
+            "Gerado por `dx ci generate --provider {}`; alterações entre os marcadores são sobrescritas.",
+            opts.provider.name()
+        ),
+        &opts.provider.render(&pipeline),
+    );
+
+    outln!(
+        "Pipeline de CI ({}) para stack {}:",
+        opts.provider.name(),
+        pipeline.stack().name()
+    );
+    for s in &pipeline.steps {
+        outln!("- {}", s.name);
+    }
+    let path = root.join(opts.provider.path());
+    let written = managed::write(
+        &path,
+        &MARKERS,
+        &block,
+        &managed::WriteOptions {
+            dry_run: opts.dry_run,
+            force: opts.force,
+        },
+    )?;
+
+    crate::output::set_data(serde_json::json!({
+        "provider": opts.provider.name(),
+        "stack": pipeline.stack().name(),
+        "path": path.display().to_string(),
+        "working_directory": pipeline.working_dir,
+        "steps": pipeline.steps.iter().map(|s| s.name).collect::<Vec<_>>(),
+        "written": written,
+    }));
+    Ok(())
+}
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Platform-aware command construction.
+//!
+//! Project wrappers are shell scripts on Unix (`gradlew`, `mvnw`) and batch
+//! files on Windows (`gradlew.bat`, `mvnw.cmd`), so they are resolved to the
+//! file for the current platform and spawned by path instead of as `./gradlew`.
+//! Commands shown in logs and hints, meant to be pasted in a terminal, quote
+//! their arguments the way the platform's shell expects: single quotes for sh,
+//! double quotes for cmd and PowerShell.
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// File name of the project wrapper `name` (`gradlew`, `mvnw`) on this platform.
+pub fn wrapper_name(name: &str) -> String {
+    match name {
+        _ if !cfg!(windows) => name.to_string(),
+        "mvnw" => "mvnw.cmd".to_string(),
+        _ => format!("{name}.bat"),
+    }
+}
+
+/// The wrapper `name` of the project in `dir`, when it has one for this platform.
+pub fn wrapper(dir: &Path, name: &str) -> Option<PathBuf> {
+    let path = dir.join(wrapper_name(name));
+    path.is_file().then_some(path)
+}
+
+/// `arg` as typed in the platform's shell: quoted when it is empty or has
+/// spaces or characters the shell would interpret.
+pub fn quote(arg: &str) -> String {
+    let safe = |c: char| {
+        c.is_ascii_alphanumeric()
+            || "-_./:=@%+,".contains(c)
+            || (cfg!(windows) && c == '\\')
+    };
+    if !arg.is_empty() && arg.chars().all(safe) {
+        arg.to_string()
+    } else if cfg!(windows) {
+        format!("\"{}\"", arg.replace('"', "\\\""))
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// `program args...` as typed in the platform's shell, for logs and hints.
+pub fn command_line<I, S>(parts: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    parts
+        .into_iter()
+        .map(|p| quote(&p.as_ref().to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
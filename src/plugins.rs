@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! External subcommands (git-style plugins).
+//!
+//! `dx <name> [args...]` that is not a built-in command is resolved, in order,
+//! from the project registry `.dx/plugins.json`:
+//!
+//! ```json
+//! { "deploy-preview": { "command": "scripts/deploy-preview.sh", "args": ["--fast"], "description": "..." } }
+//! ```
+//!
+//! and then from an executable `dx-<name>` on `PATH`. Plugins receive the
+//! project context through environment variables: `DX_PROJECT_DIR`, `DX_DIR`
+//! (the `.dx` folder), `DX_ANALYSIS_JSON` (temporary file with the analyzer
+//! model), `DX_OUTPUT` (`text`/`json`), `DX_BIN` and `DX_VERSION`.
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+
+use crate::error::{DxError, DxResult, IoContext};
+
+/// Prefix of plugin executables on `PATH`.
+const PREFIX: &str = "dx-";
+
+#[derive(Deserialize)]
+struct RegistryEntry {
+    command: String,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+fn registry_path(project_dir: &Path) -> PathBuf {
+    project_dir.join(".dx").join("plugins.json")
+}
+
+fn load_registry(project_dir: &Path) -> DxResult<BTreeMap<String, RegistryEntry>> {
+    let path = registry_path(project_dir);
+    let Ok(data) = fs::read_to_string(&path) else {
+        return Ok(BTreeMap::new());
+    };
+    serde_json::from_str(&data)
+        .map_err(|e| DxError::InvalidInput(format!("{} inválido: {e}", path.display())))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// File names a program may have on this platform (`.exe`, `.cmd`, ... on Windows).
+fn candidate_names(program: &str) -> Vec<String> {
+    if cfg!(windows) {
+        let exts = std::env::var("PATHEXT").unwrap_or_else(|_| ".EXE;.CMD;.BAT".to_string());
+        exts.split(';')
+            .filter(|e| !e.is_empty())
+            .map(|e| format!("{program}{}", e.to_ascii_lowercase()))
+            .collect()
+    } else {
+        vec![program.to_string()]
+    }
+}
+
+fn path_dirs() -> Vec<PathBuf> {
+    std::env::var_os("PATH")
+        .map(|p| std::env::split_paths(&p).collect())
+        .unwrap_or_default()
+}
+
+/// Full path of `program` on `PATH` (with the platform's executable extensions).
+pub fn find_on_path(program: &str) -> Option<PathBuf> {
+    path_dirs().into_iter().find_map(|dir| {
+        candidate_names(program)
+            .into_iter()
+            .map(|name| dir.join(name))
+            .find(|p| is_executable(p))
+    })
+}
+
+/// `dx-*` executables on `PATH`, by plugin name (first match wins, like the shell).
+fn path_plugins() -> BTreeMap<String, PathBuf> {
+    let mut found = BTreeMap::new();
+    for dir in path_dirs() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if let Some(name) = stem.strip_prefix(PREFIX)
+                && !name.is_empty()
+                && is_executable(&path)
+            {
+                found.entry(name.to_string()).or_insert(path);
+            }
+        }
+    }
+    found
+}
+
+fn resolve(project_dir: &Path, name: &str) -> DxResult<Option<(PathBuf, Vec<String>)>> {
+    if let Some(entry) = load_registry(project_dir)?.remove(name) {
+        // Paths are relative to the project; bare names are looked up on PATH
+        let command = if entry.command.contains('/') || entry.command.contains('\\') {
+            project_dir.join(&entry.command)
+        } else {
+            find_on_path(&entry.command).unwrap_or_else(|| PathBuf::from(&entry.command))
+        };
+        return Ok(Some((command, entry.args)));
+    }
+    Ok(find_on_path(&format!("{PREFIX}{name}")).map(|p| (p, Vec::new())))
+}
+
+/// Run the external subcommand `args[0]` with the remaining arguments,
+/// exiting with the plugin's own exit code.
+pub fn run(args: Vec<OsString>) -> DxResult {
+    let mut args = args.into_iter();
+    let name = args
+        .next()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let project_dir = std::env::current_dir().context("Erro ao obter o diretório atual")?;
+
+    let Some((command, base_args)) = resolve(&project_dir, &name)? else {
+        return Err(DxError::InvalidInput(format!(
+            "Subcomando desconhecido: '{name}'. Nenhum plugin '{PREFIX}{name}' encontrado no PATH nem em {}.\nDica: veja os comandos com 'dx --help' e os plugins com 'dx plugins'.",
+            registry_path(&project_dir).display()
+        )));
+    };
+
+    let ds_config = crate::dev_services::detect_dependencies(&project_dir);
+    let model = crate::report::build_analyzer_model(&project_dir, &ds_config);
+    let analysis = std::env::temp_dir().join(format!("dx-analysis-{}.json", std::process::id()));
+    fs::write(
+        &analysis,
+        serde_json::to_string_pretty(&model).unwrap_or_default(),
+    )
+    .context("Erro ao gravar o modelo do analyzer para o plugin")?;
+
+    tracing::debug!(plugin = %name, command = %command.display(), "executando plugin");
+    let status = Command::new(&command)
+        .args(base_args)
+        .args(args)
+        .env("DX_PROJECT_DIR", &project_dir)
+        .env("DX_DIR", project_dir.join(".dx"))
+        .env("DX_ANALYSIS_JSON", &analysis)
+        .env(
+            "DX_OUTPUT",
+            if crate::output::is_json() {
+                "json"
+            } else {
+                "text"
+            },
+        )
+        .env(
+            "DX_BIN",
+            std::env::current_exe().unwrap_or_else(|_| PathBuf::from("dx")),
+        )
+        .env("DX_VERSION", env!("CARGO_PKG_VERSION"))
+        .status();
+    let _ = fs::remove_file(&analysis);
+
+    let status = status.map_err(|e| {
+        DxError::Tool(format!(
+            "Falha ao executar o plugin '{name}' ({}): {e}",
+            command.display()
+        ))
+    })?;
+    if !status.success() {
+        crate::output::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+/// List plugins from `.dx/plugins.json` and from `PATH`.
+pub fn list() -> DxResult {
+    let project_dir = std::env::current_dir().context("Erro ao obter o diretório atual")?;
+    let registry = load_registry(&project_dir)?;
+    let on_path = path_plugins();
+
+    let mut plugins = Vec::new();
+    for (name, entry) in &registry {
+        outln!(
+            "- {name} (registro: {}){}",
+            entry.command,
+            entry
+                .description
+                .as_deref()
+                .map(|d| format!(" — {d}"))
+                .unwrap_or_default()
+        );
+        plugins.push(serde_json::json!({
+            "name": name,
+            "source": "registry",
+            "command": entry.command,
+            "description": entry.description,
+        }));
+    }
+    // Registry entries shadow PATH executables with the same name
+    for (name, path) in on_path.iter().filter(|(n, _)| !registry.contains_key(*n)) {
+        outln!("- {name} (PATH: {})", path.display());
+        plugins.push(serde_json::json!({
+            "name": name,
+            "source": "path",
+            "command": path.display().to_string(),
+            "description": null,
+        }));
+    }
+    if plugins.is_empty() {
+        outln!(
+            "Nenhum plugin encontrado (executáveis '{PREFIX}<nome>' no PATH ou {}).",
+            registry_path(&project_dir).display()
+        );
+    }
+    crate::output::set_data(serde_json::json!({ "plugins": plugins }));
+    Ok(())
+}
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Ports of the Dev Services in the project's README.
+//!
+//! A README with the `<!-- dx-cli:ports:start -->` and
+//! `<!-- dx-cli:ports:end -->` markers gets, between them, a table of the
+//! manifest's services with their host ports and web UIs. `dx dev-services`
+//! rewrites it from the saved manifest (local overrides included) each time it
+//! generates one, so onboarding docs follow port changes. A README without the
+//! markers is left alone: the section is opt-in.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::detectors::Detector;
+use crate::dev_services::ManifestService;
+use crate::i18n::DocLanguage;
+use crate::managed::Markers;
+
+const MARKERS: Markers = Markers {
+    start: "<!-- dx-cli:ports:start -->",
+    end: "<!-- dx-cli:ports:end -->",
+};
+
+/// Comment after the start marker, in the project's documentation language.
+fn note(lang: DocLanguage) -> &'static str {
+    lang.pick(
+        "<!-- Gerado por `dx dev-services`; alterações entre os marcadores são sobrescritas. -->",
+        "<!-- Generated by `dx dev-services`; changes between the markers are overwritten. -->",
+        "<!-- Generado por `dx dev-services`; los cambios entre los marcadores se sobrescriben. -->",
+    )
+}
+
+/// Container port of the web UI of `service`, if it has one.
+pub fn ui_port(detectors: &[Box<dyn Detector>], service: &str) -> Option<u16> {
+    if let Some(detector) = detectors.iter().find(|d| d.provides(service)) {
+        return detector.ui(service);
+    }
+    match service {
+        // Observability stack
+        "grafana" => Some(3000),
+        "prometheus" => Some(9090),
+        crate::proxy::SERVICE => Some(crate::proxy::DASHBOARD_PORT),
+        _ => None,
+    }
+}
+
+/// The block between the markers (markers included) for `services`.
+fn block(
+    detectors: &[Box<dyn Detector>],
+    services: &[ManifestService],
+    lang: DocLanguage,
+) -> String {
+    let mut block = format!("{}\n{}\n\n", MARKERS.start, note(lang));
+    block.push_str(lang.pick(
+        "| Serviço | Portas no host | UI |\n",
+        "| Service | Host ports | UI |\n",
+        "| Servicio | Puertos en el host | UI |\n",
+    ));
+    block.push_str("|---------|----------------|----|\n");
+    for service in services {
+        let ports = if service.ports.is_empty() {
+            "-".to_string()
+        } else {
+            let ports: Vec<String> = service
+                .ports
+                .iter()
+                .map(|(host, _)| host.to_string())
+                .collect();
+            ports.join(", ")
+        };
+        let ui = ui_port(detectors, &service.name)
+            .and_then(|container| service.published(container))
+            .map_or("-".to_string(), |port| format!("http://localhost:{port}"));
+        block.push_str(&format!("| {} | {ports} | {ui} |\n", service.name));
+    }
+    block.push_str(&format!("\n{}\n", MARKERS.end));
+    block
+}
+
+/// Rewrite the ports section of the README of `project_dir` from the manifest
+/// at `compose`. Returns the README when it changed; `None` when it has no
+/// section or it was already up to date.
+pub fn sync(project_dir: &Path, compose: &Path) -> std::io::Result<Option<PathBuf>> {
+    let readme = project_dir.join("README.md");
+    let Ok(content) = fs::read_to_string(&readme) else {
+        return Ok(None);
+    };
+    let services = crate::dev_services::manifest_services(&fs::read_to_string(compose)?);
+    let Some(updated) = MARKERS.replace(
+        &content,
+        &block(
+            &crate::detectors::catalog(project_dir),
+            &services,
+            DocLanguage::of(project_dir),
+        ),
+    ) else {
+        return Ok(None);
+    };
+    if updated == content {
+        return Ok(None);
+    }
+    fs::write(&readme, updated)?;
+    Ok(Some(readme))
+}
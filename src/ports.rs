@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Host ports of the manifest that are already taken on this machine.
+//!
+//! Before the manifest is written, each port a service publishes (5432,
+//! 6379, 9092, 3000...) is checked: when something else already listens on
+//! it, the service is published on the next free port instead (5432 becomes
+//! 5433:5432) and the remap is recorded in `.dx/ports.json`. Recorded remaps
+//! are kept on later runs, so the ports don't move while the `.env` files and
+//! the report point at them; a port the current manifest already publishes is
+//! the stack's own (its containers may be running) and stays. Everything
+//! reading the manifest (`dx env`, the presets' env files, the report) sees
+//! the actual ports. `[dev_services] remap_ports = false` turns it off (the
+//! record is then dropped); on a remote engine (`run --context/--host`) the
+//! ports are published there and only the recorded remaps apply.
+use std::collections::BTreeSet;
+use std::fs;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::dev_services::{DockerComposeConfig, DockerService};
+
+/// Record of the remaps, relative to `.dx`.
+pub const FILE: &str = "ports.json";
+/// Ports tried after a taken one before giving up.
+const MAX_TRIES: u16 = 100;
+
+/// A service published on another host port than its container port.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Remap {
+    pub service: String,
+    pub container: u16,
+    pub host: u16,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Record {
+    remaps: Vec<Remap>,
+}
+
+fn path(project_dir: &Path) -> PathBuf {
+    project_dir.join(".dx").join(FILE)
+}
+
+/// Remaps recorded in `.dx/ports.json`.
+pub fn recorded(project_dir: &Path) -> Vec<Remap> {
+    fs::read_to_string(path(project_dir))
+        .ok()
+        .and_then(|data| serde_json::from_str::<Record>(&data).ok())
+        .map(|record| record.remaps)
+        .unwrap_or_default()
+}
+
+/// Whether `port` can be bound on this machine.
+pub(crate) fn free(port: u16) -> bool {
+    TcpListener::bind(("0.0.0.0", port)).is_ok()
+}
+
+/// Host ports published by the manifest in `.dx`.
+fn published(project_dir: &Path) -> BTreeSet<u16> {
+    let compose = project_dir.join(".dx").join("docker-compose.yml");
+    let yaml = fs::read_to_string(compose).unwrap_or_default();
+    crate::dev_services::manifest_services(&yaml)
+        .iter()
+        .flat_map(|s| s.ports.iter().map(|(host, _)| *host))
+        .collect()
+}
+
+/// Publish the services of `config` on free host ports, keeping the
+/// recorded remaps. Returns the remaps in effect, in service order.
+pub fn remap(project_dir: &Path, config: &mut DockerComposeConfig) -> Vec<Remap> {
+    let settings = crate::settings::get(project_dir);
+    if settings.dev_services.remap_ports == Some(false) {
+        return Vec::new();
+    }
+    let recorded = recorded(project_dir);
+    let check = crate::remote::engine(project_dir).is_none();
+    let own = if check {
+        published(project_dir)
+    } else {
+        BTreeSet::new()
+    };
+    // Every container port counts as taken: moving 9092 to 9093 would clash
+    // with the Kafka UI
+    let mut taken: BTreeSet<u16> = config
+        .services
+        .values()
+        .flat_map(|s| s.ports.iter().copied())
+        .collect();
+    let mut remaps = Vec::new();
+    for name in config.service_names() {
+        let service = config.services.get_mut(&name).expect("listed service");
+        for container in service.ports.clone() {
+            let host = match recorded
+                .iter()
+                .find(|r| r.service == name && r.container == container)
+            {
+                Some(remap) => Some(remap.host),
+                None if !check || own.contains(&container) || free(container) => None,
+                None => {
+                    let next = (1..=MAX_TRIES)
+                        .filter_map(|n| container.checked_add(n))
+                        .find(|port| !taken.contains(port) && !own.contains(port) && free(*port));
+                    if next.is_none() {
+                        tracing::warn!(
+                            "porta {container} ({name}) em uso e nenhuma livre até +{MAX_TRIES}"
+                        );
+                    }
+                    next
+                }
+            };
+            if let Some(host) = host {
+                taken.insert(host);
+                service.host_ports.insert(container, host);
+                remaps.push(Remap {
+                    service: name.clone(),
+                    container,
+                    host,
+                });
+            }
+        }
+    }
+    remaps
+}
+
+/// Content of `.dx/ports.json` recording `remaps`.
+pub fn record(remaps: &[Remap]) -> String {
+    let record = Record {
+        remaps: remaps.to_vec(),
+    };
+    serde_json::to_string_pretty(&record).expect("remaps serialize") + "\n"
+}
+
+/// `text` (a report line about `service`) with the container ports in its
+/// `localhost:<port>` addresses replaced by the published ones.
+pub fn rewrite(text: &str, service: &DockerService) -> String {
+    let mut text = text.to_string();
+    for (container, host) in &service.host_ports {
+        let from = format!("localhost:{container}");
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+        while let Some(at) = rest.find(&from) {
+            let end = at + from.len();
+            // Don't touch longer ports (localhost:80 in localhost:8080)
+            let port = match rest[end..].starts_with(|c: char| c.is_ascii_digit()) {
+                true => container,
+                false => host,
+            };
+            out.push_str(&rest[..at]);
+            out.push_str(&format!("localhost:{port}"));
+            rest = &rest[end..];
+        }
+        out.push_str(rest);
+        text = out;
+    }
+    text
+}
@@ -0,0 +1,399 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Framework presets for Dev Services.
+//!
+//! When the project is a Rails, Laravel, Spring Boot or Django app, the
+//! database service follows the framework's conventions (Rails'
+//! `<app>_development`, the `DB_*` values of Laravel's `.env`, ...) and saving
+//! the manifest writes the connection where the framework reads it:
+//! `.env.development` (Rails, via dotenv), `.env` (Laravel, Django) or
+//! `application-dev.properties` (Spring Boot, profile `dev`). Only the preset's
+//! keys are changed in that file; the rest of it is kept. `[project] framework`
+//! in dx.toml overrides the detection.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::dev_services::{DatabaseEngine, DockerComposeConfig};
+use crate::error::{DxResult, IoContext};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Framework {
+    Rails,
+    Laravel,
+    SpringBoot,
+    Django,
+}
+
+impl Framework {
+    pub fn name(self) -> &'static str {
+        match self {
+            Framework::Rails => "Rails",
+            Framework::Laravel => "Laravel",
+            Framework::SpringBoot => "Spring Boot",
+            Framework::Django => "Django",
+        }
+    }
+
+    /// Framework named in dx.toml (`framework = "Spring Boot"`).
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', '_'], " ").as_str() {
+            "rails" | "ruby on rails" => Some(Framework::Rails),
+            "laravel" => Some(Framework::Laravel),
+            "spring" | "spring boot" => Some(Framework::SpringBoot),
+            "django" => Some(Framework::Django),
+            _ => None,
+        }
+    }
+
+    /// Settings file the connection is written to, relative to the project.
+    fn env_file(self) -> &'static str {
+        match self {
+            Framework::Rails => ".env.development",
+            Framework::Laravel | Framework::Django => ".env",
+            Framework::SpringBoot => "src/main/resources/application-dev.properties",
+        }
+    }
+}
+
+/// Database defaults of the framework detected in a project.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Preset {
+    pub framework: Framework,
+    /// Database created by the container
+    pub database: String,
+    /// Database user; `None` keeps the image's default
+    pub user: Option<String>,
+    /// Password the app already uses; `None` keeps the dx default
+    pub password: Option<String>,
+    /// Settings file written on save, relative to the project
+    pub env_file: String,
+}
+
+fn read(dir: &Path, file: &str) -> String {
+    fs::read_to_string(dir.join(file)).unwrap_or_default()
+}
+
+/// Database identifier for a name (`MyShop` -> `my_shop`, `order-api` -> `order_api`).
+fn identifier(name: &str) -> String {
+    let mut id = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() && prev_lower {
+            id.push('_');
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        id.push(if c.is_ascii_alphanumeric() {
+            c.to_ascii_lowercase()
+        } else {
+            '_'
+        });
+    }
+    id.trim_matches('_').to_string()
+}
+
+fn dir_name(dir: &Path) -> String {
+    dir.canonicalize()
+        .ok()
+        .and_then(|d| d.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "app".into())
+}
+
+/// Value of `KEY=value` in a dotenv file.
+pub fn dotenv_value(content: &str, key: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let (k, v) = line.trim().split_once('=')?;
+        (k.trim() == key).then(|| v.trim().trim_matches(['"', '\'']).to_string())
+    })
+}
+
+fn detect_framework(dir: &Path) -> Option<Framework> {
+    let has = |f: &str| dir.join(f).exists();
+    if let Some(name) = crate::settings::get(dir).project.framework {
+        return Framework::from_name(&name);
+    }
+    if has("config/application.rb") && read(dir, "Gemfile").contains("rails") {
+        Some(Framework::Rails)
+    } else if has("artisan") && read(dir, "composer.json").contains("laravel/framework") {
+        Some(Framework::Laravel)
+    } else if ["pom.xml", "build.gradle", "build.gradle.kts"]
+        .iter()
+        .any(|f| read(dir, f).contains("org.springframework.boot"))
+    {
+        Some(Framework::SpringBoot)
+    } else if has("manage.py") {
+        Some(Framework::Django)
+    } else {
+        None
+    }
+}
+
+/// Rails app name: the module of `config/application.rb`.
+fn rails_app(dir: &Path) -> Option<String> {
+    read(dir, "config/application.rb").lines().find_map(|l| {
+        let name = l.trim().strip_prefix("module ")?;
+        Some(identifier(name.trim()))
+    })
+}
+
+/// Django project: the package of `DJANGO_SETTINGS_MODULE` in manage.py.
+fn django_project(dir: &Path) -> Option<String> {
+    let manage = read(dir, "manage.py");
+    let at = manage.find("DJANGO_SETTINGS_MODULE")?;
+    let module = manage[at..].split(['"', '\'']).nth(2)?;
+    Some(identifier(module.split('.').next()?))
+}
+
+/// Spring Boot app: `spring.application.name`, the Maven artifactId or the
+/// Gradle root project name.
+fn spring_app(dir: &Path) -> Option<String> {
+    let properties = read(dir, "src/main/resources/application.properties");
+    let from_properties = properties.lines().find_map(|l| {
+        let (k, v) = l.split_once('=')?;
+        (k.trim() == "spring.application.name").then(|| v.trim().to_string())
+    });
+    let from_pom = || {
+        let pom = read(dir, "pom.xml");
+        // Skip the <parent> block, whose artifactId is spring-boot-starter-parent
+        let body = pom.split("</parent>").last()?;
+        let start = body.find("<artifactId>")? + "<artifactId>".len();
+        let end = body[start..].find("</artifactId>")? + start;
+        Some(body[start..end].trim().to_string())
+    };
+    let from_gradle = || {
+        ["settings.gradle", "settings.gradle.kts"]
+            .iter()
+            .flat_map(|f| read(dir, f).lines().map(str::to_string).collect::<Vec<_>>())
+            .find_map(|l| {
+                let (k, v) = l.split_once('=')?;
+                (k.trim() == "rootProject.name")
+                    .then(|| v.trim().trim_matches(['"', '\'']).to_string())
+            })
+    };
+    from_properties
+        .or_else(from_pom)
+        .or_else(from_gradle)
+        .filter(|n| !n.is_empty())
+        .map(|n| identifier(&n))
+}
+
+/// Preset of the framework used by the project at `dir`, if any.
+pub fn detect(dir: &Path) -> Option<Preset> {
+    let framework = detect_framework(dir)?;
+    let fallback = || identifier(&dir_name(dir));
+    let (database, user, password) = match framework {
+        Framework::Rails => (
+            format!("{}_development", rails_app(dir).unwrap_or_else(fallback)),
+            None,
+            None,
+        ),
+        Framework::Laravel => {
+            // The app's .env (or the .env.example it is copied from)
+            let env = [".env", ".env.example"]
+                .iter()
+                .map(|f| read(dir, f))
+                .find(|c| !c.is_empty())
+                .unwrap_or_default();
+            let value = |key: &str| dotenv_value(&env, key).filter(|v| !v.is_empty());
+            (
+                value("DB_DATABASE").unwrap_or_else(|| "laravel".into()),
+                Some(value("DB_USERNAME").unwrap_or_else(|| "root".into())),
+                value("DB_PASSWORD"),
+            )
+        }
+        Framework::SpringBoot => (spring_app(dir).unwrap_or_else(fallback), None, None),
+        Framework::Django => (django_project(dir).unwrap_or_else(fallback), None, None),
+    };
+    Some(Preset {
+        framework,
+        database,
+        user,
+        password,
+        env_file: framework.env_file().to_string(),
+    })
+}
+
+impl Preset {
+    /// Set the database name, user and password of the database services.
+    pub fn apply(&self, config: &mut DockerComposeConfig) {
+        for service in config.manifest_services() {
+            let Some(db) = service.database() else {
+                continue;
+            };
+            let Some(svc) = config.services.get_mut(&service.name) else {
+                continue;
+            };
+            let mut set = |key: &str, value: &str| {
+                svc.env.insert(key.to_string(), value.to_string());
+            };
+            match db.engine {
+                DatabaseEngine::Postgres => {
+                    set("POSTGRES_DB", &self.database);
+                    if let Some(user) = &self.user {
+                        set("POSTGRES_USER", user);
+                    }
+                    if let Some(password) = &self.password {
+                        set("POSTGRES_PASSWORD", password);
+                    }
+                }
+                DatabaseEngine::Mysql => {
+                    set("MARIADB_DATABASE", &self.database);
+                    match (&self.user, &self.password) {
+                        (Some(user), password) if user != "root" => {
+                            set("MARIADB_USER", user);
+                            set(
+                                "MARIADB_PASSWORD",
+                                password.as_deref().unwrap_or(&db.password),
+                            );
+                        }
+                        (_, Some(password)) => set("MARIADB_ROOT_PASSWORD", password),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// Settings the framework reads, for the services of `config`.
+    fn settings(&self, config: &DockerComposeConfig) -> Vec<(String, String)> {
+        let services = config.manifest_services();
+        let db = services.iter().find_map(|s| s.database());
+        let redis = services
+            .iter()
+            .find(|s| s.name == "redis")
+            .map(|s| s.host_port(6379));
+        let mut settings = Vec::new();
+        let mut set = |key: &str, value: String| settings.push((key.to_string(), value));
+        match self.framework {
+            Framework::Rails | Framework::Django => {
+                if let Some(db) = &db {
+                    let url = match (self.framework, db.engine) {
+                        (Framework::Rails, DatabaseEngine::Mysql) => db.url_with_scheme("mysql2"),
+                        _ => db.url(),
+                    };
+                    set("DATABASE_URL", url);
+                }
+                if let Some(port) = redis {
+                    set("REDIS_URL", format!("redis://localhost:{port}"));
+                }
+            }
+            Framework::Laravel => {
+                if let Some(db) = &db {
+                    let connection = match db.engine {
+                        DatabaseEngine::Postgres => "pgsql",
+                        DatabaseEngine::Mysql => "mysql",
+                    };
+                    set("DB_CONNECTION", connection.into());
+                    set("DB_HOST", "127.0.0.1".into());
+                    set("DB_PORT", db.port.to_string());
+                    set("DB_DATABASE", db.name.clone());
+                    set("DB_USERNAME", db.user.clone());
+                    set("DB_PASSWORD", db.password.clone());
+                }
+                if let Some(port) = redis {
+                    set("REDIS_HOST", "127.0.0.1".into());
+                    set("REDIS_PORT", port.to_string());
+                }
+            }
+            Framework::SpringBoot => {
+                if let Some(db) = &db {
+                    set("spring.datasource.url", db.jdbc_url());
+                    set("spring.datasource.username", db.user.clone());
+                    set("spring.datasource.password", db.password.clone());
+                }
+                if let Some(port) = redis {
+                    set("spring.data.redis.host", "localhost".into());
+                    set("spring.data.redis.port", port.to_string());
+                }
+            }
+        }
+        settings
+    }
+
+    /// Write the connection settings for `config` into the framework's file.
+    /// Returns the file when it changed.
+    pub fn write_env_file(
+        &self,
+        project_dir: &Path,
+        config: &DockerComposeConfig,
+    ) -> DxResult<Option<PathBuf>> {
+        let settings = self.settings(config);
+        if settings.is_empty() {
+            return Ok(None);
+        }
+        let path = project_dir.join(&self.env_file);
+        let mut existing = fs::read_to_string(&path).ok();
+        // Laravel's .env starts as a copy of .env.example
+        if existing.is_none() && self.framework == Framework::Laravel {
+            existing = fs::read_to_string(project_dir.join(".env.example")).ok();
+        }
+        let existing = existing.unwrap_or_default();
+        let header = format!(
+            "# Dev Services (dx dev-services, preset {})",
+            self.framework.name()
+        );
+        let content = upsert(&existing, &settings, &header);
+        if fs::read_to_string(&path).is_ok_and(|c| c == content) {
+            return Ok(None);
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context(format!("Erro ao criar {}", parent.display()))?;
+        }
+        fs::write(&path, content).context(format!("Erro ao escrever {}", path.display()))?;
+        Ok(Some(path))
+    }
+}
+
+/// Key of a `KEY=value` / `key: value` line, also when commented out.
+fn line_key(line: &str) -> Option<(&str, bool)> {
+    let trimmed = line.trim_start();
+    let (commented, text) = match trimmed.strip_prefix('#') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, trimmed),
+    };
+    let end = text.find(['=', ':'])?;
+    let key = text[..end].trim();
+    (!key.is_empty() && !key.contains(' ')).then_some((key, commented))
+}
+
+/// `content` with each of `settings` set: active lines are replaced, otherwise
+/// the first commented-out one is, otherwise the setting is appended below
+/// `header`.
+pub fn upsert(content: &str, settings: &[(String, String)], header: &str) -> String {
+    let active = |key: &str| {
+        content
+            .lines()
+            .any(|l| line_key(l).is_some_and(|(k, commented)| k == key && !commented))
+    };
+    let mut done: Vec<&str> = Vec::new();
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let setting = line_key(line).and_then(|(key, commented)| {
+            settings.iter().find(|(k, _)| {
+                k == key && (!commented || !active(k) && !done.contains(&k.as_str()))
+            })
+        });
+        match setting {
+            Some((key, value)) => {
+                lines.push(format!("{key}={value}"));
+                done.push(key);
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+    let missing: Vec<_> = settings
+        .iter()
+        .filter(|(k, _)| !done.contains(&k.as_str()))
+        .collect();
+    if !missing.is_empty() {
+        if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push(header.to_string());
+        lines.extend(missing.iter().map(|(k, v)| format!("{k}={v}")));
+    }
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
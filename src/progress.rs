@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Spinners for long operations and per-phase timings (`--timings`).
+//!
+//! Spinners are drawn on stderr only when it is a terminal and never in
+//! `--quiet`, `--output json` or `--ci` runs. Timings are always collected;
+//! `--timings` prints the summary (or adds it to the JSON envelope).
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+static START: OnceLock<Instant> = OnceLock::new();
+static SPINNERS: AtomicBool = AtomicBool::new(false);
+static SHOW_TIMINGS: AtomicBool = AtomicBool::new(false);
+static CURRENT: Mutex<Option<ProgressBar>> = Mutex::new(None);
+/// (label, total time, runs), in first-seen order.
+static TIMINGS: Mutex<Vec<(String, Duration, u32)>> = Mutex::new(Vec::new());
+
+/// Call after `output::init` and `ci::init`.
+pub fn init(quiet: bool, timings: bool) {
+    START.get_or_init(Instant::now);
+    let interactive = std::io::stderr().is_terminal();
+    SPINNERS.store(
+        interactive && !quiet && !crate::output::is_json() && !crate::ci::is_ci(),
+        Ordering::Relaxed,
+    );
+    SHOW_TIMINGS.store(timings && !quiet, Ordering::Relaxed);
+}
+
+fn record(label: &str, elapsed: Duration) {
+    let mut timings = TIMINGS.lock().unwrap();
+    match timings.iter_mut().find(|(l, _, _)| l == label) {
+        Some((_, total, runs)) => {
+            *total += elapsed;
+            *runs += 1;
+        }
+        None => timings.push((label.to_string(), elapsed, 1)),
+    }
+}
+
+/// Time `f` under `label` without drawing anything (for phases that stream
+/// their own output, like `docker compose up`).
+pub fn timed<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    record(label, start.elapsed());
+    result
+}
+
+/// Run `f` behind a spinner labelled `label` and record its duration. A
+/// phase started inside another one only updates the outer spinner's message.
+pub fn phase<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let spinner = SPINNERS.load(Ordering::Relaxed).then(|| {
+        let mut current = CURRENT.lock().unwrap();
+        match current.as_ref() {
+            Some(outer) => {
+                outer.set_message(label.to_string());
+                None
+            }
+            None => {
+                let pb = ProgressBar::new_spinner();
+                pb.set_style(
+                    ProgressStyle::with_template("{spinner} {prefix} {wide_msg:.dim} {elapsed}")
+                        .unwrap(),
+                );
+                pb.set_prefix(label.to_string());
+                pb.enable_steady_tick(Duration::from_millis(100));
+                *current = Some(pb.clone());
+                Some(pb)
+            }
+        }
+    });
+
+    let result = timed(label, f);
+
+    if let Some(pb) = spinner.flatten() {
+        pb.finish_and_clear();
+        *CURRENT.lock().unwrap() = None;
+    }
+    result
+}
+
+/// Show what the current phase is working on (e.g. the package being looked up).
+pub fn detail(message: impl Display) {
+    if let Some(pb) = CURRENT.lock().unwrap().as_ref() {
+        pb.set_message(message.to_string());
+    }
+}
+
+pub fn format_duration(d: Duration) -> String {
+    if d.as_secs() >= 1 {
+        format!("{:.2} s", d.as_secs_f64())
+    } else {
+        format!("{} ms", d.as_millis())
+    }
+}
+
+/// Timing summary for `--timings`: printed to stderr in text mode, returned
+/// for the JSON envelope otherwise. `None` when `--timings` wasn't given.
+pub fn summary() -> Option<serde_json::Value> {
+    if !SHOW_TIMINGS.load(Ordering::Relaxed) {
+        return None;
+    }
+    let total = START.get().map(Instant::elapsed).unwrap_or_default();
+    let timings = TIMINGS.lock().unwrap();
+    if !crate::output::is_json() {
+        let width = timings
+            .iter()
+            .map(|(l, _, _)| l.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(5);
+        eprintln!("\nTempos:");
+        for (label, elapsed, runs) in timings.iter() {
+            let runs = if *runs > 1 {
+                format!(" ({runs}x)")
+            } else {
+                String::new()
+            };
+            eprintln!("  {label:<width$}  {:>9}{runs}", format_duration(*elapsed));
+        }
+        eprintln!("  {:<width$}  {:>9}", "Total", format_duration(total));
+    }
+    Some(serde_json::json!({
+        "total_ms": total.as_millis() as u64,
+        "phases": timings
+            .iter()
+            .map(|(label, elapsed, runs)| serde_json::json!({
+                "phase": label,
+                "ms": elapsed.as_millis() as u64,
+                "runs": runs,
+            }))
+            .collect::<Vec<_>>(),
+    }))
+}
@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Reverse proxy giving each app and web UI of a multi-service project a
+//! `*.localhost` hostname.
+//!
+//! A project with several apps (a root project plus subprojects, or a
+//! `frontend/` and `backend/` layout) gets `proxy`: Traefik on port 80, so
+//! `http://frontend.localhost` and `http://backend.localhost` replace port
+//! numbers (browsers resolve `*.localhost` to the loopback without any DNS
+//! setup). The apps run on the host: their routes go to
+//! `host.docker.internal:<port>` through a file provider
+//! (`.dx/proxy/apps.yml`), the port coming from the app's `PORT` (`.env`),
+//! Spring's `server.port`, its frontend framework or the stack's usual port.
+//! Dev Services with a web UI (Kafka UI, MinIO console, Mailpit, Grafana...)
+//! are routed by Traefik labels as `<service>.localhost`, and Traefik's own
+//! dashboard is `traefik.localhost`. The analyzer report lists the URLs.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::detectors::Detector;
+use crate::dev_services::{DockerComposeConfig, DockerService};
+use crate::i18n::DocLanguage;
+
+pub const SERVICE: &str = "proxy";
+/// Entry point of the apps and UIs.
+pub const PORT: u16 = 80;
+/// Traefik's dashboard and API.
+pub const DASHBOARD_PORT: u16 = 8090;
+/// Dynamic configuration with the routes to the apps, relative to `.dx`.
+pub const ROUTES_FILE: &str = "proxy/apps.yml";
+
+/// Name of the root project among the apps.
+const ROOT_APP: &str = "app";
+/// Depth searched for the apps of a workspace.
+const MAX_DEPTH: usize = 2;
+
+/// An app of the project, run on the host.
+#[derive(Debug)]
+pub struct App {
+    /// Hostname label (`<name>.localhost`)
+    pub name: String,
+    pub dir: PathBuf,
+    pub port: u16,
+}
+
+/// Hostname label for a directory name: lowercase letters, digits and `-`.
+fn label(name: &str) -> String {
+    let label: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let label = label.trim_matches('-').to_string();
+    if label.is_empty() {
+        ROOT_APP.into()
+    } else {
+        label
+    }
+}
+
+/// Dev server port of a frontend framework in `dir`'s `package.json`.
+fn frontend_port(dir: &Path) -> Option<u16> {
+    let package = std::fs::read_to_string(dir.join("package.json")).ok()?;
+    let has = |dependency: &str| package.contains(&format!("\"{dependency}\""));
+    if has("@angular/core") {
+        Some(4200)
+    } else if has("astro") {
+        Some(4321)
+    } else if has("vite") {
+        Some(5173)
+    } else if has("next") || has("nuxt") || has("react-scripts") || has("@remix-run/dev") {
+        Some(3000)
+    } else {
+        None
+    }
+}
+
+/// Port the app in `dir` listens on.
+fn app_port(dir: &Path) -> u16 {
+    let configured = std::fs::read_to_string(dir.join(".env"))
+        .ok()
+        .and_then(|env| crate::presets::dotenv_value(&env, "PORT"))
+        .and_then(|port| port.parse().ok());
+    configured
+        .or_else(|| frontend_port(dir))
+        .unwrap_or_else(|| crate::graphql::app_port(dir))
+}
+
+/// Apps of `project_dir`: the project itself, if it is one, and its
+/// subprojects. Fewer than two apps need no proxy, so none is returned.
+pub fn apps(project_dir: &Path) -> Vec<App> {
+    let mut dirs = Vec::new();
+    if crate::workspace::is_project_root(project_dir) {
+        dirs.push((ROOT_APP.to_string(), project_dir.to_path_buf()));
+    }
+    for dir in crate::workspace::subprojects(project_dir, MAX_DEPTH) {
+        let name = dir.file_name().map(|n| n.to_string_lossy().into_owned());
+        dirs.push((label(&name.unwrap_or_default()), dir));
+    }
+    if dirs.len() < 2 {
+        return Vec::new();
+    }
+    let mut apps: Vec<App> = Vec::new();
+    for (name, dir) in dirs {
+        // Same folder name in two places: keep both, the second numbered
+        let mut unique = name.clone();
+        let mut n = 2;
+        while apps.iter().any(|a| a.name == unique) {
+            unique = format!("{name}-{n}");
+            n += 1;
+        }
+        let port = app_port(&dir);
+        apps.push(App {
+            name: unique,
+            dir,
+            port,
+        });
+    }
+    apps
+}
+
+/// The file provider's routes to `apps`.
+pub fn routes(apps: &[App]) -> String {
+    let mut out = String::from("# Gerado pelo dx (dev-services); não edite.\nhttp:\n  routers:\n");
+    for app in apps {
+        out.push_str(&format!(
+            "    {0}:\n      rule: Host(`{0}.localhost`)\n      entryPoints: [web]\n      service: {0}\n",
+            app.name
+        ));
+    }
+    out.push_str("  services:\n");
+    for app in apps {
+        out.push_str(&format!(
+            "    {}:\n      loadBalancer:\n        servers:\n          - url: http://host.docker.internal:{}\n",
+            app.name, app.port
+        ));
+    }
+    out
+}
+
+/// Files mounted by the proxy of `project_dir`, relative to `.dx`.
+pub fn files(project_dir: &Path) -> Vec<(String, String)> {
+    vec![(ROUTES_FILE.to_string(), routes(&apps(project_dir)))]
+}
+
+fn traefik() -> DockerService {
+    let command = [
+        "--providers.docker=true".to_string(),
+        "--providers.docker.exposedbydefault=false".to_string(),
+        "--providers.file.filename=/etc/traefik/apps.yml".to_string(),
+        "--providers.file.watch=true".to_string(),
+        format!("--entrypoints.web.address=:{PORT}"),
+        format!("--entrypoints.traefik.address=:{DASHBOARD_PORT}"),
+        "--api.insecure=true".to_string(),
+    ];
+    let mut labels = BTreeMap::new();
+    labels.insert("traefik.enable".into(), "true".into());
+    labels.insert(
+        "traefik.http.routers.traefik.rule".into(),
+        "Host(`traefik.localhost`)".into(),
+    );
+    labels.insert(
+        "traefik.http.routers.traefik.entrypoints".into(),
+        "web".into(),
+    );
+    labels.insert(
+        "traefik.http.routers.traefik.service".into(),
+        "api@internal".into(),
+    );
+    DockerService {
+        image: "traefik:v3.1".into(),
+        ports: vec![PORT, DASHBOARD_PORT],
+        volumes: vec![
+            "/var/run/docker.sock:/var/run/docker.sock:ro".into(),
+            format!("./{ROUTES_FILE}:/etc/traefik/apps.yml:ro"),
+        ],
+        command: Some(command.join(" ")),
+        profiles: vec![SERVICE.into()],
+        labels,
+        extra_hosts: vec!["host.docker.internal:host-gateway".into()],
+        ..Default::default()
+    }
+}
+
+/// Add the proxy to `config` when `project_dir` has several apps, and route
+/// the web UIs of its services.
+pub fn apply(project_dir: &Path, config: &mut DockerComposeConfig) {
+    if apps(project_dir).is_empty() {
+        return;
+    }
+    config.add_service(SERVICE, traefik());
+    label_uis(config, &crate::detectors::catalog(project_dir));
+}
+
+/// Route the web UI of each service of `config` as `<service>.localhost`
+/// (when it has the proxy).
+pub fn label_uis(config: &mut DockerComposeConfig, detectors: &[Box<dyn Detector>]) {
+    if !config.services.contains_key(SERVICE) {
+        return;
+    }
+    for (name, service) in config.services.iter_mut() {
+        // Traefik routes its own dashboard
+        if name == SERVICE {
+            continue;
+        }
+        let Some(port) = crate::port_docs::ui_port(detectors, name) else {
+            continue;
+        };
+        let router = format!("traefik.http.routers.{name}");
+        service
+            .labels
+            .insert("traefik.enable".into(), "true".into());
+        service.labels.insert(
+            format!("{router}.rule"),
+            format!("Host(`{name}.localhost`)"),
+        );
+        service
+            .labels
+            .insert(format!("{router}.entrypoints"), "web".into());
+        service.labels.insert(
+            format!("traefik.http.services.{name}.loadbalancer.server.port"),
+            port.to_string(),
+        );
+    }
+}
+
+/// Report info of the proxy service.
+pub fn info(service: &str) -> Option<String> {
+    (service == SERVICE).then(|| {
+        format!(
+            "apps e UIs em http://<nome>.localhost; painel: http://traefik.localhost (ou http://localhost:{DASHBOARD_PORT})"
+        )
+    })
+}
+
+/// Analyzer report section with the proxy's URLs.
+pub fn urls(project_dir: &Path, config: &DockerComposeConfig, lang: DocLanguage) -> String {
+    if !config.services.contains_key(SERVICE) {
+        return String::new();
+    }
+    let t = |pt, en, es| lang.pick(pt, en, es);
+    let mut out = format!(
+        "\n### {}\n\n",
+        t(
+            "URLs pelo proxy (Traefik)",
+            "URLs through the proxy (Traefik)",
+            "URLs por el proxy (Traefik)"
+        )
+    );
+    out.push_str(t(
+        "| URL | Destino |\n",
+        "| URL | Target |\n",
+        "| URL | Destino |\n",
+    ));
+    out.push_str("|-----|---------|\n");
+    for app in apps(project_dir) {
+        let dir = app.dir.strip_prefix(project_dir).unwrap_or(&app.dir);
+        let dir = if dir.as_os_str().is_empty() {
+            ".".to_string()
+        } else {
+            dir.display().to_string().replace('\\', "/")
+        };
+        out.push_str(&format!(
+            "| http://{}.localhost | {} `{dir}` (localhost:{}) |\n",
+            app.name,
+            t("app em", "app in", "app en"),
+            app.port
+        ));
+    }
+    for name in config.service_names() {
+        let service = &config.services[&name];
+        let rule = format!("traefik.http.routers.{name}.rule");
+        let port = format!("traefik.http.services.{name}.loadbalancer.server.port");
+        if service.labels.contains_key(&rule)
+            && let Some(port) = service.labels.get(&port)
+        {
+            out.push_str(&match lang {
+                DocLanguage::Pt => {
+                    format!("| http://{name}.localhost | serviço `{name}` (porta {port}) |\n")
+                }
+                DocLanguage::En => {
+                    format!("| http://{name}.localhost | service `{name}` (port {port}) |\n")
+                }
+                DocLanguage::Es => {
+                    format!("| http://{name}.localhost | servicio `{name}` (puerto {port}) |\n")
+                }
+            });
+        }
+    }
+    out.push_str(t(
+        "| http://traefik.localhost | painel do Traefik |\n",
+        "| http://traefik.localhost | Traefik dashboard |\n",
+        "| http://traefik.localhost | panel de Traefik |\n",
+    ));
+    out.push_str(&match lang {
+        DocLanguage::Pt => format!(
+            "\nAs apps rodam no host; o Traefik as alcança por `host.docker.internal`. As rotas ficam em `.dx/{ROUTES_FILE}`.\n"
+        ),
+        DocLanguage::En => format!(
+            "\nThe apps run on the host; Traefik reaches them through `host.docker.internal`. The routes are in `.dx/{ROUTES_FILE}`.\n"
+        ),
+        DocLanguage::Es => format!(
+            "\nLas apps se ejecutan en el host; Traefik las alcanza por `host.docker.internal`. Las rutas están en `.dx/{ROUTES_FILE}`.\n"
+        ),
+    });
+    out
+}
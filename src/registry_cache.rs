@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Latest versions answered by the package registries
+//! (`~/.cache/dx/registry/latest-versions.json`).
+//!
+//! Each lookup URL (mirrors included) maps to the version it answered and
+//! when. Within the TTL of the `registry` cache category, the analyzer reuses
+//! the stored versions instead of querying the registries again, in this
+//! project or any other. Failed lookups are not stored. `dx cache clear
+//! --category registry` empties the cache.
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+const CATEGORY: &str = "registry";
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Entry {
+    version: String,
+    /// Unix time of the lookup.
+    checked_at: u64,
+}
+
+type Entries = BTreeMap<String, Entry>;
+
+/// Entries read on the first lookup of the run.
+static ENTRIES: Mutex<Option<Entries>> = Mutex::new(None);
+
+fn path() -> PathBuf {
+    crate::cache::category_dir(CATEGORY).join("latest-versions.json")
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn fresh(entry: &Entry) -> bool {
+    now().saturating_sub(entry.checked_at) < crate::cache::ttl_for(CATEGORY)
+}
+
+fn read() -> Entries {
+    std::fs::read_to_string(path())
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Version stored for `url`, unless it expired.
+pub fn get(url: &str) -> Option<String> {
+    let mut entries = ENTRIES.lock().unwrap();
+    let entry = entries
+        .get_or_insert_with(read)
+        .get(url)
+        .filter(|e| fresh(e))?;
+    tracing::debug!(url, version = %entry.version, "última versão em cache");
+    Some(entry.version.clone())
+}
+
+/// Store the version `url` answered. The file is read again first, so runs
+/// in parallel (`dx all`) keep each other's entries; expired ones are dropped.
+pub fn put(url: &str, version: &str) {
+    let mut entries = ENTRIES.lock().unwrap();
+    let mut stored = read();
+    stored.retain(|_, e| fresh(e));
+    stored.insert(
+        url.to_string(),
+        Entry {
+            version: version.to_string(),
+            checked_at: now(),
+        },
+    );
+    let path = path();
+    let tmp = path.with_extension(format!("json.{}", std::process::id()));
+    let written = std::fs::create_dir_all(crate::cache::category_dir(CATEGORY))
+        .and_then(|_| {
+            std::fs::write(
+                &tmp,
+                serde_json::to_string_pretty(&stored).unwrap_or_default(),
+            )
+        })
+        .and_then(|_| std::fs::rename(&tmp, &path));
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&tmp);
+        tracing::debug!(path = %path.display(), "não foi possível gravar o cache de registries: {e}");
+    }
+    *entries = Some(stored);
+}
@@ -0,0 +1,468 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Packaging metadata for distribution (`dx release package`).
+//!
+//! Reads the crate's `Cargo.toml` and the release artifacts (archives or bare
+//! binaries named with their OS and architecture, e.g.
+//! `dx-linux-x86_64.tar.gz`, `dx-windows-x86_64.exe`) and writes, under
+//! `packaging/`:
+//!
+//! - `homebrew/<crate>.rb`: formula with the macOS and Linux artifacts;
+//! - `scoop/<crate>.json`: manifest with the Windows artifacts;
+//! - `nfpm/<crate>-<arch>.yaml`: [nFPM](https://nfpm.goreleaser.com) config
+//!   that builds the `.deb` and `.rpm` from the release binary of each Linux
+//!   architecture.
+//!
+//! URLs point to `--base-url` (by default the GitHub release of the version,
+//! from `package.repository`) and checksums are computed from the files, so
+//! the definitions are reproducible from the repository.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::error::{DxError, DxResult, IoContext};
+use crate::managed::{self, Markers};
+
+const MARKERS: Markers = Markers {
+    start: "# dx-cli:release:start",
+    end: "# dx-cli:release:end",
+};
+
+const NOTE: &str =
+    "Gerado por `dx release package`; alterações entre os marcadores são sobrescritas.";
+
+/// Scoop's comment key, also how a dx-generated manifest is recognized.
+const SCOOP_NOTE: &str = "Gerado por `dx release package`; o arquivo é sobrescrito.";
+
+#[derive(Debug, Deserialize)]
+struct CargoManifest {
+    package: Option<Package>,
+    #[serde(default)]
+    bin: Vec<Bin>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    name: String,
+    version: String,
+    description: Option<String>,
+    license: Option<String>,
+    homepage: Option<String>,
+    repository: Option<String>,
+    #[serde(default)]
+    authors: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Bin {
+    name: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Os {
+    Linux,
+    Macos,
+    Windows,
+}
+
+impl Os {
+    fn name(self) -> &'static str {
+        match self {
+            Os::Linux => "linux",
+            Os::Macos => "macos",
+            Os::Windows => "windows",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Arch {
+    X86_64,
+    Aarch64,
+}
+
+impl Arch {
+    fn name(self) -> &'static str {
+        match self {
+            Arch::X86_64 => "x86_64",
+            Arch::Aarch64 => "aarch64",
+        }
+    }
+
+    /// Debian/nFPM name
+    fn deb(self) -> &'static str {
+        match self {
+            Arch::X86_64 => "amd64",
+            Arch::Aarch64 => "arm64",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Artifact {
+    file: String,
+    os: Os,
+    arch: Arch,
+    /// tar.gz/zip, as opposed to a bare binary
+    archive: bool,
+    sha256: String,
+    url: String,
+}
+
+/// OS, architecture and whether it's an archive, from the artifact's file name.
+fn classify(file: &str) -> Option<(Os, Arch, bool)> {
+    let lower = file.to_ascii_lowercase();
+    if [".sha256", ".sig", ".asc", ".txt", ".json", ".deb", ".rpm"]
+        .iter()
+        .any(|ext| lower.ends_with(ext))
+    {
+        return None;
+    }
+    let has = |words: &[&str]| words.iter().any(|w| lower.contains(w));
+    let os = if has(&["windows", "win64"]) || lower.ends_with(".exe") {
+        Os::Windows
+    } else if has(&["darwin", "macos", "apple", "osx"]) {
+        Os::Macos
+    } else if has(&["linux"]) {
+        Os::Linux
+    } else {
+        return None;
+    };
+    let arch = if has(&["aarch64", "arm64"]) {
+        Arch::Aarch64
+    } else if has(&["x86_64", "amd64", "x64"]) {
+        Arch::X86_64
+    } else {
+        return None;
+    };
+    let archive = [".tar.gz", ".tgz", ".tar.xz", ".zip"]
+        .iter()
+        .any(|ext| lower.ends_with(ext));
+    Some((os, arch, archive))
+}
+
+fn artifacts(dir: &Path, base_url: &str) -> DxResult<Vec<Artifact>> {
+    let entries = fs::read_dir(dir).map_err(|_| {
+        DxError::NotFound(format!(
+            "Diretório de artefatos não encontrado: {}\nDica: gere os binários de release (ex.: cargo build --release) e informe a pasta com --artifacts",
+            dir.display()
+        ))
+    })?;
+    let mut found = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(file) = path.file_name().and_then(|f| f.to_str()) else {
+            continue;
+        };
+        if !path.is_file() {
+            continue;
+        }
+        let Some((os, arch, archive)) = classify(file) else {
+            tracing::debug!("artefato ignorado (SO/arquitetura não reconhecidos): {file}");
+            continue;
+        };
+        found.push(Artifact {
+            file: file.to_string(),
+            os,
+            arch,
+            archive,
+            sha256: crate::sha256::file(&path)
+                .context(format!("Erro ao ler {}", path.display()))?,
+            url: format!("{}/{file}", base_url.trim_end_matches('/')),
+        });
+    }
+    found.sort_by(|a, b| a.file.cmp(&b.file));
+    Ok(found)
+}
+
+/// Quoted string literal, valid in Ruby and YAML.
+fn quoted(s: &str) -> String {
+    serde_json::to_string(s)
+        .unwrap_or_default()
+        .replace("#{", "\\#{")
+}
+
+/// `dx-cli` -> `DxCli`
+fn class_name(name: &str) -> String {
+    name.split(['-', '_'])
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or(String::new(), |c| {
+                c.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+fn homebrew(pkg: &Package, bin: &str, artifacts: &[&Artifact]) -> String {
+    let mut body = format!("class {} < Formula\n", class_name(&pkg.name));
+    if let Some(desc) = &pkg.description {
+        body.push_str(&format!("  desc {}\n", quoted(desc.trim())));
+    }
+    if let Some(homepage) = pkg.homepage.as_ref().or(pkg.repository.as_ref()) {
+        body.push_str(&format!("  homepage {}\n", quoted(homepage)));
+    }
+    body.push_str(&format!("  version {}\n", quoted(&pkg.version)));
+    if let Some(license) = &pkg.license {
+        let any: Vec<String> = license.split(" OR ").map(|l| quoted(l.trim())).collect();
+        if any.len() > 1 {
+            body.push_str(&format!("  license any_of: [{}]\n", any.join(", ")));
+        } else {
+            body.push_str(&format!("  license {}\n", quoted(license)));
+        }
+    }
+    for (os, block) in [(Os::Macos, "on_macos"), (Os::Linux, "on_linux")] {
+        let for_os: Vec<&&Artifact> = artifacts.iter().filter(|a| a.os == os).collect();
+        if for_os.is_empty() {
+            continue;
+        }
+        body.push_str(&format!("\n  {block} do\n"));
+        for a in for_os {
+            let cpu = match a.arch {
+                Arch::Aarch64 => "on_arm",
+                Arch::X86_64 => "on_intel",
+            };
+            body.push_str(&format!(
+                "    {cpu} do\n      url {}\n      sha256 {}\n    end\n",
+                quoted(&a.url),
+                quoted(&a.sha256)
+            ));
+        }
+        body.push_str("  end\n");
+    }
+    // A bare binary is staged under its download name
+    let install = if artifacts.iter().all(|a| a.archive) {
+        format!("bin.install {}", quoted(bin))
+    } else {
+        format!("bin.install Dir[\"*\"].first => {}", quoted(bin))
+    };
+    body.push_str(&format!(
+        "\n  def install\n    {install}\n  end\n\n  test do\n    system \"#{{bin}}/{bin}\", \"--version\"\n  end\nend\n"
+    ));
+    body
+}
+
+fn scoop(pkg: &Package, bin: &str, artifacts: &[&Artifact]) -> String {
+    let mut architecture = serde_json::Map::new();
+    for a in artifacts {
+        let key = match a.arch {
+            Arch::X86_64 => "64bit",
+            Arch::Aarch64 => "arm64",
+        };
+        let bin = if a.archive {
+            json!(format!("{bin}.exe"))
+        } else {
+            json!([[a.file, bin]])
+        };
+        architecture.insert(
+            key.to_string(),
+            json!({ "url": a.url, "hash": a.sha256, "bin": bin }),
+        );
+    }
+    let mut manifest = serde_json::Map::new();
+    manifest.insert("##".into(), json!(SCOOP_NOTE));
+    manifest.insert("version".into(), json!(pkg.version));
+    if let Some(desc) = &pkg.description {
+        manifest.insert("description".into(), json!(desc.trim()));
+    }
+    if let Some(homepage) = pkg.homepage.as_ref().or(pkg.repository.as_ref()) {
+        manifest.insert("homepage".into(), json!(homepage));
+    }
+    if let Some(license) = &pkg.license {
+        // Scoop writes SPDX alternatives as A|B
+        manifest.insert("license".into(), json!(license.replace(" OR ", "|")));
+    }
+    manifest.insert("architecture".into(), architecture.into());
+    serde_json::to_string_pretty(&manifest).unwrap_or_default() + "\n"
+}
+
+fn nfpm(pkg: &Package, bin: &str, arch: Arch) -> String {
+    let maintainer = pkg
+        .authors
+        .first()
+        .cloned()
+        .unwrap_or_else(|| format!("{} maintainers", pkg.name));
+    let mut body = format!(
+        "name: {}\narch: {}\nplatform: linux\nversion: {}\nsection: utils\npriority: optional\nmaintainer: {}\n",
+        quoted(&pkg.name),
+        arch.deb(),
+        quoted(&pkg.version),
+        quoted(&maintainer)
+    );
+    if let Some(desc) = &pkg.description {
+        body.push_str(&format!("description: {}\n", quoted(desc.trim())));
+    }
+    if let Some(homepage) = pkg.homepage.as_ref().or(pkg.repository.as_ref()) {
+        body.push_str(&format!("homepage: {}\n", quoted(homepage)));
+    }
+    if let Some(license) = &pkg.license {
+        body.push_str(&format!("license: {}\n", quoted(license)));
+    }
+    body.push_str(&format!(
+        "contents:\n  - src: {}\n    dst: {}\n    file_info:\n      mode: 0755\n",
+        quoted(&format!(
+            "target/{}-unknown-linux-gnu/release/{bin}",
+            arch.name()
+        )),
+        quoted(&format!("/usr/bin/{bin}"))
+    ));
+    body
+}
+
+/// Write a Scoop manifest unless a hand-written one is in the way.
+fn write_scoop(path: &Path, content: &str, opts: &PackageOptions) -> DxResult<bool> {
+    let existing = fs::read_to_string(path).ok();
+    let generated = existing.as_deref().is_none_or(|c| {
+        serde_json::from_str::<serde_json::Value>(c).is_ok_and(|v| v["##"] == SCOOP_NOTE)
+    });
+    if !generated && !opts.force && !opts.dry_run {
+        return Err(DxError::InvalidInput(format!(
+            "{} já existe e não foi gerado pelo dx; use --force para sobrescrever.",
+            path.display()
+        )));
+    }
+    if opts.dry_run {
+        outln!("\n{content}");
+        outln!("Execução em modo --dry-run: nenhum arquivo foi alterado.");
+        return Ok(false);
+    }
+    if existing.as_deref() == Some(content) {
+        outln!("\n{} já está atualizado.", path.display());
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context(format!("Erro ao criar {}", parent.display()))?;
+    }
+    fs::write(path, content).context(format!("Erro ao escrever {}", path.display()))?;
+    outln!("\nArquivo salvo: {}", path.display());
+    Ok(true)
+}
+
+pub struct PackageOptions {
+    /// Directory with the release artifacts (relative to the project)
+    pub artifacts: PathBuf,
+    /// Where the artifacts are downloaded from
+    pub base_url: Option<String>,
+    /// Output directory (relative to the project)
+    pub out: PathBuf,
+    /// Print the files instead of writing them
+    pub dry_run: bool,
+    /// Overwrite files that were not generated by dx
+    pub force: bool,
+}
+
+/// `dx release package`: generate the Homebrew, Scoop and deb/rpm definitions.
+pub fn package(dir: Option<PathBuf>, opts: PackageOptions) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !project_dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            project_dir.display()
+        )));
+    }
+    let cargo_toml = project_dir.join("Cargo.toml");
+    let content = fs::read_to_string(&cargo_toml).map_err(|_| {
+        DxError::NotFound(format!(
+            "Cargo.toml não encontrado em {}",
+            project_dir.display()
+        ))
+    })?;
+    let manifest: CargoManifest = toml_edit::de::from_str(&content)
+        .map_err(|e| DxError::InvalidInput(format!("{} inválido: {e}", cargo_toml.display())))?;
+    let Some(pkg) = manifest.package else {
+        return Err(DxError::InvalidInput(format!(
+            "{} não tem [package]; execute no diretório do crate a distribuir.",
+            cargo_toml.display()
+        )));
+    };
+    let bin = manifest.bin.first().map_or(pkg.name.as_str(), |b| &b.name);
+
+    let base_url = match (&opts.base_url, &pkg.repository) {
+        (Some(url), _) => url.clone(),
+        (None, Some(repo)) if repo.starts_with("https://github.com/") => format!(
+            "{}/releases/download/v{}",
+            repo.trim_end_matches('/').trim_end_matches(".git"),
+            pkg.version
+        ),
+        _ => {
+            return Err(DxError::InvalidInput(
+                "Informe --base-url ou defina package.repository (GitHub) no Cargo.toml para montar as URLs dos artefatos.".to_string(),
+            ));
+        }
+    };
+    let artifacts = artifacts(&project_dir.join(&opts.artifacts), &base_url)?;
+    if artifacts.is_empty() {
+        return Err(DxError::NotFound(format!(
+            "Nenhum artefato reconhecido em {} (os nomes devem indicar SO e arquitetura, ex.: {bin}-linux-x86_64.tar.gz).",
+            project_dir.join(&opts.artifacts).display()
+        )));
+    }
+
+    outln!("Pacote {} {} (binário: {bin})", pkg.name, pkg.version);
+    for a in &artifacts {
+        outln!("- {} ({} {})", a.file, a.os.name(), a.arch.name());
+    }
+
+    let out = project_dir.join(&opts.out);
+    let write_opts = managed::WriteOptions {
+        dry_run: opts.dry_run,
+        force: opts.force,
+    };
+    let mut files = Vec::new();
+    let unix: Vec<&Artifact> = artifacts.iter().filter(|a| a.os != Os::Windows).collect();
+    if !unix.is_empty() {
+        let path = out.join("homebrew").join(format!("{}.rb", pkg.name));
+        let block = MARKERS.wrap(NOTE, &homebrew(&pkg, bin, &unix));
+        files.push((
+            path.clone(),
+            managed::write(&path, &MARKERS, &block, &write_opts)?,
+        ));
+    }
+    let windows: Vec<&Artifact> = artifacts.iter().filter(|a| a.os == Os::Windows).collect();
+    if !windows.is_empty() {
+        let path = out.join("scoop").join(format!("{}.json", pkg.name));
+        let written = write_scoop(&path, &scoop(&pkg, bin, &windows), &opts)?;
+        files.push((path, written));
+    }
+    let mut linux: Vec<Arch> = artifacts
+        .iter()
+        .filter(|a| a.os == Os::Linux)
+        .map(|a| a.arch)
+        .collect();
+    linux.sort();
+    linux.dedup();
+    for arch in linux {
+        let path = out
+            .join("nfpm")
+            .join(format!("{}-{}.yaml", pkg.name, arch.deb()));
+        let block = MARKERS.wrap(NOTE, &nfpm(&pkg, bin, arch));
+        files.push((
+            path.clone(),
+            managed::write(&path, &MARKERS, &block, &write_opts)?,
+        ));
+    }
+
+    crate::output::set_data(json!({
+        "name": pkg.name,
+        "version": pkg.version,
+        "bin": bin,
+        "artifacts": artifacts
+            .iter()
+            .map(|a| json!({
+                "file": a.file,
+                "os": a.os.name(),
+                "arch": a.arch.name(),
+                "sha256": a.sha256,
+                "url": a.url,
+            }))
+            .collect::<Vec<_>>(),
+        "files": files
+            .iter()
+            .map(|(path, written)| json!({ "path": path.display().to_string(), "written": written }))
+            .collect::<Vec<_>>(),
+    }));
+    Ok(())
+}
@@ -0,0 +1,339 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Dev Services on a remote Docker engine (`dx dev-services run --context
+//! <context>` or `--host ssh://user@box`).
+//!
+//! Compose reaches the engine through `DOCKER_CONTEXT`/`DOCKER_HOST`. The
+//! choice is kept in `.dx/remote.json`, so `stop`, `restart`, `remove`, `logs`,
+//! `status` and `snapshot` talk to the same engine until a `run` without the
+//! flags goes back to the local one. Bind mounts would be resolved on the remote machine,
+//! where the project doesn't exist: in the manifest sent to it
+//! (`.dx/docker-compose.remote.yml`), files of the project become Compose
+//! `configs` with their content inline, and missing or empty folders (data
+//! folders) become named volumes. Paths outside the project are kept, as they
+//! name paths of the remote machine. Published ports are reached on the
+//! remote host, so the report links to it instead of localhost.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+use crate::compose::ComposeFile;
+use crate::containerized::normalize;
+use crate::error::{DxError, DxResult, IoContext};
+
+const STATE_FILE: &str = "remote.json";
+
+/// The Docker engine the Dev Services run on.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Engine {
+    /// A `docker context`
+    Context(String),
+    /// A `DOCKER_HOST` URL (`ssh://user@box`, `tcp://box:2376`)
+    Host(String),
+}
+
+impl Engine {
+    /// The engine chosen with `--context`/`--host`, if any.
+    pub fn from_flags(context: Option<String>, host: Option<String>) -> DxResult<Option<Engine>> {
+        match (context, host) {
+            (Some(context), _) => Ok(Some(Engine::Context(context))),
+            (None, Some(host)) if host.contains("://") => Ok(Some(Engine::Host(host))),
+            (None, Some(host)) => Err(DxError::InvalidInput(format!(
+                "--host espera a URL do Docker (ex.: ssh://usuario@maquina ou tcp://maquina:2376), recebido: '{host}'."
+            ))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Engine::Context(name) => format!("contexto Docker '{name}'"),
+            Engine::Host(url) => format!("host Docker {url}"),
+        }
+    }
+
+    /// `command` talking to this engine.
+    fn apply(&self, command: &mut Command) {
+        // DOCKER_HOST takes precedence over the context, so only one is set
+        match self {
+            Engine::Context(name) => command
+                .env("DOCKER_CONTEXT", name)
+                .env_remove("DOCKER_HOST"),
+            Engine::Host(url) => command.env("DOCKER_HOST", url).env_remove("DOCKER_CONTEXT"),
+        };
+    }
+
+    /// Host where the published ports are reached; `None` for a local engine.
+    fn address(&self) -> DxResult<Option<String>> {
+        let url = match self {
+            Engine::Host(url) => url.clone(),
+            Engine::Context(name) => {
+                let output = Command::new("docker")
+                    .args([
+                        "context",
+                        "inspect",
+                        "--format",
+                        "{{.Endpoints.docker.Host}}",
+                    ])
+                    .arg(name)
+                    .stdin(Stdio::null())
+                    .output()
+                    .map_err(|e| {
+                        DxError::Tool(format!("Erro ao executar 'docker context inspect': {e}"))
+                    })?;
+                if !output.status.success() {
+                    return Err(DxError::InvalidInput(format!(
+                        "Contexto Docker '{name}' não encontrado (veja 'docker context ls')."
+                    )));
+                }
+                String::from_utf8_lossy(&output.stdout).trim().to_string()
+            }
+        };
+        Ok(host_of(&url))
+    }
+}
+
+/// Host name of a Docker endpoint URL, or `None` for local sockets.
+fn host_of(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    if matches!(scheme, "unix" | "npipe") {
+        return None;
+    }
+    let authority = rest.split('/').next().unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match host.strip_prefix('[') {
+        // [::1]:2376
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    (!host.is_empty() && host != "localhost" && host != "127.0.0.1").then(|| host.to_string())
+}
+
+/// What `.dx/remote.json` keeps.
+#[derive(Debug, Serialize, Deserialize)]
+struct State {
+    engine: Engine,
+    /// Host of the published ports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+}
+
+fn state_path(project_dir: &Path) -> PathBuf {
+    project_dir.join(".dx").join(STATE_FILE)
+}
+
+fn load(project_dir: &Path) -> Option<State> {
+    let data = fs::read_to_string(state_path(project_dir)).ok()?;
+    serde_json::from_str(&data)
+        .inspect_err(|e| {
+            tracing::warn!(
+                "{} inválido, ignorado: {e}",
+                state_path(project_dir).display()
+            )
+        })
+        .ok()
+}
+
+/// Remember `engine` for the next commands (`None`: back to the local engine).
+pub fn save(project_dir: &Path, engine: Option<Engine>) -> DxResult {
+    let path = state_path(project_dir);
+    let Some(engine) = engine else {
+        if path.exists() {
+            fs::remove_file(&path).context(format!("Erro ao remover {}", path.display()))?;
+        }
+        return Ok(());
+    };
+    let state = State {
+        address: engine.address()?,
+        engine,
+    };
+    let data = serde_json::to_string_pretty(&state).expect("remote state serializes");
+    fs::write(&path, data + "\n").context(format!("Erro ao salvar {}", path.display()))
+}
+
+/// The engine of the last `run`, if remote.
+pub fn engine(project_dir: &Path) -> Option<Engine> {
+    load(project_dir).map(|s| s.engine)
+}
+
+/// Host of the published ports of the last `run`, if remote.
+pub fn address(project_dir: &Path) -> Option<String> {
+    load(project_dir).and_then(|s| s.address)
+}
+
+/// `program` (`docker`, `docker-compose`) talking to the engine of the last
+/// `run`.
+pub fn command(project_dir: &Path, program: &str) -> Command {
+    let mut command = Command::new(program);
+    if let Some(engine) = engine(project_dir) {
+        engine.apply(&mut command);
+    }
+    command
+}
+
+/// The manifest Compose runs with on the engine of the last `run`: the one
+/// written for the remote engine next to `compose_path`, or `compose_path`.
+pub fn manifest(project_dir: &Path, compose_path: &Path) -> PathBuf {
+    let remote = compose_path.with_file_name(crate::compose::REMOTE_FILE);
+    if engine(project_dir).is_some() && remote.is_file() {
+        remote
+    } else {
+        compose_path.to_path_buf()
+    }
+}
+
+/// Path in the project of the bind mount `source` of the manifest at
+/// `dx_dir`, or `None` for named volumes and paths outside the project.
+fn project_path(source: &str, dx_dir: &Path, project_dir: &Path) -> Option<PathBuf> {
+    let path = if source.starts_with("./") || source.starts_with("../") || source == "." {
+        dx_dir.join(source)
+    } else if source.starts_with('/') {
+        PathBuf::from(source)
+    } else {
+        return None;
+    };
+    let path = normalize(&path);
+    path.starts_with(normalize(project_dir)).then_some(path)
+}
+
+/// Files under `path` (itself if it is a file), in order.
+fn files(path: &Path) -> Vec<PathBuf> {
+    if path.is_file() {
+        return vec![path.to_path_buf()];
+    }
+    let mut files: Vec<PathBuf> = ignore::WalkBuilder::new(path)
+        .standard_filters(false)
+        .build()
+        .flatten()
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Name of a config or volume: the service and the container path.
+fn object_name(service: &str, target: &str) -> String {
+    let target: String = target
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{service}-{}", target.trim_matches('-'))
+}
+
+/// The manifest with the project's bind mounts replaced by configs and
+/// named volumes.
+pub fn translate(yaml: &str, project_dir: &Path) -> DxResult<String> {
+    let dx_dir = project_dir.join(".dx");
+    let mut file = ComposeFile::parse(yaml)
+        .map_err(|e| DxError::InvalidInput(format!("Manifesto inválido: {e}")))?;
+    let mut configs = Mapping::new();
+    let mut volumes = Vec::new();
+    for (name, service) in file.services.iter_mut() {
+        let mut service_configs = Vec::new();
+        for volume in std::mem::take(&mut service.volumes) {
+            let mut parts = volume.splitn(3, ':');
+            let (Some(source), Some(target), mode) = (parts.next(), parts.next(), parts.next())
+            else {
+                service.volumes.push(volume);
+                continue;
+            };
+            let Some(path) = project_path(source, &dx_dir, project_dir) else {
+                service.volumes.push(volume);
+                continue;
+            };
+            let found = files(&path);
+            if found.is_empty() {
+                // A data folder: it lives on the remote engine
+                let volume_name = object_name(name, target);
+                service.volumes.push(match mode {
+                    Some(mode) => format!("{volume_name}:{target}:{mode}"),
+                    None => format!("{volume_name}:{target}"),
+                });
+                volumes.push(volume_name);
+                continue;
+            }
+            for found in found {
+                let target = match found.strip_prefix(&path) {
+                    Ok(rest) if !rest.as_os_str().is_empty() => {
+                        format!(
+                            "{}/{}",
+                            target.trim_end_matches('/'),
+                            rest.to_string_lossy().replace('\\', "/")
+                        )
+                    }
+                    _ => target.to_string(),
+                };
+                let content = fs::read_to_string(&found).map_err(|e| {
+                    DxError::InvalidInput(format!(
+                        "{} não pode ir para o Docker remoto como config (só arquivos de texto): {e}",
+                        found.display()
+                    ))
+                })?;
+                let config_name = object_name(name, &target);
+                let mut config = Mapping::new();
+                config.insert("content".into(), content.into());
+                configs.insert(config_name.clone().into(), config.into());
+                let mut reference = Mapping::new();
+                reference.insert("source".into(), config_name.into());
+                reference.insert("target".into(), target.into());
+                service_configs.push(Value::Mapping(reference));
+            }
+        }
+        if !service_configs.is_empty() {
+            let entry = service
+                .extra
+                .entry("configs".to_string())
+                .or_insert_with(|| Value::Sequence(Vec::new()));
+            if let Value::Sequence(list) = entry {
+                list.extend(service_configs);
+            }
+        }
+    }
+    for volume in volumes {
+        file.volumes.entry(volume).or_insert(None);
+    }
+    if !configs.is_empty() {
+        let entry = file
+            .extra
+            .entry("configs".to_string())
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+        if let Value::Mapping(all) = entry {
+            all.extend(configs);
+        }
+    }
+    Ok(file.to_yaml())
+}
+
+/// Write the manifest for the remote engine next to `compose_path` and
+/// return its path.
+pub fn write_remote_compose(
+    project_dir: &Path,
+    compose_path: &Path,
+    engine: &Engine,
+) -> DxResult<PathBuf> {
+    let yaml = fs::read_to_string(compose_path)
+        .context(format!("Erro ao ler {}", compose_path.display()))?;
+    let project_dir = normalize(&std::path::absolute(project_dir).unwrap_or_default());
+    let content = format!(
+        "# Gerado pelo dx (dev-services run no {}); não edite.\n{}",
+        engine.describe(),
+        translate(&yaml, &project_dir)?
+    );
+    let path = compose_path.with_file_name(crate::compose::REMOTE_FILE);
+    fs::write(&path, content).context(format!("Erro ao salvar {}", path.display()))?;
+    tracing::debug!(path = %path.display(), "manifesto traduzido para o Docker remoto");
+    Ok(path)
+}
@@ -1,8 +1,11 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright (c) 2025 The dx-cli Contributors
 
+use crate::detectors::Detector;
 use crate::dev_services::{DockerComposeConfig, DockerService};
 use crate::dev_dependencies;
+use crate::i18n::DocLanguage;
+use serde_json::{json, Value};
 use std::path::Path;
 
 fn linkify_image(image: &str) -> String {
@@ -37,64 +40,95 @@ fn linkify_image(image: &str) -> String {
 /// and the detected DockerComposeConfig. This is shared by `analyzer` and
 /// `dev-services` so that the report content is identical.
 pub fn build_analyzer_report(project_dir: &Path, ds_config: &DockerComposeConfig) -> String {
-    use std::collections::HashSet;
     let mut report = String::new();
+    // Language of the report prose ([docs] language in dx.toml)
+    let lang = DocLanguage::of(project_dir);
+    let t = |pt, en, es| lang.pick(pt, en, es);
 
     // Header with identity and quick badges
     report.push_str("# dx-cli _analyzer_\n\n");
-    report.push_str(&format!("Projeto: {}\n\n", project_dir.display()));
+    report.push_str(&format!("{}: {}\n\n", t("Projeto", "Project", "Proyecto"), project_dir.display()));
     report.push_str("[![dx-anywhere](https://img.shields.io/badge/DX--Anywhere-CLI-1ED6FF?logo=https://raw.githubusercontent.com/dx-anywhere/dx-cli/HEAD/images/dx-logo.svg)](#) ");
     report.push_str("[![Report](https://img.shields.io/badge/Report-Markdown-informational)](#) ");
     report.push_str("[![Platform](https://img.shields.io/badge/Platform-Windows%20|%20macOS%20|%20Linux-green)](#)\n\n");
 
     // Intro/callout
-    report.push_str("> ℹ️ Este relatório resume o que o dx-cli aplicaria ao seu projeto: Dev Services, badges e próximas ações.\n\n");
+    report.push_str(t(
+        "> ℹ️ Este relatório resume o que o dx-cli aplicaria ao seu projeto: Dev Services, badges e próximas ações.\n\n",
+        "> ℹ️ This report summarizes what dx-cli would apply to your project: Dev Services, badges and next steps.\n\n",
+        "> ℹ️ Este informe resume lo que dx-cli aplicaría a su proyecto: Dev Services, badges y próximos pasos.\n\n",
+    ));
 
     // Table of contents
-    report.push_str("## Tabela de Conteúdos\n");
-    report.push_str("- [Resumo](#resumo)\n");
-    report.push_str("- [Dev Services](#dev-services)\n");
-    report.push_str("- [Dependências de Desenvolvimento](#dependências-de-desenvolvimento)\n");
-    report.push_str("- [Badges para README.md](#badges-para-readmemd)\n");
-    report.push_str("- [Próximas Ações](#próximas-ações)\n");
-    report.push_str("- [Outras Capabilities](#outras-capabilities)\n\n");
+    let summary = t("Resumo", "Summary", "Resumen");
+    let dev_deps = t("Dependências de Desenvolvimento", "Development Dependencies", "Dependencias de Desarrollo");
+    let badges = t("Badges para README.md", "Badges for README.md", "Badges para README.md");
+    let next = t("Próximas Ações", "Next Steps", "Próximos Pasos");
+    let other = t("Outras Capabilities", "Other Capabilities", "Otras Capacidades");
+    report.push_str(&format!("## {}\n", t("Tabela de Conteúdos", "Table of Contents", "Tabla de Contenidos")));
+    for title in [summary, "Dev Services", dev_deps, badges, next, other] {
+        report.push_str(&format!("- [{}](#{})\n", title, crate::i18n::anchor(title)));
+    }
+    report.push('\n');
 
     // Summary section
-    report.push_str("## Resumo\n\n");
+    report.push_str(&format!("## {}\n\n", summary));
+    let detectors = crate::detectors::catalog(project_dir);
     let svc_count = ds_config.services.len();
+    let detected = t("Serviços detectados", "Detected services", "Servicios detectados");
     if svc_count == 0 {
-        report.push_str("- 🚫 Nenhuma dependência de serviço detectada\n");
-        report.push_str("- 💡 Dica: adicione variáveis/.env ou dependências (Postgres, Redis, Kafka/Redpanda, MongoDB, Flink, etc.)\n\n");
+        report.push_str(t(
+            "- 🚫 Nenhuma dependência de serviço detectada\n",
+            "- 🚫 No service dependencies detected\n",
+            "- 🚫 No se detectaron dependencias de servicios\n",
+        ));
+        report.push_str(t(
+            "- 💡 Dica: adicione variáveis/.env ou dependências (Postgres, Redis, Kafka/Redpanda, MongoDB, Flink, etc.)\n\n",
+            "- 💡 Tip: add variables/.env or dependencies (Postgres, Redis, Kafka/Redpanda, MongoDB, Flink, etc.)\n\n",
+            "- 💡 Consejo: agregue variables/.env o dependencias (Postgres, Redis, Kafka/Redpanda, MongoDB, Flink, etc.)\n\n",
+        ));
     } else {
-        report.push_str(&format!("- ✅ Serviços detectados: {}\n", svc_count));
-        let mut names: Vec<_> = ds_config.services.keys().cloned().collect();
-        names.sort();
-        report.push_str(&format!("- 🧩 Lista: {}\n\n", names.join(", ")));
+        report.push_str(&format!("- ✅ {}: {}\n", detected, svc_count));
+        report.push_str(&format!("- 🧩 {}: {}\n\n", t("Lista", "List", "Lista"), ds_config.service_names().join(", ")));
     }
 
     // Dev Services section
     report.push_str("## Dev Services\n\n");
     if ds_config.services.is_empty() {
-        report.push_str("Nenhuma dependência detectada.\n\n");
+        report.push_str(t(
+            "Nenhuma dependência detectada.\n\n",
+            "No dependencies detected.\n\n",
+            "No se detectaron dependencias.\n\n",
+        ));
     } else {
-        report.push_str("Serviços detectados:\n");
-        for (name, _svc) in &ds_config.services {
+        report.push_str(&format!("{}:\n", detected));
+        for name in ds_config.service_names() {
             report.push_str(&format!("- {}\n", name));
         }
 
         // Services overview table
-        report.push_str("\n### Visão geral dos serviços\n\n");
-        report.push_str("| Serviço | Imagem | Portas | Volumes | Credenciais/Info |\n");
+        report.push_str(&format!(
+            "\n### {}\n\n",
+            t("Visão geral dos serviços", "Services overview", "Resumen de los servicios")
+        ));
+        report.push_str(t(
+            "| Serviço | Imagem | Portas | Volumes | Credenciais/Info |\n",
+            "| Service | Image | Ports | Volumes | Credentials/Info |\n",
+            "| Servicio | Imagen | Puertos | Volúmenes | Credenciales/Info |\n",
+        ));
         report.push_str("|--------|--------|--------|---------|------------------|\n");
         let mut entries: Vec<_> = ds_config.services.iter().collect();
         entries.sort_by(|a, b| a.0.cmp(b.0));
+        // Published ports of a remote engine (`run --context/--host`) are on its host
+        let host = crate::remote::address(project_dir).unwrap_or_else(|| "localhost".to_string());
         for (name, svc) in entries {
             let ports_md = if svc.ports.is_empty() {
                 "-".to_string()
             } else {
                 svc.ports
                     .iter()
-                    .map(|p| format!("[{}](http://localhost:{})", p, p))
+                    .map(|p| svc.host_port(*p))
+                    .map(|p| format!("[{}](http://{}:{})", p, host, p))
                     .collect::<Vec<_>>()
                     .join(", ")
             };
@@ -103,7 +137,7 @@ pub fn build_analyzer_report(project_dir: &Path, ds_config: &DockerComposeConfig
             } else {
                 svc.volumes.len().to_string()
             };
-            let info = service_info(name, svc);
+            let info = crate::ports::rewrite(&service_info(&detectors, name, svc, &host), svc);
             let image_link = linkify_image(&svc.image);
             report.push_str(&format!(
                 "| {} | {} | {} | {} | {} |\n",
@@ -111,175 +145,375 @@ pub fn build_analyzer_report(project_dir: &Path, ds_config: &DockerComposeConfig
             ));
         }
 
+        // Estimated footprint against what this machine has
+        let footprints = crate::resources::estimate_all(ds_config);
+        let total = crate::resources::total(&footprints);
+        let machine = crate::resources::machine(project_dir);
+        let size = |mb: u64| crate::clean::format_size(mb * 1024 * 1024);
+        report.push_str(&format!("\n### {}\n\n", t("Recursos estimados", "Estimated resources", "Recursos estimados")));
+        report.push_str(t(
+            "Estimativa em repouso, a partir dos padrões de cada imagem ou dos limites configurados no manifesto. O disco soma a imagem e uma base de dados inicial por volume nomeado.\n\n",
+            "Estimate at rest, from the defaults of each image or the limits configured in the manifest. Disk adds the image and an initial database per named volume.\n\n",
+            "Estimación en reposo, a partir de los valores por defecto de cada imagen o de los límites configurados en el manifiesto. El disco suma la imagen y una base de datos inicial por volumen con nombre.\n\n",
+        ));
+        report.push_str(t(
+            "| Serviço | Memória | CPU | Disco | Origem |\n",
+            "| Service | Memory | CPU | Disk | Source |\n",
+            "| Servicio | Memoria | CPU | Disco | Origen |\n",
+        ));
+        report.push_str("|---------|---------|-----|-------|--------|\n");
+        for f in &footprints {
+            report.push_str(&format!(
+                "| {} | {} | {:.2} | {} | {} |\n",
+                f.service,
+                size(f.memory_mb),
+                f.cpus,
+                size(f.disk_mb),
+                if f.configured {
+                    t("limite configurado", "configured limit", "límite configurado")
+                } else {
+                    t("padrão da imagem", "image default", "valor por defecto de la imagen")
+                }
+            ));
+        }
+        report.push_str(&format!(
+            "| **Total** | **{}** | **{:.2}** | **{}** | |\n",
+            size(total.memory_mb),
+            total.cpus,
+            size(total.disk_mb)
+        ));
+        let unknown = || "?".to_string();
+        let memory = machine.memory_total_mb.map(size).unwrap_or_else(unknown);
+        let available = machine.memory_available_mb.map(size).unwrap_or_else(unknown);
+        let cpus = machine.cpus.map(|c| c.to_string()).unwrap_or_else(unknown);
+        let disk = machine.disk_free_mb.map(size).unwrap_or_else(unknown);
+        report.push_str(&match lang {
+            DocLanguage::Pt => format!("\nMáquina: memória {memory} (disponível {available}), {cpus} CPUs, disco livre {disk}.\n"),
+            DocLanguage::En => format!("\nMachine: memory {memory} ({available} available), {cpus} CPUs, {disk} free disk.\n"),
+            DocLanguage::Es => format!("\nMáquina: memoria {memory} (disponible {available}), {cpus} CPUs, disco libre {disk}.\n"),
+        });
+        let warnings = crate::resources::warnings(&total, &machine);
+        if !warnings.is_empty() {
+            report.push('\n');
+            for w in warnings {
+                report.push_str(&format!("- ⚠️ {w}\n"));
+            }
+        }
+
+        if !ds_config.iac.is_empty() {
+            report.push_str(&format!(
+                "\n### {}\n\n",
+                t("Recursos de infraestrutura (IaC)", "Infrastructure resources (IaC)", "Recursos de infraestructura (IaC)")
+            ));
+            report.push_str(t(
+                "Recursos gerenciados declarados no código de infraestrutura e o serviço local que os substitui:\n\n",
+                "Managed resources declared in the infrastructure code and the local service that replaces them:\n\n",
+                "Recursos gestionados declarados en el código de infraestructura y el servicio local que los reemplaza:\n\n",
+            ));
+            report.push_str(t(
+                "| Recurso | Arquivo | Serviço local |\n",
+                "| Resource | File | Local service |\n",
+                "| Recurso | Archivo | Servicio local |\n",
+            ));
+            report.push_str("|---------|---------|---------------|\n");
+            for r in &ds_config.iac {
+                report.push_str(&format!("| `{}` | {} | {} |\n", r.kind, r.file, r.service.name()));
+            }
+        }
+
+        if let Some(preset) = &ds_config.preset {
+            report.push_str(&format!(
+                "\n### {}\n\n",
+                t("Preset de framework", "Framework preset", "Preset de framework")
+            ));
+            let framework = preset.framework.name();
+            let database = &preset.database;
+            let env_file = &preset.env_file;
+            let user = |label: &str| preset.user.as_ref().map(|u| format!(", {label} `{u}`")).unwrap_or_default();
+            report.push_str(&match lang {
+                DocLanguage::Pt => format!(
+                    "Preset **{framework}** aplicado: banco `{database}`{}. Ao salvar com `dx dev-services`, a conexão é gravada em `{env_file}`.\n",
+                    user("usuário")
+                ),
+                DocLanguage::En => format!(
+                    "Preset **{framework}** applied: database `{database}`{}. When saving with `dx dev-services`, the connection is written to `{env_file}`.\n",
+                    user("user")
+                ),
+                DocLanguage::Es => format!(
+                    "Preset **{framework}** aplicado: base de datos `{database}`{}. Al guardar con `dx dev-services`, la conexión se escribe en `{env_file}`.\n",
+                    user("usuario")
+                ),
+            });
+        }
+
+        report.push_str(&crate::jobs::runbook(ds_config, lang));
+        report.push_str(&crate::localstack::endpoints(ds_config, lang));
+        report.push_str(&crate::proxy::urls(project_dir, ds_config, lang));
+
+        // Images without a build for this machine's architecture
+        let arch = crate::arch::Arch::host();
+        let emulated: Vec<_> = ds_config
+            .manifest_services()
+            .into_iter()
+            .filter(|s| crate::arch::emulated(&s.image, arch))
+            .collect();
+        if !emulated.is_empty() {
+            report.push_str(&format!("\n### {} ({})\n\n", t("Arquitetura", "Architecture", "Arquitectura"), arch.name()));
+            let platform = crate::arch::AMD64_PLATFORM;
+            report.push_str(&match lang {
+                DocLanguage::Pt => format!("Sem build nativo para {}: rodam emulados (`platform: {platform}`), mais lentos. Troque a imagem no `.dx/docker-compose.override.yml` quando houver uma multi-arch.\n\n", arch.name()),
+                DocLanguage::En => format!("No native build for {}: they run emulated (`platform: {platform}`), slower. Swap the image in `.dx/docker-compose.override.yml` when there is a multi-arch one.\n\n", arch.name()),
+                DocLanguage::Es => format!("Sin build nativo para {}: se ejecutan emulados (`platform: {platform}`), más lentos. Cambie la imagen en `.dx/docker-compose.override.yml` cuando haya una multi-arch.\n\n", arch.name()),
+            });
+            for service in emulated {
+                report.push_str(&format!("- {}: `{}`\n", service.name, service.image));
+            }
+        }
+
+        // Local changes merged from .dx/docker-compose.override.yml
+        if let Some(overrides) = ds_config.overrides.as_ref().filter(|o| !o.services.is_empty()) {
+            report.push_str(&format!("\n### {}\n\n", t("Overrides locais", "Local overrides", "Overrides locales")));
+            report.push_str(t(
+                "Aplicados de `.dx/docker-compose.override.yml` sobre o manifesto gerado (a tabela acima mostra os valores detectados):\n\n",
+                "Applied from `.dx/docker-compose.override.yml` on top of the generated manifest (the table above shows the detected values):\n\n",
+                "Aplicados desde `.dx/docker-compose.override.yml` sobre el manifiesto generado (la tabla de arriba muestra los valores detectados):\n\n",
+            ));
+            for (name, service) in &overrides.services {
+                let origin = if ds_config.services.contains_key(name) {
+                    ""
+                } else {
+                    t(" (serviço novo)", " (new service)", " (servicio nuevo)")
+                };
+                report.push_str(&format!("- {}{}: {}\n", name, origin, service.keys().join(", ")));
+            }
+        }
+
         // Proposed YAML (collapsible)
-        report.push_str("\n### docker-compose.yaml proposto\n\n");
+        report.push_str(&format!(
+            "\n### {}\n\n",
+            t("docker-compose.yaml proposto", "Proposed docker-compose.yaml", "docker-compose.yaml propuesto")
+        ));
         report.push_str("<details>\n");
-        report.push_str("<summary>Mostrar YAML</summary>\n\n");
+        report.push_str(&format!("<summary>{}</summary>\n\n", t("Mostrar YAML", "Show YAML", "Mostrar YAML")));
         report.push_str("```yaml\n");
         report.push_str(&ds_config.to_yaml());
         report.push_str("\n```\n");
         report.push_str("</details>\n\n");
 
         // Tip callout
-        report.push_str("> 💡 Dica: ajuste portas/volumes conforme seu ambiente. Com Docker Compose v2, use `docker compose` em vez de `docker-compose`.\n\n");
+        report.push_str(t(
+            "> 💡 Dica: ajuste portas/volumes conforme seu ambiente. Com Docker Compose v2, use `docker compose` em vez de `docker-compose`.\n\n",
+            "> 💡 Tip: adjust ports/volumes to your environment. With Docker Compose v2, use `docker compose` instead of `docker-compose`.\n\n",
+            "> 💡 Consejo: ajuste puertos/volúmenes según su entorno. Con Docker Compose v2, use `docker compose` en lugar de `docker-compose`.\n\n",
+        ));
+    }
+
+    // Detected services kept out of the manifest on purpose
+    if !ds_config.excluded.is_empty() {
+        report.push_str(&format!("### {}\n\n", t("Serviços excluídos", "Excluded services", "Servicios excluidos")));
+        report.push_str(t(
+            "Detectados, mas fora do manifesto por `[dev_services] exclude` no dx.toml (ou `--without`):\n\n",
+            "Detected, but left out of the manifest by `[dev_services] exclude` in dx.toml (or `--without`):\n\n",
+            "Detectados, pero fuera del manifiesto por `[dev_services] exclude` en dx.toml (o `--without`):\n\n",
+        ));
+        for name in &ds_config.excluded {
+            report.push_str(&format!("- {}\n", name));
+        }
+        report.push('\n');
     }
 
     // Dev dependencies section
-    report.push_str("## Dependências de Desenvolvimento\n\n");
+    report.push_str(&format!("## {}\n\n", dev_deps));
     match dev_dependencies::get_dependencies(project_dir) {
         Ok(deps) => {
             if deps.is_empty() {
-                report.push_str("Nenhuma dependência de desenvolvimento encontrada.\n\n");
+                report.push_str(t(
+                    "Nenhuma dependência de desenvolvimento encontrada.\n\n",
+                    "No development dependencies found.\n\n",
+                    "No se encontraron dependencias de desarrollo.\n\n",
+                ));
             } else {
-                report.push_str("| Dependência | Versão Atual | Última Versão | Comando de Atualização |\n");
+                report.push_str(t(
+                    "| Dependência | Versão Atual | Última Versão | Comando de Atualização |\n",
+                    "| Dependency | Current Version | Latest Version | Update Command |\n",
+                    "| Dependencia | Versión Actual | Última Versión | Comando de Actualización |\n",
+                ));
                 report.push_str("|-------------|--------------|---------------|------------------------|\n");
                 for d in deps {
                     let latest = d.latest_version.clone().unwrap_or_else(|| "-".to_string());
                     report.push_str(&format!("| {} | {} | {} | `{}` |\n", d.link(), d.current_version, latest, d.update_command));
                 }
-                report.push_str("\nPara atualizar todas: `dx dev-dependencies update`\n\n");
+                report.push_str(&format!(
+                    "\n{}: `dx dev-dependencies update`\n\n",
+                    t("Para atualizar todas", "To update all", "Para actualizar todas")
+                ));
             }
         }
         Err(e) => {
-            report.push_str(&format!("Erro ao obter dependências: {e}\n\n"));
+            report.push_str(&format!(
+                "{}: {e}\n\n",
+                t("Erro ao obter dependências", "Error getting dependencies", "Error al obtener dependencias")
+            ));
         }
     }
 
     // Badges section for README injection
-    report.push_str("## Badges para README.md\n\n");
-    report.push_str("Abaixo você vê as badges renderizadas. Em seguida, há um bloco colapsável com o Markdown para copiar e colar entre os marcadores no seu README.md.\n\n");
-    // Build badges
-    let mut badges: HashSet<&str> = HashSet::new();
-    let keys: HashSet<String> = ds_config.services.keys().cloned().collect();
-    for k in &keys {
-        let kl = k.to_lowercase();
-        match kl.as_str() {
-            "postgres" => {
-                badges.insert("[![PostgreSQL](https://img.shields.io/badge/PostgreSQL-Dev_Service-blue?logo=postgresql)](#)");
-            }
-            "mysql" => {
-                badges.insert(
-                    "[![MySQL](https://img.shields.io/badge/MySQL-Dev_Service-blue?logo=mysql)](#)",
-                );
-            }
-            "redis" => {
-                badges.insert(
-                    "[![Redis](https://img.shields.io/badge/Redis-Dev_Service-red?logo=redis)](#)",
-                );
-            }
-            "mongodb" => {
-                badges.insert("[![MongoDB](https://img.shields.io/badge/MongoDB-Dev_Service-green?logo=mongodb)](#)");
-            }
-            "kafka" => {
-                badges.insert("[![Kafka](https://img.shields.io/badge/Kafka-Dev_Service-black?logo=apachekafka)](#)");
-            }
-            "kafka-ui" => { /* skip explicit UI badge */ }
-            "jobmanager" | "taskmanager" => {
-                badges.insert("[![Apache Flink](https://img.shields.io/badge/Flink-Dev_Service-orange?logo=apacheflink)](#)");
-            }
-            _ => {}
-        }
-    }
-    let mut badge_lines: Vec<&str> = badges.into_iter().collect();
-    badge_lines.sort();
-    // Always append the dx-anywhere badge at the end (using repo logo)
-    let dx_anywhere_badge = "[![dx-anywhere](https://img.shields.io/badge/DX--Anywhere-CLI-1ED6FF?logo=https://raw.githubusercontent.com/dx-anywhere/dx-cli/HEAD/images/dx-logo.svg)](#)";
-    let rendered_line = if badge_lines.is_empty() {
-        dx_anywhere_badge.to_string()
-    } else {
-        format!("{} {}", badge_lines.join(" "), dx_anywhere_badge)
-    };
+    report.push_str(&format!("## {}\n\n", badges));
+    report.push_str(t(
+        "Abaixo você vê as badges renderizadas. Em seguida, há um bloco colapsável com o Markdown para copiar e colar entre os marcadores no seu README.md.\n\n",
+        "Below are the rendered badges. Next, a collapsible block has the Markdown to copy and paste between the markers in your README.md.\n\n",
+        "Abajo se ven los badges renderizados. A continuación, hay un bloque plegable con el Markdown para copiar y pegar entre los marcadores de su README.md.\n\n",
+    ));
+    // Same badges as `dx dev-badges`, dx-anywhere's last
+    let rendered_line = crate::dev_badges::generate_badges_markdown(project_dir, &ds_config.service_names());
     // Rendered badges line
     report.push_str(&rendered_line);
     report.push_str("\n\n");
     // Collapsible code block for README injection
     report.push_str("<details>\n");
-    report.push_str("<summary>Mostrar bloco de badges (Markdown)</summary>\n\n");
+    report.push_str(&format!(
+        "<summary>{}</summary>\n\n",
+        t("Mostrar bloco de badges (Markdown)", "Show badges block (Markdown)", "Mostrar bloque de badges (Markdown)")
+    ));
     report.push_str("```md\n");
     report.push_str("<!-- dx-cli:badges:start -->\n");
     report.push_str(&rendered_line);
-    report.push_str("\n");
+    report.push('\n');
     report.push_str("<!-- dx-cli:badges:end -->\n");
     report.push_str("```\n\n");
     report.push_str("</details>\n\n");
 
     // Next steps
-    report.push_str("## Próximas Ações\n\n");
-    report.push_str("- 🧪 Visualizar ajuda da CLI: `dx --help`\n");
-    report.push_str("- 🧱 Gerar/Salvar Dev Services: `dx dev-services`\n");
-    report.push_str("- 🏷️ Aplicar badges: `dx dev-badges` (ou `dx dev-badges clean`)\n");
-    report.push_str("- 🩺 Reexecutar análise: `dx analyzer`\n\n");
+    report.push_str(&format!("## {}\n\n", next));
+    report.push_str(t(
+        "- 🧪 Visualizar ajuda da CLI: `dx --help`\n\
+         - 🧱 Gerar/Salvar Dev Services: `dx dev-services`\n\
+         - 🏷️ Aplicar badges: `dx dev-badges` (ou `dx dev-badges clean`)\n\
+         - 🩺 Reexecutar análise: `dx analyzer`\n\n",
+        "- 🧪 Show the CLI help: `dx --help`\n\
+         - 🧱 Generate/Save Dev Services: `dx dev-services`\n\
+         - 🏷️ Apply badges: `dx dev-badges` (or `dx dev-badges clean`)\n\
+         - 🩺 Run the analysis again: `dx analyzer`\n\n",
+        "- 🧪 Ver la ayuda de la CLI: `dx --help`\n\
+         - 🧱 Generar/Guardar Dev Services: `dx dev-services`\n\
+         - 🏷️ Aplicar badges: `dx dev-badges` (o `dx dev-badges clean`)\n\
+         - 🩺 Volver a ejecutar el análisis: `dx analyzer`\n\n",
+    ));
 
-    report.push_str("## Outras Capabilities\n\n");
-    report.push_str("- Dev Badges: aplicar badges das tecnologias detectadas (dx dev-badges)\n");
-    report.push_str("- Portal: Dev UI com integrações e operações (dx portal)\n");
-    report.push_str("- Testes: geração/execução assistidas (dx tests)\n");
-    report.push_str("- Config: wizards e config tipada (dx config)\n");
-    report.push_str("- Docs: documentação viva + Q&A (dx docs)\n");
-    report.push_str("- Governança: guardrails e scorecards (dx governance)\n");
-    report.push_str("- Telemetria: observabilidade por padrão (inclusa no dev-services)\n\n");
+    report.push_str(&format!("## {}\n\n", other));
+    report.push_str(t(
+        "- Dev Badges: aplicar badges das tecnologias detectadas (dx dev-badges)\n\
+         - Portal: Dev UI com integrações e operações (dx portal)\n\
+         - Testes: geração/execução assistidas (dx tests)\n\
+         - Config: wizards e config tipada (dx config)\n\
+         - Docs: documentação viva + Q&A (dx docs)\n\
+         - Governança: guardrails e scorecards (dx governance)\n\
+         - Telemetria: observabilidade por padrão (inclusa no dev-services)\n\n",
+        "- Dev Badges: apply the badges of the detected technologies (dx dev-badges)\n\
+         - Portal: Dev UI with integrations and operations (dx portal)\n\
+         - Tests: assisted generation/execution (dx tests)\n\
+         - Config: wizards and typed config (dx config)\n\
+         - Docs: living documentation + Q&A (dx docs)\n\
+         - Governance: guardrails and scorecards (dx governance)\n\
+         - Telemetry: observability by default (included in dev-services)\n\n",
+        "- Dev Badges: aplicar los badges de las tecnologías detectadas (dx dev-badges)\n\
+         - Portal: Dev UI con integraciones y operaciones (dx portal)\n\
+         - Tests: generación/ejecución asistidas (dx tests)\n\
+         - Config: asistentes y config tipada (dx config)\n\
+         - Docs: documentación viva + Q&A (dx docs)\n\
+         - Gobernanza: guardrails y scorecards (dx governance)\n\
+         - Telemetría: observabilidad por defecto (incluida en dev-services)\n\n",
+    ));
 
     // Footer
     report.push_str("---\n");
-    report.push_str("Relatório gerado pelo dx-cli.\n");
+    report.push_str(t("Relatório gerado pelo dx-cli.\n", "Report generated by dx-cli.\n", "Informe generado por dx-cli.\n"));
 
     report
 }
 
-fn service_info(name: &str, svc: &DockerService) -> String {
-    let n = name.to_lowercase();
-    // Convenience closure to fetch env var
-    let env = |k: &str| svc.env.get(k).cloned();
+/// Build the analyzer JSON model for a project: the same facts summarized by the
+/// markdown report, in a shape that policies and tooling can consume.
+pub fn build_analyzer_model(project_dir: &Path, ds_config: &DockerComposeConfig) -> Value {
+    let (language, framework) = crate::telemetry::detect_language_and_framework(project_dir);
+    let markers: Vec<&str> = [
+        "Cargo.toml",
+        "package.json",
+        "requirements.txt",
+        "pyproject.toml",
+        "setup.py",
+        "pom.xml",
+        "build.gradle",
+        "build.gradle.kts",
+        "Gemfile",
+        "go.mod",
+        "composer.json",
+    ]
+    .into_iter()
+    .filter(|m| project_dir.join(m).is_file())
+    .collect();
+
+    let mut entries: Vec<_> = ds_config.services.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    let services: Vec<Value> = entries
+        .into_iter()
+        .map(|(name, svc)| {
+            json!({
+                "name": name,
+                "image": svc.image,
+                "ports": svc.ports,
+                "host_ports": svc.host_ports,
+                "volumes": svc.volumes,
+                "env": svc.env,
+                "command": svc.command,
+            })
+        })
+        .collect();
 
+    let footprints = crate::resources::estimate_all(ds_config);
+
+    let gitignore = std::fs::read_to_string(project_dir.join(".gitignore")).ok();
+    let ignores_dx = gitignore
+        .as_deref()
+        .is_some_and(|c| crate::gitignore::contains(c, crate::gitignore::DX_DIR));
+
+    json!({
+        "project": {
+            "path": project_dir.display().to_string(),
+            "name": project_dir.file_name().and_then(|n| n.to_str()).unwrap_or(""),
+            "language": language,
+            "framework": framework,
+            "markers": markers,
+        },
+        "services": services,
+        "excluded": ds_config.excluded,
+        "jobs": ds_config.jobs,
+        "resources": {
+            "services": footprints,
+            "total": crate::resources::total(&footprints),
+        },
+        "files": {
+            "readme": project_dir.join("README.md").is_file(),
+            "gitignore": gitignore.is_some(),
+            "gitignore_has_dx": ignores_dx,
+            "env": project_dir.join(".env").is_file(),
+        },
+    })
+}
+
+fn service_info(detectors: &[Box<dyn Detector>], name: &str, svc: &DockerService, host: &str) -> String {
+    let n = name.to_lowercase();
+    // Services of the detectors describe themselves
+    if let Some(info) = detectors.iter().find(|d| d.provides(&n)).and_then(|d| d.info(&n, svc)) {
+        return info;
+    }
+    if let Some(info) = crate::api_mocks::info(&n, svc) {
+        return info;
+    }
+    if let Some(info) = crate::proxy::info(&n) {
+        return info;
+    }
     match n.as_str() {
-        // Databases
-        "postgres" => {
-            let user = env("POSTGRES_USER").unwrap_or_else(|| "postgres".to_string());
-            let pass = env("POSTGRES_PASSWORD").unwrap_or_else(|| "example".to_string());
-            let db = env("POSTGRES_DB").unwrap_or_else(|| "app".to_string());
-            format!(
-                "user: {}, pass: {}, db: {}, url: postgres://{}:{}@localhost:5432/{}",
-                user, pass, db, user, pass, db
-            )
-        }
-        "mysql" | "mariadb" => {
-            let user = "root".to_string();
-            let pass = env("MARIADB_ROOT_PASSWORD")
-                .or_else(|| env("MYSQL_ROOT_PASSWORD"))
-                .unwrap_or_else(|| "example".to_string());
-            let db = env("MARIADB_DATABASE")
-                .or_else(|| env("MYSQL_DATABASE"))
-                .unwrap_or_else(|| "app".to_string());
-            format!(
-                "user: {}, pass: {}, db: {}, url: mysql://{}:{}@localhost:3306/{}",
-                user, pass, db, user, pass, db
-            )
-        }
-        "mongodb" => {
-            let user = env("MONGO_INITDB_ROOT_USERNAME").unwrap_or_else(|| "root".to_string());
-            let pass = env("MONGO_INITDB_ROOT_PASSWORD").unwrap_or_else(|| "example".to_string());
-            format!(
-                "user: {}, pass: {}, url: mongodb://{}:{}@localhost:27017",
-                user, pass, user, pass
-            )
-        }
-        "redis" => {
-            // If REDIS_PASSWORD present, report it, otherwise default: no auth
-            if let Some(p) = env("REDIS_PASSWORD") {
-                format!("senha: {} (requirepass habilitado)", p)
-            } else {
-                "sem senha (default)".to_string()
-            }
-        }
-        // Messaging / Streaming
-        "kafka" => {
-            // Redpanda default advertised host 29092
-            "Bootstrap: localhost:29092".to_string()
-        }
-        "kafka-ui" => "UI: http://localhost:9093".to_string(),
-        // Flink
-        "jobmanager" => "Flink UI: http://localhost:8081".to_string(),
-        "taskmanager" => "Seguido pelo JobManager (sem UI)".to_string(),
         // Observability stack
         "grafana" => {
             let anon = svc
@@ -294,7 +528,7 @@ fn service_info(name: &str, svc: &DockerService) -> String {
             }
         }
         "prometheus" => "scrape: otel-collector:8889".to_string(),
-        "loki" => "push: http://localhost:3100/loki/api/v1/push".to_string(),
+        "loki" => format!("push: http://{host}:3100/loki/api/v1/push"),
         "tempo" => "OTLP gRPC: 4317, HTTP: 4318".to_string(),
         "otel-collector" => "OTLP HTTP: 4318 | gRPC: 4317 | Prom (metrics): 8889".to_string(),
         _ => "-".to_string(),
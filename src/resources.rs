@@ -0,0 +1,300 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Resource footprint of the generated environment (analyzer report).
+//!
+//! Each service is estimated from typical idle figures of its image (memory,
+//! CPU, image size), replaced by the limits configured in the manifest when
+//! there are any (`--memory`/`--smp` of Redpanda, `-Xmx` of JVM services,
+//! Flink's `memory.process.size`). Named volumes count a baseline of initial
+//! data. The machine's memory, CPUs and free disk are read from the platform
+//! (`/proc/meminfo`, `sysctl`, `wmic`, `df`) to warn when the total won't fit.
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use serde::Serialize;
+
+use crate::dev_services::{DockerComposeConfig, DockerService};
+
+/// Initial data counted for each named volume, in MiB.
+const VOLUME_BASELINE_MB: u64 = 100;
+
+/// Typical figures of an image: (name, memory MiB, CPUs, image size MiB).
+const IMAGE_DEFAULTS: &[(&str, u64, f64, u64)] = &[
+    ("postgres", 256, 0.5, 250),
+    ("mariadb", 400, 0.5, 400),
+    ("mysql", 400, 0.5, 600),
+    ("redis", 64, 0.25, 40),
+    ("valkey", 64, 0.25, 40),
+    ("mongo", 400, 0.5, 700),
+    ("redpanda", 1024, 1.0, 550),
+    ("kafka", 1024, 1.0, 800),
+    ("cp-kafka", 1024, 1.0, 800),
+    ("kafka-ui", 384, 0.25, 300),
+    ("flink", 1600, 1.0, 800),
+    ("minio", 256, 0.25, 150),
+    ("mc", 32, 0.1, 100),
+    ("curl", 16, 0.1, 25),
+    ("traefik", 64, 0.25, 180),
+    ("localstack", 512, 0.5, 1100),
+    ("mailpit", 32, 0.1, 40),
+    ("elasticsearch", 1024, 1.0, 1200),
+    ("opensearch", 1024, 1.0, 1300),
+    ("opensearch-dashboards", 512, 0.25, 1500),
+    ("prism", 128, 0.25, 250),
+    ("gripmock", 64, 0.25, 100),
+    ("rabbitmq", 200, 0.25, 250),
+    ("unleash-server", 256, 0.25, 400),
+    ("flagsmith", 512, 0.5, 900),
+    ("ld-relay", 64, 0.25, 60),
+    ("nginx", 16, 0.1, 50),
+    ("grafana", 150, 0.25, 450),
+    ("prometheus", 200, 0.25, 250),
+    ("loki", 150, 0.25, 80),
+    ("tempo", 150, 0.25, 100),
+    ("opentelemetry-collector", 150, 0.25, 250),
+    ("opentelemetry-collector-contrib", 150, 0.25, 250),
+];
+
+/// Any other image.
+const FALLBACK: (u64, f64, u64) = (256, 0.25, 300);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Footprint {
+    pub service: String,
+    pub memory_mb: u64,
+    pub cpus: f64,
+    pub disk_mb: u64,
+    /// Whether memory/CPU come from limits configured in the manifest
+    pub configured: bool,
+}
+
+/// Memory, CPUs and free disk of this machine; `None` where unknown.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Machine {
+    pub memory_total_mb: Option<u64>,
+    pub memory_available_mb: Option<u64>,
+    pub cpus: Option<usize>,
+    pub disk_free_mb: Option<u64>,
+}
+
+/// Image name without registry, path or tag (`grafana/grafana:11` -> `grafana`).
+pub fn image_name(image: &str) -> &str {
+    let image = image.split('@').next().unwrap_or(image);
+    let last = image.rsplit('/').next().unwrap_or(image);
+    last.split(':').next().unwrap_or(last)
+}
+
+/// `512M`, `1g`, `1600m`, `2048k` in MiB.
+fn parse_size(text: &str) -> Option<u64> {
+    let text = text.trim().to_ascii_lowercase();
+    let digits: String = text.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let value: u64 = digits.parse().ok()?;
+    let unit = text[digits.len()..].trim_end_matches(['b', 'i']);
+    match unit {
+        "k" => Some(value / 1024),
+        "" | "m" => Some(value),
+        "g" => Some(value * 1024),
+        _ => None,
+    }
+}
+
+/// Value following `flag` in `command` (`--memory 512M` or `--memory=512M`).
+fn flag_value<'a>(command: &'a str, flag: &str) -> Option<&'a str> {
+    let mut words = command.split_whitespace();
+    while let Some(word) = words.next() {
+        if word == flag {
+            return words.next();
+        }
+        if let Some(value) = word.strip_prefix(flag).and_then(|v| v.strip_prefix('=')) {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Memory (MiB) and CPUs set in the service's command or environment.
+fn configured_limits(svc: &DockerService) -> (Option<u64>, Option<f64>) {
+    let command = svc.command.as_deref().unwrap_or("");
+    let mut memory = flag_value(command, "--memory").and_then(parse_size);
+    let cpus = flag_value(command, "--smp").and_then(|v| v.parse::<f64>().ok());
+    for value in svc.env.values() {
+        if memory.is_some() {
+            break;
+        }
+        // JVM heap, plus metaspace/threads overhead
+        if let Some(heap) = value
+            .split_whitespace()
+            .find_map(|w| w.strip_prefix("-Xmx"))
+            .and_then(parse_size)
+        {
+            memory = Some(heap + heap / 4);
+        } else if let Some(size) = value
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("jobmanager.memory.process.size:"))
+            .or_else(|| {
+                value
+                    .lines()
+                    .find_map(|l| l.trim().strip_prefix("taskmanager.memory.process.size:"))
+            })
+            .and_then(parse_size)
+        {
+            memory = Some(size);
+        }
+    }
+    (memory, cpus)
+}
+
+fn estimate(name: &str, svc: &DockerService) -> Footprint {
+    let image = image_name(&svc.image);
+    let (memory, cpus, image_mb) = IMAGE_DEFAULTS
+        .iter()
+        .find(|(n, ..)| *n == image)
+        .map(|(_, m, c, d)| (*m, *c, *d))
+        .unwrap_or(FALLBACK);
+    let named_volumes = svc
+        .volumes
+        .iter()
+        .filter_map(|v| v.split_once(':'))
+        .filter(|(source, _)| !source.is_empty() && !source.starts_with(['.', '/', '~', '$']))
+        .count() as u64;
+    let (configured_memory, configured_cpus) = configured_limits(svc);
+    Footprint {
+        service: name.to_string(),
+        memory_mb: configured_memory.unwrap_or(memory),
+        cpus: configured_cpus.unwrap_or(cpus),
+        disk_mb: image_mb + named_volumes * VOLUME_BASELINE_MB,
+        configured: configured_memory.is_some() || configured_cpus.is_some(),
+    }
+}
+
+/// Footprint of each service of `config`, sorted by name.
+pub fn estimate_all(config: &DockerComposeConfig) -> Vec<Footprint> {
+    config
+        .service_names()
+        .into_iter()
+        .map(|name| estimate(&name, &config.services[&name]))
+        .collect()
+}
+
+/// Sum of `footprints` as a `total` row.
+pub fn total(footprints: &[Footprint]) -> Footprint {
+    Footprint {
+        service: "total".to_string(),
+        memory_mb: footprints.iter().map(|f| f.memory_mb).sum(),
+        cpus: footprints.iter().map(|f| f.cpus).sum(),
+        disk_mb: footprints.iter().map(|f| f.disk_mb).sum(),
+        configured: false,
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// (total, available) memory in MiB.
+fn memory() -> (Option<u64>, Option<u64>) {
+    if let Ok(meminfo) = std::fs::read_to_string("/proc/meminfo") {
+        let field = |name: &str| {
+            meminfo
+                .lines()
+                .find_map(|l| l.strip_prefix(name))
+                .and_then(|v| v.trim().trim_end_matches("kB").trim().parse::<u64>().ok())
+                .map(|kb| kb / 1024)
+        };
+        return (field("MemTotal:"), field("MemAvailable:"));
+    }
+    if cfg!(target_os = "macos") {
+        let total = command_output("sysctl", &["-n", "hw.memsize"])
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(|bytes| bytes / 1024 / 1024);
+        return (total, None);
+    }
+    if cfg!(windows) {
+        let output = command_output(
+            "wmic",
+            &["OS", "get", "FreePhysicalMemory,TotalVisibleMemorySize", "/Value"],
+        )
+        .unwrap_or_default();
+        let field = |name: &str| {
+            output
+                .lines()
+                .find_map(|l| l.trim().strip_prefix(name))
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(|kb| kb / 1024)
+        };
+        return (
+            field("TotalVisibleMemorySize="),
+            field("FreePhysicalMemory="),
+        );
+    }
+    (None, None)
+}
+
+/// Free space (MiB) of the filesystem holding `dir`.
+fn disk_free(dir: &Path) -> Option<u64> {
+    if cfg!(windows) {
+        return None;
+    }
+    let output = command_output("df", &["-Pk", &dir.display().to_string()])?;
+    // Filesystem 1024-blocks Used Available Capacity Mounted on
+    let line = output.lines().nth(1)?;
+    let available: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available / 1024)
+}
+
+/// Resources of this machine, as far as they can be detected.
+pub fn machine(project_dir: &Path) -> Machine {
+    let (memory_total_mb, memory_available_mb) = memory();
+    Machine {
+        memory_total_mb,
+        memory_available_mb,
+        cpus: std::thread::available_parallelism().ok().map(|n| n.get()),
+        disk_free_mb: disk_free(project_dir),
+    }
+}
+
+/// Warnings when `total` exceeds what `machine` has.
+pub fn warnings(total: &Footprint, machine: &Machine) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let size = |mb: u64| crate::clean::format_size(mb * 1024 * 1024);
+    match (machine.memory_available_mb, machine.memory_total_mb) {
+        (Some(available), _) if total.memory_mb > available => warnings.push(format!(
+            "Memória estimada ({}) acima da disponível agora ({}): feche aplicações, reduza serviços (ex.: `[telemetry] level = \"metrics\"`) ou aumente a memória do Docker.",
+            size(total.memory_mb),
+            size(available)
+        )),
+        (None, Some(memory)) if total.memory_mb > memory * 3 / 4 => warnings.push(format!(
+            "Memória estimada ({}) acima de 75% da memória da máquina ({}).",
+            size(total.memory_mb),
+            size(memory)
+        )),
+        _ => {}
+    }
+    if let Some(cpus) = machine.cpus
+        && total.cpus > cpus as f64
+    {
+        warnings.push(format!(
+            "CPU estimada ({:.2}) acima dos {cpus} núcleos da máquina: os serviços vão competir por CPU.",
+            total.cpus
+        ));
+    }
+    if let Some(free) = machine.disk_free_mb
+        && total.disk_mb > free
+    {
+        warnings.push(format!(
+            "Disco estimado ({}) acima do espaço livre ({}).",
+            size(total.disk_mb),
+            size(free)
+        ));
+    }
+    warnings
+}
@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! `dx dev-services scale <service>=<n>...`: number of containers of a
+//! service (Kafka consumers, Flink taskmanagers, workers).
+//!
+//! The scale is written as `scale: <n>` on the service in the override file
+//! (`.dx/docker-compose.override.yml`, created if missing), edited as text so
+//! the user's comments and layout stay, and the manifest is regenerated with
+//! it: the next `run` and `dx dev-services` keep the scale instead of going
+//! back to one container. Then `up -d --scale`, with the project's runtime
+//! (see [`crate::compose`]), applies it to the running stack. A service
+//! publishing a fixed host port can't have more than one container, so that
+//! is refused before anything is written.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::compose::{OVERRIDE_FILES, Runner, Runtime};
+use crate::error::{DxError, DxResult, IoContext};
+
+/// Parse `service=n`.
+fn parse(spec: &str) -> DxResult<(String, u32)> {
+    spec.split_once('=')
+        .and_then(|(service, n)| Some((service.trim(), n.trim().parse().ok()?)))
+        .filter(|(service, _)| !service.is_empty())
+        .map(|(service, n)| (service.to_string(), n))
+        .ok_or_else(|| {
+            DxError::InvalidInput(format!(
+                "Escala inválida: '{spec}' (use serviço=N, ex.: kafka-consumer=3)."
+            ))
+        })
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Whether `line` has content (not blank, not a comment).
+fn content(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with('#')
+}
+
+/// Key of a `key: value` line, unquoted.
+fn key_of(line: &str) -> Option<&str> {
+    let (key, _) = line.trim().split_once(':')?;
+    Some(key.trim().trim_matches(|c| c == '"' || c == '\''))
+}
+
+/// `yaml` with `scale: n` on `service`; `None` when the file isn't laid out
+/// in block style where it would go.
+fn set_scale(yaml: &str, service: &str, n: u32) -> Option<String> {
+    let mut lines: Vec<String> = yaml.lines().map(str::to_string).collect();
+    let services = lines
+        .iter()
+        .position(|l| indent(l) == 0 && key_of(l) == Some("services"));
+    let Some(services) = services else {
+        if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.extend([
+            "services:".to_string(),
+            format!("  {service}:"),
+            format!("    scale: {n}"),
+        ]);
+        return Some(lines.join("\n") + "\n");
+    };
+    // `services: {}` or another flow value
+    if !lines[services].trim_end().ends_with(':') {
+        return None;
+    }
+    let end = (services + 1..lines.len())
+        .find(|&i| content(&lines[i]) && indent(&lines[i]) == 0)
+        .unwrap_or(lines.len());
+    let step = (services + 1..end)
+        .find(|&i| content(&lines[i]))
+        .map_or(2, |i| indent(&lines[i]));
+    let header = (services + 1..end)
+        .find(|&i| indent(&lines[i]) == step && key_of(&lines[i]) == Some(service));
+    let Some(header) = header else {
+        // After the last service
+        let at = (services + 1..end)
+            .rev()
+            .find(|&i| content(&lines[i]))
+            .map_or(services + 1, |i| i + 1);
+        let pad = " ".repeat(step);
+        lines.splice(
+            at..at,
+            [format!("{pad}{service}:"), format!("{pad}{pad}scale: {n}")],
+        );
+        return Some(lines.join("\n") + "\n");
+    };
+    if !lines[header]
+        .split('#')
+        .next()
+        .unwrap_or_default()
+        .trim_end()
+        .ends_with(':')
+    {
+        return None;
+    }
+    let body_end = (header + 1..end)
+        .find(|&i| content(&lines[i]) && indent(&lines[i]) <= step)
+        .unwrap_or(end);
+    let key_indent = (header + 1..body_end)
+        .find(|&i| content(&lines[i]))
+        .map_or(step * 2, |i| indent(&lines[i]));
+    let pad = " ".repeat(key_indent);
+    let existing = (header + 1..body_end)
+        .find(|&i| indent(&lines[i]) == key_indent && key_of(&lines[i]) == Some("scale"));
+    match existing {
+        Some(i) => {
+            // Keeping a comment after the value
+            let comment = lines[i].find(" #").map(|c| lines[i][c..].to_string());
+            lines[i] = format!("{pad}scale: {n}{}", comment.unwrap_or_default());
+        }
+        None => lines.insert(header + 1, format!("{pad}scale: {n}")),
+    }
+    Some(lines.join("\n") + "\n")
+}
+
+/// The override file of `dx_dir`, existing or to create.
+fn override_path(dx_dir: &Path) -> PathBuf {
+    OVERRIDE_FILES
+        .iter()
+        .map(|name| dx_dir.join(name))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| dx_dir.join(OVERRIDE_FILES[0]))
+}
+
+/// `dx dev-services scale`: set the number of containers of services of
+/// the project at `dir`.
+pub fn scale(dir: Option<PathBuf>, specs: Vec<String>, runtime: Option<Runtime>) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    let dx_dir = project_dir.join(".dx");
+    let compose_path = dx_dir.join("docker-compose.yml");
+    if !compose_path.exists() {
+        return Err(DxError::NotFound(format!(
+            "Arquivo não encontrado: {}\nDica: gere o manifesto com:\n  dx dev-services",
+            compose_path.display()
+        )));
+    }
+    let scales = specs
+        .iter()
+        .map(|spec| parse(spec))
+        .collect::<DxResult<Vec<_>>>()?;
+    let yaml = fs::read_to_string(&compose_path)
+        .context(format!("Erro ao ler {}", compose_path.display()))?;
+    let services = crate::dev_services::manifest_services(&yaml);
+    for (name, n) in &scales {
+        let Some(service) = services.iter().find(|s| &s.name == name) else {
+            let names: Vec<&str> = services.iter().map(|s| s.name.as_str()).collect();
+            return Err(DxError::InvalidInput(format!(
+                "Serviço desconhecido: '{name}'. Disponíveis: {}",
+                names.join(", ")
+            )));
+        };
+        if *n > 1
+            && let Some((host, _)) = service.ports.first()
+        {
+            return Err(DxError::InvalidInput(format!(
+                "'{name}' publica a porta {host} do host, que só um container pode usar.\nDica: no override, troque por uma faixa (\"{host}-{}:{}\") ou remova a porta publicada.",
+                u32::from(*host) + n - 1,
+                service.ports[0].1
+            )));
+        }
+    }
+
+    let path = override_path(&dx_dir);
+    let mut content = fs::read_to_string(&path).unwrap_or_default();
+    for (name, n) in &scales {
+        content = set_scale(&content, name, *n).ok_or_else(|| {
+            DxError::InvalidInput(format!(
+                "Não foi possível gravar a escala de '{name}' em {}: use o formato de blocos do YAML (sem {{ }}) em services.\nDica: adicione 'scale: {n}' ao serviço manualmente.",
+                path.display()
+            ))
+        })?;
+        outln!("Escala de '{name}': {n}.");
+    }
+    fs::write(&path, content).context(format!("Erro ao salvar {}", path.display()))?;
+    outln!("Escala salva em {}.", path.display());
+    crate::telemetry::apply(&project_dir).context("Erro ao regenerar .dx/docker-compose.yml")?;
+    outln!("Manifesto atualizado: {}", compose_path.display());
+
+    let runtime = Runtime::resolve(&project_dir, runtime)?;
+    // `up` recreates the containers: with the images `run` gave them
+    let arch = crate::arch::write_override(&project_dir, &compose_path, crate::arch::Arch::host())?;
+    let mut runner = Runner::new(&project_dir, runtime, &compose_path);
+    if let Some(arch) = &arch {
+        runner = runner.file(arch);
+    }
+    runner.scale(&scales)?;
+    outln!("Serviços escalados.");
+    Ok(())
+}
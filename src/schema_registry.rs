@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Schema registry for Kafka clients that serialize with Avro or Protobuf.
+//!
+//! The Confluent serializers and schema registry clients
+//! (`kafka-avro-serializer`, `confluent_kafka.schema_registry`,
+//! `@kafkajs/confluent-schema-registry`, `SCHEMA_REGISTRY_URL`...) turn on
+//! the schema registry built into Redpanda (the `kafka` service, Confluent
+//! API compatible) on port 8081, and add `schema-registry-init`: a one-shot
+//! `curl` that registers the project's schemas once the registry answers.
+//! Each `.avsc` (Avro) and `.proto` (Protobuf) file of the repository is a
+//! subject named with the topic name strategy: `orders.avsc` is
+//! `orders-value`, `orders-key.avsc` stays `orders-key`. The request bodies
+//! are generated under `.dx/schema-registry/`; registering an unchanged
+//! schema again is a no-op for the registry, so reruns are harmless. Protobuf
+//! files importing other files of the project are left out: a plain
+//! registration can't resolve their references.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::detectors::{Keyword, exact, word};
+use crate::dev_services::DockerComposeConfig;
+
+pub const SERVICE: &str = "schema-registry-init";
+/// Port of the registry's API.
+pub const PORT: u16 = 8081;
+/// Directory of the script and request bodies, relative to `.dx`.
+pub const DIR: &str = "schema-registry";
+
+const KAFKA: &str = "kafka";
+const KAFKA_UI: &str = "kafka-ui";
+/// Listener flag of `redpanda start`.
+const REDPANDA_FLAG: &str = "--schema-registry-addr";
+/// Schema files above this size are not read.
+const MAX_SCHEMA_BYTES: u64 = 512 * 1024;
+const SKIP_DIRS: &[&str] = &[".dx", "node_modules", "target", "build", "dist", "vendor"];
+
+pub const KEYWORDS: &[Keyword] = &[
+    // Java/Kotlin (io.confluent)
+    word("kafka-avro-serializer"),
+    word("kafka-protobuf-serializer"),
+    word("kafka-json-schema-serializer"),
+    exact("KafkaAvroSerializer"),
+    exact("KafkaAvroDeserializer"),
+    exact("KafkaProtobufSerializer"),
+    exact("KafkaProtobufDeserializer"),
+    // Python, Node.js, Go, .NET and Rust clients
+    word("confluent_kafka.schema_registry"),
+    word("@kafkajs/confluent-schema-registry"),
+    word("confluent-kafka-go/v2/schemaregistry"),
+    word("github.com/riferrei/srclient"),
+    word("confluent.schemaregistry"),
+    word("schema_registry_converter"),
+    // Client configuration
+    word("schema.registry.url"),
+    exact("SCHEMA_REGISTRY_URL"),
+];
+
+/// Registers the subjects given as arguments, reading each body from
+/// `<subject>.json`.
+pub const SCRIPT: &str = r#"#!/bin/sh
+# Gerado pelo dx (dev-services); não edite.
+# Registra no Schema Registry os schemas do projeto passados como argumentos.
+set -e
+for subject in "$@"; do
+  curl -sS --fail --retry 30 --retry-connrefused --retry-delay 2 \
+    -X POST -H 'Content-Type: application/vnd.schemaregistry.v1+json' \
+    --data "@/schemas/$subject.json" \
+    "http://kafka:8081/subjects/$subject/versions"
+  echo " <- $subject"
+done
+"#;
+
+/// A schema of the project to register.
+#[derive(Debug)]
+pub struct Schema {
+    pub subject: String,
+    /// `schemaType` of the registry API (`AVRO`, `PROTOBUF`)
+    pub kind: &'static str,
+    /// Path relative to the project
+    pub file: String,
+    content: String,
+}
+
+/// Subject of the schema in `file` (topic name strategy).
+fn subject(file: &Path) -> Option<String> {
+    let stem = file.file_stem()?.to_str()?;
+    if stem.ends_with("-key") || stem.ends_with("-value") {
+        Some(stem.to_string())
+    } else {
+        Some(format!("{stem}-value"))
+    }
+}
+
+/// Whether the Protobuf `content` imports files other than the well-known
+/// types, which the registry knows.
+fn imports_project_files(content: &str) -> bool {
+    content.lines().any(|line| {
+        line.trim_start()
+            .strip_prefix("import ")
+            .is_some_and(|rest| !rest.contains("google/protobuf/"))
+    })
+}
+
+/// Avro and Protobuf schemas under `project_dir`, by subject. When two files
+/// map to the same subject, the first path wins.
+pub fn scan(project_dir: &Path) -> Vec<Schema> {
+    let walker = ignore::WalkBuilder::new(project_dir)
+        .filter_entry(|e| {
+            !(e.file_type().is_some_and(|t| t.is_dir())
+                && e.file_name()
+                    .to_str()
+                    .is_some_and(|n| SKIP_DIRS.contains(&n)))
+        })
+        .build();
+    let mut schemas: BTreeMap<String, Schema> = BTreeMap::new();
+    let mut entries: Vec<_> = walker.flatten().collect();
+    entries.sort_by(|a, b| a.path().cmp(b.path()));
+    for entry in entries {
+        let path = entry.path();
+        let kind = match path.extension().and_then(|e| e.to_str()) {
+            Some("avsc") => "AVRO",
+            Some("proto") => "PROTOBUF",
+            _ => continue,
+        };
+        if entry
+            .metadata()
+            .map_or(true, |m| m.len() > MAX_SCHEMA_BYTES)
+        {
+            continue;
+        }
+        let (Some(subject), Ok(content)) = (subject(path), fs::read_to_string(path)) else {
+            continue;
+        };
+        let file = path.strip_prefix(project_dir).unwrap_or(path);
+        let file = file.display().to_string().replace('\\', "/");
+        if kind == "PROTOBUF" && imports_project_files(&content) {
+            tracing::debug!(file, "schema Protobuf com imports não registrado");
+            continue;
+        }
+        if let Some(first) = schemas.get(&subject) {
+            tracing::warn!(
+                "{file} ignorado: o subject '{subject}' já vem de {}",
+                first.file
+            );
+            continue;
+        }
+        schemas.insert(
+            subject.clone(),
+            Schema {
+                subject,
+                kind,
+                file,
+                content,
+            },
+        );
+    }
+    schemas.into_values().collect()
+}
+
+/// Command of `schema-registry-init` registering `subjects`.
+pub fn init_command(subjects: &[String]) -> String {
+    let mut command = "sh /schemas/register.sh".to_string();
+    for subject in subjects {
+        command.push(' ');
+        command.push_str(subject);
+    }
+    command
+}
+
+/// Subjects registered by `command` (see [`init_command`]).
+pub fn command_subjects(command: &str) -> Vec<&str> {
+    command.split_whitespace().skip(2).collect()
+}
+
+/// Request bodies of the project's schemas, mounted by `schema-registry-init`
+/// next to [`SCRIPT`] (paths relative to `.dx`).
+pub fn bodies(project_dir: &Path) -> Vec<(String, String)> {
+    scan(project_dir)
+        .into_iter()
+        .map(|schema| {
+            let body = serde_json::json!({ "schemaType": schema.kind, "schema": schema.content });
+            (
+                format!("{DIR}/{}.json", schema.subject),
+                format!("{body}\n"),
+            )
+        })
+        .collect()
+}
+
+/// Turn on the registry of the Kafka service of `config` and point
+/// `schema-registry-init` (when there is one) at the project's schemas.
+pub fn apply(project_dir: &Path, config: &mut DockerComposeConfig) {
+    if !config.services.contains_key(SERVICE) {
+        return;
+    }
+    // `SCHEMA_REGISTRY_URL` alone still needs the broker
+    if !config.services.contains_key(KAFKA)
+        && let Some(detector) = crate::detectors::for_service(KAFKA)
+    {
+        crate::dev_services::provision(config, detector.as_ref());
+    }
+    if let Some(kafka) = config.services.get_mut(KAFKA) {
+        if let Some(command) = &mut kafka.command
+            && command.starts_with("redpanda start")
+            && !command.contains(REDPANDA_FLAG)
+        {
+            command.push_str(&format!(" {REDPANDA_FLAG} 0.0.0.0:{PORT}"));
+        }
+        if !kafka.ports.contains(&PORT) {
+            kafka.ports.push(PORT);
+        }
+    }
+    if let Some(ui) = config.services.get_mut(KAFKA_UI) {
+        ui.env.insert(
+            "KAFKA_CLUSTERS_0_SCHEMAREGISTRY".into(),
+            format!("http://{KAFKA}:{PORT}"),
+        );
+    }
+
+    let subjects: Vec<String> = scan(project_dir).into_iter().map(|s| s.subject).collect();
+    if subjects.is_empty() {
+        config.services.remove(SERVICE);
+    } else if let Some(init) = config.services.get_mut(SERVICE) {
+        init.command = Some(init_command(&subjects));
+    }
+}
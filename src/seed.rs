@@ -0,0 +1,603 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Seed data of the Dev Services (`dx dev-services seed`).
+//!
+//! The files under `.dx/seed/` run inside the database containers, with each
+//! database's own client, through Compose's `exec` (with the project's runtime,
+//! see [`crate::compose`], on the remote engine of the last `run` or, with
+//! `--instance`, in a named instance): `*.sql` with `psql`/`mysql` in
+//! PostgreSQL or MySQL, `*.js` with `mongosh` in MongoDB and `*.cypher` with
+//! `cypher-shell` in Neo4j. A file directly in `.dx/seed/` goes to the only
+//! service of its kind; `.dx/seed/<service>/` targets one service when the
+//! manifest has several. Each service gets its files in file name order
+//! (`01-users.sql` before `02-orders.sql`), services in the order of
+//! `--service` or of the manifest. With `--schema`, fake records generated from
+//! a JSON Schema or SQL DDL (see `datagen`) are inserted instead; the tables
+//! must already exist (`dx dev-services migrate`).
+//!
+//! Every seed applied is recorded in the database itself (table or collection
+//! `dx_seed`, nodes `:DxSeed`) with a checksum of its content, in the same run
+//! as the seed: running again only applies new files, a database recreated
+//! from an empty volume is seeded again, and a file changed after it was
+//! applied is reported and left alone unless `--force`.
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::Serialize;
+
+use crate::compose::{Runner, Runtime};
+use crate::datagen;
+use crate::dev_services::{DatabaseEngine, ManifestService};
+use crate::error::{DxError, DxResult, IoContext};
+use crate::snapshots::{container, mongo_auth, mysql_script};
+
+/// Seed files, relative to `.dx`.
+pub const DIR: &str = "seed";
+/// Table, collection or node label recording the applied seeds.
+const MARKER: &str = "dx_seed";
+
+/// Database that runs seeds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Engine {
+    Postgres,
+    Mysql,
+    Mongo,
+    Neo4j,
+}
+
+impl Engine {
+    fn of(service: &ManifestService) -> Option<Engine> {
+        if let Some(db) = service.database() {
+            return Some(match db.engine {
+                DatabaseEngine::Postgres => Engine::Postgres,
+                DatabaseEngine::Mysql => Engine::Mysql,
+            });
+        }
+        let is = |name: &str| {
+            service.name == name
+                || service.image.starts_with(&format!("{name}:"))
+                || service.image.contains(&format!("/{name}:"))
+        };
+        if is("mongo") || is("mongodb") {
+            Some(Engine::Mongo)
+        } else if is("neo4j") {
+            Some(Engine::Neo4j)
+        } else {
+            None
+        }
+    }
+
+    /// Extension of its seed files.
+    fn extension(self) -> &'static str {
+        match self {
+            Engine::Postgres | Engine::Mysql => "sql",
+            Engine::Mongo => "js",
+            Engine::Neo4j => "cypher",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Engine::Postgres => "PostgreSQL",
+            Engine::Mysql => "MySQL",
+            Engine::Mongo => "MongoDB",
+            Engine::Neo4j => "Neo4j",
+        }
+    }
+
+    fn client(self) -> &'static str {
+        match self {
+            Engine::Postgres => "psql",
+            Engine::Mysql => "mysql",
+            Engine::Mongo => "mongosh",
+            Engine::Neo4j => "cypher-shell",
+        }
+    }
+
+    /// Statement recording `seed` as applied, run right after it.
+    fn mark(self, seed: &Seed) -> String {
+        let sql = |text: &str| text.replace('\\', "\\\\").replace('\'', "''");
+        let (name, checksum) = (&seed.name, &seed.checksum);
+        match self {
+            Engine::Postgres => format!(
+                "INSERT INTO {MARKER} (file, checksum) VALUES ('{}', '{checksum}') \
+                 ON CONFLICT (file) DO UPDATE SET checksum = EXCLUDED.checksum, applied_at = now();\n",
+                name.replace('\'', "''")
+            ),
+            Engine::Mysql => format!(
+                "REPLACE INTO {MARKER} (file, checksum) VALUES ('{}', '{checksum}');\n",
+                sql(name)
+            ),
+            Engine::Mongo => format!(
+                "db.{MARKER}.replaceOne({{ _id: {0} }}, {{ _id: {0}, checksum: '{checksum}', applied_at: new Date() }}, {{ upsert: true }});\n",
+                serde_json::Value::from(name.as_str())
+            ),
+            Engine::Neo4j => format!(
+                "MERGE (s:DxSeed {{file: '{}'}}) SET s.checksum = '{checksum}', s.applied_at = datetime();\n",
+                name.replace('\\', "\\\\").replace('\'', "\\'")
+            ),
+        }
+    }
+}
+
+/// A seed for one service: a file or the generated records.
+#[derive(Debug, Serialize)]
+struct Seed {
+    /// Path relative to `.dx/seed` (`postgres/01-users.sql`) or
+    /// `schema:<file>`; the key of its marker
+    name: String,
+    checksum: String,
+    status: Status,
+    #[serde(skip)]
+    content: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    /// Not run (`--dry-run`)
+    Pending,
+    Applied,
+    /// Recorded with the same checksum
+    Skipped,
+    /// Recorded with another checksum and not reapplied
+    Changed,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Status::Pending => "a aplicar",
+            Status::Applied => "aplicado",
+            Status::Skipped => "já aplicado",
+            Status::Changed => "alterado desde que foi aplicado; use --force para reaplicar",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Target<'a> {
+    service: &'a str,
+    engine: Engine,
+    seeds: Vec<Seed>,
+}
+
+impl Seed {
+    fn new(name: String, content: String) -> Seed {
+        Seed {
+            checksum: crate::sha256::hex(content.as_bytes()),
+            name,
+            status: Status::Pending,
+            content,
+        }
+    }
+}
+
+/// The only service among `services` that runs `engines`' files.
+fn only<'a>(
+    services: &'a [(&'a ManifestService, Engine)],
+    engines: &[Engine],
+    what: &str,
+) -> DxResult<&'a (&'a ManifestService, Engine)> {
+    let matching: Vec<_> = services
+        .iter()
+        .filter(|(_, e)| engines.contains(e))
+        .collect();
+    match matching.as_slice() {
+        [one] => Ok(one),
+        [] => Err(DxError::NotFound(format!(
+            "Nenhum serviço {} no manifesto para {what}.",
+            engines
+                .iter()
+                .map(|e| e.label())
+                .collect::<Vec<_>>()
+                .join("/")
+        ))),
+        many => Err(DxError::InvalidInput(format!(
+            "Mais de um serviço pode receber {what} ({}).\nDica: mova o arquivo para {DIR}/<serviço>/ ou use --service.",
+            many.iter()
+                .map(|(s, _)| s.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// Seed files of `.dx/seed`, by service name. Files outside a service
+/// directory go to the only one of `selected` of their kind.
+fn files(
+    seed_dir: &Path,
+    services: &[(&ManifestService, Engine)],
+    selected: &[(&ManifestService, Engine)],
+) -> DxResult<BTreeMap<String, Vec<Seed>>> {
+    let mut found: Vec<(String, PathBuf)> = Vec::new();
+    let entries = |dir: &Path| -> DxResult<Vec<PathBuf>> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+            .context(format!("Erro ao ler {}", dir.display()))?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                !p.file_name()
+                    .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+            })
+            .collect();
+        paths.sort();
+        Ok(paths)
+    };
+    for path in entries(seed_dir)? {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        if path.is_dir() {
+            let Some((service, engine)) = services.iter().find(|(s, _)| s.name == file_name) else {
+                return Err(DxError::InvalidInput(format!(
+                    "{} não corresponde a um banco de dados do manifesto.",
+                    path.display()
+                )));
+            };
+            for file in entries(&path)? {
+                let ext = file.extension().and_then(|e| e.to_str());
+                if ext.is_some_and(|ext| ["sql", "js", "cypher"].contains(&ext))
+                    && ext != Some(engine.extension())
+                {
+                    return Err(DxError::InvalidInput(format!(
+                        "{} não é um seed de {} (use arquivos .{}).",
+                        file.display(),
+                        engine.label(),
+                        engine.extension()
+                    )));
+                }
+                if ext == Some(engine.extension()) {
+                    found.push((service.name.clone(), file));
+                }
+            }
+            continue;
+        }
+        let engines: &[Engine] = match path.extension().and_then(|e| e.to_str()) {
+            Some("sql") => &[Engine::Postgres, Engine::Mysql],
+            Some("js") => &[Engine::Mongo],
+            Some("cypher") => &[Engine::Neo4j],
+            _ => continue,
+        };
+        // Meant for a service left out by --service
+        let kind =
+            |list: &[(&ManifestService, Engine)]| list.iter().any(|(_, e)| engines.contains(e));
+        if !kind(selected) && kind(services) {
+            continue;
+        }
+        let (service, _) = only(selected, engines, &file_name)?;
+        found.push((service.name.clone(), path));
+    }
+
+    let mut by_service: BTreeMap<String, Vec<(String, Seed)>> = BTreeMap::new();
+    for (service, path) in found {
+        let content =
+            fs::read_to_string(&path).context(format!("Erro ao ler {}", path.display()))?;
+        let name = path
+            .strip_prefix(seed_dir)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        by_service
+            .entry(service)
+            .or_default()
+            .push((file_name.into_owned(), Seed::new(name, content)));
+    }
+    // File name order, wherever the file is
+    Ok(by_service
+        .into_iter()
+        .map(|(service, mut seeds)| {
+            seeds.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
+            (service, seeds.into_iter().map(|(_, s)| s).collect())
+        })
+        .collect())
+}
+
+/// Script inserting the generated `data` into a service of `engine`.
+fn generated(engine: Engine, data: &datagen::Dataset) -> DxResult<String> {
+    match engine {
+        Engine::Postgres | Engine::Mysql => Ok(datagen::to_sql(data)),
+        Engine::Mongo => Ok(data
+            .iter()
+            .map(|(collection, rows)| {
+                format!(
+                    "db.getCollection({}).insertMany({});\n",
+                    serde_json::Value::from(collection.as_str()),
+                    serde_json::to_string(rows).unwrap_or_default()
+                )
+            })
+            .collect()),
+        Engine::Neo4j => Err(DxError::Unsupported(
+            "Dados gerados por --schema vão para PostgreSQL, MySQL ou MongoDB; use arquivos .cypher para o Neo4j.".to_string(),
+        )),
+    }
+}
+
+/// `exec -T` into the service with its client, ready for a script on stdin
+/// or, with `query`, for one statement.
+fn client(
+    runner: &Runner,
+    service: &ManifestService,
+    engine: Engine,
+    query: Option<&str>,
+) -> DxResult<Command> {
+    let name = service.name.as_str();
+    let mut command;
+    match engine {
+        Engine::Postgres => {
+            let db = service.database().expect("database service");
+            command = runner.exec(name, &[("PGPASSWORD", &db.password)])?;
+            command
+                .args(["psql", "-X", "-q", "-v", "ON_ERROR_STOP=1", "-U"])
+                .arg(&db.user)
+                .arg("-d")
+                .arg(&db.name);
+            match query {
+                Some(query) => command.args(["-tA", "-c", query]),
+                // One transaction: the seed and its marker or neither
+                None => command.arg("-1"),
+            };
+        }
+        Engine::Mysql => {
+            let db = service.database().expect("database service");
+            command = runner.exec(name, &[("MYSQL_PWD", &db.password)])?;
+            command
+                .args(["sh", "-c"])
+                .arg(mysql_script(["mariadb", "mysql"]))
+                .arg("sh")
+                .arg(format!("-u{}", db.user))
+                .arg(&db.name);
+            if let Some(query) = query {
+                command.args(["-N", "-B", "-e", query]);
+            }
+        }
+        Engine::Mongo => {
+            let db = service
+                .env
+                .get("MONGO_INITDB_DATABASE")
+                .map_or("test", String::as_str);
+            command = runner.exec(name, &[])?;
+            command
+                .args(["mongosh", "--quiet"])
+                .args(mongo_auth(service))
+                .arg(db);
+            match query {
+                Some(query) => command.args(["--eval", query]),
+                // Stops at the first error, unlike the REPL on stdin
+                None => command.args(["--file", "/dev/stdin"]),
+            };
+        }
+        Engine::Neo4j => {
+            command = runner.exec(name, &[])?;
+            command.args(["cypher-shell", "--format", "plain"]);
+            if let Some((user, password)) = service
+                .env
+                .get("NEO4J_AUTH")
+                .and_then(|auth| auth.split_once('/'))
+            {
+                command.args(["-u", user, "-p", password]);
+            }
+            if let Some(query) = query {
+                command.arg(query);
+            }
+        }
+    }
+    Ok(command)
+}
+
+/// Run `command` with `stdin`, failing with its stderr.
+fn run(mut command: Command, what: &str, stdin: &str) -> DxResult<String> {
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    tracing::debug!("executando: {command:?}");
+    let mut child = command.spawn().map_err(|e| {
+        DxError::Tool(format!(
+            "Erro ao executar '{}': {e}",
+            command.get_program().to_string_lossy()
+        ))
+    })?;
+    if let Some(mut input) = child.stdin.take() {
+        // A client that exits early closes the pipe; its stderr tells why
+        let _ = input.write_all(stdin.as_bytes());
+    }
+    let output = child
+        .wait_with_output()
+        .context(format!("Erro ao executar {what}"))?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+    Err(DxError::Tool(format!(
+        "{what} falhou ({}): {}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr).trim()
+    )))
+}
+
+/// Seeds recorded in the service's database, as name → checksum.
+fn applied(
+    runner: &Runner,
+    service: &ManifestService,
+    engine: Engine,
+) -> DxResult<BTreeMap<String, String>> {
+    let query = match engine {
+        Engine::Postgres => format!(
+            "CREATE TABLE IF NOT EXISTS {MARKER} (file TEXT PRIMARY KEY, checksum TEXT NOT NULL, applied_at TIMESTAMPTZ NOT NULL DEFAULT now()); \
+             SELECT file || ' ' || checksum FROM {MARKER}"
+        ),
+        Engine::Mysql => format!(
+            "CREATE TABLE IF NOT EXISTS {MARKER} (file VARCHAR(255) PRIMARY KEY, checksum VARCHAR(64) NOT NULL, applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP); \
+             SELECT CONCAT(file, ' ', checksum) FROM {MARKER}"
+        ),
+        Engine::Mongo => {
+            format!("db.{MARKER}.find().forEach(s => print(s._id + ' ' + s.checksum))")
+        }
+        Engine::Neo4j => "MATCH (s:DxSeed) RETURN s.file + ' ' + s.checksum AS seed".to_string(),
+    };
+    let command = client(runner, service, engine, Some(&query))?;
+    let output = run(command, engine.client(), "")?;
+    let lines = output.lines().map(|line| line.trim().trim_matches('"'));
+    Ok(lines
+        // cypher-shell prints the column name first
+        .skip(usize::from(engine == Engine::Neo4j))
+        .filter_map(|line| line.rsplit_once(' '))
+        .map(|(name, checksum)| (name.to_string(), checksum.to_string()))
+        .collect())
+}
+
+pub struct SeedOptions {
+    /// Only these services, in this order (all, in manifest order, when empty)
+    pub services: Vec<String>,
+    /// Generate the records from this schema instead of reading `.dx/seed`
+    pub schema: Option<PathBuf>,
+    pub count: usize,
+    pub seed: u64,
+    pub locale: datagen::Locale,
+    /// Reapply seeds already recorded
+    pub force: bool,
+    /// Only show what would run where
+    pub dry_run: bool,
+    /// Named instance (`run --instance`) to seed instead of the default stack
+    pub instance: Option<String>,
+    /// `--runtime`
+    pub runtime: Option<Runtime>,
+}
+
+/// `dx dev-services seed`.
+pub fn seed(dir: Option<PathBuf>, opts: SeedOptions) -> DxResult {
+    let (project_dir, compose_path, manifest) = crate::snapshots::load(dir)?;
+    let databases: Vec<(&ManifestService, Engine)> = manifest
+        .iter()
+        .filter_map(|s| Engine::of(s).map(|e| (s, e)))
+        .collect();
+    if let Some(unknown) = opts
+        .services
+        .iter()
+        .find(|w| !databases.iter().any(|(s, _)| s.name == **w))
+    {
+        return Err(DxError::InvalidInput(format!(
+            "Serviço '{unknown}' não é um banco de dados do manifesto (PostgreSQL, MySQL, MongoDB ou Neo4j)."
+        )));
+    }
+    let selected: Vec<(&ManifestService, Engine)> = if opts.services.is_empty() {
+        databases.clone()
+    } else {
+        opts.services
+            .iter()
+            .filter_map(|w| databases.iter().find(|(s, _)| s.name == *w).copied())
+            .collect()
+    };
+
+    let seed_dir = project_dir.join(".dx").join(DIR);
+    let mut seeds = match &opts.schema {
+        Some(schema) => {
+            let schema_path = project_dir.join(schema);
+            let (_, tables) = datagen::load(&schema_path)?;
+            let data = datagen::generate(&tables, opts.count, opts.seed, opts.locale)?;
+            let what = format!("os dados de {}", schema.display());
+            let (service, engine) = only(
+                &selected,
+                &[Engine::Postgres, Engine::Mysql, Engine::Mongo],
+                &what,
+            )?;
+            let name = format!("schema:{}", schema.display()).replace('\\', "/");
+            let content = generated(*engine, &data)?;
+            BTreeMap::from([(service.name.clone(), vec![Seed::new(name, content)])])
+        }
+        None if seed_dir.is_dir() => files(&seed_dir, &databases, &selected)?,
+        None => BTreeMap::new(),
+    };
+    let mut targets: Vec<Target> = selected
+        .iter()
+        .filter_map(|(service, engine)| {
+            seeds.remove(&service.name).map(|seeds| Target {
+                service: &service.name,
+                engine: *engine,
+                seeds,
+            })
+        })
+        .collect();
+    if targets.is_empty() {
+        return Err(DxError::NotFound(format!(
+            "Nenhum seed em {}.\nDica: crie arquivos .sql (PostgreSQL/MySQL), .js (MongoDB) ou .cypher (Neo4j) em {} (ou em {}/<serviço>/) ou gere dados com --schema.",
+            seed_dir.display(),
+            seed_dir.display(),
+            seed_dir.display()
+        )));
+    }
+
+    if !opts.dry_run {
+        let runtime = Runtime::resolve(&project_dir, opts.runtime)?;
+        let stack = match &opts.instance {
+            Some(name) => crate::instances::existing(&project_dir, name)?,
+            None => crate::remote::manifest(&project_dir, &compose_path),
+        };
+        let runner = Runner::new(&project_dir, runtime, &stack);
+        for target in &mut targets {
+            let (service, engine) = (
+                manifest
+                    .iter()
+                    .find(|s| s.name == target.service)
+                    .expect("manifest service"),
+                target.engine,
+            );
+            container(&runner, target.service, false)?;
+            let applied = applied(&runner, service, engine)?;
+            for seed in &mut target.seeds {
+                seed.status = match applied.get(&seed.name) {
+                    Some(checksum) if *checksum == seed.checksum && !opts.force => Status::Skipped,
+                    Some(_) if !opts.force => Status::Changed,
+                    _ => Status::Pending,
+                };
+                if seed.status != Status::Pending {
+                    continue;
+                }
+                let script = format!("{}\n{}", seed.content, engine.mark(seed));
+                crate::progress::timed(&format!("seed {}", seed.name), || {
+                    run(
+                        client(&runner, service, engine, None)?,
+                        &format!("{} ({})", engine.client(), seed.name),
+                        &script,
+                    )
+                })?;
+                seed.status = Status::Applied;
+            }
+        }
+    }
+
+    crate::output::set_data(serde_json::json!({
+        "dry_run": opts.dry_run,
+        "services": targets,
+    }));
+    for target in &targets {
+        outln!("{} ({}):", target.service, target.engine.label());
+        for seed in &target.seeds {
+            outln!("- {}: {}", seed.name, seed.status.label());
+        }
+    }
+    let count = |status: Status| {
+        targets
+            .iter()
+            .flat_map(|t| &t.seeds)
+            .filter(|s| s.status == status)
+            .count()
+    };
+    if opts.dry_run {
+        outln!(
+            "{} seed(s) seriam aplicados, exceto os já registrados no banco.",
+            count(Status::Pending)
+        );
+    } else {
+        outln!(
+            "{} seed(s) aplicado(s), {} já aplicado(s), {} alterado(s).",
+            count(Status::Applied),
+            count(Status::Skipped),
+            count(Status::Changed)
+        );
+    }
+    Ok(())
+}
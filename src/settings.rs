@@ -0,0 +1,326 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Configuration of dx itself (`dx.toml`).
+//!
+//! dx reads `dx.toml` from the project root and from the user config
+//! directory (`$XDG_CONFIG_HOME/dx/dx.toml`, `%APPDATA%\dx\dx.toml` or
+//! `~/.config/dx/dx.toml`). Project keys override user keys (tables are merged
+//! key by key, arrays are replaced) and command-line flags override both.
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use toml_edit::{DocumentMut, Item, Table};
+
+use crate::error::{DxError, DxResult};
+
+pub const FILE_NAME: &str = "dx.toml";
+
+/// Report file name; saved under `.dx` unless another path is configured.
+pub const DEFAULT_REPORT: &str = "analyzer-report.md";
+
+/// Overrides the user-level file location (mostly for tests and CI).
+pub const USER_CONFIG_ENV: &str = "DX_CONFIG";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub project: ProjectSettings,
+    pub telemetry: TelemetrySettings,
+    pub analyzer: AnalyzerSettings,
+    pub clean: CleanSettings,
+    /// Image pins by service name, e.g. `postgres = "postgres:16.4-alpine"`.
+    pub images: BTreeMap<String, String>,
+    pub registries: RegistrySettings,
+    pub network: NetworkSettings,
+    pub dev_services: DevServicesSettings,
+    pub updates: UpdateSettings,
+    pub pages: PagesSettings,
+    pub docs: DocsSettings,
+}
+
+/// Overrides for the language/framework detection (dashboards, reports).
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectSettings {
+    pub language: Option<String>,
+    pub framework: Option<String>,
+}
+
+/// Generated documentation (reports, runbooks, README sections).
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DocsSettings {
+    /// Language of the generated prose, independent of the CLI messages.
+    pub language: crate::i18n::DocLanguage,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetrySettings {
+    pub level: TelemetryLevel,
+}
+
+/// Which telemetry services `dx dev-services` adds to the manifest.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TelemetryLevel {
+    /// Collector, Prometheus, Grafana, Loki and Tempo.
+    #[default]
+    Full,
+    /// Collector, Prometheus and Grafana (no logs or traces backends).
+    Metrics,
+    /// No telemetry services.
+    Off,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnalyzerSettings {
+    /// Where `dx analyzer` and `dx dev-services` save the report, relative to
+    /// the project (default: `.dx/analyzer-report.md`).
+    pub report_path: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CleanSettings {
+    /// Globs added to `--exclude`.
+    pub exclude: Vec<String>,
+    pub respect_gitignore: bool,
+}
+
+/// Base URLs of package registry mirrors, used instead of the public ones.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RegistrySettings {
+    pub npm: Option<String>,
+    pub crates: Option<String>,
+    pub pypi: Option<String>,
+    pub go: Option<String>,
+    pub maven: Option<String>,
+    pub packagist: Option<String>,
+    pub rubygems: Option<String>,
+    /// OSV.dev API, for `dx dev-dependencies audit`
+    pub osv: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DevServicesSettings {
+    /// Idle time after which `dx dev-services run` stops the containers
+    /// (e.g. `"2h"`; `"off"` disables it).
+    pub auto_stop: Option<String>,
+    /// How long `dx dev-services run` watches the logs of the new containers
+    /// for fatal errors (default `"10s"`; `"off"` disables it).
+    pub smoke_check: Option<String>,
+    /// Publish services whose host port is taken on this machine on a free
+    /// one (default `true`).
+    pub remap_ports: Option<bool>,
+    /// Healthchecks on the generated services, waited for by the services
+    /// that depend on them (default `true`).
+    pub healthchecks: Option<bool>,
+    /// Services left out of the manifest even when detected (e.g.
+    /// `["kafka-ui", "grafana"]`).
+    pub exclude: Vec<String>,
+}
+
+/// Where `dx dev-dependencies update --create-pr` pushes and opens the PR.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UpdateSettings {
+    /// Git remote to push to (default: `origin`).
+    pub remote: Option<String>,
+    /// Target branch of the PR (default: the current branch).
+    pub base: Option<String>,
+    /// Hosting of the remote, when its host doesn't tell (self-hosted).
+    pub provider: Option<GitProvider>,
+    /// API base URL, e.g. `https://git.example.com/api/v4`.
+    pub api_url: Option<String>,
+    /// `owner/repo` (GitLab: the project path), when the remote URL doesn't tell.
+    pub repository: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitProvider {
+    Github,
+    Gitlab,
+}
+
+/// Where `dx publish pages` publishes the project dashboard.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PagesSettings {
+    /// Branch served by GitHub Pages (default: `gh-pages`).
+    pub branch: Option<String>,
+    /// Git remote to push to (default: `origin`).
+    pub remote: Option<String>,
+    /// Built docs site, relative to the project (default: `site`, `book`, ...).
+    pub docs: Option<String>,
+}
+
+/// HTTP client limits (registries, policy bundles).
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkSettings {
+    /// Per-request timeout, in seconds.
+    pub timeout: u64,
+    /// Maximum number of requests in flight.
+    pub concurrency: usize,
+    /// Maximum requests per second to the same host (0 = unlimited).
+    pub rate_limit: u32,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        NetworkSettings {
+            timeout: 30,
+            concurrency: 8,
+            rate_limit: 10,
+        }
+    }
+}
+
+/// `dx.toml` of the project at `project_dir`.
+pub fn project_path(project_dir: &Path) -> PathBuf {
+    project_dir.join(FILE_NAME)
+}
+
+/// User-level `dx.toml`, shared by every project on the machine.
+pub fn user_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(USER_CONFIG_ENV).filter(|p| !p.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("dx").join(FILE_NAME));
+    }
+    if let Some(dir) = std::env::var_os("APPDATA").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("dx").join(FILE_NAME));
+    }
+    std::env::var_os("HOME")
+        .filter(|d| !d.is_empty())
+        .map(|home| {
+            PathBuf::from(home)
+                .join(".config")
+                .join("dx")
+                .join(FILE_NAME)
+        })
+}
+
+fn read(path: &Path) -> DxResult<Option<DocumentMut>> {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(source) => {
+            return Err(DxError::Io {
+                context: format!("Erro ao ler {}", path.display()),
+                source,
+            });
+        }
+    };
+    tracing::debug!(path = %path.display(), "configuração do dx carregada");
+    content
+        .parse::<DocumentMut>()
+        .map(Some)
+        .map_err(|e| DxError::InvalidInput(format!("{} inválido: {}", path.display(), e.message())))
+}
+
+/// Merge `over` into `base`: nested tables key by key, anything else replaced.
+fn merge(base: &mut Table, over: &Table) {
+    for (key, item) in over.iter() {
+        match (base.get_mut(key), item) {
+            (Some(Item::Table(b)), Item::Table(o)) => merge(b, o),
+            _ => {
+                base.insert(key, item.clone());
+            }
+        }
+    }
+}
+
+/// Load the effective settings for `project_dir` (user file, then project file).
+pub fn load(project_dir: &Path) -> DxResult<Settings> {
+    let mut sources = Vec::new();
+    let mut doc = DocumentMut::new();
+    let paths = user_path().into_iter().chain([project_path(project_dir)]);
+    for path in paths {
+        if let Some(layer) = read(&path)? {
+            merge(doc.as_table_mut(), layer.as_table());
+            sources.push(path.display().to_string());
+        }
+    }
+    if sources.is_empty() {
+        return Ok(Settings::default());
+    }
+    toml_edit::de::from_document(doc).map_err(|e| {
+        DxError::InvalidInput(format!(
+            "Configuração inválida ({}): {}",
+            sources.join(", "),
+            e.message()
+        ))
+    })
+}
+
+/// Like [`load`], for code paths that can't fail: an invalid `dx.toml` is
+/// logged and the defaults are used.
+pub fn get(project_dir: &Path) -> Settings {
+    load(project_dir).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "ignorando dx.toml inválido");
+        Settings::default()
+    })
+}
+
+/// Add `services` to `[dev_services] exclude` of the project's dx.toml
+/// (created if missing), keeping the rest of the file as written. Returns the
+/// services that weren't excluded yet.
+pub fn exclude_services(project_dir: &Path, services: &[String]) -> DxResult<Vec<String>> {
+    let path = project_path(project_dir);
+    let mut doc = read(&path)?.unwrap_or_default();
+    let table = doc
+        .entry("dev_services")
+        .or_insert_with(toml_edit::table)
+        .as_table_mut()
+        .ok_or_else(|| {
+            DxError::InvalidInput(format!(
+                "{}: [dev_services] deve ser uma tabela",
+                path.display()
+            ))
+        })?;
+    let list = table
+        .entry("exclude")
+        .or_insert_with(|| toml_edit::value(toml_edit::Array::new()))
+        .as_array_mut()
+        .ok_or_else(|| {
+            DxError::InvalidInput(format!(
+                "{}: [dev_services] exclude deve ser uma lista",
+                path.display()
+            ))
+        })?;
+    let mut added = Vec::new();
+    for service in services {
+        if !list.iter().any(|v| v.as_str() == Some(service)) && !added.contains(service) {
+            list.push(service.as_str());
+            added.push(service.clone());
+        }
+    }
+    if !added.is_empty() {
+        fs::write(&path, doc.to_string()).map_err(|source| DxError::Io {
+            context: format!("Erro ao gravar {}", path.display()),
+            source,
+        })?;
+    }
+    Ok(added)
+}
+
+impl Settings {
+    /// Where the analyzer report of `project_dir` is saved: the configured
+    /// `report_path` (relative to the project) or `.dx/analyzer-report.md`.
+    pub fn report_path(&self, project_dir: &Path) -> PathBuf {
+        match self.analyzer.report_path.as_deref() {
+            Some(path) if path != DEFAULT_REPORT => project_dir.join(path),
+            _ => project_dir.join(".dx").join(DEFAULT_REPORT),
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Log smoke checks after `dx dev-services run`.
+//!
+//! `up -d` returns as soon as the containers are started, before a database
+//! finds its port taken or a broker rejects its credentials. So `run` watches
+//! the new containers for `[dev_services] smoke_check` (10s by default, `off`
+//! disables it), then reads the logs they wrote since `up` and their state:
+//! known fatal messages (port already in use, authentication failure, out of
+//! memory) and containers that stopped with an error or keep restarting fail
+//! the service. Each service gets a one-line verdict, and `run` exits with
+//! code 1 when any failed. Without containers to look at (Compose can't list
+//! them), there is nothing to check.
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::compose::{Runner, Runtime};
+use crate::error::{DxError, DxResult};
+use crate::logs::Prefixer;
+
+const DEFAULT_WINDOW: Duration = Duration::from_secs(10);
+/// Longest log line quoted in a verdict
+const QUOTE_LIMIT: usize = 120;
+
+/// Why a service failed the check.
+#[derive(Debug)]
+enum Failure {
+    PortInUse(String),
+    Authentication(String),
+    OutOfMemory(String),
+    Exited(i64),
+    Restarting,
+}
+
+impl Failure {
+    /// Failure announced by a log line, if any.
+    fn from_log(line: &str) -> Option<Failure> {
+        const PORT: &[&str] = &[
+            "address already in use",
+            "port is already allocated",
+            "eaddrinuse",
+            "failed to bind",
+        ];
+        const AUTHENTICATION: &[&str] = &[
+            "authentication failed",
+            "access denied for user",
+            "noauth authentication required",
+            "wrongpass",
+            "invalid credentials",
+        ];
+        const MEMORY: &[&str] = &[
+            "out of memory",
+            "outofmemoryerror",
+            "cannot allocate memory",
+            "java heap space",
+        ];
+        let lower = line.to_ascii_lowercase();
+        let matches = |patterns: &[&str]| patterns.iter().any(|p| lower.contains(p));
+        let quote = || {
+            let line = line.trim();
+            match line.char_indices().nth(QUOTE_LIMIT) {
+                Some((end, _)) => format!("{}...", &line[..end]),
+                None => line.to_string(),
+            }
+        };
+        if matches(PORT) {
+            Some(Failure::PortInUse(quote()))
+        } else if matches(AUTHENTICATION) {
+            Some(Failure::Authentication(quote()))
+        } else if matches(MEMORY) {
+            Some(Failure::OutOfMemory(quote()))
+        } else {
+            None
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Failure::PortInUse(line) => format!("porta já em uso: {line}"),
+            Failure::Authentication(line) => format!("falha de autenticação: {line}"),
+            Failure::OutOfMemory(line) => format!("memória insuficiente: {line}"),
+            Failure::Exited(137) => {
+                "parou com código 137 (memória insuficiente ou encerrado)".to_string()
+            }
+            Failure::Exited(code) => format!("parou com código {code}"),
+            Failure::Restarting => "reiniciando em loop".to_string(),
+        }
+    }
+}
+
+/// How long to watch: `[dev_services] smoke_check`, or the default.
+pub fn window(project_dir: &Path) -> DxResult<Option<Duration>> {
+    match crate::settings::load(project_dir)?.dev_services.smoke_check {
+        Some(value) => crate::auto_stop::parse_duration(&value).map_err(|_| {
+            DxError::InvalidInput(format!(
+                "Duração inválida para smoke_check: '{value}' (ex.: 10s, 1m ou off)."
+            ))
+        }),
+        None => Ok(Some(DEFAULT_WINDOW)),
+    }
+}
+
+/// Services `up` started: those of the active profiles, or the `--service`
+/// ones.
+fn started_services(compose_path: &Path, profiles: &str, services: &[String]) -> Vec<String> {
+    let yaml = std::fs::read_to_string(compose_path).unwrap_or_default();
+    let active: Vec<&str> = profiles.split(',').collect();
+    crate::dev_services::manifest_services(&yaml)
+        .into_iter()
+        .filter(|s| {
+            s.profiles.is_empty() || s.profiles.iter().any(|p| active.contains(&p.as_str()))
+        })
+        .filter(|s| services.is_empty() || services.contains(&s.name))
+        .map(|s| s.name)
+        .collect()
+}
+
+/// Compose logs of `services` written in the last `since`.
+fn logs(runner: &Runner, since: Duration, services: &[String]) -> String {
+    runner.recent_logs(since, services).unwrap_or_else(|e| {
+        tracing::warn!("não foi possível ler os logs dos serviços: {e}");
+        String::new()
+    })
+}
+
+/// Watch the services started by `up` (at `started`) for `window` and
+/// report a verdict for each one; fails when any of them failed.
+pub fn check(
+    project_dir: &Path,
+    runtime: Runtime,
+    compose_path: &Path,
+    profiles: &str,
+    services: &[String],
+    window: Option<Duration>,
+    started: Instant,
+) -> DxResult {
+    let Some(window) = window else {
+        return Ok(());
+    };
+    let runner = Runner::new(project_dir, runtime, compose_path);
+    let Some(containers) = crate::status::containers(&runner) else {
+        tracing::debug!("Compose não listou os containers; verificação dos logs ignorada");
+        return Ok(());
+    };
+    let checked: Vec<String> = started_services(compose_path, profiles, services)
+        .into_iter()
+        .filter(|s| containers.contains_key(s))
+        .collect();
+    if checked.is_empty() {
+        return Ok(());
+    }
+
+    outln!(
+        "Verificando os logs dos serviços por {}...",
+        crate::auto_stop::format_duration(window)
+    );
+    std::thread::sleep(window);
+    let mut failures: BTreeMap<&str, Failure> = BTreeMap::new();
+    let prefixer = Prefixer::new(checked.clone());
+    for line in logs(&runner, started.elapsed(), &checked).lines() {
+        let Some((service, text)) = prefixer.parse(line) else {
+            continue;
+        };
+        // The first fatal message tells the cause
+        if let Some(service) = checked.iter().find(|s| *s == service)
+            && !failures.contains_key(service.as_str())
+            && let Some(failure) = Failure::from_log(text)
+        {
+            failures.insert(service, failure);
+        }
+    }
+    let containers = crate::status::containers(&runner).unwrap_or(containers);
+    for service in &checked {
+        let state = containers
+            .get(service)
+            .map(|c| (c.state.as_str(), c.exit_code));
+        let failure = match state {
+            Some(("exited" | "dead", code)) if code != 0 => Failure::Exited(code),
+            Some(("restarting", _)) => Failure::Restarting,
+            _ => continue,
+        };
+        failures.entry(service).or_insert(failure);
+    }
+
+    let width = checked.iter().map(|s| s.len()).max().unwrap_or(0);
+    for service in &checked {
+        match failures.get(service.as_str()) {
+            Some(failure) => outln!("  {service:<width$}  {}", failure.describe()),
+            None => outln!("  {service:<width$}  ok"),
+        }
+    }
+    if failures.is_empty() {
+        return Ok(());
+    }
+    let names: Vec<&str> = failures.keys().copied().collect();
+    Err(DxError::CheckFailed(format!(
+        "{} serviço(s) com erro depois de subir: {}.\nDica: veja os logs com 'dx dev-services logs {}'.",
+        names.len(),
+        names.join(", "),
+        names[0]
+    )))
+}
@@ -0,0 +1,668 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Data snapshots of the Dev Services (`dx dev-services snapshot`).
+//!
+//! A snapshot is a directory under `.dx/snapshots/<name>/` with one dump per
+//! service of `.dx/docker-compose.yml` and a `snapshot.json` describing how
+//! each was taken. Databases are dumped with their own tools (`pg_dumpall`,
+//! `mysqldump`/`mariadb-dump`, `mongodump`), Redis with a copy of its RDB
+//! file and any other service with named volumes with a tar of each volume.
+//! Stateless services are skipped. After a `run` on a remote engine (see
+//! [`crate::remote`]) the commands go to it, with the manifest sent there;
+//! the tar of the volumes mounts the snapshot directory, so it stays local.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::compose::{Runner, Runtime};
+use crate::dev_services::{DatabaseEngine, ManifestService};
+use crate::error::{DxError, DxResult, IoContext};
+
+/// Image used to read and write volumes for the generic method.
+const VOLUME_HELPER_IMAGE: &str = "alpine:3";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Method {
+    PgDumpall,
+    Mysqldump,
+    Mongodump,
+    RedisRdb,
+    VolumeTar,
+}
+
+impl Method {
+    fn label(self) -> &'static str {
+        match self {
+            Method::PgDumpall => "pg_dumpall",
+            Method::Mysqldump => "mysqldump",
+            Method::Mongodump => "mongodump",
+            Method::RedisRdb => "cópia do RDB",
+            Method::VolumeTar => "tar dos volumes",
+        }
+    }
+}
+
+/// How one service was saved.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    service: String,
+    method: Method,
+    /// Files in the snapshot directory
+    files: Vec<String>,
+    /// Container paths of the volumes (`volume-tar`), in the order of `files`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Metadata {
+    name: String,
+    /// Seconds since the Unix epoch
+    created_at: u64,
+    services: Vec<Entry>,
+}
+
+fn snapshots_dir(project_dir: &Path) -> PathBuf {
+    project_dir.join(".dx").join("snapshots")
+}
+
+fn validate_name(name: &str) -> DxResult<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(DxError::InvalidInput(format!(
+            "Nome de snapshot inválido: '{name}' (use letras, números, '-', '_' ou '.')."
+        )))
+    }
+}
+
+/// Project directory and the services of its manifest.
+pub fn load(dir: Option<PathBuf>) -> DxResult<(PathBuf, PathBuf, Vec<ManifestService>)> {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !project_dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            project_dir.display()
+        )));
+    }
+    let compose = project_dir.join(".dx").join("docker-compose.yml");
+    let yaml = fs::read_to_string(&compose).map_err(|_| {
+        DxError::NotFound(format!(
+            "Arquivo não encontrado: {}\nDica: gere o manifesto e suba os serviços com:\n  dx dev-services && dx dev-services run",
+            compose.display()
+        ))
+    })?;
+    let services = crate::dev_services::manifest_services(&yaml);
+    Ok((project_dir, compose, services))
+}
+
+/// Restrict `services` to the `--service` names, rejecting unknown ones.
+fn select<'a, T>(
+    items: &'a [T],
+    name: impl Fn(&T) -> &str,
+    wanted: &[String],
+) -> DxResult<Vec<&'a T>> {
+    if let Some(unknown) = wanted
+        .iter()
+        .find(|w| !items.iter().any(|i| name(i) == w.as_str()))
+    {
+        return Err(DxError::InvalidInput(format!(
+            "Serviço '{unknown}' não encontrado."
+        )));
+    }
+    Ok(items
+        .iter()
+        .filter(|i| wanted.is_empty() || wanted.iter().any(|w| w == name(i)))
+        .collect())
+}
+
+fn is_named_volume(source: &str) -> bool {
+    !source.is_empty() && !source.starts_with(['.', '/', '~', '$'])
+}
+
+fn method(service: &ManifestService) -> Option<Method> {
+    let is = |names: &[&str]| {
+        names.iter().any(|n| {
+            service.name == *n
+                || service.image.starts_with(&format!("{n}:"))
+                || service.image.ends_with(&format!("/{n}"))
+                || service.image.contains(&format!("/{n}:"))
+        })
+    };
+    if let Some(db) = service.database() {
+        return Some(match db.engine {
+            DatabaseEngine::Postgres => Method::PgDumpall,
+            DatabaseEngine::Mysql => Method::Mysqldump,
+        });
+    }
+    if is(&["mongo", "mongodb"]) {
+        Some(Method::Mongodump)
+    } else if is(&["redis", "valkey"]) {
+        Some(Method::RedisRdb)
+    } else if service.volumes.iter().any(|(s, _)| is_named_volume(s)) {
+        Some(Method::VolumeTar)
+    } else {
+        None
+    }
+}
+
+/// Run `command`, failing with its stderr. `stdin`/`stdout` redirect to files.
+fn run(
+    mut command: Command,
+    what: &str,
+    stdin: Option<&Path>,
+    stdout: Option<&Path>,
+) -> DxResult<String> {
+    match stdin {
+        Some(path) => {
+            command.stdin(fs::File::open(path).context(format!("Erro ao ler {}", path.display()))?)
+        }
+        None => command.stdin(Stdio::null()),
+    };
+    match stdout {
+        Some(path) => command
+            .stdout(fs::File::create(path).context(format!("Erro ao criar {}", path.display()))?),
+        None => command.stdout(Stdio::piped()),
+    };
+    tracing::debug!("executando: {command:?}");
+    let output = command.stderr(Stdio::piped()).output().map_err(|e| {
+        DxError::Tool(format!(
+            "Erro ao executar '{}': {e}",
+            command.get_program().to_string_lossy()
+        ))
+    })?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(DxError::Tool(format!(
+        "{what} falhou ({}): {}",
+        output.status,
+        stderr.trim()
+    )))
+}
+
+/// Id of the service's container; `all` includes stopped ones.
+pub fn container(runner: &Runner, service: &str, all: bool) -> DxResult<String> {
+    let mut command = runner.command(&["ps", "-q"])?;
+    if all {
+        command.arg("-a");
+    }
+    command.arg(service);
+    let id = run(command, "compose ps", None, None)?;
+    match id.lines().next() {
+        Some(id) if !id.is_empty() => Ok(id.to_string()),
+        _ => Err(DxError::Tool(format!(
+            "O serviço '{service}' não está em execução.\nDica: suba os serviços com: dx dev-services run"
+        ))),
+    }
+}
+
+fn stop_start(runner: &Runner, service: &str, action: &str) -> DxResult<()> {
+    let command = runner.command(&[action, service])?;
+    run(command, &format!("compose {action}"), None, None).map(|_| ())
+}
+
+/// `run` of the runtime with the service's volumes and the snapshot
+/// directory at `/backup`.
+fn volume_helper(runner: &Runner, container: &str, snapshot_dir: &Path, script: &str) -> Command {
+    let mut command = runner.runtime_command();
+    command
+        .args(["run", "--rm", "--volumes-from", container, "-v"])
+        .arg(format!("{}:/backup", snapshot_dir.display()))
+        .args([VOLUME_HELPER_IMAGE, "sh", "-c", script]);
+    command
+}
+
+pub fn mysql_script(tools: [&str; 2]) -> String {
+    format!(
+        "if command -v {0} >/dev/null 2>&1; then exec {0} \"$@\"; else exec {1} \"$@\"; fi",
+        tools[0], tools[1]
+    )
+}
+
+pub fn mongo_auth(service: &ManifestService) -> Vec<String> {
+    match (
+        service.env.get("MONGO_INITDB_ROOT_USERNAME"),
+        service.env.get("MONGO_INITDB_ROOT_PASSWORD"),
+    ) {
+        (Some(user), Some(password)) => vec![
+            "--username".into(),
+            user.clone(),
+            "--password".into(),
+            password.clone(),
+            "--authenticationDatabase".into(),
+            "admin".into(),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+fn save(
+    runner: &Runner,
+    service: &ManifestService,
+    method: Method,
+    snapshot_dir: &Path,
+) -> DxResult<Entry> {
+    let name = service.name.as_str();
+    let mut entry = Entry {
+        service: name.to_string(),
+        method,
+        files: Vec::new(),
+        paths: Vec::new(),
+    };
+    match method {
+        Method::PgDumpall => {
+            let db = service.database().expect("database service");
+            let file = format!("{name}.sql");
+            container(runner, name, false)?;
+            let mut command = runner.exec(name, &[("PGPASSWORD", &db.password)])?;
+            command
+                .args(["pg_dumpall", "--clean", "--if-exists", "-U"])
+                .arg(&db.user);
+            run(command, "pg_dumpall", None, Some(&snapshot_dir.join(&file)))?;
+            entry.files.push(file);
+        }
+        Method::Mysqldump => {
+            let db = service.database().expect("database service");
+            let file = format!("{name}.sql");
+            container(runner, name, false)?;
+            let mut command = runner.exec(name, &[("MYSQL_PWD", &db.password)])?;
+            command
+                .args(["sh", "-c"])
+                .arg(mysql_script(["mariadb-dump", "mysqldump"]))
+                .arg("sh")
+                .arg(format!("-u{}", db.user))
+                .args(["--databases", &db.name]);
+            run(command, "mysqldump", None, Some(&snapshot_dir.join(&file)))?;
+            entry.files.push(file);
+        }
+        Method::Mongodump => {
+            let file = format!("{name}.archive");
+            container(runner, name, false)?;
+            let mut command = runner.exec(name, &[])?;
+            command
+                .args(["mongodump", "--archive", "--quiet"])
+                .args(mongo_auth(service));
+            run(command, "mongodump", None, Some(&snapshot_dir.join(&file)))?;
+            entry.files.push(file);
+        }
+        Method::RedisRdb => {
+            let file = format!("{name}.rdb");
+            container(runner, name, false)?;
+            let mut command = runner.exec(name, &[])?;
+            command.args(["redis-cli", "SAVE"]);
+            run(command, "redis-cli SAVE", None, None)?;
+            let mut command = runner.command(&["cp"])?;
+            command
+                .arg(format!("{name}:/data/dump.rdb"))
+                .arg(snapshot_dir.join(&file));
+            run(command, "compose cp", None, None)?;
+            entry.files.push(file);
+        }
+        Method::VolumeTar => {
+            let id = container(runner, name, true)?;
+            // Stopped while copying so the files are consistent
+            stop_start(runner, name, "stop")?;
+            let mut result = Ok(());
+            for (source, target) in service.volumes.iter().filter(|(s, _)| is_named_volume(s)) {
+                let file = format!("{name}-{source}.tar.gz");
+                let script = format!("tar czf '/backup/{file}' -C '{target}' .");
+                result =
+                    run(volume_helper(runner, &id, snapshot_dir, &script), "tar", None, None).map(|_| ());
+                if result.is_err() {
+                    break;
+                }
+                entry.files.push(file);
+                entry.paths.push(target.clone());
+            }
+            stop_start(runner, name, "start")?;
+            result?;
+        }
+    }
+    Ok(entry)
+}
+
+fn load_back(
+    runner: &Runner,
+    service: &ManifestService,
+    entry: &Entry,
+    snapshot_dir: &Path,
+) -> DxResult<()> {
+    let name = service.name.as_str();
+    let file = |i: usize| -> DxResult<PathBuf> {
+        let path = entry
+            .files
+            .get(i)
+            .map(|f| snapshot_dir.join(f))
+            .filter(|p| p.is_file());
+        path.ok_or_else(|| {
+            DxError::NotFound(format!(
+                "Arquivo do snapshot ausente para o serviço '{name}' em {}",
+                snapshot_dir.display()
+            ))
+        })
+    };
+    match entry.method {
+        Method::PgDumpall => {
+            let db = service.database().ok_or_else(|| not_a_database(name))?;
+            container(runner, name, false)?;
+            let mut command = runner.exec(name, &[("PGPASSWORD", &db.password)])?;
+            command
+                .args(["psql", "-q", "-d", "postgres", "-U"])
+                .arg(&db.user);
+            run(command, "psql", Some(&file(0)?), None)?;
+        }
+        Method::Mysqldump => {
+            let db = service.database().ok_or_else(|| not_a_database(name))?;
+            container(runner, name, false)?;
+            let mut command = runner.exec(name, &[("MYSQL_PWD", &db.password)])?;
+            command
+                .args(["sh", "-c"])
+                .arg(mysql_script(["mariadb", "mysql"]))
+                .arg("sh")
+                .arg(format!("-u{}", db.user));
+            run(command, "mysql", Some(&file(0)?), None)?;
+        }
+        Method::Mongodump => {
+            container(runner, name, false)?;
+            let mut command = runner.exec(name, &[])?;
+            command
+                .args(["mongorestore", "--archive", "--drop", "--quiet"])
+                .args(mongo_auth(service));
+            run(command, "mongorestore", Some(&file(0)?), None)?;
+        }
+        Method::RedisRdb => {
+            let rdb = file(0)?;
+            container(runner, name, true)?;
+            // Redis rewrites dump.rdb on shutdown, so the copy goes in while stopped
+            stop_start(runner, name, "stop")?;
+            let mut command = runner.command(&["cp"])?;
+            command.arg(&rdb).arg(format!("{name}:/data/dump.rdb"));
+            let result = run(command, "compose cp", None, None);
+            stop_start(runner, name, "start")?;
+            result?;
+        }
+        Method::VolumeTar => {
+            let id = container(runner, name, true)?;
+            let archives = (0..entry.files.len())
+                .map(file)
+                .collect::<DxResult<Vec<_>>>()?;
+            stop_start(runner, name, "stop")?;
+            let mut result = Ok(());
+            for (archive, target) in archives.iter().zip(&entry.paths) {
+                let archive = archive.file_name().unwrap_or_default().to_string_lossy();
+                let script = format!(
+                    "find '{target}' -mindepth 1 -delete && tar xzf '/backup/{archive}' -C '{target}'"
+                );
+                result =
+                    run(volume_helper(runner, &id, snapshot_dir, &script), "tar", None, None).map(|_| ());
+                if result.is_err() {
+                    break;
+                }
+            }
+            stop_start(runner, name, "start")?;
+            result?;
+        }
+    }
+    Ok(())
+}
+
+/// Refuse `method` on a remote engine when it needs the snapshot directory
+/// mounted in a container, which would be a path of the remote machine.
+fn local_only(project_dir: &Path, service: &str, method: Method) -> DxResult<()> {
+    if method == Method::VolumeTar && crate::remote::engine(project_dir).is_some() {
+        return Err(DxError::Unsupported(format!(
+            "O serviço '{service}' é salvo com o tar dos volumes, que não funciona no Docker remoto.\nDica: deixe-o de fora com --service <outros serviços>."
+        )));
+    }
+    Ok(())
+}
+
+fn not_a_database(service: &str) -> DxError {
+    DxError::InvalidInput(format!(
+        "O serviço '{service}' não é mais um banco de dados no manifesto; o snapshot não pode ser restaurado nele."
+    ))
+}
+
+pub struct SnapshotOptions {
+    /// Only these services (all when empty)
+    pub services: Vec<String>,
+    /// Replace an existing snapshot with the same name
+    pub force: bool,
+    /// `--runtime`
+    pub runtime: Option<Runtime>,
+}
+
+/// `dx dev-services snapshot create <name>`.
+pub fn create(dir: Option<PathBuf>, name: &str, opts: SnapshotOptions) -> DxResult {
+    validate_name(name)?;
+    let (project_dir, compose_path, services) = load(dir)?;
+    let manifest = crate::remote::manifest(&project_dir, &compose_path);
+    let runner = Runner::new(&project_dir, Runtime::resolve(&project_dir, opts.runtime)?, &manifest);
+    let snapshot_dir = snapshots_dir(&project_dir).join(name);
+    if snapshot_dir.exists() && !opts.force {
+        return Err(DxError::InvalidInput(format!(
+            "O snapshot '{name}' já existe em {}. Use --force para substituí-lo.",
+            snapshot_dir.display()
+        )));
+    }
+    let selected = select(&services, |s| &s.name, &opts.services)?;
+    let plan: Vec<(&ManifestService, Method)> = selected
+        .into_iter()
+        .filter_map(|s| method(s).map(|m| (s, m)))
+        .collect();
+    if plan.is_empty() {
+        return Err(DxError::NotFound(format!(
+            "Nenhum serviço com dados para salvar em {}.",
+            compose_path.display()
+        )));
+    }
+    for (service, method) in &plan {
+        local_only(&project_dir, &service.name, *method)?;
+    }
+
+    // Written to a temporary directory first, so a failure keeps the previous snapshot
+    let partial = snapshots_dir(&project_dir).join(format!(".{name}.partial"));
+    let _ = fs::remove_dir_all(&partial);
+    fs::create_dir_all(&partial).context(format!("Erro ao criar {}", partial.display()))?;
+    // `docker run -v` needs an absolute path
+    let partial = partial.canonicalize().unwrap_or(partial);
+
+    outln!("Criando snapshot '{name}' dos Dev Services...");
+    let mut entries = Vec::new();
+    for (service, method) in plan {
+        let saved = crate::progress::timed(&format!("snapshot {}", service.name), || {
+            save(&runner, service, method, &partial)
+        });
+        match saved {
+            Ok(entry) => {
+                outln!("- {}: {}", service.name, method.label());
+                entries.push(entry);
+            }
+            Err(e) => {
+                let _ = fs::remove_dir_all(&partial);
+                return Err(e);
+            }
+        }
+    }
+
+    let metadata = Metadata {
+        name: name.to_string(),
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        services: entries,
+    };
+    let json = serde_json::to_string_pretty(&metadata).unwrap_or_default();
+    let metadata_path = partial.join("snapshot.json");
+    fs::write(&metadata_path, format!("{json}\n"))
+        .context(format!("Erro ao salvar {}", metadata_path.display()))?;
+    if snapshot_dir.exists() {
+        fs::remove_dir_all(&snapshot_dir)
+            .context(format!("Erro ao remover {}", snapshot_dir.display()))?;
+    }
+    fs::rename(&partial, &snapshot_dir)
+        .context(format!("Erro ao salvar {}", snapshot_dir.display()))?;
+
+    crate::output::set_data(serde_json::json!({
+        "name": name,
+        "path": snapshot_dir.display().to_string(),
+        "services": metadata.services,
+    }));
+    outln!(
+        "Snapshot salvo em {} ({}). Para voltar a este estado: dx dev-services snapshot restore {name}",
+        snapshot_dir.display(),
+        crate::clean::format_size(dir_size(&snapshot_dir))
+    );
+    Ok(())
+}
+
+fn read_metadata(snapshot_dir: &Path) -> Option<Metadata> {
+    let content = fs::read_to_string(snapshot_dir.join("snapshot.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.metadata().ok())
+                .map(|m| m.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Snapshots of the project, sorted by name.
+fn snapshots(project_dir: &Path) -> Vec<(PathBuf, Metadata)> {
+    let mut found: Vec<(PathBuf, Metadata)> = fs::read_dir(snapshots_dir(project_dir))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter_map(|p| read_metadata(&p).map(|m| (p, m)))
+                .collect()
+        })
+        .unwrap_or_default();
+    found.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+    found
+}
+
+/// `dx dev-services snapshot restore <name>`.
+pub fn restore(dir: Option<PathBuf>, name: &str, opts: SnapshotOptions) -> DxResult {
+    validate_name(name)?;
+    let (project_dir, compose_path, services) = load(dir)?;
+    let manifest = crate::remote::manifest(&project_dir, &compose_path);
+    let runner = Runner::new(&project_dir, Runtime::resolve(&project_dir, opts.runtime)?, &manifest);
+    let snapshot_dir = snapshots_dir(&project_dir).join(name);
+    let Some(metadata) = read_metadata(&snapshot_dir) else {
+        let available: Vec<String> = snapshots(&project_dir)
+            .into_iter()
+            .map(|(_, m)| m.name)
+            .collect();
+        return Err(DxError::NotFound(format!(
+            "Snapshot '{name}' não encontrado em {}.{}",
+            snapshots_dir(&project_dir).display(),
+            if available.is_empty() {
+                "\nDica: crie um com: dx dev-services snapshot create <nome>".to_string()
+            } else {
+                format!("\nSnapshots disponíveis: {}", available.join(", "))
+            }
+        )));
+    };
+    let entries = select(&metadata.services, |e| &e.service, &opts.services)?;
+    for entry in &entries {
+        local_only(&project_dir, &entry.service, entry.method)?;
+    }
+    let snapshot_dir = snapshot_dir.canonicalize().unwrap_or(snapshot_dir);
+
+    outln!("Restaurando o snapshot '{name}'...");
+    let mut restored = Vec::new();
+    for entry in entries {
+        let Some(service) = services.iter().find(|s| s.name == entry.service) else {
+            outln!(
+                "- {}: ignorado (serviço não está mais no manifesto)",
+                entry.service
+            );
+            continue;
+        };
+        crate::progress::timed(&format!("restore {}", entry.service), || {
+            load_back(&runner, service, entry, &snapshot_dir)
+        })?;
+        outln!("- {}: {}", entry.service, entry.method.label());
+        restored.push(entry.service.clone());
+    }
+    crate::output::set_data(serde_json::json!({
+        "name": name,
+        "restored": restored,
+    }));
+    outln!("Snapshot '{name}' restaurado.");
+    Ok(())
+}
+
+/// `dx dev-services snapshot list`.
+pub fn list(dir: Option<PathBuf>) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !project_dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            project_dir.display()
+        )));
+    }
+    let found = snapshots(&project_dir);
+    crate::output::set_data(serde_json::json!({
+        "snapshots": found
+            .iter()
+            .map(|(path, m)| serde_json::json!({
+                "name": m.name,
+                "created_at": m.created_at,
+                "size_bytes": dir_size(path),
+                "services": m.services.iter().map(|e| &e.service).collect::<Vec<_>>(),
+            }))
+            .collect::<Vec<_>>(),
+    }));
+    if found.is_empty() {
+        outln!(
+            "Nenhum snapshot em {}. Crie um com: dx dev-services snapshot create <nome>",
+            snapshots_dir(&project_dir).display()
+        );
+        return Ok(());
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    for (path, m) in &found {
+        let services: Vec<&str> = m.services.iter().map(|e| e.service.as_str()).collect();
+        outln!(
+            "- {} ({}, {}): {}",
+            m.name,
+            age(now.saturating_sub(m.created_at)),
+            crate::clean::format_size(dir_size(path)),
+            services.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// "há 5 min", "há 3 h", "há 2 dia(s)".
+pub fn age(secs: u64) -> String {
+    match secs {
+        0..60 => "agora".to_string(),
+        60..3600 => format!("há {} min", secs / 60),
+        3600..86400 => format!("há {} h", secs / 3600),
+        _ => format!("há {} dia(s)", secs / 86400),
+    }
+}
@@ -0,0 +1,278 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! `dx dev-services status`: container state plus readiness of each service.
+//!
+//! The state comes from `docker compose ps --all --format json` (a JSON array
+//! on older Compose v2 releases, one object per line on newer ones). A running
+//! container is not necessarily accepting connections yet, so each service with
+//! a published port is also probed from the host: PostgreSQL answers an
+//! `SSLRequest`, MySQL/MariaDB sends its handshake, Redis replies to `PING`;
+//! Kafka, MongoDB and the rest must accept a TCP connection. A Docker
+//! healthcheck that reports `unhealthy` wins over a successful probe.
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dev_services::ManifestService;
+use crate::error::{DxError, DxResult};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Probe {
+    Postgres,
+    Mysql,
+    Redis,
+    Tcp,
+}
+
+impl Probe {
+    fn for_image(image: &str) -> Probe {
+        match crate::resources::image_name(image) {
+            "postgres" | "postgis" => Probe::Postgres,
+            "mysql" | "mariadb" => Probe::Mysql,
+            "redis" | "valkey" => Probe::Redis,
+            _ => Probe::Tcp,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Readiness {
+    Ready,
+    Waiting,
+    Unhealthy,
+    /// Not running, or nothing published to probe
+    Unknown,
+}
+
+impl Readiness {
+    fn label(self) -> &'static str {
+        match self {
+            Readiness::Ready => "pronto",
+            Readiness::Waiting => "aguardando",
+            Readiness::Unhealthy => "não saudável",
+            Readiness::Unknown => "-",
+        }
+    }
+}
+
+/// One entry of `docker compose ps --format json`.
+#[derive(Debug, Deserialize)]
+pub struct Container {
+    #[serde(rename = "Service")]
+    service: String,
+    #[serde(rename = "State", default)]
+    pub state: String,
+    #[serde(rename = "Health", default)]
+    health: String,
+    #[serde(rename = "ExitCode", default)]
+    pub exit_code: i64,
+}
+
+/// Containers of the runner's manifest, by service; `None` when Compose can't
+/// be asked.
+pub fn containers(runner: &crate::compose::Runner) -> Option<HashMap<String, Container>> {
+    let output = runner.ps().ok()?;
+    Some(
+        parse_ps(&output)
+            .into_iter()
+            .map(|c| (c.service.clone(), c))
+            .collect(),
+    )
+}
+
+fn parse_ps(stdout: &str) -> Vec<Container> {
+    let stdout = stdout.trim();
+    if stdout.starts_with('[') {
+        return serde_json::from_str(stdout).unwrap_or_default();
+    }
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str(line.trim()).ok())
+        .collect()
+}
+
+fn state_label(state: &str) -> &str {
+    match state {
+        "running" => "em execução",
+        "exited" | "dead" => "parado",
+        "restarting" => "reiniciando",
+        "paused" => "pausado",
+        "created" => "criado",
+        "" => "não criado",
+        other => other,
+    }
+}
+
+/// Whether the service answers on `port` of `host` (this machine, or the
+/// remote Docker host of `run --context/--host`).
+fn probe(kind: Probe, host: &str, port: u16) -> bool {
+    let Some(addr) = (host, port)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut a| a.next())
+    else {
+        return false;
+    };
+    let Ok(mut stream) = TcpStream::connect_timeout(&addr, PROBE_TIMEOUT) else {
+        return false;
+    };
+    let _ = stream.set_read_timeout(Some(PROBE_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(PROBE_TIMEOUT));
+    let mut reply = [0u8; 16];
+    match kind {
+        Probe::Tcp => true,
+        // SSLRequest: length 8, code 80877103; the server answers 'S' or 'N'
+        Probe::Postgres => {
+            stream
+                .write_all(&[0, 0, 0, 8, 0x04, 0xd2, 0x16, 0x2f])
+                .is_ok()
+                && stream.read_exact(&mut reply[..1]).is_ok()
+                && matches!(reply[0], b'S' | b'N')
+        }
+        // Handshake packet: 3-byte length, sequence 0, protocol version 10
+        // (0xff is an error packet, e.g. "too many connections")
+        Probe::Mysql => stream.read_exact(&mut reply[..5]).is_ok() && reply[4] == 10,
+        // +PONG, or -NOAUTH when a password is set: either way it's serving
+        Probe::Redis => {
+            stream.write_all(b"PING\r\n").is_ok()
+                && stream.read(&mut reply).is_ok_and(|n| {
+                    reply[..n].starts_with(b"+PONG") || reply[..n].starts_with(b"-NOAUTH")
+                })
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Row {
+    service: String,
+    image: String,
+    port: Option<u16>,
+    state: String,
+    health: Option<String>,
+    probe: Probe,
+    readiness: Readiness,
+}
+
+/// `containers` is `None` when Docker couldn't be asked.
+fn row(
+    service: &ManifestService,
+    containers: Option<&HashMap<String, Container>>,
+    host: &str,
+) -> Row {
+    let container = containers.and_then(|c| c.get(&service.name));
+    let port = service.ports.first().map(|(host, _)| *host);
+    let state = container.map(|c| c.state.clone()).unwrap_or_default();
+    let health = container
+        .map(|c| c.health.clone())
+        .filter(|h| !h.is_empty());
+    let kind = Probe::for_image(&service.image);
+    // Without Docker's answer, the probe alone tells whether it's up
+    let running = match containers {
+        Some(_) => container.is_some_and(|c| c.state == "running"),
+        None => true,
+    };
+    let readiness = match (running, port) {
+        _ if health.as_deref() == Some("unhealthy") => Readiness::Unhealthy,
+        (true, Some(port)) if probe(kind, host, port) => Readiness::Ready,
+        (true, Some(_)) => Readiness::Waiting,
+        _ => Readiness::Unknown,
+    };
+    Row {
+        service: service.name.clone(),
+        image: service.image.clone(),
+        port,
+        state,
+        health,
+        probe: kind,
+        readiness,
+    }
+}
+
+/// `dx dev-services status`.
+pub fn status(dir: Option<PathBuf>, runtime: Option<crate::compose::Runtime>) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !project_dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            project_dir.display()
+        )));
+    }
+    let compose = project_dir.join(".dx").join("docker-compose.yml");
+    let yaml = fs::read_to_string(&compose).map_err(|_| {
+        DxError::NotFound(format!(
+            "Arquivo não encontrado: {}\nDica: gere o manifesto e suba os serviços com:\n  dx dev-services && dx dev-services run",
+            compose.display()
+        ))
+    })?;
+    let services = crate::dev_services::manifest_services(&yaml);
+
+    let runtime = crate::compose::Runtime::resolve(&project_dir, runtime)?;
+    let runner = crate::compose::Runner::new(&project_dir, runtime, &compose);
+    let containers = crate::progress::timed("docker compose ps", || containers(&runner));
+    let host = crate::remote::address(&project_dir).unwrap_or_else(|| "127.0.0.1".to_string());
+    if containers.is_none() {
+        eprintln!(
+            "Aviso: não foi possível consultar 'docker compose ps'; a prontidão vem apenas das portas."
+        );
+    }
+    let rows: Vec<Row> = services
+        .iter()
+        .map(|s| row(s, containers.as_ref(), &host))
+        .collect();
+
+    let ready = rows
+        .iter()
+        .filter(|r| r.readiness == Readiness::Ready)
+        .count();
+    crate::output::set_data(serde_json::json!({
+        "compose": compose.display().to_string(),
+        "docker": containers.is_some(),
+        "ready": ready,
+        "services": rows,
+    }));
+
+    let state = |r: &Row| match (&containers, r.health.as_deref()) {
+        (None, _) => "?".to_string(),
+        (Some(_), Some(health)) => format!("{} ({health})", state_label(&r.state)),
+        (Some(_), None) => state_label(&r.state).to_string(),
+    };
+    let table: Vec<[String; 5]> = rows
+        .iter()
+        .map(|r| {
+            [
+                r.service.clone(),
+                r.image.clone(),
+                r.port.map_or("-".to_string(), |p| p.to_string()),
+                state(r),
+                r.readiness.label().to_string(),
+            ]
+        })
+        .collect();
+    let header = ["SERVIÇO", "IMAGEM", "PORTA", "ESTADO", "PRONTIDÃO"].map(String::from);
+    let mut widths = [0usize; 5];
+    for line in std::iter::once(&header).chain(&table) {
+        for (w, cell) in widths.iter_mut().zip(line) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    for line in std::iter::once(&header).chain(&table) {
+        let cells: Vec<String> = line
+            .iter()
+            .zip(widths)
+            .map(|(cell, w)| format!("{cell:<w$}"))
+            .collect();
+        outln!("{}", cells.join("  ").trim_end());
+    }
+    outln!("\n{ready} de {} serviço(s) prontos.", rows.len());
+    Ok(())
+}
@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Task runner scaffolding (`dx tasks generate`).
+//!
+//! Writes a `justfile` or `Makefile` with the same targets in every project
+//! (setup, services-up, test, lint, analyze, clean). The stack-native commands
+//! come from the CI pipeline model, so local tasks and CI run the same thing.
+use std::path::{Path, PathBuf};
+
+use crate::error::{DxError, DxResult};
+use crate::managed::{self, Markers};
+use crate::pipeline::{Pipeline, Stack, Stage};
+
+const MARKERS: Markers = Markers {
+    start: "# dx-cli:tasks:start",
+    end: "# dx-cli:tasks:end",
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum TaskFormat {
+    /// justfile (https://just.systems)
+    Just,
+    /// Makefile (GNU make)
+    Make,
+}
+
+impl TaskFormat {
+    fn name(self) -> &'static str {
+        match self {
+            TaskFormat::Just => "just",
+            TaskFormat::Make => "make",
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            TaskFormat::Just => "justfile",
+            TaskFormat::Make => "Makefile",
+        }
+    }
+}
+
+struct Task {
+    name: &'static str,
+    description: &'static str,
+    commands: Vec<String>,
+}
+
+/// Fetches dependencies for stacks whose pipeline has no install step.
+fn fetch_command(stack: Stack, dir: &Path) -> Option<&'static str> {
+    match stack {
+        Stack::Rust => Some("cargo fetch"),
+        Stack::Go => Some("go mod download"),
+        Stack::Maven if dir.join("mvnw").is_file() => Some("./mvnw -B dependency:resolve"),
+        Stack::Maven => Some("mvn -B dependency:resolve"),
+        _ => None,
+    }
+}
+
+/// Removes the stack's build outputs and installed dependencies.
+fn clean_command(stack: Stack, dir: &Path) -> Option<&'static str> {
+    match stack {
+        Stack::Rust => Some("cargo clean"),
+        Stack::Node => Some("rm -rf node_modules"),
+        Stack::Python => Some("rm -rf .pytest_cache .ruff_cache build dist"),
+        Stack::Go => Some("go clean ./..."),
+        Stack::Maven if dir.join("mvnw").is_file() => Some("./mvnw -B clean"),
+        Stack::Maven => Some("mvn -B clean"),
+        Stack::Gradle if dir.join("gradlew").is_file() => Some("./gradlew clean"),
+        Stack::Gradle => Some("gradle clean"),
+        Stack::Php => Some("rm -rf vendor"),
+        Stack::Ruby => Some("rm -rf vendor/bundle"),
+        Stack::Unknown => None,
+    }
+}
+
+/// Commands of the pipeline steps in `stage` (without the CI tool installs).
+fn stage_commands(pipeline: &Pipeline, stage: Stage) -> Vec<String> {
+    pipeline
+        .steps
+        .iter()
+        .filter(|s| s.stage == stage)
+        .flat_map(|s| s.run.lines().map(str::to_string).collect::<Vec<_>>())
+        .collect()
+}
+
+/// `commands`, or a notice when the stack has none.
+fn or_notice(commands: Vec<String>, notice: &str) -> Vec<String> {
+    if commands.is_empty() {
+        vec![format!("@echo \"{notice}\"")]
+    } else {
+        commands
+    }
+}
+
+fn tasks(pipeline: &Pipeline, dir: &Path) -> Vec<Task> {
+    let stack = pipeline.stack();
+    let mut setup = stage_commands(pipeline, Stage::Install);
+    setup.extend(fetch_command(stack, dir).map(str::to_string));
+    setup.push("dx dev-services".to_string());
+    let mut clean: Vec<String> = clean_command(stack, dir)
+        .map(str::to_string)
+        .into_iter()
+        .collect();
+    clean.push("dx clean --yes --backup".to_string());
+    vec![
+        Task {
+            name: "setup",
+            description: "Instala as dependências e gera o manifesto dos Dev Services",
+            commands: setup,
+        },
+        Task {
+            name: "services-up",
+            description: "Sobe os Dev Services (.dx/docker-compose.yml)",
+            commands: vec!["dx dev-services run".to_string()],
+        },
+        Task {
+            name: "test",
+            description: "Executa os testes",
+            commands: or_notice(
+                stage_commands(pipeline, Stage::Test),
+                "Nenhum teste configurado para esta stack.",
+            ),
+        },
+        Task {
+            name: "lint",
+            description: "Executa o lint",
+            commands: or_notice(
+                stage_commands(pipeline, Stage::Lint),
+                "Nenhum lint configurado para esta stack.",
+            ),
+        },
+        Task {
+            name: "analyze",
+            description: "Analisa o projeto com o dx",
+            commands: vec!["dx analyzer".to_string()],
+        },
+        Task {
+            name: "clean",
+            description: "Remove artefatos de build e as pastas .dx (com backup)",
+            commands: clean,
+        },
+    ]
+}
+
+fn render_just(tasks: &[Task]) -> String {
+    let mut out = String::from("\n# Lista as tarefas\ndefault:\n    @just --list\n");
+    for task in tasks {
+        out.push_str(&format!("\n# {}\n{}:\n", task.description, task.name));
+        for command in &task.commands {
+            out.push_str(&format!("    {command}\n"));
+        }
+    }
+    out
+}
+
+fn render_make(tasks: &[Task]) -> String {
+    let names: Vec<&str> = tasks.iter().map(|t| t.name).collect();
+    let mut out = format!("\n.PHONY: help {}\n\n", names.join(" "));
+    let width = names.iter().map(|n| n.len()).max().unwrap_or(0);
+    out.push_str("# Lista as tarefas\nhelp:\n");
+    for task in tasks {
+        out.push_str(&format!(
+            "\t@echo \"  {:<width$}  {}\"\n",
+            task.name, task.description
+        ));
+    }
+    for task in tasks {
+        out.push_str(&format!("\n# {}\n{}:\n", task.description, task.name));
+        for command in &task.commands {
+            // `$` is make's own expansion character
+            out.push_str(&format!("\t{}\n", command.replace('$', "$$")));
+        }
+    }
+    out
+}
+
+pub struct GenerateOptions {
+    pub format: TaskFormat,
+    /// Print the file instead of writing it
+    pub dry_run: bool,
+    /// Overwrite a file that was not generated by dx
+    pub force: bool,
+}
+
+/// `dx tasks generate`: write the justfile/Makefile for the project at `dir`.
+pub fn generate(dir: Option<PathBuf>, opts: GenerateOptions) -> DxResult {
+    let dir = dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            dir.display()
+        )));
+    }
+    let pipeline = Pipeline::detect(&dir, &dir);
+    let tasks = tasks(&pipeline, &dir);
+    let body = match opts.format {
+        TaskFormat::Just => render_just(&tasks),
+        TaskFormat::Make => render_make(&tasks),
+    };
+    let block = MARKERS.wrap(
+        &format!(
+            "Gerado por `dx tasks generate --format {}`; alterações entre os marcadores são sobrescritas.",
+            opts.format.name()
+        ),
+        &body,
+    );
+
+    outln!(
+        "Tarefas ({}) para stack {}:",
+        opts.format.file_name(),
+        pipeline.stack().name()
+    );
+    for task in &tasks {
+        outln!("- {:<12} {}", task.name, task.description);
+    }
+    let path = dir.join(opts.format.file_name());
+    let written = managed::write(
+        &path,
+        &MARKERS,
+        &block,
+        &managed::WriteOptions {
+            dry_run: opts.dry_run,
+            force: opts.force,
+        },
+    )?;
+
+    crate::output::set_data(serde_json::json!({
+        "format": opts.format.name(),
+        "stack": pipeline.stack().name(),
+        "path": path.display().to_string(),
+        "tasks": tasks
+            .iter()
+            .map(|t| serde_json::json!({ "name": t.name, "commands": t.commands }))
+            .collect::<Vec<_>>(),
+        "written": written,
+    }));
+    Ok(())
+}
@@ -1,80 +1,132 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright (c) 2025 The dx-cli Contributors
 
-use crate::dev_services::{create_docker_compose_file, DockerComposeConfig, DockerService};
+use crate::dev_services::{DockerComposeConfig, DockerService};
+use crate::settings::TelemetryLevel;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Compose profile of the telemetry stack (`dev-services run --profile observability`)
+const PROFILE: &str = "observability";
+
 pub struct TelemetryResult {
     pub compose_path: PathBuf,
     pub config: DockerComposeConfig,
 }
 
-pub fn apply(project_dir: &Path) -> std::io::Result<TelemetryResult> {
-    let dx_dir = project_dir.join(".dx");
-    let telemetry_dir = dx_dir.join("telemetry");
-    let grafana_dir = telemetry_dir.join("grafana");
-    let grafana_prov_ds = grafana_dir.join("provisioning").join("datasources");
-    let grafana_prov_dash = grafana_dir.join("provisioning").join("dashboards");
-    let grafana_dash_dir = grafana_dir.join("dashboards");
-    let prometheus_dir = telemetry_dir.join("prometheus");
-    let tempo_dir = telemetry_dir.join("tempo");
-
-    // Ensure directories
-    fs::create_dir_all(&dx_dir)?;
-    fs::create_dir_all(&grafana_prov_ds)?;
-    fs::create_dir_all(&grafana_prov_dash)?;
-    fs::create_dir_all(&grafana_dash_dir)?;
-    fs::create_dir_all(&prometheus_dir)?;
-    fs::create_dir_all(&tempo_dir)?;
-
-    // Write Grafana provisioning: datasources
-    let datasources_yaml = grafana_datasources_yaml();
-    fs::write(grafana_prov_ds.join("datasources.yaml"), datasources_yaml)?;
-
-    // Write Grafana provisioning: dashboards
-    let dashboards_yaml = grafana_dashboards_yaml();
-    fs::write(grafana_prov_dash.join("dashboards.yaml"), dashboards_yaml)?;
-
-    // Write Prometheus config
-    let prometheus_yaml = prometheus_config_yaml();
-    fs::write(prometheus_dir.join("prometheus.yml"), prometheus_yaml)?;
+/// A file generated under `.dx`, with its path relative to the `.dx` directory.
+pub struct GeneratedFile {
+    pub path: PathBuf,
+    pub content: String,
+}
 
-    // Write OTel Collector config
-    let otel_cfg = telemetry_dir.join("otel-collector-config.yaml");
-    let otel_yaml = otel_collector_config_yaml();
-    fs::write(&otel_cfg, otel_yaml)?;
+/// Render every `.dx` artifact produced by `apply` in memory, without touching disk:
+/// the merged docker-compose.yml plus the telemetry configs and dashboards for the
+/// telemetry level configured in dx.toml.
+pub fn render(project_dir: &Path) -> (Vec<GeneratedFile>, DockerComposeConfig) {
+    let file = |path: &str, content: String| GeneratedFile {
+        path: PathBuf::from(path),
+        content,
+    };
 
-    // Write Tempo config (storage backend + receivers)
-    let tempo_cfg = tempo_dir.join("tempo.yaml");
-    let tempo_yaml = tempo_config_yaml();
-    fs::write(&tempo_cfg, tempo_yaml)?;
+    let settings = crate::settings::get(project_dir);
+    let level = settings.telemetry.level;
 
     // Detect language/framework and add a simple dashboard
     let (lang, framework) = detect_language_and_framework(project_dir);
     let dash = simple_dashboard_json(&lang, framework.as_deref());
-    fs::write(grafana_dash_dir.join(format!("{}-overview.json", lang.to_lowercase())), dash)?;
 
     // Build a docker-compose for telemetry and merge into the main dev-services compose
     // Start from detected dev services (if any)
     let mut base = crate::dev_services::detect_dependencies(project_dir);
-    let telemetry_cfg = build_telemetry_compose();
+    let telemetry_cfg = build_telemetry_compose(level);
     for (name, svc) in telemetry_cfg.services.into_iter() {
         base.add_service(&name, svc);
     }
+    base.exclude(&settings.dev_services.exclude);
+    // Route the telemetry UIs too
+    crate::proxy::label_uis(&mut base, &crate::detectors::catalog(project_dir));
+    base.pin_images(&crate::dev_services::image_pins(project_dir));
+    // Host ports already taken on this machine
+    let remaps = crate::ports::remap(project_dir, &mut base);
+    // Local changes kept by the user across regenerations
+    base.overrides = crate::compose::ComposeFile::load_override(&project_dir.join(".dx")).map(|(_, file)| file);
 
-    let compose_path = dx_dir.join("docker-compose.yml");
-    create_docker_compose_file(&base, &compose_path)?;
+    let mut files = vec![file("docker-compose.yml", base.to_yaml())];
+    if !remaps.is_empty() {
+        files.push(file(crate::ports::FILE, crate::ports::record(&remaps)));
+    }
+    // Files mounted by the detected services (seed data, ...)
+    for detector in crate::detectors::registry() {
+        if base.services.keys().any(|name| detector.provides(name)) {
+            files.extend(detector.files().into_iter().map(|(path, content)| file(&path, content)));
+        }
+    }
+    // Schemas the registry job registers
+    if base.services.contains_key(crate::schema_registry::SERVICE) {
+        files.extend(
+            crate::schema_registry::bodies(project_dir).into_iter().map(|(path, content)| file(&path, content)),
+        );
+    }
+    // Routes of the proxy to the apps on the host
+    if base.services.contains_key(crate::proxy::SERVICE) {
+        files.extend(crate::proxy::files(project_dir).into_iter().map(|(path, content)| file(&path, content)));
+    }
+    if level != TelemetryLevel::Off {
+        files.extend([
+            file(
+                "telemetry/grafana/provisioning/datasources/datasources.yaml",
+                grafana_datasources_yaml(level),
+            ),
+            file(
+                "telemetry/grafana/provisioning/dashboards/dashboards.yaml",
+                grafana_dashboards_yaml(),
+            ),
+            file(
+                &format!("telemetry/grafana/dashboards/{}-overview.json", lang.to_lowercase()),
+                dash,
+            ),
+            file("telemetry/prometheus/prometheus.yml", prometheus_config_yaml()),
+            file("telemetry/otel-collector-config.yaml", otel_collector_config_yaml(level)),
+        ]);
+    }
+    if level == TelemetryLevel::Full {
+        files.push(file("telemetry/tempo/tempo.yaml", tempo_config_yaml()));
+    }
+
+    (files, base)
+}
+
+pub fn apply(project_dir: &Path) -> std::io::Result<TelemetryResult> {
+    let dx_dir = project_dir.join(".dx");
+    let (files, config) = render(project_dir);
+
+    // Ensure directories and write every generated file
+    for f in &files {
+        let path = dx_dir.join(&f.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &f.content)?;
+    }
+    // No remapped port anymore: drop the stale record
+    if !files.iter().any(|f| f.path == Path::new(crate::ports::FILE)) {
+        let _ = fs::remove_file(dx_dir.join(crate::ports::FILE));
+    }
+    crate::upgrade::write_meta(project_dir)?;
 
     Ok(TelemetryResult {
-        compose_path,
-        config: base,
+        compose_path: dx_dir.join("docker-compose.yml"),
+        config,
     })
 }
 
-fn build_telemetry_compose() -> DockerComposeConfig {
+fn build_telemetry_compose(level: TelemetryLevel) -> DockerComposeConfig {
     let mut cfg = DockerComposeConfig::new();
+    if level == TelemetryLevel::Off {
+        return cfg;
+    }
 
     // Loki
     cfg.add_service(
@@ -85,6 +137,8 @@ fn build_telemetry_compose() -> DockerComposeConfig {
             ports: vec![3100],
             volumes: vec!["loki-data:/loki".to_string()],
             command: None,
+            profiles: vec![PROFILE.to_string()],
+            ..Default::default()
         },
     );
 
@@ -100,6 +154,8 @@ fn build_telemetry_compose() -> DockerComposeConfig {
                 "tempo-data:/var/tempo".to_string(),
             ],
             command: Some("-config.file=/etc/tempo.yaml".to_string()),
+            profiles: vec![PROFILE.to_string()],
+            ..Default::default()
         },
     );
 
@@ -115,6 +171,8 @@ fn build_telemetry_compose() -> DockerComposeConfig {
                 "prom-data:/prometheus".to_string(),
             ],
             command: None,
+            profiles: vec![PROFILE.to_string()],
+            ..Default::default()
         },
     );
 
@@ -137,6 +195,8 @@ fn build_telemetry_compose() -> DockerComposeConfig {
                 "grafana-storage:/var/lib/grafana".to_string(),
             ],
             command: None,
+            profiles: vec![PROFILE.to_string()],
+            ..Default::default()
         },
     );
 
@@ -152,9 +212,18 @@ fn build_telemetry_compose() -> DockerComposeConfig {
                 rel_bind("telemetry/otel-collector-config.yaml")
             )],
             command: Some("--config=/etc/otel-collector-config.yaml".to_string()),
+            profiles: vec![PROFILE.to_string()],
+            ..Default::default()
         },
     );
 
+
+    // Metrics only: drop the logs and traces backends
+    if level == TelemetryLevel::Metrics {
+        cfg.services.remove("loki");
+        cfg.services.remove("tempo");
+    }
+
     cfg
 }
 
@@ -167,16 +236,20 @@ fn rel_bind(p: &str) -> String {
     s
 }
 
-fn grafana_datasources_yaml() -> String {
-    // Provision three datasources: Prometheus, Loki, Tempo
-    let s = r#"apiVersion: 1
+fn grafana_datasources_yaml(level: TelemetryLevel) -> String {
+    // Provision Prometheus, plus Loki and Tempo when they are part of the stack
+    let mut s = r#"apiVersion: 1
 datasources:
   - name: Prometheus
     type: prometheus
     access: proxy
     url: http://prometheus:9090
     isDefault: true
-  - name: Loki
+"#
+    .to_string();
+    if level == TelemetryLevel::Full {
+        s.push_str(
+            r#"  - name: Loki
     type: loki
     access: proxy
     url: http://loki:3100
@@ -184,8 +257,10 @@ datasources:
     type: tempo
     access: proxy
     url: http://tempo:3200
-"#;
-    s.to_string()
+"#,
+        );
+    }
+    s
 }
 
 fn grafana_dashboards_yaml() -> String {
@@ -251,10 +326,11 @@ storage:
     s.to_string()
 }
 
-fn otel_collector_config_yaml() -> String {
+fn otel_collector_config_yaml(level: TelemetryLevel) -> String {
     // Expose Prometheus exporter at 0.0.0.0:8889; receive OTLP on 4317/4318; export
     // metrics to Prometheus (scraped), logs to Loki via OTLP HTTP, traces to Tempo via OTLP gRPC
-    let s = r#"receivers:
+    let full = level == TelemetryLevel::Full;
+    let mut s = r#"receivers:
   otlp:
     protocols:
       grpc:
@@ -264,13 +340,21 @@ fn otel_collector_config_yaml() -> String {
 exporters:
   prometheus:
     endpoint: 0.0.0.0:8889
-  otlphttp/loki:
+"#
+    .to_string();
+    if full {
+        s.push_str(
+            r#"  otlphttp/loki:
     endpoint: http://loki:3100/otlp
   otlp/tempo:
     endpoint: tempo:4317
     tls:
       insecure: true
-processors:
+"#,
+        );
+    }
+    s.push_str(
+        r#"processors:
   batch: {}
   memory_limiter:
     check_interval: 1s
@@ -282,7 +366,11 @@ service:
       receivers: [otlp]
       processors: [memory_limiter, batch]
       exporters: [prometheus]
-    logs:
+"#,
+    );
+    if full {
+        s.push_str(
+            r#"    logs:
       receivers: [otlp]
       processors: [memory_limiter, batch]
       exporters: [otlphttp/loki]
@@ -290,11 +378,26 @@ service:
       receivers: [otlp]
       processors: [memory_limiter, batch]
       exporters: [otlp/tempo]
-"#;
-    s.to_string()
+"#,
+        );
+    }
+    s
+}
+
+/// Project language and framework: `[project]` in dx.toml when set, otherwise
+/// detected from the project files.
+pub fn detect_language_and_framework(project_dir: &Path) -> (String, Option<String>) {
+    let project = crate::settings::get(project_dir).project;
+    match project.language {
+        Some(language) => (language, project.framework),
+        None => {
+            let (language, framework) = detect_from_files(project_dir);
+            (language, project.framework.or(framework))
+        }
+    }
 }
 
-fn detect_language_and_framework(project_dir: &Path) -> (String, Option<String>) {
+fn detect_from_files(project_dir: &Path) -> (String, Option<String>) {
     // Very simple heuristics
     let p = project_dir;
     if p.join("Cargo.toml").exists() {
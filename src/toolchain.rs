@@ -0,0 +1,465 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Language toolchains the project pins, for `dx doctor` and the CI
+//! pipeline.
+//!
+//! A pin comes from the file of the tool itself (`.nvmrc`, `.python-version`,
+//! `rust-toolchain.toml`, the `go` directive of go.mod, `.java-version`,
+//! `.ruby-version`) or, failing that, from the version managers' files
+//! (`.tool-versions` of asdf, `[tools]` of `mise.toml`). A missing or
+//! different version is installed with mise or asdf when either is on PATH,
+//! else with the tool's own manager (rustup, nvm, pyenv).
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::pipeline::Stack;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tool {
+    Node,
+    Python,
+    Rust,
+    Go,
+    Java,
+    Ruby,
+    Php,
+}
+
+const TOOLS: [Tool; 7] = [
+    Tool::Node,
+    Tool::Python,
+    Tool::Rust,
+    Tool::Go,
+    Tool::Java,
+    Tool::Ruby,
+    Tool::Php,
+];
+
+impl Tool {
+    /// Toolchain of the projects of `stack`.
+    pub fn for_stack(stack: Stack) -> Option<Tool> {
+        match stack {
+            Stack::Node => Some(Tool::Node),
+            Stack::Python => Some(Tool::Python),
+            Stack::Rust => Some(Tool::Rust),
+            Stack::Go => Some(Tool::Go),
+            Stack::Maven | Stack::Gradle => Some(Tool::Java),
+            Stack::Ruby => Some(Tool::Ruby),
+            Stack::Php => Some(Tool::Php),
+            Stack::Unknown => None,
+        }
+    }
+
+    /// Program reporting the installed version, with its arguments.
+    pub fn program(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            Tool::Node => ("node", &["--version"]),
+            Tool::Python => ("python3", &["--version"]),
+            Tool::Rust => ("rustc", &["--version"]),
+            Tool::Go => ("go", &["version"]),
+            Tool::Java => ("java", &["-version"]),
+            Tool::Ruby => ("ruby", &["--version"]),
+            Tool::Php => ("php", &["--version"]),
+        }
+    }
+
+    /// Name of the tool in mise.
+    fn mise_name(self) -> &'static str {
+        match self {
+            Tool::Node => "node",
+            Tool::Python => "python",
+            Tool::Rust => "rust",
+            Tool::Go => "go",
+            Tool::Java => "java",
+            Tool::Ruby => "ruby",
+            Tool::Php => "php",
+        }
+    }
+
+    /// Name of the asdf plugin.
+    fn asdf_name(self) -> &'static str {
+        match self {
+            Tool::Node => "nodejs",
+            Tool::Go => "golang",
+            other => other.mise_name(),
+        }
+    }
+
+    /// Tool named `name` in `.tool-versions` or `mise.toml`.
+    fn from_name(name: &str) -> Option<Tool> {
+        TOOLS
+            .into_iter()
+            .find(|t| t.mise_name() == name || t.asdf_name() == name)
+    }
+
+    /// Files of the tool itself that pin its version.
+    fn own_files(self) -> &'static [&'static str] {
+        match self {
+            Tool::Node => &[".nvmrc", ".node-version"],
+            Tool::Python => &[".python-version"],
+            Tool::Rust => &["rust-toolchain.toml", "rust-toolchain"],
+            Tool::Go => &["go.mod"],
+            Tool::Java => &[".java-version"],
+            Tool::Ruby => &[".ruby-version"],
+            Tool::Php => &[],
+        }
+    }
+}
+
+/// A toolchain version the project asks for.
+#[derive(Debug, Clone)]
+pub struct Pin {
+    pub tool: Tool,
+    pub version: String,
+    /// File it was read from
+    pub source: &'static str,
+}
+
+/// Version in one of the tool's own files.
+fn own_pin(dir: &Path, file: &str) -> Option<String> {
+    let content = fs::read_to_string(dir.join(file)).ok()?;
+    match file {
+        "rust-toolchain.toml" => content.lines().find_map(|l| {
+            let (key, value) = l.split_once('=')?;
+            (key.trim() == "channel").then(|| value.trim().trim_matches('"').to_string())
+        }),
+        "go.mod" => content
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("go ").map(|v| v.trim().to_string())),
+        _ => {
+            let line = content.lines().map(str::trim).find(|l| !l.is_empty())?;
+            Some(line.trim_start_matches('v').to_string())
+        }
+    }
+}
+
+/// Versions listed in `.tool-versions` (`nodejs 20.11.0`), by tool.
+fn tool_versions(dir: &Path) -> Vec<(Tool, String)> {
+    let content = fs::read_to_string(dir.join(".tool-versions")).unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.split('#').next()?.trim();
+            let mut words = line.split_whitespace();
+            let tool = Tool::from_name(words.next()?)?;
+            Some((tool, words.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// mise's configuration of the project at `dir`, with its file name.
+fn mise_config(dir: &Path) -> Option<(&'static str, String)> {
+    ["mise.toml", ".mise.toml"]
+        .into_iter()
+        .find_map(|f| Some((f, fs::read_to_string(dir.join(f)).ok()?)))
+}
+
+/// Versions in `[tools]` of mise's configuration (`node = "20"`,
+/// `python = ["3.12"]`, `java = { version = "21" }`).
+fn mise_tools(dir: &Path) -> Vec<(Tool, String, &'static str)> {
+    let Some((file, content)) = mise_config(dir) else {
+        return Vec::new();
+    };
+    let Ok(doc) = content.parse::<toml_edit::DocumentMut>() else {
+        tracing::warn!("{file} inválido; ignorando as versões de [tools]");
+        return Vec::new();
+    };
+    let Some(tools) = doc.get("tools").and_then(|t| t.as_table_like()) else {
+        return Vec::new();
+    };
+    tools
+        .iter()
+        .filter_map(|(name, item)| {
+            let tool = Tool::from_name(name)?;
+            let version = item
+                .as_str()
+                .or_else(|| item.as_array()?.get(0)?.as_str())
+                .or_else(|| item.as_table_like()?.get("version")?.as_str())
+                .or_else(|| item.as_value()?.as_inline_table()?.get("version")?.as_str())?;
+            Some((tool, version.to_string(), file))
+        })
+        .collect()
+}
+
+/// Java pins of version managers carry the distribution (`temurin-21.0.2`),
+/// Go ones may be written as `go1.22`.
+fn normalize(tool: Tool, version: &str) -> String {
+    let version = version.trim();
+    match tool {
+        Tool::Java => match version.find(|c: char| c.is_ascii_digit()) {
+            Some(i) if version[..i].ends_with('-') => version[i..].to_string(),
+            _ => version.to_string(),
+        },
+        Tool::Go => version.trim_start_matches("go").to_string(),
+        _ => version.to_string(),
+    }
+}
+
+/// The version of `tool` the project at `dir` pins, if any.
+pub fn pin(dir: &Path, tool: Tool) -> Option<Pin> {
+    let own = tool
+        .own_files()
+        .iter()
+        .find_map(|f| Some((own_pin(dir, f)?, *f)));
+    let (version, source) = own
+        .or_else(|| {
+            tool_versions(dir)
+                .into_iter()
+                .find(|(t, _)| *t == tool)
+                .map(|(_, v)| (v, ".tool-versions"))
+        })
+        .or_else(|| {
+            mise_tools(dir)
+                .into_iter()
+                .find(|(t, _, _)| *t == tool)
+                .map(|(_, v, file)| (v, file))
+        })?;
+    Some(Pin {
+        tool,
+        version: normalize(tool, &version),
+        source,
+    })
+}
+
+/// Whether mise defines the task `name` in the project at `dir`: a
+/// `[tasks.<name>]` table of its configuration or a script in one of its task
+/// folders.
+pub fn mise_task(dir: &Path, name: &str) -> bool {
+    let in_config = mise_config(dir).is_some_and(|(_, content)| {
+        content
+            .parse::<toml_edit::DocumentMut>()
+            .ok()
+            .and_then(|doc| doc.get("tasks")?.get(name).map(|_| ()))
+            .is_some()
+    });
+    in_config
+        || [".mise/tasks", "mise-tasks", ".mise-tasks"]
+            .iter()
+            .any(|folder| dir.join(folder).join(name).is_file())
+}
+
+/// The version manager pinning the toolchains of the project at `dir`, when it
+/// is on PATH: mise reads its own configuration and `.tool-versions`, asdf
+/// only the latter.
+pub fn version_manager(dir: &Path) -> Option<Installer> {
+    let tool_versions = dir.join(".tool-versions").is_file();
+    if on_path("mise") && (tool_versions || mise_config(dir).is_some()) {
+        Some(Installer::Mise)
+    } else if on_path("asdf") && tool_versions {
+        Some(Installer::Asdf)
+    } else {
+        None
+    }
+}
+
+/// Folder of asdf's shims (`$ASDF_DATA_DIR/shims`, by default
+/// `~/.asdf/shims`), which run the version `.tool-versions` pins.
+pub fn asdf_shims() -> Option<PathBuf> {
+    let data = std::env::var_os("ASDF_DATA_DIR")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".asdf")))?;
+    Some(data.join("shims")).filter(|shims| shims.is_dir())
+}
+
+/// Every toolchain the project at `dir` pins.
+pub fn pins(dir: &Path) -> Vec<Pin> {
+    TOOLS.into_iter().filter_map(|t| pin(dir, t)).collect()
+}
+
+/// First version number in a `--version` output (`v20.11.0`, `go1.22.1`,
+/// `openjdk version "21.0.2"`).
+fn version_in(text: &str) -> Option<String> {
+    text.split_whitespace().find_map(|token| {
+        let token = token
+            .trim_matches(|c| c == '"' || c == '\'' || c == ',')
+            .trim_start_matches("go")
+            .trim_start_matches('v');
+        let version: String = token
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        let version = version.trim_end_matches('.');
+        (version.contains('.') && version.starts_with(|c: char| c.is_ascii_digit()))
+            .then(|| version.to_string())
+    })
+}
+
+/// Installed version of `tool` in the project at `dir` (where version
+/// manager shims pick the pinned one): `None` when the program isn't on
+/// PATH, `Some(None)` when its output has no version.
+pub fn installed(dir: &Path, tool: Tool) -> Option<Option<String>> {
+    let (program, args) = tool.program();
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // `java -version` prints to standard error
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Some(version_in(&text))
+}
+
+/// Whether `installed` satisfies the pinned `required` version: the go
+/// directive of go.mod is a minimum, the other pins name a release line
+/// (`20` is any 20.x); channels (`stable`, `lts/*`) match any release.
+pub fn satisfies(pin: &Pin, installed: &str) -> bool {
+    let required = pin.version.as_str();
+    if !required.starts_with(|c: char| c.is_ascii_digit()) {
+        return true;
+    }
+    if pin.tool == Tool::Go && pin.source == "go.mod" {
+        return crate::osv::compare_versions(installed, required).is_ge();
+    }
+    let installed: Vec<&str> = installed.split('.').collect();
+    required
+        .split('.')
+        .enumerate()
+        .all(|(i, part)| installed.get(i) == Some(&part))
+}
+
+pub fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|p| p.join(program).is_file()))
+        .unwrap_or(false)
+}
+
+/// A version manager able to install a toolchain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Installer {
+    Mise,
+    Asdf,
+    Rustup,
+    /// nvm is a shell function loaded from `nvm.sh`
+    Nvm(PathBuf),
+    Pyenv,
+}
+
+impl Installer {
+    /// The manager that would install `tool` on this machine.
+    pub fn find(tool: Tool) -> Option<Installer> {
+        if on_path("mise") {
+            return Some(Installer::Mise);
+        }
+        if on_path("asdf") {
+            return Some(Installer::Asdf);
+        }
+        match tool {
+            Tool::Rust if on_path("rustup") => Some(Installer::Rustup),
+            Tool::Python if on_path("pyenv") => Some(Installer::Pyenv),
+            Tool::Node => {
+                let dir = std::env::var_os("NVM_DIR")
+                    .map(PathBuf::from)
+                    .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".nvm")))?;
+                dir.join("nvm.sh")
+                    .is_file()
+                    .then(|| Installer::Nvm(dir.join("nvm.sh")))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Installer::Mise => "mise",
+            Installer::Asdf => "asdf",
+            Installer::Rustup => "rustup",
+            Installer::Nvm(_) => "nvm",
+            Installer::Pyenv => "pyenv",
+        }
+    }
+
+    /// Command line installing `version` of `tool`.
+    pub fn command_line(&self, tool: Tool, version: &str) -> String {
+        match self {
+            Installer::Mise => format!("mise install {}@{version}", tool.mise_name()),
+            Installer::Asdf => format!(
+                "asdf install {} {}",
+                tool.asdf_name(),
+                asdf_version(version)
+            ),
+            Installer::Rustup => format!("rustup toolchain install {version}"),
+            Installer::Nvm(_) => format!("nvm install {version}"),
+            Installer::Pyenv => format!("pyenv install --skip-existing {version}"),
+        }
+    }
+
+    /// Install `version` of `tool` in the project at `dir`, with the
+    /// manager's output on standard error.
+    pub fn install(&self, dir: &Path, tool: Tool, version: &str) -> std::io::Result<bool> {
+        let mut command = match self {
+            Installer::Mise => {
+                let mut c = Command::new("mise");
+                c.arg("install")
+                    .arg(format!("{}@{version}", tool.mise_name()));
+                c
+            }
+            Installer::Asdf => {
+                // Adding a plugin that is already there fails harmlessly
+                let _ = Command::new("asdf")
+                    .args(["plugin", "add", tool.asdf_name()])
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status();
+                let mut c = Command::new("asdf");
+                c.args(["install", tool.asdf_name(), &asdf_version(version)]);
+                c
+            }
+            Installer::Rustup => {
+                let mut c = Command::new("rustup");
+                c.args(["toolchain", "install", version]);
+                c
+            }
+            Installer::Nvm(script) => {
+                let mut c = Command::new("bash");
+                c.args(["-c", ". \"$0\" && nvm install \"$1\""])
+                    .arg(script)
+                    .arg(version);
+                c
+            }
+            Installer::Pyenv => {
+                let mut c = Command::new("pyenv");
+                c.args(["install", "--skip-existing", version]);
+                c
+            }
+        };
+        tracing::debug!("executando: {command:?}");
+        let status = command
+            .current_dir(dir)
+            .stdin(Stdio::inherit())
+            .stdout(std::io::stderr())
+            .stderr(Stdio::inherit())
+            .status()?;
+        Ok(status.success())
+    }
+}
+
+/// asdf installs exact versions; a release line is its latest one.
+fn asdf_version(version: &str) -> String {
+    if version.starts_with(|c: char| c.is_ascii_digit()) && version.matches('.').count() < 2 {
+        format!("latest:{version}")
+    } else {
+        version.to_string()
+    }
+}
+
+/// How to install `version` of `tool` by hand, without a known manager.
+pub fn manual_hint(tool: Tool, version: &str) -> String {
+    match tool {
+        Tool::Rust => format!("rustup toolchain install {version}"),
+        Tool::Node => format!("nvm install {version}"),
+        Tool::Python => format!("pyenv install {version}"),
+        Tool::Go => format!("go install golang.org/dl/go{version}@latest"),
+        Tool::Java => format!("sdk install java {version}-tem"),
+        Tool::Ruby | Tool::Php => format!("instale a versão {version} (ou use mise/asdf)"),
+    }
+}
@@ -0,0 +1,276 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! `dx dev-dependencies update --create-pr`: the updates on a branch, in a PR.
+//!
+//! Starting from a clean working tree, dx applies `dev-dependencies update`,
+//! commits the manifest on a new branch (`dx/update-<dependency>-<version>`,
+//! or `dx/update-dev-dependencies-<hash>` for several) with a conventional
+//! message that links the registry page of each new version, pushes it and
+//! opens a pull request (GitHub) or merge request (GitLab) through the API.
+//! The original branch is checked out again afterwards. The remote, the
+//! target branch and, for self-hosted instances, the provider, API URL and
+//! repository come from `[updates]` in dx.toml; the token from
+//! `DX_GIT_TOKEN`, else `GITHUB_TOKEN`/`GITLAB_TOKEN`.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
+
+use crate::error::{DxError, DxResult};
+use crate::git;
+use crate::settings::GitProvider;
+
+const TOKEN_ENV: &str = "DX_GIT_TOKEN";
+
+/// Command line overrides of `[updates]`.
+pub struct PrOptions {
+    pub remote: Option<String>,
+    pub base: Option<String>,
+}
+
+/// A dev dependency whose declared version changed.
+struct Change {
+    name: String,
+    from: String,
+    to: String,
+    url: Option<String>,
+}
+
+/// Where and how to open the PR.
+struct Hosting {
+    provider: GitProvider,
+    api: String,
+    repository: String,
+    token: String,
+}
+
+fn hosting(project_dir: &Path, remote_url: &str) -> DxResult<Hosting> {
+    let settings = crate::settings::load(project_dir)?.updates;
+    let (host, path) = crate::git::parse_remote(remote_url);
+    let provider = settings
+        .provider
+        .or_else(|| match host.as_deref()? {
+            "github.com" => Some(GitProvider::Github),
+            h if h.contains("gitlab") => Some(GitProvider::Gitlab),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            DxError::InvalidInput(format!(
+                "Não foi possível identificar o provedor de '{remote_url}'; defina [updates] provider = \"github\" ou \"gitlab\" no dx.toml."
+            ))
+        })?;
+    let api = match (settings.api_url, host.as_deref(), provider) {
+        (Some(api), _, _) => api.trim_end_matches('/').to_string(),
+        (None, Some("github.com"), GitProvider::Github) => "https://api.github.com".to_string(),
+        (None, Some(host), GitProvider::Github) => format!("https://{host}/api/v3"),
+        (None, Some(host), GitProvider::Gitlab) => format!("https://{host}/api/v4"),
+        (None, None, _) => {
+            return Err(DxError::InvalidInput(format!(
+                "Remote local '{remote_url}': defina [updates] api_url no dx.toml."
+            )));
+        }
+    };
+    let provider_env = match provider {
+        GitProvider::Github => "GITHUB_TOKEN",
+        GitProvider::Gitlab => "GITLAB_TOKEN",
+    };
+    let token = [TOKEN_ENV, provider_env]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|t| !t.is_empty()))
+        .ok_or_else(|| {
+            DxError::InvalidInput(format!(
+                "Token de acesso não encontrado: defina {TOKEN_ENV} (ou {provider_env})."
+            ))
+        })?;
+    Ok(Hosting {
+        provider,
+        api,
+        repository: settings.repository.unwrap_or(path),
+        token,
+    })
+}
+
+fn changes(project_dir: &Path, before: &BTreeMap<String, String>) -> Vec<Change> {
+    crate::dev_dependencies::versions(project_dir)
+        .into_iter()
+        .filter_map(|(name, to)| {
+            let from = before.get(&name)?.clone();
+            (from != to).then(|| Change {
+                url: crate::dev_dependencies::release_url(project_dir, &name, &to),
+                name,
+                from,
+                to,
+            })
+        })
+        .collect()
+}
+
+/// Branch name, stable for the same set of updates so a rerun doesn't open a
+/// second PR.
+fn branch_name(changes: &[Change]) -> String {
+    let slug = |s: &str| -> String {
+        s.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect::<String>()
+            .trim_matches('-')
+            .to_string()
+    };
+    match changes {
+        [change] => format!("dx/update-{}-{}", slug(&change.name), slug(&change.to)),
+        _ => {
+            let key: String = changes
+                .iter()
+                .map(|c| format!("{}@{}\n", c.name, c.to))
+                .collect();
+            format!(
+                "dx/update-dev-dependencies-{}",
+                &crate::sha256::hex(key.as_bytes())[..8]
+            )
+        }
+    }
+}
+
+fn title(changes: &[Change]) -> String {
+    match changes {
+        [change] => format!("chore(deps): update {} to {}", change.name, change.to),
+        _ => format!("chore(deps): update {} dev dependencies", changes.len()),
+    }
+}
+
+/// One Markdown line per update, with the link to the release page.
+fn summary(changes: &[Change]) -> String {
+    changes
+        .iter()
+        .map(|c| match &c.url {
+            Some(url) => format!("- {}: {} -> {} ({url})\n", c.name, c.from, c.to),
+            None => format!("- {}: {} -> {}\n", c.name, c.from, c.to),
+        })
+        .collect()
+}
+
+/// Open the PR/MR and return its URL.
+fn open(hosting: &Hosting, branch: &str, base: &str, title: &str, body: &str) -> DxResult<String> {
+    let (url, headers, payload, link) = match hosting.provider {
+        GitProvider::Github => (
+            format!("{}/repos/{}/pulls", hosting.api, hosting.repository),
+            vec![
+                ("Authorization", format!("Bearer {}", hosting.token)),
+                ("Accept", "application/vnd.github+json".to_string()),
+            ],
+            json!({"title": title, "head": branch, "base": base, "body": body}),
+            "html_url",
+        ),
+        GitProvider::Gitlab => (
+            format!(
+                "{}/projects/{}/merge_requests",
+                hosting.api,
+                hosting.repository.replace('/', "%2F")
+            ),
+            vec![("PRIVATE-TOKEN", hosting.token.clone())],
+            json!({
+                "title": title,
+                "source_branch": branch,
+                "target_branch": base,
+                "description": body,
+                "remove_source_branch": true,
+            }),
+            "web_url",
+        ),
+    };
+    let headers: Vec<(&str, &str)> = headers.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    let (status, response) = crate::net::send_json(&url, &headers, &payload)
+        .map_err(|e| DxError::Network(format!("Falha ao abrir o PR em {url}: {e}")))?;
+    let response: Value = serde_json::from_str(&response).unwrap_or(Value::String(response));
+    if !(200..300).contains(&status) {
+        let message = response
+            .get("message")
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| response.to_string());
+        return Err(DxError::Network(format!(
+            "A API recusou o PR (HTTP {status}): {message}"
+        )));
+    }
+    Ok(response
+        .get(link)
+        .and_then(|u| u.as_str())
+        .unwrap_or_default()
+        .to_string())
+}
+
+/// Update `name` (or every dev dependency) on a new branch and open a PR.
+pub fn create(dir: Option<PathBuf>, name: Option<String>, options: PrOptions) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    git::run(&project_dir, &["rev-parse", "--show-toplevel"]).map_err(|_| {
+        DxError::InvalidInput(format!(
+            "{} não está num repositório git.",
+            project_dir.display()
+        ))
+    })?;
+    if !git::run(&project_dir, &["status", "--porcelain"])?.is_empty() {
+        return Err(DxError::InvalidInput(
+            "Há alterações não commitadas; faça commit ou stash antes de criar o PR.".to_string(),
+        ));
+    }
+    let settings = crate::settings::load(&project_dir)?.updates;
+    let remote = options
+        .remote
+        .or(settings.remote)
+        .unwrap_or_else(|| "origin".to_string());
+    let remote_url = git::run(&project_dir, &["remote", "get-url", &remote])
+        .map_err(|_| DxError::InvalidInput(format!("Remote '{remote}' não configurado.")))?;
+    let hosting = hosting(&project_dir, &remote_url)?;
+    let current = git::run(&project_dir, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+    let base = options.base.or(settings.base).unwrap_or(current.clone());
+
+    let before = crate::dev_dependencies::versions(&project_dir);
+    let updated = crate::dev_dependencies::update(Some(project_dir.clone()), name);
+    let changes = changes(&project_dir, &before);
+    if updated.is_err() || changes.is_empty() {
+        // Manifests rewritten with the same versions (or half-updated) stay as they were
+        git::run(&project_dir, &["checkout", "--", "."])?;
+        updated?;
+        outln!("Nenhuma dependência com versão nova; nenhum PR criado.");
+        crate::output::set_data(json!({"changes": [], "url": null}));
+        return Ok(());
+    }
+
+    let branch = branch_name(&changes);
+    let title = title(&changes);
+    let summary = summary(&changes);
+    git::run(&project_dir, &["checkout", "-b", &branch])?;
+    let committed = git::run(
+        &project_dir,
+        &["commit", "--all", "--quiet", "-m", &title, "-m", &summary],
+    );
+    if committed.is_err() {
+        git::run(&project_dir, &["checkout", "--", "."])?;
+    }
+    git::run(&project_dir, &["checkout", "--quiet", &current])?;
+    if let Err(e) = committed {
+        git::run(&project_dir, &["branch", "-D", &branch])?;
+        return Err(e);
+    }
+    git::run(&project_dir, &["push", "--quiet", &remote, &branch])?;
+    outln!("Branch '{branch}' publicada em '{remote}'.");
+
+    let body = format!("Atualizado por `dx dev-dependencies update`:\n\n{summary}");
+    let url = open(&hosting, &branch, &base, &title, &body)?;
+    outln!("PR aberto: {url}");
+    crate::output::set_data(json!({
+        "branch": branch,
+        "base": base,
+        "url": url,
+        "changes": changes
+            .iter()
+            .map(|c| json!({"name": c.name, "from": c.from, "to": c.to, "url": c.url}))
+            .collect::<Vec<_>>(),
+    }));
+    Ok(())
+}
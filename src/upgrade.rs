@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Versioned `.dx` folders and `dx upgrade`.
+//!
+//! Every time dx writes the manifests it also records `.dx/meta.json` with the
+//! format (`schema_version`) they were generated in. A `.dx` without it predates
+//! the versioning and is format 0. When the templates change in a way that breaks
+//! existing folders, a migration is appended to [`MIGRATIONS`] and
+//! [`SCHEMA_VERSION`] follows it; `dx upgrade` (and `dx dev-services run`, before
+//! starting the containers) applies the pending ones in order and prints their
+//! changelog.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::error::{DxError, DxResult, IoContext};
+
+/// Format written by this version of dx.
+pub const SCHEMA_VERSION: u32 = 1;
+
+const META_FILE: &str = "meta.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Meta {
+    schema_version: u32,
+    /// dx that last wrote or upgraded the folder
+    #[serde(default)]
+    dx_version: String,
+}
+
+/// One step of the format: brings a `.dx` from `version - 1` to `version`.
+pub struct Migration {
+    pub version: u32,
+    /// Changelog line shown when the migration runs
+    pub description: &'static str,
+    /// Returns whether anything in the folder changed
+    apply: fn(&Path) -> DxResult<bool>,
+}
+
+/// Registered migrations, in order; the last one is [`SCHEMA_VERSION`].
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "Caminhos de telemetry no compose viram bind mounts relativos ao .dx (./telemetry/)",
+    apply: relative_telemetry_mounts,
+}];
+
+/// v1: compose files written before the telemetry configs moved next to the
+/// manifest mount `.dx/telemetry/...` (resolved against `.dx`, so it points to
+/// `.dx/.dx/telemetry`) or `telemetry/...` (a named volume, not a bind mount).
+fn relative_telemetry_mounts(dx_dir: &Path) -> DxResult<bool> {
+    let compose = dx_dir.join("docker-compose.yml");
+    let Ok(content) = fs::read_to_string(&compose) else {
+        return Ok(false);
+    };
+    let mut fixed = content.replace(".dx/telemetry/", "telemetry/");
+    if !fixed.contains("./telemetry/") {
+        fixed = fixed.replace("telemetry/", "./telemetry/");
+    }
+    if fixed == content {
+        return Ok(false);
+    }
+    fs::write(&compose, fixed).context(format!("Erro ao gravar {}", compose.display()))?;
+    Ok(true)
+}
+
+fn meta_path(dx_dir: &Path) -> PathBuf {
+    dx_dir.join(META_FILE)
+}
+
+/// Format of the `.dx` in `project_dir`: `None` when there are no manifests yet,
+/// 0 for folders written before `meta.json` existed.
+pub fn current_version(project_dir: &Path) -> DxResult<Option<u32>> {
+    let dx_dir = project_dir.join(".dx");
+    let path = meta_path(&dx_dir);
+    match fs::read_to_string(&path) {
+        Ok(content) => {
+            let meta: Meta = serde_json::from_str(&content)
+                .map_err(|e| DxError::InvalidInput(format!("{} inválido: {e}", path.display())))?;
+            Ok(Some(meta.schema_version))
+        }
+        Err(_) if dx_dir.join("docker-compose.yml").is_file() => Ok(Some(0)),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Record that the `.dx` in `project_dir` is in the current format.
+pub fn write_meta(project_dir: &Path) -> std::io::Result<()> {
+    let meta = Meta {
+        schema_version: SCHEMA_VERSION,
+        dx_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    let dx_dir = project_dir.join(".dx");
+    fs::create_dir_all(&dx_dir)?;
+    let content = serde_json::to_string_pretty(&meta).map_err(std::io::Error::other)?;
+    fs::write(meta_path(&dx_dir), content + "\n")
+}
+
+/// Migrations that take a `.dx` in format `from` to the current one.
+pub fn pending(from: u32) -> impl Iterator<Item = &'static Migration> {
+    MIGRATIONS.iter().filter(move |m| m.version > from)
+}
+
+fn newer_format(project_dir: &Path, version: u32) -> DxError {
+    DxError::Unsupported(format!(
+        "{} está no formato v{version}, mais novo que o desta versão do dx (v{SCHEMA_VERSION}). Atualize o dx.",
+        project_dir.join(".dx").display()
+    ))
+}
+
+/// Apply the pending migrations of `project_dir` and print the changelog.
+pub fn apply_pending(project_dir: &Path) -> DxResult {
+    let Some(from) = current_version(project_dir)? else {
+        return Ok(());
+    };
+    if from > SCHEMA_VERSION {
+        return Err(newer_format(project_dir, from));
+    }
+    if from == SCHEMA_VERSION {
+        return Ok(());
+    }
+    let dx_dir = project_dir.join(".dx");
+    outln!(
+        "Atualizando {} (v{from} -> v{SCHEMA_VERSION}):",
+        dx_dir.display()
+    );
+    for migration in pending(from) {
+        let changed = (migration.apply)(&dx_dir)?;
+        outln!(
+            "- v{}: {}{}",
+            migration.version,
+            migration.description,
+            if changed { "" } else { " (nada a alterar)" }
+        );
+    }
+    write_meta(project_dir).context(format!("Erro ao gravar {}", meta_path(&dx_dir).display()))
+}
+
+pub struct UpgradeOptions {
+    pub dry_run: bool,
+}
+
+/// `dx upgrade`: bring the `.dx` folder to the format of this version of dx.
+pub fn run(dir: Option<PathBuf>, opts: UpgradeOptions) -> DxResult {
+    let project_dir =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !project_dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            project_dir.display()
+        )));
+    }
+    let Some(from) = current_version(&project_dir)? else {
+        return Err(DxError::NotFound(format!(
+            "Nenhum manifesto em {}. Gere com: dx dev-services",
+            project_dir.join(".dx").display()
+        )));
+    };
+    if from > SCHEMA_VERSION {
+        return Err(newer_format(&project_dir, from));
+    }
+
+    let migrations: Vec<&Migration> = pending(from).collect();
+    crate::output::set_data(json!({
+        "from": from,
+        "to": SCHEMA_VERSION,
+        "dry_run": opts.dry_run,
+        "migrations": migrations
+            .iter()
+            .map(|m| json!({ "version": m.version, "description": m.description }))
+            .collect::<Vec<_>>(),
+    }));
+
+    if from == SCHEMA_VERSION {
+        outln!("O .dx já está no formato atual (v{SCHEMA_VERSION}).");
+        return Ok(());
+    }
+    if opts.dry_run {
+        outln!("Migrações pendentes (v{from} -> v{SCHEMA_VERSION}):");
+        for m in &migrations {
+            outln!("- v{}: {}", m.version, m.description);
+        }
+        outln!("\nPara aplicar: dx upgrade");
+        return Ok(());
+    }
+
+    apply_pending(&project_dir)?;
+    outln!("\n.dx atualizado para o formato v{SCHEMA_VERSION}.");
+    Ok(())
+}
@@ -0,0 +1,238 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! `dx watch`: keeps the `.dx` artifacts in sync with the code.
+//!
+//! The Dev Services manifest (`dx dev-services`), the README badges
+//! (`dx dev-badges`) and the analyzer report (`dx analyzer`) are generated
+//! once, then refreshed after each quiet period following file changes. Only
+//! stale artifacts are regenerated: the detection (through the detection
+//! cache) tells whether the services changed, so an edit that doesn't touch
+//! them only refreshes the report. Each artifact is written by its own dx
+//! command, run as a child process; a failure is reported and watching goes
+//! on. In CI mode the artifacts are generated once and the command exits.
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{RecvTimeoutError, channel};
+use std::time::Duration;
+
+use notify::{EventKind, RecursiveMode, Watcher, recommended_watcher};
+use serde_json::Value;
+
+use crate::error::{DxError, DxResult};
+
+/// Quiet period after the last file event before refreshing.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Directories whose changes never affect the artifacts.
+const IGNORED_DIRS: &[&str] = &[".git", "target", "node_modules", "build", "dist", "vendor"];
+
+pub struct WatchOptions {
+    /// Global flags forwarded to each run (`--no-cache`, `--offline`, ...)
+    pub global_args: Vec<String>,
+}
+
+/// An artifact kept in sync, in the order they are regenerated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Artifact {
+    Manifest,
+    Badges,
+    Report,
+}
+
+impl Artifact {
+    /// dx command that writes it.
+    fn command(self) -> &'static str {
+        match self {
+            Artifact::Manifest => "dev-services",
+            Artifact::Badges => "dev-badges",
+            Artifact::Report => "analyzer",
+        }
+    }
+
+    fn updated(self) -> &'static str {
+        match self {
+            Artifact::Manifest => "Manifesto .dx/docker-compose.yml atualizado.",
+            Artifact::Badges => "Badges do README atualizadas.",
+            Artifact::Report => "Relatório do analyzer atualizado.",
+        }
+    }
+}
+
+/// What the artifacts were last generated from.
+#[derive(Default)]
+struct Synced {
+    /// Detected Dev Services configuration (the manifest's input)
+    config: Option<Value>,
+    /// Service names (the badges' input)
+    services: Option<Vec<String>>,
+}
+
+impl Synced {
+    /// Artifacts made stale by `changed` (relative paths).
+    fn stale(&mut self, project: &Path, changed: &BTreeSet<PathBuf>) -> BTreeSet<Artifact> {
+        let detected = crate::dev_services::detect_dependencies(project);
+        let services = detected.service_names();
+        let config = serde_json::to_value(&detected).unwrap_or_default();
+        // dx.toml and the compose override shape the manifest beyond detection
+        let settings = changed
+            .iter()
+            .any(|p| p == Path::new("dx.toml") || is_override(p));
+
+        let mut stale = BTreeSet::from([Artifact::Report]);
+        if settings || self.config.as_ref() != Some(&config) {
+            stale.insert(Artifact::Manifest);
+            // `dev-services` rewrites the report along with the manifest
+            if !services.is_empty() {
+                stale.remove(&Artifact::Report);
+            }
+        }
+        if settings || self.services.as_ref() != Some(&services) {
+            stale.insert(Artifact::Badges);
+        }
+        self.config = Some(config);
+        self.services = Some(services);
+        stale
+    }
+}
+
+fn is_override(path: &Path) -> bool {
+    crate::compose::OVERRIDE_FILES
+        .iter()
+        .any(|name| path == Path::new(".dx").join(name))
+}
+
+/// Whether a change to `path` (relative to the project) can make an artifact
+/// stale. The artifacts themselves are excluded, so writing them doesn't
+/// trigger another refresh.
+fn relevant(path: &Path, report: Option<&Path>) -> bool {
+    if path.as_os_str().is_empty() || path == Path::new("README.md") || Some(path) == report {
+        return false;
+    }
+    if path.starts_with(".dx") {
+        return is_override(path);
+    }
+    !path.components().any(|c| {
+        let c = c.as_os_str().to_string_lossy();
+        IGNORED_DIRS.contains(&c.as_ref())
+    })
+}
+
+/// Run `dx <artifact command>` in `project`; false if it failed.
+fn regenerate(exe: &Path, project: &Path, artifact: Artifact, opts: &WatchOptions) -> bool {
+    tracing::debug!(command = artifact.command(), "regenerando");
+    let output = Command::new(exe)
+        .args(&opts.global_args)
+        .arg(artifact.command())
+        .current_dir(project)
+        .stdin(Stdio::null())
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            outln!("{}", artifact.updated());
+            true
+        }
+        Ok(output) => {
+            eprintln!(
+                "'dx {}' falhou ({}):\n{}",
+                artifact.command(),
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim_end()
+            );
+            false
+        }
+        Err(e) => {
+            eprintln!("Erro ao executar 'dx {}': {e}", artifact.command());
+            false
+        }
+    }
+}
+
+/// Regenerate the artifacts made stale by `changed`; false if any failed.
+fn sync(
+    exe: &Path,
+    project: &Path,
+    synced: &mut Synced,
+    changed: &BTreeSet<PathBuf>,
+    opts: &WatchOptions,
+) -> bool {
+    let mut ok = true;
+    // Every stale artifact is regenerated, even after a failure
+    for artifact in synced.stale(project, changed) {
+        ok &= regenerate(exe, project, artifact, opts);
+    }
+    ok
+}
+
+/// `dx watch`: keep the artifacts of the project at `dir` in sync until
+/// interrupted.
+pub fn run(dir: Option<PathBuf>, opts: WatchOptions) -> DxResult {
+    let dir = dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    // Canonical, so the watcher's event paths can be made relative to it
+    let project = dir
+        .canonicalize()
+        .ok()
+        .filter(|d| d.is_dir())
+        .ok_or_else(|| DxError::InvalidInput(format!("Diretório inválido: {}", dir.display())))?;
+    let exe = std::env::current_exe()
+        .map_err(|e| DxError::Tool(format!("Executável do dx não encontrado: {e}")))?;
+    let report = crate::settings::get(&project).report_path(&project);
+    let report = report.strip_prefix(&project).ok();
+
+    let mut synced = Synced::default();
+    let ok = sync(&exe, &project, &mut synced, &BTreeSet::new(), &opts);
+    if crate::ci::is_ci() {
+        outln!("Modo CI: artefatos gerados uma única vez (sem monitorar alterações).");
+        return if ok {
+            Ok(())
+        } else {
+            Err(DxError::Tool(
+                "Não foi possível atualizar todos os artefatos.".to_string(),
+            ))
+        };
+    }
+
+    let (tx, rx) = channel();
+    let mut watcher = recommended_watcher(move |res| {
+        tx.send(res).ok();
+    })
+    .map_err(|e| DxError::Tool(format!("não foi possível iniciar watcher: {e}")))?;
+    watcher
+        .watch(&project, RecursiveMode::Recursive)
+        .map_err(|e| DxError::Tool(format!("não foi possível observar diretório: {e}")))?;
+    outln!(
+        "Monitorando alterações em {} (Ctrl-C para sair)",
+        project.display()
+    );
+
+    let mut pending = BTreeSet::new();
+    loop {
+        match rx.recv_timeout(DEBOUNCE) {
+            Ok(Ok(event)) => {
+                if !matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                ) {
+                    continue;
+                }
+                pending.extend(
+                    event
+                        .paths
+                        .iter()
+                        .filter_map(|p| p.strip_prefix(&project).ok())
+                        .filter(|p| relevant(p, report))
+                        .map(Path::to_path_buf),
+                );
+            }
+            Ok(Err(e)) => tracing::warn!(error = %e, "erro do watcher"),
+            Err(RecvTimeoutError::Timeout) if !pending.is_empty() => {
+                let changed = std::mem::take(&mut pending);
+                outln!("Alterações detectadas em {} arquivo(s).", changed.len());
+                sync(&exe, &project, &mut synced, &changed, &opts);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    Ok(())
+}
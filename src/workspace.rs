@@ -0,0 +1,348 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Subproject discovery and `dx all`, which fans a dx command out to every
+//! subproject of a workspace.
+//!
+//! Each subproject runs `dx <command>` as a child process with the
+//! subproject as working directory, at most `--jobs` at a time. Output lines
+//! are prefixed with the subproject name; a summary closes the run.
+use std::ffi::OsString;
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::{DxError, DxResult};
+
+/// Files that mark the root of a project.
+pub const PROJECT_MARKERS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "requirements.txt",
+    "pyproject.toml",
+    "setup.py",
+    "pom.xml",
+    "build.gradle",
+    "Gemfile",
+    "go.mod",
+    "composer.json",
+];
+
+/// Directories never searched for subprojects (besides hidden ones).
+const SKIP_DIRS: &[&str] = &[
+    ".git",
+    ".github",
+    ".idea",
+    ".vscode",
+    ".dx",
+    "node_modules",
+    "target",
+    "build",
+    "dist",
+    "vendor",
+];
+
+pub fn is_project_root(dir: &Path) -> bool {
+    PROJECT_MARKERS.iter().any(|m| dir.join(m).is_file())
+}
+
+/// Project roots under `root` (not `root` itself), sorted. Directories that
+/// aren't projects are searched up to `max_depth` levels deep; a project's own
+/// subdirectories are not.
+pub fn subprojects(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    collect(root, max_depth, &mut found);
+    found.sort();
+    found
+}
+
+fn collect(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) {
+    if depth == 0 {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if name.starts_with('.') || SKIP_DIRS.iter().any(|s| s.eq_ignore_ascii_case(name)) {
+            continue;
+        }
+        if is_project_root(&path) {
+            found.push(path);
+        } else {
+            collect(&path, depth - 1, found);
+        }
+    }
+}
+
+/// Folder name that used to enable the recursive mode implicitly. Still
+/// honored, with a deprecation warning.
+const LEGACY_RECURSIVE_DIR: &str = "test-projects";
+
+/// Run `f` on `dir` or, with `recursive`, on each of its subprojects (the
+/// analyzer's discovery). `action` labels the recursive run.
+pub fn for_each_project(
+    dir: &Path,
+    recursive: bool,
+    action: &str,
+    f: impl Fn(&Path) -> DxResult,
+) -> DxResult {
+    let legacy = !recursive
+        && dir.is_dir()
+        && dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.eq_ignore_ascii_case(LEGACY_RECURSIVE_DIR));
+    if legacy {
+        tracing::warn!(
+            "processar os projetos de uma pasta '{LEGACY_RECURSIVE_DIR}' sem --recursive está obsoleto e deixará de funcionar; use --recursive"
+        );
+    }
+    if !recursive && !legacy {
+        return f(dir);
+    }
+    if !dir.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            dir.display()
+        )));
+    }
+
+    let projects = subprojects(dir, 1);
+    if projects.is_empty() {
+        return Err(DxError::NotFound(format!(
+            "Nenhum subprojeto encontrado em {}",
+            dir.display()
+        )));
+    }
+    outln!("{action} em todos os projetos dentro de: {}", dir.display());
+    let mut failure = None;
+    for project in projects {
+        outln!("\n== Projeto: {} ==", project.display());
+        if let Err(e) = f(&project) {
+            eprintln!("{e}");
+            failure = Some(e);
+        }
+    }
+    failure.map_or(Ok(()), Err)
+}
+
+pub struct AllOptions {
+    /// Command (and its arguments) to run in each subproject
+    pub command: Vec<OsString>,
+    /// Maximum number of subprojects processed at the same time
+    pub jobs: Option<usize>,
+    /// How deep to look for subprojects
+    pub max_depth: usize,
+    /// Global flags forwarded to each run (`--output json`, `--ci`, ...)
+    pub global_args: Vec<String>,
+}
+
+struct ProjectRun {
+    name: String,
+    path: PathBuf,
+    code: Option<i32>,
+    elapsed: Duration,
+    /// JSON envelope printed by the child in `--output json` mode
+    result: Option<serde_json::Value>,
+}
+
+/// Print `reader`'s lines to stdout/stderr (following `outln!`) with `prefix`.
+fn forward_lines(reader: impl Read, prefix: &str, to_stderr: bool) {
+    for line in BufReader::new(reader).lines().map_while(Result::ok) {
+        if to_stderr {
+            eprintln!("{prefix}{line}");
+        } else {
+            outln!("{prefix}{line}");
+        }
+    }
+}
+
+fn run_one(exe: &Path, project: &Path, name: &str, opts: &AllOptions, prefix: &str) -> ProjectRun {
+    let start = Instant::now();
+    let json = crate::output::is_json();
+    let child = Command::new(exe)
+        .args(&opts.global_args)
+        .args(&opts.command)
+        .current_dir(project)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{prefix}falha ao executar dx: {e}");
+            return ProjectRun {
+                name: name.to_string(),
+                path: project.to_path_buf(),
+                code: None,
+                elapsed: start.elapsed(),
+                result: None,
+            };
+        }
+    };
+
+    let stdout = child.stdout.take().expect("piped stdout");
+    let stderr = child.stderr.take().expect("piped stderr");
+    let (result, status) = std::thread::scope(|s| {
+        s.spawn(|| forward_lines(stderr, prefix, true));
+        let result = if json {
+            // The child's stdout is its JSON envelope: keep it for the summary
+            let mut out = String::new();
+            let _ = BufReader::new(stdout).read_to_string(&mut out);
+            serde_json::from_str(&out).ok()
+        } else {
+            forward_lines(stdout, prefix, false);
+            None
+        };
+        (result, child.wait())
+    });
+
+    ProjectRun {
+        name: name.to_string(),
+        path: project.to_path_buf(),
+        code: status.ok().and_then(|s| s.code()),
+        elapsed: start.elapsed(),
+        result,
+    }
+}
+
+/// `dx all <command>`: run `dx <command>` in every subproject of `dir`.
+pub fn run_all(dir: Option<PathBuf>, opts: AllOptions) -> DxResult {
+    let root =
+        dir.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    if !root.is_dir() {
+        return Err(DxError::InvalidInput(format!(
+            "Diretório inválido: {}",
+            root.display()
+        )));
+    }
+    let Some(first) = opts.command.first() else {
+        return Err(DxError::InvalidInput(
+            "Informe o comando a executar, ex.: dx all analyzer".to_string(),
+        ));
+    };
+    if first == "all" || first == "run-all" {
+        return Err(DxError::InvalidInput(
+            "'dx all' não pode ser aninhado.".to_string(),
+        ));
+    }
+    let exe = std::env::current_exe().map_err(|source| DxError::Io {
+        context: "Erro ao localizar o executável do dx".to_string(),
+        source,
+    })?;
+
+    let projects = crate::progress::phase("Procurando subprojetos", || {
+        subprojects(&root, opts.max_depth)
+    });
+    let command_line = opts
+        .command
+        .iter()
+        .map(|a| a.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if projects.is_empty() {
+        return Err(DxError::NotFound(format!(
+            "Nenhum subprojeto encontrado em {}",
+            root.display()
+        )));
+    }
+
+    let names: Vec<String> = projects
+        .iter()
+        .map(|p| {
+            p.strip_prefix(&root)
+                .unwrap_or(p)
+                .display()
+                .to_string()
+                .replace('\\', "/")
+        })
+        .collect();
+    let width = names.iter().map(|n| n.chars().count()).max().unwrap_or(0);
+    let jobs = opts
+        .jobs
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(4, |n| n.get()))
+        .max(1)
+        .min(projects.len());
+    outln!(
+        "Executando 'dx {command_line}' em {} projeto(s) de {} ({} em paralelo)...\n",
+        projects.len(),
+        root.display(),
+        jobs
+    );
+
+    // Workers pull the next subproject from a shared queue
+    let queue = Mutex::new(projects.iter().zip(&names).enumerate());
+    let runs: Mutex<Vec<(usize, ProjectRun)>> = Mutex::new(Vec::new());
+    std::thread::scope(|s| {
+        for _ in 0..jobs {
+            s.spawn(|| {
+                loop {
+                    let next = queue.lock().unwrap().next();
+                    let Some((i, (project, name))) = next else {
+                        break;
+                    };
+                    let prefix = format!("[{name:<width$}] ");
+                    let run = run_one(&exe, project, name, &opts, &prefix);
+                    runs.lock().unwrap().push((i, run));
+                }
+            });
+        }
+    });
+    let mut runs = runs.into_inner().unwrap();
+    runs.sort_by_key(|(i, _)| *i);
+    let runs: Vec<ProjectRun> = runs.into_iter().map(|(_, r)| r).collect();
+
+    let failed = runs.iter().filter(|r| r.code != Some(0)).count();
+    outln!("\nResumo (dx {command_line}):");
+    for run in &runs {
+        let status = match run.code {
+            Some(0) => "ok".to_string(),
+            Some(code) => format!("falhou (código {code})"),
+            None => "falhou (interrompido)".to_string(),
+        };
+        outln!(
+            "- {:<width$}  {status} em {}",
+            run.name,
+            crate::progress::format_duration(run.elapsed)
+        );
+    }
+    outln!(
+        "{} de {} projeto(s) concluído(s) com sucesso.",
+        runs.len() - failed,
+        runs.len()
+    );
+
+    crate::output::set_data(serde_json::json!({
+        "command": command_line,
+        "root": root.display().to_string(),
+        "projects": runs
+            .iter()
+            .map(|r| serde_json::json!({
+                "name": r.name,
+                "path": r.path.display().to_string(),
+                "exit_code": r.code,
+                "ms": r.elapsed.as_millis() as u64,
+                "result": r.result,
+            }))
+            .collect::<Vec<_>>(),
+        "succeeded": runs.len() - failed,
+        "failed": failed,
+    }));
+
+    if failed > 0 {
+        return Err(DxError::CheckFailed(format!(
+            "'dx {command_line}' falhou em {failed} de {} projeto(s).",
+            runs.len()
+        )));
+    }
+    Ok(())
+}
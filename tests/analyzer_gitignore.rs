@@ -4,7 +4,7 @@ use std::io::Write;
 use std::process::Command;
 
 #[test]
-fn analyzer_adds_dx_to_gitignore_when_missing() {
+fn analyzer_adds_dx_to_gitignore_only_when_asked() {
     // Prepare an isolated temp project directory
     let tmp = env::temp_dir();
     let test_dir = tmp.join(format!("dx-cli-analyzer-gitignore-{}",
@@ -18,15 +18,27 @@ fn analyzer_adds_dx_to_gitignore_when_missing() {
     // Ensure there is no .gitignore initially
     assert!(!test_dir.join(".gitignore").exists());
 
-    // Run analyzer without saving report
+    // Run analyzer without saving report: .gitignore must be left alone
     let exe = env!("CARGO_BIN_EXE_dx");
     let status = Command::new(exe)
+        .env("DX_CI", "0")
         .arg("analyzer")
         .arg("--no-save")
         .arg(test_dir.to_string_lossy().to_string())
         .status()
         .expect("failed to run analyzer");
     assert!(status.success());
+    assert!(!test_dir.join(".gitignore").exists());
+
+    // Opt-in flag
+    let status = Command::new(exe)
+        .env("DX_CI", "0")
+        .arg("analyzer")
+        .arg("--write-gitignore")
+        .arg(test_dir.to_string_lossy().to_string())
+        .status()
+        .expect("failed to run analyzer");
+    assert!(status.success());
 
     // .gitignore should be created with .dx entry
     let gi_path = test_dir.join(".gitignore");
@@ -37,7 +49,7 @@ fn analyzer_adds_dx_to_gitignore_when_missing() {
 }
 
 #[test]
-fn analyzer_does_not_duplicate_dx_entry() {
+fn ignore_setup_does_not_duplicate_dx_entry() {
     // Prepare an isolated temp project directory
     let tmp = env::temp_dir();
     let test_dir = tmp.join(format!("dx-cli-analyzer-gitignore-dupe-{}",
@@ -52,20 +64,54 @@ fn analyzer_does_not_duplicate_dx_entry() {
     let mut f = fs::File::create(test_dir.join(".gitignore")).unwrap();
     write!(f, "target\n.DS_Store").unwrap();
 
-    // Run analyzer without saving
+    // Run setup twice
     let exe = env!("CARGO_BIN_EXE_dx");
-    let status = Command::new(exe)
-        .arg("analyzer")
-        .arg("--no-save")
-        .arg(test_dir.to_string_lossy().to_string())
-        .status()
-        .expect("failed to run analyzer");
-    assert!(status.success());
+    for _ in 0..2 {
+        let status = Command::new(exe)
+            .env("DX_CI", "0")
+            .args(["ignore", "setup"])
+            .arg(test_dir.to_string_lossy().to_string())
+            .status()
+            .expect("failed to run ignore setup");
+        assert!(status.success());
+    }
 
     // Verify single .dx line exists
     let content = fs::read_to_string(test_dir.join(".gitignore")).unwrap();
     let dx_count = content.lines().filter(|l| l.trim() == ".dx").count();
     assert_eq!(dx_count, 1, "expected exactly one .dx entry, got {} in: {}", dx_count, content);
+    assert!(content.starts_with("target\n.DS_Store\n"), "content was: {}", content);
 
     let _ = fs::remove_dir_all(&test_dir);
 }
+
+#[test]
+fn ignore_setup_artifacts_keeps_dx_versioned() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join(".gitignore"), "/.dx/backups\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CI", "0")
+        .args(["ignore", "setup", "--artifacts", "--entry", "coverage/"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run ignore setup");
+    assert!(output.status.success());
+
+    let content = fs::read_to_string(tmp.path().join(".gitignore")).unwrap();
+    assert!(!content.lines().any(|l| l.trim() == ".dx"), "{}", content);
+    assert!(!content.contains(".dx/backups/"), "equivalent entry was duplicated: {}", content);
+    assert!(content.contains(".dx/badges/*.svg"), "{}", content);
+    assert!(content.contains("coverage/"), "{}", content);
+
+    // Never written in CI mode
+    let other = tempfile::tempdir().expect("tempdir");
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CI", "1")
+        .args(["ignore", "setup"])
+        .arg(other.path())
+        .output()
+        .expect("failed to run ignore setup");
+    assert_eq!(output.status.code(), Some(8));
+    assert!(!other.path().join(".gitignore").exists());
+}
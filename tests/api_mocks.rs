@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const OPENAPI: &str = "openapi: 3.0.3\ninfo:\n  title: Orders\n  version: '1'\npaths: {}\n";
+const PROTO: &str = "syntax = \"proto3\";\npackage greeter;\n\nservice Greeter {\n  rpc SayHello (Hello) returns (Hello);\n}\n\nmessage Hello {\n  string name = 1;\n}\n";
+
+fn dev_services(dir: &Path) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .args(["--no-cache", "dev-services"])
+        .arg(dir)
+        .output()
+        .expect("failed to run dx");
+    assert!(output.status.success(), "{output:?}");
+    fs::read_to_string(dir.join(".dx/docker-compose.yml")).unwrap()
+}
+
+#[test]
+fn specs_become_mock_servers_in_the_manifest() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::create_dir_all(dir.join("api")).unwrap();
+    fs::create_dir_all(dir.join("proto")).unwrap();
+    fs::write(dir.join("api/orders.yaml"), OPENAPI).unwrap();
+    fs::write(dir.join("proto/greeter.proto"), PROTO).unwrap();
+    // Neither a spec nor a service definition
+    fs::write(dir.join("api/config.yaml"), "server:\n  port: 8080\n").unwrap();
+    fs::write(dir.join("proto/types.proto"), "message Empty {}\n").unwrap();
+
+    let compose = dev_services(dir);
+    assert!(
+        compose.contains(
+            "  api-mock:\n    image: stoplight/prism:5\n    command: mock -h 0.0.0.0 -p 4010 /specs/api/orders.yaml\n    profiles:\n    - mocks\n"
+        ),
+        "{compose}"
+    );
+    assert!(
+        compose.contains("    - ../api/orders.yaml:/specs/api/orders.yaml:ro\n"),
+        "{compose}"
+    );
+    assert!(
+        compose.contains("    command: --imports=/proto /proto/proto/greeter.proto\n"),
+        "{compose}"
+    );
+    assert!(compose.contains("    - 4770:4770\n"), "{compose}");
+    assert!(!compose.contains("types.proto"), "{compose}");
+    assert!(!compose.contains("api/config.yaml"), "{compose}");
+
+    let report = fs::read_to_string(dir.join(".dx/analyzer-report.md")).unwrap();
+    assert!(
+        report.contains("mock de api/orders.yaml: http://localhost:4010"),
+        "{report}"
+    );
+    assert!(
+        report.contains("gRPC: localhost:4770, stubs: http://localhost:4771/add"),
+        "{report}"
+    );
+}
+
+#[test]
+fn each_openapi_spec_gets_its_own_mock_and_port() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(dir.join("orders.yaml"), OPENAPI).unwrap();
+    fs::write(
+        dir.join("payments.json"),
+        r#"{"swagger": "2.0", "info": {"title": "Payments", "version": "1"}, "paths": {}}"#,
+    )
+    .unwrap();
+
+    let compose = dev_services(dir);
+    assert!(
+        compose.contains("  api-mock-orders:\n    image: stoplight/prism:5\n    command: mock -h 0.0.0.0 -p 4010 /specs/orders.yaml\n"),
+        "{compose}"
+    );
+    assert!(
+        compose.contains("  api-mock-payments:\n    image: stoplight/prism:5\n    command: mock -h 0.0.0.0 -p 4011 /specs/payments.json\n"),
+        "{compose}"
+    );
+}
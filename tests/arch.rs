@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Output;
+
+mod common;
+
+/// Fake `docker`: records its arguments.
+const DOCKER: &str = "#!/bin/sh
+echo \"$*\" >> \"$DOCKER_LOG\"
+exit 0
+";
+
+fn setup() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(&project).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(
+        project.join(".env.example"),
+        "DATABASE_URL=postgres://localhost/app\nMYSQL_URL=mysql://localhost/app\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("dx.toml"),
+        "[images]\npostgres = \"postgis/postgis:16-3.4\"\nmysql = \"mysql:5.7\"\n\n[dev_services]\nsmoke_check = \"off\"\n",
+    )
+    .unwrap();
+    fs::write(bin.join("docker"), DOCKER).unwrap();
+    fs::set_permissions(bin.join("docker"), fs::Permissions::from_mode(0o755)).unwrap();
+    tmp
+}
+
+fn dx(tmp: &Path, arch: &str, args: &[&str]) -> Output {
+    common::with_fake_tools(tmp)
+        .env("DX_ARCH", arch)
+        .args(args)
+        .output()
+        .expect("failed to run dx")
+}
+
+fn manifest(tmp: &Path, arch: &str) -> String {
+    let output = dx(tmp, arch, &["--no-cache", "dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    fs::read_to_string(tmp.join("project/.dx/docker-compose.yml")).unwrap()
+}
+
+#[test]
+fn amd64_only_images_are_swapped_or_emulated_on_arm64() {
+    let tmp = setup();
+    let project = tmp.path().join("project");
+    let arch_file = project.join(".dx/docker-compose.arch.yml");
+
+    // The manifest keeps the configured images on every machine
+    let yaml = manifest(tmp.path(), "arm64");
+    assert_eq!(yaml, manifest(tmp.path(), "amd64"));
+    assert!(
+        yaml.contains("    image: postgis/postgis:16-3.4\n"),
+        "{yaml}"
+    );
+    assert!(!yaml.contains("platform:"), "{yaml}");
+    let report = fs::read_to_string(project.join(".dx/analyzer-report.md")).unwrap();
+    assert!(!report.contains("### Arquitetura"), "{report}");
+
+    // run fits them to the machine, beside the manifest
+    let output = dx(tmp.path(), "arm64", &["dev-services", "run"]);
+    assert!(output.status.success(), "{output:?}");
+    let fitted = fs::read_to_string(&arch_file).unwrap();
+    assert!(
+        fitted.contains("  postgres:\n    image: imresamu/postgis:16-3.4\n"),
+        "{fitted}"
+    );
+    assert!(
+        fitted.contains("  mysql:\n    platform: linux/amd64\n"),
+        "{fitted}"
+    );
+    let log = fs::read_to_string(tmp.path().join("docker.log")).unwrap();
+    let up = log.lines().find(|l| l.contains(" up -d")).unwrap();
+    assert!(
+        up.contains("/.dx/docker-compose.yml -f ") && up.contains("/.dx/docker-compose.arch.yml"),
+        "{log}"
+    );
+
+    // Native builds on amd64: nothing to fit
+    let output = dx(tmp.path(), "amd64", &["dev-services", "run"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!arch_file.exists());
+
+    let output = dx(tmp.path(), "arm64", &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    let report = fs::read_to_string(project.join(".dx/analyzer-report.md")).unwrap();
+    assert!(
+        report.contains("### Arquitetura (arm64)\n") && report.contains("- mysql: `mysql:5.7`\n"),
+        "{report}"
+    );
+
+    // Doctor warns about the emulated image only
+    let output = dx(tmp.path(), "arm64", &["--json", "--offline", "doctor"]);
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let images: Vec<_> = envelope["data"]["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|c| c["kind"] == "image")
+        .map(|c| (c["name"].as_str().unwrap(), c["status"].as_str().unwrap()))
+        .collect();
+    assert_eq!(images, [("Imagem mysql:5.7 (mysql)", "warning")]);
+}
+
+#[test]
+fn manifests_generated_on_arm64_verify_on_amd64() {
+    let tmp = setup();
+    let output = dx(tmp.path(), "arm64", &["dev-services", "run"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        tmp.path()
+            .join("project/.dx/docker-compose.arch.yml")
+            .is_file()
+    );
+
+    let output = dx(tmp.path(), "amd64", &["governance", "verify-manifests"]);
+    assert!(output.status.success(), "{output:?}");
+}
@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+mod common;
+
+const MANIFEST: &str = "services:\n  redis:\n    image: redis:7-alpine\n    ports:\n      - '65431:6379'\n  grafana:\n    image: grafana/grafana:latest\n    ports:\n      - '65432:3000'\n";
+
+/// Fake `docker` that records its arguments; `ps` reports one labeled container.
+const DOCKER: &str = "#!/bin/sh\necho \"$*\" >> \"$DOCKER_LOG\"\ncase \"$1\" in\n  ps) echo c0ffee ;;\nesac\nexit 0\n";
+
+fn setup() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(project.join(".dx")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(project.join(".dx/docker-compose.yml"), MANIFEST).unwrap();
+    fs::write(bin.join("docker"), DOCKER).unwrap();
+    fs::set_permissions(bin.join("docker"), fs::Permissions::from_mode(0o755)).unwrap();
+    tmp
+}
+
+fn log(tmp: &Path) -> String {
+    fs::read_to_string(tmp.join("docker.log")).unwrap_or_default()
+}
+
+#[test]
+fn auto_stop_labels_containers_and_stops_them_when_idle() {
+    let tmp = setup();
+    let project = tmp.path().join("project");
+
+    let output =
+        common::run_with_fake_tools(tmp.path(), &["dev-services", "run", "--auto-stop", "1s"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Auto-stop: os serviços param após 1s"),
+        "{stdout}"
+    );
+
+    let labels = fs::read_to_string(project.join(".dx/auto-stop/compose.yml")).unwrap();
+    assert!(
+        labels.contains("  redis:\n    labels:\n      dev.dx.auto-stop: \"1s\"\n"),
+        "{labels}"
+    );
+    assert!(labels.contains("  grafana:\n"), "{labels}");
+    assert!(
+        log(tmp.path()).contains("/.dx/auto-stop/compose.yml up -d"),
+        "{}",
+        log(tmp.path())
+    );
+
+    // Nothing connects to the published ports, so the watchdog stops the containers
+    let deadline = Instant::now() + Duration::from_secs(20);
+    while !log(tmp.path()).contains("stop c0ffee") {
+        assert!(
+            Instant::now() < deadline,
+            "watchdog didn't stop the containers: {}",
+            log(tmp.path())
+        );
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    assert!(
+        log(tmp.path())
+            .contains("ps -q --filter label=dev.dx.auto-stop --filter label=dev.dx.project=")
+    );
+}
+
+#[test]
+fn auto_stop_comes_from_dx_toml_and_can_be_turned_off() {
+    let tmp = setup();
+    let project = tmp.path().join("project");
+    fs::write(
+        project.join("dx.toml"),
+        "[dev_services]\nauto_stop = \"2h\"\n",
+    )
+    .unwrap();
+
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "run"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("param após 2h"), "{stdout}");
+    assert!(project.join(".dx/auto-stop/state.json").exists());
+
+    // `off` retires the watchdog and the labels
+    let output =
+        common::run_with_fake_tools(tmp.path(), &["dev-services", "run", "--auto-stop", "off"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!project.join(".dx/auto-stop/state.json").exists());
+    assert!(!project.join(".dx/auto-stop/compose.yml").exists());
+
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &["dev-services", "run", "--auto-stop", "2 horas"],
+    );
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+}
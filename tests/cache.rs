@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::{Duration, SystemTime};
+
+fn dx_cache(cache: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dx"))
+        .arg("cache")
+        .args(args)
+        .env("XDG_CACHE_HOME", cache)
+        // Auto-prune is disabled in CI mode
+        .env("DX_CI", "0")
+        .output()
+        .expect("failed to run dx cache")
+}
+
+fn age(path: &Path, days: u64) {
+    let file = fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60))
+        .unwrap();
+}
+
+#[test]
+fn cache_stats_and_clear_by_category() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dx = tmp.path().join("dx");
+    fs::create_dir_all(dx.join("registry")).unwrap();
+    fs::create_dir_all(dx.join("dashboards")).unwrap();
+    fs::write(dx.join("registry").join("serde.json"), vec![b'x'; 2048]).unwrap();
+    fs::write(dx.join("dashboards").join("grafana.json"), vec![b'x'; 1024]).unwrap();
+
+    let output = dx_cache(tmp.path(), &["stats"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("- registry: 1 entrada(s), 2.0 KB (TTL 1d)"), "{}", stdout);
+    assert!(stdout.contains("Total: 3.0 KB"), "{}", stdout);
+
+    let output = dx_cache(tmp.path(), &["clear", "--category", "registry"]);
+    assert!(output.status.success());
+    assert!(!dx.join("registry").exists());
+    assert!(dx.join("dashboards").join("grafana.json").exists());
+
+    let output = dx_cache(tmp.path(), &["stats", "--category", "nope"]);
+    assert!(!output.status.success());
+
+    let output = dx_cache(tmp.path(), &["clear"]);
+    assert!(output.status.success());
+    assert!(!dx.join("dashboards").exists());
+}
+
+#[test]
+fn cache_prune_removes_entries_older_than_ttl() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let registry = tmp.path().join("dx").join("registry");
+    fs::create_dir_all(&registry).unwrap();
+    fs::write(registry.join("old.json"), "{}").unwrap();
+    fs::write(registry.join("fresh.json"), "{}").unwrap();
+    age(&registry.join("old.json"), 3);
+    // A recent stamp skips the automatic daily pruning
+    fs::write(tmp.path().join("dx").join(".last-prune"), "").unwrap();
+
+    let output = dx_cache(tmp.path(), &["stats"]);
+    assert!(output.status.success());
+    assert!(registry.join("old.json").exists());
+
+    let output = dx_cache(tmp.path(), &["prune"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Entradas expiradas removidas: 1"), "{}", stdout);
+    assert!(!registry.join("old.json").exists());
+    assert!(registry.join("fresh.json").exists());
+}
+
+#[test]
+fn cache_is_pruned_automatically() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let registry = tmp.path().join("dx").join("registry");
+    fs::create_dir_all(&registry).unwrap();
+    fs::write(registry.join("old.json"), "{}").unwrap();
+    age(&registry.join("old.json"), 3);
+
+    // Any command triggers the daily pruning
+    let output = dx_cache(tmp.path(), &["stats"]);
+    assert!(output.status.success());
+    assert!(!registry.join("old.json").exists());
+    assert!(tmp.path().join("dx").join(".last-prune").exists());
+}
+
+#[test]
+fn latest_versions_are_reused_until_cleared() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    // Answers a single lookup
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0u8; 2048];
+        let n = stream.read(&mut buf).unwrap();
+        let body = r#"{"version":"9.0.0"}"#;
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        String::from_utf8_lossy(&buf[..n]).into_owned()
+    });
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("app");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("package.json"),
+        r#"{"devDependencies": {"eslint": "1.0.0"}}"#,
+    )
+    .unwrap();
+    fs::write(
+        project.join("dx.toml"),
+        format!("[registries]\nnpm = \"http://{addr}\"\n\n[network]\ntimeout = 2\n"),
+    )
+    .unwrap();
+    let analyzer = || {
+        let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+            .args(["analyzer", "--report-path", "report.md"])
+            .arg(&project)
+            .env("XDG_CACHE_HOME", tmp.path())
+            .env("DX_CONFIG", tmp.path().join("user-dx.toml"))
+            .env("NO_PROXY", "*")
+            .output()
+            .expect("failed to run dx analyzer");
+        assert!(output.status.success(), "{output:?}");
+        fs::read_to_string(project.join("report.md")).unwrap()
+    };
+
+    let row = "| [eslint](https://www.npmjs.com/package/eslint) | 1.0.0 | 9.0.0 |";
+    assert!(analyzer().contains(row));
+    assert!(server.join().unwrap().starts_with("GET /eslint/latest "));
+    let cached = tmp.path().join("dx/registry/latest-versions.json");
+    let entries = fs::read_to_string(&cached).unwrap();
+    assert!(
+        entries.contains(&format!("\"http://{addr}/eslint/latest\"")),
+        "{entries}"
+    );
+
+    // The registry is gone: the second run is answered by the cache
+    let report = analyzer();
+    assert!(report.contains(row), "{report}");
+
+    let output = dx_cache(tmp.path(), &["clear", "--category", "registry"]);
+    assert!(output.status.success());
+    assert!(!cached.exists());
+    assert!(analyzer().contains("| [eslint](https://www.npmjs.com/package/eslint) | 1.0.0 | - |"));
+}
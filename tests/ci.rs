@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::process::Output;
+
+mod common;
+
+fn dx(dir: &std::path::Path, env: &[(&str, &str)], args: &[&str]) -> Output {
+    let mut cmd = common::dx(dir);
+    for var in ["GITHUB_ACTIONS", "GITLAB_CI", "DX_CI"] {
+        cmd.env_remove(var);
+    }
+    cmd.envs(env.iter().copied())
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run dx")
+}
+
+fn rust_project() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    tmp
+}
+
+#[test]
+fn ci_flag_skips_gitignore_mutation() {
+    let tmp = rust_project();
+    let output = dx(tmp.path(), &[], &["--ci", "analyzer", "--write-gitignore"]);
+    assert!(output.status.success());
+    assert!(!tmp.path().join(".gitignore").exists());
+}
+
+#[test]
+fn ci_is_detected_from_provider_variables() {
+    let tmp = rust_project();
+    let output = dx(
+        tmp.path(),
+        &[("GITHUB_ACTIONS", "true")],
+        &["analyzer", "--write-gitignore"],
+    );
+    assert!(output.status.success());
+    assert!(!tmp.path().join(".gitignore").exists());
+
+    // DX_CI=0 opts out of the detection
+    let output = dx(
+        tmp.path(),
+        &[("CI", "true"), ("DX_CI", "0")],
+        &["analyzer", "--write-gitignore"],
+    );
+    assert!(output.status.success());
+    assert!(tmp.path().join(".gitignore").exists());
+}
+
+#[test]
+fn ci_mode_never_prompts() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::create_dir_all(tmp.path().join("api").join(".dx")).unwrap();
+    let output = dx(tmp.path(), &[("CI", "1")], &["clean"]);
+    assert_eq!(output.status.code(), Some(8));
+    assert!(tmp.path().join("api").join(".dx").is_dir());
+}
@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+
+mod common;
+
+fn workflow(dir: &Path) -> String {
+    fs::read_to_string(dir.join(".github/workflows/dx.yml")).expect("workflow")
+}
+
+#[test]
+fn github_workflow_follows_the_rust_toolchain() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(tmp.path().join("Cargo.lock"), "").unwrap();
+    fs::write(
+        tmp.path().join("rust-toolchain.toml"),
+        "[toolchain]\nchannel = \"1.85\"\n",
+    )
+    .unwrap();
+
+    let output = common::run_in(tmp.path(), &["ci", "generate", "--provider", "github"]);
+    assert!(output.status.success(), "{output:?}");
+    let yml = workflow(tmp.path());
+    assert!(yml.starts_with("# dx-cli:ci:start"), "{yml}");
+    assert!(yml.contains("toolchain: '1.85'"), "{yml}");
+    assert!(
+        yml.contains("hashFiles('Cargo.lock', 'Cargo.toml')"),
+        "{yml}"
+    );
+    assert!(
+        yml.contains("cargo clippy --all-targets -- -D warnings"),
+        "{yml}"
+    );
+    assert!(yml.contains("cargo test --locked"), "{yml}");
+    assert!(yml.contains("./dx --ci analyzer --no-save"), "{yml}");
+    assert!(yml.contains("cargo audit"), "{yml}");
+    assert!(!yml.contains("working-directory"), "{yml}");
+}
+
+#[test]
+fn github_workflow_follows_the_tool_versions_file() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("requirements.txt"), "pytest\n").unwrap();
+    fs::write(
+        tmp.path().join(".tool-versions"),
+        "nodejs 20.11.0\npython 3.11.9\n",
+    )
+    .unwrap();
+
+    let output = common::run_in(tmp.path(), &["ci", "generate", "--provider", "github"]);
+    assert!(output.status.success(), "{output:?}");
+    let yml = workflow(tmp.path());
+    assert!(yml.contains("python-version: 3.11.9"), "{yml}");
+}
+
+#[test]
+fn regeneration_keeps_content_outside_the_markers() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("go.mod"), "module demo\n\ngo 1.22\n").unwrap();
+    assert!(
+        common::run_in(tmp.path(), &["ci", "generate"])
+            .status
+            .success()
+    );
+
+    let path = tmp.path().join(".github/workflows/dx.yml");
+    let edited = format!(
+        "# mantido pelo time\n{}# fim\n",
+        workflow(tmp.path()).replace("go vet ./...", "go vet ./cmd/...")
+    );
+    fs::write(&path, edited).unwrap();
+
+    let output = common::run_in(tmp.path(), &["--json", "ci", "generate"]);
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("JSON envelope");
+    assert_eq!(json["data"]["stack"], "go");
+    assert_eq!(json["data"]["written"], true);
+    let yml = workflow(tmp.path());
+    assert!(
+        yml.starts_with("# mantido pelo time\n# dx-cli:ci:start"),
+        "{yml}"
+    );
+    assert!(yml.ends_with("# dx-cli:ci:end\n# fim\n"), "{yml}");
+    assert!(
+        yml.contains("go vet ./...") && !yml.contains("./cmd/"),
+        "{yml}"
+    );
+    assert!(yml.contains("go-version: '1.22'"), "{yml}");
+}
+
+#[test]
+fn foreign_workflow_needs_force_and_dry_run_writes_nothing() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("package.json"),
+        "{\n  \"scripts\": {\n    \"lint\": \"eslint .\",\n    \"test\": \"echo \\\"Error: no test specified\\\" && exit 1\"\n  }\n}\n",
+    )
+    .unwrap();
+    let path = tmp.path().join(".github/workflows/dx.yml");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "name: mine\n").unwrap();
+
+    let output = common::run_in(tmp.path(), &["ci", "generate"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+
+    let output = common::run_in(tmp.path(), &["ci", "generate", "--dry-run"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("run: npm run lint"), "{stdout}");
+    assert!(!stdout.contains("npm run test"), "{stdout}");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "name: mine\n");
+
+    assert!(
+        common::run_in(tmp.path(), &["ci", "generate", "--force"])
+            .status
+            .success()
+    );
+    assert!(workflow(tmp.path()).contains("npm audit --audit-level=high"));
+}
+
+#[test]
+fn nested_project_writes_to_the_repository_root() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::create_dir(tmp.path().join(".git")).unwrap();
+    let app = tmp.path().join("apps").join("api");
+    fs::create_dir_all(&app).unwrap();
+    fs::write(app.join("requirements.txt"), "flask\n").unwrap();
+
+    let output = common::run_in(&app, &["ci", "generate"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!app.join(".github").exists());
+    let yml = workflow(tmp.path());
+    assert!(yml.contains("working-directory: apps/api"), "{yml}");
+    assert!(
+        yml.contains("hashFiles('apps/api/requirements.txt')"),
+        "{yml}"
+    );
+    assert!(yml.contains("pip-audit -r requirements.txt"), "{yml}");
+}
+
+#[test]
+fn every_provider_runs_the_same_steps() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("package.json"),
+        "{\n  \"scripts\": {\n    \"build\": \"tsc\",\n    \"test\": \"vitest run\"\n  }\n}\n",
+    )
+    .unwrap();
+    fs::write(tmp.path().join("package-lock.json"), "{}\n").unwrap();
+    fs::write(tmp.path().join(".nvmrc"), "v20.11.0\n").unwrap();
+
+    let files = [
+        ("gitlab", ".gitlab-ci.yml", "image: node:20.11.0"),
+        ("circleci", ".circleci/config.yml", "image: node:20.11.0"),
+        ("azure", "azure-pipelines.yml", "container: node:20.11.0"),
+    ];
+    for (provider, file, image) in files {
+        let output = common::run_in(tmp.path(), &["ci", "generate", "--provider", provider]);
+        assert!(output.status.success(), "{provider}: {output:?}");
+        let yml = fs::read_to_string(tmp.path().join(file)).expect(file);
+        assert!(yml.contains("# dx-cli:ci:start"), "{yml}");
+        assert!(yml.contains(image), "{yml}");
+        for command in [
+            "npm ci",
+            "npm run build",
+            "npm run test",
+            "./dx --ci analyzer --no-save",
+            "npm audit --audit-level=high",
+        ] {
+            assert!(yml.contains(command), "{provider} sem `{command}`:\n{yml}");
+        }
+    }
+    let gitlab = fs::read_to_string(tmp.path().join(".gitlab-ci.yml")).unwrap();
+    assert!(
+        gitlab.contains("npm_config_cache: $CI_PROJECT_DIR/.npm"),
+        "{gitlab}"
+    );
+    assert!(gitlab.contains("  - analyze\n"), "{gitlab}");
+    assert!(!gitlab.contains("  - lint\n"), "{gitlab}");
+    let circleci = fs::read_to_string(tmp.path().join(".circleci/config.yml")).unwrap();
+    assert!(
+        circleci.contains("node-v1-{{ checksum \"package-lock.json\" }}"),
+        "{circleci}"
+    );
+}
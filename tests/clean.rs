@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::process::{Command, Stdio};
+
+fn workspace_with_dx_dirs() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    for project in ["api", "web"] {
+        let dx = tmp.path().join(project).join(".dx");
+        fs::create_dir_all(&dx).unwrap();
+        fs::write(dx.join("docker-compose.yml"), vec![b'x'; 2048]).unwrap();
+    }
+    tmp
+}
+
+#[test]
+fn clean_dry_run_lists_sizes_without_removing() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let tmp = workspace_with_dx_dirs();
+    let output = Command::new(exe)
+        .args(["clean", "--dry-run"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run dx clean --dry-run");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("(2.0 KB)"), "{}", stdout);
+    assert!(stdout.contains("Total: 2 pasta(s), 4.0 KB"), "{}", stdout);
+    assert!(tmp.path().join("api/.dx").is_dir());
+    assert!(tmp.path().join("web/.dx").is_dir());
+}
+
+#[test]
+fn clean_requires_confirmation_when_not_interactive() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let tmp = workspace_with_dx_dirs();
+    let output = Command::new(exe)
+        .arg("clean")
+        .arg(tmp.path())
+        .stdin(Stdio::null())
+        .output()
+        .expect("failed to run dx clean");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--yes"), "{}", stderr);
+    assert!(tmp.path().join("api/.dx").is_dir());
+}
+
+#[test]
+fn clean_yes_removes_and_reports_reclaimed_space() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let tmp = workspace_with_dx_dirs();
+    let output = Command::new(exe)
+        .args(["clean", "--yes"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run dx clean --yes");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Pasta(s) .dx removidas: 2 | espaço liberado: 4.0 KB"),
+        "{}",
+        stdout
+    );
+    assert!(!tmp.path().join("api/.dx").exists());
+    assert!(!tmp.path().join("web/.dx").exists());
+    assert!(tmp.path().join("api").is_dir());
+}
+
+#[test]
+fn clean_honors_depth_exclude_and_gitignore() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let tmp = workspace_with_dx_dirs();
+    let root = tmp.path();
+    fs::create_dir_all(root.join("apps/deep/.dx")).unwrap();
+    fs::create_dir_all(root.join("node_modules/pkg/.dx")).unwrap();
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::write(root.join(".gitignore"), "node_modules/\n").unwrap();
+
+    let dry_run = |extra: &[&str]| {
+        let output = Command::new(exe)
+            .args(["clean", "--dry-run"])
+            .args(extra)
+            .arg(root)
+            .output()
+            .expect("failed to run dx clean --dry-run");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let stdout = dry_run(&[]);
+    assert!(stdout.contains("Total: 4 pasta(s)"), "{}", stdout);
+
+    let stdout = dry_run(&["--max-depth", "1"]);
+    assert!(stdout.contains("Total: 2 pasta(s)"), "{}", stdout);
+    assert!(!stdout.contains("deep"), "{}", stdout);
+
+    let stdout = dry_run(&["--exclude", "api", "--exclude", "apps/**"]);
+    assert!(stdout.contains("Total: 2 pasta(s)"), "{}", stdout);
+
+    let stdout = dry_run(&["--respect-gitignore"]);
+    assert!(stdout.contains("Total: 3 pasta(s)"), "{}", stdout);
+    assert!(!stdout.contains("node_modules"), "{}", stdout);
+}
+
+#[test]
+fn clean_backup_moves_folders_into_archive() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let tmp = workspace_with_dx_dirs();
+    let backups = tempfile::tempdir().expect("tempdir");
+    let output = Command::new(exe)
+        .args(["clean", "--yes", "--backup", "--backup-dir"])
+        .arg(backups.path())
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run dx clean --backup");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Backup salvo em:"), "{}", stdout);
+    assert!(!tmp.path().join("api/.dx").exists());
+
+    let archives: Vec<_> = fs::read_dir(backups.path())
+        .unwrap()
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    assert_eq!(archives.len(), 1);
+    let archive = &archives[0];
+    assert!(archive.join("api/.dx/docker-compose.yml").is_file());
+    assert!(archive.join("web/.dx/docker-compose.yml").is_file());
+    let manifest = fs::read_to_string(archive.join("manifest.json")).unwrap();
+    assert!(manifest.contains("\"original\""), "{}", manifest);
+}
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+//! Runs the dx binary for the integration tests. Each test binary uses its
+//! own subset of these helpers.
+#![allow(dead_code)]
+
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+/// `dx` with its user config kept in `dir` instead of the real one, and
+/// outside of CI whatever runs the tests.
+pub fn dx(dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_dx"));
+    command
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .env_remove("CI")
+        .stdin(Stdio::null());
+    command
+}
+
+/// Run `dx <args> <dir>`.
+pub fn run(dir: &Path, args: &[&str]) -> Output {
+    dx(dir)
+        .args(args)
+        .arg(dir)
+        .output()
+        .expect("failed to run dx")
+}
+
+/// Run `dx <args>` from `dir`.
+pub fn run_in(dir: &Path, args: &[&str]) -> Output {
+    dx(dir)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run dx")
+}
+
+/// `dx` run from `tmp/project` with the fake tools of `tmp/bin` (docker,
+/// podman, ...) first on the PATH; they log their calls to `tmp/docker.log`.
+pub fn with_fake_tools(tmp: &Path) -> Command {
+    let mut paths = vec![tmp.join("bin")];
+    paths.extend(std::env::split_paths(
+        &std::env::var_os("PATH").unwrap_or_default(),
+    ));
+    let mut command = dx(tmp);
+    command
+        .env("XDG_CACHE_HOME", tmp.join("cache"))
+        .env("PATH", std::env::join_paths(paths).unwrap())
+        .env("DOCKER_LOG", tmp.join("docker.log"))
+        .env_remove("DOCKER_HOST")
+        .env_remove("DOCKER_CONTEXT")
+        .env_remove("DX_CONTAINERIZED")
+        .current_dir(tmp.join("project"));
+    command
+}
+
+/// Run `dx <args>` with the fake tools of `tmp/bin`.
+pub fn run_with_fake_tools(tmp: &Path, args: &[&str]) -> Output {
+    with_fake_tools(tmp)
+        .args(args)
+        .output()
+        .expect("failed to run dx")
+}
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::process::Command;
+
+fn project() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dx = tmp.path().join(".dx");
+    fs::create_dir_all(&dx).unwrap();
+    fs::write(
+        dx.join("docker-compose.yml"),
+        "version: '3.8'\nservices:\n  postgres:\n    image: postgres:16\n  redis:\n    image: redis:7\nvolumes:\n  pgdata:\n",
+    )
+    .unwrap();
+    fs::write(dx.join("config.json"), "{\"db_url\": \"x\", \"port\": \"8080\"}").unwrap();
+    tmp
+}
+
+fn complete(dir: &std::path::Path, words: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("COMPLETE", "fish")
+        .arg("--")
+        .args(words)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run dx completion");
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn completions_prints_registration_script() {
+    for shell in ["bash", "zsh", "fish", "powershell"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+            .args(["completions", shell])
+            .output()
+            .expect("failed to run dx completions");
+        assert!(output.status.success(), "{shell}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("COMPLETE"), "{shell}: {stdout}");
+    }
+}
+
+#[test]
+fn completes_service_names_from_manifest() {
+    let tmp = project();
+    let stdout = complete(tmp.path(), &["dx", "dev-services", "stop", "--service", ""]);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, ["postgres", "redis"], "{stdout}");
+
+    let stdout = complete(tmp.path(), &["dx", "dev-services", "run", "-s", "re"]);
+    assert_eq!(stdout.trim(), "redis");
+}
+
+#[test]
+fn completes_dev_config_keys() {
+    let tmp = project();
+    let stdout = complete(tmp.path(), &["dx", "dev-config", "delete", "p"]);
+    assert_eq!(stdout.trim(), "port");
+}
+
+#[test]
+fn manpages_writes_one_page_per_command() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let out = tmp.path().join("man");
+    let status = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .arg("manpages")
+        .arg(&out)
+        .status()
+        .expect("failed to run dx manpages");
+    assert!(status.success());
+    for page in ["dx.1", "dx-clean.1", "dx-dev-services-stop.1", "dx-cache-prune.1"] {
+        assert!(out.join(page).is_file(), "missing {page}");
+    }
+    let root = fs::read_to_string(out.join("dx.1")).unwrap();
+    assert!(root.contains(".TH dx"), "{root}");
+}
@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Output;
+
+mod common;
+
+const MANIFEST: &str = "services:
+  prometheus:
+    image: prom/prometheus:latest
+    ports:
+      - '9090:9090'
+    volumes:
+      - ./telemetry/prometheus/prometheus.yml:/etc/prometheus/prometheus.yml
+      - prom-data:/prometheus
+
+volumes:
+  prom-data:
+";
+
+/// Fake `docker`: records its arguments and reports the mounts of the
+/// container in `$MOUNTS` for `inspect`.
+const DOCKER: &str = "#!/bin/sh
+echo \"$*\" >> \"$DOCKER_LOG\"
+case \"$1\" in
+  inspect) [ -n \"$MOUNTS\" ] && echo \"$MOUNTS\" && exit 0; exit 1 ;;
+esac
+exit 0
+";
+
+fn setup() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(project.join(".dx")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(project.join(".dx/docker-compose.yml"), MANIFEST).unwrap();
+    fs::write(bin.join("docker"), DOCKER).unwrap();
+    fs::set_permissions(bin.join("docker"), fs::Permissions::from_mode(0o755)).unwrap();
+    tmp
+}
+
+fn dx(tmp: &Path, envs: &[(&str, &str)], args: &[&str]) -> Output {
+    common::with_fake_tools(tmp)
+        .env("DOCKER_HOST", "unix:///var/run/docker.sock")
+        .env("HOSTNAME", "c0ffee")
+        .env_remove("DX_HOST_PROJECT_DIR")
+        .envs(envs.iter().copied())
+        .args(args)
+        .output()
+        .expect("failed to run dx")
+}
+
+fn host_compose(tmp: &Path) -> String {
+    fs::read_to_string(tmp.join("project/.dx/docker-compose.containerized.yml")).unwrap()
+}
+
+#[test]
+fn containerized_run_translates_bind_mounts_to_the_host() {
+    let tmp = setup();
+    let output = dx(
+        tmp.path(),
+        &[("DX_HOST_PROJECT_DIR", "/home/dev/my app/")],
+        &["dev-services", "run", "--containerized-cli"],
+    );
+    assert!(output.status.success(), "{output:?}");
+
+    let compose = host_compose(tmp.path());
+    assert!(
+        compose.contains(
+            "    - /home/dev/my app/.dx/telemetry/prometheus/prometheus.yml:/etc/prometheus/prometheus.yml\n"
+        ),
+        "{compose}"
+    );
+    // Named volumes stay as they are
+    assert!(
+        compose.contains("    - prom-data:/prometheus\n"),
+        "{compose}"
+    );
+
+    let log = fs::read_to_string(tmp.path().join("docker.log")).unwrap();
+    assert!(
+        log.contains("/.dx/docker-compose.containerized.yml up -d"),
+        "{log}"
+    );
+}
+
+#[test]
+fn containerized_run_finds_the_host_path_in_its_own_mounts() {
+    let tmp = setup();
+    let project = tmp.path().join("project");
+    let mounts = format!(
+        r#"[{{"Source":"/var/run/docker.sock","Destination":"/var/run/docker.sock"}},{{"Source":"/Users/dev/src","Destination":"{}"}}]"#,
+        tmp.path().canonicalize().unwrap().display()
+    );
+    // The official image enables the mode through the environment
+    let output = dx(
+        tmp.path(),
+        &[("MOUNTS", &mounts), ("DX_CONTAINERIZED", "1")],
+        &["dev-services", "run"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        host_compose(tmp.path()).contains(
+            "    - /Users/dev/src/project/.dx/telemetry/prometheus/prometheus.yml:/etc/prometheus/prometheus.yml\n"
+        ),
+        "{}",
+        host_compose(tmp.path())
+    );
+    let log = fs::read_to_string(tmp.path().join("docker.log")).unwrap();
+    assert!(
+        log.starts_with("inspect --format {{json .Mounts}} c0ffee\n"),
+        "{log}"
+    );
+
+    // Not mounted from the host: the path can't be translated
+    let output = dx(
+        tmp.path(),
+        &[],
+        &["dev-services", "run", "--containerized-cli"],
+    );
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("DX_HOST_PROJECT_DIR"), "{stderr}");
+    assert!(project.join(".dx/docker-compose.yml").exists());
+}
+
+#[test]
+fn containerized_manifest_is_not_reported_as_drift() {
+    let tmp = setup();
+    let project = tmp.path().join("project");
+    // A manifest generated by dx instead of the hand-written one
+    fs::remove_dir_all(project.join(".dx")).unwrap();
+    fs::write(
+        project.join("package.json"),
+        r#"{"dependencies": {"pg": "^8.11"}}"#,
+    )
+    .unwrap();
+    let output = dx(tmp.path(), &[], &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    let output = dx(
+        tmp.path(),
+        &[("DX_HOST_PROJECT_DIR", "/home/dev/app")],
+        &["dev-services", "run", "--containerized-cli"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        project
+            .join(".dx/docker-compose.containerized.yml")
+            .is_file()
+    );
+
+    let output = dx(
+        tmp.path(),
+        &[],
+        &["--json", "governance", "verify-manifests"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        envelope["data"]["derived"],
+        serde_json::json!(["docker-compose.containerized.yml"])
+    );
+}
@@ -0,0 +1,257 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::mpsc::{Receiver, channel};
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+struct Daemon {
+    child: Child,
+    stdin: ChildStdin,
+    messages: Receiver<Value>,
+}
+
+impl Daemon {
+    fn start(dir: &Path) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_dx"))
+            .env("DX_CONFIG", dir.join("user-dx.toml"))
+            .arg("daemon")
+            .current_dir(dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to start dx daemon");
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (tx, messages) = channel();
+        std::thread::spawn(move || read_messages(stdout, |m| tx.send(m).is_ok()));
+        Daemon {
+            child,
+            stdin,
+            messages,
+        }
+    }
+
+    fn send(&mut self, message: Value) {
+        writeln!(self.stdin, "{message}").unwrap();
+    }
+
+    fn next(&self) -> Value {
+        self.messages
+            .recv_timeout(Duration::from_secs(20))
+            .expect("no message from the daemon")
+    }
+
+    fn request(&mut self, id: u64, method: &str, params: Value) -> Value {
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        let response = self.next();
+        assert_eq!(response["id"], id, "{response}");
+        response
+    }
+
+    fn stop(mut self) {
+        self.send(json!({ "jsonrpc": "2.0", "method": "exit" }));
+        let status = self.child.wait().unwrap();
+        assert!(status.success(), "{status:?}");
+    }
+}
+
+/// Messages on `stdout`, either one per line or framed with Content-Length.
+fn read_messages(stdout: ChildStdout, mut deliver: impl FnMut(Value) -> bool) {
+    let mut reader = BufReader::new(stdout);
+    let mut line = String::new();
+    while reader.read_line(&mut line).unwrap_or(0) > 0 {
+        let message = match line.trim().strip_prefix("Content-Length: ") {
+            Some(length) => {
+                let mut body = vec![0; length.parse().unwrap()];
+                let mut blank = String::new();
+                reader.read_line(&mut blank).unwrap();
+                reader.read_exact(&mut body).unwrap();
+                serde_json::from_slice(&body).unwrap()
+            }
+            None if line.trim().is_empty() => {
+                line.clear();
+                continue;
+            }
+            None => serde_json::from_str(&line).unwrap(),
+        };
+        line.clear();
+        if !deliver(message) {
+            break;
+        }
+    }
+}
+
+#[test]
+fn answers_state_and_runs_commands() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("requirements.txt"), "flask\npsycopg2\n").unwrap();
+    let mut daemon = Daemon::start(tmp.path());
+
+    let init = daemon.request(1, "initialize", json!({}));
+    assert_eq!(init["result"]["serverInfo"]["name"], "dx");
+    assert!(
+        init["result"]["methods"]
+            .as_array()
+            .unwrap()
+            .contains(&"command/run".into())
+    );
+
+    let state = daemon.request(
+        2,
+        "project/state",
+        json!({ "sections": ["stack", "dependencies"] }),
+    );
+    let state = &state["result"];
+    assert_eq!(state["stack"]["name"], "python", "{state}");
+    assert_eq!(state["dependencies"], json!(["postgres"]), "{state}");
+    assert!(state.get("env").is_none(), "{state}");
+
+    let error = daemon.request(3, "project/state", json!({ "sections": ["nope"] }));
+    assert_eq!(error["error"]["code"], -32602, "{error}");
+    let error = daemon.request(4, "project/nope", json!({}));
+    assert_eq!(error["error"]["code"], -32601, "{error}");
+
+    let run = daemon.request(
+        5,
+        "command/run",
+        json!({ "args": ["tasks", "generate", "--dry-run"] }),
+    );
+    assert_eq!(run["result"]["exit_code"], 0, "{run}");
+    assert_eq!(
+        run["result"]["envelope"]["data"]["stack"], "python",
+        "{run}"
+    );
+    assert!(!tmp.path().join("justfile").exists());
+
+    daemon.stop();
+}
+
+#[test]
+fn replies_with_lsp_framing() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let mut daemon = Daemon::start(tmp.path());
+    let body = json!({ "jsonrpc": "2.0", "id": 7, "method": "shutdown" }).to_string();
+    write!(
+        daemon.stdin,
+        "Content-Length: {}\r\nContent-Type: application/vscode-jsonrpc\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+    let response = daemon.next();
+    assert_eq!(response["id"], 7, "{response}");
+    assert!(response["result"].is_null(), "{response}");
+    assert!(daemon.child.wait().unwrap().success());
+}
+
+#[test]
+fn subscribers_are_notified_and_sections_refreshed() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::create_dir(tmp.path().join(".dx")).unwrap();
+    let mut daemon = Daemon::start(tmp.path());
+
+    let state = daemon.request(1, "project/state", json!({ "sections": ["env"] }));
+    assert_eq!(state["result"]["env"], json!({}), "{state}");
+    let subscribed = daemon.request(2, "project/subscribe", Value::Null);
+    assert_eq!(subscribed["result"], true);
+
+    fs::write(
+        tmp.path().join(".dx/config.json"),
+        "{\"api.token\": \"abc\"}\n",
+    )
+    .unwrap();
+    let notification = daemon.next();
+    assert_eq!(notification["method"], "project/changed", "{notification}");
+    let params = &notification["params"];
+    assert!(
+        params["paths"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|p| p.as_str().unwrap().ends_with("config.json")),
+        "{notification}"
+    );
+    assert!(
+        params["sections"]
+            .as_array()
+            .unwrap()
+            .contains(&"env".into()),
+        "{notification}"
+    );
+
+    // Drain further notifications for the same change before asking again
+    while daemon
+        .messages
+        .recv_timeout(Duration::from_millis(500))
+        .is_ok()
+    {}
+    let state = daemon.request(3, "project/state", json!({ "sections": ["env"] }));
+    assert_eq!(state["result"]["env"]["API_TOKEN"], "abc", "{state}");
+
+    daemon.stop();
+}
+
+#[test]
+fn generates_data_from_a_schema() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("schema.sql"),
+        "CREATE TABLE users (id SERIAL PRIMARY KEY, email VARCHAR(80));\n",
+    )
+    .unwrap();
+    let mut daemon = Daemon::start(tmp.path());
+
+    let data = daemon.request(
+        1,
+        "data/generate",
+        json!({ "schema": "schema.sql", "count": 2, "locale": "en-US" }),
+    );
+    let result = &data["result"];
+    assert_eq!(result["seed"], 42, "{data}");
+    assert_eq!(result["locale"], "en-US", "{data}");
+    let users = result["tables"]["users"].as_array().unwrap();
+    assert_eq!(users.len(), 2, "{data}");
+    assert_eq!(users[1]["id"], 2, "{data}");
+    assert!(users[0]["email"].as_str().unwrap().contains("@example."));
+
+    let error = daemon.request(2, "data/generate", json!({ "schema": "nope.sql" }));
+    assert_eq!(error["error"]["code"], -32602, "{error}");
+
+    daemon.stop();
+}
+
+#[test]
+fn lists_the_latest_operations() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::create_dir(tmp.path().join(".dx")).unwrap();
+    let entry = |command: &str| {
+        json!({
+            "started_at": 1_700_000_000u64,
+            "command": command,
+            "args": [command],
+            "duration_ms": 10,
+            "exit_code": 0,
+            "ok": true,
+            "version": "1.0.0",
+        })
+        .to_string()
+    };
+    fs::write(
+        tmp.path().join(".dx/history.jsonl"),
+        format!("{}\n{}\n", entry("analyzer"), entry("doctor")),
+    )
+    .unwrap();
+    let mut daemon = Daemon::start(tmp.path());
+
+    let history = daemon.request(1, "history/list", json!({ "limit": 1 }));
+    let entries = history["result"]["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1, "{history}");
+    assert_eq!(entries[0]["command"], "doctor", "{history}");
+
+    daemon.stop();
+}
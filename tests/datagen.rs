@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::process::Output;
+
+mod common;
+
+const DDL: &str = "-- accounts
+CREATE TABLE IF NOT EXISTS public.users (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR(120) NOT NULL,
+  email character varying(255) UNIQUE NOT NULL,
+  uf CHAR(2),
+  status text NOT NULL CHECK (status IN ('Active', 'blocked')),
+  created_at timestamptz DEFAULT now()
+);
+CREATE TABLE orders (
+  id bigint GENERATED ALWAYS AS IDENTITY,
+  user_id integer REFERENCES users(id),
+  kind ENUM('Pickup','Delivery'),
+  code VARCHAR(4),
+  PRIMARY KEY (id)
+);
+";
+
+const JSON_SCHEMA: &str = r#"{
+  "title": "user",
+  "type": "object",
+  "properties": {
+    "id": { "type": "integer" },
+    "firstName": { "type": "string" },
+    "email": { "type": "string", "format": "email" },
+    "address": {
+      "type": "object",
+      "properties": { "street": { "type": "string" }, "city": { "type": "string" } }
+    },
+    "role": { "enum": ["admin", "member"] },
+    "score": { "type": "number", "minimum": 0, "maximum": 5 }
+  }
+}"#;
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn sql_fixtures_follow_the_ddl() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("schema.sql"), DDL).unwrap();
+
+    let output = common::run_in(tmp.path(), &["tests", "fixtures", "schema.sql", "-n", "5"]);
+    assert!(output.status.success(), "{output:?}");
+    let sql = stdout(&output);
+    assert!(
+        sql.starts_with(
+            "INSERT INTO public.users (id, name, email, uf, status, created_at) VALUES\n  (1, '"
+        ),
+        "{sql}"
+    );
+    assert!(
+        sql.contains("INSERT INTO orders (id, user_id, kind, code) VALUES\n  (1, "),
+        "{sql}"
+    );
+    assert_eq!(sql.matches("),\n").count(), 8, "{sql}");
+    assert_eq!(sql.matches(");\n").count(), 2, "{sql}");
+
+    let orders = sql.split("INSERT INTO orders").nth(1).unwrap();
+    for (row, line) in orders.lines().skip(1).filter(|l| !l.is_empty()).enumerate() {
+        let values: Vec<&str> = line
+            .trim()
+            .trim_start_matches('(')
+            .trim_end_matches([';', ',', ')'])
+            .split(", ")
+            .collect();
+        assert_eq!(values[0], (row + 1).to_string(), "{line}");
+        let user: usize = values[1].parse().unwrap();
+        assert!((1..=5).contains(&user), "{line}");
+        assert!(["'Pickup'", "'Delivery'"].contains(&values[2]), "{line}");
+        assert!(values[3].trim_matches('\'').chars().count() <= 4, "{line}");
+    }
+    let users = sql.split("INSERT INTO orders").next().unwrap();
+    for line in users.lines().skip(1).filter(|l| !l.is_empty()) {
+        assert!(
+            line.contains("'Active'") || line.contains("'blocked'"),
+            "{line}"
+        );
+        assert!(line.contains("@example."), "{line}");
+    }
+}
+
+#[test]
+fn fixtures_are_deterministic_per_seed() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("schema.sql"), DDL).unwrap();
+
+    let first = common::run_in(tmp.path(), &["tests", "fixtures", "schema.sql"]);
+    let again = common::run_in(
+        tmp.path(),
+        &["tests", "fixtures", "schema.sql", "--seed", "42"],
+    );
+    let other = common::run_in(
+        tmp.path(),
+        &["tests", "fixtures", "schema.sql", "--seed", "7"],
+    );
+    assert!(first.status.success(), "{first:?}");
+    assert_eq!(stdout(&first), stdout(&again));
+    assert_ne!(stdout(&first), stdout(&other));
+}
+
+#[test]
+fn json_schema_fixtures_are_written_to_a_file() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("user.json"), JSON_SCHEMA).unwrap();
+
+    let output = common::run_in(
+        tmp.path(),
+        &[
+            "tests",
+            "fixtures",
+            "user.json",
+            "-n",
+            "3",
+            "--locale",
+            "en-US",
+            "-o",
+            "fixtures/users.json",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        stdout(&output).contains("3 registro(s) por tabela (user) gravados em"),
+        "{output:?}"
+    );
+
+    let content = fs::read_to_string(tmp.path().join("fixtures/users.json")).unwrap();
+    let first = content.find("\"firstName\"").unwrap();
+    assert!(first < content.find("\"email\"").unwrap(), "{content}");
+    assert!(content.find("\"email\"").unwrap() < content.find("\"address\"").unwrap());
+
+    let users: serde_json::Value = serde_json::from_str(&content).unwrap();
+    let users = users.as_array().expect("one table is an array");
+    assert_eq!(users.len(), 3);
+    for (row, user) in users.iter().enumerate() {
+        assert_eq!(user["id"], row as u64 + 1, "{user}");
+        let name = user["firstName"].as_str().unwrap().to_lowercase();
+        assert!(user["email"].as_str().unwrap().starts_with(&name), "{user}");
+        assert!(user["address"]["city"].is_string(), "{user}");
+        assert!(["admin", "member"].contains(&user["role"].as_str().unwrap()));
+        let score = user["score"].as_f64().unwrap();
+        assert!((0.0..=5.0).contains(&score), "{user}");
+    }
+
+    // Same data as SQL
+    let output = common::run_in(
+        tmp.path(),
+        &[
+            "tests",
+            "fixtures",
+            "user.json",
+            "--format",
+            "sql",
+            "-n",
+            "1",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        stdout(&output)
+            .starts_with("INSERT INTO user (id, firstName, email, address, role, score) VALUES"),
+        "{output:?}"
+    );
+}
+
+#[test]
+fn invalid_fixture_requests_are_rejected() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("schema.sql"), DDL).unwrap();
+    fs::write(tmp.path().join("schema.yaml"), "type: object\n").unwrap();
+
+    let output = common::run_in(
+        tmp.path(),
+        &["tests", "fixtures", "schema.sql", "--locale", "fr-FR"],
+    );
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Locale não suportado: 'fr-FR'"));
+
+    let output = common::run_in(tmp.path(), &["tests", "fixtures", "schema.sql", "-n", "0"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+
+    let output = common::run_in(tmp.path(), &["tests", "fixtures", "schema.yaml"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+
+    let output = common::run_in(tmp.path(), &["tests", "fixtures", "missing.json"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+}
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+mod common;
+
+use serde_json::Value;
+
+fn dx(dir: &Path, args: &[&str]) -> Output {
+    common::dx(dir)
+        .env("DX_CI", "0")
+        .args(args)
+        .arg(dir)
+        .output()
+        .expect("failed to run dx")
+}
+
+fn manifest(dir: &Path, flags: &[&str]) -> String {
+    let mut args = flags.to_vec();
+    args.extend(["dev-services", "--no-save"]);
+    let output = dx(dir, &args);
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn cache_file(dir: &Path) -> std::path::PathBuf {
+    dir.join(".dx/cache/detection.json")
+}
+
+#[test]
+fn unchanged_project_reuses_the_cached_detection() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("requirements.txt"), "redis\n").unwrap();
+
+    assert!(manifest(tmp.path(), &[]).contains("image: redis"));
+    let content = fs::read_to_string(cache_file(tmp.path())).expect("cache written");
+    let mut cache: Value = serde_json::from_str(&content).unwrap();
+    assert!(cache["dev-services"]["fingerprint"].is_string());
+
+    // A stored result is trusted while the files are unchanged
+    cache["dev-services"]["value"]["services"] = serde_json::json!({});
+    fs::write(cache_file(tmp.path()), cache.to_string()).unwrap();
+    assert!(!manifest(tmp.path(), &[]).contains("image: redis"));
+
+    // --no-cache rescans, and so does any change to the project
+    assert!(manifest(tmp.path(), &["--no-cache"]).contains("image: redis"));
+    fs::write(tmp.path().join("requirements.txt"), "redis\npsycopg2\n").unwrap();
+    let stdout = manifest(tmp.path(), &[]);
+    assert!(stdout.contains("image: redis"), "{stdout}");
+    assert!(stdout.contains("image: postgres"), "{stdout}");
+}
+
+#[test]
+fn no_cache_and_ci_mode_leave_no_cache_file() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("requirements.txt"), "redis\n").unwrap();
+
+    manifest(tmp.path(), &["--no-cache"]);
+    assert!(!cache_file(tmp.path()).exists());
+    manifest(tmp.path(), &["--ci"]);
+    assert!(!cache_file(tmp.path()).exists());
+}
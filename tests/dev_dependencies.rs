@@ -1,6 +1,7 @@
 use std::fs;
-use std::process::Command;
-use tempfile;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::process::{Command, Output};
 
 #[test]
 fn dev_dependencies_list_node() {
@@ -137,3 +138,1237 @@ fn dev_dependencies_list_flink() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("flink-test-utils"));
 }
+
+fn list_in(dir: &std::path::Path) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .args(["dev-dependencies", "list"])
+        .current_dir(dir)
+        .output()
+        .expect("run list");
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn dev_dependencies_list_kotlin_multiplatform() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("build.gradle.kts"),
+        r#"plugins {
+    kotlin("multiplatform") version "2.0.21"
+}
+
+kotlin {
+    jvm()
+    sourceSets {
+        commonMain.dependencies {
+            implementation("io.ktor:ktor-client-core:3.0.0")
+        }
+        commonTest.dependencies {
+            implementation(kotlin("test"))
+        }
+        val jvmTest by getting {
+            dependencies {
+                implementation("io.mockk:mockk:1.13.12")
+            }
+        }
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    let stdout = list_in(tmp.path());
+    assert!(stdout.contains("org.jetbrains.kotlin:kotlin-test = 2.0.21"), "{stdout}");
+    assert!(stdout.contains("io.mockk:mockk = 1.13.12"), "{stdout}");
+    assert!(!stdout.contains("ktor-client-core"), "{stdout}");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .args(["dev-config", "list"])
+        .current_dir(tmp.path())
+        .output()
+        .expect("run dev-config");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Stack detectada: Kotlin Multiplatform"), "{stdout}");
+}
+
+#[test]
+fn dev_dependencies_list_scala() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("build.sbt"),
+        r#"ThisBuild / scalaVersion := "2.13.14"
+
+val scalatestVersion = "3.2.19"
+
+libraryDependencies ++= Seq(
+  "org.typelevel" %% "cats-core" % "2.12.0",
+  "org.scalatest" %% "scalatest" % scalatestVersion % Test,
+  "org.mockito" % "mockito-core" % "5.12.0" % "test"
+)
+"#,
+    )
+    .unwrap();
+
+    let stdout = list_in(tmp.path());
+    assert!(stdout.contains("org.scalatest::scalatest = 3.2.19"), "{stdout}");
+    assert!(stdout.contains("org.mockito:mockito-core = 5.12.0"), "{stdout}");
+    assert!(!stdout.contains("cats-core"), "{stdout}");
+}
+
+#[test]
+fn dev_dependencies_list_swift() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("Package.swift"),
+        r#"// swift-tools-version:5.9
+import PackageDescription
+
+let package = Package(
+    name: "App",
+    dependencies: [
+        .package(url: "https://github.com/apple/swift-argument-parser.git", from: "1.3.0"),
+        .package(
+            url: "https://github.com/pointfreeco/swift-snapshot-testing",
+            "1.15.0"..<"2.0.0"
+        ),
+        .package(path: "../Shared"),
+    ],
+    targets: [.executableTarget(name: "App")]
+)
+"#,
+    )
+    .unwrap();
+
+    let stdout = list_in(tmp.path());
+    assert!(stdout.contains("- swift-argument-parser = 1.3.0"), "{stdout}");
+    assert!(stdout.contains("- swift-snapshot-testing = 1.15.0..<2.0.0"), "{stdout}");
+    assert!(!stdout.contains("Shared"), "{stdout}");
+}
+
+#[test]
+fn dev_dependencies_list_cpp() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("CMakeLists.txt"),
+        "cmake_minimum_required(VERSION 3.20)\nproject(app CXX)\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("conanfile.txt"),
+        "[requires]\nfmt/10.2.1\n\n[test_requires]\ngtest/1.14.0@user/stable\n\n[generators]\nCMakeDeps\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("vcpkg.json"),
+        r#"{
+  "dependencies": ["spdlog", { "name": "catch2", "version>=": "3.5.0" }],
+  "overrides": [{ "name": "spdlog", "version": "1.13.0" }]
+}"#,
+    )
+    .unwrap();
+
+    let stdout = list_in(tmp.path());
+    for line in [
+        "- fmt = 10.2.1",
+        "- gtest = 1.14.0",
+        "- spdlog = 1.13.0",
+        "- catch2 = >=3.5.0",
+    ] {
+        assert!(stdout.contains(line), "{line}: {stdout}");
+    }
+    assert!(!stdout.contains("CMakeDeps"), "{stdout}");
+}
+
+const GO_MOD: &str = "module example.com/app
+
+go 1.22
+
+require (
+\tgithub.com/stretchr/testify v1.8.0
+)
+
+require (
+\tgithub.com/davecgh/go-spew v1.1.1 // indirect
+\tgolang.org/x/text v0.3.0 // indirect
+)
+";
+
+fn dx_deps(dir: &std::path::Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .env("NO_PROXY", "*")
+        .args(["dev-dependencies"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("run dx")
+}
+
+#[test]
+fn dev_dependencies_add_update_delete_go() {
+    // Module proxy: every module's latest version is v1.9.0
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut buf = [0u8; 2048];
+            let _ = stream.read(&mut buf).unwrap();
+            let body = r#"{"Version":"v1.9.0"}"#;
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let go_mod = dir.join("go.mod");
+    fs::write(&go_mod, GO_MOD).unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\ngo = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+
+    // Into the direct block, in order; the version without `v` is a tag
+    let output = dx_deps(dir, &["add", "github.com/google/uuid", "1.6.0"]);
+    assert!(output.status.success(), "{output:?}");
+    // An indirect requirement becomes a direct one
+    let output = dx_deps(dir, &["add", "golang.org/x/text", "v0.14.0"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&go_mod).unwrap();
+    assert!(
+        content.contains(
+            "require (\n\tgithub.com/google/uuid v1.6.0\n\tgithub.com/stretchr/testify v1.8.0\n)"
+        ),
+        "{content}"
+    );
+    assert!(
+        content.contains("\tgolang.org/x/text v0.14.0\n"),
+        "{content}"
+    );
+
+    let output = dx_deps(dir, &["update", "github.com/stretchr/testify"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&go_mod).unwrap();
+    assert!(
+        content.contains("\tgithub.com/stretchr/testify v1.9.0\n"),
+        "{content}"
+    );
+    assert!(
+        content.contains("\tgithub.com/google/uuid v1.6.0\n"),
+        "{content}"
+    );
+
+    // Updating everything leaves the indirect requirements to `go mod tidy`
+    let output = dx_deps(dir, &["update"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&go_mod).unwrap();
+    assert!(
+        content.contains("\tgithub.com/google/uuid v1.9.0\n"),
+        "{content}"
+    );
+    assert!(
+        content.contains("\tgithub.com/davecgh/go-spew v1.1.1 // indirect\n"),
+        "{content}"
+    );
+
+    // The last entry of a block takes the block with it
+    for name in ["golang.org/x/text", "github.com/davecgh/go-spew"] {
+        let output = dx_deps(dir, &["delete", name]);
+        assert!(output.status.success(), "{output:?}");
+    }
+    let content = fs::read_to_string(&go_mod).unwrap();
+    assert_eq!(
+        content,
+        "module example.com/app\n\ngo 1.22\n\nrequire (\n\tgithub.com/google/uuid v1.9.0\n\tgithub.com/stretchr/testify v1.9.0\n)\n"
+    );
+
+    let output = dx_deps(dir, &["delete", "github.com/x/y"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+}
+
+#[test]
+fn tidy_only_applies_to_go_projects() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("package.json"),
+        "{\n  \"devDependencies\": {}\n}\n",
+    )
+    .unwrap();
+    let output = dx_deps(tmp.path(), &["add", "eslint", "1.0.0", "--tidy"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("--tidy só se aplica a projetos Go"),
+        "{output:?}"
+    );
+}
+
+const POM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+    <modelVersion>4.0.0</modelVersion>
+    <groupId>com.example</groupId>
+    <artifactId>app</artifactId>
+
+    <properties>
+        <junit.version>5.9.0</junit.version>
+    </properties>
+
+    <dependencies>
+        <!-- Web -->
+        <dependency>
+            <groupId>org.springframework.boot</groupId>
+            <artifactId>spring-boot-starter-web</artifactId>
+        </dependency>
+        <dependency>
+            <groupId>org.junit.jupiter</groupId>
+            <artifactId>junit-jupiter</artifactId>
+            <version>${junit.version}</version>
+            <scope>test</scope>
+        </dependency>
+    </dependencies>
+</project>
+"#;
+
+/// Maven Central mirror: every artifact's latest version is 5.11.0.
+fn maven_repository() -> std::net::SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut buf = [0u8; 2048];
+            let _ = stream.read(&mut buf).unwrap();
+            let body = "<metadata><versioning><latest>5.11.0</latest></versioning></metadata>";
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    addr
+}
+
+#[test]
+fn dev_dependencies_add_update_delete_maven() {
+    let addr = maven_repository();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let pom = dir.join("pom.xml");
+    fs::write(&pom, POM).unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\nmaven = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+
+    let output = dx_deps(dir, &["add", "org.assertj:assertj-core", "3.25.0"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&pom).unwrap();
+    assert!(
+        content.contains(
+            "            <scope>test</scope>
+        </dependency>
+        <dependency>
+            <groupId>org.assertj</groupId>
+            <artifactId>assertj-core</artifactId>
+            <version>3.25.0</version>
+            <scope>test</scope>
+        </dependency>
+    </dependencies>
+"
+        ),
+        "{content}"
+    );
+
+    // A version from <properties> is bumped where it is defined
+    let output = dx_deps(dir, &["update", "org.junit.jupiter:junit-jupiter"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&pom).unwrap();
+    assert!(
+        content.contains("<junit.version>5.11.0</junit.version>"),
+        "{content}"
+    );
+    assert!(content.contains("<version>3.25.0</version>"), "{content}");
+
+    let output = dx_deps(dir, &["update"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&pom).unwrap();
+    assert!(content.contains("<version>5.11.0</version>"), "{content}");
+    // Without <version>, the parent or a BOM manages it
+    assert!(
+        content.contains("<artifactId>spring-boot-starter-web</artifactId>\n        </dependency>"),
+        "{content}"
+    );
+
+    let output = dx_deps(dir, &["delete", "org.assertj:assertj-core"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        fs::read_to_string(&pom).unwrap(),
+        POM.replace("5.9.0", "5.11.0")
+    );
+
+    let output = dx_deps(dir, &["delete", "org.assertj:assertj-core"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+    let output = dx_deps(dir, &["add", "assertj-core"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+}
+
+#[test]
+fn dev_dependencies_add_maven_creates_the_dependencies_block() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let pom = tmp.path().join("pom.xml");
+    fs::write(
+        &pom,
+        "<project>\n  <modelVersion>4.0.0</modelVersion>\n</project>\n",
+    )
+    .unwrap();
+
+    let output = dx_deps(tmp.path(), &["add", "junit:junit", "4.13.2"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        fs::read_to_string(&pom).unwrap(),
+        "<project>
+  <modelVersion>4.0.0</modelVersion>
+
+  <dependencies>
+    <dependency>
+      <groupId>junit</groupId>
+      <artifactId>junit</artifactId>
+      <version>4.13.2</version>
+      <scope>test</scope>
+    </dependency>
+  </dependencies>
+</project>
+"
+    );
+}
+
+const BUILD_GRADLE: &str = "plugins {
+    id 'java'
+}
+
+dependencies {
+    implementation 'org.springframework.boot:spring-boot-starter-web'
+
+    testImplementation 'org.springframework.boot:spring-boot-starter-test'
+    testImplementation \"org.mockito:mockito-core:5.8.0\" // mocks
+}
+";
+
+#[test]
+fn dev_dependencies_add_update_delete_gradle() {
+    let addr = maven_repository();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let build = dir.join("build.gradle");
+    fs::write(&build, BUILD_GRADLE).unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\nmaven = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+
+    // After the last test dependency, quoted like the first one
+    let output = dx_deps(dir, &["add", "org.assertj:assertj-core", "3.25.0"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&build).unwrap();
+    assert!(
+        content.contains(
+            "    testImplementation \"org.mockito:mockito-core:5.8.0\" // mocks\n    testImplementation 'org.assertj:assertj-core:3.25.0'\n}\n"
+        ),
+        "{content}"
+    );
+
+    let output = dx_deps(dir, &["update", "org.mockito:mockito-core"]);
+    assert!(output.status.success(), "{output:?}");
+    let output = dx_deps(dir, &["update"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&build).unwrap();
+    assert!(
+        content.contains("\"org.mockito:mockito-core:5.11.0\" // mocks\n"),
+        "{content}"
+    );
+    assert!(
+        content.contains("'org.assertj:assertj-core:5.11.0'"),
+        "{content}"
+    );
+    // Without a version, the Spring Boot platform manages it
+    assert!(
+        content
+            .contains("testImplementation 'org.springframework.boot:spring-boot-starter-test'\n"),
+        "{content}"
+    );
+
+    let output = dx_deps(dir, &["delete", "org.assertj:assertj-core"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        fs::read_to_string(&build).unwrap(),
+        BUILD_GRADLE.replace("5.8.0", "5.11.0")
+    );
+
+    let output = dx_deps(dir, &["delete", "org.assertj:assertj-core"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+}
+
+#[test]
+fn dev_dependencies_gradle_kotlin_dsl_and_version_catalog() {
+    let addr = maven_repository();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let build = dir.join("build.gradle.kts");
+    let catalog = dir.join("gradle/libs.versions.toml");
+    fs::create_dir_all(dir.join("gradle")).unwrap();
+    fs::write(
+        &build,
+        "dependencies {
+    testImplementation(libs.junit.jupiter)
+    testImplementation(libs.junit.params)
+}
+",
+    )
+    .unwrap();
+    fs::write(
+        &catalog,
+        "[versions]
+junit = \"5.9.0\" # JUnit 5
+
+[libraries]
+junit-jupiter = { module = \"org.junit.jupiter:junit-jupiter\", version.ref = \"junit\" }
+junit-params = { module = \"org.junit.jupiter:junit-jupiter-params\", version.ref = \"junit\" }
+",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\nmaven = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+
+    let output = dx_deps(dir, &["list"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("- org.junit.jupiter:junit-jupiter-params = 5.9.0"),
+        "{stdout}"
+    );
+
+    // New libraries go to the catalog
+    let output = dx_deps(dir, &["add", "org.assertj:assertj-core", "3.25.0"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(fs::read_to_string(&build).unwrap().ends_with(
+        "    testImplementation(libs.junit.params)\n    testImplementation(libs.assertj.core)\n}\n"
+    ));
+
+    // The shared version is bumped where it is defined, once
+    let output = dx_deps(dir, &["update"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        fs::read_to_string(&catalog).unwrap(),
+        "[versions]
+junit = \"5.11.0\" # JUnit 5
+
+[libraries]
+junit-jupiter = { module = \"org.junit.jupiter:junit-jupiter\", version.ref = \"junit\" }
+junit-params = { module = \"org.junit.jupiter:junit-jupiter-params\", version.ref = \"junit\" }
+assertj-core = { module = \"org.assertj:assertj-core\", version = \"5.11.0\" }
+"
+    );
+
+    // The catalog entry stays for the other modules
+    let output = dx_deps(dir, &["delete", "org.junit.jupiter:junit-jupiter-params"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        fs::read_to_string(&build).unwrap(),
+        "dependencies {
+    testImplementation(libs.junit.jupiter)
+    testImplementation(libs.assertj.core)
+}
+"
+    );
+    assert!(
+        fs::read_to_string(&catalog)
+            .unwrap()
+            .contains("junit-params")
+    );
+
+    // A build without a dependencies block gets one
+    fs::write(&build, "plugins {\n    java\n}\n").unwrap();
+    fs::remove_file(&catalog).unwrap();
+    let output = dx_deps(dir, &["add", "junit:junit", "4.13.2"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        fs::read_to_string(&build).unwrap(),
+        "plugins {\n    java\n}\n\ndependencies {\n    testImplementation(\"junit:junit:4.13.2\")\n}\n"
+    );
+}
+
+const GEMFILE: &str = "source 'https://rubygems.org'
+
+gem 'rails', '~> 7.0.8'
+gem 'pg', '~> 1.5'  # PostgreSQL adapter
+
+group :development, :test do
+    # Specs
+    gem 'rspec-rails', '~> 6.0'
+    gem 'faker', '3.2.0'  # fixed on purpose
+    gem 'debug'
+end
+
+group :test do
+  gem 'vcr', '>= 6.0', '< 7'
+end
+";
+
+/// RubyGems API: every gem's latest version is 7.1.3.
+fn rubygems() -> std::net::SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut buf = [0u8; 2048];
+            let _ = stream.read(&mut buf).unwrap();
+            let body = r#"{"name":"gem","version":"7.1.3"}"#;
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    addr
+}
+
+#[test]
+fn dev_dependencies_add_update_delete_ruby() {
+    let addr = rubygems();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let gemfile = dir.join("Gemfile");
+    fs::write(&gemfile, GEMFILE).unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\nrubygems = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+
+    // Into the development/test group, pinned like `bundle add`
+    let output = dx_deps(dir, &["add", "webmock"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&gemfile).unwrap();
+    assert!(
+        content.contains("    gem 'debug'\n    gem 'webmock', '~> 7.1'\nend\n"),
+        "{content}"
+    );
+
+    // The operator and precision are kept; comments stay in place
+    let output = dx_deps(dir, &["update"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&gemfile).unwrap();
+    assert!(
+        content.contains("    # Specs\n    gem 'rspec-rails', '~> 7.1'\n"),
+        "{content}"
+    );
+    assert!(
+        content.contains("    gem 'faker', '7.1.3'  # fixed on purpose\n"),
+        "{content}"
+    );
+    assert!(content.contains("    gem 'debug'\n"), "{content}");
+    assert!(
+        content.contains("  gem 'vcr', '>= 6.0', '< 7'\n"),
+        "{content}"
+    );
+    assert!(content.contains("gem 'rails', '~> 7.0.8'\n"), "{content}");
+
+    // A runtime gem can't be declared twice
+    let output = dx_deps(dir, &["add", "pg", "1.6"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+
+    // Removing the last gem of a group removes the group
+    let output = dx_deps(dir, &["delete", "vcr"]);
+    assert!(output.status.success(), "{output:?}");
+    let output = dx_deps(dir, &["delete", "faker"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&gemfile).unwrap();
+    assert!(
+        content.ends_with(
+            "group :development, :test do\n    # Specs\n    gem 'rspec-rails', '~> 7.1'\n    gem 'debug'\n    gem 'webmock', '~> 7.1'\nend\n"
+        ),
+        "{content}"
+    );
+}
+
+#[test]
+fn dev_dependencies_ruby_creates_the_group() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let gemfile = dir.join("Gemfile");
+    fs::write(
+        &gemfile,
+        "source \"https://rubygems.org\"\n\ngem \"sinatra\"\n",
+    )
+    .unwrap();
+
+    let output = dx_deps(dir, &["add", "rack-test", ">= 2.1"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&gemfile).unwrap();
+    assert_eq!(
+        content,
+        "source \"https://rubygems.org\"\n\ngem \"sinatra\"\n\ngroup :development, :test do\n  gem \"rack-test\", \">= 2.1\"\nend\n"
+    );
+    let output = dx_deps(dir, &["list"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("rack-test"), "{stdout}");
+    assert!(!stdout.contains("sinatra"), "{stdout}");
+}
+
+/// PyPI JSON API: every package's latest version is 8.1.1.
+fn pypi() -> std::net::SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut buf = [0u8; 2048];
+            let _ = stream.read(&mut buf).unwrap();
+            let body = r#"{"info":{"version":"8.1.1"}}"#;
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    addr
+}
+
+const POETRY_PYPROJECT: &str = r#"[tool.poetry]
+name = "app"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+fastapi = "^0.110"
+
+[tool.poetry.group.dev.dependencies]
+pytest = "^7.4"  # test runner
+pytest-cov = { version = "^4.1", extras = ["toml"] }
+mypy = { git = "https://github.com/python/mypy.git" }
+
+[build-system]
+requires = ["poetry-core"]
+"#;
+
+#[test]
+fn dev_dependencies_add_update_delete_poetry() {
+    let addr = pypi();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let pyproject = dir.join("pyproject.toml");
+    fs::write(&pyproject, POETRY_PYPROJECT).unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\npypi = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+
+    // Pinned with a caret, like `poetry add`
+    let output = dx_deps(dir, &["add", "ruff"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&pyproject).unwrap();
+    assert!(
+        content.contains(
+            "mypy = { git = \"https://github.com/python/mypy.git\" }\nruff = \"^8.1.1\"\n\n[build-system]"
+        ),
+        "{content}"
+    );
+
+    // Operators and comments survive; git dependencies are kept
+    let output = dx_deps(dir, &["update"]);
+    assert!(output.status.success(), "{output:?}");
+    let content = fs::read_to_string(&pyproject).unwrap();
+    assert!(
+        content.contains("pytest = \"^8.1\"  # test runner\n"),
+        "{content}"
+    );
+    assert!(
+        content.contains("pytest-cov = { version = \"^8.1\", extras = [\"toml\"] }\n"),
+        "{content}"
+    );
+    assert!(content.contains("fastapi = \"^0.110\"\n"), "{content}");
+
+    // Names are matched as pip does
+    let output = dx_deps(dir, &["delete", "Pytest_Cov"]);
+    assert!(output.status.success(), "{output:?}");
+    let output = dx_deps(dir, &["list"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("pytest"), "{stdout}");
+    assert!(!stdout.contains("pytest-cov"), "{stdout}");
+    assert!(!stdout.contains("fastapi"), "{stdout}");
+
+    // Without a dev group, `add` creates it
+    fs::write(
+        &pyproject,
+        "[tool.poetry]\nname = \"app\"\n\n[tool.poetry.dependencies]\npython = \"^3.11\"\n",
+    )
+    .unwrap();
+    let output = dx_deps(dir, &["add", "pytest", "^8.0"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        fs::read_to_string(&pyproject).unwrap(),
+        "[tool.poetry]\nname = \"app\"\n\n[tool.poetry.dependencies]\npython = \"^3.11\"\n\n[tool.poetry.group.dev.dependencies]\npytest = \"^8.0\"\n"
+    );
+}
+
+#[test]
+fn dev_dependencies_reject_unexpected_poetry_tables() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let pyproject = dir.join("pyproject.toml");
+    for (content, path) in [
+        ("[tool.poetry]\nname = \"app\"\ngroup = 3\n", "[tool.poetry.group]"),
+        (
+            "[tool.poetry]\nname = \"app\"\n\n[tool.poetry.group.dev]\ndependencies = \"oops\"\n",
+            "[tool.poetry.group.dev.dependencies]",
+        ),
+    ] {
+        fs::write(&pyproject, content).unwrap();
+        let output = dx_deps(dir, &["add", "pytest", "^8.0"]);
+        assert_eq!(output.status.code(), Some(2), "{output:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains(&format!("{path} não é uma tabela no pyproject.toml")),
+            "{stderr}"
+        );
+        assert_eq!(fs::read_to_string(&pyproject).unwrap(), content);
+    }
+}
+
+const PEP621_PYPROJECT: &str = r#"[project]
+name = "app"
+dependencies = ["fastapi>=0.110"]
+
+[project.optional-dependencies]
+dev = [
+    "pytest>=7.4",
+    "pytest-cov[toml]~=4.1 ; python_version >= '3.9'",
+    "black",
+]
+docs = ["mkdocs>=1.5"]
+"#;
+
+#[test]
+fn dev_dependencies_add_update_delete_pep621() {
+    let addr = pypi();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let pyproject = dir.join("pyproject.toml");
+    fs::write(&pyproject, PEP621_PYPROJECT).unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\npypi = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+
+    let output = dx_deps(dir, &["add", "ruff"]);
+    assert!(output.status.success(), "{output:?}");
+    let output = dx_deps(dir, &["add", "coverage", "7.4.0"]);
+    assert!(output.status.success(), "{output:?}");
+    let output = dx_deps(dir, &["update"]);
+    assert!(output.status.success(), "{output:?}");
+    let output = dx_deps(dir, &["delete", "pytest"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        fs::read_to_string(&pyproject).unwrap(),
+        r#"[project]
+name = "app"
+dependencies = ["fastapi>=0.110"]
+
+[project.optional-dependencies]
+dev = [
+    "pytest-cov[toml]~=8.1 ; python_version >= '3.9'",
+    "black",
+    "ruff>=8.1.1",
+    "coverage==8.1.1",
+]
+docs = ["mkdocs>=1.5"]
+"#
+    );
+}
+
+/// OSV records of the fake OSV.dev: a GitHub advisory rated critical and one
+/// rated only by its CVSS vector (7.5, high).
+fn osv_record(id: &str) -> String {
+    match id {
+        "GHSA-35jh-r3h4-6jhm" => r#"{"id": "GHSA-35jh-r3h4-6jhm", "aliases": ["CVE-2021-23337"],
+            "summary": "Command Injection in lodash",
+            "database_specific": {"severity": "CRITICAL"},
+            "affected": [{"package": {"name": "lodash", "ecosystem": "npm"},
+                "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "4.17.21"}]}]}]}"#
+            .to_string(),
+        _ => format!(
+            r#"{{"id": "{id}", "summary": "ReDoS in lodash",
+            "severity": [{{"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:H"}}],
+            "affected": [{{"package": {{"name": "lodash", "ecosystem": "npm"}},
+                "ranges": [{{"type": "SEMVER", "events": [{{"introduced": "0"}}, {{"fixed": "4.17.19"}}]}},
+                           {{"type": "SEMVER", "events": [{{"introduced": "4.17.20"}}, {{"fixed": "4.17.22"}}]}}]}}]}}"#
+        ),
+    }
+}
+
+/// Fake OSV.dev: lodash has two advisories, other packages none. The bodies
+/// of the `querybatch` requests are recorded.
+fn osv() -> (
+    std::net::SocketAddr,
+    std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let batches = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorded = batches.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            // Headers, then the body up to its Content-Length
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length || n == 0 {
+                        break;
+                    }
+                }
+            }
+            let text = String::from_utf8_lossy(&request).to_string();
+            let (head, body) = text.split_once("\r\n\r\n").unwrap();
+            let path = head.split_whitespace().nth(1).unwrap().to_string();
+            let response = if path == "/v1/querybatch" {
+                recorded.lock().unwrap().push(body.to_string());
+                let batch: serde_json::Value = serde_json::from_str(body).unwrap();
+                let results: Vec<serde_json::Value> = batch["queries"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|q| match q["package"]["name"].as_str() {
+                        Some("lodash") => serde_json::json!({"vulns": [
+                            {"id": "GHSA-35jh-r3h4-6jhm"}, {"id": "GHSA-29mw-wpgm-hmr9"}
+                        ]}),
+                        _ => serde_json::json!({}),
+                    })
+                    .collect();
+                serde_json::json!({ "results": results }).to_string()
+            } else {
+                osv_record(path.trim_start_matches("/v1/vulns/"))
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            );
+        }
+    });
+    (addr, batches)
+}
+
+#[test]
+fn dev_dependencies_audit_reports_advisories_and_fails_on_critical() {
+    let (addr, batches) = osv();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(
+        dir.join("package.json"),
+        r#"{"devDependencies": {"lodash": "^4.17.20", "jest": "29.7", "eslint": "*", "local": "file:../local"}}"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\nosv = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+
+    let output = dx_deps(dir, &["audit"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "lodash ^4.17.20\n  [CRÍTICA] GHSA-35jh-r3h4-6jhm (CVE-2021-23337): Command Injection in lodash\n      corrigida em 4.17.21\n  [ALTA] GHSA-29mw-wpgm-hmr9: ReDoS in lodash\n      corrigida em 4.17.22\n  Atualize com: dx dev-dependencies add lodash 4.17.22\n"
+        ),
+        "{stdout}"
+    );
+    assert!(stdout.contains("eslint (*)"), "{stdout}");
+    assert!(stdout.contains("local (file:../local)"), "{stdout}");
+    assert!(
+        stdout.contains("2 vulnerabilidade(s) em 1 de 2 dependência(s): 1 crítica, 1 alta."),
+        "{stdout}"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("1 vulnerabilidade(s) crítica(s)"),
+        "{stderr}"
+    );
+
+    // The declared specs are checked at the version they start from
+    let batches = batches.lock().unwrap();
+    assert_eq!(batches.len(), 1);
+    assert!(
+        batches[0].contains(r#""version":"4.17.20""#)
+            && batches[0].contains(r#""version":"29.7.0""#),
+        "{}",
+        batches[0]
+    );
+}
+
+#[test]
+fn dev_dependencies_audit_passes_without_critical_advisories() {
+    let (addr, _) = osv();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"app\"\n\n[dev-dependencies]\ninsta = \"1.39\"\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\nosv = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+
+    let output = dx_deps(dir, &["audit"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "Nenhuma vulnerabilidade conhecida em 1 dependência(s) (crates.io no OSV.dev)."
+        ),
+        "{stdout}"
+    );
+
+    let output = dx_deps(dir, &["--offline", "audit"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+}
+
+/// Registry answering license metadata: npm packages and a Maven artifact
+/// (`${junit.version}` of `POM`).
+fn license_registry() -> std::net::SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut buf = [0u8; 2048];
+            let n = stream.read(&mut buf).unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let body = match path {
+                "/jest/29.7.0" => r#"{"license": "MIT"}"#,
+                "/typescript/latest" => r#"{"license": "Apache-2.0"}"#,
+                "/gpl-tool/1.0.0" => r#"{"license": "GPL-3.0-or-later"}"#,
+                "/dual/2.0.0" => {
+                    r#"{"licenses": [{"type": "MIT"}, {"type": "Apache License, Version 2.0"}]}"#
+                }
+                "/org/junit/jupiter/junit-jupiter/5.9.0/junit-jupiter-5.9.0.pom" => {
+                    "<project><licenses><license><name>Eclipse Public License v2.0</name></license></licenses></project>"
+                }
+                _ => "",
+            };
+            let status = if body.is_empty() {
+                "404 Not Found"
+            } else {
+                "200 OK"
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    addr
+}
+
+#[test]
+fn dev_dependencies_licenses_groups_and_checks_the_allowlist() {
+    let addr = license_registry();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(
+        dir.join("package.json"),
+        r#"{"devDependencies": {"jest": "^29.7.0", "typescript": "*", "gpl-tool": "1.0.0", "dual": "~2.0.0", "private-lib": "1.2.3"}}"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\nnpm = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+
+    // Without an allowlist, only the groups
+    let output = dx_deps(dir, &["licenses"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with(
+            "Apache-2.0 (1)\n  - typescript *\nGPL-3.0-or-later (1)\n  - gpl-tool 1.0.0\nMIT (1)\n  - jest ^29.7.0\nMIT OR Apache-2.0 (1)\n  - dual ~2.0.0\nDesconhecida (1)\n  - private-lib 1.2.3\n"
+        ),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("dx dev-config add licenses.allow"),
+        "{stdout}"
+    );
+
+    let output = dx_deps(dir, &["--offline", "licenses"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+
+    // One alternative of a dual license is enough; unknown licenses are not violations
+    fs::create_dir_all(dir.join(".dx")).unwrap();
+    fs::write(
+        dir.join(".dx/config.json"),
+        r#"{"licenses.allow": "MIT, Apache-2.0"}"#,
+    )
+    .unwrap();
+    let output = dx_deps(dir, &["licenses"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.ends_with("Fora de licenses.allow:\n  - gpl-tool 1.0.0 (GPL-3.0-or-later)\n"),
+        "{stdout}"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("1 dependência(s) com licença fora de licenses.allow: gpl-tool."),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn dev_dependencies_licenses_reads_maven_poms() {
+    let addr = license_registry();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(dir.join("pom.xml"), POM).unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\nmaven = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+    let output = dx_deps(dir, &["--json", "licenses"]);
+    assert!(output.status.success(), "{output:?}");
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        envelope["data"]["licenses"][0]["license"], "EPL-2.0",
+        "{envelope}"
+    );
+    assert_eq!(
+        envelope["data"]["licenses"][0]["dependencies"][0]["name"],
+        "org.junit.jupiter:junit-jupiter"
+    );
+}
+
+/// npm registry answering the latest version of each package.
+fn latest_registry() -> std::net::SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut buf = [0u8; 2048];
+            let n = stream.read(&mut buf).unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let version = match path {
+                "/jest/latest" => "30.1.0",
+                "/eslint/latest" => "9.4.2",
+                "/prettier/latest" => "3.3.4",
+                "/tsx/latest" => "0.5.0",
+                "/vitest/latest" => "2.0.0",
+                _ => "",
+            };
+            let (status, body) = if version.is_empty() {
+                ("404 Not Found", String::new())
+            } else {
+                ("200 OK", format!(r#"{{"version": "{version}"}}"#))
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    addr
+}
+
+#[test]
+fn dev_dependencies_outdated_classifies_the_jumps() {
+    let addr = latest_registry();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(
+        dir.join("package.json"),
+        r#"{"devDependencies": {"jest": "^29.7.0", "eslint": "~9.1.0", "prettier": "3.3.3", "tsx": "^0.4.1", "vitest": "2.0.0", "private-lib": "1.2.3", "typescript": "latest"}}"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("dx.toml"),
+        format!("[registries]\nnpm = \"http://{addr}\"\n"),
+    )
+    .unwrap();
+
+    let output = dx_deps(dir, &["outdated"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with(
+            "jest      ^29.7.0 -> 30.1.0 (major)\ntsx       ^0.4.1 -> 0.5.0 (major)\neslint    ~9.1.0 -> 9.4.2 (minor)\nprettier  3.3.3 -> 3.3.4 (patch)\n"
+        ),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("private-lib (1.2.3), typescript (latest)"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("4 desatualizada(s): 2 major, 1 minor, 1 patch."),
+        "{stdout}"
+    );
+
+    let output = dx_deps(dir, &["--json", "outdated", "--fail-on", "major"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["data"]["outdated"][0]["name"], "jest");
+    assert_eq!(envelope["data"]["outdated"][0]["bump"], "major");
+    assert_eq!(envelope["data"]["outdated"][3]["bump"], "patch");
+    assert_eq!(
+        envelope["error"]["message"],
+        "2 dependência(s) com atualização major ou maior (--fail-on major): jest, tsx."
+    );
+
+    // Patch-only drift passes a major gate; the latest versions come from the cache
+    fs::write(
+        dir.join("package.json"),
+        r#"{"devDependencies": {"prettier": "3.3.3", "vitest": "2.0.0"}}"#,
+    )
+    .unwrap();
+    let output = dx_deps(dir, &["--offline", "outdated", "--fail-on", "major"]);
+    assert!(output.status.success(), "{output:?}");
+    let output = dx_deps(dir, &["--offline", "outdated", "--fail-on", "patch"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Fake program logging its name and arguments to `$CALLS`.
+fn script(bin: &Path, name: &str) {
+    fs::write(
+        bin.join(name),
+        format!("#!/bin/sh\necho \"{name} $*\" >> \"$CALLS\"\n"),
+    )
+    .unwrap();
+    fs::set_permissions(bin.join(name), fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+/// `dx dev-test --once` with only `bin` in the PATH.
+fn dev_test(tmp: &Path, bin: &Path, project: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", tmp.join("user-dx.toml"))
+        .env("PATH", bin)
+        .env("HOME", tmp)
+        .env("CALLS", tmp.join("calls.log"))
+        .env_remove("ASDF_DATA_DIR")
+        .env_remove("CI")
+        .args(["dev-test", "--once"])
+        .arg(project)
+        .output()
+        .expect("failed to run dx")
+}
+
+fn calls(tmp: &Path) -> String {
+    let log = tmp.join("calls.log");
+    let calls = fs::read_to_string(&log).unwrap_or_default();
+    let _ = fs::remove_file(log);
+    calls
+}
+
+#[test]
+fn pinned_toolchains_run_the_tests_through_mise_or_asdf() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let tmp = tmp.path();
+    let project = tmp.join("project");
+    let bin = tmp.join("bin");
+    fs::create_dir_all(&project).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(project.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+    script(&bin, "cargo");
+
+    // Nothing pinned: the cargo on PATH
+    let output = dev_test(tmp, &bin, &project);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(calls(tmp), "cargo test\n");
+
+    // mise runs the toolchain of .tool-versions...
+    fs::write(project.join(".tool-versions"), "rust 1.82.0\n").unwrap();
+    script(&bin, "mise");
+    let output = dev_test(tmp, &bin, &project);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(calls(tmp), "mise exec -- cargo test\n");
+
+    // ...and the project's own test task
+    fs::write(
+        project.join(".mise.toml"),
+        "[tools]\nrust = \"1.82\"\n\n[tasks.test]\nrun = \"cargo nextest run\"\n",
+    )
+    .unwrap();
+    let output = dev_test(tmp, &bin, &project);
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("> Executando testes: mise run test"),
+        "{output:?}"
+    );
+    assert_eq!(calls(tmp), "mise run test\n");
+
+    // asdf's shims come before the rest of the PATH
+    fs::remove_file(project.join(".mise.toml")).unwrap();
+    fs::remove_file(bin.join("mise")).unwrap();
+    script(&bin, "asdf");
+    let shims = tmp.join(".asdf/shims");
+    fs::create_dir_all(&shims).unwrap();
+    fs::write(
+        shims.join("cargo"),
+        "#!/bin/sh\necho \"shim cargo $*\" >> \"$CALLS\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(shims.join("cargo"), fs::Permissions::from_mode(0o755)).unwrap();
+    let output = dev_test(tmp, &bin, &project);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(calls(tmp), "shim cargo test\n");
+}
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+
+mod common;
+
+const README: &str = "# Projeto\n\n## Início rápido\n\n```sh\n# Preparar\nmkdir -p out\ncd out && echo pronto > status.txt\n```\n\n```powershell\nRemove-Item -Recurse out\n```\n\n## Outra seção\n\n```sh\nfalse\n```\n";
+
+#[test]
+fn verify_quickstart_lints_only_the_quickstart_section() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(dir.join("README.md"), README).unwrap();
+
+    let output = common::run(dir, &["--output", "json", "docs", "verify-quickstart"]);
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let data = &json["data"];
+    assert_eq!(data["section"], "Início rápido");
+    assert_eq!(data["mode"], "lint");
+    let commands: Vec<&str> = data["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["command"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        commands,
+        ["mkdir -p out", "cd out && echo pronto > status.txt"]
+    );
+    // Lint mode doesn't run anything
+    assert!(!dir.join("out").exists());
+}
+
+#[test]
+fn verify_quickstart_reports_broken_steps() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(
+        dir.join("README.md"),
+        "## Quickstart\n\n```bash\ncd docs\ndx-programa-inexistente --help\necho 'ok\ndx dev-services --nao-existe\n```\n",
+    )
+    .unwrap();
+
+    let output = common::run(dir, &["docs", "verify-quickstart"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[QUEBRADO] linha 4: cd docs — diretório 'docs' não existe"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("programa 'dx-programa-inexistente' não encontrado no PATH"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("linha 6: echo 'ok — erro de sintaxe"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("linha 7: dx dev-services --nao-existe — argumentos do dx inválidos"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn verify_quickstart_runs_steps_in_a_sandbox() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(dir.join("README.md"), README).unwrap();
+
+    let output = common::run(dir, &["docs", "verify-quickstart", "--run"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!dir.join("out").exists(), "the project must not be touched");
+
+    fs::write(
+        dir.join("README.md"),
+        "## Quickstart\n\n```console\n$ test -f README.md\n$ ls nao-existe\nls: nao-existe: No such file\n$ echo depois\n```\n",
+    )
+    .unwrap();
+    let output = common::run(dir, &["docs", "verify-quickstart", "--run"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[OK] linha 4: test -f README.md"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("[QUEBRADO] linha 5: ls nao-existe — falhou"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("[IGNORADO] linha 7: echo depois"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn verify_quickstart_without_section_is_not_found() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(dir.join("README.md"), "# Projeto\n\nSem passos.\n").unwrap();
+
+    let output = common::run(dir, &["docs", "verify-quickstart"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Seção de quickstart não encontrada"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+mod common;
+
+fn dx(dir: &Path, args: &[&str]) {
+    let output = common::run(dir, args);
+    assert!(output.status.success(), "{output:?}");
+}
+
+fn project(language: Option<&str>) -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(dir.join("requirements.txt"), "kafka-python==2.0\n").unwrap();
+    fs::write(
+        dir.join("README.md"),
+        "# App\n\n<!-- dx-cli:ports:start -->\n<!-- dx-cli:ports:end -->\n",
+    )
+    .unwrap();
+    if let Some(language) = language {
+        fs::write(
+            dir.join("dx.toml"),
+            format!("[docs]\nlanguage = \"{language}\"\n"),
+        )
+        .unwrap();
+    }
+    tmp
+}
+
+#[test]
+fn generated_docs_follow_the_project_language() {
+    let tmp = project(Some("en"));
+    let dir = tmp.path();
+    dx(dir, &["--no-cache", "dev-services"]);
+    dx(dir, &["dev-badges"]);
+
+    let report = fs::read_to_string(dir.join(".dx/analyzer-report.md")).unwrap();
+    for expected in [
+        "## Table of Contents\n- [Summary](#summary)\n",
+        "- [Next Steps](#next-steps)\n",
+        "### Services overview\n",
+        "| Service | Image | Ports | Volumes | Credentials/Info |\n",
+        "### Estimated resources\n",
+        "## Development Dependencies\n",
+        "Report generated by dx-cli.\n",
+    ] {
+        assert!(report.contains(expected), "{expected:?} in {report}");
+    }
+    assert!(!report.contains("Tabela de Conteúdos"), "{report}");
+
+    let readme = fs::read_to_string(dir.join("README.md")).unwrap();
+    assert!(
+        readme.contains("<!-- Generated by `dx dev-services`; changes between the markers are overwritten. -->\n\n| Service | Host ports | UI |\n"),
+        "{readme}"
+    );
+    assert!(
+        readme.contains("[![Kafka (dev service)](https://img.shields.io/badge/Kafka-Dev_Service-black?logo=apachekafka)](#)"),
+        "{readme}"
+    );
+
+    // Spanish, and a README created from scratch
+    fs::write(dir.join("dx.toml"), "[docs]\nlanguage = \"es\"\n").unwrap();
+    fs::remove_file(dir.join("README.md")).unwrap();
+    dx(dir, &["dev-badges"]);
+    dx(dir, &["analyzer"]);
+    let readme = fs::read_to_string(dir.join("README.md")).unwrap();
+    assert!(readme.starts_with("# Proyecto\n"), "{readme}");
+    assert!(
+        readme.contains("[![Kafka (servicio de desarrollo)]"),
+        "{readme}"
+    );
+    let report = fs::read_to_string(dir.join(".dx/analyzer-report.md")).unwrap();
+    assert!(
+        report.contains("- [Próximos Pasos](#próximos-pasos)\n"),
+        "{report}"
+    );
+}
+
+#[test]
+fn generated_docs_default_to_portuguese() {
+    let tmp = project(None);
+    let dir = tmp.path();
+    dx(dir, &["--no-cache", "dev-services"]);
+
+    let report = fs::read_to_string(dir.join(".dx/analyzer-report.md")).unwrap();
+    assert!(
+        report.contains("## Tabela de Conteúdos\n- [Resumo](#resumo)\n"),
+        "{report}"
+    );
+    assert!(
+        report.contains("- [Próximas Ações](#próximas-ações)\n"),
+        "{report}"
+    );
+    assert!(
+        report.contains(
+            "[![Kafka](https://img.shields.io/badge/Kafka-Dev_Service-black?logo=apachekafka)](#)"
+        ),
+        "{report}"
+    );
+    let readme = fs::read_to_string(dir.join("README.md")).unwrap();
+    assert!(
+        readme.contains("| Serviço | Portas no host | UI |\n"),
+        "{readme}"
+    );
+
+    // Only pt, en and es
+    fs::write(dir.join("dx.toml"), "[docs]\nlanguage = \"fr\"\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .args(["analyzer", "--no-save"])
+        .arg(dir)
+        .output()
+        .expect("failed to run dx");
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+}
@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod common;
+
+/// Fake `docker`: a running daemon with Compose v2 and no containers.
+const DOCKER: &str = "#!/bin/sh
+case \"$*\" in
+  version*) echo 27.3.1 ;;
+  \"compose version --short\") echo 2.29.7 ;;
+  *) ;;
+esac
+exit 0
+";
+
+const NODE: &str = "#!/bin/sh
+echo v20.11.1
+";
+
+fn script(bin: &Path, name: &str, content: &str) {
+    fs::write(bin.join(name), content).unwrap();
+    fs::set_permissions(bin.join(name), fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+/// Registry answering 404 to everything, as a registry root may.
+fn registry() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut buf = [0u8; 2048];
+            let _ = stream.read(&mut buf).unwrap();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            );
+        }
+    });
+    format!("http://{addr}")
+}
+
+fn setup(registry: &str) -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(&project).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(
+        project.join("package.json"),
+        r#"{"devDependencies": {"jest": "^29.0.0"}}"#,
+    )
+    .unwrap();
+    fs::write(project.join(".nvmrc"), "20\n").unwrap();
+    fs::write(
+        project.join("dx.toml"),
+        format!("[registries]\nnpm = \"{registry}\"\n"),
+    )
+    .unwrap();
+    script(&bin, "docker", DOCKER);
+    script(&bin, "node", NODE);
+    tmp
+}
+
+/// Run dx with the fake tools first on PATH.
+fn dx(tmp: &Path, args: &[&str]) -> Output {
+    common::with_fake_tools(tmp)
+        .env("HOME", tmp)
+        .env_remove("NVM_DIR")
+        .env_remove("HTTPS_PROXY")
+        .env_remove("https_proxy")
+        .env_remove("HTTP_PROXY")
+        .env_remove("http_proxy")
+        .args(args)
+        .output()
+        .expect("failed to run dx")
+}
+
+fn check<'a>(json: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    json["data"]["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == name)
+        .unwrap_or_else(|| panic!("no check '{name}': {json}"))
+}
+
+#[test]
+fn doctor_reports_a_ready_machine() {
+    let tmp = setup(&registry());
+    let output = dx(tmp.path(), &["--json", "doctor"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(json["command"], "doctor");
+    assert_eq!(json["data"]["stack"], "node");
+    let docker = check(&json, "Docker");
+    assert_eq!(docker["status"], "ok");
+    assert!(docker["detail"].as_str().unwrap().starts_with("27.3.1"));
+    assert_eq!(check(&json, "Docker Compose")["status"], "ok");
+    let node = check(&json, "node");
+    assert_eq!(node["status"], "ok");
+    assert_eq!(node["detail"], "20.11.1 (o projeto pede 20 em .nvmrc)");
+    let registry = check(&json, "Registry npm");
+    assert_eq!(registry["status"], "ok", "{json}");
+    assert!(registry["detail"].as_str().unwrap().contains("HTTP 404"));
+
+    // --offline leaves the network out
+    let output = dx(tmp.path(), &["--json", "--offline", "doctor"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(
+        json["data"]["checks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|c| c["kind"] != "network")
+    );
+}
+
+#[test]
+fn doctor_suggests_fixes_for_each_problem() {
+    // A port nobody listens on after the listener is dropped
+    let closed = TcpListener::bind("127.0.0.1:0").unwrap();
+    let unreachable = format!("http://{}", closed.local_addr().unwrap());
+    drop(closed);
+    let tmp = setup(&unreachable);
+    let project = tmp.path().join("project");
+    fs::write(project.join(".nvmrc"), "22\n").unwrap();
+    let busy = TcpListener::bind("0.0.0.0:0").unwrap();
+    let port = busy.local_addr().unwrap().port();
+    fs::create_dir_all(project.join(".dx")).unwrap();
+    fs::write(
+        project.join(".dx/docker-compose.yml"),
+        format!(
+            "services:\n  postgres:\n    image: postgres:16\n    ports:\n      - \"{port}:5432\"\n"
+        ),
+    )
+    .unwrap();
+
+    let output = dx(tmp.path(), &["doctor"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "[aviso] node: 20.11.1 instalado; o projeto pede 22 (.nvmrc)\n    Dica: nvm install 22"
+        ),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains(&format!(
+            "[erro] Porta {port} (postgres): em uso por outro processo"
+        )),
+        "{stdout}"
+    );
+    assert!(stdout.contains(&format!("lsof -i :{port}")), "{stdout}");
+    assert!(stdout.contains("[erro] Registry npm:"), "{stdout}");
+    assert!(stdout.contains("[registries]"), "{stdout}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("problema(s) a corrigir"), "{stderr}");
+}
+
+#[test]
+fn doctor_without_docker_or_node() {
+    let tmp = setup(&registry());
+    let bin = tmp.path().join("bin");
+    fs::remove_file(bin.join("docker")).unwrap();
+    fs::remove_file(bin.join("node")).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", tmp.path().join("user-dx.toml"))
+        .env("XDG_CACHE_HOME", tmp.path().join("cache"))
+        .env("PATH", &bin)
+        .env("HOME", tmp.path())
+        .env_remove("NVM_DIR")
+        .args(["--offline", "doctor"])
+        .current_dir(tmp.path().join("project"))
+        .output()
+        .expect("failed to run dx");
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[erro] Docker: nem docker, podman ou nerdctl encontrados no PATH"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("Docker Compose"), "{stdout}");
+    assert!(
+        stdout.contains("[erro] node: não encontrado no PATH\n    Dica: nvm install 20"),
+        "{stdout}"
+    );
+}
+
+/// Fake `mise`: `install node@<v>` replaces the fake `node` with one of
+/// version `<v>`.
+const MISE: &str = "#!/bin/sh
+echo \"mise $*\" >> \"$DOCKER_LOG\"
+case \"$2\" in
+  node@*) printf '#!/bin/sh\\necho v%s\\n' \"${2#node@}\" > \"$(dirname \"$0\")/node\" ;;
+  *) exit 1 ;;
+esac
+";
+
+#[test]
+fn doctor_fix_installs_the_pinned_toolchain() {
+    let tmp = setup(&registry());
+    let project = tmp.path().join("project");
+    fs::remove_file(project.join(".nvmrc")).unwrap();
+    fs::write(project.join(".tool-versions"), "nodejs 22.3.0 # LTS\n").unwrap();
+    script(&tmp.path().join("bin"), "mise", MISE);
+
+    let output = dx(tmp.path(), &["--offline", "doctor"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "[aviso] node: 20.11.1 instalado; o projeto pede 22.3.0 (.tool-versions)\n    Dica: mise install node@22.3.0, ou rode 'dx doctor --fix'."
+        ),
+        "{stdout}"
+    );
+
+    let output = dx(tmp.path(), &["--offline", "doctor", "--fix"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Instalando node 22.3.0 com mise..."),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("[ok] node: 22.3.0 (o projeto pede 22.3.0 em .tool-versions)"),
+        "{stdout}"
+    );
+    let log = fs::read_to_string(tmp.path().join("docker.log")).unwrap();
+    assert!(log.contains("mise install node@22.3.0"), "{log}");
+}
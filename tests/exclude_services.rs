@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+mod common;
+
+fn dx(dir: &Path, args: &[&str]) -> Output {
+    let output = common::run(dir, args);
+    assert!(output.status.success(), "{output:?}");
+    output
+}
+
+fn manifest(dir: &Path) -> String {
+    fs::read_to_string(dir.join(".dx/docker-compose.yml")).unwrap()
+}
+
+#[test]
+fn without_leaves_detected_services_out_and_is_persisted() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(dir.join("requirements.txt"), "kafka-python==2.0\n").unwrap();
+    let config = "# Configuração do time\n[docs]\nlanguage = \"pt\"\n";
+    fs::write(dir.join("dx.toml"), config).unwrap();
+
+    // --no-save applies it to that run only
+    let output = dx(
+        dir,
+        &[
+            "--json",
+            "dev-services",
+            "--no-save",
+            "--without",
+            "kafka-ui",
+        ],
+    );
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        envelope["data"]["excluded"],
+        serde_json::json!(["kafka-ui"])
+    );
+    assert_eq!(fs::read_to_string(dir.join("dx.toml")).unwrap(), config);
+
+    dx(dir, &["dev-services", "--without", "kafka-ui,grafana"]);
+    assert_eq!(
+        fs::read_to_string(dir.join("dx.toml")).unwrap(),
+        format!("{config}\n[dev_services]\nexclude = [\"kafka-ui\", \"grafana\"]\n")
+    );
+    let yaml = manifest(dir);
+    assert!(yaml.contains("\n  kafka:\n"), "{yaml}");
+    assert!(
+        !yaml.contains("kafka-ui") && !yaml.contains("\n  grafana:\n"),
+        "{yaml}"
+    );
+    let report = fs::read_to_string(dir.join(".dx/analyzer-report.md")).unwrap();
+    assert!(
+        report.contains("### Serviços excluídos\n\n") && report.contains("- kafka-ui\n- grafana\n"),
+        "{report}"
+    );
+
+    // Regenerating without the flag keeps them out
+    fs::remove_dir_all(dir.join(".dx")).unwrap();
+    dx(dir, &["dev-services", "--without", "kafka-ui"]);
+    dx(dir, &["--no-cache", "dev-services"]);
+    let yaml = manifest(dir);
+    assert!(
+        !yaml.contains("kafka-ui") && !yaml.contains("\n  grafana:\n"),
+        "{yaml}"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("dx.toml"))
+            .unwrap()
+            .matches("kafka-ui")
+            .count(),
+        1
+    );
+}
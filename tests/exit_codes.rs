@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+
+mod common;
+
+#[test]
+fn missing_entries_exit_with_not_found() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let output = common::run_in(tmp.path(), &["dev-config", "update", "nope", "1"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("'nope' não existe"), "{}", stderr);
+
+    fs::write(tmp.path().join("package.json"), "{\"devDependencies\": {}}\n").unwrap();
+    let output = common::run_in(tmp.path(), &["dev-dependencies", "delete", "eslint"]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn invalid_input_and_unsupported_stacks_have_distinct_codes() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let output = common::run_in(tmp.path(), &["cache", "clear", "--category", "nope"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = common::run_in(tmp.path(), &["dev-dependencies", "list"]);
+    assert_eq!(output.status.code(), Some(4));
+
+    fs::write(tmp.path().join("Package.swift"), "// swift-tools-version:5.9\n").unwrap();
+    let output = common::run_in(tmp.path(), &["dev-dependencies", "add", "swift-log"]);
+    assert_eq!(output.status.code(), Some(4));
+}
+
+#[test]
+fn clean_without_confirmation_exits_with_aborted() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::create_dir_all(tmp.path().join("api").join(".dx")).unwrap();
+    let output = common::run_in(tmp.path(), &["clean"]);
+    assert_eq!(output.status.code(), Some(8));
+    assert!(tmp.path().join("api").join(".dx").is_dir());
+}
+
+#[test]
+fn json_envelope_carries_error_kind_and_code() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let output = common::run_in(tmp.path(), &["--output", "json", "dev-config", "delete", "nope"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("json envelope");
+    assert_eq!(json["ok"], false);
+    assert_eq!(json["error"]["kind"], "not_found");
+    assert_eq!(json["error"]["exit_code"], 3);
+}
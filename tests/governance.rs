@@ -0,0 +1,367 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::process::Command;
+
+fn project_with_postgres() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[dependencies]\npostgres = \"0.19\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(tmp.path().join(".dx").join("policies")).unwrap();
+    tmp
+}
+
+#[test]
+fn governance_without_policies_succeeds() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let output = Command::new(exe)
+        .args(["governance", "check"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run dx governance check");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Nenhuma policy encontrada"), "{}", stdout);
+}
+
+#[test]
+fn governance_cel_policies_pass_and_fail() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let tmp = project_with_postgres();
+    let policies = tmp.path().join(".dx").join("policies");
+    fs::write(
+        policies.join("has-postgres.cel"),
+        "# Projeto deve usar Postgres\nservices.exists(s, s.name == \"postgres\")\n",
+    )
+    .unwrap();
+    fs::write(
+        policies.join("language.cel"),
+        "project.language == 'Rust' && size(project.markers) > 0\n",
+    )
+    .unwrap();
+
+    let output = Command::new(exe)
+        .arg("governance")
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run dx governance");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("[OK] has-postgres (cel)"), "{}", stdout);
+    assert!(stdout.contains("[OK] language (cel)"), "{}", stdout);
+
+    fs::write(
+        policies.join("no-pinned-alpine.cel"),
+        "// Imagens alpine não são permitidas\nservices.all(s, !s.image.contains('alpine'))\n",
+    )
+    .unwrap();
+    let output = Command::new(exe)
+        .args(["governance", "check"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run dx governance check");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{}", stdout);
+    assert!(stdout.contains("[FALHOU] no-pinned-alpine (cel)"), "{}", stdout);
+    assert!(stdout.contains("Imagens alpine não são permitidas"), "{}", stdout);
+}
+
+#[test]
+fn governance_reports_invalid_cel_expression() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let tmp = project_with_postgres();
+    fs::write(
+        tmp.path().join(".dx").join("policies").join("broken.cel"),
+        "services.all(s, \n",
+    )
+    .unwrap();
+    let output = Command::new(exe)
+        .args(["governance", "check"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run dx governance check");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[ERRO] broken (cel)"), "{}", stdout);
+}
+
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn governance_sync_installs_and_pins_git_bundle() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let bundle = tempfile::tempdir().expect("tempdir");
+    let cache = tempfile::tempdir().expect("tempdir");
+    let project = project_with_postgres();
+
+    fs::create_dir_all(bundle.path().join("policies")).unwrap();
+    fs::write(
+        bundle.path().join("policies").join("no-latest.cel"),
+        "# Imagens não devem usar a tag latest\nservices.all(s, !s.image.endsWith(':latest'))\n",
+    )
+    .unwrap();
+    fs::write(bundle.path().join("README.md"), "# org policies\n").unwrap();
+    git(bundle.path(), &["init", "--quiet"]);
+    git(bundle.path(), &["add", "."]);
+    git(
+        bundle.path(),
+        &[
+            "-c",
+            "user.name=dx",
+            "-c",
+            "user.email=dx@example.com",
+            "commit",
+            "--quiet",
+            "-m",
+            "policies",
+        ],
+    );
+
+    let output = Command::new(exe)
+        .args(["governance", "sync", "--source"])
+        .arg(bundle.path())
+        .arg(project.path())
+        .env("XDG_CACHE_HOME", cache.path())
+        .output()
+        .expect("failed to run dx governance sync");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let installed = project
+        .path()
+        .join(".dx/policies/shared/policies/no-latest.cel");
+    assert!(installed.is_file(), "{}", stdout);
+    assert!(!project.path().join(".dx/policies/shared/README.md").exists());
+
+    let pin = fs::read_to_string(project.path().join(".dx").join("governance.json")).unwrap();
+    assert!(pin.contains("\"version\""), "{}", pin);
+
+    // Second sync reuses the pinned version from cache without touching the source
+    fs::remove_dir_all(bundle.path().join(".git")).unwrap();
+    let output = Command::new(exe)
+        .args(["governance", "sync"])
+        .arg(project.path())
+        .env("XDG_CACHE_HOME", cache.path())
+        .output()
+        .expect("failed to run dx governance sync");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Usando bundle em cache"), "{}", stdout);
+
+    let output = Command::new(exe)
+        .args(["governance", "check"])
+        .arg(project.path())
+        .output()
+        .expect("failed to run dx governance check");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[OK] shared/policies/no-latest (cel)"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn governance_sync_refetches_branch_pins_and_rejects_options() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let bundle = tempfile::tempdir().expect("tempdir");
+    let cache = tempfile::tempdir().expect("tempdir");
+    let project = project_with_postgres();
+    let commit = |name: &str| {
+        fs::write(
+            bundle.path().join(format!("{name}.cel")),
+            "services.all(s, true)\n",
+        )
+        .unwrap();
+        git(bundle.path(), &["add", "."]);
+        git(
+            bundle.path(),
+            &[
+                "-c",
+                "user.name=dx",
+                "-c",
+                "user.email=dx@example.com",
+                "commit",
+                "--quiet",
+                "-m",
+                name,
+            ],
+        );
+    };
+    git(bundle.path(), &["init", "--quiet", "--initial-branch", "main"]);
+    commit("first");
+    let sync = |args: &[&str]| {
+        Command::new(exe)
+            .args(["governance", "sync"])
+            .args(args)
+            .arg(project.path())
+            .env("XDG_CACHE_HOME", cache.path())
+            .output()
+            .expect("failed to run dx governance sync")
+    };
+    let source = bundle.path().to_string_lossy().into_owned();
+    let output = sync(&["--source", &source, "--version", "main"]);
+    assert!(output.status.success(), "{output:?}");
+
+    // The branch moved: the next sync brings the new commit instead of the cached one
+    commit("second");
+    let output = sync(&[]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("Usando bundle em cache"), "{stdout}");
+    assert!(
+        project
+            .path()
+            .join(".dx/policies/shared/second.cel")
+            .is_file()
+    );
+
+    // Nothing that git would take for an option
+    let output = sync(&["--version=--upload-pack=touch"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let output = sync(&["--source=-oops"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+}
+
+#[cfg(unix)]
+#[test]
+fn governance_sync_skips_symlinks_and_keeps_similar_pins_apart() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let bundle = tempfile::tempdir().expect("tempdir");
+    let cache = tempfile::tempdir().expect("tempdir");
+    let outside = tempfile::tempdir().expect("tempdir");
+    let project = project_with_postgres();
+    let commit = |name: &str| {
+        git(bundle.path(), &["add", "."]);
+        git(
+            bundle.path(),
+            &[
+                "-c",
+                "user.name=dx",
+                "-c",
+                "user.email=dx@example.com",
+                "commit",
+                "--quiet",
+                "-m",
+                name,
+            ],
+        );
+        git(bundle.path(), &["tag", name]);
+    };
+    git(bundle.path(), &["init", "--quiet"]);
+    fs::write(bundle.path().join("ok.cel"), "services.all(s, true)\n").unwrap();
+    // A policy pointing at a file of the machine running the sync
+    fs::write(outside.path().join("id_rsa"), "secret\n").unwrap();
+    std::os::unix::fs::symlink(outside.path().join("id_rsa"), bundle.path().join("leak.cel"))
+        .unwrap();
+    commit("v1.2");
+    fs::write(bundle.path().join("other.cel"), "services.all(s, true)\n").unwrap();
+    commit("v1-2");
+
+    let source = bundle.path().to_string_lossy().into_owned();
+    for version in ["v1.2", "v1-2"] {
+        let output = Command::new(exe)
+            .args(["governance", "sync", "--source", &source, "--version", version])
+            .arg(project.path())
+            .env("XDG_CACHE_HOME", cache.path())
+            .output()
+            .expect("failed to run dx governance sync");
+        assert!(output.status.success(), "{output:?}");
+    }
+    let shared = project.path().join(".dx/policies/shared");
+    assert!(shared.join("ok.cel").is_file());
+    assert!(shared.join("other.cel").is_file());
+    assert!(!shared.join("leak.cel").exists());
+
+    // One cache directory per version, even though both read "v1-2" once slugged
+    let sources: Vec<_> = fs::read_dir(cache.path().join("dx").join("governance"))
+        .unwrap()
+        .flatten()
+        .collect();
+    assert_eq!(sources.len(), 1, "{sources:?}");
+    let versions = fs::read_dir(sources[0].path())
+        .unwrap()
+        .flatten()
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .count();
+    assert_eq!(versions, 2);
+}
+
+#[test]
+fn governance_verify_manifests_detects_hand_edits() {
+    let exe = env!("CARGO_BIN_EXE_dx");
+    let project = project_with_postgres();
+
+    let status = Command::new(exe)
+        .arg("dev-services")
+        .arg(project.path())
+        .output()
+        .expect("failed to run dx dev-services")
+        .status;
+    assert!(status.success());
+
+    let verify = || {
+        Command::new(exe)
+            .args(["governance", "verify-manifests"])
+            .arg(project.path())
+            .output()
+            .expect("failed to run dx governance verify-manifests")
+    };
+
+    let output = verify();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("[OK]"), "{}", stdout);
+
+    // The override file is allowlisted; the manifest must include it
+    let dx = project.path().join(".dx");
+    fs::write(
+        dx.join("docker-compose.override.yml"),
+        "services:\n  postgres:\n    ports: ['15432:5432']\n",
+    )
+    .unwrap();
+    let output = verify();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{}", stdout);
+    assert!(stdout.contains("[DIVERGENTE] docker-compose.yml: modificado"), "{}", stdout);
+    let status = Command::new(exe)
+        .arg("dev-services")
+        .arg(project.path())
+        .output()
+        .expect("failed to run dx dev-services")
+        .status;
+    assert!(status.success());
+    let output = verify();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("[PERMITIDO] docker-compose.override.yml"), "{}", stdout);
+
+    // Hand-editing a generated file is drift
+    let prom = dx.join("telemetry").join("prometheus").join("prometheus.yml");
+    let content = fs::read_to_string(&prom).unwrap();
+    fs::write(&prom, content.replace("30s", "5s")).unwrap();
+    let output = verify();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{}", stdout);
+    assert!(
+        stdout.contains("[DIVERGENTE] telemetry/prometheus/prometheus.yml: modificado"),
+        "{}",
+        stdout
+    );
+}
@@ -0,0 +1,229 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::path::Path;
+use std::process::Output;
+use std::sync::{Arc, Mutex};
+
+mod common;
+
+/// Requests received by the fake Grafana: method, path, authorization, body.
+type Requests = Arc<Mutex<Vec<(String, String, String, String)>>>;
+
+/// Fake Grafana: has the Prometheus datasource and no folders; `token` is
+/// the only token it accepts.
+fn grafana(token: &'static str) -> (SocketAddr, Requests) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requests = Requests::default();
+    let received = requests.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let mut parts = line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+            let (mut length, mut authorization) = (0, String::new());
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                let (name, value) = header.split_once(':').unwrap();
+                match name.to_ascii_lowercase().as_str() {
+                    "content-length" => length = value.trim().parse().unwrap(),
+                    "authorization" => authorization = value.trim().to_string(),
+                    _ => {}
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let body = String::from_utf8(body).unwrap();
+            received.lock().unwrap().push((
+                method.clone(),
+                path.clone(),
+                authorization.clone(),
+                body.clone(),
+            ));
+
+            let (status, response) = if authorization != format!("Bearer {token}") {
+                (401, r#"{"message":"invalid API key"}"#.to_string())
+            } else {
+                match (method.as_str(), path.as_str()) {
+                    ("GET", "/api/datasources/name/Prometheus") => (200, "{}".to_string()),
+                    ("GET", p) if p.starts_with("/api/datasources/name/") => {
+                        (404, r#"{"message":"Data source not found"}"#.to_string())
+                    }
+                    ("POST", "/api/datasources") => (200, "{}".to_string()),
+                    ("GET", "/api/folders") => (200, "[]".to_string()),
+                    ("POST", "/api/folders") => (200, r#"{"uid":"team-folder"}"#.to_string()),
+                    ("POST", "/api/dashboards/db") => {
+                        let uid =
+                            serde_json::from_str::<serde_json::Value>(&body).unwrap()["dashboard"]
+                                ["uid"]
+                                .as_str()
+                                .unwrap()
+                                .to_string();
+                        (200, format!(r#"{{"url":"/d/{uid}/overview"}}"#))
+                    }
+                    _ => (500, "{}".to_string()),
+                }
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            );
+        }
+    });
+    (addr, requests)
+}
+
+fn dx(dir: &Path, token: Option<&str>, args: &[&str]) -> Output {
+    let mut command = common::dx(dir);
+    command
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .env("NO_PROXY", "*")
+        .env_remove("GRAFANA_TOKEN")
+        .args(args)
+        .current_dir(dir);
+    if let Some(token) = token {
+        command.env("GRAFANA_TOKEN", token);
+    }
+    command.output().expect("failed to run dx")
+}
+
+/// A project with the telemetry files generated by `dx dev-services`.
+fn project() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("package.json"),
+        r#"{"dependencies": {"pg": "^8.11"}}"#,
+    )
+    .unwrap();
+    let output = dx(tmp.path(), None, &["--offline", "dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    tmp
+}
+
+#[test]
+fn export_dashboards_creates_missing_datasources_and_saves_the_dashboards() {
+    let (addr, requests) = grafana("secret");
+    let tmp = project();
+    let dashboards = tmp.path().join(".dx/telemetry/grafana/dashboards");
+    // A dashboard improved locally, with its own uid
+    fs::write(
+        dashboards.join("api.json"),
+        r#"{"id": 7, "uid": "api-latency", "title": "API latency", "panels": []}"#,
+    )
+    .unwrap();
+
+    let url = format!("http://{addr}/");
+    let output = dx(
+        tmp.path(),
+        Some("secret"),
+        &[
+            "dev-services",
+            "telemetry",
+            "export-dashboards",
+            "--to",
+            &url,
+            "--folder",
+            "Team",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Datasource 'Prometheus' já existe; mantido."),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("Datasource 'Loki' criado (http://loki:3100)"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains(&format!(
+            "Dashboard 'API latency' exportado: http://{addr}/d/api-latency/overview"
+        )),
+        "{stdout}"
+    );
+
+    let requests = requests.lock().unwrap();
+    assert!(requests.iter().all(|r| r.2 == "Bearer secret"));
+    let created: Vec<serde_json::Value> = requests
+        .iter()
+        .filter(|r| r.0 == "POST" && r.1 == "/api/datasources")
+        .map(|r| serde_json::from_str(&r.3).unwrap())
+        .collect();
+    let names: Vec<&str> = created
+        .iter()
+        .map(|d| d["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["Loki", "Tempo"]);
+    assert!(created.iter().all(|d| d.get("isDefault").is_none()));
+
+    let saved: Vec<serde_json::Value> = requests
+        .iter()
+        .filter(|r| r.1 == "/api/dashboards/db")
+        .map(|r| serde_json::from_str(&r.3).unwrap())
+        .collect();
+    assert_eq!(saved.len(), 2);
+    for body in &saved {
+        assert_eq!(body["overwrite"], true);
+        assert_eq!(body["folderUid"], "team-folder");
+        assert!(body["dashboard"]["id"].is_null());
+    }
+    assert_eq!(saved[0]["dashboard"]["uid"], "api-latency");
+    // The generated dashboard gets a stable uid from its file name
+    assert_eq!(saved[1]["dashboard"]["uid"], "dx-javascript-overview");
+}
+
+#[test]
+fn export_dashboards_reports_a_refused_token() {
+    let (addr, _) = grafana("secret");
+    let tmp = project();
+    let url = format!("http://{addr}");
+    let args = [
+        "dev-services",
+        "telemetry",
+        "export-dashboards",
+        "--to",
+        &url,
+    ];
+
+    let output = dx(tmp.path(), None, &args);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("GRAFANA_TOKEN"));
+
+    let output = dx(tmp.path(), Some("wrong"), &args);
+    assert_eq!(output.status.code(), Some(6), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("HTTP 401"), "{stderr}");
+    assert!(stderr.contains("invalid API key"), "{stderr}");
+}
+
+#[test]
+fn export_dashboards_needs_the_generated_dashboards() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let output = dx(
+        tmp.path(),
+        Some("secret"),
+        &[
+            "dev-services",
+            "telemetry",
+            "export-dashboards",
+            "--to",
+            "http://127.0.0.1:1",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("dx dev-services"), "{stderr}");
+}
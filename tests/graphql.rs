@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+
+mod common;
+
+fn read(dir: &Path, file: &str) -> String {
+    fs::read_to_string(dir.join(file)).unwrap_or_default()
+}
+
+#[test]
+fn apollo_server_gets_a_sandbox_pointed_at_the_app() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(
+        dir.join("package.json"),
+        r#"{"dependencies": {"@apollo/server": "^4.10", "graphql": "^16.8"}}"#,
+    )
+    .unwrap();
+    fs::write(dir.join(".env"), "PORT=4100\n").unwrap();
+
+    let output = common::run(dir, &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let compose = read(dir, ".dx/docker-compose.yml");
+    assert!(
+        compose.contains(
+            "  graphql-sandbox:\n    image: nginx:1.27-alpine\n    profiles:\n    - graphql\n    environment:\n      GRAPHQL_ENDPOINT: http://localhost:4100/graphql\n    ports:\n    - 4080:4080\n"
+        ),
+        "{compose}"
+    );
+    assert!(read(dir, ".dx/graphql/index.html").contains("initialEndpoint: query || endpoint"));
+    assert!(
+        read(dir, ".dx/graphql/default.conf.template")
+            .contains(r#"return 200 '{"endpoint": "${GRAPHQL_ENDPOINT}"}';"#)
+    );
+
+    let report = read(dir, ".dx/analyzer-report.md");
+    assert!(
+        report.contains("Sandbox: http://localhost:4080, endpoint: http://localhost:4100/graphql"),
+        "{report}"
+    );
+    assert!(
+        report.contains("https://img.shields.io/badge/GraphQL-Sandbox-E10098?logo=graphql"),
+        "{report}"
+    );
+}
+
+#[test]
+fn schema_files_reveal_a_graphql_server() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let resources = dir.join("src/main/resources");
+    fs::create_dir_all(resources.join("graphql")).unwrap();
+    fs::write(dir.join("pom.xml"), "<project></project>\n").unwrap();
+    fs::write(
+        resources.join("application.properties"),
+        "server.port=8081\nspring.graphql.path=/api/graphql\n",
+    )
+    .unwrap();
+    fs::write(
+        resources.join("graphql/schema.graphqls"),
+        "type Query {\n  books: [Book]\n}\n",
+    )
+    .unwrap();
+    // Client operations are not a schema
+    fs::write(
+        dir.join("books.graphql"),
+        "query Books {\n  books { title }\n}\n",
+    )
+    .unwrap();
+
+    let output = common::run(dir, &["--no-cache", "dev-services", "--no-save"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("      GRAPHQL_ENDPOINT: http://localhost:8081/api/graphql\n"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains(
+            "    - ../src/main/resources/graphql/schema.graphqls:/usr/share/nginx/html/schema/src/main/resources/graphql/schema.graphqls:ro\n"
+        ),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("books.graphql"), "{stdout}");
+}
+
+#[test]
+fn graphql_clients_alone_add_no_sandbox() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(
+        dir.join("package.json"),
+        r#"{"dependencies": {"graphql-request": "^6.1", "graphql": "^16.8"}}"#,
+    )
+    .unwrap();
+
+    let output = common::run(dir, &["--no-cache", "dev-services", "--no-save"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        !String::from_utf8_lossy(&output.stdout).contains("graphql-sandbox"),
+        "{output:?}"
+    );
+}
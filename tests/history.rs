@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod common;
+
+fn dx(dir: &Path, args: &[&str]) -> Output {
+    common::dx(dir)
+        .env("DX_CI", "0")
+        .env_remove("DX_HISTORY")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run dx")
+}
+
+fn history(dir: &Path) -> Vec<serde_json::Value> {
+    fs::read_to_string(dir.join(".dx/history.jsonl"))
+        .unwrap_or_default()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn commands_are_recorded_in_the_project_history() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+
+    // No `.dx` yet: nothing is written
+    let output = dx(dir, &["tests", "fixtures", "missing.sql"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+    assert!(!dir.join(".dx").exists());
+
+    fs::create_dir(dir.join(".dx")).unwrap();
+    let output = dx(dir, &["tests", "fixtures", "missing.sql"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+    let output = dx(dir, &["dev-config", "add", "API_TOKEN", "s3cret"]);
+    assert!(output.status.success(), "{output:?}");
+    // The project given as argument is the one recorded
+    let other = tempfile::tempdir().expect("tempdir");
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .env("DX_CI", "0")
+        .args(["dev-services", "--no-save"])
+        .arg(dir)
+        .current_dir(other.path())
+        .output()
+        .expect("failed to run dx");
+    assert!(output.status.success(), "{output:?}");
+    assert!(!other.path().join(".dx").exists());
+    // Not recorded: CI runs and `dx history`
+    let output = dx(dir, &["--ci", "dev-services", "--no-save"]);
+    assert!(output.status.success(), "{output:?}");
+    let output = dx(dir, &["history"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let entries = history(dir);
+    assert_eq!(entries.len(), 3, "{entries:?}");
+    assert_eq!(entries[0]["command"], "tests fixtures");
+    assert_eq!(entries[0]["exit_code"], 3);
+    assert_eq!(entries[0]["ok"], false);
+    assert!(
+        entries[0]["error"]
+            .as_str()
+            .unwrap()
+            .contains("missing.sql")
+    );
+    assert_eq!(
+        entries[1]["args"],
+        serde_json::json!(["dev-config", "add", "API_TOKEN", "***"])
+    );
+    assert!(
+        !fs::read_to_string(dir.join(".dx/history.jsonl"))
+            .unwrap()
+            .contains("s3cret")
+    );
+    assert_eq!(entries[2]["command"], "dev-services");
+    assert_eq!(entries[2]["ok"], true);
+    assert!(entries[2]["duration_ms"].is_u64());
+    assert!(entries[2]["started_at"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn history_lists_filters_and_summarizes() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::create_dir(dir.join(".dx")).unwrap();
+    let entry = |command: &str, ms: u64, code: i32| {
+        serde_json::json!({
+            "started_at": 1_700_000_000u64,
+            "command": command,
+            "args": command.split(' ').collect::<Vec<_>>(),
+            "duration_ms": ms,
+            "exit_code": code,
+            "ok": code == 0,
+            "version": "1.0.0",
+        })
+        .to_string()
+    };
+    let lines = [
+        entry("dev-services run", 3000, 0),
+        entry("dev-services run", 1000, 7),
+        entry("dev-services run", 2000, 0),
+        entry("analyzer", 500, 0),
+        "not json".to_string(),
+    ];
+    fs::write(dir.join(".dx/history.jsonl"), lines.join("\n") + "\n").unwrap();
+
+    let output = dx(dir, &["history", "-n", "2"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let listed: Vec<&str> = stdout.lines().collect();
+    assert_eq!(listed.len(), 2, "{stdout}");
+    assert!(listed[0].ends_with("dx analyzer (500 ms): ok"), "{stdout}");
+    assert!(
+        listed[1].ends_with("dx dev-services run (2.00 s): ok"),
+        "{stdout}"
+    );
+
+    let output = dx(dir, &["history", "--failed"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("dx dev-services run (1.00 s): falhou (código 7)"),
+        "{stdout}"
+    );
+    assert_eq!(stdout.lines().count(), 1, "{stdout}");
+
+    let output = dx(
+        dir,
+        &["history", "--command", "dev-services", "--stats", "--json"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["data"]["entries"].as_array().unwrap().len(), 3);
+    assert_eq!(
+        envelope["data"]["stats"],
+        serde_json::json!([{
+            "command": "dev-services run",
+            "runs": 3,
+            "failures": 1,
+            "median_ms": 2000,
+            "max_ms": 3000,
+        }])
+    );
+
+    let empty = tempfile::tempdir().expect("tempdir");
+    let output = dx(empty.path(), &["history"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Nenhuma operação registrada"));
+}
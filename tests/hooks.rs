@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+mod common;
+
+fn dx(dir: &Path, args: &[&str]) -> Output {
+    common::dx(dir)
+        .env("DX_CI", "0")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run dx")
+}
+
+#[test]
+fn pre_commit_manifest_publishes_every_hook() {
+    let manifest =
+        fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join(".pre-commit-hooks.yaml"))
+            .expect(".pre-commit-hooks.yaml");
+    let published: Vec<(String, String)> = manifest
+        .split("\n- ")
+        .skip(1)
+        .map(|hook| {
+            let field = |key: &str| {
+                hook.lines()
+                    .find_map(|l| l.trim().strip_prefix(key))
+                    .unwrap_or_else(|| panic!("{key} ausente em:\n{hook}"))
+                    .to_string()
+            };
+            (field("id: "), field("entry: "))
+        })
+        .collect();
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let output = dx(tmp.path(), &["--json", "hook"]);
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("JSON envelope");
+    let hooks: Vec<(String, String)> = json["data"]["hooks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|h| {
+            (
+                h["id"].as_str().unwrap().to_string(),
+                format!("dx hook {}", h["name"].as_str().unwrap()),
+            )
+        })
+        .collect();
+    assert_eq!(published, hooks);
+}
+
+#[test]
+fn gitignore_hook_fails_until_dx_is_ignored() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    assert!(dx(tmp.path(), &["hook", "gitignore"]).status.success());
+
+    fs::create_dir(tmp.path().join(".dx")).unwrap();
+    fs::write(tmp.path().join(".gitignore"), ".dx/backups/\n").unwrap();
+    let output = dx(tmp.path(), &["--json", "hook", "gitignore"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("JSON envelope");
+    assert!(
+        json["data"]["missing"]
+            .as_array()
+            .unwrap()
+            .contains(&".dx/.env".into()),
+        "{json}"
+    );
+
+    assert!(
+        dx(tmp.path(), &["ignore", "setup", "--artifacts"])
+            .status
+            .success()
+    );
+    let output = dx(tmp.path(), &["hook", "gitignore"]);
+    assert!(output.status.success(), "{output:?}");
+}
+
+#[test]
+fn governance_hook_runs_the_policies() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let policies = tmp.path().join(".dx/policies");
+    fs::create_dir_all(&policies).unwrap();
+    fs::write(policies.join("sempre-falha.cel"), "false\n").unwrap();
+
+    let output = dx(tmp.path(), &["hook", "governance"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("sempre-falha"), "{stdout}");
+}
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+
+mod common;
+
+#[test]
+fn terraform_resources_become_local_services() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let infra = tmp.path().join("infra");
+    fs::create_dir(&infra).unwrap();
+    fs::write(
+        infra.join("main.tf"),
+        r#"resource "aws_db_instance" "main" {
+  engine         = "postgres"
+  engine_version = "16.3"
+}
+
+resource "aws_elasticache_cluster" "sessions" {
+  engine = "memcached"
+}
+
+module "events" {
+  source = "terraform-aws-modules/msk-kafka-cluster/aws"
+}
+
+resource "aws_s3_bucket" "uploads" {
+  bucket = "uploads-${var.env}"
+}
+"#,
+    )
+    .unwrap();
+
+    let output = common::run_in(tmp.path(), &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("- aws_db_instance (infra/main.tf) -> postgres"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("aws_elasticache_cluster"), "{stdout}");
+
+    let compose = fs::read_to_string(tmp.path().join(".dx/docker-compose.yml")).unwrap();
+    for service in ["postgres:", "kafka:", "minio:"] {
+        assert!(compose.contains(service), "sem {service}:\n{compose}");
+    }
+    assert!(!compose.contains("redis:"), "{compose}");
+    assert!(
+        compose.contains("server /data --console-address :9001"),
+        "{compose}"
+    );
+    let report = fs::read_to_string(tmp.path().join(".dx/analyzer-report.md")).unwrap();
+    assert!(
+        report.contains("| `aws_s3_bucket` | infra/main.tf | minio |"),
+        "{report}"
+    );
+}
+
+#[test]
+fn cloudformation_and_cdk_resources_are_mapped() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("template.yaml"),
+        "AWSTemplateFormatVersion: \"2010-09-09\"\nResources:\n  Db:\n    Type: AWS::RDS::DBCluster\n    Properties:\n      Engine: aurora-mysql\n  Policy:\n    Type: AWS::S3::BucketPolicy\n",
+    )
+    .unwrap();
+    let lib = tmp.path().join("lib");
+    fs::create_dir(&lib).unwrap();
+    fs::write(
+        lib.join("stack.ts"),
+        "import * as elasticache from 'aws-cdk-lib/aws-elasticache';\nnew elasticache.CfnReplicationGroup(this, 'Cache', {});\n",
+    )
+    .unwrap();
+    // Synthesized output is not scanned
+    fs::create_dir(tmp.path().join("cdk.out")).unwrap();
+    fs::write(
+        tmp.path().join("cdk.out/Stack.template.json"),
+        "{\"Resources\": {\"B\": {\"Type\": \"AWS::MSK::Cluster\"}}}\n",
+    )
+    .unwrap();
+
+    let output = common::run_in(tmp.path(), &["dev-services", "--no-save"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("- AWS::RDS::DBCluster (template.yaml) -> mysql"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("- elasticache.CfnReplicationGroup (lib/stack.ts) -> redis"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("minio"), "{stdout}");
+    assert!(!stdout.contains("kafka"), "{stdout}");
+}
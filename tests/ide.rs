@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+
+mod common;
+
+use serde_json::Value;
+
+fn vscode(dir: &Path, file: &str) -> Value {
+    let content = fs::read_to_string(dir.join(".vscode").join(file)).expect(file);
+    serde_json::from_str(&content).expect("valid JSON")
+}
+
+fn names(entries: &Value, key: &str) -> Vec<String> {
+    entries
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|e| e[key].as_str().unwrap_or_default().to_string())
+        .collect()
+}
+
+#[test]
+fn python_project_with_services_gets_tasks_debugging_and_env() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("requirements.txt"), "flask\n").unwrap();
+    fs::create_dir(tmp.path().join(".dx")).unwrap();
+    fs::write(
+        tmp.path().join(".dx/docker-compose.yml"),
+        "services:\n  redis:\n    image: redis:7\n    ports:\n      - \"6380:6379\"\n  api:\n    image: python:3.12\n    ports:\n      - \"5679:5678\"\n",
+    )
+    .unwrap();
+
+    let output = common::run_in(tmp.path(), &["ide", "generate"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let tasks = vscode(tmp.path(), "tasks.json");
+    assert_eq!(tasks["version"], "2.0.0");
+    let labels = names(&tasks["tasks"], "label");
+    for label in ["dx: test", "dx: lint", "dx: dev-services up", "dx: analyze"] {
+        assert!(labels.contains(&label.to_string()), "{labels:?}");
+    }
+
+    let launch = vscode(tmp.path(), "launch.json");
+    let configs = launch["configurations"].as_array().unwrap();
+    let attach = configs
+        .iter()
+        .find(|c| c["request"] == "attach")
+        .expect("attach configuration");
+    assert_eq!(attach["type"], "debugpy");
+    assert_eq!(attach["connect"]["port"], 5679);
+    assert!(
+        configs
+            .iter()
+            .any(|c| c["envFile"] == "${workspaceFolder}/.dx/.env")
+    );
+    let env = fs::read_to_string(tmp.path().join(".dx/.env")).unwrap();
+    assert!(env.contains("REDIS_URL=redis://localhost:6380"), "{env}");
+
+    let extensions = vscode(tmp.path(), "extensions.json");
+    let ids = extensions["recommendations"].as_array().unwrap();
+    assert!(ids.contains(&"ms-python.debugpy".into()), "{ids:?}");
+    assert!(
+        ids.contains(&"ms-azuretools.vscode-docker".into()),
+        "{ids:?}"
+    );
+}
+
+#[test]
+fn regeneration_keeps_entries_added_by_hand() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("go.mod"), "module demo\n\ngo 1.22\n").unwrap();
+    assert!(
+        common::run_in(tmp.path(), &["ide", "generate"])
+            .status
+            .success()
+    );
+
+    let path = tmp.path().join(".vscode/tasks.json");
+    let mut tasks = vscode(tmp.path(), "tasks.json");
+    tasks["tasks"]
+        .as_array_mut()
+        .unwrap()
+        .push(serde_json::json!({ "label": "minha tarefa", "type": "shell", "command": "make" }));
+    fs::write(&path, serde_json::to_string(&tasks).unwrap()).unwrap();
+    fs::write(
+        tmp.path().join(".vscode/extensions.json"),
+        "{\"recommendations\": [\"eamodio.gitlens\"]}",
+    )
+    .unwrap();
+
+    let output = common::run_in(tmp.path(), &["--json", "ide", "generate"]);
+    assert!(output.status.success(), "{output:?}");
+    let json: Value = serde_json::from_slice(&output.stdout).expect("JSON envelope");
+    assert_eq!(json["data"]["stack"], "go");
+    assert!(
+        !tmp.path().join(".dx").exists(),
+        "no dx environment to export"
+    );
+
+    let labels = names(&vscode(tmp.path(), "tasks.json")["tasks"], "label");
+    assert_eq!(labels[0], "minha tarefa", "{labels:?}");
+    assert_eq!(
+        labels.iter().filter(|l| *l == "dx: test").count(),
+        1,
+        "{labels:?}"
+    );
+    let ids = vscode(tmp.path(), "extensions.json")["recommendations"].clone();
+    assert_eq!(ids[0], "eamodio.gitlens");
+    assert!(ids.as_array().unwrap().contains(&"golang.go".into()));
+    let launch = vscode(tmp.path(), "launch.json");
+    assert!(names(&launch["configurations"], "name").contains(&"dx: attach :2345".to_string()));
+}
+
+#[test]
+fn commented_json_needs_force() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\n",
+    )
+    .unwrap();
+    fs::create_dir(tmp.path().join(".vscode")).unwrap();
+    let launch = tmp.path().join(".vscode/launch.json");
+    fs::write(&launch, "// meu launch\n{}\n").unwrap();
+
+    let output = common::run_in(tmp.path(), &["ide", "generate"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(!tmp.path().join(".vscode/tasks.json").exists());
+
+    let output = common::run_in(tmp.path(), &["ide", "generate", "--force"]);
+    assert!(output.status.success(), "{output:?}");
+    let configs = names(&vscode(tmp.path(), "launch.json")["configurations"], "name");
+    assert!(configs.contains(&"dx: launch".to_string()), "{configs:?}");
+}
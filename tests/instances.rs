@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Output;
+
+mod common;
+
+const MANIFEST: &str = "services:\n  postgres:\n    image: postgres:16-alpine\n    container_name: api-postgres\n    ports:\n      - '15432:5432'\n    volumes:\n      - ./init:/docker-entrypoint-initdb.d\n      - pgdata:/var/lib/postgresql/data\n  redis:\n    image: redis:7-alpine\n    ports:\n      - '127.0.0.1:16379:6379'\nvolumes:\n  pgdata: {}\n";
+
+/// Fake `docker` that records its arguments.
+const DOCKER: &str = "#!/bin/sh\necho \"$*\" >> \"$DOCKER_LOG\"\nexit 0\n";
+
+fn setup() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("api");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(project.join(".dx")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(project.join(".dx/docker-compose.yml"), MANIFEST).unwrap();
+    fs::write(bin.join("docker"), DOCKER).unwrap();
+    fs::set_permissions(bin.join("docker"), fs::Permissions::from_mode(0o755)).unwrap();
+    tmp
+}
+
+fn dx(tmp: &Path, args: &[&str]) -> Output {
+    common::with_fake_tools(tmp)
+        .args(args)
+        .current_dir(tmp.join("api"))
+        .output()
+        .expect("failed to run dx")
+}
+
+fn log(tmp: &Path) -> String {
+    fs::read_to_string(tmp.join("docker.log")).unwrap_or_default()
+}
+
+fn offset(project: &Path, name: &str) -> u64 {
+    let path = project.join(format!(".dx/instances/{name}.json"));
+    let record: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+    record["offset"].as_u64().unwrap()
+}
+
+#[test]
+fn run_instance_gets_its_own_project_and_shifted_ports() {
+    let tmp = setup();
+    let project = tmp.path().join("api");
+
+    let output = dx(
+        tmp.path(),
+        &["dev-services", "run", "--instance", "feature-x"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "Instância 'feature-x': projeto Compose 'api-feature-x', portas do manifesto +100\n  postgres: localhost:15532 -> 5432\n  redis: localhost:16479 -> 6379\n"
+        ),
+        "{stdout}"
+    );
+
+    let manifest = fs::read_to_string(project.join(".dx/instances/feature-x.yml")).unwrap();
+    assert!(manifest.contains("name: api-feature-x"), "{manifest}");
+    assert!(manifest.contains("15532:5432"), "{manifest}");
+    assert!(manifest.contains("127.0.0.1:16479:6379"), "{manifest}");
+    assert!(!manifest.contains("container_name"), "{manifest}");
+    let init = project.join(".dx/init");
+    assert!(
+        manifest.contains(&format!("{}:/docker-entrypoint-initdb.d", init.display())),
+        "{manifest}"
+    );
+    assert!(
+        manifest.contains("pgdata:/var/lib/postgresql/data"),
+        "{manifest}"
+    );
+    assert_eq!(offset(&project, "feature-x"), 100);
+    assert!(
+        log(tmp.path()).contains("/.dx/instances/feature-x.yml up -d"),
+        "{}",
+        log(tmp.path())
+    );
+    // The default manifest is untouched
+    assert_eq!(
+        fs::read_to_string(project.join(".dx/docker-compose.yml")).unwrap(),
+        MANIFEST
+    );
+
+    // A second instance takes the next offset; reruns keep theirs
+    let output = dx(tmp.path(), &["dev-services", "run", "--instance", "hotfix"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(offset(&project, "hotfix"), 200);
+    let output = dx(
+        tmp.path(),
+        &["dev-services", "run", "--instance", "feature-x"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(offset(&project, "feature-x"), 100);
+
+    let output = dx(
+        tmp.path(),
+        &["dev-services", "stop", "--instance", "hotfix"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        log(tmp.path()).contains("/.dx/instances/hotfix.yml stop"),
+        "{}",
+        log(tmp.path())
+    );
+
+    // Removing an instance releases its offset
+    let output = dx(
+        tmp.path(),
+        &["dev-services", "remove", "--instance", "feature-x"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        log(tmp.path()).contains("/.dx/instances/feature-x.yml down -v"),
+        "{}",
+        log(tmp.path())
+    );
+    assert!(!project.join(".dx/instances/feature-x.json").exists());
+    assert!(!project.join(".dx/instances/feature-x.yml").exists());
+    let output = dx(tmp.path(), &["dev-services", "run", "--instance", "docs"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(offset(&project, "docs"), 100);
+}
+
+#[test]
+fn instance_names_are_validated_and_must_exist_to_stop() {
+    let tmp = setup();
+    let project = tmp.path().join("api");
+
+    let output = dx(
+        tmp.path(),
+        &["dev-services", "run", "--instance", "Feature X"],
+    );
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Nome de instância inválido: 'Feature X'"),
+        "{stderr}"
+    );
+    assert!(!project.join(".dx/instances").exists());
+
+    let output = dx(
+        tmp.path(),
+        &["dev-services", "stop", "--instance", "feature-x"],
+    );
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("dx dev-services run --instance feature-x"),
+        "{stderr}"
+    );
+    assert!(log(tmp.path()).is_empty(), "{}", log(tmp.path()));
+}
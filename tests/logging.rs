@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::process::{Command, Output};
+
+fn dev_services(project: &std::path::Path, flags: &[&str], dx_log: Option<&str>) -> Output {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_dx"));
+    cmd.args(flags)
+        .args(["dev-services", "--no-save"])
+        .arg(project)
+        .env_remove("DX_LOG");
+    if let Some(level) = dx_log {
+        cmd.env("DX_LOG", level);
+    }
+    cmd.output().expect("failed to run dx dev-services")
+}
+
+fn project_with_redis() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[dependencies]\nredis = \"0.25\"\n",
+    )
+    .unwrap();
+    tmp
+}
+
+#[test]
+fn detection_evidence_is_logged_to_stderr_only_when_verbose() {
+    let project = project_with_redis();
+
+    let quiet = dev_services(project.path(), &[], None);
+    assert!(quiet.status.success());
+    assert!(!String::from_utf8_lossy(&quiet.stderr).contains("dependência detectada"));
+
+    // The first run cached the detection; evidence is only logged on a rescan
+    let verbose = dev_services(project.path(), &["-vv", "--no-cache"], None);
+    assert!(verbose.status.success());
+    let stderr = String::from_utf8_lossy(&verbose.stderr);
+    assert!(stderr.contains("dependência detectada"), "{}", stderr);
+    assert!(stderr.contains("Cargo.toml"), "{}", stderr);
+    assert!(!String::from_utf8_lossy(&verbose.stdout).contains("dependência detectada"));
+}
+
+#[test]
+fn dx_log_env_overrides_verbosity_flags() {
+    let project = project_with_redis();
+    let output = dev_services(project.path(), &["--quiet"], Some("dx=debug"));
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("dependência detectada"));
+}
+
+#[test]
+fn verbose_and_quiet_conflict() {
+    let project = project_with_redis();
+    let output = dev_services(project.path(), &["-v", "--quiet"], None);
+    assert!(!output.status.success());
+}
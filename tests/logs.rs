@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+
+mod common;
+
+const MANIFEST: &str = "services:\n  postgres:\n    image: postgres:16-alpine\n  kafka-ui:\n    image: provectuslabs/kafka-ui:latest\n    profiles:\n    - messaging\n";
+
+/// Compose V2: records its arguments and prints prefixed lines.
+const DOCKER: &str = r#"#!/bin/sh
+echo "$@ profiles=$COMPOSE_PROFILES" > "$(dirname "$0")/../docker.log"
+echo "postgres-1  | database system is ready"
+echo "kafka-ui-2  | started | port 8080"
+echo "no prefix here"
+"#;
+
+/// Compose V1 only: `docker compose` doesn't exist.
+const DOCKER_V1: &str =
+    "#!/bin/sh\necho \"docker: 'compose' is not a docker command.\" >&2\nexit 1\n";
+const DOCKER_COMPOSE: &str = r#"#!/bin/sh
+echo "$@" > "$(dirname "$0")/../docker-compose.log"
+echo "project_kafka-ui_1  | up"
+"#;
+
+fn setup(scripts: &[(&str, &str)]) -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(project.join(".dx")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(project.join(".dx/docker-compose.yml"), MANIFEST).unwrap();
+    for (name, script) in scripts {
+        fs::write(bin.join(name), script).unwrap();
+        fs::set_permissions(bin.join(name), fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    tmp
+}
+
+#[test]
+fn logs_are_prefixed_with_the_service_name() {
+    let tmp = setup(&[("docker", DOCKER)]);
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &[
+            "dev-services",
+            "logs",
+            "kafka-ui",
+            "--follow",
+            "--tail",
+            "50",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "postgres | database system is ready\nkafka-ui-2 | started | port 8080\nno prefix here\n"
+    );
+    let args = fs::read_to_string(tmp.path().join("docker.log")).unwrap();
+    assert!(
+        args.ends_with(
+            "/.dx/docker-compose.yml logs --no-color --follow --tail 50 kafka-ui profiles=messaging\n"
+        ),
+        "{args}"
+    );
+}
+
+#[test]
+fn logs_fall_back_to_docker_compose_v1() {
+    let tmp = setup(&[("docker", DOCKER_V1), ("docker-compose", DOCKER_COMPOSE)]);
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "logs"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "kafka-ui | up\n");
+    let args = fs::read_to_string(tmp.path().join("docker-compose.log")).unwrap();
+    assert!(args.ends_with("logs --no-color\n"), "{args}");
+}
+
+#[test]
+fn logs_reject_unknown_services_and_bad_tails() {
+    let tmp = setup(&[("docker", DOCKER)]);
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "logs", "redis"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Serviço desconhecido: 'redis'. Disponíveis: postgres, kafka-ui"),
+        "{output:?}"
+    );
+
+    let output =
+        common::run_with_fake_tools(tmp.path(), &["dev-services", "logs", "--tail", "many"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(!tmp.path().join("docker.log").exists());
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::{Duration, Instant};
+
+fn node_project(dx_toml: &str) -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("package.json"),
+        "{\n  \"devDependencies\": {\n    \"eslint\": \"1.0.0\"\n  }\n}\n",
+    )
+    .unwrap();
+    fs::write(tmp.path().join("dx.toml"), dx_toml).unwrap();
+    tmp
+}
+
+fn dx_update(dir: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .env("NO_PROXY", "*")
+        .args(["dev-dependencies", "update", "eslint"])
+        .current_dir(dir)
+        .output()
+        .expect("run update")
+}
+
+#[test]
+fn requests_identify_dx_and_honor_the_timeout() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    // Read the request and never answer
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0u8; 2048];
+        let n = stream.read(&mut buf).unwrap();
+        let request = String::from_utf8_lossy(&buf[..n]).to_string();
+        std::thread::sleep(Duration::from_secs(3));
+        let _ = stream.write_all(b"HTTP/1.1 500 Internal Server Error\r\n\r\n");
+        request
+    });
+
+    let tmp = node_project(&format!(
+        "[registries]\nnpm = \"http://{addr}\"\n\n[network]\ntimeout = 1\n"
+    ));
+    let start = Instant::now();
+    let output = dx_update(tmp.path());
+    let elapsed = start.elapsed();
+
+    // A failed lookup keeps the current version
+    assert!(output.status.success(), "{output:?}");
+    assert!(elapsed < Duration::from_secs(3), "took {elapsed:?}");
+    let pkg = fs::read_to_string(tmp.path().join("package.json")).unwrap();
+    assert!(pkg.contains("1.0.0"), "{pkg}");
+
+    let request = server.join().unwrap().to_lowercase();
+    assert!(request.contains("user-agent: dx-cli/"), "{request}");
+}
+
+#[test]
+fn invalid_network_settings_are_rejected() {
+    let tmp = node_project("[network]\nconcurrency = \"many\"\n");
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", tmp.path().join("user-dx.toml"))
+        .args([
+            "governance",
+            "sync",
+            "--source",
+            "http://127.0.0.1:9/bundle.json",
+        ])
+        .current_dir(tmp.path())
+        .output()
+        .expect("run sync");
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+}
+
+#[test]
+fn registry_lookups_overlap() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    // Hold the first request until the second one arrives (or 5s pass)
+    let server = std::thread::spawn(move || {
+        let mut streams = vec![listener.accept().unwrap().0];
+        listener.set_nonblocking(true).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while streams.len() < 2 && Instant::now() < deadline {
+            match listener.accept() {
+                Ok((stream, _)) => streams.push(stream),
+                Err(_) => std::thread::sleep(Duration::from_millis(20)),
+            }
+        }
+        let overlapped = streams.len() == 2;
+        listener.set_nonblocking(false).unwrap();
+        for _ in streams.len()..2 {
+            streams.push(listener.accept().unwrap().0);
+        }
+        for mut stream in streams {
+            stream.set_nonblocking(false).unwrap();
+            let mut buf = [0u8; 2048];
+            let _ = stream.read(&mut buf).unwrap();
+            let body = r#"{"version":"9.0.0"}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        }
+        overlapped
+    });
+
+    let tmp = node_project(&format!(
+        "[registries]\nnpm = \"http://{addr}\"\n\n[network]\ntimeout = 10\nrate_limit = 0\n"
+    ));
+    fs::write(
+        tmp.path().join("package.json"),
+        "{\n  \"devDependencies\": {\n    \"eslint\": \"1.0.0\",\n    \"prettier\": \"1.0.0\"\n  }\n}\n",
+    )
+    .unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", tmp.path().join("user-dx.toml"))
+        .env("XDG_CACHE_HOME", tmp.path().join("cache"))
+        .env("NO_PROXY", "*")
+        .args(["dev-dependencies", "update"])
+        .current_dir(tmp.path())
+        .output()
+        .expect("run update");
+    assert!(output.status.success(), "{output:?}");
+    assert!(server.join().unwrap(), "lookups ran one after the other");
+    let pkg = fs::read_to_string(tmp.path().join("package.json")).unwrap();
+    assert_eq!(pkg.matches("\"9.0.0\"").count(), 2, "{pkg}");
+}
+
+#[test]
+fn offline_skips_registry_lookups() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let tmp = node_project(&format!("[registries]\nnpm = \"http://{addr}\"\n"));
+    let dx = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_dx"))
+            .env("DX_CONFIG", tmp.path().join("user-dx.toml"))
+            .env("XDG_CACHE_HOME", tmp.path().join("cache"))
+            .env("NO_PROXY", "*")
+            .args(args)
+            .arg(tmp.path())
+            .output()
+            .expect("run dx")
+    };
+
+    let output = dx(&["--offline", "analyzer"]);
+    assert!(output.status.success(), "{output:?}");
+    let report = fs::read_to_string(tmp.path().join(".dx/analyzer-report.md")).unwrap();
+    assert!(
+        report.contains("| [eslint](https://www.npmjs.com/package/eslint) | 1.0.0 | - |"),
+        "{report}"
+    );
+    let output = dx(&["--offline", "dev-dependencies", "update"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+
+    listener.set_nonblocking(true).unwrap();
+    assert!(listener.accept().is_err(), "a registry was queried");
+}
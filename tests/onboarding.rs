@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+mod common;
+
+fn dx(dir: &Path, args: &[&str]) -> Output {
+    common::dx(dir)
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run dx")
+}
+
+#[test]
+fn bare_dx_suggests_the_next_commands_for_the_project() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(
+        dir.join("package.json"),
+        r#"{"dependencies": {"pg": "^8.11", "redis": "^4.6"}}"#,
+    )
+    .unwrap();
+
+    let output = dx(dir, &[]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("(node)\n"), "{stdout}");
+    assert!(
+        stdout.contains("Manifesto dos Dev Services: não gerado"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains(
+            "  1. dx dev-services  gera .dx/docker-compose.yml com postgres e redis\n  2. dx dev-test      roda os testes a cada mudança nos arquivos\n  3. dx ci generate   cria o pipeline de CI para node\n"
+        ),
+        "{stdout}"
+    );
+    // The detection pass is cached like the other commands'
+    assert!(dir.join(".dx/cache/detection.json").is_file());
+
+    // With the manifest, a pinned toolchain and a CI workflow
+    let output = dx(dir, &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    fs::write(dir.join(".nvmrc"), "20\n").unwrap();
+    fs::create_dir_all(dir.join(".github/workflows")).unwrap();
+    let output = dx(dir, &["--json"]);
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let data = &json["data"];
+    assert_eq!(data["stack"], "node");
+    assert_eq!(data["manifest"], true);
+    assert_eq!(data["services"], serde_json::json!(["postgres", "redis"]));
+    let commands: Vec<&str> = data["suggestions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["command"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        commands,
+        ["dx dev-services run", "dx doctor", "dx dev-test"]
+    );
+}
+
+#[test]
+fn bare_dx_outside_a_project_points_to_the_subprojects() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    for name in ["api", "web"] {
+        fs::create_dir_all(dir.join(name)).unwrap();
+    }
+    fs::write(dir.join("api/go.mod"), "module api\n\ngo 1.22\n").unwrap();
+    fs::write(dir.join("web/package.json"), "{}").unwrap();
+
+    let output = dx(dir, &[]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Nenhum projeto reconhecido em"), "{stdout}");
+    assert!(stdout.contains("Subprojetos: api, web"), "{stdout}");
+    assert!(
+        stdout.contains("  1. dx all analyzer  analisa os 2 subprojetos em paralelo"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("  2. cd api && dx"), "{stdout}");
+    assert!(!dir.join(".dx").exists());
+}
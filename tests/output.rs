@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::process::Command;
+
+fn run_json(args: &[&str], dir: &std::path::Path) -> (bool, serde_json::Value) {
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .args(args)
+        .arg(dir)
+        .output()
+        .expect("failed to run dx");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = serde_json::from_str(&stdout)
+        .unwrap_or_else(|e| panic!("stdout is not JSON ({e}): {stdout}"));
+    (output.status.success(), json)
+}
+
+#[test]
+fn json_output_wraps_command_result_in_envelope() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::create_dir_all(tmp.path().join("api").join(".dx")).unwrap();
+
+    let (ok, json) = run_json(&["--output", "json", "clean", "--dry-run"], tmp.path());
+    assert!(ok);
+    assert_eq!(json["schema_version"], 1);
+    assert_eq!(json["command"], "clean");
+    assert_eq!(json["ok"], true);
+    assert_eq!(json["data"]["dry_run"], true);
+    assert_eq!(json["data"]["folders"].as_array().unwrap().len(), 1);
+    assert!(tmp.path().join("api").join(".dx").exists());
+}
+
+#[test]
+fn json_output_reports_failures() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let policies = tmp.path().join(".dx").join("policies");
+    fs::create_dir_all(&policies).unwrap();
+    fs::write(policies.join("never.cel"), "false\n").unwrap();
+
+    let (ok, json) = run_json(&["governance", "check", "--json"], tmp.path());
+    assert!(!ok);
+    assert_eq!(json["command"], "governance check");
+    assert_eq!(json["ok"], false);
+    assert_eq!(json["data"]["policies"][0]["name"], "never");
+    assert_eq!(json["data"]["policies"][0]["outcome"], "failed");
+}
+
+#[test]
+fn json_output_for_commands_without_structured_data() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .args(["portal", "--json"])
+        .current_dir(tmp.path())
+        .output()
+        .expect("failed to run dx");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["command"], "portal");
+    assert!(json["data"].is_null());
+    // Human-readable text moves to stderr
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Portal do Dev"));
+}
+
+#[test]
+fn json_output_for_listings_and_detection() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("package.json"),
+        r#"{"dependencies": {"pg": "^8.11"}, "devDependencies": {"jest": "^29.0"}}"#,
+    )
+    .unwrap();
+    fs::create_dir_all(tmp.path().join(".dx")).unwrap();
+    fs::write(
+        tmp.path().join(".dx/config.json"),
+        r#"{"LOG_LEVEL": "debug"}"#,
+    )
+    .unwrap();
+
+    let (ok, json) = run_json(&["--json", "dev-dependencies"], tmp.path());
+    assert!(ok, "{json}");
+    assert_eq!(json["command"], "dev-dependencies");
+    assert_eq!(json["data"]["stack"], "node");
+    assert_eq!(json["data"]["dependencies"][0]["name"], "jest");
+    assert_eq!(json["data"]["dependencies"][0]["version"], "^29.0");
+
+    let (ok, json) = run_json(&["--json", "dev-config"], tmp.path());
+    assert!(ok, "{json}");
+    assert_eq!(json["data"]["stack"], "Node.js");
+    assert_eq!(json["data"]["entries"]["LOG_LEVEL"], "debug");
+
+    let (ok, json) = run_json(
+        &["--json", "--no-cache", "dev-services", "--no-save"],
+        tmp.path(),
+    );
+    assert!(ok, "{json}");
+    let postgres = &json["data"]["services"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["name"] == "postgres")
+        .unwrap_or_else(|| panic!("{json}"));
+    assert_eq!(postgres["ports"][0]["host"], 5432);
+    assert!(json["data"]["compose"].is_null());
+
+    let (ok, json) = run_json(&["--json", "--no-cache", "analyzer"], tmp.path());
+    assert!(ok, "{json}");
+    assert!(
+        json["data"]["report"]
+            .as_str()
+            .unwrap()
+            .ends_with("analyzer-report.md"),
+        "{json}"
+    );
+    assert!(!json["data"]["services"].as_array().unwrap().is_empty());
+}
@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod common;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .output()
+        .expect("failed to run git");
+    assert!(output.status.success(), "git {args:?}: {output:?}");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn dx(dir: &Path, args: &[&str]) -> Output {
+    common::dx(dir)
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .args(args)
+        .arg(dir)
+        .output()
+        .expect("failed to run dx")
+}
+
+fn project(dir: &Path) {
+    fs::write(
+        dir.join("package.json"),
+        r#"{"dependencies": {"pg": "^8.11"}}"#,
+    )
+    .unwrap();
+    fs::create_dir_all(dir.join("coverage")).unwrap();
+    fs::write(
+        dir.join("coverage/lcov.info"),
+        "SF:src/a.js\nLF:6\nLH:6\nend_of_record\nSF:src/b.js\nLF:4\nLH:2\nend_of_record\n",
+    )
+    .unwrap();
+    fs::create_dir_all(dir.join("site/guide")).unwrap();
+    fs::write(dir.join("site/index.html"), "<h1>Docs</h1>\n").unwrap();
+    fs::write(dir.join("site/guide/index.html"), "<h1>Guia</h1>\n").unwrap();
+}
+
+#[test]
+fn dry_run_builds_the_dashboard_in_dx_pages() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    project(dir);
+
+    let output = dx(dir, &["publish", "pages", "--dry-run"]);
+    assert!(output.status.success(), "{output:?}");
+    let site = dir.join(".dx/pages");
+    let index = fs::read_to_string(site.join("index.html")).unwrap();
+    assert!(
+        index.contains("![coverage](badges/coverage.svg)"),
+        "{index}"
+    );
+    assert!(
+        index.contains("[Relatório do dx (analyzer)](report.html)"),
+        "{index}"
+    );
+    assert!(index.contains("[Documentação](docs/index.html)"), "{index}");
+    assert!(index.contains("img.shields.io/badge/PostgreSQL"), "{index}");
+    assert!(
+        fs::read_to_string(site.join("badges/coverage.json"))
+            .unwrap()
+            .contains(r#""message": "80.0%""#)
+    );
+    assert!(
+        fs::read_to_string(site.join("badges/coverage.svg"))
+            .unwrap()
+            .contains(r##"fill="#97ca00""##)
+    );
+    assert!(
+        fs::read_to_string(site.join("report.md"))
+            .unwrap()
+            .contains("postgres")
+    );
+    assert!(site.join("docs/guide/index.html").exists());
+    assert!(site.join(".nojekyll").exists());
+}
+
+#[test]
+fn publish_commits_the_site_on_the_pages_branch() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let remote = tmp.path().join("remote.git");
+    let dir = tmp.path().join("app");
+    fs::create_dir_all(&dir).unwrap();
+    git(
+        tmp.path(),
+        &["init", "--quiet", "--bare", remote.to_str().unwrap()],
+    );
+    git(&dir, &["init", "--quiet", "-b", "main"]);
+    git(&dir, &["config", "user.name", "Dev"]);
+    git(&dir, &["config", "user.email", "dev@example.com"]);
+    project(&dir);
+    fs::write(dir.join(".gitignore"), ".dx\n").unwrap();
+    git(&dir, &["add", "-A"]);
+    git(&dir, &["commit", "--quiet", "-m", "initial"]);
+    git(&dir, &["remote", "add", "origin", remote.to_str().unwrap()]);
+
+    let output = dx(&dir, &["publish", "pages"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Publicado em 'gh-pages' de 'origin'"),
+        "{output:?}"
+    );
+    let files = git(&remote, &["ls-tree", "-r", "--name-only", "gh-pages"]);
+    for file in [
+        ".nojekyll",
+        "badges/coverage.svg",
+        "docs/index.html",
+        "index.html",
+        "report.html",
+    ] {
+        assert!(files.lines().any(|f| f == file), "{file}: {files}");
+    }
+    let log = git(&remote, &["log", "--format=%an %s", "gh-pages"]);
+    assert!(log.starts_with("Dev dx publish pages ("), "{log}");
+    // The project's own branch and working tree are untouched
+    assert_eq!(git(&dir, &["status", "--porcelain"]), "");
+    assert_eq!(git(&dir, &["branch", "--show-current"]).trim(), "main");
+
+    // A new coverage report is a new commit on top of the published one
+    fs::write(dir.join("coverage/lcov.info"), "LF:10\nLH:10\n").unwrap();
+    let output = dx(&dir, &["publish", "pages"]);
+    assert!(output.status.success(), "{output:?}");
+    let log = git(&remote, &["log", "--format=%s", "gh-pages"]);
+    assert_eq!(log.lines().count(), 2, "{log}");
+    let svg = git(&remote, &["show", "gh-pages:badges/coverage.svg"]);
+    assert!(svg.contains("100.0%"), "{svg}");
+
+    let output = dx(&dir, &["publish", "pages"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("já está atualizada"),
+        "{output:?}"
+    );
+}
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Gradle project in a directory whose path has a space.
+fn gradle_project(tmp: &Path) -> PathBuf {
+    let project = tmp.join("my project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("build.gradle"), "plugins { id 'java' }\n").unwrap();
+    project
+}
+
+fn dev_test(tmp: &Path, project: &Path) -> Output {
+    // Nothing but the project's wrapper can run the tests
+    let bin = tmp.join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", tmp.join("user-dx.toml"))
+        .env("PATH", &bin)
+        .args(["--ci", "dev-test"])
+        .current_dir(project)
+        .output()
+        .expect("failed to run dx")
+}
+
+#[cfg(unix)]
+#[test]
+fn dev_test_runs_the_gradle_wrapper_by_path() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = gradle_project(tmp.path());
+    let gradlew = project.join("gradlew");
+    fs::write(&gradlew, "#!/bin/sh\necho \"gradlew $*\" > ran.txt\n").unwrap();
+    fs::set_permissions(&gradlew, fs::Permissions::from_mode(0o755)).unwrap();
+    // The Windows wrapper is not run on Unix
+    fs::write(project.join("gradlew.bat"), "@echo off\r\nexit /b 1\r\n").unwrap();
+
+    let output = dev_test(tmp.path(), &project);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(&format!(
+            "> Executando testes: '{}' test",
+            gradlew.display()
+        )),
+        "{stdout}"
+    );
+    assert_eq!(
+        fs::read_to_string(project.join("ran.txt")).unwrap(),
+        "gradlew test\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn dev_test_ignores_the_batch_wrapper_on_unix() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = gradle_project(tmp.path());
+    fs::write(project.join("gradlew.bat"), "@echo off\r\n").unwrap();
+
+    let output = dev_test(tmp.path(), &project);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("> Executando testes: gradle test"), "{stdout}");
+    assert_eq!(output.status.code(), Some(7), "{output:?}");
+}
+
+#[cfg(windows)]
+#[test]
+fn dev_test_runs_gradlew_bat_on_windows() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = gradle_project(tmp.path());
+    let gradlew = project.join("gradlew.bat");
+    fs::write(&gradlew, "@echo off\r\necho gradlew %* > ran.txt\r\n").unwrap();
+
+    let output = dev_test(tmp.path(), &project);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(&format!(
+            "> Executando testes: \"{}\" test",
+            gradlew.display()
+        )),
+        "{stdout}"
+    );
+    assert!(
+        fs::read_to_string(project.join("ran.txt"))
+            .unwrap()
+            .contains("gradlew test")
+    );
+}
+
+#[cfg(windows)]
+#[test]
+fn dev_services_migrate_uses_the_windows_maven_wrapper() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("my project");
+    fs::create_dir_all(project.join(".dx")).unwrap();
+    fs::write(
+        project.join("pom.xml"),
+        "<project><build><plugins><plugin><artifactId>flyway-maven-plugin</artifactId></plugin></plugins></build></project>\n",
+    )
+    .unwrap();
+    fs::write(project.join("mvnw.cmd"), "@echo off\r\n").unwrap();
+    fs::write(
+        project.join(".dx/docker-compose.yml"),
+        "services:\n  postgres:\n    image: postgres:16-alpine\n    ports:\n      - '5432:5432'\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", tmp.path().join("user-dx.toml"))
+        .args(["dev-services", "migrate", "--dry-run"])
+        .current_dir(&project)
+        .output()
+        .expect("failed to run dx");
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("mvnw "), "{stdout}");
+}
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+fn write_script(path: &Path, body: &str) {
+    fs::write(path, format!("#!/bin/sh\n{body}\n")).unwrap();
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+fn path_with(dir: &Path) -> std::ffi::OsString {
+    let mut paths = vec![dir.to_path_buf()];
+    paths.extend(std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()));
+    std::env::join_paths(paths).unwrap()
+}
+
+fn dx(project: &Path, bin: &Path, args: &[&str]) -> std::process::Output {
+    common::dx(project)
+        .env("PATH", path_with(bin))
+        .args(args)
+        .current_dir(project)
+        .output()
+        .expect("failed to run dx")
+}
+
+fn setup() -> (tempfile::TempDir, PathBuf, PathBuf) {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(project.join(".dx")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    (tmp, project, bin)
+}
+
+#[test]
+fn path_plugin_receives_args_and_project_context() {
+    let (_tmp, project, bin) = setup();
+    fs::write(project.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+    write_script(
+        &bin.join("dx-hello"),
+        "echo \"args=$*\"\necho \"dir=$DX_DIR\"\ngrep -q Cargo.toml \"$DX_ANALYSIS_JSON\" && echo analysis-ok",
+    );
+
+    let output = dx(&project, &bin, &["hello", "a", "--flag"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("args=a --flag"), "{stdout}");
+    assert!(stdout.contains(&format!("dir={}", project.join(".dx").display())), "{stdout}");
+    assert!(stdout.contains("analysis-ok"), "{stdout}");
+}
+
+#[test]
+fn registry_plugin_takes_precedence_and_exit_code_is_forwarded() {
+    let (_tmp, project, bin) = setup();
+    write_script(&bin.join("dx-deploy-preview"), "echo from-path");
+    fs::create_dir_all(project.join("scripts")).unwrap();
+    write_script(&project.join("scripts/preview.sh"), "echo \"from-registry $*\"\nexit 42");
+    fs::write(
+        project.join(".dx/plugins.json"),
+        r#"{"deploy-preview": {"command": "scripts/preview.sh", "args": ["--env", "pr"], "description": "Preview"}}"#,
+    )
+    .unwrap();
+
+    let output = dx(&project, &bin, &["deploy-preview", "1"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(42), "{stdout}");
+    assert!(stdout.contains("from-registry --env pr 1"), "{stdout}");
+
+    let output = dx(&project, &bin, &["plugins"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("deploy-preview (registro: scripts/preview.sh) — Preview"), "{stdout}");
+}
+
+#[test]
+fn unknown_subcommand_is_invalid_input() {
+    let (_tmp, project, bin) = setup();
+    let output = dx(&project, &bin, &["no-such-plugin"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("dx-no-such-plugin"), "{stderr}");
+}
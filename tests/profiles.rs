@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+mod common;
+
+/// Fake `docker` that records the active profiles and its arguments.
+const DOCKER: &str =
+    "#!/bin/sh\necho \"COMPOSE_PROFILES=$COMPOSE_PROFILES $*\" >> \"$DOCKER_LOG\"\nexit 0\n";
+
+fn setup() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(
+        project.join("src/app.py"),
+        "import psycopg2\nimport redis\n",
+    )
+    .unwrap();
+    fs::write(bin.join("docker"), DOCKER).unwrap();
+    fs::set_permissions(bin.join("docker"), fs::Permissions::from_mode(0o755)).unwrap();
+    tmp
+}
+
+fn log(tmp: &Path) -> String {
+    fs::read_to_string(tmp.join("docker.log")).unwrap_or_default()
+}
+
+#[test]
+fn manifest_puts_services_and_telemetry_in_profiles() {
+    let tmp = setup();
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let compose = fs::read_to_string(tmp.path().join("project/.dx/docker-compose.yml")).unwrap();
+    assert!(
+        compose.contains("  postgres:\n    image: postgres:16-alpine\n    profiles:\n    - db\n"),
+        "{compose}"
+    );
+    assert!(
+        compose.contains("    profiles:\n    - cache\n"),
+        "{compose}"
+    );
+    assert!(
+        compose.contains(
+            "  grafana:\n    image: grafana/grafana:latest\n    profiles:\n    - observability\n"
+        ),
+        "{compose}"
+    );
+}
+
+#[test]
+fn run_activates_the_selected_profiles_and_stop_all_of_them() {
+    let tmp = setup();
+    assert!(
+        common::run_with_fake_tools(tmp.path(), &["dev-services"])
+            .status
+            .success()
+    );
+
+    let output =
+        common::run_with_fake_tools(tmp.path(), &["dev-services", "run", "--profile", "db"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        log(tmp.path()).starts_with("COMPOSE_PROFILES=db compose -f "),
+        "{}",
+        log(tmp.path())
+    );
+
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "stop"]);
+    assert!(output.status.success(), "{output:?}");
+    let stop = log(tmp.path());
+    let stop = stop.lines().last().unwrap();
+    assert!(
+        stop.starts_with("COMPOSE_PROFILES=cache,db,observability compose -f "),
+        "{stop}"
+    );
+    assert!(stop.ends_with(" stop"), "{stop}");
+
+    let output =
+        common::run_with_fake_tools(tmp.path(), &["dev-services", "run", "-p", "messaging"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Profile desconhecido: 'messaging'. Disponíveis: cache, db, observability"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+
+mod common;
+
+const CARGO_TOML: &str = r#"[package]
+name = "hello-cli"
+version = "1.2.3"
+description = "Diz olá"
+license = "MIT OR Apache-2.0"
+repository = "https://github.com/acme/hello-cli"
+authors = ["Acme <dev@acme.io>"]
+
+[[bin]]
+name = "hello"
+path = "src/main.rs"
+"#;
+
+fn crate_with_artifacts() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(dir.join("Cargo.toml"), CARGO_TOML).unwrap();
+    fs::create_dir_all(dir.join("dist")).unwrap();
+    fs::write(dir.join("dist/hello-linux-x86_64.tar.gz"), "abc").unwrap();
+    fs::write(
+        dir.join("dist/hello-macos-aarch64.tar.gz"),
+        "a".repeat(1000),
+    )
+    .unwrap();
+    fs::write(dir.join("dist/hello-windows-x86_64.exe"), "abc").unwrap();
+    fs::write(dir.join("dist/SHA256SUMS.txt"), "ignored").unwrap();
+    tmp
+}
+
+const SHA_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+const SHA_1000_A: &str = "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3";
+
+#[test]
+fn release_package_writes_homebrew_scoop_and_nfpm() {
+    let tmp = crate_with_artifacts();
+    let dir = tmp.path();
+
+    let output = common::run(dir, &["release", "package"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let formula = fs::read_to_string(dir.join("packaging/homebrew/hello-cli.rb")).unwrap();
+    assert!(formula.starts_with("# dx-cli:release:start\n"), "{formula}");
+    assert!(formula.contains("class HelloCli < Formula"), "{formula}");
+    assert!(
+        formula.contains("license any_of: [\"MIT\", \"Apache-2.0\"]"),
+        "{formula}"
+    );
+    assert!(
+        formula.contains(&format!(
+            "  on_macos do\n    on_arm do\n      url \"https://github.com/acme/hello-cli/releases/download/v1.2.3/hello-macos-aarch64.tar.gz\"\n      sha256 \"{SHA_1000_A}\"\n"
+        )),
+        "{formula}"
+    );
+    assert!(
+        formula.contains(&format!("sha256 \"{SHA_ABC}\"")),
+        "{formula}"
+    );
+    assert!(formula.contains("bin.install \"hello\""), "{formula}");
+
+    let scoop: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(dir.join("packaging/scoop/hello-cli.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(scoop["version"], "1.2.3");
+    assert_eq!(scoop["license"], "MIT|Apache-2.0");
+    assert_eq!(scoop["architecture"]["64bit"]["hash"], SHA_ABC);
+    assert_eq!(
+        scoop["architecture"]["64bit"]["bin"],
+        serde_json::json!([["hello-windows-x86_64.exe", "hello"]])
+    );
+
+    let nfpm = fs::read_to_string(dir.join("packaging/nfpm/hello-cli-amd64.yaml")).unwrap();
+    assert!(nfpm.contains("arch: amd64\n"), "{nfpm}");
+    assert!(
+        nfpm.contains("maintainer: \"Acme <dev@acme.io>\"\n"),
+        "{nfpm}"
+    );
+    assert!(
+        nfpm.contains("  - src: \"target/x86_64-unknown-linux-gnu/release/hello\"\n    dst: \"/usr/bin/hello\"\n"),
+        "{nfpm}"
+    );
+    assert!(!dir.join("packaging/nfpm/hello-cli-arm64.yaml").exists());
+
+    // Re-running keeps the files; a new artifact updates them
+    let output = common::run(dir, &["release", "package"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("já está atualizado"), "{stdout}");
+    fs::write(dir.join("dist/hello-linux-aarch64.tar.gz"), "abc").unwrap();
+    let output = common::run(dir, &["--json", "release", "package"]);
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["artifacts"].as_array().unwrap().len(), 4);
+    assert!(dir.join("packaging/nfpm/hello-cli-arm64.yaml").exists());
+}
+
+#[test]
+fn release_package_keeps_hand_written_files_and_needs_urls() {
+    let tmp = crate_with_artifacts();
+    let dir = tmp.path();
+    fs::create_dir_all(dir.join("packaging/scoop")).unwrap();
+    fs::write(
+        dir.join("packaging/scoop/hello-cli.json"),
+        "{\"version\": \"0.1\"}\n",
+    )
+    .unwrap();
+
+    let output = common::run(dir, &["release", "package"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let output = common::run(dir, &["release", "package", "--force"]);
+    assert!(output.status.success(), "{output:?}");
+
+    // Without a GitHub repository the download URL must be given
+    fs::write(
+        dir.join("Cargo.toml"),
+        CARGO_TOML.replace("repository = \"https://github.com/acme/hello-cli\"\n", ""),
+    )
+    .unwrap();
+    let output = common::run(dir, &["release", "package"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let output = common::run(
+        dir,
+        &[
+            "release",
+            "package",
+            "--base-url",
+            "https://downloads.acme.io/1.2.3",
+            "--dry-run",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("url \"https://downloads.acme.io/1.2.3/hello-linux-x86_64.tar.gz\""),
+        "{stdout}"
+    );
+
+    let output = common::run(
+        dir,
+        &[
+            "release",
+            "package",
+            "--base-url",
+            "https://downloads.acme.io/1.2.3",
+            "--artifacts",
+            "nada",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+}
@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+mod common;
+
+const MANIFEST: &str = "services:
+  prometheus:
+    image: prom/prometheus:latest
+    ports:
+      - '9090:9090'
+    volumes:
+      - ./telemetry/prometheus/prometheus.yml:/etc/prometheus/prometheus.yml:ro
+      - ./data/prometheus:/prometheus
+      - /srv/shared:/shared
+      - prom-data:/var/lib/prom
+
+volumes:
+  prom-data:
+";
+
+/// Fake `docker`: records the engine it was pointed at and its arguments;
+/// `context inspect` answers with the endpoint of every context.
+const DOCKER: &str = "#!/bin/sh
+echo \"[$DOCKER_HOST|$DOCKER_CONTEXT] $*\" >> \"$DOCKER_LOG\"
+case \"$1\" in
+  context) echo \"ssh://dev@build-box:22\" ;;
+esac
+exit 0
+";
+
+fn setup() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(project.join(".dx/telemetry/prometheus")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(
+        project.join("package.json"),
+        r#"{"dependencies": {"pg": "^8.11"}}"#,
+    )
+    .unwrap();
+    fs::write(project.join(".dx/docker-compose.yml"), MANIFEST).unwrap();
+    fs::write(
+        project.join(".dx/telemetry/prometheus/prometheus.yml"),
+        "scrape_configs: []\n",
+    )
+    .unwrap();
+    fs::write(project.join(".dx/analyzer-report.md"), "old\n").unwrap();
+    // Only the calls of each command, without the smoke check's
+    fs::write(
+        project.join("dx.toml"),
+        "[dev_services]\nsmoke_check = \"off\"\n",
+    )
+    .unwrap();
+    fs::write(bin.join("docker"), DOCKER).unwrap();
+    fs::set_permissions(bin.join("docker"), fs::Permissions::from_mode(0o755)).unwrap();
+    tmp
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_default()
+}
+
+#[test]
+fn remote_host_sends_files_as_configs_and_links_the_report_to_it() {
+    let tmp = setup();
+    let project = tmp.path().join("project");
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &["dev-services", "run", "--host", "ssh://dev@box"],
+    );
+    assert!(output.status.success(), "{output:?}");
+
+    let compose = read(&project.join(".dx/docker-compose.remote.yml"));
+    // Files of the project go inline; data folders become named volumes
+    assert!(
+        compose.contains(
+            "    configs:\n    - source: prometheus-etc-prometheus-prometheus.yml\n      target: /etc/prometheus/prometheus.yml\n"
+        ),
+        "{compose}"
+    );
+    assert!(
+        compose.contains(
+            "  prometheus-etc-prometheus-prometheus.yml:\n    content: |\n      scrape_configs: []\n"
+        ),
+        "{compose}"
+    );
+    assert!(
+        compose.contains("    - prometheus-prometheus:/prometheus\n"),
+        "{compose}"
+    );
+    assert!(
+        compose.contains("  prometheus-prometheus: null\n"),
+        "{compose}"
+    );
+    // Paths of the remote machine and named volumes are kept
+    assert!(compose.contains("    - /srv/shared:/shared\n"), "{compose}");
+    assert!(
+        compose.contains("    - prom-data:/var/lib/prom\n"),
+        "{compose}"
+    );
+    assert!(!compose.contains("./telemetry"), "{compose}");
+
+    let report = read(&project.join(".dx/analyzer-report.md"));
+    assert!(report.contains("(http://box:5432)"), "{report}");
+
+    // stop talks to the same engine
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "stop"]);
+    assert!(output.status.success(), "{output:?}");
+    let log = read(&tmp.path().join("docker.log"));
+    assert!(
+        log.lines()
+            .all(|l| l.starts_with("[ssh://dev@box|] compose ")),
+        "{log}"
+    );
+    assert!(
+        log.lines()
+            .next()
+            .unwrap()
+            .contains("/.dx/docker-compose.remote.yml up -d"),
+        "{log}"
+    );
+    assert!(log.lines().nth(1).unwrap().ends_with(" stop"), "{log}");
+
+    // Without the flags, back to the local engine
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "run"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!project.join(".dx/remote.json").exists());
+    let log = read(&tmp.path().join("docker.log"));
+    let last = log.lines().last().unwrap();
+    assert!(
+        last.starts_with("[|] compose -f ") && last.contains("/.dx/docker-compose.yml up -d"),
+        "{log}"
+    );
+}
+
+#[test]
+fn remote_manifest_is_not_reported_as_drift() {
+    let tmp = setup();
+    let project = tmp.path().join("project");
+    // A manifest generated by dx instead of the hand-written one
+    fs::remove_dir_all(project.join(".dx")).unwrap();
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &["dev-services", "run", "--host", "ssh://dev@box"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(project.join(".dx/docker-compose.remote.yml").is_file());
+
+    let output =
+        common::run_with_fake_tools(tmp.path(), &["--json", "governance", "verify-manifests"]);
+    assert!(output.status.success(), "{output:?}");
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        envelope["data"]["derived"],
+        serde_json::json!(["docker-compose.remote.yml"])
+    );
+}
+
+#[test]
+fn remote_context_links_the_report_to_its_endpoint() {
+    let tmp = setup();
+    let project = tmp.path().join("project");
+    let output =
+        common::run_with_fake_tools(tmp.path(), &["dev-services", "run", "--context", "build"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Docker remoto: contexto Docker 'build'"),
+        "{stdout}"
+    );
+    let report = read(&project.join(".dx/analyzer-report.md"));
+    assert!(report.contains("(http://build-box:5432)"), "{report}");
+    let log = read(&tmp.path().join("docker.log"));
+    assert!(
+        log.lines().last().unwrap().starts_with("[|build] compose "),
+        "{log}"
+    );
+}
+
+#[test]
+fn remote_host_must_be_a_docker_url() {
+    let tmp = setup();
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "run", "--host", "box"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("ssh://usuario@maquina"), "{stderr}");
+    assert!(!tmp.path().join("docker.log").exists());
+}
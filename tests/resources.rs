@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn analyzer_report_estimates_the_resource_footprint() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(
+        dir.join("requirements.txt"),
+        "psycopg2-binary==2.9\nredis==5.0\nkafka-python==2.0\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .args(["--no-cache", "dev-services"])
+        .arg(dir)
+        .output()
+        .expect("failed to run dx");
+    assert!(output.status.success(), "{output:?}");
+
+    let report = fs::read_to_string(dir.join(".dx/analyzer-report.md")).unwrap();
+    assert!(report.contains("### Recursos estimados"), "{report}");
+    // Redpanda's `--memory 512M --smp 1` replace the image defaults
+    assert!(
+        report.contains("| kafka | 512.0 MB | 1.00 | 650.0 MB | limite configurado |"),
+        "{report}"
+    );
+    assert!(
+        report.contains("| redis | 64.0 MB | 0.25 | 140.0 MB | padrão da imagem |"),
+        "{report}"
+    );
+    assert!(report.contains("| **Total** |"), "{report}");
+    assert!(report.contains("Máquina: memória "), "{report}");
+}
+
+#[test]
+fn policies_can_budget_the_footprint() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(dir.join("requirements.txt"), "psycopg2\nredis\n").unwrap();
+    let policies = dir.join(".dx/policies");
+    fs::create_dir_all(&policies).unwrap();
+    fs::write(
+        policies.join("memory-budget.cel"),
+        "// Dev Services devem caber em 1 GB\nresources.total.memory_mb <= 1024 && resources.services.exists(s, s.service == 'postgres' && s.memory_mb == 256)\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .args(["governance", "check"])
+        .arg(dir)
+        .output()
+        .expect("failed to run dx");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("[OK] memory-budget (cel)"), "{stdout}");
+}
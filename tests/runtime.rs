@@ -0,0 +1,329 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod common;
+
+fn script(bin: &Path, name: &str, content: &str) {
+    fs::write(bin.join(name), content).unwrap();
+    fs::set_permissions(bin.join(name), fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+/// `dx` with only `bin` in the PATH.
+fn dx(dir: &Path, bin: &Path, args: &[&str]) -> Output {
+    common::dx(dir)
+        .env("PATH", bin)
+        .args(args)
+        .arg(dir)
+        .output()
+        .expect("failed to run dx")
+}
+
+/// `dx dev-config add <key> <value>` in `dir`.
+fn dev_config(dir: &Path, key: &str, value: &str) {
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .args(["dev-config", "add", key, value])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run dx");
+    assert!(output.status.success(), "{output:?}");
+}
+
+#[test]
+fn podman_and_nerdctl_run_the_compose_commands() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(&dir).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(dir.join("requirements.txt"), "redis==5.0\n").unwrap();
+    let log = tmp.path().join("calls.log");
+    // `podman compose` has no provider: podman-compose takes over
+    script(
+        &bin,
+        "podman",
+        "#!/bin/sh\necho \"podman $*\" >> \"$CALLS\"\nexit 125\n",
+    );
+    script(
+        &bin,
+        "podman-compose",
+        "#!/bin/sh\necho \"podman-compose $*\" >> \"$CALLS\"\n",
+    );
+    script(
+        &bin,
+        "nerdctl",
+        "#!/bin/sh\necho \"nerdctl $*\" >> \"$CALLS\"\n",
+    );
+    let run = |args: &[&str]| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_dx"));
+        command
+            .env("DX_CONFIG", dir.join("user-dx.toml"))
+            .env("PATH", &bin)
+            .env("CALLS", &log)
+            .env_remove("CI")
+            .args(args)
+            .arg(&dir);
+        let output = command.output().expect("failed to run dx");
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    run(&["dev-services"]);
+    let manifest = dir.join(".dx/docker-compose.yml");
+
+    // Without docker in the PATH, Podman is picked
+    let stdout = run(&["dev-services", "stop"]);
+    assert!(
+        stdout.contains("Serviços parados com 'podman-compose'"),
+        "{stdout}"
+    );
+    let calls = fs::read_to_string(&log).unwrap();
+    assert_eq!(
+        calls,
+        format!(
+            "podman compose -f {0} stop\npodman-compose -f {0} stop\n",
+            manifest.display()
+        )
+    );
+
+    fs::remove_file(&log).unwrap();
+    run(&["dev-services", "restart", "--runtime", "nerdctl"]);
+    assert_eq!(
+        fs::read_to_string(&log).unwrap(),
+        format!("nerdctl compose -f {} restart\n", manifest.display())
+    );
+
+    // .dx/config.json picks it when there is no flag
+    fs::remove_file(&log).unwrap();
+    dev_config(&dir, "container.runtime", "nerdctl");
+    run(&["dev-services", "remove"]);
+    assert_eq!(
+        fs::read_to_string(&log).unwrap(),
+        format!("nerdctl compose -f {} down -v\n", manifest.display())
+    );
+}
+
+#[test]
+fn unknown_runtime_in_dev_config_is_invalid_input() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let bin = dir.join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(dir.join("requirements.txt"), "redis==5.0\n").unwrap();
+    assert!(dx(dir, &bin, &["dev-services"]).status.success());
+    dev_config(dir, "container.runtime", "lxc");
+
+    let output = dx(dir, &bin, &["dev-services", "stop"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("container.runtime inválido"),
+        "{output:?}"
+    );
+}
+
+#[test]
+fn snapshots_run_on_the_project_runtime() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(&dir).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(dir.join("requirements.txt"), "redis==5.0\n").unwrap();
+    let log = tmp.path().join("calls.log");
+    script(
+        &bin,
+        "nerdctl",
+        "#!/bin/sh\necho \"nerdctl $*\" >> \"$CALLS\"\ncase \"$*\" in *\" ps -q\"*) echo c0ffee ;; esac\n",
+    );
+    assert!(dx(&dir, &bin, &["dev-services"]).status.success());
+    dev_config(&dir, "container.runtime", "nerdctl");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .env("PATH", &bin)
+        .env("CALLS", &log)
+        .env_remove("CI")
+        .args(["dev-services", "snapshot", "create", "base"])
+        .current_dir(&dir)
+        .output()
+        .expect("failed to run dx");
+    assert!(output.status.success(), "{output:?}");
+    let calls = fs::read_to_string(&log).unwrap();
+    let manifest = dir.join(".dx/docker-compose.yml");
+    assert!(
+        calls.contains(&format!(
+            "nerdctl compose -f {} exec -T redis redis-cli SAVE\n",
+            manifest.display()
+        )),
+        "{calls}"
+    );
+    assert!(
+        calls.contains(&format!(
+            "nerdctl compose -f {} cp redis:/data/dump.rdb ",
+            manifest.display()
+        )),
+        "{calls}"
+    );
+}
+
+#[test]
+fn seed_runs_in_the_named_instance_with_the_runtime_flag() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(dir.join(".dx/seed")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(dir.join(".env"), "DATABASE_URL=postgres://localhost/app\n").unwrap();
+    fs::write(
+        dir.join(".dx/seed/01-users.sql"),
+        "INSERT INTO users VALUES (1);\n",
+    )
+    .unwrap();
+    let log = tmp.path().join("calls.log");
+    script(
+        &bin,
+        "podman",
+        "#!/bin/sh\necho \"podman $*\" >> \"$CALLS\"\ncase \"$*\" in *\" ps -q\"*) echo c0ffee ;; esac\nwhile read -r _; do :; done\n",
+    );
+    assert!(dx(&dir, &bin, &["dev-services"]).status.success());
+    let instance = dir.join(".dx/instances/feature-x.yml");
+    fs::create_dir_all(instance.parent().unwrap()).unwrap();
+    fs::copy(dir.join(".dx/docker-compose.yml"), &instance).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .env("PATH", &bin)
+        .env("CALLS", &log)
+        .env_remove("CI")
+        .args([
+            "dev-services",
+            "seed",
+            "--instance",
+            "feature-x",
+            "--runtime",
+            "podman",
+        ])
+        .current_dir(&dir)
+        .output()
+        .expect("failed to run dx");
+    assert!(output.status.success(), "{output:?}");
+    let calls = fs::read_to_string(&log).unwrap();
+    assert!(
+        calls.contains(&format!(
+            "podman compose -f {} exec -T -e PGPASSWORD=",
+            instance.display()
+        )),
+        "{calls}"
+    );
+    assert!(!calls.contains("docker-compose.yml"), "{calls}");
+}
+
+#[test]
+fn status_asks_the_project_runtime() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(&dir).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(dir.join("requirements.txt"), "redis==5.0\n").unwrap();
+    let log = tmp.path().join("calls.log");
+    script(
+        &bin,
+        "nerdctl",
+        "#!/bin/sh\necho \"nerdctl $*\" >> \"$CALLS\"\necho '[]'\n",
+    );
+    assert!(dx(&dir, &bin, &["dev-services"]).status.success());
+    dev_config(&dir, "container.runtime", "nerdctl");
+
+    Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", dir.join("user-dx.toml"))
+        .env("PATH", &bin)
+        .env("CALLS", &log)
+        .env_remove("CI")
+        .args(["dev-services", "status"])
+        .arg(&dir)
+        .output()
+        .expect("failed to run dx");
+    assert_eq!(
+        fs::read_to_string(&log).unwrap(),
+        format!(
+            "nerdctl compose -f {} ps --all --format json\n",
+            dir.join(".dx/docker-compose.yml").display()
+        )
+    );
+}
+
+#[test]
+fn status_scale_and_snapshots_take_the_runtime_flag() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(&dir).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(dir.join("requirements.txt"), "redis==5.0\n").unwrap();
+    let log = tmp.path().join("calls.log");
+    // Both installed: without the flag, docker would be picked
+    for runtime in ["docker", "nerdctl"] {
+        script(
+            &bin,
+            runtime,
+            &format!(
+                "#!/bin/sh\necho \"{runtime} $*\" >> \"$CALLS\"\ncase \"$*\" in *\" ps -q\"*) echo c0ffee ;; *\" ps \"*) echo '[]' ;; *\" cp redis:\"*) for a; do :; done; : > \"$a\" ;; esac\n"
+            ),
+        );
+    }
+    assert!(dx(&dir, &bin, &["dev-services"]).status.success());
+    let run = |args: &[&str]| {
+        let output = common::dx(&dir)
+            .env("PATH", &bin)
+            .env("CALLS", &log)
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .expect("failed to run dx");
+        assert!(output.status.success(), "{output:?}");
+    };
+    run(&["dev-services", "status", "--runtime", "nerdctl"]);
+    run(&["dev-services", "scale", "redis=1", "--runtime", "nerdctl"]);
+    run(&[
+        "dev-services",
+        "snapshot",
+        "create",
+        "base",
+        "-s",
+        "redis",
+        "--runtime",
+        "nerdctl",
+    ]);
+    run(&[
+        "dev-services",
+        "snapshot",
+        "restore",
+        "base",
+        "-s",
+        "redis",
+        "--runtime",
+        "nerdctl",
+    ]);
+
+    let calls = fs::read_to_string(&log).unwrap();
+    assert!(!calls.contains("docker "), "{calls}");
+    for call in [
+        " ps --all --format json",
+        " up -d --no-deps --scale redis=1 redis",
+        " exec -T redis redis-cli SAVE",
+        " cp redis:/data/dump.rdb ",
+    ] {
+        assert!(
+            calls
+                .lines()
+                .any(|l| l.starts_with("nerdctl compose -f ") && l.contains(call)),
+            "{call}: {calls}"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+mod common;
+
+/// Fake `docker`: records its arguments.
+const DOCKER: &str = "#!/bin/sh
+echo \"$*\" >> \"$DOCKER_LOG\"
+exit 0
+";
+
+const OVERRIDE: &str = "# Local workers
+services:
+  worker:
+    # Consumes the orders topic
+    image: busybox:1.36
+    command: sleep infinity
+";
+
+fn setup() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(project.join(".dx")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(
+        project.join("package.json"),
+        r#"{"dependencies": {"pg": "^8.11"}}"#,
+    )
+    .unwrap();
+    fs::write(project.join(".dx/docker-compose.override.yml"), OVERRIDE).unwrap();
+    fs::write(bin.join("docker"), DOCKER).unwrap();
+    fs::set_permissions(bin.join("docker"), fs::Permissions::from_mode(0o755)).unwrap();
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    tmp
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_default()
+}
+
+#[test]
+fn scale_persists_into_the_override_and_applies_it() {
+    let tmp = setup();
+    let project = tmp.path().join("project");
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "scale", "worker=3"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let overrides = read(&project.join(".dx/docker-compose.override.yml"));
+    assert_eq!(
+        overrides,
+        "# Local workers\nservices:\n  worker:\n    scale: 3\n    # Consumes the orders topic\n    image: busybox:1.36\n    command: sleep infinity\n"
+    );
+    let manifest = read(&project.join(".dx/docker-compose.yml"));
+    assert!(manifest.contains("scale: 3"), "{manifest}");
+    let log = read(&tmp.path().join("docker.log"));
+    let last = log.lines().last().unwrap();
+    assert!(
+        last.contains("/.dx/docker-compose.yml up -d --no-deps --scale worker=3 worker"),
+        "{log}"
+    );
+
+    // Scaling again replaces the value; regenerating keeps it
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "scale", "worker=1"]);
+    assert!(output.status.success(), "{output:?}");
+    let overrides = read(&project.join(".dx/docker-compose.override.yml"));
+    assert!(overrides.contains("    scale: 1\n"), "{overrides}");
+    assert!(!overrides.contains("scale: 3"), "{overrides}");
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    let manifest = read(&project.join(".dx/docker-compose.yml"));
+    assert!(manifest.contains("scale: 1"), "{manifest}");
+}
+
+#[test]
+fn scale_adds_a_generated_service_to_the_override() {
+    let tmp = setup();
+    let project = tmp.path().join("project");
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "scale", "postgres=1"]);
+    assert!(output.status.success(), "{output:?}");
+    let overrides = read(&project.join(".dx/docker-compose.override.yml"));
+    assert!(
+        overrides.ends_with("    command: sleep infinity\n  postgres:\n    scale: 1\n"),
+        "{overrides}"
+    );
+}
+
+#[test]
+fn scale_refuses_fixed_host_ports_and_unknown_services() {
+    let tmp = setup();
+    let project = tmp.path().join("project");
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "scale", "postgres=2"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("porta 5432"), "{stderr}");
+
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "scale", "kafka=2"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Serviço desconhecido: 'kafka'"), "{stderr}");
+
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "scale", "worker"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+
+    assert_eq!(
+        read(&project.join(".dx/docker-compose.override.yml")),
+        OVERRIDE
+    );
+    assert!(!tmp.path().join("docker.log").exists());
+}
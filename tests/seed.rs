@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::process::Output;
+
+mod common;
+
+/// Names and statuses of the seeds in the plan, by service.
+fn plan(output: &Output) -> Vec<(String, Vec<String>)> {
+    assert!(output.status.success(), "{output:?}");
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    envelope["data"]["services"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|target| {
+            let seeds = target["seeds"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|s| format!("{} {}", s["name"].as_str().unwrap(), s["status"]))
+                .collect();
+            (target["service"].as_str().unwrap().to_string(), seeds)
+        })
+        .collect()
+}
+
+#[test]
+fn seed_plans_the_files_per_service_in_order() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+
+    // No manifest yet
+    let output = common::run(dir, &["dev-services", "seed", "--dry-run"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+
+    fs::write(
+        dir.join(".env"),
+        "DATABASE_URL=postgres://localhost/app\nMONGODB_URI=mongodb://localhost\n",
+    )
+    .unwrap();
+    let output = common::run(dir, &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let output = common::run(dir, &["dev-services", "seed", "--dry-run"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains(".dx/seed"));
+
+    let seed = dir.join(".dx/seed");
+    fs::create_dir_all(seed.join("postgres")).unwrap();
+    fs::write(
+        seed.join("02-orders.sql"),
+        "INSERT INTO orders VALUES (1);\n",
+    )
+    .unwrap();
+    fs::write(
+        seed.join("postgres/01-users.sql"),
+        "INSERT INTO users VALUES (1);\n",
+    )
+    .unwrap();
+    fs::write(
+        seed.join("users.js"),
+        "db.users.insertOne({ name: 'Ana' });\n",
+    )
+    .unwrap();
+    fs::write(seed.join("README.md"), "Seeds do projeto\n").unwrap();
+
+    let output = common::run(dir, &["--json", "dev-services", "seed", "--dry-run"]);
+    assert_eq!(
+        plan(&output),
+        [
+            (
+                "mongodb".to_string(),
+                vec!["users.js \"pending\"".to_string()]
+            ),
+            (
+                "postgres".to_string(),
+                vec![
+                    "postgres/01-users.sql \"pending\"".to_string(),
+                    "02-orders.sql \"pending\"".to_string(),
+                ]
+            ),
+        ]
+    );
+
+    // --service picks the services and their order
+    let output = common::run(
+        dir,
+        &[
+            "--json",
+            "dev-services",
+            "seed",
+            "--dry-run",
+            "-s",
+            "postgres",
+            "-s",
+            "mongodb",
+        ],
+    );
+    let services: Vec<String> = plan(&output).into_iter().map(|(s, _)| s).collect();
+    assert_eq!(services, ["postgres", "mongodb"]);
+    let output = common::run(dir, &["dev-services", "seed", "--dry-run", "-s", "grafana"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+
+    // A directory or file that doesn't fit the manifest
+    fs::write(seed.join("graph.cypher"), "CREATE (:User);\n").unwrap();
+    let output = common::run(dir, &["dev-services", "seed", "--dry-run"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Neo4j"));
+    fs::remove_file(seed.join("graph.cypher")).unwrap();
+    fs::write(seed.join("postgres/users.js"), "db.users.drop();\n").unwrap();
+    let output = common::run(dir, &["dev-services", "seed", "--dry-run"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    fs::remove_file(seed.join("postgres/users.js")).unwrap();
+    fs::create_dir(seed.join("redis")).unwrap();
+    let output = common::run(dir, &["dev-services", "seed", "--dry-run"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+}
+
+#[test]
+fn seed_generates_records_from_a_schema() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(dir.join(".env"), "DATABASE_URL=postgres://localhost/app\n").unwrap();
+    fs::write(
+        dir.join("schema.sql"),
+        "CREATE TABLE users (id SERIAL PRIMARY KEY, email TEXT NOT NULL);\n",
+    )
+    .unwrap();
+    let output = common::run(dir, &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let args = [
+        "--json",
+        "dev-services",
+        "seed",
+        "--dry-run",
+        "--schema",
+        "schema.sql",
+        "-n",
+        "5",
+    ];
+    let first = common::run(dir, &args);
+    let plan = plan(&first);
+    assert_eq!(
+        plan,
+        [(
+            "postgres".to_string(),
+            vec!["schema:schema.sql \"pending\"".to_string()]
+        )]
+    );
+    // Same options, same data: the marker's checksum doesn't change
+    let checksum = |output: &Output| {
+        let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        envelope["data"]["services"][0]["seeds"][0]["checksum"].clone()
+    };
+    assert_eq!(checksum(&first), checksum(&common::run(dir, &args)));
+
+    // Generation options need --schema
+    let output = common::run(dir, &["dev-services", "seed", "-n", "5"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+}
@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod common;
+
+/// Run dx in `dir` with `user_config` as the user-level dx.toml.
+fn dx(dir: &Path, user_config: &Path, args: &[&str]) -> Output {
+    common::dx(dir)
+        .env("DX_CONFIG", user_config)
+        .env("DX_CI", "0")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run dx")
+}
+
+/// Rust project with a postgres dependency and an isolated (missing) user config.
+fn project(dx_toml: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[dependencies]\npostgres = \"0.19\"\n",
+    )
+    .unwrap();
+    if !dx_toml.is_empty() {
+        fs::write(tmp.path().join("dx.toml"), dx_toml).unwrap();
+    }
+    let user = tmp.path().join("user-dx.toml");
+    (tmp, user)
+}
+
+fn compose(dir: &Path) -> String {
+    fs::read_to_string(dir.join(".dx").join("docker-compose.yml")).expect("compose generated")
+}
+
+#[test]
+fn telemetry_level_and_image_pins() {
+    let (tmp, user) = project(
+        "[telemetry]\nlevel = \"metrics\"\n\n[images]\npostgres = \"postgres:16.4-alpine\"\ngrafana = \"grafana/grafana:11.1.0\"\n",
+    );
+    let output = dx(tmp.path(), &user, &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    let yaml = compose(tmp.path());
+    assert!(yaml.contains("image: postgres:16.4-alpine"), "{yaml}");
+    assert!(yaml.contains("image: grafana/grafana:11.1.0"), "{yaml}");
+    assert!(yaml.contains("prometheus:"), "{yaml}");
+    assert!(!yaml.contains("loki"), "{yaml}");
+    assert!(!yaml.contains("tempo"), "{yaml}");
+    let telemetry = tmp.path().join(".dx").join("telemetry");
+    let collector = fs::read_to_string(telemetry.join("otel-collector-config.yaml")).unwrap();
+    assert!(!collector.contains("loki"), "{collector}");
+    assert!(!telemetry.join("tempo").exists());
+
+    // Generated manifests match what verify-manifests expects from dx.toml
+    let output = dx(tmp.path(), &user, &["governance", "verify-manifests"]);
+    assert!(output.status.success(), "{output:?}");
+}
+
+#[test]
+fn project_file_overrides_user_file() {
+    let (tmp, user) = project("[images]\npostgres = \"postgres:16\"\n");
+    fs::write(
+        &user,
+        "[telemetry]\nlevel = \"off\"\n\n[images]\npostgres = \"postgres:15\"\n",
+    )
+    .unwrap();
+    let output = dx(tmp.path(), &user, &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    let yaml = compose(tmp.path());
+    assert!(yaml.contains("image: postgres:16"), "{yaml}");
+    assert!(!yaml.contains("grafana"), "{yaml}");
+    assert!(!tmp.path().join(".dx").join("telemetry").exists());
+}
+
+#[test]
+fn report_path_and_language_defaults() {
+    let (tmp, user) = project(
+        "[project]\nlanguage = \"Java\"\n\n[analyzer]\nreport_path = \"docs/dx-report.md\"\n",
+    );
+    let output = dx(tmp.path(), &user, &["analyzer"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(tmp.path().join("docs").join("dx-report.md").is_file());
+    assert!(!tmp.path().join(".dx").join("analyzer-report.md").exists());
+
+    // The flag wins over dx.toml
+    let output = dx(
+        tmp.path(),
+        &user,
+        &["analyzer", "--report-path", "other.md"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(tmp.path().join("other.md").is_file());
+
+    let output = dx(tmp.path(), &user, &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(tmp.path().join("docs").join("dx-report.md").is_file());
+    let dashboards = tmp.path().join(".dx/telemetry/grafana/dashboards");
+    assert!(dashboards.join("java-overview.json").is_file());
+    assert!(!dashboards.join("rust-overview.json").exists());
+}
+
+#[test]
+fn clean_exclude_from_dx_toml() {
+    let (tmp, user) = project("[clean]\nexclude = [\"keep\"]\n");
+    fs::create_dir_all(tmp.path().join("keep").join(".dx")).unwrap();
+    fs::create_dir_all(tmp.path().join("api").join(".dx")).unwrap();
+    let output = dx(tmp.path(), &user, &["clean", "--yes"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(tmp.path().join("keep").join(".dx").is_dir());
+    assert!(!tmp.path().join("api").join(".dx").exists());
+}
+
+#[test]
+fn invalid_dx_toml_is_rejected() {
+    let (tmp, user) = project("[telemetry]\nlevel = \"verbose\"\n");
+    let output = dx(tmp.path(), &user, &["analyzer", "--no-save"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("dx.toml"));
+
+    let (tmp, user) = project("[analyser]\nreport_path = \"x.md\"\n");
+    let output = dx(tmp.path(), &user, &["clean", "--dry-run"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn registry_mirror_is_used() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0u8; 2048];
+        let n = stream.read(&mut buf).unwrap();
+        let request = String::from_utf8_lossy(&buf[..n]).to_string();
+        let body = r#"{"version":"9.9.9"}"#;
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        request
+    });
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("package.json"),
+        "{\n  \"devDependencies\": {\n    \"eslint\": \"1.0.0\"\n  }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("dx.toml"),
+        format!("[registries]\nnpm = \"http://{addr}/npm/\"\n"),
+    )
+    .unwrap();
+    let user = tmp.path().join("user-dx.toml");
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_CONFIG", &user)
+        .env("NO_PROXY", "*")
+        .args(["dev-dependencies", "update", "eslint"])
+        .current_dir(tmp.path())
+        .output()
+        .expect("run update");
+    assert!(output.status.success(), "{output:?}");
+    let request = server.join().unwrap();
+    assert!(request.starts_with("GET /npm/eslint/latest "), "{request}");
+    let pkg = fs::read_to_string(tmp.path().join("package.json")).unwrap();
+    assert!(pkg.contains("9.9.9"), "{pkg}");
+}
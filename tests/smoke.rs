@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+
+mod common;
+
+const MANIFEST: &str = "services:
+  postgres:
+    image: postgres:16
+    ports:
+      - '5432:5432'
+  redis:
+    image: redis:7
+    ports:
+      - '6379:6379'
+  kafka:
+    image: apache/kafka:3.7.0
+    ports:
+      - '9092:9092'
+    profiles: [messaging]
+";
+
+/// Fake `docker`: postgres rejects its password, redis stops with an error
+/// and kafka comes up.
+const DOCKER: &str = r#"#!/bin/sh
+echo "$*" >> "$DOCKER_LOG"
+case "$*" in
+  *" ps "*)
+    echo '{"Service":"postgres","State":"running","ExitCode":0}'
+    echo '{"Service":"redis","State":"exited","ExitCode":1}'
+    echo '{"Service":"kafka","State":"running","ExitCode":0}'
+    ;;
+  *" logs "*)
+    echo 'postgres-1  | LOG:  database system is ready to accept connections'
+    echo 'postgres-1  | FATAL:  password authentication failed for user "dev"'
+    echo 'redis-1     | Ready to accept connections tcp'
+    echo 'kafka-1     | [KafkaServer id=1] started'
+    ;;
+esac
+exit 0
+"#;
+
+fn setup(smoke_check: &str) -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(project.join(".dx")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(project.join(".dx/docker-compose.yml"), MANIFEST).unwrap();
+    fs::write(
+        project.join("dx.toml"),
+        format!("[dev_services]\nsmoke_check = \"{smoke_check}\"\n"),
+    )
+    .unwrap();
+    fs::write(bin.join("docker"), DOCKER).unwrap();
+    fs::set_permissions(bin.join("docker"), fs::Permissions::from_mode(0o755)).unwrap();
+    tmp
+}
+
+#[test]
+fn run_reports_services_failing_after_up() {
+    let tmp = setup("1s");
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "run"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Verificando os logs dos serviços por 1s..."),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains(
+            "  postgres  falha de autenticação: FATAL:  password authentication failed for user \"dev\"\n"
+        ),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("  redis     parou com código 1\n"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("  kafka     ok\n"), "{stdout}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("2 serviço(s) com erro depois de subir: postgres, redis."),
+        "{stderr}"
+    );
+
+    // Only the logs written since `up`, of the services it started
+    let log = fs::read_to_string(tmp.path().join("docker.log")).unwrap();
+    let logs = log.lines().find(|l| l.contains(" logs ")).unwrap();
+    assert!(logs.contains(" --no-color --since "), "{log}");
+    assert!(logs.ends_with(" postgres redis kafka"), "{log}");
+}
+
+#[test]
+fn run_checks_only_the_started_services() {
+    let tmp = setup("1s");
+    let output =
+        common::run_with_fake_tools(tmp.path(), &["dev-services", "run", "--service", "kafka"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("  kafka  ok\n"), "{stdout}");
+    assert!(!stdout.contains("postgres"), "{stdout}");
+}
+
+#[test]
+fn run_without_smoke_check_trusts_up() {
+    let tmp = setup("off");
+    let output = common::run_with_fake_tools(tmp.path(), &["dev-services", "run"]);
+    assert!(output.status.success(), "{output:?}");
+    let log = fs::read_to_string(tmp.path().join("docker.log")).unwrap();
+    assert_eq!(log.lines().count(), 1, "{log}");
+    assert!(log.contains(" up -d"), "{log}");
+}
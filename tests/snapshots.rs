@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+mod common;
+
+const MANIFEST: &str = "services:\n  postgres:\n    image: postgres:16-alpine\n    environment:\n      POSTGRES_USER: app\n      POSTGRES_PASSWORD: secret\n      POSTGRES_DB: shop\n    ports:\n      - '5432:5432'\n    volumes:\n      - postgres-data:/var/lib/postgresql/data\n  redis:\n    image: redis:7-alpine\n    ports:\n      - '6379:6379'\n  kafka:\n    image: redpandadata/redpanda:latest\n    volumes:\n      - redpanda-data:/var/lib/redpanda/data\n  kafka-ui:\n    image: provectuslabs/kafka-ui:latest\nvolumes:\n  postgres-data:\n  redpanda-data:\n";
+
+/// Fake `docker` that records its arguments and answers like the real one.
+const DOCKER: &str = r#"#!/bin/sh
+echo "$*" >> "$DOCKER_LOG"
+case "$*" in
+  *" ps -q"*) echo "c0ffee" ;;
+  *pg_dumpall*) echo "-- PostgreSQL database cluster dump" ;;
+  *psql*) cat > "$DOCKER_LOG.restored" ;;
+  *" cp redis:/data/dump.rdb "*) for last; do :; done; echo "REDIS0011" > "$last" ;;
+esac
+exit 0
+"#;
+
+fn setup() -> (tempfile::TempDir, std::path::PathBuf) {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(project.join(".dx")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(project.join(".dx/docker-compose.yml"), MANIFEST).unwrap();
+    fs::write(bin.join("docker"), DOCKER).unwrap();
+    fs::set_permissions(bin.join("docker"), fs::Permissions::from_mode(0o755)).unwrap();
+    (tmp, project)
+}
+
+fn log(tmp: &Path) -> String {
+    fs::read_to_string(tmp.join("docker.log")).unwrap_or_default()
+}
+
+#[test]
+fn snapshot_create_dumps_each_stateful_service() {
+    let (tmp, project) = setup();
+
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &["dev-services", "snapshot", "create", "antes-da-migracao"],
+    );
+    assert!(output.status.success(), "{output:?}");
+
+    let snapshot = project.join(".dx/snapshots/antes-da-migracao");
+    let dump = fs::read_to_string(snapshot.join("postgres.sql")).unwrap();
+    assert!(dump.contains("database cluster dump"), "{dump}");
+    assert_eq!(
+        fs::read_to_string(snapshot.join("redis.rdb")).unwrap(),
+        "REDIS0011\n"
+    );
+    let metadata: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(snapshot.join("snapshot.json")).unwrap()).unwrap();
+    let methods: Vec<(&str, &str)> = metadata["services"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| {
+            (
+                s["service"].as_str().unwrap(),
+                s["method"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        methods,
+        [
+            ("postgres", "pg-dumpall"),
+            ("redis", "redis-rdb"),
+            ("kafka", "volume-tar")
+        ]
+    );
+
+    let log = log(tmp.path());
+    assert!(
+        log.contains("exec -T -e PGPASSWORD=secret postgres pg_dumpall --clean --if-exists -U app"),
+        "{log}"
+    );
+    assert!(log.contains("exec -T redis redis-cli SAVE"), "{log}");
+    assert!(log.contains("run --rm --volumes-from c0ffee"), "{log}");
+    assert!(
+        log.contains("tar czf '/backup/kafka-redpanda-data.tar.gz' -C '/var/lib/redpanda/data' ."),
+        "{log}"
+    );
+    assert!(!log.contains("kafka-ui"), "{log}");
+
+    // Names are unique unless --force is given
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &["dev-services", "snapshot", "create", "antes-da-migracao"],
+    );
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &[
+            "dev-services",
+            "snapshot",
+            "create",
+            "antes-da-migracao",
+            "--force",
+            "--service",
+            "postgres",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(!snapshot.join("redis.rdb").exists());
+}
+
+#[test]
+fn snapshot_restore_loads_the_dumps_back() {
+    let (tmp, _project) = setup();
+    let output =
+        common::run_with_fake_tools(tmp.path(), &["dev-services", "snapshot", "create", "base"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let output =
+        common::run_with_fake_tools(tmp.path(), &["--json", "dev-services", "snapshot", "list"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["snapshots"][0]["name"], "base");
+
+    fs::remove_file(tmp.path().join("docker.log")).unwrap();
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &[
+            "dev-services",
+            "snapshot",
+            "restore",
+            "base",
+            "-s",
+            "postgres",
+            "-s",
+            "redis",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let restored = fs::read_to_string(tmp.path().join("docker.log.restored")).unwrap();
+    assert!(restored.contains("database cluster dump"), "{restored}");
+    let log = log(tmp.path());
+    assert!(log.contains("postgres psql -q -d postgres -U app"), "{log}");
+    assert!(log.contains("stop redis"), "{log}");
+    assert!(log.contains("redis.rdb redis:/data/dump.rdb"), "{log}");
+    assert!(log.contains("start redis"), "{log}");
+    assert!(!log.contains("volumes-from"), "{log}");
+}
+
+#[test]
+fn snapshot_errors_are_reported() {
+    let (tmp, _project) = setup();
+    let output =
+        common::run_with_fake_tools(tmp.path(), &["dev-services", "snapshot", "restore", "nope"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Snapshot 'nope' não encontrado"),
+        "{stderr}"
+    );
+
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &["dev-services", "snapshot", "create", "../fora"],
+    );
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &[
+            "dev-services",
+            "snapshot",
+            "create",
+            "x",
+            "--service",
+            "mongodb",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+}
+
+#[test]
+fn snapshots_follow_the_remote_engine_of_the_last_run() {
+    let (tmp, project) = setup();
+    fs::write(
+        project.join(".dx/remote.json"),
+        r#"{"engine": {"host": "ssh://dev@box"}, "address": "box"}"#,
+    )
+    .unwrap();
+    fs::write(project.join(".dx/docker-compose.remote.yml"), MANIFEST).unwrap();
+
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &[
+            "dev-services",
+            "snapshot",
+            "create",
+            "remoto",
+            "--service",
+            "redis",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let log = log(tmp.path());
+    assert!(
+        log.contains("/.dx/docker-compose.remote.yml exec -T redis redis-cli SAVE"),
+        "{log}"
+    );
+
+    // The volume tar mounts the snapshot folder, which the remote machine doesn't have
+    let output = common::run_with_fake_tools(
+        tmp.path(),
+        &["dev-services", "snapshot", "create", "remoto", "--force"],
+    );
+    assert_eq!(output.status.code(), Some(4), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("'kafka'"), "{stderr}");
+}
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+#![cfg(unix)]
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Output;
+
+mod common;
+
+/// Fake `docker` whose `compose ps` reports every service of the manifest.
+const DOCKER: &str = r#"#!/bin/sh
+echo '{"Service":"redis","State":"running","Health":""}'
+echo '{"Service":"postgres","State":"running","Health":"starting"}'
+echo '{"Service":"kafka","State":"exited","Health":""}'
+"#;
+
+/// A Redis that answers `PING`, on a free port.
+fn fake_redis() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buf = [0u8; 64];
+            if stream.read(&mut buf).is_ok() {
+                let _ = stream.write_all(b"+PONG\r\n");
+            }
+        }
+    });
+    port
+}
+
+/// A port nothing listens on.
+fn closed_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn setup(docker: Option<&str>) -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    let bin = tmp.path().join("bin");
+    fs::create_dir_all(project.join(".dx")).unwrap();
+    fs::create_dir_all(&bin).unwrap();
+    let manifest = format!(
+        "services:\n  redis:\n    image: redis:7-alpine\n    ports:\n      - '{}:6379'\n  postgres:\n    image: postgres:16-alpine\n    ports:\n      - '{}:5432'\n  kafka:\n    image: redpandadata/redpanda:latest\n    ports:\n      - '{}:9092'\n",
+        fake_redis(),
+        closed_port(),
+        closed_port()
+    );
+    fs::write(project.join(".dx/docker-compose.yml"), manifest).unwrap();
+    if let Some(script) = docker {
+        fs::write(bin.join("docker"), script).unwrap();
+        fs::set_permissions(bin.join("docker"), fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    tmp
+}
+
+fn dx(tmp: &Path, args: &[&str]) -> Output {
+    common::dx(tmp)
+        // Only the fake docker (or none at all)
+        .env("PATH", tmp.join("bin"))
+        .args(args)
+        .current_dir(tmp.join("project"))
+        .output()
+        .expect("failed to run dx")
+}
+
+#[test]
+fn status_combines_container_state_and_probes() {
+    let tmp = setup(Some(DOCKER));
+
+    let output = dx(tmp.path(), &["dev-services", "status"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = |service: &str| {
+        stdout
+            .lines()
+            .find(|l| l.starts_with(service))
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    assert!(stdout.starts_with("SERVIÇO"), "{stdout}");
+    assert!(line("redis").ends_with("em execução pronto"), "{stdout}");
+    assert!(
+        line("postgres").ends_with("em execução (starting) aguardando"),
+        "{stdout}"
+    );
+    assert!(line("kafka").ends_with("parado -"), "{stdout}");
+    assert!(stdout.contains("1 de 3 serviço(s) prontos."), "{stdout}");
+
+    let output = dx(tmp.path(), &["--json", "dev-services", "status"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let services = &json["data"]["services"];
+    assert_eq!(services[0]["service"], "redis");
+    assert_eq!(services[0]["probe"], "redis");
+    assert_eq!(services[0]["readiness"], "ready");
+    assert_eq!(services[1]["health"], "starting");
+    assert_eq!(services[2]["state"], "exited");
+}
+
+#[test]
+fn status_falls_back_to_probes_without_docker() {
+    let tmp = setup(None);
+
+    let output = dx(tmp.path(), &["--json", "dev-services", "status"]);
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["docker"], false);
+    assert_eq!(json["data"]["ready"], 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("docker compose ps"), "{stderr}");
+}
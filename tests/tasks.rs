@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+
+mod common;
+
+fn rust_project() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        tmp.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    tmp
+}
+
+#[test]
+fn justfile_has_the_standard_targets() {
+    let tmp = rust_project();
+    let output = common::run_in(tmp.path(), &["tasks", "generate"]);
+    assert!(output.status.success(), "{output:?}");
+    let justfile = fs::read_to_string(tmp.path().join("justfile")).unwrap();
+    for target in [
+        "setup:",
+        "services-up:",
+        "test:",
+        "lint:",
+        "analyze:",
+        "clean:",
+    ] {
+        assert!(
+            justfile.contains(&format!("\n{target}\n")),
+            "{target}:\n{justfile}"
+        );
+    }
+    assert!(
+        justfile.contains("    cargo fetch\n    dx dev-services\n"),
+        "{justfile}"
+    );
+    assert!(justfile.contains("    dx dev-services run\n"), "{justfile}");
+    assert!(
+        justfile.contains("    cargo clippy --all-targets -- -D warnings\n"),
+        "{justfile}"
+    );
+    assert!(justfile.contains("    dx analyzer\n"), "{justfile}");
+    assert!(
+        justfile.contains("    cargo clean\n    dx clean --yes --backup\n"),
+        "{justfile}"
+    );
+    // CI-only tool installs stay out of local tasks
+    assert!(!justfile.contains("cargo install"), "{justfile}");
+}
+
+#[test]
+fn makefile_keeps_user_targets_outside_the_markers() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::write(tmp.path().join("requirements.txt"), "flask\n").unwrap();
+    let makefile = tmp.path().join("Makefile");
+    fs::write(&makefile, "deploy:\n\t./deploy.sh\n").unwrap();
+
+    // A Makefile dx didn't write is only replaced with --force
+    let output = common::run_in(tmp.path(), &["tasks", "generate", "--format", "make"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+
+    let block = String::from_utf8(
+        common::run_in(
+            tmp.path(),
+            &["tasks", "generate", "--format", "make", "--dry-run"],
+        )
+        .stdout,
+    )
+    .unwrap();
+    let start = block.find("# dx-cli:tasks:start").expect("block");
+    let end = block.find("# dx-cli:tasks:end").expect("block") + "# dx-cli:tasks:end\n".len();
+    fs::write(
+        &makefile,
+        format!("{}\ndeploy:\n\t./deploy.sh\n", &block[start..end]),
+    )
+    .unwrap();
+
+    let output = common::run_in(
+        tmp.path(),
+        &["--json", "tasks", "generate", "--format", "make"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("JSON envelope");
+    assert_eq!(json["data"]["stack"], "python");
+    assert_eq!(json["data"]["written"], false);
+    let content = fs::read_to_string(&makefile).unwrap();
+    assert!(
+        content.contains("\ntest:\n\tpython -m pytest\n"),
+        "{content}"
+    );
+    assert!(content.contains("\nlint:\n\truff check .\n"), "{content}");
+    assert!(content.ends_with("deploy:\n\t./deploy.sh\n"), "{content}");
+}
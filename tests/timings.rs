@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::process::{Command, Output};
+
+fn dx_clean(dir: &std::path::Path, global: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dx"))
+        .args(global)
+        .args(["clean", "--dry-run"])
+        .arg(dir)
+        .output()
+        .expect("failed to run dx clean")
+}
+
+fn workspace() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    fs::create_dir_all(tmp.path().join("api").join(".dx")).unwrap();
+    tmp
+}
+
+#[test]
+fn timings_summary_goes_to_stderr() {
+    let tmp = workspace();
+    let output = dx_clean(tmp.path(), &["--timings"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stderr.contains("Tempos:"), "{stderr}");
+    assert!(stderr.contains("Procurando pastas .dx"), "{stderr}");
+    assert!(stderr.contains("Total"), "{stderr}");
+    assert!(!stdout.contains("Tempos:"), "{stdout}");
+
+    // Without the flag (and in quiet mode) there is no summary
+    let output = dx_clean(tmp.path(), &[]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Tempos:"));
+    let output = dx_clean(tmp.path(), &["--timings", "--quiet"]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Tempos:"));
+}
+
+#[test]
+fn timings_are_part_of_json_envelope() {
+    let tmp = workspace();
+    let output = dx_clean(tmp.path(), &["--json", "--timings"]);
+    assert!(output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout is a JSON envelope");
+    let phases = json["timings"]["phases"].as_array().expect("timings.phases");
+    assert!(phases.iter().any(|p| p["phase"] == "Procurando pastas .dx"), "{json}");
+    assert!(json["timings"]["total_ms"].is_u64());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Tempos:"));
+
+    let output = dx_clean(tmp.path(), &["--json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.get("timings").is_none());
+}
@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod common;
+
+const PACKAGE_JSON: &str = "{\n  \"devDependencies\": {\n    \"eslint\": \"1.0.0\"\n  }\n}\n";
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .env("HOME", dir)
+        .output()
+        .expect("failed to run git");
+    assert!(output.status.success(), "git {args:?}: {output:?}");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// Node project committed on `main`, with a bare repository as `origin`.
+fn repository(dx_toml: &str) -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let project = tmp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    git(
+        tmp.path(),
+        &["init", "--quiet", "--bare", "remote/acme/app.git"],
+    );
+    git(&project, &["init", "--quiet", "-b", "main"]);
+    git(&project, &["config", "user.name", "Dev"]);
+    git(&project, &["config", "user.email", "dev@example.com"]);
+    fs::write(project.join("package.json"), PACKAGE_JSON).unwrap();
+    fs::write(project.join("dx.toml"), dx_toml).unwrap();
+    git(&project, &["add", "-A"]);
+    git(&project, &["commit", "--quiet", "-m", "initial"]);
+    let remote = tmp.path().join("remote/acme/app.git");
+    git(
+        &project,
+        &["remote", "add", "origin", remote.to_str().unwrap()],
+    );
+    tmp
+}
+
+fn dx(tmp: &Path, args: &[&str]) -> Output {
+    common::dx(tmp)
+        .env("NO_PROXY", "*")
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .env("HOME", tmp)
+        .env_remove("GITHUB_TOKEN")
+        .env_remove("GITLAB_TOKEN")
+        .args(args)
+        .current_dir(tmp.join("project"))
+        .output()
+        .expect("failed to run dx")
+}
+
+/// Read one HTTP request (headers and body).
+fn read_request(stream: &mut std::net::TcpStream) -> String {
+    let mut reader = BufReader::new(stream);
+    let mut request = String::new();
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse().unwrap();
+        }
+        request.push_str(&line);
+        if line == "\r\n" || line.is_empty() {
+            break;
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+    request + &String::from_utf8_lossy(&body)
+}
+
+/// Registry and GitHub API: answers `responses` in order, returns the requests.
+fn server(
+    responses: Vec<(&'static str, &'static str)>,
+) -> (SocketAddr, std::thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            requests.push(read_request(&mut stream));
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        }
+        requests
+    });
+    (addr, handle)
+}
+
+#[test]
+fn create_pr_pushes_a_branch_and_opens_a_pull_request() {
+    let (addr, server) = server(vec![
+        ("200 OK", r#"{"version":"9.1.0"}"#),
+        (
+            "201 Created",
+            r#"{"html_url":"https://github.com/acme/app/pull/7"}"#,
+        ),
+    ]);
+    let tmp = repository(&format!(
+        "[registries]\nnpm = \"http://{addr}\"\n\n[updates]\nprovider = \"github\"\napi_url = \"http://{addr}/api\"\n"
+    ));
+    let project = tmp.path().join("project");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dx"))
+        .env("DX_GIT_TOKEN", "secret")
+        .env("DX_CONFIG", tmp.path().join("user-dx.toml"))
+        .env("NO_PROXY", "*")
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .args(["dev-dependencies", "update", "--create-pr"])
+        .current_dir(&project)
+        .output()
+        .expect("failed to run dx");
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("PR aberto: https://github.com/acme/app/pull/7"),
+        "{stdout}"
+    );
+
+    let requests = server.join().unwrap();
+    assert!(
+        requests[0].starts_with("GET /eslint/latest "),
+        "{requests:?}"
+    );
+    let pr = &requests[1];
+    assert!(pr.starts_with("POST /api/repos/acme/app/pulls "), "{pr}");
+    assert!(
+        pr.to_lowercase().contains("authorization: bearer secret"),
+        "{pr}"
+    );
+    assert!(pr.contains(r#""head":"dx/update-eslint-9.1.0""#), "{pr}");
+    assert!(pr.contains(r#""base":"main""#), "{pr}");
+
+    // The update lives on the pushed branch only
+    assert_eq!(git(&project, &["branch", "--show-current"]).trim(), "main");
+    assert_eq!(
+        fs::read_to_string(project.join("package.json")).unwrap(),
+        PACKAGE_JSON
+    );
+    let remote = tmp.path().join("remote/acme/app.git");
+    let message = git(
+        &remote,
+        &["log", "-1", "--format=%B", "dx/update-eslint-9.1.0"],
+    );
+    assert!(
+        message.starts_with("chore(deps): update eslint to 9.1.0\n\n- eslint: 1.0.0 -> 9.1.0 (https://www.npmjs.com/package/eslint/v/9.1.0)\n"),
+        "{message}"
+    );
+    let pkg = git(&remote, &["show", "dx/update-eslint-9.1.0:package.json"]);
+    assert!(pkg.contains("\"eslint\": \"9.1.0\""), "{pkg}");
+}
+
+#[test]
+fn create_pr_requires_a_clean_tree_and_a_token() {
+    let tmp =
+        repository("[updates]\nprovider = \"gitlab\"\napi_url = \"http://127.0.0.1:9/api/v4\"\n");
+    let project = tmp.path().join("project");
+
+    let output = dx(tmp.path(), &["dev-dependencies", "update", "--create-pr"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("defina DX_GIT_TOKEN (ou GITLAB_TOKEN)"),
+        "{output:?}"
+    );
+
+    fs::write(project.join("notes.txt"), "wip\n").unwrap();
+    let output = dx(tmp.path(), &["dev-dependencies", "update", "--create-pr"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Há alterações não commitadas"),
+        "{output:?}"
+    );
+    assert_eq!(
+        fs::read_to_string(project.join("package.json")).unwrap(),
+        PACKAGE_JSON
+    );
+}
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+
+mod common;
+
+/// Compose written before the telemetry configs were bind mounted from `./telemetry`.
+const LEGACY_COMPOSE: &str = "services:\n  prometheus:\n    image: prom/prometheus:latest\n    volumes:\n      - .dx/telemetry/prometheus/prometheus.yml:/etc/prometheus/prometheus.yml\n";
+
+#[test]
+fn upgrade_migrates_legacy_dx_folders() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::create_dir_all(dir.join(".dx")).unwrap();
+    fs::write(dir.join(".dx/docker-compose.yml"), LEGACY_COMPOSE).unwrap();
+
+    let output = common::run(dir, &["upgrade", "--dry-run"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Migrações pendentes (v0 -> v1)"), "{stdout}");
+    assert!(stdout.contains("- v1: Caminhos de telemetry"), "{stdout}");
+    assert_eq!(
+        fs::read_to_string(dir.join(".dx/docker-compose.yml")).unwrap(),
+        LEGACY_COMPOSE
+    );
+
+    let output = common::run(dir, &["upgrade"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("(v0 -> v1)"), "{stdout}");
+    let compose = fs::read_to_string(dir.join(".dx/docker-compose.yml")).unwrap();
+    assert!(
+        compose.contains("- ./telemetry/prometheus/prometheus.yml:/etc/prometheus/prometheus.yml"),
+        "{compose}"
+    );
+    let meta: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.join(".dx/meta.json")).unwrap()).unwrap();
+    assert_eq!(meta["schema_version"], 1);
+
+    let output = common::run(dir, &["--json", "upgrade"]);
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["from"], 1);
+    assert_eq!(json["data"]["migrations"], serde_json::json!([]));
+}
+
+#[test]
+fn generated_dx_folders_are_current() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    fs::write(dir.join("requirements.txt"), "redis\n").unwrap();
+
+    let output = common::run(dir, &["dev-services"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(dir.join(".dx/meta.json").is_file());
+
+    let output = common::run(dir, &["upgrade"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("já está no formato atual"), "{stdout}");
+}
+
+#[test]
+fn upgrade_refuses_newer_or_missing_folders() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    let output = common::run(dir, &["upgrade"]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+
+    fs::create_dir_all(dir.join(".dx")).unwrap();
+    fs::write(dir.join(".dx/meta.json"), "{\"schema_version\": 99}\n").unwrap();
+    let output = common::run(dir, &["upgrade"]);
+    assert_eq!(output.status.code(), Some(4), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("formato v99"), "{stderr}");
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+mod common;
+
+fn dx(dir: &Path) -> Command {
+    let mut command = common::dx(dir);
+    command
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .env("CI", "false")
+        .args(["--offline", "watch"])
+        .arg(dir);
+    command
+}
+
+fn project(dir: &Path) {
+    fs::write(
+        dir.join("package.json"),
+        r#"{"dependencies": {"pg": "^8.11"}}"#,
+    )
+    .unwrap();
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_default()
+}
+
+/// Wait (up to 30s) until `done` holds.
+fn eventually(mut done: impl FnMut() -> bool) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(30) {
+        if done() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    false
+}
+
+#[test]
+fn ci_mode_generates_the_artifacts_once() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    project(dir);
+
+    let output = dx(dir).arg("--ci").output().expect("failed to run dx");
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Manifesto .dx/docker-compose.yml atualizado."),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Badges do README atualizadas."), "{stdout}");
+    assert!(stdout.contains("Modo CI"), "{stdout}");
+    assert!(read(&dir.join(".dx/docker-compose.yml")).contains("postgres"));
+    assert!(read(&dir.join("README.md")).contains("PostgreSQL"));
+    assert!(read(&dir.join(".dx/analyzer-report.md")).contains("postgres"));
+}
+
+#[test]
+fn changes_refresh_the_stale_artifacts() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let dir = tmp.path();
+    project(dir);
+
+    let mut child = dx(dir)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("failed to run dx");
+    let compose = dir.join(".dx/docker-compose.yml");
+    let readme = dir.join("README.md");
+    let synced = eventually(|| read(&readme).contains("PostgreSQL"));
+
+    // A new dependency: manifest and badges follow
+    let refreshed = synced && {
+        std::thread::sleep(Duration::from_millis(500));
+        fs::write(
+            dir.join("package.json"),
+            r#"{"dependencies": {"pg": "^8.11", "ioredis": "^5.4"}}"#,
+        )
+        .unwrap();
+        eventually(|| read(&compose).contains("redis") && read(&readme).contains("Redis"))
+    };
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert!(synced, "{}", read(&compose));
+    assert!(refreshed, "{}\n{}", read(&compose), read(&readme));
+    assert!(read(&compose).contains("postgres"));
+}
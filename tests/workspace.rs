@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright (c) 2025 The dx-cli Contributors
+use std::fs;
+
+mod common;
+
+/// Workspace with a Node project at `web/` and a Rust project at `services/api/`.
+fn workspace() -> tempfile::TempDir {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let web = tmp.path().join("web");
+    let api = tmp.path().join("services").join("api");
+    fs::create_dir_all(&web).unwrap();
+    fs::create_dir_all(&api).unwrap();
+    fs::write(
+        web.join("package.json"),
+        "{\n  \"devDependencies\": {\n    \"eslint\": \"1.0.0\"\n  }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        api.join("Cargo.toml"),
+        "[package]\nname = \"api\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    tmp
+}
+
+#[test]
+fn all_runs_the_command_in_each_subproject() {
+    let tmp = workspace();
+    let output = common::run_in(tmp.path(), &["all", "-j", "2", "dev-dependencies", "list"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[web         ] - eslint = 1.0.0"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("[services/api] Nenhuma dependência encontrada."),
+        "{stdout}"
+    );
+    assert!(stdout.contains("2 de 2 projeto(s)"), "{stdout}");
+}
+
+#[test]
+fn all_reports_failures_in_the_summary() {
+    let tmp = workspace();
+    let output = common::run_in(
+        tmp.path(),
+        &["--json", "all", "dev-dependencies", "delete", "eslint"],
+    );
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("JSON envelope");
+    let projects = json["data"]["projects"].as_array().unwrap();
+    let code =
+        |name: &str| projects.iter().find(|p| p["name"] == name).unwrap()["exit_code"].clone();
+    assert_eq!(code("web"), 0);
+    assert_eq!(code("services/api"), 3);
+    let api = projects
+        .iter()
+        .find(|p| p["name"] == "services/api")
+        .unwrap();
+    assert_eq!(api["result"]["error"]["kind"], "not_found", "{json}");
+    assert_eq!(json["data"]["failed"], 1);
+    assert!(
+        !fs::read_to_string(tmp.path().join("web/package.json"))
+            .unwrap()
+            .contains("eslint")
+    );
+}
+
+#[test]
+fn all_requires_subprojects_and_rejects_nesting() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let output = common::run_in(tmp.path(), &["all", "analyzer"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    let tmp = workspace();
+    let output = common::run_in(tmp.path(), &["all", "all", "analyzer"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn recursive_flag_processes_each_subproject() {
+    let tmp = workspace();
+    // Without the flag only the given directory is processed
+    let output = common::run_in(tmp.path(), &["dev-badges", "--no-save"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("== Projeto:"));
+
+    let output = common::run_in(tmp.path(), &["dev-badges", "--no-save", "--all-projects"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("== Projeto:"), "{stdout}");
+    assert!(stdout.contains("web"), "{stdout}");
+
+    let output = common::run_in(tmp.path(), &["dev-services", "--no-save", "-r"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Executando dev-services"));
+}
+
+#[test]
+fn test_projects_folder_is_recursive_with_deprecation_notice() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().join("test-projects");
+    fs::create_dir_all(root.join("web")).unwrap();
+    fs::write(root.join("web").join("package.json"), "{}\n").unwrap();
+    let output = common::run_in(&root, &["dev-badges", "clean"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("== Projeto:"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--recursive"));
+}